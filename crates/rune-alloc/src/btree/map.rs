@@ -951,7 +951,11 @@ where
         into_ok(self.get_with(&mut (), key, infallible_cmp))
     }
 
-    pub(crate) fn get_with<C, Q, E>(
+    /// Like [`BTreeMap::get`] but allows for custom value comparisons.
+    ///
+    /// The comparison implementation should to be coherent with the ones used
+    /// for insertion, else unexpected values might be accessed.
+    pub fn get_with<C, Q, E>(
         &self,
         cx: &mut C,
         key: &Q,
@@ -1194,7 +1198,11 @@ where
         into_ok(self.contains_key_with(&mut (), key, infallible_cmp))
     }
 
-    pub(crate) fn contains_key_with<C, Q, E>(
+    /// Like [`BTreeMap::contains_key`] but allows for custom value comparisons.
+    ///
+    /// The comparison implementation should to be coherent with the ones used
+    /// for insertion, else unexpected values might be accessed.
+    pub fn contains_key_with<C, Q, E>(
         &self,
         cx: &mut C,
         key: &Q,
@@ -1202,8 +1210,8 @@ where
     ) -> Result<bool, E>
     where
         C: ?Sized,
-        Q: ?Sized + Ord,
-        K: Borrow<Q> + Ord,
+        Q: ?Sized,
+        K: Borrow<Q>,
     {
         Ok(self.get_with(cx, key, cmp)?.is_some())
     }
@@ -1415,7 +1423,11 @@ where
         into_ok(self.remove_entry_with(&mut (), key, infallible_cmp))
     }
 
-    pub(crate) fn remove_entry_with<C: ?Sized, Q: ?Sized, E>(
+    /// Like [`BTreeMap::remove_entry`] but allows for custom value comparisons.
+    ///
+    /// The comparison implementation should to be coherent with the ones used
+    /// for insertion, else unexpected values might be removed.
+    pub fn remove_entry_with<C: ?Sized, Q: ?Sized, E>(
         &mut self,
         cx: &mut C,
         key: &Q,
@@ -1578,7 +1590,11 @@ where
         into_ok(self.range_with(&mut (), range, infallible_cmp))
     }
 
-    pub(crate) fn range_with<C, Q, R, E>(
+    /// Like [`BTreeMap::range`] but allows for custom value comparisons.
+    ///
+    /// The comparison implementation should to be coherent with the ones used
+    /// for insertion, else unexpected values might be accessed.
+    pub fn range_with<C, Q, R, E>(
         &self,
         cx: &mut C,
         range: R,
@@ -1693,7 +1709,11 @@ where
         into_ok(self.entry_with(&mut (), key, infallible_cmp))
     }
 
-    pub(crate) fn entry_with<C: ?Sized, E>(
+    /// Like [`BTreeMap::entry`] but allows for custom value comparisons.
+    ///
+    /// The comparison implementation should to be coherent with the ones used
+    /// for insertion, else unexpected values might be accessed.
+    pub fn entry_with<C: ?Sized, E>(
         &mut self,
         cx: &mut C,
         key: K,
@@ -3693,14 +3713,13 @@ where
             Some(current) => current.next_leaf_edge(),
         };
 
-        let handle = edge.insert_recursing(key, value, self.alloc, |ins| {
+        let handle = edge.insert_recursing(key, value, self.alloc, |ins, new_root| {
             drop(ins.left);
             // SAFETY: The handle to the newly inserted value is always on a
             // leaf node, so adding a new root node doesn't invalidate it.
             let root = unsafe { self.root.reborrow().as_mut().unwrap() };
-            root.push_internal_level(self.alloc)?
+            root.push_internal_level_in(new_root)
                 .push(ins.kv.0, ins.kv.1, ins.right);
-            Ok(())
         })?;
         self.current = handle.left_edge().next_back_kv().ok();
         *self.length += 1;
@@ -3743,14 +3762,13 @@ where
             Some(current) => current.next_back_leaf_edge(),
         };
 
-        let handle = edge.insert_recursing(key, value, self.alloc, |ins| {
+        let handle = edge.insert_recursing(key, value, self.alloc, |ins, new_root| {
             drop(ins.left);
             // SAFETY: The handle to the newly inserted value is always on a
             // leaf node, so adding a new root node doesn't invalidate it.
             let root = unsafe { self.root.reborrow().as_mut().unwrap() };
-            root.push_internal_level(self.alloc)?
+            root.push_internal_level_in(new_root)
                 .push(ins.kv.0, ins.kv.1, ins.right);
-            Ok(())
         })?;
         self.current = handle.right_edge().next_kv().ok();
         *self.length += 1;
@@ -394,16 +394,16 @@ where
                 val_ptr
             }
             Some(handle) => {
-                let new_handle = handle.insert_recursing(self.key, value, self.alloc, |ins| {
-                    drop(ins.left);
-                    // SAFETY: Pushing a new root node doesn't invalidate
-                    // handles to existing nodes.
-                    let map = unsafe { self.dormant_map.reborrow() };
-                    let root = map.root.as_mut().unwrap(); // same as ins.left
-                    root.push_internal_level(self.alloc)?
-                        .push(ins.kv.0, ins.kv.1, ins.right);
-                    Ok(())
-                })?;
+                let new_handle =
+                    handle.insert_recursing(self.key, value, self.alloc, |ins, new_root| {
+                        drop(ins.left);
+                        // SAFETY: Pushing a new root node doesn't invalidate
+                        // handles to existing nodes.
+                        let map = unsafe { self.dormant_map.reborrow() };
+                        let root = map.root.as_mut().unwrap(); // same as ins.left
+                        root.push_internal_level_in(new_root)
+                            .push(ins.kv.0, ins.kv.1, ins.right);
+                    })?;
 
                 // Get the pointer to the value
                 let val_ptr = new_handle.into_val_mut();
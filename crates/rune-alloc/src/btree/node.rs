@@ -32,6 +32,7 @@
 //   an edge both identifies a position and contains a pointer to a child node.
 
 use core::alloc::Layout;
+use core::convert::Infallible;
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::slice::SliceIndex;
@@ -39,6 +40,8 @@ use core::slice::SliceIndex;
 use crate::alloc::{AllocError, Allocator};
 use crate::ptr::{self, NonNull};
 
+use super::map::into_ok;
+
 const B: usize = 6;
 pub(crate) const CAPACITY: usize = 2 * B - 1;
 pub(crate) const MIN_LEN_AFTER_SPLIT: usize = B - 1;
@@ -293,11 +296,8 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Leaf> {
 }
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
-    fn new_internal<A>(child: Root<K, V>, alloc: &A) -> Result<Self, AllocError>
-    where
-        A: Allocator,
-    {
-        let mut new_node = unsafe { InternalNode::new(alloc)? };
+    fn new_internal(child: Root<K, V>, new_root: NewRoot<K, V>) -> Self {
+        let mut new_node = new_root.node;
 
         // SAFETY: new_node has been initialized to the point where we can
         // construct a reference to it.
@@ -306,7 +306,7 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
             new_node.edges[0].write(child.node);
         }
 
-        Ok(unsafe { NodeRef::from_new_internal(new_node, child.height + 1) })
+        unsafe { NodeRef::from_new_internal(new_node, child.height + 1) }
     }
 
     /// # Safety
@@ -765,16 +765,26 @@ impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
     where
         A: Allocator,
     {
-        super::mem::take_mut(self, |old_root| {
-            Ok(NodeRef::new_internal(old_root, alloc)?.forget_type())
-        })?;
+        let new_root = NewRoot::new(alloc)?;
+        Ok(self.push_internal_level_in(new_root))
+    }
+
+    /// Like `push_internal_level`, but uses a node which has already been
+    /// allocated, so it can't fail.
+    pub(crate) fn push_internal_level_in(
+        &mut self,
+        new_root: NewRoot<K, V>,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
+        into_ok(super::mem::take_mut(self, |old_root| {
+            Ok::<_, Infallible>(NodeRef::new_internal(old_root, new_root).forget_type())
+        }));
 
         // `self.borrow_mut()`, except that we just forgot we're internal now:
-        Ok(NodeRef {
+        NodeRef {
             height: self.height,
             node: self.node,
             _marker: PhantomData,
-        })
+        }
     }
 
     /// Removes the internal root node, using its first child as the new root node.
@@ -1176,29 +1186,23 @@ where
     ///
     /// Returns a dormant handle to the inserted node which can be reawakened
     /// once splitting is complete.
-    fn insert<A>(
+    fn insert(
         self,
         key: K,
         val: V,
-        alloc: &A,
-    ) -> Result<
-        (
-            Option<SplitResult<'a, K, V, marker::Leaf>>,
-            Handle<NodeRef<marker::DormantMut, K, V, marker::Leaf>, marker::KV>,
-        ),
-        AllocError,
-    >
-    where
-        A: Allocator,
-    {
+        nodes: &mut SplitNodes<K, V>,
+    ) -> (
+        Option<SplitResult<'a, K, V, marker::Leaf>>,
+        Handle<NodeRef<marker::DormantMut, K, V, marker::Leaf>, marker::KV>,
+    ) {
         if self.node.len() < CAPACITY {
             // SAFETY: There is enough space in the node for insertion.
             let handle = unsafe { self.insert_fit(key, val) };
-            Ok((None, handle.dormant()))
+            (None, handle.dormant())
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split(alloc)?;
+            let mut result = middle.split(nodes.take_leaf());
             let insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(result.left.reborrow_mut(), insert_idx)
@@ -1210,7 +1214,7 @@ where
             // SAFETY: We just split the node, so there is enough space for
             // insertion.
             let handle = unsafe { insertion_edge.insert_fit(key, val).dormant() };
-            Ok((Some(result), handle))
+            (Some(result), handle)
         }
     }
 }
@@ -1258,25 +1262,22 @@ where
     /// Inserts a new key-value pair and an edge that will go to the right of that new pair
     /// between this edge and the key-value pair to the right of this edge. This method splits
     /// the node if there isn't enough room.
-    fn insert<A>(
+    fn insert(
         mut self,
         key: K,
         val: V,
         edge: Root<K, V>,
-        alloc: &A,
-    ) -> Result<Option<SplitResult<'a, K, V, marker::Internal>>, AllocError>
-    where
-        A: Allocator,
-    {
+        nodes: &mut SplitNodes<K, V>,
+    ) -> Option<SplitResult<'a, K, V, marker::Internal>> {
         assert!(edge.height == self.node.height - 1);
 
         if self.node.len() < CAPACITY {
             self.insert_fit(key, val, edge);
-            Ok(None)
+            None
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split(alloc)?;
+            let mut result = middle.split(nodes.take_internal());
             let mut insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(result.left.reborrow_mut(), insert_idx)
@@ -1286,7 +1287,7 @@ where
                 },
             };
             insertion_edge.insert_fit(key, val, edge);
-            Ok(Some(result))
+            Some(result)
         }
     }
 }
@@ -1303,17 +1304,22 @@ where
     /// If the returned result is some `SplitResult`, the `left` field will be the root node.
     /// The returned pointer points to the inserted value, which in the case of `SplitResult`
     /// is in the `left` or `right` tree.
+    ///
+    /// All nodes needed for splitting are allocated before the tree is
+    /// modified, so if allocation fails the tree is left untouched.
     pub(crate) fn insert_recursing<A>(
         self,
         key: K,
         value: V,
         alloc: &A,
-        split_root: impl FnOnce(SplitResult<'a, K, V, marker::LeafOrInternal>) -> Result<(), AllocError>,
+        split_root: impl FnOnce(SplitResult<'a, K, V, marker::LeafOrInternal>, NewRoot<K, V>),
     ) -> Result<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV>, AllocError>
     where
         A: Allocator,
     {
-        let (mut split, handle) = match self.insert(key, value, alloc)? {
+        let mut nodes = SplitNodes::for_insert(self.reborrow().into_node(), alloc)?;
+
+        let (mut split, handle) = match self.insert(key, value, &mut nodes) {
             // SAFETY: we have finished splitting and can now re-awaken the
             // handle to the inserted element.
             (None, handle) => return Ok(unsafe { handle.awaken() }),
@@ -1323,7 +1329,7 @@ where
         loop {
             split = match split.left.ascend() {
                 Ok(parent) => {
-                    match parent.insert(split.kv.0, split.kv.1, split.right, alloc)? {
+                    match parent.insert(split.kv.0, split.kv.1, split.right, &mut nodes) {
                         // SAFETY: we have finished splitting and can now re-awaken the
                         // handle to the inserted element.
                         None => return Ok(unsafe { handle.awaken() }),
@@ -1331,10 +1337,13 @@ where
                     }
                 }
                 Err(root) => {
-                    split_root(SplitResult {
-                        left: root,
-                        ..split
-                    })?;
+                    split_root(
+                        SplitResult {
+                            left: root,
+                            ..split
+                        },
+                        nodes.take_root(),
+                    );
                     // SAFETY: we have finished splitting and can now re-awaken the
                     // handle to the inserted element.
                     return Ok(unsafe { handle.awaken() });
@@ -1528,24 +1537,19 @@ where
     /// - The key and value pointed to by this handle are extracted.
     /// - All the key-value pairs to the right of this handle are put into a newly
     ///   allocated node.
-    pub(crate) fn split<A>(
+    fn split(
         mut self,
-        alloc: &A,
-    ) -> Result<SplitResult<'a, K, V, marker::Leaf>, AllocError>
-    where
-        A: Allocator,
-    {
-        let mut new_node = LeafNode::new(alloc)?;
-
+        mut new_node: NonNull<LeafNode<K, V>>,
+    ) -> SplitResult<'a, K, V, marker::Leaf> {
         let kv = self.split_leaf_data(unsafe { new_node.as_mut() });
 
         let right = NodeRef::from_new_leaf(new_node);
 
-        Ok(SplitResult {
+        SplitResult {
             left: self.node,
             kv,
             right,
-        })
+        }
     }
 
     /// Removes the key-value pair pointed to by this handle and returns it, along with the edge
@@ -1578,17 +1582,12 @@ where
     /// - The key and value pointed to by this handle are extracted.
     /// - All the edges and key-value pairs to the right of this handle are put into
     ///   a newly allocated node.
-    pub(crate) fn split<A>(
+    fn split(
         mut self,
-        alloc: &A,
-    ) -> Result<SplitResult<'a, K, V, marker::Internal>, AllocError>
-    where
-        A: Allocator,
-    {
+        mut new_node: NonNull<InternalNode<K, V>>,
+    ) -> SplitResult<'a, K, V, marker::Internal> {
         let old_len = self.node.len();
         unsafe {
-            let mut new_node = InternalNode::new(alloc)?;
-
             // SAFETY: new_node has been initialized to the point where we can
             // construct a reference to it.
             let kv = {
@@ -1605,11 +1604,11 @@ where
             let height = self.node.height;
             let right = NodeRef::from_new_internal(new_node, height);
 
-            Ok(SplitResult {
+            SplitResult {
                 left: self.node,
                 kv,
                 right,
-            })
+            }
         }
     }
 }
@@ -2127,6 +2126,158 @@ impl<'a, K, V> SplitResult<'a, K, V, marker::Internal> {
     }
 }
 
+/// An internal node which has been allocated ahead of time to become the new
+/// root of a tree.
+pub(crate) struct NewRoot<K, V> {
+    node: NonNull<InternalNode<K, V>>,
+}
+
+impl<K, V> NewRoot<K, V> {
+    fn new<A>(alloc: &A) -> Result<Self, AllocError>
+    where
+        A: Allocator,
+    {
+        Ok(Self {
+            node: unsafe { InternalNode::new(alloc)? },
+        })
+    }
+}
+
+/// Nodes allocated ahead of an insertion, so that splitting nodes all the way
+/// up to the root can't fail half-way through and leave the tree in an
+/// inconsistent state.
+///
+/// Spare internal nodes are chained together through their first edge, which
+/// is uninitialized until the node is put to use.
+struct SplitNodes<K, V> {
+    leaf: Option<NonNull<LeafNode<K, V>>>,
+    internal: Option<NonNull<InternalNode<K, V>>>,
+    internal_len: usize,
+}
+
+impl<K, V> SplitNodes<K, V> {
+    /// Allocate every node needed to insert a key-value pair into the given
+    /// leaf.
+    fn for_insert<'a, A>(
+        leaf: NodeRef<marker::Immut<'a>, K, V, marker::Leaf>,
+        alloc: &A,
+    ) -> Result<Self, AllocError>
+    where
+        K: 'a,
+        V: 'a,
+        A: Allocator,
+    {
+        let mut this = Self {
+            leaf: None,
+            internal: None,
+            internal_len: 0,
+        };
+
+        if leaf.len() < CAPACITY {
+            return Ok(this);
+        }
+
+        // Every full ancestor is split as well, and if the root is reached a
+        // new root is pushed on top of it.
+        let mut internal = 0;
+        let mut node = leaf.forget_type();
+
+        loop {
+            match node.ascend() {
+                Ok(parent) => {
+                    let parent = parent.into_node();
+
+                    if parent.len() < CAPACITY {
+                        break;
+                    }
+
+                    internal += 1;
+                    node = parent.forget_type();
+                }
+                Err(..) => {
+                    internal += 1;
+                    break;
+                }
+            }
+        }
+
+        if let Err(error) = this.allocate(internal, alloc) {
+            this.free(alloc);
+            return Err(error);
+        }
+
+        Ok(this)
+    }
+
+    fn allocate<A>(&mut self, internal: usize, alloc: &A) -> Result<(), AllocError>
+    where
+        A: Allocator,
+    {
+        self.leaf = Some(LeafNode::new(alloc)?);
+
+        for _ in 0..internal {
+            let mut node = unsafe { InternalNode::new(alloc)? };
+
+            if let Some(next) = self.internal {
+                unsafe {
+                    node.as_mut().edges[0].write(next.cast());
+                }
+            }
+
+            self.internal = Some(node);
+            self.internal_len += 1;
+        }
+
+        Ok(())
+    }
+
+    fn free<A>(self, alloc: &A)
+    where
+        A: Allocator,
+    {
+        let mut this = self;
+
+        unsafe {
+            if let Some(leaf) = this.leaf.take() {
+                alloc.deallocate(leaf.cast(), Layout::new::<LeafNode<K, V>>());
+            }
+
+            while this.internal_len > 0 {
+                let node = this.take_internal();
+                alloc.deallocate(node.cast(), Layout::new::<InternalNode<K, V>>());
+            }
+        }
+    }
+
+    fn take_leaf(&mut self) -> NonNull<LeafNode<K, V>> {
+        self.leaf
+            .take()
+            .expect("leaf node should have been allocated")
+    }
+
+    fn take_internal(&mut self) -> NonNull<InternalNode<K, V>> {
+        let node = self
+            .internal
+            .take()
+            .expect("internal node should have been allocated");
+
+        self.internal_len -= 1;
+
+        if self.internal_len > 0 {
+            // SAFETY: Spare nodes are chained through their first edge.
+            self.internal = Some(unsafe { node.as_ref().edges[0].assume_init_read().cast() });
+        }
+
+        node
+    }
+
+    fn take_root(&mut self) -> NewRoot<K, V> {
+        NewRoot {
+            node: self.take_internal(),
+        }
+    }
+}
+
 pub(crate) mod marker {
     use core::marker::PhantomData;
 
@@ -78,9 +78,8 @@ fn test_splitpoint() {
 fn test_partial_eq() {
     let mut root1 = NodeRef::new_leaf(&Global).abort();
     root1.borrow_mut().push(1, ());
-    let mut root1 = NodeRef::new_internal(root1.forget_type(), &Global)
-        .abort()
-        .forget_type();
+    let new_root = NewRoot::new(&Global).abort();
+    let mut root1 = NodeRef::new_internal(root1.forget_type(), new_root).forget_type();
     let root2 = Root::new(&Global).abort();
     root1.reborrow().assert_back_pointers();
     root2.reborrow().assert_back_pointers();
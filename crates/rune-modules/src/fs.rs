@@ -27,17 +27,679 @@
 //! }
 //! ```
 
-use rune::{ContextError, Module};
 use std::io;
+use std::path;
+use std::time::UNIX_EPOCH;
+
+use rune::alloc;
+use rune::alloc::fmt::TryWrite;
+use rune::runtime::sandbox::{self, Restricted};
+use rune::runtime::{Bytes, Formatter, Mut, Ref, Value, VmError};
+use rune::{item, nested_try, Any, ContextError, Module, TypeHash};
 use tokio::fs;
 
-/// Construct the `fs` module.
+use crate::time::Duration;
+
+/// A module for working with the filesystem.
+///
+/// Functions which take a path accept either a string or a [`PathBuf`].
+///
+/// If a sandbox is installed in the context, reading and writing paths which
+/// it doesn't allow fails with a `SandboxError` instead of an `io::Error`.
+/// Without a sandbox, errors are the same as before.
+///
+/// # Tokio
+///
+/// This module is implemented using [Tokio], and requires the Tokio runtime to
+/// be in scope.
+///
+/// [Tokio]: https://tokio.rs
+#[rune::module(::fs)]
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
-    let mut module = Module::with_crate("fs")?;
-    module.function("read_to_string", read_to_string).build()?;
-    Ok(module)
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.function_meta(read_to_string)?;
+    m.function_meta(read)?;
+    m.function_meta(write)?;
+    m.function_meta(append)?;
+    m.function_meta(create_dir)?;
+    m.function_meta(create_dir_all)?;
+    m.function_meta(remove_file)?;
+    m.function_meta(remove_dir)?;
+    m.function_meta(remove_dir_all)?;
+    m.function_meta(rename)?;
+    m.function_meta(copy)?;
+    m.function_meta(exists)?;
+    m.function_meta(metadata)?;
+    m.function_meta(read_dir)?;
+
+    m.ty::<PathBuf>()?;
+    m.function_meta(PathBuf::new__meta)?;
+    m.function_meta(PathBuf::join__meta)?;
+    m.function_meta(PathBuf::parent__meta)?;
+    m.function_meta(PathBuf::file_name__meta)?;
+    m.function_meta(PathBuf::file_stem__meta)?;
+    m.function_meta(PathBuf::extension__meta)?;
+    m.function_meta(PathBuf::with_extension__meta)?;
+    m.function_meta(PathBuf::is_absolute__meta)?;
+    m.function_meta(PathBuf::is_relative__meta)?;
+    m.function_meta(PathBuf::partial_eq__meta)?;
+    m.implement_trait::<PathBuf>(item!(::std::cmp::PartialEq))?;
+    m.function_meta(PathBuf::eq__meta)?;
+    m.implement_trait::<PathBuf>(item!(::std::cmp::Eq))?;
+    m.function_meta(PathBuf::clone__meta)?;
+    m.implement_trait::<PathBuf>(item!(::std::clone::Clone))?;
+    m.function_meta(PathBuf::display_fmt__meta)?;
+    m.function_meta(PathBuf::debug_fmt__meta)?;
+
+    m.ty::<ReadDir>()?;
+    m.function_meta(ReadDir::next__meta)?;
+    m.function_meta(ReadDir::debug_fmt__meta)?;
+
+    m.ty::<DirEntry>()?;
+    m.function_meta(DirEntry::path__meta)?;
+    m.function_meta(DirEntry::file_name__meta)?;
+    m.function_meta(DirEntry::metadata__meta)?;
+    m.function_meta(DirEntry::debug_fmt__meta)?;
+
+    m.ty::<Metadata>()?;
+    m.function_meta(Metadata::len__meta)?;
+    m.function_meta(Metadata::is_dir__meta)?;
+    m.function_meta(Metadata::is_file__meta)?;
+    m.function_meta(Metadata::is_symlink__meta)?;
+    m.function_meta(Metadata::modified__meta)?;
+    m.function_meta(Metadata::debug_fmt__meta)?;
+
+    Ok(m)
+}
+
+/// Reads the entire contents of a file into a string.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let contents = fs::read_to_string("foo.txt").await?;
+/// println!("{contents}");
+/// ```
+#[rune::function]
+async fn read_to_string(path: Value) -> Result<Result<String, Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::read(&path));
+    Ok(fs::read_to_string(path).await.map_err(Restricted::Error))
+}
+
+/// Reads the entire contents of a file into bytes.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let contents = fs::read("foo.bin").await?;
+/// println!("{}", contents.len());
+/// ```
+#[rune::function]
+async fn read(path: Value) -> Result<Result<Bytes, Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::read(&path));
+    let bytes = nested_try!(fs::read(path).await);
+    Ok(Ok(Bytes::try_from(bytes)?))
+}
+
+/// Writes a string or bytes as the entire contents of a file.
+///
+/// This creates the file if it does not exist, and replaces its contents if it
+/// does.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::write("foo.txt", "Hello World").await?;
+/// fs::write("foo.bin", b"\x00\x01").await?;
+/// ```
+#[rune::function]
+async fn write(path: Value, contents: Value) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::write(&path));
+    let contents = to_contents(&contents)?;
+    Ok(fs::write(path, contents).await.map_err(Restricted::Error))
+}
+
+/// Appends a string or bytes to the end of a file.
+///
+/// This creates the file if it does not exist.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::append("log.txt", "a line\n").await?;
+/// ```
+#[rune::function]
+async fn append(
+    path: Value,
+    contents: Value,
+) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    use tokio::io::AsyncWriteExt;
+
+    let path = to_path(&path)?;
+    nested_try!(sandbox::write(&path));
+    let contents = to_contents(&contents)?;
+
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await;
+
+    let mut file = nested_try!(file);
+    nested_try!(file.write_all(&contents).await);
+    Ok(file.flush().await.map_err(Restricted::Error))
+}
+
+/// Creates a new, empty directory at the provided path.
+///
+/// This fails if the parent of the directory doesn't exist or if the directory
+/// already exists. See [`create_dir_all`] to create a directory and all of its
+/// missing parents.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::create_dir("target").await?;
+/// ```
+#[rune::function]
+async fn create_dir(path: Value) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::write(&path));
+    Ok(fs::create_dir(path).await.map_err(Restricted::Error))
+}
+
+/// Recursively creates a directory and all of its parent components if they
+/// are missing.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::create_dir_all("target/debug/deps").await?;
+/// ```
+#[rune::function]
+async fn create_dir_all(path: Value) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::write(&path));
+    Ok(fs::create_dir_all(path).await.map_err(Restricted::Error))
+}
+
+/// Removes a file from the filesystem.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::remove_file("foo.txt").await?;
+/// ```
+#[rune::function]
+async fn remove_file(path: Value) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::write(&path));
+    Ok(fs::remove_file(path).await.map_err(Restricted::Error))
+}
+
+/// Removes an empty directory.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::remove_dir("target").await?;
+/// ```
+#[rune::function]
+async fn remove_dir(path: Value) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::write(&path));
+    Ok(fs::remove_dir(path).await.map_err(Restricted::Error))
+}
+
+/// Removes a directory at this path, after removing all its contents.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::remove_dir_all("target").await?;
+/// ```
+#[rune::function]
+async fn remove_dir_all(path: Value) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::write(&path));
+    Ok(fs::remove_dir_all(path).await.map_err(Restricted::Error))
+}
+
+/// Renames a file or directory to a new name, replacing the original file if
+/// `to` already exists.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// fs::rename("a.txt", "b.txt").await?;
+/// ```
+#[rune::function]
+async fn rename(from: Value, to: Value) -> Result<Result<(), Restricted<io::Error>>, VmError> {
+    let from = to_path(&from)?;
+    let to = to_path(&to)?;
+    nested_try!(sandbox::write(&from));
+    nested_try!(sandbox::write(&to));
+    Ok(fs::rename(from, to).await.map_err(Restricted::Error))
+}
+
+/// Copies the contents of one file to another, returning the number of bytes
+/// copied.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let copied = fs::copy("a.txt", "b.txt").await?;
+/// ```
+#[rune::function]
+async fn copy(from: Value, to: Value) -> Result<Result<u64, Restricted<io::Error>>, VmError> {
+    let from = to_path(&from)?;
+    let to = to_path(&to)?;
+    nested_try!(sandbox::read(&from));
+    nested_try!(sandbox::write(&to));
+    Ok(fs::copy(from, to).await.map_err(Restricted::Error))
+}
+
+/// Tests if a path exists.
+///
+/// Unlike checking for an error from [`metadata`], this returns `false` if
+/// the path is a broken symbolic link.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// if !fs::exists("target").await? {
+///     fs::create_dir("target").await?;
+/// }
+/// ```
+#[rune::function]
+async fn exists(path: Value) -> Result<Result<bool, Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::read(&path));
+    Ok(fs::try_exists(path).await.map_err(Restricted::Error))
+}
+
+/// Queries the filesystem for metadata about a path, following symbolic links.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let metadata = fs::metadata("foo.txt").await?;
+/// println!("{} bytes", metadata.len());
+/// ```
+#[rune::function]
+async fn metadata(path: Value) -> Result<Result<Metadata, Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::read(&path));
+    let inner = nested_try!(fs::metadata(path).await);
+    Ok(Ok(Metadata { inner }))
+}
+
+/// Returns a stream over the entries within a directory.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let entries = fs::read_dir(".").await?;
+///
+/// while let Some(entry) = entries.next().await? {
+///     println!("{}", entry.path());
+/// }
+/// ```
+#[rune::function]
+async fn read_dir(path: Value) -> Result<Result<ReadDir, Restricted<io::Error>>, VmError> {
+    let path = to_path(&path)?;
+    nested_try!(sandbox::read(&path));
+    let inner = nested_try!(fs::read_dir(path).await);
+    Ok(Ok(ReadDir { inner }))
+}
+
+/// An owned, mutable path.
+///
+/// # Examples
+///
+/// ```rune
+/// use fs::PathBuf;
+///
+/// let path = PathBuf::new("src").join("main.rn");
+/// assert_eq!(path.extension(), Some("rn"));
+/// assert_eq!(path.parent(), Some(PathBuf::new("src")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Any)]
+#[rune(item = ::fs)]
+struct PathBuf {
+    inner: path::PathBuf,
 }
 
-async fn read_to_string(path: String) -> io::Result<String> {
-    fs::read_to_string(&path).await
+impl PathBuf {
+    /// Construct a path from a string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// let path = PathBuf::new("foo.txt");
+    /// assert_eq!(path.file_name(), Some("foo.txt"));
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    fn new(path: &str) -> Self {
+        Self {
+            inner: path::PathBuf::from(path),
+        }
+    }
+
+    /// Creates a new path by adjoining a string or path to this one.
+    ///
+    /// If the adjoined path is absolute, it replaces the current path.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// let path = PathBuf::new("/etc").join("passwd");
+    /// assert_eq!(path, PathBuf::new("/etc/passwd"));
+    /// ```
+    #[rune::function(keep, instance)]
+    fn join(&self, path: Value) -> Result<Self, VmError> {
+        Ok(Self {
+            inner: self.inner.join(to_path(&path)?),
+        })
+    }
+
+    /// Returns the path without its final component, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// assert_eq!(PathBuf::new("/foo/bar").parent(), Some(PathBuf::new("/foo")));
+    /// assert_eq!(PathBuf::new("/").parent(), None);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn parent(&self) -> Option<Self> {
+        Some(Self {
+            inner: self.inner.parent()?.to_path_buf(),
+        })
+    }
+
+    /// Returns the final component of the path, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// assert_eq!(PathBuf::new("tmp/foo.txt").file_name(), Some("foo.txt"));
+    /// assert_eq!(PathBuf::new("..").file_name(), None);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn file_name(&self) -> Option<String> {
+        Some(self.inner.file_name()?.to_string_lossy().into_owned())
+    }
+
+    /// Returns the final component of the path without its extension.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// assert_eq!(PathBuf::new("foo.tar.gz").file_stem(), Some("foo.tar"));
+    /// ```
+    #[rune::function(keep, instance)]
+    fn file_stem(&self) -> Option<String> {
+        Some(self.inner.file_stem()?.to_string_lossy().into_owned())
+    }
+
+    /// Returns the extension of the final component of the path, if there is
+    /// one.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// assert_eq!(PathBuf::new("foo.rs").extension(), Some("rs"));
+    /// assert_eq!(PathBuf::new("foo").extension(), None);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn extension(&self) -> Option<String> {
+        Some(self.inner.extension()?.to_string_lossy().into_owned())
+    }
+
+    /// Creates a new path with the given extension.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// let path = PathBuf::new("foo.rs").with_extension("txt");
+    /// assert_eq!(path, PathBuf::new("foo.txt"));
+    /// ```
+    #[rune::function(keep, instance)]
+    fn with_extension(&self, extension: &str) -> Self {
+        Self {
+            inner: self.inner.with_extension(extension),
+        }
+    }
+
+    /// Tests if the path is absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// assert!(PathBuf::new("/etc").is_absolute());
+    /// assert!(!PathBuf::new("etc").is_absolute());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn is_absolute(&self) -> bool {
+        self.inner.is_absolute()
+    }
+
+    /// Tests if the path is relative.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use fs::PathBuf;
+    ///
+    /// assert!(PathBuf::new("etc").is_relative());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn is_relative(&self) -> bool {
+        self.inner.is_relative()
+    }
+
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        self.inner == rhs.inner
+    }
+
+    #[rune::function(keep, instance, protocol = EQ)]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.inner == rhs.inner
+    }
+
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Clone::clone(self)
+    }
+
+    #[rune::function(keep, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{}", self.inner.display())
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// A stream over the entries in a directory, as returned by [`read_dir`].
+#[derive(Debug, Any)]
+#[rune(item = ::fs)]
+struct ReadDir {
+    inner: fs::ReadDir,
+}
+
+impl ReadDir {
+    /// Returns the next entry in the directory, or `None` if there are no more
+    /// entries.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let entries = fs::read_dir(".").await?;
+    ///
+    /// while let Some(entry) = entries.next().await? {
+    ///     println!("{}", entry.file_name());
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::next)]
+    async fn next(mut this: Mut<Self>) -> io::Result<Option<DirEntry>> {
+        let entry = this.inner.next_entry().await?;
+        Ok(entry.map(|inner| DirEntry { inner }))
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// An entry in a directory.
+#[derive(Debug, Any)]
+#[rune(item = ::fs)]
+struct DirEntry {
+    inner: fs::DirEntry,
+}
+
+impl DirEntry {
+    /// Returns the full path to the entry.
+    #[rune::function(keep, instance)]
+    fn path(&self) -> PathBuf {
+        PathBuf {
+            inner: self.inner.path(),
+        }
+    }
+
+    /// Returns the name of the entry, without any leading path.
+    #[rune::function(keep, instance)]
+    fn file_name(&self) -> String {
+        self.inner.file_name().to_string_lossy().into_owned()
+    }
+
+    /// Queries the metadata of the entry, without following symbolic links.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let entries = fs::read_dir(".").await?;
+    ///
+    /// while let Some(entry) = entries.next().await? {
+    ///     if entry.metadata().await?.is_dir() {
+    ///         println!("{}/", entry.file_name());
+    ///     }
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::metadata)]
+    async fn metadata(this: Ref<Self>) -> io::Result<Metadata> {
+        let inner = this.inner.metadata().await?;
+        Ok(Metadata { inner })
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// Metadata about a file or directory.
+#[derive(Debug, Any)]
+#[rune(item = ::fs)]
+struct Metadata {
+    inner: std::fs::Metadata,
+}
+
+impl Metadata {
+    /// Returns the size of the file in bytes.
+    #[rune::function(keep, instance)]
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    /// Tests if the metadata is for a directory.
+    #[rune::function(keep, instance)]
+    fn is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    /// Tests if the metadata is for a regular file.
+    #[rune::function(keep, instance)]
+    fn is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    /// Tests if the metadata is for a symbolic link.
+    #[rune::function(keep, instance)]
+    fn is_symlink(&self) -> bool {
+        self.inner.is_symlink()
+    }
+
+    /// Returns the last modification time, as the duration since the Unix
+    /// epoch.
+    ///
+    /// This errors on platforms where the modification time isn't available.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let before = fs::metadata("a.txt").await?.modified()?;
+    /// let after = fs::metadata("b.txt").await?.modified()?;
+    ///
+    /// if before < after {
+    ///     println!("a.txt is older");
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn modified(&self) -> io::Result<Duration> {
+        let modified = self.inner.modified()?;
+        let duration = modified
+            .duration_since(UNIX_EPOCH)
+            .map_err(io::Error::other)?;
+        Ok(Duration::from_std(duration))
+    }
+
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+/// Convert a string or a [`PathBuf`] into a path.
+fn to_path(value: &Value) -> Result<path::PathBuf, VmError> {
+    if value.type_hash() == PathBuf::HASH {
+        return Ok(value.borrow_ref::<PathBuf>()?.inner.clone());
+    }
+
+    Ok(path::PathBuf::from(&*value.borrow_string_ref()?))
+}
+
+/// Convert a string or bytes into the contents of a file.
+fn to_contents(value: &Value) -> Result<Vec<u8>, VmError> {
+    if value.type_hash() == Bytes::HASH {
+        return Ok(value.borrow_ref::<Bytes>()?.as_slice().to_vec());
+    }
+
+    Ok(value.borrow_string_ref()?.as_bytes().to_vec())
 }
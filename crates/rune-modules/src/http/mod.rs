@@ -47,16 +47,45 @@
 //!     dbg(response);
 //! }
 //! ```
+//!
+//! It also provides a small HTTP/1.1 server, which dispatches requests to Rune
+//! functions:
+//!
+//! ```rust,ignore
+//! use http::{ResponseBuilder, Server, StatusCode};
+//!
+//! async fn webhook(request) {
+//!     let event = request.json()?;
+//!     dbg(event);
+//!     Ok(ResponseBuilder::new().status(StatusCode::ACCEPTED))
+//! }
+//!
+//! async fn main() {
+//!     let server = Server::bind("127.0.0.1:8080").await?;
+//!     server.post("/webhook", webhook)?;
+//!     server.serve().await?;
+//! }
+//! ```
+
+mod multipart;
+use self::multipart::{Form, Part};
+
+mod server;
+use self::server::{Request, ResponseBuilder, Server};
 
 use core::cmp::Ordering;
+use core::fmt;
 use core::hash::Hash;
 
 use rune::alloc;
 use rune::alloc::fmt::TryWrite;
 use rune::alloc::prelude::*;
-use rune::runtime::{Bytes, Formatter, Hasher, Ref};
+use rune::runtime::sandbox::{self, Restricted, SandboxError};
+use rune::runtime::{Bytes, Formatter, Hasher, Mut, Ref};
 use rune::{docstring, item, nested_try, Any, ContextError, Module, ToConstValue, Value};
 
+use crate::time::Duration;
+
 /// A simple HTTP module for Rune.
 ///
 /// # Examples
@@ -79,6 +108,16 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     m.function_meta(Client::put__meta)?;
     m.function_meta(Client::delete__meta)?;
     m.function_meta(Client::head__meta)?;
+    m.function_meta(Client::builder__meta)?;
+
+    m.ty::<ClientBuilder>()?;
+    m.function_meta(ClientBuilder::default_header__meta)?;
+    m.function_meta(ClientBuilder::user_agent__meta)?;
+    m.function_meta(ClientBuilder::proxy__meta)?;
+    m.function_meta(ClientBuilder::max_redirects__meta)?;
+    m.function_meta(ClientBuilder::timeout__meta)?;
+    m.function_meta(ClientBuilder::connect_timeout__meta)?;
+    m.function_meta(ClientBuilder::build__meta)?;
 
     m.ty::<Response>()?;
     m.function_meta(Response::text__meta)?;
@@ -87,6 +126,13 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     m.function_meta(Response::status__meta)?;
     m.function_meta(Response::version__meta)?;
     m.function_meta(Response::content_length__meta)?;
+    m.function_meta(Response::url__meta)?;
+    m.function_meta(Response::header__meta)?;
+    m.function_meta(Response::headers__meta)?;
+    m.function_meta(Response::bytes_stream__meta)?;
+
+    m.ty::<BytesStream>()?;
+    m.function_meta(BytesStream::next__meta)?;
 
     m.ty::<RequestBuilder>()?;
     m.function_meta(RequestBuilder::send__meta)?;
@@ -95,6 +141,22 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     m.function_meta(RequestBuilder::bearer_auth__meta)?;
     m.function_meta(RequestBuilder::fetch_mode_no_cors__meta)?;
     m.function_meta(RequestBuilder::body_bytes__meta)?;
+    m.function_meta(RequestBuilder::query__meta)?;
+    m.function_meta(RequestBuilder::json__meta)?;
+    m.function_meta(RequestBuilder::form__meta)?;
+    m.function_meta(RequestBuilder::multipart__meta)?;
+    m.function_meta(RequestBuilder::timeout__meta)?;
+
+    m.ty::<Form>()?;
+    m.function_meta(Form::new__meta)?;
+    m.function_meta(Form::text__meta)?;
+    m.function_meta(Form::part__meta)?;
+
+    m.ty::<Part>()?;
+    m.function_meta(Part::bytes__meta)?;
+    m.function_meta(Part::text__meta)?;
+    m.function_meta(Part::file_name__meta)?;
+    m.function_meta(Part::mime_str__meta)?;
 
     m.ty::<StatusCode>()?;
     m.function_meta(StatusCode::as_u16__meta)?;
@@ -1364,6 +1426,37 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
         /// ```
     })?;
 
+    m.ty::<Server>()?;
+    m.function_meta(Server::bind__meta)?;
+    m.function_meta(Server::local_addr__meta)?;
+    m.function_meta(Server::max_body_size__meta)?;
+    m.function_meta(Server::route__meta)?;
+    m.function_meta(Server::get__meta)?;
+    m.function_meta(Server::post__meta)?;
+    m.function_meta(Server::put__meta)?;
+    m.function_meta(Server::delete__meta)?;
+    m.function_meta(Server::serve__meta)?;
+
+    m.ty::<Request>()?;
+    m.function_meta(Request::method__meta)?;
+    m.function_meta(Request::path__meta)?;
+    m.function_meta(Request::query__meta)?;
+    m.function_meta(Request::param__meta)?;
+    m.function_meta(Request::header__meta)?;
+    m.function_meta(Request::headers__meta)?;
+    m.function_meta(Request::remote_addr__meta)?;
+    m.function_meta(Request::body__meta)?;
+    m.function_meta(Request::text__meta)?;
+    m.function_meta(Request::json__meta)?;
+
+    m.ty::<ResponseBuilder>()?;
+    m.function_meta(ResponseBuilder::new__meta)?;
+    m.function_meta(ResponseBuilder::status__meta)?;
+    m.function_meta(ResponseBuilder::header__meta)?;
+    m.function_meta(ResponseBuilder::body_bytes__meta)?;
+    m.function_meta(ResponseBuilder::text__meta)?;
+    m.function_meta(ResponseBuilder::json__meta)?;
+
     m.ty::<Error>()?;
     m.function_meta(Error::display_fmt__meta)?;
     Ok(m)
@@ -1373,21 +1466,137 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
 #[derive(Debug, Any)]
 #[rune(item = ::http)]
 pub struct Error {
-    inner: reqwest::Error,
+    kind: ErrorKind,
+}
+
+impl Error {
+    #[inline]
+    fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Reqwest(reqwest::Error),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Utf8(core::str::Utf8Error),
+    InvalidMethod,
+    InvalidRoute,
+    InvalidHeader,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Reqwest(error) => error.fmt(f),
+            ErrorKind::Io(error) => error.fmt(f),
+            ErrorKind::Json(error) => error.fmt(f),
+            ErrorKind::Utf8(error) => error.fmt(f),
+            ErrorKind::InvalidMethod => write!(f, "Invalid HTTP method"),
+            ErrorKind::InvalidRoute => {
+                write!(f, "Invalid route, expected a path like `/items/:id`")
+            }
+            ErrorKind::InvalidHeader => write!(f, "Invalid header name or value"),
+        }
+    }
 }
 
 impl From<reqwest::Error> for Error {
     #[inline]
-    fn from(inner: reqwest::Error) -> Self {
-        Self { inner }
+    fn from(error: reqwest::Error) -> Self {
+        Self::new(ErrorKind::Reqwest(error))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    #[inline]
+    fn from(error: std::io::Error) -> Self {
+        Self::new(ErrorKind::Io(error))
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    #[inline]
+    fn from(error: serde_json::Error) -> Self {
+        Self::new(ErrorKind::Json(error))
+    }
+}
+
+impl From<core::str::Utf8Error> for Error {
+    #[inline]
+    fn from(error: core::str::Utf8Error) -> Self {
+        Self::new(ErrorKind::Utf8(error))
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+/// Convert an error from sending a request, surfacing redirects which were
+/// denied by the sandbox as a [`SandboxError`].
+fn restricted(error: reqwest::Error) -> Restricted<Error> {
+    let mut source = std::error::Error::source(&error);
+
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<SandboxError>() {
+            return Restricted::Denied(error.clone());
+        }
+
+        source = error.source();
+    }
+
+    Restricted::Error(Error::from(error))
+}
+
+/// The number of redirects which are followed by default.
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// Construct a redirect policy which follows at most `max` redirects, and only
+/// to hosts permitted by the sandbox.
+fn redirect_policy(max: usize) -> reqwest::redirect::Policy {
+    if max == 0 {
+        return reqwest::redirect::Policy::none();
     }
+
+    reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= max {
+            return attempt.error("too many redirects");
+        }
+
+        match sandbox::connect(attempt.url().host_str().unwrap_or_default()) {
+            Ok(()) => attempt.follow(),
+            Err(error) => attempt.error(error),
+        }
+    })
+}
+
+/// Construct a client which consults the sandbox when following redirects.
+fn client() -> Result<reqwest::Client, reqwest::Error> {
+    reqwest::Client::builder()
+        .redirect(redirect_policy(DEFAULT_MAX_REDIRECTS))
+        .build()
+}
+
+/// Send a request after checking that its host is permitted by the sandbox.
+async fn execute(request: reqwest::RequestBuilder) -> Result<Response, Restricted<Error>> {
+    let (client, request) = request.build_split();
+    let request = request.map_err(restricted)?;
+    sandbox::connect(request.url().host_str().unwrap_or_default())?;
+    let response = client.execute(request).await.map_err(restricted)?;
+    Ok(Response { response })
 }
 
 impl Error {
     /// Write a display representation the error.
     #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
     fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
-        write!(f, "{}", self.inner)
+        write!(f, "{}", self.kind)
     }
 }
 
@@ -1493,6 +1702,124 @@ impl Response {
     fn content_length(&self) -> Option<u64> {
         self.response.content_length()
     }
+
+    /// Get the final URL of the response, after any redirects have been
+    /// followed.
+    #[rune::function(keep, instance)]
+    fn url(&self) -> alloc::Result<String> {
+        self.response.url().as_str().try_to_owned()
+    }
+
+    /// Get the value of the given header, if present.
+    ///
+    /// Header names are matched without regard to case. If the header occurs
+    /// more than once, the first value is returned.
+    ///
+    /// ```rune,no_run
+    /// let response = http::get("http://example.com").await?;
+    ///
+    /// if let Some(content_type) = response.header("content-type") {
+    ///     println!("{content_type}");
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn header(&self, name: &str) -> alloc::Result<Option<String>> {
+        let Some(value) = self.response.headers().get(name) else {
+            return Ok(None);
+        };
+
+        Ok(Some(header_value(value)?))
+    }
+
+    /// Get all headers of the response as a vector of name and value pairs.
+    ///
+    /// Header names are in lowercase, and a header which occurs more than once
+    /// is included once for every value.
+    ///
+    /// ```rune,no_run
+    /// let response = http::get("http://example.com").await?;
+    ///
+    /// for (name, value) in response.headers() {
+    ///     println!("{name}: {value}");
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn headers(&self) -> alloc::Result<Vec<(String, String)>> {
+        let headers = self.response.headers();
+        let mut output = Vec::try_with_capacity(headers.len())?;
+
+        for (name, value) in headers {
+            output.try_push((name.as_str().try_to_owned()?, header_value(value)?))?;
+        }
+
+        Ok(output)
+    }
+
+    /// Get the body of the response as a stream of chunks of bytes, as they
+    /// are received.
+    ///
+    /// ```rune,no_run
+    /// async fn chunks(response) {
+    ///     let stream = response.bytes_stream();
+    ///
+    ///     while let Some(chunk) = stream.next().await? {
+    ///         yield chunk;
+    ///     }
+    /// }
+    ///
+    /// let response = http::get("http://example.com").await?;
+    /// let chunks = chunks(response);
+    ///
+    /// while let Some(chunk) = chunks.next().await {
+    ///     println!("received {} bytes", chunk.len());
+    /// }
+    /// ```
+    #[rune::function(keep)]
+    fn bytes_stream(self) -> BytesStream {
+        BytesStream {
+            response: self.response,
+        }
+    }
+}
+
+/// The body of a [`Response`] as a stream of bytes, see
+/// [`Response::bytes_stream`].
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub struct BytesStream {
+    response: reqwest::Response,
+}
+
+impl BytesStream {
+    /// Get the next chunk of the body, or `None` once the whole body has been
+    /// received.
+    ///
+    /// ```rune,no_run
+    /// let response = http::get("http://example.com").await?;
+    /// let stream = response.bytes_stream();
+    ///
+    /// while let Some(chunk) = stream.next().await? {
+    ///     println!("received {} bytes", chunk.len());
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::next)]
+    async fn next(mut this: Mut<Self>) -> alloc::Result<Result<Option<Bytes>, Error>> {
+        let Some(chunk) = nested_try!(this.response.chunk().await) else {
+            return Ok(Ok(None));
+        };
+
+        let mut bytes = Vec::try_with_capacity(chunk.len())?;
+        bytes.try_extend_from_slice(chunk.as_ref())?;
+        Ok(Ok(Some(Bytes::from_vec(bytes))))
+    }
+}
+
+/// Convert a header value into a string, replacing any bytes which are not
+/// valid UTF-8.
+fn header_value(value: &reqwest::header::HeaderValue) -> alloc::Result<String> {
+    std::string::String::from_utf8_lossy(value.as_bytes())
+        .as_ref()
+        .try_to_owned()
 }
 
 /// An HTTP status code.
@@ -1948,10 +2275,13 @@ impl RequestBuilder {
     ///
     /// let response = response.text().await?;
     /// ```
+    ///
+    /// If a sandbox is installed in the context, this fails with a
+    /// `SandboxError` unless the host of the request, and of any redirect
+    /// which is followed, is allowed to be connected to.
     #[rune::function(keep)]
-    async fn send(self) -> Result<Response, Error> {
-        let response = self.request.send().await?;
-        Ok(Response { response })
+    async fn send(self) -> Result<Response, Restricted<Error>> {
+        execute(self.request).await
     }
 
     /// Modify a header in the request.
@@ -2073,6 +2403,255 @@ impl RequestBuilder {
             request: self.request.body(bytes.into_std()),
         }
     }
+
+    /// Append the given parameters to the query string of the request.
+    ///
+    /// The parameters are given as an object, or as a vector of pairs to
+    /// control their order or to repeat a parameter.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.get("http://example.com/search")
+    ///     .query([("q", "rune"), ("page", 2)])
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn query(self, params: Value) -> Self {
+        Self {
+            request: self.request.query(&params),
+        }
+    }
+
+    /// Set the request body to the given value encoded as JSON.
+    ///
+    /// This also sets the `content-type` header to `application/json`.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.post("https://postman-echo.com/post")
+    ///     .json(#{ name: "rune", tags: ["scripting"] })
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn json(self, value: Value) -> Self {
+        Self {
+            request: self.request.json(&value),
+        }
+    }
+
+    /// Set the request body to the given object encoded as a URL encoded
+    /// form.
+    ///
+    /// This also sets the `content-type` header to
+    /// `application/x-www-form-urlencoded`.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.post("https://postman-echo.com/post")
+    ///     .form(#{ username: "rune", remember: true })
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn form(self, value: Value) -> Self {
+        Self {
+            request: self.request.form(&value),
+        }
+    }
+
+    /// Set the request body to a multipart form.
+    ///
+    /// ```rune,no_run
+    /// use http::{Form, Part};
+    ///
+    /// let form = Form::new()
+    ///     .text("title", "Hello")
+    ///     .part("file", Part::bytes(b"Hello World").file_name("hello.txt"));
+    ///
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.post("https://postman-echo.com/post")
+    ///     .multipart(form)
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn multipart(self, form: Form) -> Self {
+        Self {
+            request: self.request.multipart(form.inner),
+        }
+    }
+
+    /// Set a timeout for the request, from when it starts connecting until the
+    /// response body has been received.
+    ///
+    /// This overrides the timeout set on the client.
+    ///
+    /// ```rune,no_run
+    /// use time::Duration;
+    ///
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.get("http://example.com")
+    ///     .timeout(Duration::from_secs(5))
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            request: self.request.timeout(timeout.into_std()),
+        }
+    }
+}
+
+/// A builder to configure a [`Client`].
+///
+/// To construct a ClientBuilder, use [`Client::builder`].
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub struct ClientBuilder {
+    builder: reqwest::ClientBuilder,
+    headers: reqwest::header::HeaderMap,
+    max_redirects: usize,
+}
+
+impl ClientBuilder {
+    /// Add a header which is sent with every request.
+    ///
+    /// Errors if the header name or value is invalid.
+    ///
+    /// ```rune
+    /// let client = http::Client::builder()
+    ///     .default_header("accept", "application/json")?
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn default_header(mut self, key: &str, value: &str) -> Result<Self, Error> {
+        let name = reqwest::header::HeaderName::from_bytes(key.as_bytes());
+        let value = reqwest::header::HeaderValue::from_str(value);
+
+        let (Ok(name), Ok(value)) = (name, value) else {
+            return Err(Error::new(ErrorKind::InvalidHeader));
+        };
+
+        self.headers.append(name, value);
+        Ok(self)
+    }
+
+    /// Set the `user-agent` header which is sent with every request.
+    ///
+    /// ```rune
+    /// let client = http::Client::builder()
+    ///     .user_agent("my-script/1.0")
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn user_agent(self, user_agent: &str) -> Self {
+        Self {
+            builder: self.builder.user_agent(user_agent),
+            ..self
+        }
+    }
+
+    /// Send all requests through the proxy at the given URL.
+    ///
+    /// If a sandbox is installed in the context, this fails with a
+    /// `SandboxError` unless the host of the proxy is allowed to be connected
+    /// to.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::builder()
+    ///     .proxy("http://proxy.example.com:8080")?
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn proxy(self, url: &str) -> Result<Self, Restricted<Error>> {
+        let proxy = reqwest::Proxy::all(url).map_err(restricted)?;
+
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_owned));
+
+        sandbox::connect(host.unwrap_or_default())?;
+
+        Ok(Self {
+            builder: self.builder.proxy(proxy),
+            ..self
+        })
+    }
+
+    /// Set the largest number of redirects which are followed, where `0`
+    /// disables following redirects. Defaults to 10.
+    ///
+    /// ```rune
+    /// let client = http::Client::builder()
+    ///     .max_redirects(0)
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn max_redirects(self, max: usize) -> Self {
+        Self {
+            max_redirects: max,
+            ..self
+        }
+    }
+
+    /// Set a timeout for every request, from when it starts connecting until
+    /// the response body has been received.
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let client = http::Client::builder()
+    ///     .timeout(Duration::from_secs(30))
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            builder: self.builder.timeout(timeout.into_std()),
+            ..self
+        }
+    }
+
+    /// Set a timeout for connecting to a host.
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let client = http::Client::builder()
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn connect_timeout(self, timeout: Duration) -> Self {
+        Self {
+            builder: self.builder.connect_timeout(timeout.into_std()),
+            ..self
+        }
+    }
+
+    /// Construct the client.
+    ///
+    /// ```rune
+    /// let client = http::Client::builder().build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn build(self) -> Result<Client, Error> {
+        let client = self
+            .builder
+            .default_headers(self.headers)
+            .redirect(redirect_policy(self.max_redirects))
+            .build()?;
+
+        Ok(Client { client })
+    }
 }
 
 impl Client {
@@ -2086,7 +2665,31 @@ impl Client {
     #[rune::function(keep, path = Self::new)]
     fn new() -> Self {
         Self {
-            client: reqwest::Client::new(),
+            // Like `reqwest::Client::new`, this only fails if the TLS backend
+            // cannot be initialized.
+            client: client().expect("failed to construct http client"),
+        }
+    }
+
+    /// Construct a builder to configure a client.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let client = http::Client::builder()
+    ///     .default_header("accept", "application/json")?
+    ///     .timeout(Duration::from_secs(30))
+    ///     .max_redirects(3)
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep, path = Self::builder)]
+    fn builder() -> ClientBuilder {
+        ClientBuilder {
+            builder: reqwest::Client::builder(),
+            headers: reqwest::header::HeaderMap::new(),
+            max_redirects: DEFAULT_MAX_REDIRECTS,
         }
     }
 
@@ -2222,10 +2825,9 @@ impl Client {
 /// let timezone = json["timezone"];
 /// ```
 #[rune::function]
-async fn get(url: Ref<str>) -> Result<Response, Error> {
-    Ok(Response {
-        response: reqwest::get(url.as_ref()).await?,
-    })
+async fn get(url: Ref<str>) -> Result<Response, Restricted<Error>> {
+    let client = client().map_err(restricted)?;
+    execute(client.get(url.as_ref())).await
 }
 
 mod const_version {
@@ -0,0 +1,115 @@
+use rune::runtime::Bytes;
+use rune::Any;
+
+use super::Error;
+
+/// A multipart form to upload with [`RequestBuilder::multipart`].
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// use http::{Form, Part};
+///
+/// let contents = fs::read("report.csv").await?;
+///
+/// let form = Form::new()
+///     .text("title", "Monthly report")
+///     .part("file", Part::bytes(contents).file_name("report.csv").mime_str("text/csv")?);
+///
+/// let client = http::Client::new();
+///
+/// let response = client.post("https://example.com/upload")
+///     .multipart(form)
+///     .send()
+///     .await?;
+/// ```
+///
+/// [`RequestBuilder::multipart`]: super::RequestBuilder
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub(super) struct Form {
+    pub(super) inner: reqwest::multipart::Form,
+}
+
+impl Form {
+    /// Construct a new empty form.
+    #[rune::function(keep, path = Self::new)]
+    fn new() -> Self {
+        Self {
+            inner: reqwest::multipart::Form::new(),
+        }
+    }
+
+    /// Add a text field to the form.
+    #[rune::function(keep)]
+    fn text(self, name: &str, value: &str) -> Self {
+        Self {
+            inner: self.inner.text(name.to_owned(), value.to_owned()),
+        }
+    }
+
+    /// Add a part to the form.
+    #[rune::function(keep)]
+    fn part(self, name: &str, part: Part) -> Self {
+        Self {
+            inner: self.inner.part(name.to_owned(), part.inner),
+        }
+    }
+}
+
+/// A part of a multipart [`Form`].
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub(super) struct Part {
+    inner: reqwest::multipart::Part,
+}
+
+impl Part {
+    /// Construct a part from bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let part = http::Part::bytes(b"\x00\x01").file_name("data.bin");
+    /// ```
+    #[rune::function(keep, path = Self::bytes)]
+    fn bytes(bytes: Bytes) -> Self {
+        let bytes = bytes.into_vec();
+
+        Self {
+            inner: reqwest::multipart::Part::bytes(bytes.into_std()),
+        }
+    }
+
+    /// Construct a part from text.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let part = http::Part::text("hello").mime_str("text/plain")?;
+    /// ```
+    #[rune::function(keep, path = Self::text)]
+    fn text(text: &str) -> Self {
+        Self {
+            inner: reqwest::multipart::Part::text(text.to_owned()),
+        }
+    }
+
+    /// Set the file name of the part.
+    #[rune::function(keep)]
+    fn file_name(self, name: &str) -> Self {
+        Self {
+            inner: self.inner.file_name(name.to_owned()),
+        }
+    }
+
+    /// Set the mime type of the part.
+    ///
+    /// Errors if the mime type can't be parsed.
+    #[rune::function(keep)]
+    fn mime_str(self, mime: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: self.inner.mime_str(mime)?,
+        })
+    }
+}
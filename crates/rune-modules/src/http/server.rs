@@ -0,0 +1,1091 @@
+use core::future::{poll_fn, Future};
+use core::task::Poll;
+
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::rc::Rc;
+
+use rune::alloc;
+use rune::alloc::prelude::*;
+use rune::runtime::sandbox::{self, Restricted};
+use rune::runtime::{self, Bytes, Function, Ref, Value, VmError};
+use rune::{nested_try, Any, TypeHash};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::{Error, ErrorKind, StatusCode};
+
+/// The largest request head which is accepted.
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+/// The largest number of headers which is accepted in a request.
+const MAX_HEADERS: usize = 64;
+/// The default limit for the size of a request body.
+const DEFAULT_MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// An HTTP/1.1 server which dispatches requests to Rune functions.
+///
+/// Routes are matched in the order they were added. A route path is made up of
+/// segments separated by `/`, where a segment starting with `:` matches any
+/// single segment of the request path and makes it available through
+/// [`Request::param`].
+///
+/// A handler is called with the [`Request`] and may be an `async` function.
+/// It returns a [`ResponseBuilder`], a `String` which is sent as text, or
+/// `Bytes`. A handler which returns an `Err` results in a
+/// `500 Internal Server Error` response.
+///
+/// Requests for which no route matches the path receive a `404 Not Found`
+/// response, and those where only the method doesn't match receive a
+/// `405 Method Not Allowed` response. Routes for `GET` also answer `HEAD`
+/// requests.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// use http::{ResponseBuilder, Server, StatusCode};
+///
+/// async fn hello(request) {
+///     let name = request.param("name").unwrap_or("world");
+///     `Hello, ${name}!`
+/// }
+///
+/// async fn webhook(request) {
+///     let event = request.json()?;
+///     println!("received {}", event["kind"]);
+///     Ok(ResponseBuilder::new().status(StatusCode::ACCEPTED))
+/// }
+///
+/// let server = Server::bind("127.0.0.1:8080").await?;
+/// server.get("/hello/:name", hello)?;
+/// server.post("/webhook", webhook)?;
+/// server.serve().await?;
+/// ```
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub(super) struct Server {
+    listener: TcpListener,
+    routes: Vec<Route>,
+    max_body_size: usize,
+}
+
+impl Server {
+    /// Bind a server to the given address.
+    ///
+    /// Bind to port `0` to have the operating system pick a free port, which
+    /// can then be looked up with [`Server::local_addr`].
+    ///
+    /// If a sandbox is installed in the context, this fails with a
+    /// `SandboxError` unless the host of the address is allowed to be listened
+    /// on.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// println!("listening on {}", server.local_addr()?);
+    /// ```
+    #[rune::function(keep, path = Self::bind)]
+    async fn bind(addr: Ref<str>) -> Result<Self, Restricted<Error>> {
+        sandbox::listen(host(&addr))?;
+
+        let listener = match TcpListener::bind(addr.as_ref()).await {
+            Ok(listener) => listener,
+            Err(error) => return Err(Restricted::Error(Error::from(error))),
+        };
+
+        Ok(Self {
+            listener,
+            routes: Vec::new(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        })
+    }
+
+    /// Get the address the server is bound to, like `127.0.0.1:8080`.
+    #[rune::function(keep, instance)]
+    fn local_addr(&self) -> alloc::Result<Result<String, Error>> {
+        let addr = nested_try!(self.listener.local_addr());
+        Ok(Ok(try_format!("{addr}")))
+    }
+
+    /// Set the largest request body in bytes which is accepted.
+    ///
+    /// Requests with a larger body receive a `413 Payload Too Large` response.
+    /// Defaults to 16 MiB.
+    #[rune::function(keep, instance)]
+    fn max_body_size(&mut self, size: usize) {
+        self.max_body_size = size;
+    }
+
+    /// Add a route for the given method and path.
+    ///
+    /// The method `*` matches any method.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// async fn item(request) {
+    ///     `${request.method()} item ${request.param("id")?}`
+    /// }
+    ///
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.route("PATCH", "/items/:id", item)?;
+    /// server.route("*", "/any", item)?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn route(
+        &mut self,
+        method: &str,
+        path: &str,
+        handler: Function,
+    ) -> alloc::Result<Result<(), Error>> {
+        let method = match method {
+            "*" => None,
+            method => match reqwest::Method::from_bytes(method.as_bytes()) {
+                Ok(method) => Some(method),
+                Err(..) => return Ok(Err(Error::new(ErrorKind::InvalidMethod))),
+            },
+        };
+
+        self.add(method, path, handler)
+    }
+
+    /// Add a route for `GET` requests to the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.get("/health", |_| "ok")?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn get(&mut self, path: &str, handler: Function) -> alloc::Result<Result<(), Error>> {
+        self.add(Some(reqwest::Method::GET), path, handler)
+    }
+
+    /// Add a route for `POST` requests to the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.post("/echo", |request| request.body())?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn post(&mut self, path: &str, handler: Function) -> alloc::Result<Result<(), Error>> {
+        self.add(Some(reqwest::Method::POST), path, handler)
+    }
+
+    /// Add a route for `PUT` requests to the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.put("/items/:id", |request| request.param("id"))?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn put(&mut self, path: &str, handler: Function) -> alloc::Result<Result<(), Error>> {
+        self.add(Some(reqwest::Method::PUT), path, handler)
+    }
+
+    /// Add a route for `DELETE` requests to the given path.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.delete("/items/:id", |request| request.param("id"))?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn delete(&mut self, path: &str, handler: Function) -> alloc::Result<Result<(), Error>> {
+        self.add(Some(reqwest::Method::DELETE), path, handler)
+    }
+
+    /// Serve requests until the returned future is dropped.
+    ///
+    /// Connections are served concurrently on the task which awaits the
+    /// server. Serving stops with an error if a handler raises an error, like
+    /// a panic, or if accepting a connection fails.
+    ///
+    /// # Examples
+    ///
+    /// Serving a single request by racing the server against a client:
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.get("/", |_| "hello")?;
+    ///
+    /// let url = `http://${server.local_addr()?}/`;
+    /// let server = server.serve();
+    ///
+    /// let response = select {
+    ///     result = server => panic!("server stopped: {result:?}"),
+    ///     response = http::get(url) => response?,
+    /// };
+    ///
+    /// assert_eq!(response.text().await?, "hello");
+    /// ```
+    #[rune::function(keep, instance)]
+    async fn serve(self) -> Result<Result<(), Error>, VmError> {
+        let Self {
+            listener,
+            routes,
+            max_body_size,
+        } = self;
+
+        let routes = Rc::new(routes);
+        let mut connections = std::vec::Vec::new();
+
+        poll_fn(move |cx| {
+            loop {
+                match listener.poll_accept(cx) {
+                    Poll::Ready(Ok((stream, remote))) => {
+                        let routes = routes.clone();
+                        let future = connection(routes, stream, remote, max_body_size);
+                        connections.push(std::boxed::Box::pin(future));
+                    }
+                    Poll::Ready(Err(error)) => return Poll::Ready(Ok(Err(Error::from(error)))),
+                    Poll::Pending => break,
+                }
+            }
+
+            let mut index = 0;
+
+            while let Some(future) = connections.get_mut(index) {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(result) => {
+                        drop(connections.swap_remove(index));
+
+                        if let Err(error) = result {
+                            return Poll::Ready(Err(error));
+                        }
+                    }
+                    Poll::Pending => {
+                        index += 1;
+                    }
+                }
+            }
+
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn add(
+        &mut self,
+        method: Option<reqwest::Method>,
+        path: &str,
+        handler: Function,
+    ) -> alloc::Result<Result<(), Error>> {
+        let Some(path) = path.strip_prefix('/') else {
+            return Ok(Err(Error::new(ErrorKind::InvalidRoute)));
+        };
+
+        let mut segments = Vec::new();
+
+        for segment in path.split('/') {
+            let segment = match segment.strip_prefix(':') {
+                Some("") => return Ok(Err(Error::new(ErrorKind::InvalidRoute))),
+                Some(name) => Segment::Param(name.try_to_owned()?),
+                None => Segment::Literal(segment.try_to_owned()?),
+            };
+
+            segments.try_push(segment)?;
+        }
+
+        self.routes.try_push(Route {
+            method,
+            segments,
+            handler,
+        })?;
+
+        Ok(Ok(()))
+    }
+}
+
+/// A request received by a [`Server`].
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub(super) struct Request {
+    method: reqwest::Method,
+    path: String,
+    query: Option<String>,
+    headers: Vec<(String, String)>,
+    params: Vec<(String, String)>,
+    body: Bytes,
+    remote: SocketAddr,
+}
+
+impl Request {
+    /// Get the method of the request, like `GET`.
+    #[rune::function(keep, instance)]
+    fn method(&self) -> alloc::Result<String> {
+        self.method.as_str().try_to_owned()
+    }
+
+    /// Get the path of the request, like `/items/42`.
+    ///
+    /// The path is provided as it was received, without decoding.
+    #[rune::function(keep, instance)]
+    fn path(&self) -> alloc::Result<String> {
+        self.path.try_clone()
+    }
+
+    /// Get the decoded value of the given query parameter, if present.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// async fn search(request) {
+    ///     let term = request.query("q").unwrap_or("");
+    ///     `searching for ${term}`
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn query(&self, name: &str) -> alloc::Result<Option<String>> {
+        let Some(query) = &self.query else {
+            return Ok(None);
+        };
+
+        for pair in query.split('&') {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+            if decode(key, true)?.as_deref() == Some(name) {
+                return decode(value, true);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the value of the given path parameter, if the route has it.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.get("/users/:id", |request| request.param("id"))?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn param(&self, name: &str) -> alloc::Result<Option<String>> {
+        lookup(&self.params, name)
+    }
+
+    /// Get the value of the given header, if present.
+    ///
+    /// Header names are matched without regard to case.
+    #[rune::function(keep, instance)]
+    fn header(&self, name: &str) -> alloc::Result<Option<String>> {
+        for (key, value) in &self.headers {
+            if key.eq_ignore_ascii_case(name) {
+                return Ok(Some(value.try_clone()?));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get all headers of the request as a vector of name and value pairs.
+    ///
+    /// Header names are in lowercase.
+    #[rune::function(keep, instance)]
+    fn headers(&self) -> alloc::Result<Vec<(String, String)>> {
+        self.headers.try_clone()
+    }
+
+    /// Get the address of the client which sent the request.
+    #[rune::function(keep, instance)]
+    fn remote_addr(&self) -> alloc::Result<String> {
+        Ok(try_format!("{}", self.remote))
+    }
+
+    /// Get the body of the request as bytes.
+    #[rune::function(keep, instance)]
+    fn body(&self) -> alloc::Result<Bytes> {
+        self.body.try_clone()
+    }
+
+    /// Get the body of the request as text.
+    ///
+    /// Errors if the body is not valid UTF-8.
+    #[rune::function(keep, instance)]
+    fn text(&self) -> alloc::Result<Result<String, Error>> {
+        let text = nested_try!(core::str::from_utf8(self.body.as_slice()));
+        Ok(Ok(text.try_to_owned()?))
+    }
+
+    /// Get the body of the request as a Rune value decoded from JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// async fn create(request) {
+    ///     let item = request.json()?;
+    ///     Ok(`created ${item["name"]}`)
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn json(&self) -> Result<Value, Error> {
+        Ok(serde_json::from_slice(self.body.as_slice())?)
+    }
+}
+
+/// A builder for the response to a [`Request`], returned from a handler.
+///
+/// Errors from building the response, like an invalid header name, are
+/// deferred until the response is sent, at which point a
+/// `500 Internal Server Error` response is sent instead.
+///
+/// # Examples
+///
+/// ```rune
+/// use http::{ResponseBuilder, StatusCode};
+///
+/// let response = ResponseBuilder::new()
+///     .status(StatusCode::CREATED)
+///     .header("location", "/items/42")
+///     .json(#{ id: 42 });
+/// ```
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub(super) struct ResponseBuilder {
+    status: reqwest::StatusCode,
+    headers: reqwest::header::HeaderMap,
+    body: Bytes,
+    error: Option<Error>,
+}
+
+impl ResponseBuilder {
+    /// Construct a new response builder for an empty `200 OK` response.
+    #[rune::function(keep, path = Self::new)]
+    fn new() -> Self {
+        Self::with_status(reqwest::StatusCode::OK)
+    }
+
+    /// Set the status code of the response.
+    #[rune::function(keep)]
+    fn status(mut self, status: StatusCode) -> Self {
+        self.status = status.inner;
+        self
+    }
+
+    /// Add a header to the response.
+    #[rune::function(keep)]
+    fn header(mut self, key: &str, value: &str) -> Self {
+        let name = reqwest::header::HeaderName::from_bytes(key.as_bytes());
+        let value = reqwest::header::HeaderValue::from_str(value);
+
+        match (name, value) {
+            (Ok(name), Ok(value)) => {
+                self.headers.append(name, value);
+            }
+            _ => {
+                self.error = Some(Error::new(ErrorKind::InvalidHeader));
+            }
+        }
+
+        self
+    }
+
+    /// Set the body of the response from bytes.
+    #[rune::function(keep)]
+    fn body_bytes(mut self, bytes: Bytes) -> Self {
+        self.body = bytes;
+        self
+    }
+
+    /// Set the body of the response to the given text.
+    ///
+    /// Unless a content type has been set, it is set to `text/plain`.
+    #[rune::function(keep)]
+    fn text(self, text: &str) -> alloc::Result<Self> {
+        let bytes = Bytes::from_vec(Vec::try_from(text.as_bytes())?);
+        Ok(self.body_with_type(bytes, "text/plain; charset=utf-8"))
+    }
+
+    /// Set the body of the response to the given value encoded as JSON.
+    ///
+    /// Unless a content type has been set, it is set to `application/json`.
+    #[rune::function(keep)]
+    fn json(mut self, value: Value) -> alloc::Result<Self> {
+        let bytes = match serde_json::to_vec(&value) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                self.error = Some(Error::from(error));
+                return Ok(self);
+            }
+        };
+
+        let bytes = Bytes::from_vec(Vec::try_from(bytes)?);
+        Ok(self.body_with_type(bytes, "application/json"))
+    }
+
+    fn with_status(status: reqwest::StatusCode) -> Self {
+        Self {
+            status,
+            headers: reqwest::header::HeaderMap::new(),
+            body: Bytes::new(),
+            error: None,
+        }
+    }
+
+    fn body_with_type(mut self, body: Bytes, content_type: &'static str) -> Self {
+        if !self.headers.contains_key(reqwest::header::CONTENT_TYPE) {
+            let value = reqwest::header::HeaderValue::from_static(content_type);
+            self.headers.insert(reqwest::header::CONTENT_TYPE, value);
+        }
+
+        self.body = body;
+        self
+    }
+
+    /// Convert a value returned from a handler into a response.
+    fn from_handler(value: Value) -> Result<Self, VmError> {
+        let hash = value.type_hash();
+
+        if hash == String::HASH {
+            return Ok(Self::new().text(&value.borrow_string_ref()?)?);
+        }
+
+        if hash == Bytes::HASH {
+            let bytes = rune::from_value(value)?;
+            return Ok(Self::new().body_with_type(bytes, "application/octet-stream"));
+        }
+
+        if hash == <Result<Value, Value>>::HASH {
+            return match rune::from_value::<Result<Value, Value>>(value)? {
+                Ok(value) => Self::from_handler(value),
+                Err(..) => Ok(Self::with_status(
+                    reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                )),
+            };
+        }
+
+        Ok(rune::from_value(value)?)
+    }
+
+    /// Encode the response, replacing it with an error if it couldn't be
+    /// built.
+    fn encode(&self, head: bool, keep_alive: bool) -> io::Result<std::vec::Vec<u8>> {
+        if let Some(error) = &self.error {
+            let error = std::format!("{error}");
+
+            let mut response = Self::with_status(reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+            response.body =
+                Bytes::from_vec(Vec::try_from(error.into_bytes()).map_err(io::Error::other)?);
+            return response.encode(head, keep_alive);
+        }
+
+        let mut out = std::vec::Vec::new();
+
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\n",
+            self.status.as_str(),
+            self.status.canonical_reason().unwrap_or_default()
+        )?;
+
+        for (name, value) in &self.headers {
+            out.extend_from_slice(name.as_str().as_bytes());
+            out.extend_from_slice(b": ");
+            out.extend_from_slice(value.as_bytes());
+            out.extend_from_slice(b"\r\n");
+        }
+
+        if !self.headers.contains_key(reqwest::header::CONTENT_LENGTH) {
+            write!(out, "content-length: {}\r\n", self.body.len())?;
+        }
+
+        if !keep_alive {
+            out.extend_from_slice(b"connection: close\r\n");
+        }
+
+        out.extend_from_slice(b"\r\n");
+
+        if !head {
+            out.extend_from_slice(self.body.as_slice());
+        }
+
+        Ok(out)
+    }
+}
+
+#[derive(Debug)]
+struct Route {
+    method: Option<reqwest::Method>,
+    segments: Vec<Segment>,
+    handler: Function,
+}
+
+impl Route {
+    /// Match the route against the given path, returning the path parameters
+    /// if it matches.
+    fn matches(&self, path: &str) -> alloc::Result<Option<Vec<(String, String)>>> {
+        let mut params = Vec::new();
+        let mut parts = path.strip_prefix('/').unwrap_or(path).split('/');
+
+        for segment in &self.segments {
+            let Some(part) = parts.next() else {
+                return Ok(None);
+            };
+
+            match segment {
+                Segment::Literal(literal) => {
+                    if literal != part {
+                        return Ok(None);
+                    }
+                }
+                Segment::Param(name) => {
+                    if part.is_empty() {
+                        return Ok(None);
+                    }
+
+                    let value = match decode(part, false)? {
+                        Some(value) => value,
+                        None => part.try_to_owned()?,
+                    };
+
+                    params.try_push((name.try_clone()?, value))?;
+                }
+            }
+        }
+
+        if parts.next().is_some() {
+            return Ok(None);
+        }
+
+        Ok(Some(params))
+    }
+
+    /// Test if the route handles the given method.
+    fn allows(&self, method: &reqwest::Method) -> bool {
+        match &self.method {
+            None => true,
+            Some(allowed) => {
+                allowed == method
+                    || *allowed == reqwest::Method::GET && *method == reqwest::Method::HEAD
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Segment {
+    Literal(String),
+    Param(String),
+}
+
+/// An error while reading a request.
+enum ReadError {
+    /// The connection failed or was closed.
+    Io,
+    /// The request is invalid and should be answered with the given status
+    /// before closing the connection.
+    Status(reqwest::StatusCode),
+    /// The virtual machine failed.
+    Vm(VmError),
+}
+
+impl From<io::Error> for ReadError {
+    #[inline]
+    fn from(_: io::Error) -> Self {
+        Self::Io
+    }
+}
+
+impl From<alloc::Error> for ReadError {
+    #[inline]
+    fn from(error: alloc::Error) -> Self {
+        Self::Vm(VmError::from(error))
+    }
+}
+
+/// A request which has been read from a connection.
+struct Incoming {
+    request: Request,
+    keep_alive: bool,
+}
+
+/// Serve requests over a single connection until it is closed.
+async fn connection(
+    routes: Rc<Vec<Route>>,
+    mut stream: TcpStream,
+    remote: SocketAddr,
+    max_body_size: usize,
+) -> Result<(), VmError> {
+    let mut buf = std::vec::Vec::new();
+
+    loop {
+        let incoming = match read_request(&mut stream, &mut buf, remote, max_body_size).await {
+            Ok(Some(incoming)) => incoming,
+            Ok(None) | Err(ReadError::Io) => return Ok(()),
+            Err(ReadError::Status(status)) => {
+                let response = ResponseBuilder::with_status(status);
+
+                if let Ok(out) = response.encode(false, false) {
+                    _ = stream.write_all(&out).await;
+                }
+
+                return Ok(());
+            }
+            Err(ReadError::Vm(error)) => return Err(error),
+        };
+
+        let Incoming {
+            request,
+            keep_alive,
+        } = incoming;
+
+        let head = request.method == reqwest::Method::HEAD;
+        let response = dispatch(&routes, request).await?;
+
+        let Ok(out) = response.encode(head, keep_alive) else {
+            return Ok(());
+        };
+
+        if stream.write_all(&out).await.is_err() || !keep_alive {
+            return Ok(());
+        }
+    }
+}
+
+/// Dispatch a request to the first matching route.
+async fn dispatch(routes: &[Route], mut request: Request) -> Result<ResponseBuilder, VmError> {
+    let mut found = false;
+
+    for route in routes {
+        let Some(params) = route.matches(&request.path)? else {
+            continue;
+        };
+
+        found = true;
+
+        if !route.allows(&request.method) {
+            continue;
+        }
+
+        request.params = params;
+
+        let value: Value = route.handler.call((request,))?;
+
+        let value = if value.type_hash() == runtime::Future::HASH {
+            value.into_future()?.await?
+        } else {
+            value
+        };
+
+        return ResponseBuilder::from_handler(value);
+    }
+
+    let status = if found {
+        reqwest::StatusCode::METHOD_NOT_ALLOWED
+    } else {
+        reqwest::StatusCode::NOT_FOUND
+    };
+
+    Ok(ResponseBuilder::with_status(status))
+}
+
+/// Read the next request from the connection, or `None` if the connection was
+/// closed before a new request was started.
+async fn read_request(
+    stream: &mut TcpStream,
+    buf: &mut std::vec::Vec<u8>,
+    remote: SocketAddr,
+    max_body_size: usize,
+) -> Result<Option<Incoming>, ReadError> {
+    let (head, len) = loop {
+        if let Some(head) = parse_head(buf)? {
+            break head;
+        }
+
+        if buf.len() >= MAX_HEAD_SIZE {
+            return Err(ReadError::Status(
+                reqwest::StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            ));
+        }
+
+        if read(stream, buf).await? == 0 {
+            if buf.is_empty() {
+                return Ok(None);
+            }
+
+            return Err(ReadError::Io);
+        }
+    };
+
+    buf.drain(..len);
+
+    let Head {
+        method,
+        target,
+        version,
+        headers,
+    } = head;
+
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.as_str() == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let connection = header("connection").map(str::to_ascii_lowercase);
+
+    let keep_alive = match (version, connection.as_deref()) {
+        (_, Some("close")) => false,
+        (0, Some("keep-alive")) => true,
+        (version, _) => version > 0,
+    };
+
+    let chunked = header("transfer-encoding")
+        .is_some_and(|value| value.to_ascii_lowercase().ends_with("chunked"));
+
+    let length = match header("content-length") {
+        Some(length) => match length.trim().parse::<usize>() {
+            Ok(length) => length,
+            Err(..) => return Err(ReadError::Status(reqwest::StatusCode::BAD_REQUEST)),
+        },
+        None => 0,
+    };
+
+    if length > max_body_size {
+        return Err(ReadError::Status(reqwest::StatusCode::PAYLOAD_TOO_LARGE));
+    }
+
+    if version > 0
+        && header("expect").is_some_and(|value| value.eq_ignore_ascii_case("100-continue"))
+    {
+        stream.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").await?;
+    }
+
+    let body = if chunked {
+        read_chunked(stream, buf, max_body_size).await?
+    } else {
+        fill(stream, buf, length).await?;
+        let mut body = Vec::try_with_capacity(length)?;
+        body.try_extend_from_slice(&buf[..length])?;
+        buf.drain(..length);
+        body
+    };
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (target.as_str(), None),
+    };
+
+    let request = Request {
+        method,
+        path: path.try_to_owned()?,
+        query: query.map(str::try_to_owned).transpose()?,
+        headers,
+        params: Vec::new(),
+        body: Bytes::from_vec(body),
+        remote,
+    };
+
+    Ok(Some(Incoming {
+        request,
+        keep_alive,
+    }))
+}
+
+/// The parsed head of a request.
+struct Head {
+    method: reqwest::Method,
+    target: String,
+    version: u8,
+    headers: Vec<(String, String)>,
+}
+
+/// Parse the head of a request, returning it and its length if the buffer
+/// contains all of it.
+fn parse_head(buf: &[u8]) -> Result<Option<(Head, usize)>, ReadError> {
+    let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+    let mut request = httparse::Request::new(&mut headers);
+
+    let len = match request.parse(buf) {
+        Ok(httparse::Status::Complete(len)) => len,
+        Ok(httparse::Status::Partial) => return Ok(None),
+        Err(httparse::Error::TooManyHeaders) => {
+            return Err(ReadError::Status(
+                reqwest::StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+            ));
+        }
+        Err(..) => return Err(ReadError::Status(reqwest::StatusCode::BAD_REQUEST)),
+    };
+
+    let bad_request = || ReadError::Status(reqwest::StatusCode::BAD_REQUEST);
+
+    let (Some(method), Some(target), Some(version)) =
+        (request.method, request.path, request.version)
+    else {
+        return Err(bad_request());
+    };
+
+    let method = reqwest::Method::from_bytes(method.as_bytes()).map_err(|_| bad_request())?;
+
+    let mut output = Vec::try_with_capacity(request.headers.len())?;
+
+    for header in request.headers.iter() {
+        let value = core::str::from_utf8(header.value).map_err(|_| bad_request())?;
+        let name = header.name.to_ascii_lowercase();
+        output.try_push((String::try_from(name)?, value.try_to_owned()?))?;
+    }
+
+    let head = Head {
+        method,
+        target: target.try_to_owned()?,
+        version,
+        headers: output,
+    };
+
+    Ok(Some((head, len)))
+}
+
+/// Read a body encoded with the chunked transfer encoding.
+async fn read_chunked(
+    stream: &mut TcpStream,
+    buf: &mut std::vec::Vec<u8>,
+    max_body_size: usize,
+) -> Result<Vec<u8>, ReadError> {
+    let bad_request = || ReadError::Status(reqwest::StatusCode::BAD_REQUEST);
+
+    let mut body = Vec::new();
+
+    loop {
+        let line = read_line(stream, buf).await?;
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| bad_request())?;
+
+        if size == 0 {
+            // Trailers are read and discarded.
+            while !read_line(stream, buf).await?.is_empty() {}
+            return Ok(body);
+        }
+
+        if body.len().saturating_add(size) > max_body_size {
+            return Err(ReadError::Status(reqwest::StatusCode::PAYLOAD_TOO_LARGE));
+        }
+
+        fill(stream, buf, size + 2).await?;
+
+        if buf[size..size + 2] != *b"\r\n" {
+            return Err(bad_request());
+        }
+
+        body.try_extend_from_slice(&buf[..size])?;
+        buf.drain(..size + 2);
+    }
+}
+
+/// Read a line terminated by `\r\n` from the connection.
+async fn read_line(
+    stream: &mut TcpStream,
+    buf: &mut std::vec::Vec<u8>,
+) -> Result<std::string::String, ReadError> {
+    loop {
+        if let Some(n) = buf.windows(2).position(|w| w == b"\r\n") {
+            let line = std::string::String::from_utf8_lossy(&buf[..n]).into_owned();
+            buf.drain(..n + 2);
+            return Ok(line);
+        }
+
+        if buf.len() >= MAX_HEAD_SIZE {
+            return Err(ReadError::Status(reqwest::StatusCode::BAD_REQUEST));
+        }
+
+        if read(stream, buf).await? == 0 {
+            return Err(ReadError::Io);
+        }
+    }
+}
+
+/// Read until the buffer contains at least `len` bytes.
+async fn fill(
+    stream: &mut TcpStream,
+    buf: &mut std::vec::Vec<u8>,
+    len: usize,
+) -> Result<(), ReadError> {
+    while buf.len() < len {
+        if read(stream, buf).await? == 0 {
+            return Err(ReadError::Io);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read more data from the connection into the buffer.
+async fn read(stream: &mut TcpStream, buf: &mut std::vec::Vec<u8>) -> io::Result<usize> {
+    let mut chunk = [0; 8192];
+    let n = stream.read(&mut chunk).await?;
+    buf.extend_from_slice(&chunk[..n]);
+    Ok(n)
+}
+
+/// Look up a value in a list of pairs.
+fn lookup(pairs: &[(String, String)], name: &str) -> alloc::Result<Option<String>> {
+    for (key, value) in pairs {
+        if key == name {
+            return Ok(Some(value.try_clone()?));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Get the host of an address like `127.0.0.1:8080` or `[::1]:8080`.
+fn host(addr: &str) -> &str {
+    let host = match addr.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => host,
+        _ => addr,
+    };
+
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+/// Percent-decode a component of a path or query, where `plus` indicates that
+/// `+` is decoded as a space.
+///
+/// Returns `None` if the decoded component is not valid UTF-8.
+fn decode(input: &str, plus: bool) -> alloc::Result<Option<String>> {
+    let mut out = Vec::try_with_capacity(input.len())?;
+    let mut bytes = input.bytes();
+
+    while let Some(b) = bytes.next() {
+        let b = match b {
+            b'+' if plus => b' ',
+            b'%' => {
+                let mut lookahead = bytes.clone();
+
+                let hex = lookahead
+                    .next()
+                    .and_then(|a| Some((a, lookahead.next()?)))
+                    .and_then(|(a, b)| Some((hex(a)? << 4) | hex(b)?));
+
+                match hex {
+                    Some(b) => {
+                        bytes = lookahead;
+                        b
+                    }
+                    None => b'%',
+                }
+            }
+            b => b,
+        };
+
+        out.try_push(b)?;
+    }
+
+    Ok(String::from_utf8(out).ok())
+}
+
+fn hex(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
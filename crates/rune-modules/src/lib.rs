@@ -35,7 +35,10 @@
 //! * [macros]
 //! * [process]
 //! * [rand]
+//! * [regex]
 //! * [signal]
+//! * [sync]
+//! * [task]
 //! * [test]
 //! * [time]
 //! * [toml]
@@ -54,7 +57,10 @@
 //! * `macros` for the [macros module][macros]
 //! * `process` for the [process module][process]
 //! * `rand` for the [rand module][rand]
+//! * `regex` for the [regex module][regex]
 //! * `signal` for the [signal module][signal]
+//! * `sync` for the [sync module][sync]
+//! * `task` for the [task module][task]
 //! * `test` for the [test module][test]
 //! * `time` for the [time module][time]
 //! * `toml` for the [toml module][toml]
@@ -68,7 +74,10 @@
 //! [macros]: https://docs.rs/rune-modules/0/rune_modules/macros/
 //! [process]: https://docs.rs/rune-modules/0/rune_modules/process/
 //! [rand]: https://docs.rs/rune-modules/0/rune_modules/rand/
+//! [regex]: https://docs.rs/rune-modules/0/rune_modules/regex/
 //! [signal]: https://docs.rs/rune-modules/0/rune_modules/signal/
+//! [sync]: https://docs.rs/rune-modules/0/rune_modules/sync/
+//! [task]: https://docs.rs/rune-modules/0/rune_modules/task/
 //! [test]: https://docs.rs/rune-modules/0/rune_modules/test/
 //! [time]: https://docs.rs/rune-modules/0/rune_modules/time/
 //! [toml]: https://docs.rs/rune-modules/0/rune_modules/toml/
@@ -122,9 +131,18 @@ pub mod process;
 #[cfg(feature = "rand")]
 pub mod rand;
 
+#[cfg(feature = "regex")]
+pub mod regex;
+
 #[cfg(feature = "signal")]
 pub mod signal;
 
+#[cfg(feature = "sync")]
+pub mod sync;
+
+#[cfg(feature = "task")]
+pub mod task;
+
 #[cfg(feature = "time")]
 pub mod time;
 
@@ -138,7 +156,10 @@ entry! {
     {json, "json"},
     {process, "process"},
     {rand, "rand"},
+    {regex, "regex"},
     {signal, "signal"},
+    {sync, "sync", mpsc, oneshot, broadcast},
+    {task, "task"},
     {time, "time"},
     {toml, "toml", ser, de},
 }
@@ -35,6 +35,7 @@
 use rune::alloc::clone::TryClone;
 use rune::alloc::fmt::TryWrite;
 use rune::alloc::{self, Vec};
+use rune::runtime::sandbox::{self, Restricted};
 use rune::runtime::{Bytes, Formatter, Mut, Value, VmError};
 use rune::{nested_try, Any, ContextError, Module};
 
@@ -417,8 +418,13 @@ impl Command {
     /// On Unix platforms this method will fail with
     /// `std::io::ErrorKind::WouldBlock` if the system process limit is reached
     /// (which includes other applications running on the system).
+    ///
+    /// If a sandbox is installed in the context, this fails with a
+    /// `SandboxError` unless the program is allowed to be executed.
     #[rune::function(keep, instance)]
-    fn spawn(&mut self) -> io::Result<Child> {
+    fn spawn(&mut self) -> Result<Child, Restricted<io::Error>> {
+        sandbox::execute(self.inner.as_std().get_program().to_string_lossy())?;
+
         Ok(Child {
             inner: self.inner.spawn()?,
         })
@@ -0,0 +1,660 @@
+//! The native `regex` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.14.0", features = ["regex"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::regex::module(true)?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use regex::Regex;
+//!
+//! fn main() {
+//!     let re = Regex::new("(?<level>INFO|WARN|ERROR) (?<message>.*)")?;
+//!
+//!     for line in ["INFO started", "ERROR disk full"] {
+//!         if let Some(caps) = re.captures(line) {
+//!             println!("{}: {}", caps["level"], caps["message"]);
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use rune::alloc;
+use rune::alloc::fmt::TryWrite;
+use rune::alloc::prelude::*;
+use rune::alloc::try_format;
+use rune::runtime::{Formatter, Function, Object, Ref, Value, VmError};
+use rune::{Any, ContextError, Module, TypeHash};
+
+/// The maximum number of compiled patterns kept in the cache used by
+/// [`Regex::new`].
+const CACHE_CAPACITY: usize = 256;
+
+/// Compiled patterns, so that constructing the same regex repeatedly, such as
+/// inside of a loop, only compiles it once.
+static CACHE: LazyLock<Mutex<HashMap<std::string::String, regex::Regex>>> =
+    LazyLock::new(Default::default);
+
+/// Regular expressions based on the [`regex`] crate.
+///
+/// [`regex`]: https://docs.rs/regex
+///
+/// # Examples
+///
+/// ```rune
+/// use regex::Regex;
+///
+/// let re = Regex::new("\\d+")?;
+///
+/// assert!(re.is_match("abc 123"));
+/// assert_eq!(re.replace_all("a1b22", |caps| `<${caps["0"]}>`), "a<1>b<22>");
+/// ```
+#[rune::module(::regex)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.ty::<Regex>()?;
+    m.function_meta(Regex::new__meta)?;
+    m.function_meta(Regex::escape__meta)?;
+    m.function_meta(Regex::as_str__meta)?;
+    m.function_meta(Regex::is_match__meta)?;
+    m.function_meta(Regex::find__meta)?;
+    m.function_meta(Regex::find_iter__meta)?;
+    m.function_meta(Regex::captures__meta)?;
+    m.function_meta(Regex::captures_iter__meta)?;
+    m.function_meta(Regex::split__meta)?;
+    m.function_meta(Regex::replace__meta)?;
+    m.function_meta(Regex::replace_all__meta)?;
+    m.function_meta(Regex::display_fmt__meta)?;
+    m.function_meta(Regex::debug_fmt__meta)?;
+    m.function_meta(Regex::clone__meta)?;
+    m.implement_trait::<Regex>(rune::item!(::std::clone::Clone))?;
+
+    m.ty::<Match>()?;
+    m.function_meta(Match::start__meta)?;
+    m.function_meta(Match::end__meta)?;
+    m.function_meta(Match::as_str__meta)?;
+    m.function_meta(Match::display_fmt__meta)?;
+    m.function_meta(Match::debug_fmt__meta)?;
+
+    m.ty::<Matches>()?;
+    m.function_meta(Matches::next__meta)?;
+    m.implement_trait::<Matches>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<CaptureMatches>()?;
+    m.function_meta(CaptureMatches::next__meta)?;
+    m.implement_trait::<CaptureMatches>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Error>()?;
+    m.function_meta(Error::display_fmt__meta)?;
+    m.function_meta(Error::debug_fmt__meta)?;
+    Ok(m)
+}
+
+/// A compiled regular expression.
+///
+/// See the [`regex` syntax] for the supported patterns.
+///
+/// [`regex` syntax]: https://docs.rs/regex/latest/regex/#syntax
+#[derive(Debug, Any)]
+#[rune(item = ::regex)]
+pub struct Regex {
+    inner: regex::Regex,
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    ///
+    /// Compiled patterns are cached, so constructing the same regular
+    /// expression again, such as inside of a loop, doesn't compile it again.
+    ///
+    /// Errors if the pattern is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("^\\d{4}-\\d{2}-\\d{2}$")?;
+    /// assert!(re.is_match("2024-03-01"));
+    ///
+    /// assert!(Regex::new("(unclosed").is_err());
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub fn new(pattern: &str) -> Result<Self, Error> {
+        let mut cache = CACHE.lock().unwrap_or_else(|error| error.into_inner());
+
+        if let Some(inner) = cache.get(pattern) {
+            return Ok(Self {
+                inner: inner.clone(),
+            });
+        }
+
+        let inner = regex::Regex::new(pattern)?;
+
+        if cache.len() >= CACHE_CAPACITY {
+            cache.clear();
+        }
+
+        cache.insert(pattern.to_owned(), inner.clone());
+        Ok(Self { inner })
+    }
+
+    /// Escape all regular expression meta characters in the given string, so
+    /// that it can be matched literally.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new(Regex::escape("1.5+"))?;
+    ///
+    /// assert!(re.is_match("1.5+"));
+    /// assert!(!re.is_match("1x55"));
+    /// ```
+    #[rune::function(keep, path = Self::escape)]
+    fn escape(string: &str) -> alloc::Result<String> {
+        String::try_from(regex::escape(string))
+    }
+
+    /// Get the pattern the regular expression was compiled from.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("\\w+")?;
+    /// assert_eq!(re.as_str(), "\\w+");
+    /// ```
+    #[rune::function(keep)]
+    fn as_str(&self) -> alloc::Result<String> {
+        String::try_from(self.inner.as_str())
+    }
+
+    /// Test if the regular expression matches anywhere in the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("ERROR|WARN")?;
+    ///
+    /// assert!(re.is_match("12:00 WARN low disk"));
+    /// assert!(!re.is_match("12:00 INFO started"));
+    /// ```
+    #[rune::function(keep)]
+    fn is_match(&self, string: &str) -> bool {
+        self.inner.is_match(string)
+    }
+
+    /// Find the leftmost match in the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("\\d+")?;
+    /// let m = re.find("abc 123 456").unwrap();
+    ///
+    /// assert_eq!(m.as_str(), "123");
+    /// assert_eq!(m.start(), 4);
+    /// assert_eq!(m.end(), 7);
+    ///
+    /// assert!(re.find("abc").is_none());
+    /// ```
+    #[rune::function(keep)]
+    fn find(&self, string: &str) -> alloc::Result<Option<Match>> {
+        let Some(m) = self.inner.find(string) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Match::new(m)?))
+    }
+
+    /// Iterate over all non-overlapping matches in the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("\\d+")?;
+    /// let numbers = re.find_iter("a1 b22 c333").map(|m| m.as_str()).collect::<Vec>();
+    ///
+    /// assert_eq!(numbers, ["1", "22", "333"]);
+    /// ```
+    #[rune::function(keep)]
+    fn find_iter(&self, string: Ref<str>) -> Matches {
+        Matches {
+            cursor: Cursor::new(&self.inner, string),
+        }
+    }
+
+    /// Match the regular expression against the given string and return its
+    /// capture groups as an object.
+    ///
+    /// Named groups are stored under their name, and every group is also
+    /// stored under its index, where `"0"` is the whole match. Groups which
+    /// didn't participate in the match are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("(?<key>\\w+)=(?<value>\\w+)?")?;
+    ///
+    /// let caps = re.captures("name=rune").unwrap();
+    /// assert_eq!(caps["key"], "name");
+    /// assert_eq!(caps["value"], "rune");
+    /// assert_eq!(caps["0"], "name=rune");
+    /// assert_eq!(caps["1"], "name");
+    ///
+    /// let caps = re.captures("empty=").unwrap();
+    /// assert_eq!(caps.get("value"), None);
+    ///
+    /// assert!(re.captures("nothing here").is_none());
+    /// ```
+    #[rune::function(keep)]
+    fn captures(&self, string: &str) -> Result<Option<Object>, VmError> {
+        let Some(caps) = self.inner.captures(string) else {
+            return Ok(None);
+        };
+
+        Ok(Some(captures_to_object(&self.inner, &caps)?))
+    }
+
+    /// Iterate over the capture groups of all non-overlapping matches in the
+    /// given string.
+    ///
+    /// Each item is an object like the one returned by [`Regex::captures`].
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("(?<key>\\w+)=(?<value>\\w+)")?;
+    ///
+    /// let pairs = re.captures_iter("a=1, b=2")
+    ///     .map(|caps| (caps["key"], caps["value"]))
+    ///     .collect::<Vec>();
+    ///
+    /// assert_eq!(pairs, [("a", "1"), ("b", "2")]);
+    /// ```
+    #[rune::function(keep)]
+    fn captures_iter(&self, string: Ref<str>) -> CaptureMatches {
+        CaptureMatches {
+            cursor: Cursor::new(&self.inner, string),
+        }
+    }
+
+    /// Split the given string by every match of the regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("\\s*,\\s*")?;
+    /// assert_eq!(re.split("a , b,c"), ["a", "b", "c"]);
+    /// ```
+    #[rune::function(keep)]
+    fn split(&self, string: &str) -> alloc::Result<Vec<String>> {
+        let mut parts = Vec::new();
+
+        for part in self.inner.split(string) {
+            parts.try_push(String::try_from(part)?)?;
+        }
+
+        Ok(parts)
+    }
+
+    /// Replace the leftmost match in the given string.
+    ///
+    /// See [`Regex::replace_all`] for the supported replacements.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("\\d+")?;
+    /// assert_eq!(re.replace("a1b2", "#"), "a#b2");
+    /// ```
+    #[rune::function(keep)]
+    fn replace(&self, string: &str, replacer: Value) -> Result<String, VmError> {
+        replacen(&self.inner, string, 1, replacer)
+    }
+
+    /// Replace all non-overlapping matches in the given string.
+    ///
+    /// The replacement is either a string, where `$name` or `${name}` expands
+    /// to the named or numbered group and `$$` is a literal `$`, or a function
+    /// which is called with the capture groups of each match as an object, like
+    /// the one returned by [`Regex::captures`], and returns the replacement.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("(?<y>\\d{4})-(?<m>\\d{2})-(?<d>\\d{2})")?;
+    ///
+    /// assert_eq!(re.replace_all("2024-03-01", "$d/$m/$y"), "01/03/2024");
+    ///
+    /// let re = Regex::new("\\w+")?;
+    /// let title = re.replace_all("hello rune world", |caps| {
+    ///     let word = caps["0"];
+    ///     `${word[0..1].to_uppercase()}${word[1..]}`
+    /// });
+    ///
+    /// assert_eq!(title, "Hello Rune World");
+    /// ```
+    #[rune::function(keep)]
+    fn replace_all(&self, string: &str, replacer: Value) -> Result<String, VmError> {
+        replacen(&self.inner, string, 0, replacer)
+    }
+
+    /// Write a display representation of the regular expression, which is its
+    /// pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("\\d+")?;
+    /// assert_eq!(`${re}`, "\\d+");
+    /// ```
+    #[rune::function(keep, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{}", self.inner)
+    }
+
+    /// Write a debug representation of the regular expression.
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+
+    /// Clone the regular expression.
+    ///
+    /// This is cheap, since the compiled pattern is shared.
+    #[rune::function(keep, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// A single match of a [`Regex`].
+///
+/// Offsets are byte offsets into the string that was searched.
+#[derive(Debug, Any)]
+#[rune(item = ::regex)]
+pub struct Match {
+    start: usize,
+    end: usize,
+    string: String,
+}
+
+impl Match {
+    fn new(m: regex::Match<'_>) -> alloc::Result<Self> {
+        Ok(Self {
+            start: m.start(),
+            end: m.end(),
+            string: String::try_from(m.as_str())?,
+        })
+    }
+
+    /// The byte offset where the match starts.
+    #[rune::function(keep)]
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset where the match ends.
+    #[rune::function(keep)]
+    fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The matched string.
+    #[rune::function(keep)]
+    fn as_str(&self) -> alloc::Result<String> {
+        self.string.try_clone()
+    }
+
+    /// Write a display representation of the match, which is the matched
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let m = Regex::new("\\d+")?.find("abc 123").unwrap();
+    /// assert_eq!(`${m}`, "123");
+    /// ```
+    #[rune::function(keep, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{}", self.string)
+    }
+
+    /// Write a debug representation of the match.
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "Match({}..{}, {:?})", self.start, self.end, self.string)
+    }
+}
+
+/// An iterator over the matches of a [`Regex`], returned by
+/// [`Regex::find_iter`].
+#[derive(Any)]
+#[rune(item = ::regex)]
+pub struct Matches {
+    cursor: Cursor,
+}
+
+impl Matches {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> alloc::Result<Option<Match>> {
+        let Some((start, end)) = self.cursor.next_match() else {
+            return Ok(None);
+        };
+
+        Ok(Some(Match {
+            start,
+            end,
+            string: String::try_from(&self.cursor.string[start..end])?,
+        }))
+    }
+}
+
+/// An iterator over the capture groups of the matches of a [`Regex`], returned
+/// by [`Regex::captures_iter`].
+#[derive(Any)]
+#[rune(item = ::regex)]
+pub struct CaptureMatches {
+    cursor: Cursor,
+}
+
+impl CaptureMatches {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Result<Option<Object>, VmError> {
+        let Some((start, _)) = self.cursor.next_match() else {
+            return Ok(None);
+        };
+
+        let Some(caps) = self.cursor.regex.captures_at(&self.cursor.string, start) else {
+            return Ok(None);
+        };
+
+        Ok(Some(captures_to_object(&self.cursor.regex, &caps)?))
+    }
+}
+
+/// The state of an iterator over the matches in a string.
+///
+/// Like the iterators in the `regex` crate, an empty match directly after the
+/// previous match is skipped.
+struct Cursor {
+    regex: regex::Regex,
+    string: Ref<str>,
+    at: usize,
+    last_end: Option<usize>,
+}
+
+impl Cursor {
+    fn new(regex: &regex::Regex, string: Ref<str>) -> Self {
+        Self {
+            regex: regex.clone(),
+            string,
+            at: 0,
+            last_end: None,
+        }
+    }
+
+    /// Find the next match, returning its start and end offsets.
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        loop {
+            if self.at > self.string.len() {
+                return None;
+            }
+
+            let m = self.regex.find_at(&self.string, self.at)?;
+            let (start, end) = (m.start(), m.end());
+
+            if start == end && self.last_end == Some(end) {
+                // Step over the next character to avoid matching the same
+                // empty string forever.
+                self.at = match self.string[end..].chars().next() {
+                    Some(c) => end + c.len_utf8(),
+                    None => self.string.len() + 1,
+                };
+
+                continue;
+            }
+
+            self.at = end;
+            self.last_end = Some(end);
+            return Some((start, end));
+        }
+    }
+}
+
+/// Convert capture groups into an object, keyed by both group name and index.
+fn captures_to_object(regex: &regex::Regex, caps: &regex::Captures<'_>) -> Result<Object, VmError> {
+    let mut object = Object::new();
+
+    for (index, name) in regex.capture_names().enumerate() {
+        let Some(m) = caps.get(index) else {
+            continue;
+        };
+
+        if let Some(name) = name {
+            object.insert(String::try_from(name)?, Value::try_from(m.as_str())?)?;
+        }
+
+        object.insert(try_format!("{index}"), Value::try_from(m.as_str())?)?;
+    }
+
+    Ok(object)
+}
+
+/// Replace up to `limit` matches in a string, where a limit of zero replaces
+/// all of them.
+fn replacen(
+    regex: &regex::Regex,
+    string: &str,
+    limit: usize,
+    replacer: Value,
+) -> Result<String, VmError> {
+    if replacer.type_hash() == Function::HASH {
+        let function = replacer.borrow_ref::<Function>()?;
+        let mut output = String::new();
+        let mut last = 0;
+
+        let limit = if limit == 0 { usize::MAX } else { limit };
+
+        for caps in regex.captures_iter(string).take(limit) {
+            let Some(m) = caps.get(0) else {
+                continue;
+            };
+
+            output.try_push_str(&string[last..m.start()])?;
+            let replacement = function.call::<String>((captures_to_object(regex, &caps)?,))?;
+            output.try_push_str(&replacement)?;
+            last = m.end();
+        }
+
+        output.try_push_str(&string[last..])?;
+        return Ok(output);
+    }
+
+    let replacement = replacer.borrow_string_ref()?;
+    let output = regex.replacen(string, limit, &*replacement);
+    Ok(String::try_from(output.as_ref())?)
+}
+
+/// An error raised when compiling a regular expression.
+///
+/// # Examples
+///
+/// ```rune
+/// use regex::Regex;
+///
+/// match Regex::new("(unclosed") {
+///     Err(error) => assert!(`${error}`.contains("unclosed group")),
+///     Ok(re) => panic!("unexpected regex {re}"),
+/// }
+/// ```
+#[derive(Debug, Any)]
+#[rune(item = ::regex)]
+pub struct Error {
+    error: regex::Error,
+}
+
+impl Error {
+    /// Write a display representation of the error.
+    #[rune::function(keep, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{}", self.error)
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.error)
+    }
+}
+
+impl From<regex::Error> for Error {
+    fn from(error: regex::Error) -> Self {
+        Self { error }
+    }
+}
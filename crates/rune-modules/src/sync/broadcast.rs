@@ -0,0 +1,388 @@
+//! A multi-producer, multi-consumer broadcast queue. Each sent value is seen by
+//! all consumers.
+
+use std::sync::Arc;
+
+use rune::alloc;
+use rune::alloc::clone::TryClone;
+use rune::alloc::fmt::TryWrite;
+use rune::runtime::{ConstValue, Formatter, FromValue, Mut, ToValue, Value, VmError};
+use rune::{item, Any, ContextError, Module};
+use tokio::sync::broadcast;
+
+/// A multi-producer, multi-consumer broadcast queue. Each sent value is seen
+/// by all consumers.
+///
+/// Since every receiver gets its own copy of each value, only values which can
+/// be used as constants can be broadcast.
+///
+/// # Tokio
+///
+/// This function is implemented using [Tokio], and requires the Tokio runtime
+/// to be in scope.
+///
+/// [Tokio]: https://tokio.rs
+#[rune::module(::sync::broadcast)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.function_meta(channel)?;
+
+    m.ty::<Sender>()?;
+    m.function_meta(Sender::send__meta)?;
+    m.function_meta(Sender::subscribe__meta)?;
+    m.function_meta(Sender::receiver_count__meta)?;
+    m.function_meta(Sender::clone__meta)?;
+    m.implement_trait::<Sender>(item!(::std::clone::Clone))?;
+
+    m.ty::<Receiver>()?;
+    m.function_meta(Receiver::recv__meta)?;
+    m.function_meta(Receiver::resubscribe__meta)?;
+
+    m.ty::<SendError>()?;
+    m.function_meta(SendError::into_inner__meta)?;
+    m.function_meta(SendError::display_fmt__meta)?;
+    m.function_meta(SendError::debug_fmt__meta)?;
+
+    m.ty::<RecvError>()?;
+    m.function_meta(RecvError::is_closed__meta)?;
+    m.function_meta(RecvError::lagged__meta)?;
+    m.function_meta(RecvError::display_fmt__meta)?;
+    m.function_meta(RecvError::debug_fmt__meta)?;
+    Ok(m)
+}
+
+/// Create a bounded, multi-producer, multi-consumer channel where each sent
+/// value is broadcasted to all active receivers.
+///
+/// All data sent on `Sender` will become available on every active `Receiver`
+/// in the same order as it was sent.
+///
+/// The `Sender` can be cloned to `send` to the same channel from multiple
+/// points in the process or it can be used concurrently from multiple tasks.
+/// Additional `Receiver` handles are created by calling `Sender::subscribe`.
+///
+/// If all `Receiver` handles are dropped, the `send` method will return a
+/// `SendError`. Similarly, if all `Sender` handles are dropped, the `recv`
+/// method will return a `RecvError`.
+///
+/// # Panics
+///
+/// This will panic if `capacity` is equal to `0` or larger than `usize::MAX /
+/// 2`.
+///
+/// # Examples
+///
+/// ```rune
+/// use sync::broadcast;
+///
+/// let (tx, rx1) = broadcast::channel(16);
+/// let rx2 = tx.subscribe();
+///
+/// tx.send(10)?;
+/// tx.send(20)?;
+///
+/// assert_eq!(rx1.recv().await?, 10);
+/// assert_eq!(rx1.recv().await?, 20);
+///
+/// assert_eq!(rx2.recv().await?, 10);
+/// assert_eq!(rx2.recv().await?, 20);
+/// ```
+#[rune::function]
+fn channel(capacity: usize) -> Result<(Sender, Receiver), VmError> {
+    if capacity == 0 {
+        return Err(VmError::panic("broadcast channel capacity cannot be zero"));
+    }
+
+    if capacity > usize::MAX >> 1 {
+        return Err(VmError::panic(
+            "broadcast channel capacity exceeded `usize::MAX / 2`",
+        ));
+    }
+
+    let (sender, receiver) = broadcast::channel(capacity);
+    Ok((Sender { inner: sender }, Receiver { inner: receiver }))
+}
+
+/// Sending-half of the [`broadcast`] channel.
+///
+/// Instances are created by the [`channel`] function.
+///
+/// [`broadcast`]: self
+#[derive(Any)]
+#[rune(item = ::sync::broadcast)]
+pub struct Sender {
+    inner: broadcast::Sender<Arc<ConstValue>>,
+}
+
+impl Sender {
+    /// Attempts to send a value to all active `Receiver` handles, returning it
+    /// back if it could not be sent.
+    ///
+    /// A successful send occurs when there is at least one active `Receiver`
+    /// handle. An unsuccessful send would be one where all associated
+    /// `Receiver` handles have already been dropped.
+    ///
+    /// On success, the number of subscribed `Receiver` handles is returned.
+    /// This does not mean that this number of receivers will see the message
+    /// as a receiver may drop or lag before receiving the message.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::broadcast;
+    ///
+    /// let (tx, rx1) = broadcast::channel(16);
+    /// let rx2 = tx.subscribe();
+    ///
+    /// assert_eq!(tx.send("hello")?, 2);
+    ///
+    /// drop(rx1);
+    /// drop(rx2);
+    ///
+    /// assert!(tx.send("world").is_err());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn send(&self, value: Value) -> Result<Result<usize, SendError>, VmError> {
+        let value = Arc::new(ConstValue::from_value(value)?);
+
+        match self.inner.send(value) {
+            Ok(count) => Ok(Ok(count)),
+            Err(broadcast::error::SendError(value)) => Ok(Err(SendError {
+                value: ConstValue::try_clone(&value)?.to_value()?,
+            })),
+        }
+    }
+
+    /// Creates a new `Receiver` handle that will receive values sent after
+    /// this call to `subscribe`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::broadcast;
+    ///
+    /// let (tx, _rx) = broadcast::channel(16);
+    ///
+    /// // Will not be seen
+    /// tx.send(10)?;
+    ///
+    /// let rx = tx.subscribe();
+    ///
+    /// tx.send(20)?;
+    ///
+    /// assert_eq!(rx.recv().await?, 20);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn subscribe(&self) -> Receiver {
+        Receiver {
+            inner: self.inner.subscribe(),
+        }
+    }
+
+    /// Returns the number of active receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::broadcast;
+    ///
+    /// let (tx, _rx1) = broadcast::channel(16);
+    /// assert_eq!(1, tx.receiver_count());
+    ///
+    /// let _rx2 = tx.subscribe();
+    /// assert_eq!(2, tx.receiver_count());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn receiver_count(&self) -> usize {
+        self.inner.receiver_count()
+    }
+
+    /// Clone the sender, allowing more than one task to send to the same
+    /// channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(16);
+    /// let tx2 = tx.clone();
+    ///
+    /// tx.send(1)?;
+    /// tx2.send(2)?;
+    ///
+    /// assert_eq!(rx.recv().await?, 1);
+    /// assert_eq!(rx.recv().await?, 2);
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Receiving-half of the [`broadcast`] channel.
+///
+/// Instances are created by the [`channel`] function, or by calling
+/// [`Sender::subscribe`].
+///
+/// [`broadcast`]: self
+#[derive(Any)]
+#[rune(item = ::sync::broadcast)]
+pub struct Receiver {
+    inner: broadcast::Receiver<Arc<ConstValue>>,
+}
+
+impl Receiver {
+    /// Receives the next value for this receiver.
+    ///
+    /// Each `Receiver` handle will receive a copy of every value sent after it
+    /// subscribed.
+    ///
+    /// # Errors
+    ///
+    /// If there are no more values to receive because every `Sender` has been
+    /// dropped, an error which reports `is_closed()` is returned.
+    ///
+    /// If the receiver fell behind so far that values have been overwritten,
+    /// an error reporting how many values were skipped through `lagged()` is
+    /// returned. The next call to `recv` returns the oldest value still
+    /// retained by the channel.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(2);
+    ///
+    /// tx.send(10)?;
+    /// tx.send(20)?;
+    /// tx.send(30)?;
+    ///
+    /// // The receiver lagged behind
+    /// let error = match rx.recv().await {
+    ///     Err(error) => error,
+    ///     Ok(value) => panic!("expected the receiver to lag, got {value}"),
+    /// };
+    /// assert_eq!(error.lagged(), Some(1));
+    ///
+    /// // At this point, we can abort or continue with lost messages
+    /// assert_eq!(20, rx.recv().await?);
+    /// assert_eq!(30, rx.recv().await?);
+    ///
+    /// drop(tx);
+    ///
+    /// match rx.recv().await {
+    ///     Err(error) => assert!(error.is_closed()),
+    ///     Ok(value) => panic!("expected the channel to be closed, got {value}"),
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::recv)]
+    async fn recv(mut this: Mut<Self>) -> Result<Result<Value, RecvError>, VmError> {
+        match this.inner.recv().await {
+            Ok(value) => Ok(Ok(ConstValue::try_clone(&value)?.to_value()?)),
+            Err(broadcast::error::RecvError::Closed) => Ok(Err(RecvError { lagged: None })),
+            Err(broadcast::error::RecvError::Lagged(n)) => Ok(Err(RecvError { lagged: Some(n) })),
+        }
+    }
+
+    /// Re-subscribes to the channel starting from the current tail element.
+    ///
+    /// The returned `Receiver` will receive values sent after the call to
+    /// `resubscribe`, not any which were pending on this receiver.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(2);
+    ///
+    /// tx.send(1)?;
+    /// let rx2 = rx.resubscribe();
+    /// tx.send(2)?;
+    ///
+    /// assert_eq!(rx2.recv().await?, 2);
+    /// assert_eq!(rx.recv().await?, 1);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn resubscribe(&self) -> Self {
+        Self {
+            inner: self.inner.resubscribe(),
+        }
+    }
+}
+
+/// Error returned by [`Sender::send`] when there are no active receivers.
+#[derive(Any)]
+#[rune(item = ::sync::broadcast)]
+pub struct SendError {
+    value: Value,
+}
+
+impl SendError {
+    /// Get the value which could not be sent.
+    #[rune::function(keep, instance)]
+    fn into_inner(self) -> Value {
+        self.value
+    }
+
+    /// Write a display representation of the error.
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "channel closed")
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "SendError {{ .. }}")
+    }
+}
+
+/// Error returned by [`Receiver::recv`].
+#[derive(Any)]
+#[rune(item = ::sync::broadcast)]
+pub struct RecvError {
+    lagged: Option<u64>,
+}
+
+impl RecvError {
+    /// Returns `true` if the error was caused by every `Sender` being dropped.
+    #[rune::function(keep, instance)]
+    fn is_closed(&self) -> bool {
+        self.lagged.is_none()
+    }
+
+    /// If the receiver lagged too far behind, returns the number of values
+    /// which were skipped.
+    #[rune::function(keep, instance)]
+    fn lagged(&self) -> Option<u64> {
+        self.lagged
+    }
+
+    /// Write a display representation of the error.
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        match self.lagged {
+            Some(n) => write!(f, "channel lagged by {n}"),
+            None => write!(f, "channel closed"),
+        }
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        match self.lagged {
+            Some(n) => write!(f, "Lagged({n})"),
+            None => write!(f, "Closed"),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! The native `sync` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.14.0", features = ["sync"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::sync::module(true)?)?;
+//! context.install(rune_modules::sync::mpsc::module(true)?)?;
+//! context.install(rune_modules::sync::oneshot::module(true)?)?;
+//! context.install(rune_modules::sync::broadcast::module(true)?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use sync::mpsc;
+//!
+//! async fn main() {
+//!     let (tx, rx) = mpsc::channel(16);
+//!     tx.send(42).await?;
+//!     dbg!(rx.recv().await);
+//! }
+//! ```
+//!
+//! ## Sending values between tasks
+//!
+//! Values which are sent over a channel or passed to a task spawned with
+//! `task::spawn` might end up on another thread. They are therefore converted
+//! into a thread-safe representation when sent, and back into a regular value
+//! when received. This supports the same values as constants do, like numbers,
+//! strings, vectors, tuples, objects and options.
+//!
+//! Channel endpoints can also be sent, which is how tasks are connected to each
+//! other. Sending an endpoint moves it, so the sending side can no longer use
+//! it. Clone a sender before sending it if it's still needed.
+
+// Documentation copied from the Tokio project under the MIT license.
+// See: https://github.com/tokio-rs/tokio/blob/master/LICENSE
+
+use rune::runtime::{ConstValue, FromValue, RuntimeError, ToValue, Value};
+use rune::{ContextError, Module, TypeHash};
+
+pub mod broadcast;
+pub mod mpsc;
+pub mod oneshot;
+
+/// Synchronization primitives for use in asynchronous contexts.
+///
+/// The channels are provided in the [`mpsc`], [`oneshot`] and [`broadcast`]
+/// modules.
+///
+/// # Tokio
+///
+/// This function is implemented using [Tokio], and requires the Tokio runtime
+/// to be in scope.
+///
+/// [Tokio]: https://tokio.rs
+#[rune::module(::sync)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    Module::from_meta(self::module__meta)
+}
+
+/// A value which can be moved between tasks.
+pub(crate) enum Transfer {
+    Const(ConstValue),
+    MpscSender(mpsc::Sender),
+    MpscReceiver(mpsc::Receiver),
+    OneshotSender(oneshot::Sender),
+    OneshotReceiver(oneshot::Receiver),
+    BroadcastSender(broadcast::Sender),
+    BroadcastReceiver(broadcast::Receiver),
+}
+
+impl FromValue for Transfer {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        Ok(match value.type_hash() {
+            mpsc::Sender::HASH => Self::MpscSender(value.downcast()?),
+            mpsc::Receiver::HASH => Self::MpscReceiver(value.downcast()?),
+            oneshot::Sender::HASH => Self::OneshotSender(value.downcast()?),
+            oneshot::Receiver::HASH => Self::OneshotReceiver(value.downcast()?),
+            broadcast::Sender::HASH => Self::BroadcastSender(value.downcast()?),
+            broadcast::Receiver::HASH => Self::BroadcastReceiver(value.downcast()?),
+            _ => Self::Const(ConstValue::from_value(value)?),
+        })
+    }
+}
+
+impl ToValue for Transfer {
+    fn to_value(self) -> Result<Value, RuntimeError> {
+        match self {
+            Self::Const(value) => value.to_value(),
+            Self::MpscSender(sender) => sender.to_value(),
+            Self::MpscReceiver(receiver) => receiver.to_value(),
+            Self::OneshotSender(sender) => sender.to_value(),
+            Self::OneshotReceiver(receiver) => receiver.to_value(),
+            Self::BroadcastSender(sender) => sender.to_value(),
+            Self::BroadcastReceiver(receiver) => receiver.to_value(),
+        }
+    }
+}
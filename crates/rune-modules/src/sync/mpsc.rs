@@ -0,0 +1,323 @@
+//! A multi-producer, single-consumer queue for sending values between
+//! asynchronous tasks.
+
+use rune::alloc;
+use rune::alloc::fmt::TryWrite;
+use rune::runtime::{Formatter, FromValue, Mut, Ref, ToValue, Value, VmError};
+use rune::{item, Any, ContextError, Module};
+use tokio::sync::mpsc;
+
+use super::Transfer;
+
+/// A multi-producer, single-consumer queue for sending values between
+/// asynchronous tasks.
+///
+/// # Tokio
+///
+/// This function is implemented using [Tokio], and requires the Tokio runtime
+/// to be in scope.
+///
+/// [Tokio]: https://tokio.rs
+#[rune::module(::sync::mpsc)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.function_meta(channel)?;
+
+    m.ty::<Sender>()?;
+    m.function_meta(Sender::send__meta)?;
+    m.function_meta(Sender::is_closed__meta)?;
+    m.function_meta(Sender::capacity__meta)?;
+    m.function_meta(Sender::clone__meta)?;
+    m.implement_trait::<Sender>(item!(::std::clone::Clone))?;
+
+    m.ty::<Receiver>()?;
+    m.function_meta(Receiver::recv__meta)?;
+    m.function_meta(Receiver::close__meta)?;
+
+    m.ty::<SendError>()?;
+    m.function_meta(SendError::into_inner__meta)?;
+    m.function_meta(SendError::display_fmt__meta)?;
+    m.function_meta(SendError::debug_fmt__meta)?;
+    Ok(m)
+}
+
+/// Creates a bounded mpsc channel for communicating between asynchronous tasks
+/// with backpressure.
+///
+/// The channel will buffer up to the provided number of messages. Once the
+/// buffer is full, attempts to send new messages will wait until a message is
+/// received from the channel.
+///
+/// All data sent on `Sender` will become available on `Receiver` in the same
+/// order as it was sent.
+///
+/// The `Sender` can be cloned to `send` to the same channel from multiple code
+/// locations. Only one `Receiver` is supported.
+///
+/// If the `Receiver` is disconnected while trying to `send`, the `send` method
+/// will return a `SendError`. Similarly, if all `Sender`s are disconnected
+/// while trying to `recv`, the `recv` method will return `None`.
+///
+/// # Panics
+///
+/// Panics if the buffer capacity is 0.
+///
+/// # Examples
+///
+/// ```rune
+/// use sync::mpsc;
+///
+/// let (tx, rx) = mpsc::channel(100);
+///
+/// for i in 0..10 {
+///     tx.send(i).await?;
+/// }
+///
+/// drop(tx);
+///
+/// let sum = 0;
+///
+/// while let Some(i) = rx.recv().await {
+///     sum += i;
+/// }
+///
+/// assert_eq!(sum, 45);
+/// ```
+#[rune::function]
+fn channel(buffer: usize) -> Result<(Sender, Receiver), VmError> {
+    if buffer == 0 {
+        return Err(VmError::panic("mpsc bounded channel requires buffer > 0"));
+    }
+
+    let (sender, receiver) = mpsc::channel(buffer);
+    Ok((Sender { inner: sender }, Receiver { inner: receiver }))
+}
+
+/// Sends values to the associated `Receiver`.
+///
+/// Instances are created by the [`channel`] function.
+#[derive(Any)]
+#[rune(item = ::sync::mpsc)]
+pub struct Sender {
+    inner: mpsc::Sender<Transfer>,
+}
+
+impl Sender {
+    /// Sends a value, waiting until there is capacity.
+    ///
+    /// A successful send occurs when it is determined that the other end of the
+    /// channel has not hung up already. An unsuccessful send would be one where
+    /// the corresponding receiver has already been closed. Note that a return
+    /// value of `Err` means that the data will never be received, but a return
+    /// value of `Ok` does not mean that the data will be received. It is
+    /// possible for the corresponding receiver to hang up immediately after
+    /// this function returns `Ok`.
+    ///
+    /// # Errors
+    ///
+    /// If the receive half of the channel is closed, either due to `close`
+    /// being called or the `Receiver` handle dropping, the function returns an
+    /// error. The error includes the value passed to `send`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(1);
+    ///
+    /// tx.send("hello").await?;
+    /// assert_eq!(rx.recv().await, Some("hello"));
+    ///
+    /// drop(rx);
+    ///
+    /// let error = match tx.send("world").await {
+    ///     Err(error) => error,
+    ///     Ok(()) => panic!("the receiver was dropped"),
+    /// };
+    /// assert_eq!(error.into_inner(), "world");
+    /// ```
+    #[rune::function(keep, instance, path = Self::send)]
+    async fn send(this: Ref<Self>, value: Value) -> Result<Result<(), SendError>, VmError> {
+        let inner = this.inner.clone();
+        drop(this);
+
+        match inner.send(Transfer::from_value(value)?).await {
+            Ok(()) => Ok(Ok(())),
+            Err(mpsc::error::SendError(value)) => Ok(Err(SendError {
+                value: value.to_value()?,
+            })),
+        }
+    }
+
+    /// Checks if the channel has been closed. This happens when the `Receiver`
+    /// is dropped, or when the `close` method is called on it.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(42);
+    /// assert!(!tx.is_closed());
+    ///
+    /// drop(rx);
+    ///
+    /// assert!(tx.is_closed());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// Returns the current capacity of the channel.
+    ///
+    /// The capacity goes down when sending a value and goes up when receiving
+    /// a value.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(5);
+    /// assert_eq!(tx.capacity(), 5);
+    ///
+    /// tx.send(()).await?;
+    /// assert_eq!(tx.capacity(), 4);
+    ///
+    /// rx.recv().await;
+    /// assert_eq!(tx.capacity(), 5);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Clone the sender, allowing more than one task to send to the same
+    /// channel.
+    ///
+    /// The channel is closed once every sender has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(2);
+    /// let tx2 = tx.clone();
+    ///
+    /// tx.send(1).await?;
+    /// tx2.send(2).await?;
+    ///
+    /// assert_eq!(rx.recv().await, Some(1));
+    /// assert_eq!(rx.recv().await, Some(2));
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// Receives values from the associated `Sender`.
+///
+/// Instances are created by the [`channel`] function.
+#[derive(Any)]
+#[rune(item = ::sync::mpsc)]
+pub struct Receiver {
+    inner: mpsc::Receiver<Transfer>,
+}
+
+impl Receiver {
+    /// Receives the next value for this receiver.
+    ///
+    /// This method returns `None` if the channel has been closed and there are
+    /// no remaining messages in the channel's buffer. The channel is closed
+    /// when all senders have been dropped, or when `close` is called.
+    ///
+    /// If there are no messages in the channel's buffer, but the channel has
+    /// not yet been closed, this method will sleep until a message is sent or
+    /// the channel is closed.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If `recv` is used as the event in a
+    /// `select` statement and some other branch completes first, it is
+    /// guaranteed that no messages were received on this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(100);
+    ///
+    /// tx.send("hello").await?;
+    /// drop(tx);
+    ///
+    /// assert_eq!(Some("hello"), rx.recv().await);
+    /// assert_eq!(None, rx.recv().await);
+    /// ```
+    #[rune::function(keep, instance, path = Self::recv)]
+    async fn recv(mut this: Mut<Self>) -> Result<Option<Value>, VmError> {
+        match this.inner.recv().await {
+            Some(value) => Ok(Some(value.to_value()?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Closes the receiving half of a channel without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(20);
+    ///
+    /// tx.send("hello").await?;
+    /// rx.close();
+    ///
+    /// assert!(tx.send("world").await.is_err());
+    /// assert_eq!(rx.recv().await, Some("hello"));
+    /// assert_eq!(rx.recv().await, None);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn close(&mut self) {
+        self.inner.close();
+    }
+}
+
+/// Error returned by [`Sender::send`] when the receiving half of the channel
+/// has been closed.
+#[derive(Any)]
+#[rune(item = ::sync::mpsc)]
+pub struct SendError {
+    value: Value,
+}
+
+impl SendError {
+    /// Get the value which could not be sent.
+    #[rune::function(keep, instance)]
+    fn into_inner(self) -> Value {
+        self.value
+    }
+
+    /// Write a display representation of the error.
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "channel closed")
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "SendError {{ .. }}")
+    }
+}
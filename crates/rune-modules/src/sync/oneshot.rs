@@ -0,0 +1,212 @@
+//! A one-shot channel is used for sending a single message between
+//! asynchronous tasks.
+
+use rune::alloc;
+use rune::alloc::fmt::TryWrite;
+use rune::runtime::{Formatter, FromValue, ToValue, Value, VmError};
+use rune::{Any, ContextError, Module};
+use tokio::sync::oneshot;
+
+use super::Transfer;
+
+/// A one-shot channel is used for sending a single message between
+/// asynchronous tasks.
+///
+/// # Tokio
+///
+/// This function is implemented using [Tokio], and requires the Tokio runtime
+/// to be in scope.
+///
+/// [Tokio]: https://tokio.rs
+#[rune::module(::sync::oneshot)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.function_meta(channel)?;
+
+    m.ty::<Sender>()?;
+    m.function_meta(Sender::send__meta)?;
+    m.function_meta(Sender::is_closed__meta)?;
+
+    m.ty::<Receiver>()?;
+    m.function_meta(Receiver::close__meta)?;
+    m.function_meta(Receiver::into_future__meta)?;
+
+    m.ty::<RecvError>()?;
+    m.function_meta(RecvError::display_fmt__meta)?;
+    m.function_meta(RecvError::debug_fmt__meta)?;
+    Ok(m)
+}
+
+/// Creates a new one-shot channel for sending single values across
+/// asynchronous tasks.
+///
+/// The function returns separate "send" and "receive" handles. The `Sender`
+/// handle is used by the producer to send the value. The `Receiver` handle is
+/// used by the consumer to receive the value by awaiting it.
+///
+/// Each handle can be used on separate tasks.
+///
+/// # Examples
+///
+/// ```rune
+/// use sync::oneshot;
+///
+/// let (tx, rx) = oneshot::channel();
+///
+/// if let Err(_) = tx.send(3) {
+///     panic!("the receiver dropped");
+/// }
+///
+/// assert_eq!(rx.await?, 3);
+/// ```
+#[rune::function]
+fn channel() -> (Sender, Receiver) {
+    let (sender, receiver) = oneshot::channel();
+    (Sender { inner: sender }, Receiver { inner: receiver })
+}
+
+/// Sends a value to the associated `Receiver`.
+///
+/// Instances are created by the [`channel`] function.
+#[derive(Any)]
+#[rune(item = ::sync::oneshot)]
+pub struct Sender {
+    inner: oneshot::Sender<Transfer>,
+}
+
+impl Sender {
+    /// Attempts to send a value on this channel, returning it back if it could
+    /// not be sent.
+    ///
+    /// This method consumes `self` as only one value may ever be sent on a
+    /// one-shot channel. It is not marked async because sending a message to a
+    /// one-shot channel never requires any form of waiting.
+    ///
+    /// A successful send occurs when it is determined that the other end of the
+    /// channel has not hung up already. An unsuccessful send would be one where
+    /// the corresponding receiver has already been deallocated. Note that a
+    /// return value of `Err` means that the data will never be received, but a
+    /// return value of `Ok` does not mean that the data will be received. It is
+    /// possible for the corresponding receiver to hang up immediately after
+    /// this function returns `Ok`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// drop(rx);
+    ///
+    /// assert_eq!(tx.send("hello"), Err("hello"));
+    /// ```
+    #[rune::function(keep, instance)]
+    fn send(self, value: Value) -> Result<Result<(), Value>, VmError> {
+        match self.inner.send(Transfer::from_value(value)?) {
+            Ok(()) => Ok(Ok(())),
+            Err(value) => Ok(Err(value.to_value()?)),
+        }
+    }
+
+    /// Returns `true` if the associated `Receiver` handle has been dropped.
+    ///
+    /// A `Receiver` is closed by either calling `close` explicitly or the
+    /// `Receiver` value is dropped.
+    ///
+    /// If `true` is returned, a call to `send` will always result in an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// assert!(!tx.is_closed());
+    ///
+    /// drop(rx);
+    ///
+    /// assert!(tx.is_closed());
+    /// assert!(tx.send("no receive").is_err());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+/// Receives a value from the associated `Sender`.
+///
+/// Instances are created by the [`channel`] function. The value is received by
+/// awaiting the receiver, which produces an error if the sender is dropped
+/// without sending a value.
+#[derive(Any)]
+#[rune(item = ::sync::oneshot)]
+pub struct Receiver {
+    inner: oneshot::Receiver<Transfer>,
+}
+
+impl Receiver {
+    /// Prevents the associated `Sender` handle from sending a value.
+    ///
+    /// Any `send` operation which happens after calling `close` is guaranteed
+    /// to fail. After calling `close`, the receiver should be awaited to
+    /// receive a value which was sent before the call to `close` completed.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    ///
+    /// rx.close();
+    ///
+    /// assert!(tx.send("never received").is_err());
+    /// assert!(rx.await.is_err());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn close(&mut self) {
+        self.inner.close();
+    }
+
+    /// Wait for the value sent by the associated `Sender`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// drop(tx);
+    ///
+    /// assert!(rx.await.is_err());
+    /// ```
+    #[rune::function(keep, instance, protocol = INTO_FUTURE)]
+    async fn into_future(self) -> Result<Result<Value, RecvError>, VmError> {
+        match self.inner.await {
+            Ok(value) => Ok(Ok(value.to_value()?)),
+            Err(..) => Ok(Err(RecvError)),
+        }
+    }
+}
+
+/// Error returned when awaiting a [`Receiver`] whose `Sender` was dropped
+/// without sending a value.
+#[derive(Any)]
+#[rune(item = ::sync::oneshot)]
+pub struct RecvError;
+
+impl RecvError {
+    /// Write a display representation of the error.
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "channel closed")
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "RecvError(())")
+    }
+}
@@ -0,0 +1,383 @@
+//! The native `task` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.14.0", features = ["task"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::task::module(true)?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! async fn fetch(n) {
+//!     n * 2
+//! }
+//!
+//! async fn main() {
+//!     let handle = task::spawn(fetch, 21);
+//!     dbg!(handle.await?);
+//! }
+//! ```
+
+// Documentation copied from the Tokio project under the MIT license.
+// See: https://github.com/tokio-rs/tokio/blob/master/LICENSE
+
+use rune::alloc;
+use rune::alloc::fmt::TryWrite;
+use rune::alloc::prelude::*;
+use rune::runtime::interrupt;
+use rune::runtime::{
+    Address, Formatter, FromValue, GuardedArgs, Memory, Output, Stack, SyncFunction, ToValue,
+    Value, VmError,
+};
+use rune::{docstring, Any, ContextError, Module};
+use tokio::runtime::Handle;
+use tokio::task;
+
+use crate::sync::Transfer;
+
+/// Asynchronous green-threads.
+///
+/// # Tokio
+///
+/// This function is implemented using [Tokio], and requires the Tokio runtime
+/// to be in scope.
+///
+/// [Tokio]: https://tokio.rs
+#[rune::module(::task)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.raw_function("spawn", spawn).build()?.docs(docstring! {
+        /// Spawns a new asynchronous task, returning a `JoinHandle` for it.
+        ///
+        /// The first argument is the function to run, and any remaining
+        /// arguments are passed to it when the task starts. If the function is
+        /// `async` the task runs until the returned future completes.
+        ///
+        /// The task runs detached on the host executor, potentially on another
+        /// thread, and keeps running even if the `JoinHandle` is dropped. If the
+        /// execution which spawned it has a deadline or a cancellation token,
+        /// the task is interrupted under the same conditions.
+        ///
+        /// Since the task might run on another thread, the function and its
+        /// arguments must be possible to send between threads:
+        ///
+        /// * The function must be a function item or a closure which only
+        ///   captures constant values.
+        /// * Arguments and the value produced by the task must either be
+        ///   constant values, like numbers, strings, vectors, tuples and
+        ///   objects, or channel endpoints from the `sync` module. Channel
+        ///   endpoints are moved into the task.
+        ///
+        /// # Examples
+        ///
+        /// ```rune
+        /// async fn square(n) {
+        ///     n * n
+        /// }
+        ///
+        /// let handles = [];
+        ///
+        /// for n in 0..4 {
+        ///     handles.push(task::spawn(square, n));
+        /// }
+        ///
+        /// let results = [];
+        ///
+        /// for handle in handles {
+        ///     results.push(handle.await?);
+        /// }
+        ///
+        /// assert_eq!(results, [0, 1, 4, 9]);
+        /// ```
+        ///
+        /// Fanning in results from many tasks over a channel:
+        ///
+        /// ```rune
+        /// use sync::mpsc;
+        ///
+        /// async fn worker(id, tx) {
+        ///     tx.send(id * 10).await?;
+        /// }
+        ///
+        /// let (tx, rx) = mpsc::channel(8);
+        ///
+        /// for id in 0..4 {
+        ///     task::spawn(worker, id, tx.clone());
+        /// }
+        ///
+        /// drop(tx);
+        ///
+        /// let sum = 0;
+        ///
+        /// while let Some(value) = rx.recv().await {
+        ///     sum += value;
+        /// }
+        ///
+        /// assert_eq!(sum, 60);
+        /// ```
+    })?;
+
+    m.function_meta(yield_now)?;
+
+    m.ty::<JoinHandle>()?;
+    m.function_meta(JoinHandle::abort__meta)?;
+    m.function_meta(JoinHandle::is_finished__meta)?;
+    m.function_meta(JoinHandle::into_future__meta)?;
+
+    m.ty::<JoinError>()?;
+    m.function_meta(JoinError::is_cancelled__meta)?;
+    m.function_meta(JoinError::is_panic__meta)?;
+    m.function_meta(JoinError::display_fmt__meta)?;
+    m.function_meta(JoinError::debug_fmt__meta)?;
+    Ok(m)
+}
+
+fn spawn(memory: &mut dyn Memory, addr: Address, args: usize, out: Output) -> Result<(), VmError> {
+    let Some((function, args)) = memory.slice_at(addr, args)?.split_first() else {
+        return Err(VmError::panic("spawn requires a function to run"));
+    };
+
+    let function = SyncFunction::from_value(function.clone())?;
+
+    let mut transfer = Vec::try_with_capacity(args.len())?;
+
+    for value in args {
+        transfer.try_push(Transfer::from_value(value.clone())?)?;
+    }
+
+    let Ok(handle) = Handle::try_current() else {
+        return Err(VmError::panic(
+            "spawn must be called from the context of a Tokio runtime",
+        ));
+    };
+
+    let interrupt = interrupt::current();
+
+    let inner = handle.spawn(async move {
+        let future = function.async_send_call::<Transfer>(TransferArgs(transfer));
+
+        match interrupt.scope(future).await {
+            Ok(value) => Ok(value),
+            Err(error) => Err(error.try_to_string().unwrap_or_default()),
+        }
+    });
+
+    memory.store(out, Value::new(JoinHandle { inner })?)?;
+    Ok(())
+}
+
+/// Yields execution back to the runtime.
+///
+/// A task yields by awaiting on `yield_now()`, and may resume when that future
+/// completes. This allows other spawned tasks to make progress.
+///
+/// # Examples
+///
+/// ```rune
+/// task::yield_now().await;
+/// ```
+#[rune::function]
+async fn yield_now() {
+    task::yield_now().await
+}
+
+/// An owned permission to join on a task (await its termination).
+///
+/// This is returned by `task::spawn`. Awaiting the handle waits for the task to
+/// complete and produces the value it returned, or a `JoinError` if it failed
+/// or was aborted.
+///
+/// Dropping a handle detaches the task, which keeps running in the
+/// background.
+#[derive(Any)]
+#[rune(item = ::task)]
+pub struct JoinHandle {
+    inner: task::JoinHandle<Result<Transfer, String>>,
+}
+
+impl JoinHandle {
+    /// Abort the task associated with the handle.
+    ///
+    /// Awaiting a cancelled task might complete as usual if the task was
+    /// already completed at the time it was cancelled, but most likely it will
+    /// fail with a cancelled `JoinError`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// async fn forever() {
+    ///     loop {
+    ///         task::yield_now().await;
+    ///     }
+    /// }
+    ///
+    /// let handle = task::spawn(forever);
+    /// handle.abort();
+    ///
+    /// let error = match handle.await {
+    ///     Err(error) => error,
+    ///     Ok(..) => panic!("the task should never finish"),
+    /// };
+    /// assert!(error.is_cancelled());
+    /// ```
+    #[rune::function(keep, instance)]
+    fn abort(&self) {
+        self.inner.abort();
+    }
+
+    /// Checks if the task associated with this `JoinHandle` has finished.
+    ///
+    /// Please note that this method can return `false` even if `abort` has been
+    /// called on the task. This is because the cancellation process may take
+    /// some time, and this method does not return `true` until it has
+    /// completed.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// fn work() {
+    ///     42
+    /// }
+    ///
+    /// let handle = task::spawn(work);
+    ///
+    /// while !handle.is_finished() {
+    ///     task::yield_now().await;
+    /// }
+    ///
+    /// assert_eq!(handle.await?, 42);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    /// Wait for the task to complete, producing the value it returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// fn fails() {
+    ///     panic!("boom");
+    /// }
+    ///
+    /// let error = match task::spawn(fails).await {
+    ///     Err(error) => error,
+    ///     Ok(..) => panic!("the task should fail"),
+    /// };
+    /// assert!(error.is_panic());
+    /// ```
+    #[rune::function(keep, instance, protocol = INTO_FUTURE)]
+    async fn into_future(self) -> Result<Result<Value, JoinError>, VmError> {
+        let error = match self.inner.await {
+            Ok(Ok(value)) => return Ok(Ok(value.to_value()?)),
+            Ok(Err(message)) => JoinError {
+                kind: JoinErrorKind::Panic(message),
+            },
+            Err(error) if error.is_cancelled() => JoinError {
+                kind: JoinErrorKind::Cancelled,
+            },
+            Err(error) => JoinError {
+                kind: JoinErrorKind::Panic(error.try_to_string()?),
+            },
+        };
+
+        Ok(Err(error))
+    }
+}
+
+enum JoinErrorKind {
+    Cancelled,
+    Panic(String),
+}
+
+/// Task failed to execute to completion.
+///
+/// This is produced when awaiting a `JoinHandle` whose task was aborted, or
+/// which raised an error.
+#[derive(Any)]
+#[rune(item = ::task)]
+pub struct JoinError {
+    kind: JoinErrorKind,
+}
+
+impl JoinError {
+    /// Returns true if the error was caused by the task being cancelled.
+    #[rune::function(keep, instance)]
+    fn is_cancelled(&self) -> bool {
+        matches!(self.kind, JoinErrorKind::Cancelled)
+    }
+
+    /// Returns true if the error was caused by the task raising an error.
+    #[rune::function(keep, instance)]
+    fn is_panic(&self) -> bool {
+        matches!(self.kind, JoinErrorKind::Panic(..))
+    }
+
+    /// Write a display representation of the error.
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        match &self.kind {
+            JoinErrorKind::Cancelled => write!(f, "task was cancelled"),
+            JoinErrorKind::Panic(message) => write!(f, "task panicked: {message}"),
+        }
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        match &self.kind {
+            JoinErrorKind::Cancelled => write!(f, "JoinError::Cancelled"),
+            JoinErrorKind::Panic(message) => write!(f, "JoinError::Panic({message:?})"),
+        }
+    }
+}
+
+/// Arguments which are sent to a spawned task, and converted into values once
+/// the task starts.
+struct TransferArgs(Vec<Transfer>);
+
+impl TransferArgs {
+    fn into_values(self) -> Result<Vec<Value>, VmError> {
+        let mut values = Vec::try_with_capacity(self.0.len())?;
+
+        for value in self.0 {
+            values.try_push(value.to_value()?)?;
+        }
+
+        Ok(values)
+    }
+}
+
+impl GuardedArgs for TransferArgs {
+    type Guard = ();
+
+    #[inline]
+    unsafe fn guarded_into_stack(self, stack: &mut Stack) -> Result<Self::Guard, VmError> {
+        self.into_values()?.guarded_into_stack(stack)
+    }
+
+    #[inline]
+    unsafe fn guarded_into_vec(self) -> Result<(Vec<Value>, Self::Guard), VmError> {
+        Ok((self.into_values()?, ()))
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        self.0.len()
+    }
+}
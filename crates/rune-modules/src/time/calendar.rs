@@ -0,0 +1,1838 @@
+use core::cmp::Ordering;
+use core::hash::Hash;
+
+use rune::alloc;
+use rune::alloc::fmt::TryWrite;
+use rune::alloc::String;
+use rune::runtime::{Formatter, Hasher, VmError};
+use rune::{nested_try, Any, ToConstValue};
+
+use ::time::format_description::well_known::Rfc3339;
+use ::time::format_description::{self, OwnedFormatItem};
+use ::time::{Month, PrimitiveDateTime};
+
+use super::Duration;
+
+const NANOS_PER_MILLI: i128 = 1_000_000;
+
+/// Error raised when constructing, parsing or formatting calendar types.
+///
+/// # Examples
+///
+/// ```rune
+/// use time::Date;
+///
+/// match Date::new(2024, 2, 30) {
+///     Err(error) => assert!(`${error}`.contains("day")),
+///     Ok(date) => panic!("unexpected date {date}"),
+/// }
+/// ```
+#[derive(Debug, Any)]
+#[rune(item = ::time)]
+pub struct Error {
+    error: ::time::Error,
+}
+
+impl Error {
+    /// Write a display representation of the error.
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{}", self.error)
+    }
+
+    /// Write a debug representation of the error.
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.error)
+    }
+}
+
+impl<E> From<E> for Error
+where
+    ::time::Error: From<E>,
+{
+    #[inline]
+    fn from(error: E) -> Self {
+        Self {
+            error: ::time::Error::from(error),
+        }
+    }
+}
+
+/// Parse a strftime-style format description such as `%Y-%m-%d`.
+fn strftime(format: &str) -> Result<OwnedFormatItem, Error> {
+    Ok(format_description::parse_strftime_owned(format)?)
+}
+
+/// Convert a [`Duration`] into a signed duration used by calendar arithmetic.
+fn signed(duration: &Duration, what: &str) -> Result<::time::Duration, VmError> {
+    match ::time::Duration::try_from(duration.into_std()) {
+        Ok(duration) => Ok(duration),
+        Err(..) => Err(VmError::panic(format!("overflow when {what}"))),
+    }
+}
+
+/// A date and time with an offset from UTC.
+///
+/// A `DateTime` identifies a single instant, so two values with different
+/// offsets compare equal if they refer to the same point in time.
+///
+/// # Examples
+///
+/// ```rune
+/// use time::{DateTime, Duration, UtcOffset};
+///
+/// let start = DateTime::parse_rfc3339("2024-03-01T12:30:00+02:00")?;
+/// let end = start + Duration::from_secs(90 * 60);
+///
+/// assert_eq!(end.to_rfc3339()?, "2024-03-01T14:00:00+02:00");
+/// assert_eq!(end.to_offset(UtcOffset::UTC).hour(), 12);
+/// assert_eq!(end.duration_since(start), Duration::from_secs(90 * 60));
+/// ```
+#[derive(Debug, Clone, Copy, Any, ToConstValue)]
+#[rune(item = ::time)]
+pub struct DateTime {
+    #[const_value(with = self::const_date_time)]
+    inner: ::time::OffsetDateTime,
+}
+
+impl DateTime {
+    /// The Unix epoch, `1970-01-01T00:00:00Z`.
+    pub const UNIX_EPOCH: Self = Self {
+        inner: ::time::OffsetDateTime::UNIX_EPOCH,
+    };
+
+    /// Converts [`DateTime`] into a [`time::OffsetDateTime`].
+    ///
+    /// [`time::OffsetDateTime`]: ::time::OffsetDateTime
+    pub fn into_offset_date_time(self) -> ::time::OffsetDateTime {
+        self.inner
+    }
+
+    /// Creates a [`DateTime`] from a [`time::OffsetDateTime`].
+    ///
+    /// [`time::OffsetDateTime`]: ::time::OffsetDateTime
+    pub fn from_offset_date_time(inner: ::time::OffsetDateTime) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the current date and time in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let now = DateTime::now_utc();
+    /// assert!(now.offset().is_utc());
+    /// assert!(now > DateTime::UNIX_EPOCH);
+    /// ```
+    #[rune::function(keep, path = Self::now_utc)]
+    pub fn now_utc() -> Self {
+        Self {
+            inner: ::time::OffsetDateTime::now_utc(),
+        }
+    }
+
+    /// Construct a date and time from its parts.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Date, DateTime, Time, UtcOffset};
+    ///
+    /// let date = Date::new(2024, 3, 1)?;
+    /// let time = Time::new(12, 30, 0)?;
+    /// let offset = UtcOffset::from_hms(2, 0, 0)?;
+    ///
+    /// let dt = DateTime::new(date, time, offset);
+    /// assert_eq!(dt.to_rfc3339()?, "2024-03-01T12:30:00+02:00");
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub fn new(date: &Date, time: &Time, offset: &UtcOffset) -> Self {
+        Self {
+            inner: PrimitiveDateTime::new(date.inner, time.inner).assume_offset(offset.inner),
+        }
+    }
+
+    /// Construct a date and time in UTC from a Unix timestamp in seconds.
+    ///
+    /// Errors if the timestamp is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_unix_timestamp(1_700_000_000)?;
+    /// assert_eq!(dt.to_rfc3339()?, "2023-11-14T22:13:20Z");
+    /// assert_eq!(dt.unix_timestamp(), 1_700_000_000);
+    /// ```
+    #[rune::function(keep, path = Self::from_unix_timestamp)]
+    pub fn from_unix_timestamp(timestamp: i64) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ::time::OffsetDateTime::from_unix_timestamp(timestamp)?,
+        })
+    }
+
+    /// Construct a date and time in UTC from a Unix timestamp in milliseconds.
+    ///
+    /// Errors if the timestamp is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::from_unix_timestamp_millis(1_700_000_000_250)?;
+    /// assert_eq!(dt.to_rfc3339()?, "2023-11-14T22:13:20.25Z");
+    /// assert_eq!(dt.unix_timestamp_millis(), 1_700_000_000_250);
+    /// ```
+    #[rune::function(keep, path = Self::from_unix_timestamp_millis)]
+    pub fn from_unix_timestamp_millis(timestamp: i64) -> Result<Self, Error> {
+        let nanos = i128::from(timestamp) * NANOS_PER_MILLI;
+
+        Ok(Self {
+            inner: ::time::OffsetDateTime::from_unix_timestamp_nanos(nanos)?,
+        })
+    }
+
+    /// Get the Unix timestamp of the date and time in whole seconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// assert_eq!(DateTime::UNIX_EPOCH.unix_timestamp(), 0);
+    /// ```
+    #[rune::function(keep)]
+    pub fn unix_timestamp(&self) -> i64 {
+        self.inner.unix_timestamp()
+    }
+
+    /// Get the Unix timestamp of the date and time in whole milliseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let dt = DateTime::UNIX_EPOCH + Duration::from_millis(1500);
+    /// assert_eq!(dt.unix_timestamp_millis(), 1500);
+    /// ```
+    #[rune::function(keep)]
+    pub fn unix_timestamp_millis(&self) -> i64 {
+        self.inner
+            .unix_timestamp_nanos()
+            .div_euclid(NANOS_PER_MILLI) as i64
+    }
+
+    /// Parse a date and time in the [RFC 3339] format.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("1985-04-12T23:20:50.52Z")?;
+    /// assert_eq!(dt.year(), 1985);
+    /// assert_eq!(dt.nanosecond(), 520_000_000);
+    ///
+    /// assert!(DateTime::parse_rfc3339("1985-04-12").is_err());
+    /// ```
+    #[rune::function(keep, path = Self::parse_rfc3339)]
+    pub fn parse_rfc3339(string: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ::time::OffsetDateTime::parse(string, &Rfc3339)?,
+        })
+    }
+
+    /// Format the date and time in the [RFC 3339] format.
+    ///
+    /// Errors if the date and time can't be represented, such as when the
+    /// year is outside of `0..=9999`.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// assert_eq!(DateTime::UNIX_EPOCH.to_rfc3339()?, "1970-01-01T00:00:00Z");
+    /// ```
+    #[rune::function(keep)]
+    pub fn to_rfc3339(&self) -> alloc::Result<Result<String, Error>> {
+        let string = nested_try!(self.inner.format(&Rfc3339).map_err(Error::from));
+        Ok(Ok(String::try_from(string)?))
+    }
+
+    /// Parse a date and time using a strftime-style format such as
+    /// `%Y-%m-%d %H:%M:%S %z`.
+    ///
+    /// If the format doesn't include an offset, the date and time is assumed to
+    /// be in UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse("2024-03-01 12:30:00 +0200", "%Y-%m-%d %H:%M:%S %z")?;
+    /// assert_eq!(dt.to_rfc3339()?, "2024-03-01T12:30:00+02:00");
+    ///
+    /// let dt = DateTime::parse("01/03/2024 12:30", "%d/%m/%Y %H:%M")?;
+    /// assert_eq!(dt.to_rfc3339()?, "2024-03-01T12:30:00Z");
+    /// ```
+    #[rune::function(keep, path = Self::parse)]
+    pub fn parse(string: &str, format: &str) -> Result<Self, Error> {
+        use ::time::error::{Parse, TryFromParsed};
+
+        let format = strftime(format)?;
+
+        let inner = match ::time::OffsetDateTime::parse(string, &format) {
+            Ok(inner) => inner,
+            Err(Parse::TryFromParsed(TryFromParsed::InsufficientInformation)) => {
+                PrimitiveDateTime::parse(string, &format)?.assume_utc()
+            }
+            Err(error) => return Err(Error::from(error)),
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Format the date and time using a strftime-style format such as
+    /// `%Y-%m-%d %H:%M:%S`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:00+02:00")?;
+    /// assert_eq!(dt.format("%a, %d %b %Y %H:%M:%S %z")?, "Fri, 01 Mar 2024 12:30:00 +0200");
+    ///
+    /// assert!(dt.format("%Q").is_err());
+    /// ```
+    #[rune::function(keep)]
+    pub fn format(&self, format: &str) -> alloc::Result<Result<String, Error>> {
+        let format = nested_try!(strftime(format));
+        let string = nested_try!(self.inner.format(&format).map_err(Error::from));
+        Ok(Ok(String::try_from(string)?))
+    }
+
+    /// Get the date part of the date and time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Date, DateTime};
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:00Z")?;
+    /// assert_eq!(dt.date(), Date::new(2024, 3, 1)?);
+    /// ```
+    #[rune::function(keep)]
+    pub fn date(&self) -> Date {
+        Date {
+            inner: self.inner.date(),
+        }
+    }
+
+    /// Get the time part of the date and time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Time};
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:00Z")?;
+    /// assert_eq!(dt.time(), Time::new(12, 30, 0)?);
+    /// ```
+    #[rune::function(keep)]
+    pub fn time(&self) -> Time {
+        Time {
+            inner: self.inner.time(),
+        }
+    }
+
+    /// Get the offset from UTC of the date and time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:00-05:30")?;
+    /// assert_eq!(dt.offset().whole_minutes(), -330);
+    /// ```
+    #[rune::function(keep)]
+    pub fn offset(&self) -> UtcOffset {
+        UtcOffset {
+            inner: self.inner.offset(),
+        }
+    }
+
+    /// Convert the date and time to the given offset, keeping the instant it
+    /// refers to.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, UtcOffset};
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T23:30:00Z")?;
+    /// let local = dt.to_offset(UtcOffset::from_hms(2, 0, 0)?);
+    ///
+    /// assert_eq!(local.to_rfc3339()?, "2024-03-02T01:30:00+02:00");
+    /// assert_eq!(local, dt);
+    /// ```
+    #[rune::function(keep)]
+    pub fn to_offset(&self, offset: &UtcOffset) -> Result<Self, VmError> {
+        let Some(inner) = self.inner.checked_to_offset(offset.inner) else {
+            return Err(VmError::panic(
+                "overflow when converting date and time to offset",
+            ));
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Get the year of the date.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// assert_eq!(DateTime::UNIX_EPOCH.year(), 1970);
+    /// ```
+    #[rune::function(keep)]
+    pub fn year(&self) -> i32 {
+        self.inner.year()
+    }
+
+    /// Get the month of the date, in the range `1..=12`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// assert_eq!(DateTime::UNIX_EPOCH.month(), 1);
+    /// ```
+    #[rune::function(keep)]
+    pub fn month(&self) -> u8 {
+        u8::from(self.inner.month())
+    }
+
+    /// Get the day of the month, in the range `1..=31`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// assert_eq!(DateTime::UNIX_EPOCH.day(), 1);
+    /// ```
+    #[rune::function(keep)]
+    pub fn day(&self) -> u8 {
+        self.inner.day()
+    }
+
+    /// Get the day of the year, in the range `1..=366`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-12-31T00:00:00Z")?;
+    /// assert_eq!(dt.ordinal(), 366);
+    /// ```
+    #[rune::function(keep)]
+    pub fn ordinal(&self) -> u16 {
+        self.inner.ordinal()
+    }
+
+    /// Get the ISO 8601 day of the week, where Monday is `1` and Sunday is
+    /// `7`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// // The Unix epoch was a Thursday.
+    /// assert_eq!(DateTime::UNIX_EPOCH.weekday(), 4);
+    /// ```
+    #[rune::function(keep)]
+    pub fn weekday(&self) -> u8 {
+        self.inner.weekday().number_from_monday()
+    }
+
+    /// Get the hour of the day, in the range `0..24`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:15Z")?;
+    /// assert_eq!(dt.hour(), 12);
+    /// ```
+    #[rune::function(keep)]
+    pub fn hour(&self) -> u8 {
+        self.inner.hour()
+    }
+
+    /// Get the minute within the hour, in the range `0..60`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:15Z")?;
+    /// assert_eq!(dt.minute(), 30);
+    /// ```
+    #[rune::function(keep)]
+    pub fn minute(&self) -> u8 {
+        self.inner.minute()
+    }
+
+    /// Get the second within the minute, in the range `0..60`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:15Z")?;
+    /// assert_eq!(dt.second(), 15);
+    /// ```
+    #[rune::function(keep)]
+    pub fn second(&self) -> u8 {
+        self.inner.second()
+    }
+
+    /// Get the nanoseconds within the second.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let dt = DateTime::parse_rfc3339("2024-03-01T12:30:15.5Z")?;
+    /// assert_eq!(dt.nanosecond(), 500_000_000);
+    /// ```
+    #[rune::function(keep)]
+    pub fn nanosecond(&self) -> u32 {
+        self.inner.nanosecond()
+    }
+
+    /// Returns the amount of time elapsed from another date and time to this
+    /// one, or zero duration if that date and time is later than this one.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let first = DateTime::parse_rfc3339("2024-03-01T12:00:00Z")?;
+    /// let second = DateTime::parse_rfc3339("2024-03-01T14:00:00+01:00")?;
+    ///
+    /// assert_eq!(second.duration_since(first), Duration::from_secs(3600));
+    /// assert_eq!(first.duration_since(second), Duration::ZERO);
+    /// ```
+    #[rune::function(keep)]
+    pub fn duration_since(&self, earlier: &DateTime) -> Duration {
+        let duration = self.inner - earlier.inner;
+        let duration = std::time::Duration::try_from(duration).unwrap_or_default();
+        Duration::from_std(duration)
+    }
+
+    /// Add a duration to this date and time and return a new date and time.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let dt = DateTime::UNIX_EPOCH + Duration::from_secs(86400);
+    /// assert_eq!(dt.to_rfc3339()?, "1970-01-02T00:00:00Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD)]
+    #[inline]
+    fn add(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(inner) = self
+            .inner
+            .checked_add(signed(rhs, "adding duration to date and time")?)
+        else {
+            return Err(VmError::panic(
+                "overflow when adding duration to date and time",
+            ));
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Add a duration to this date and time in place.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let dt = DateTime::UNIX_EPOCH;
+    /// dt += Duration::from_secs(60);
+    /// assert_eq!(dt.minute(), 1);
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD_ASSIGN)]
+    #[inline]
+    fn add_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        *self = self.add(rhs)?;
+        Ok(())
+    }
+
+    /// Subtract a duration from this date and time and return a new date and
+    /// time.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let dt = DateTime::UNIX_EPOCH - Duration::from_secs(1);
+    /// assert_eq!(dt.to_rfc3339()?, "1969-12-31T23:59:59Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB)]
+    #[inline]
+    fn sub(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(inner) = self
+            .inner
+            .checked_sub(signed(rhs, "subtracting duration from date and time")?)
+        else {
+            return Err(VmError::panic(
+                "overflow when subtracting duration from date and time",
+            ));
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Subtract a duration from this date and time in place.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let dt = DateTime::UNIX_EPOCH;
+    /// dt -= Duration::from_secs(60);
+    /// assert_eq!(dt.year(), 1969);
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB_ASSIGN)]
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        *self = self.sub(rhs)?;
+        Ok(())
+    }
+
+    /// Test two dates and times for partial equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// let a = DateTime::parse_rfc3339("2024-03-01T12:00:00Z")?;
+    /// let b = DateTime::parse_rfc3339("2024-03-01T13:00:00+01:00")?;
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    #[inline]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Test two dates and times for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::eq;
+    /// use time::{DateTime, Duration};
+    ///
+    /// let first = DateTime::UNIX_EPOCH;
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert_eq!(eq(first, first), true);
+    /// assert_eq!(eq(first, second), false);
+    /// ```
+    #[rune::function(keep, instance, protocol = EQ)]
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a partial ordered comparison between two dates and times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let first = DateTime::UNIX_EPOCH;
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert!(first < second);
+    /// assert!(second > first);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a totally ordered comparison between two dates and times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::cmp;
+    /// use time::{DateTime, Duration};
+    ///
+    /// let first = DateTime::UNIX_EPOCH;
+    /// let second = first + Duration::SECOND;
+    ///
+    /// assert_eq!(cmp(first, second), Ordering::Less);
+    /// assert_eq!(cmp(second, first), Ordering::Greater);
+    /// assert_eq!(cmp(first, first), Ordering::Equal);
+    /// ```
+    #[rune::function(keep, instance, protocol = CMP)]
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Hash the date and time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::hash;
+    /// use time::DateTime;
+    ///
+    /// let a = DateTime::parse_rfc3339("2024-03-01T12:00:00Z")?;
+    /// let b = DateTime::parse_rfc3339("2024-03-01T13:00:00+01:00")?;
+    ///
+    /// assert_eq!(hash(a), hash(b));
+    /// ```
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        self.inner.hash(hasher);
+    }
+
+    /// Write a display representation of the date and time, using the
+    /// [RFC 3339] format where possible.
+    ///
+    /// [RFC 3339]: https://datatracker.ietf.org/doc/html/rfc3339
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// assert_eq!(`${DateTime::UNIX_EPOCH}`, "1970-01-01T00:00:00Z");
+    /// ```
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        match self.inner.format(&Rfc3339) {
+            Ok(string) => write!(f, "{string}"),
+            Err(..) => write!(f, "{}", self.inner),
+        }
+    }
+
+    /// Write a debug representation of the date and time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::DateTime;
+    ///
+    /// println!("{:?}", DateTime::now_utc());
+    /// ```
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+
+    /// Clone the current date and time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{DateTime, Duration};
+    ///
+    /// let first = DateTime::UNIX_EPOCH;
+    /// let second = first.clone();
+    /// second += Duration::SECOND;
+    ///
+    /// assert!(first < second);
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner }
+    }
+}
+
+mod const_date_time {
+    use rune::runtime::{ConstValue, RuntimeError, Value};
+    use time::{OffsetDateTime, UtcOffset};
+
+    #[inline]
+    pub(super) fn to_const_value(dt: OffsetDateTime) -> Result<ConstValue, RuntimeError> {
+        let secs = dt.unix_timestamp();
+        let nanos = dt.nanosecond();
+        let offset = dt.offset().whole_seconds();
+        rune::to_const_value((secs, nanos, offset))
+    }
+
+    #[inline]
+    pub(super) fn from_const_value(value: &ConstValue) -> Result<OffsetDateTime, RuntimeError> {
+        from_parts(rune::from_const_value::<(i64, u32, i32)>(value)?)
+    }
+
+    #[inline]
+    pub(super) fn from_value(value: Value) -> Result<OffsetDateTime, RuntimeError> {
+        from_parts(rune::from_value::<(i64, u32, i32)>(value)?)
+    }
+
+    fn from_parts((secs, nanos, offset): (i64, u32, i32)) -> Result<OffsetDateTime, RuntimeError> {
+        let nanos = i128::from(secs) * 1_000_000_000 + i128::from(nanos);
+
+        let (Ok(dt), Ok(offset)) = (
+            OffsetDateTime::from_unix_timestamp_nanos(nanos),
+            UtcOffset::from_whole_seconds(offset),
+        ) else {
+            return Err(RuntimeError::panic("date and time out of range"));
+        };
+
+        Ok(dt.to_offset(offset))
+    }
+}
+
+/// A calendar date in the proleptic Gregorian calendar.
+///
+/// # Examples
+///
+/// ```rune
+/// use time::{Date, Duration};
+///
+/// let date = Date::new(2024, 2, 28)?;
+/// let next = date + Duration::from_secs(86400);
+///
+/// assert_eq!(`${next}`, "2024-02-29");
+/// assert!(date < next);
+/// ```
+#[derive(Debug, Clone, Copy, Any)]
+#[rune(item = ::time)]
+pub struct Date {
+    inner: ::time::Date,
+}
+
+impl Date {
+    /// Construct a date from a year, a month in the range `1..=12` and a day of
+    /// the month.
+    ///
+    /// Errors if the date doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// let date = Date::new(2024, 2, 29)?;
+    /// assert_eq!(date.day(), 29);
+    ///
+    /// assert!(Date::new(2023, 2, 29).is_err());
+    /// assert!(Date::new(2023, 13, 1).is_err());
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Self, Error> {
+        let month = Month::try_from(month)?;
+
+        Ok(Self {
+            inner: ::time::Date::from_calendar_date(year, month, day)?,
+        })
+    }
+
+    /// Parse a date using a strftime-style format such as `%Y-%m-%d`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// let date = Date::parse("01.03.2024", "%d.%m.%Y")?;
+    /// assert_eq!(date, Date::new(2024, 3, 1)?);
+    /// ```
+    #[rune::function(keep, path = Self::parse)]
+    pub fn parse(string: &str, format: &str) -> Result<Self, Error> {
+        let format = strftime(format)?;
+
+        Ok(Self {
+            inner: ::time::Date::parse(string, &format)?,
+        })
+    }
+
+    /// Format the date using a strftime-style format such as `%Y-%m-%d`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// let date = Date::new(2024, 3, 1)?;
+    /// assert_eq!(date.format("%A %-d %B %Y")?, "Friday 1 March 2024");
+    /// ```
+    #[rune::function(keep)]
+    pub fn format(&self, format: &str) -> alloc::Result<Result<String, Error>> {
+        let format = nested_try!(strftime(format));
+        let string = nested_try!(self.inner.format(&format).map_err(Error::from));
+        Ok(Ok(String::try_from(string)?))
+    }
+
+    /// Get the year of the date.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(Date::new(2024, 3, 1)?.year(), 2024);
+    /// ```
+    #[rune::function(keep)]
+    pub fn year(&self) -> i32 {
+        self.inner.year()
+    }
+
+    /// Get the month of the date, in the range `1..=12`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(Date::new(2024, 3, 1)?.month(), 3);
+    /// ```
+    #[rune::function(keep)]
+    pub fn month(&self) -> u8 {
+        u8::from(self.inner.month())
+    }
+
+    /// Get the day of the month, in the range `1..=31`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(Date::new(2024, 3, 1)?.day(), 1);
+    /// ```
+    #[rune::function(keep)]
+    pub fn day(&self) -> u8 {
+        self.inner.day()
+    }
+
+    /// Get the day of the year, in the range `1..=366`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(Date::new(2024, 3, 1)?.ordinal(), 61);
+    /// ```
+    #[rune::function(keep)]
+    pub fn ordinal(&self) -> u16 {
+        self.inner.ordinal()
+    }
+
+    /// Get the ISO 8601 day of the week, where Monday is `1` and Sunday is
+    /// `7`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(Date::new(2024, 3, 3)?.weekday(), 7);
+    /// ```
+    #[rune::function(keep)]
+    pub fn weekday(&self) -> u8 {
+        self.inner.weekday().number_from_monday()
+    }
+
+    /// Add a duration to this date and return a new date.
+    ///
+    /// Only whole days of the duration are added.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Date, Duration};
+    ///
+    /// let date = Date::new(2024, 12, 31)? + Duration::from_secs(86400);
+    /// assert_eq!(date, Date::new(2025, 1, 1)?);
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD)]
+    #[inline]
+    fn add(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(inner) = self.inner.checked_add_std(rhs.into_std()) else {
+            return Err(VmError::panic("overflow when adding duration to date"));
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Add a duration to this date in place.
+    ///
+    /// Only whole days of the duration are added.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Date, Duration};
+    ///
+    /// let date = Date::new(2024, 3, 1)?;
+    /// date += Duration::from_secs(86400);
+    /// assert_eq!(date.day(), 2);
+    /// ```
+    #[rune::function(keep, instance, protocol = ADD_ASSIGN)]
+    #[inline]
+    fn add_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        *self = self.add(rhs)?;
+        Ok(())
+    }
+
+    /// Subtract a duration from this date and return a new date.
+    ///
+    /// Only whole days of the duration are subtracted.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Date, Duration};
+    ///
+    /// let date = Date::new(2024, 3, 1)? - Duration::from_secs(86400);
+    /// assert_eq!(date, Date::new(2024, 2, 29)?);
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB)]
+    #[inline]
+    fn sub(&self, rhs: &Duration) -> Result<Self, VmError> {
+        let Some(inner) = self.inner.checked_sub_std(rhs.into_std()) else {
+            return Err(VmError::panic(
+                "overflow when subtracting duration from date",
+            ));
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Subtract a duration from this date in place.
+    ///
+    /// Only whole days of the duration are subtracted.
+    ///
+    /// # Vm Panics
+    ///
+    /// Panics if the resulting date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Date, Duration};
+    ///
+    /// let date = Date::new(2024, 3, 1)?;
+    /// date -= Duration::from_secs(86400);
+    /// assert_eq!(date.month(), 2);
+    /// ```
+    #[rune::function(keep, instance, protocol = SUB_ASSIGN)]
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Duration) -> Result<(), VmError> {
+        *self = self.sub(rhs)?;
+        Ok(())
+    }
+
+    /// Test two dates for partial equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(Date::new(2024, 3, 1)?, Date::new(2024, 3, 1)?);
+    /// assert_ne!(Date::new(2024, 3, 1)?, Date::new(2024, 3, 2)?);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    #[inline]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Test two dates for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::eq;
+    /// use time::Date;
+    ///
+    /// assert!(eq(Date::new(2024, 3, 1)?, Date::new(2024, 3, 1)?));
+    /// ```
+    #[rune::function(keep, instance, protocol = EQ)]
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a partial ordered comparison between two dates.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert!(Date::new(2024, 3, 1)? < Date::new(2024, 3, 2)?);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a totally ordered comparison between two dates.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::cmp;
+    /// use time::Date;
+    ///
+    /// assert_eq!(cmp(Date::new(2024, 3, 1)?, Date::new(2023, 3, 1)?), Ordering::Greater);
+    /// ```
+    #[rune::function(keep, instance, protocol = CMP)]
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Hash the date.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::hash;
+    /// use time::Date;
+    ///
+    /// assert_eq!(hash(Date::new(2024, 3, 1)?), hash(Date::new(2024, 3, 1)?));
+    /// ```
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        self.inner.hash(hasher);
+    }
+
+    /// Write a display representation of the date in the `YYYY-MM-DD` format.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(`${Date::new(2024, 3, 1)?}`, "2024-03-01");
+    /// ```
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{}", self.inner)
+    }
+
+    /// Write a debug representation of the date.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Date;
+    ///
+    /// assert_eq!(format!("{:?}", Date::new(2024, 3, 1)?), "2024-03-01");
+    /// ```
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{:?}", self.inner)
+    }
+
+    /// Clone the current date.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::{Date, Duration};
+    ///
+    /// let first = Date::new(2024, 3, 1)?;
+    /// let second = first.clone();
+    /// second += Duration::from_secs(86400);
+    ///
+    /// assert!(first < second);
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner }
+    }
+}
+
+/// A time of day without a date or an offset.
+///
+/// # Examples
+///
+/// ```rune
+/// use time::Time;
+///
+/// let time = Time::parse("14:05", "%H:%M")?;
+///
+/// assert_eq!(`${time}`, "14:05:00");
+/// assert!(time > Time::MIDNIGHT);
+/// ```
+#[derive(Debug, Clone, Copy, Any, ToConstValue)]
+#[rune(item = ::time)]
+pub struct Time {
+    #[const_value(with = self::const_time)]
+    inner: ::time::Time,
+}
+
+impl Time {
+    /// Midnight, `00:00:00`.
+    pub const MIDNIGHT: Self = Self {
+        inner: ::time::Time::MIDNIGHT,
+    };
+
+    /// Construct a time from an hour in the range `0..24`, a minute and a
+    /// second in the range `0..60`.
+    ///
+    /// Errors if any component is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// let time = Time::new(23, 59, 59)?;
+    /// assert_eq!(time.hour(), 23);
+    ///
+    /// assert!(Time::new(24, 0, 0).is_err());
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub fn new(hour: u8, minute: u8, second: u8) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ::time::Time::from_hms(hour, minute, second)?,
+        })
+    }
+
+    /// Construct a time from an hour, a minute, a second and nanoseconds
+    /// within the second.
+    ///
+    /// Errors if any component is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// let time = Time::from_hms_nano(12, 0, 0, 250_000_000)?;
+    /// assert_eq!(`${time}`, "12:00:00.25");
+    /// ```
+    #[rune::function(keep, path = Self::from_hms_nano)]
+    pub fn from_hms_nano(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ::time::Time::from_hms_nano(hour, minute, second, nanosecond)?,
+        })
+    }
+
+    /// Parse a time using a strftime-style format such as `%H:%M:%S`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// let time = Time::parse("02:05 PM", "%I:%M %p")?;
+    /// assert_eq!(time, Time::new(14, 5, 0)?);
+    /// ```
+    #[rune::function(keep, path = Self::parse)]
+    pub fn parse(string: &str, format: &str) -> Result<Self, Error> {
+        let format = strftime(format)?;
+
+        Ok(Self {
+            inner: ::time::Time::parse(string, &format)?,
+        })
+    }
+
+    /// Format the time using a strftime-style format such as `%H:%M:%S`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// let time = Time::new(14, 5, 0)?;
+    /// assert_eq!(time.format("%I:%M %p")?, "02:05 PM");
+    /// ```
+    #[rune::function(keep)]
+    pub fn format(&self, format: &str) -> alloc::Result<Result<String, Error>> {
+        let format = nested_try!(strftime(format));
+        let string = nested_try!(self.inner.format(&format).map_err(Error::from));
+        Ok(Ok(String::try_from(string)?))
+    }
+
+    /// Get the hour of the day, in the range `0..24`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert_eq!(Time::new(14, 5, 30)?.hour(), 14);
+    /// ```
+    #[rune::function(keep)]
+    pub fn hour(&self) -> u8 {
+        self.inner.hour()
+    }
+
+    /// Get the minute within the hour, in the range `0..60`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert_eq!(Time::new(14, 5, 30)?.minute(), 5);
+    /// ```
+    #[rune::function(keep)]
+    pub fn minute(&self) -> u8 {
+        self.inner.minute()
+    }
+
+    /// Get the second within the minute, in the range `0..60`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert_eq!(Time::new(14, 5, 30)?.second(), 30);
+    /// ```
+    #[rune::function(keep)]
+    pub fn second(&self) -> u8 {
+        self.inner.second()
+    }
+
+    /// Get the nanoseconds within the second.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert_eq!(Time::from_hms_nano(14, 5, 30, 42)?.nanosecond(), 42);
+    /// ```
+    #[rune::function(keep)]
+    pub fn nanosecond(&self) -> u32 {
+        self.inner.nanosecond()
+    }
+
+    /// Test two times for partial equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert_eq!(Time::new(0, 0, 0)?, Time::MIDNIGHT);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    #[inline]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Test two times for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::eq;
+    /// use time::Time;
+    ///
+    /// assert!(eq(Time::new(0, 0, 0)?, Time::MIDNIGHT));
+    /// ```
+    #[rune::function(keep, instance, protocol = EQ)]
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a partial ordered comparison between two times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert!(Time::new(9, 0, 0)? < Time::new(17, 0, 0)?);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a totally ordered comparison between two times.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::cmp;
+    /// use time::Time;
+    ///
+    /// assert_eq!(cmp(Time::MIDNIGHT, Time::new(0, 0, 1)?), Ordering::Less);
+    /// ```
+    #[rune::function(keep, instance, protocol = CMP)]
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Hash the time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::hash;
+    /// use time::Time;
+    ///
+    /// assert_eq!(hash(Time::new(0, 0, 0)?), hash(Time::MIDNIGHT));
+    /// ```
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        self.inner.hash(hasher);
+    }
+
+    /// Write a display representation of the time in the `HH:MM:SS` format,
+    /// followed by fractional seconds if there are any.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert_eq!(`${Time::new(9, 5, 0)?}`, "09:05:00");
+    /// assert_eq!(`${Time::from_hms_nano(9, 5, 0, 1_000)?}`, "09:05:00.000001");
+    /// ```
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        let (hour, minute, second, nanos) = self.inner.as_hms_nano();
+        write!(f, "{hour:02}:{minute:02}:{second:02}")?;
+
+        if nanos != 0 {
+            let mut digits = 9;
+            let mut nanos = nanos;
+
+            while nanos % 10 == 0 {
+                nanos /= 10;
+                digits -= 1;
+            }
+
+            write!(f, ".{nanos:0digits$}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a debug representation of the time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// assert_eq!(format!("{:?}", Time::new(9, 5, 0)?), "09:05:00");
+    /// ```
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        self.display_fmt(f)
+    }
+
+    /// Clone the current time.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Time;
+    ///
+    /// let time = Time::new(9, 5, 0)?;
+    /// assert_eq!(time.clone(), time);
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner }
+    }
+}
+
+mod const_time {
+    use rune::runtime::{ConstValue, RuntimeError, Value};
+    use time::Time;
+
+    #[inline]
+    pub(super) fn to_const_value(time: Time) -> Result<ConstValue, RuntimeError> {
+        rune::to_const_value(time.as_hms_nano())
+    }
+
+    #[inline]
+    pub(super) fn from_const_value(value: &ConstValue) -> Result<Time, RuntimeError> {
+        from_parts(rune::from_const_value::<(u8, u8, u8, u32)>(value)?)
+    }
+
+    #[inline]
+    pub(super) fn from_value(value: Value) -> Result<Time, RuntimeError> {
+        from_parts(rune::from_value::<(u8, u8, u8, u32)>(value)?)
+    }
+
+    fn from_parts((hour, minute, second, nanos): (u8, u8, u8, u32)) -> Result<Time, RuntimeError> {
+        Time::from_hms_nano(hour, minute, second, nanos)
+            .map_err(|_| RuntimeError::panic("time out of range"))
+    }
+}
+
+/// An offset from UTC.
+///
+/// # Examples
+///
+/// ```rune
+/// use time::UtcOffset;
+///
+/// let offset = UtcOffset::from_hms(-5, -30, 0)?;
+///
+/// assert_eq!(`${offset}`, "-05:30");
+/// assert_eq!(offset.whole_minutes(), -330);
+/// assert!(!offset.is_utc());
+/// ```
+#[derive(Debug, Clone, Copy, Any, ToConstValue)]
+#[rune(item = ::time)]
+pub struct UtcOffset {
+    #[const_value(with = self::const_utc_offset)]
+    inner: ::time::UtcOffset,
+}
+
+impl UtcOffset {
+    /// The UTC offset, `+00:00`.
+    pub const UTC: Self = Self {
+        inner: ::time::UtcOffset::UTC,
+    };
+
+    /// Construct an offset from hours, minutes and seconds.
+    ///
+    /// The signs of all components should match. Errors if any component is
+    /// out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// let offset = UtcOffset::from_hms(9, 30, 0)?;
+    /// assert_eq!(offset.whole_seconds(), 34200);
+    ///
+    /// assert!(UtcOffset::from_hms(26, 0, 0).is_err());
+    /// ```
+    #[rune::function(keep, path = Self::from_hms)]
+    pub fn from_hms(hours: i8, minutes: i8, seconds: i8) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ::time::UtcOffset::from_hms(hours, minutes, seconds)?,
+        })
+    }
+
+    /// Construct an offset from a number of seconds east of UTC.
+    ///
+    /// Errors if the offset is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// let offset = UtcOffset::from_whole_seconds(-3600)?;
+    /// assert_eq!(offset.whole_hours(), -1);
+    /// ```
+    #[rune::function(keep, path = Self::from_whole_seconds)]
+    pub fn from_whole_seconds(seconds: i32) -> Result<Self, Error> {
+        Ok(Self {
+            inner: ::time::UtcOffset::from_whole_seconds(seconds)?,
+        })
+    }
+
+    /// Get the number of whole hours in the offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(UtcOffset::from_hms(5, 45, 0)?.whole_hours(), 5);
+    /// ```
+    #[rune::function(keep)]
+    pub fn whole_hours(&self) -> i8 {
+        self.inner.whole_hours()
+    }
+
+    /// Get the number of whole minutes in the offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(UtcOffset::from_hms(5, 45, 0)?.whole_minutes(), 345);
+    /// ```
+    #[rune::function(keep)]
+    pub fn whole_minutes(&self) -> i16 {
+        self.inner.whole_minutes()
+    }
+
+    /// Get the number of seconds in the offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(UtcOffset::from_hms(5, 45, 0)?.whole_seconds(), 20700);
+    /// ```
+    #[rune::function(keep)]
+    pub fn whole_seconds(&self) -> i32 {
+        self.inner.whole_seconds()
+    }
+
+    /// Test if the offset is UTC.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert!(UtcOffset::UTC.is_utc());
+    /// assert!(UtcOffset::from_hms(0, 0, 0)?.is_utc());
+    /// ```
+    #[rune::function(keep)]
+    pub fn is_utc(&self) -> bool {
+        self.inner.is_utc()
+    }
+
+    /// Test two offsets for partial equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(UtcOffset::from_whole_seconds(3600)?, UtcOffset::from_hms(1, 0, 0)?);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
+    #[inline]
+    fn partial_eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Test two offsets for total equality.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::eq;
+    /// use time::UtcOffset;
+    ///
+    /// assert!(eq(UtcOffset::from_whole_seconds(0)?, UtcOffset::UTC));
+    /// ```
+    #[rune::function(keep, instance, protocol = EQ)]
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        PartialEq::eq(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a partial ordered comparison between two offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert!(UtcOffset::from_hms(-1, 0, 0)? < UtcOffset::UTC);
+    /// ```
+    #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
+    #[inline]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Perform a totally ordered comparison between two offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::cmp::Ordering;
+    /// use std::ops::cmp;
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(cmp(UtcOffset::from_hms(1, 0, 0)?, UtcOffset::UTC), Ordering::Greater);
+    /// ```
+    #[rune::function(keep, instance, protocol = CMP)]
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        Ord::cmp(&self.inner, &rhs.inner)
+    }
+
+    /// Hash the offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::ops::hash;
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(hash(UtcOffset::from_whole_seconds(0)?), hash(UtcOffset::UTC));
+    /// ```
+    #[rune::function(keep, instance, protocol = HASH)]
+    fn hash(&self, hasher: &mut Hasher) {
+        self.inner.hash(hasher);
+    }
+
+    /// Write a display representation of the offset in the `±HH:MM` format,
+    /// followed by seconds if there are any.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(`${UtcOffset::UTC}`, "+00:00");
+    /// assert_eq!(`${UtcOffset::from_hms(1, 2, 3)?}`, "+01:02:03");
+    /// ```
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        let (hours, minutes, seconds) = self.inner.as_hms();
+        let sign = if self.inner.is_negative() { '-' } else { '+' };
+
+        write!(
+            f,
+            "{sign}{:02}:{:02}",
+            hours.unsigned_abs(),
+            minutes.unsigned_abs()
+        )?;
+
+        if seconds != 0 {
+            write!(f, ":{:02}", seconds.unsigned_abs())?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a debug representation of the offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// assert_eq!(format!("{:?}", UtcOffset::UTC), "+00:00");
+    /// ```
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        self.display_fmt(f)
+    }
+
+    /// Clone the current offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::UtcOffset;
+    ///
+    /// let offset = UtcOffset::from_hms(1, 0, 0)?;
+    /// assert_eq!(offset.clone(), offset);
+    /// ```
+    #[rune::function(keep, instance, protocol = CLONE)]
+    fn clone(&self) -> Self {
+        Self { inner: self.inner }
+    }
+}
+
+mod const_utc_offset {
+    use rune::runtime::{ConstValue, RuntimeError, Value};
+    use time::UtcOffset;
+
+    #[inline]
+    pub(super) fn to_const_value(offset: UtcOffset) -> Result<ConstValue, RuntimeError> {
+        rune::to_const_value(offset.whole_seconds())
+    }
+
+    #[inline]
+    pub(super) fn from_const_value(value: &ConstValue) -> Result<UtcOffset, RuntimeError> {
+        from_seconds(rune::from_const_value::<i32>(value)?)
+    }
+
+    #[inline]
+    pub(super) fn from_value(value: Value) -> Result<UtcOffset, RuntimeError> {
+        from_seconds(rune::from_value::<i32>(value)?)
+    }
+
+    fn from_seconds(seconds: i32) -> Result<UtcOffset, RuntimeError> {
+        UtcOffset::from_whole_seconds(seconds)
+            .map_err(|_| RuntimeError::panic("offset out of range"))
+    }
+}
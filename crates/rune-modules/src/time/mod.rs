@@ -28,6 +28,19 @@
 //!     println("Message after 10 seconds!");
 //! }
 //! ```
+//!
+//! Calendar dates and times are provided through [`DateTime`], [`Date`],
+//! [`Time`] and [`UtcOffset`]:
+//!
+//! ```rust,ignore
+//! use time::{DateTime, Duration};
+//!
+//! fn main() {
+//!     let now = DateTime::now_utc();
+//!     let tomorrow = now + Duration::from_secs(24 * 60 * 60);
+//!     println!("{}", tomorrow.format("%Y-%m-%d")?);
+//! }
+//! ```
 
 use core::cmp::Ordering;
 use core::hash::Hash;
@@ -37,6 +50,9 @@ use rune::alloc::fmt::TryWrite;
 use rune::runtime::{Formatter, Hasher, Mut, VmError};
 use rune::{docstring, item, Any, ContextError, Module, ToConstValue};
 
+mod calendar;
+pub use self::calendar::{Date, DateTime, Error, Time, UtcOffset};
+
 const NANOS_PER_SEC: u32 = 1_000_000_000;
 
 /// Construct the `time` module.
@@ -213,6 +229,166 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     m.function_meta(Instant::clone__meta)?;
     m.implement_trait::<Instant>(item!(::std::clone::Clone))?;
 
+    m.ty::<DateTime>()?;
+    m.function_meta(DateTime::now_utc__meta)?;
+    m.function_meta(DateTime::new__meta)?;
+    m.function_meta(DateTime::from_unix_timestamp__meta)?;
+    m.function_meta(DateTime::from_unix_timestamp_millis__meta)?;
+    m.function_meta(DateTime::unix_timestamp__meta)?;
+    m.function_meta(DateTime::unix_timestamp_millis__meta)?;
+    m.function_meta(DateTime::parse_rfc3339__meta)?;
+    m.function_meta(DateTime::to_rfc3339__meta)?;
+    m.function_meta(DateTime::parse__meta)?;
+    m.function_meta(DateTime::format__meta)?;
+    m.function_meta(DateTime::date__meta)?;
+    m.function_meta(DateTime::time__meta)?;
+    m.function_meta(DateTime::offset__meta)?;
+    m.function_meta(DateTime::to_offset__meta)?;
+    m.function_meta(DateTime::year__meta)?;
+    m.function_meta(DateTime::month__meta)?;
+    m.function_meta(DateTime::day__meta)?;
+    m.function_meta(DateTime::ordinal__meta)?;
+    m.function_meta(DateTime::weekday__meta)?;
+    m.function_meta(DateTime::hour__meta)?;
+    m.function_meta(DateTime::minute__meta)?;
+    m.function_meta(DateTime::second__meta)?;
+    m.function_meta(DateTime::nanosecond__meta)?;
+    m.function_meta(DateTime::duration_since__meta)?;
+    m.function_meta(DateTime::add__meta)?;
+    m.function_meta(DateTime::add_assign__meta)?;
+    m.function_meta(DateTime::sub__meta)?;
+    m.function_meta(DateTime::sub_assign__meta)?;
+    m.function_meta(DateTime::partial_eq__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::PartialEq))?;
+    m.function_meta(DateTime::eq__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::Eq))?;
+    m.function_meta(DateTime::partial_cmp__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::PartialOrd))?;
+    m.function_meta(DateTime::cmp__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::cmp::Ord))?;
+    m.function_meta(DateTime::hash__meta)?;
+    m.function_meta(DateTime::display_fmt__meta)?;
+    m.function_meta(DateTime::debug_fmt__meta)?;
+    m.function_meta(DateTime::clone__meta)?;
+    m.implement_trait::<DateTime>(item!(::std::clone::Clone))?;
+
+    m.constant("UNIX_EPOCH", DateTime::UNIX_EPOCH)
+        .build_associated::<DateTime>()?
+        .docs(docstring! {
+            /// The Unix epoch, `1970-01-01T00:00:00Z`.
+            ///
+            /// # Examples
+            ///
+            /// ```rune
+            /// use time::DateTime;
+            ///
+            /// assert_eq!(DateTime::UNIX_EPOCH.unix_timestamp(), 0);
+            /// ```
+        })?;
+
+    m.ty::<Date>()?;
+    m.function_meta(Date::new__meta)?;
+    m.function_meta(Date::parse__meta)?;
+    m.function_meta(Date::format__meta)?;
+    m.function_meta(Date::year__meta)?;
+    m.function_meta(Date::month__meta)?;
+    m.function_meta(Date::day__meta)?;
+    m.function_meta(Date::ordinal__meta)?;
+    m.function_meta(Date::weekday__meta)?;
+    m.function_meta(Date::add__meta)?;
+    m.function_meta(Date::add_assign__meta)?;
+    m.function_meta(Date::sub__meta)?;
+    m.function_meta(Date::sub_assign__meta)?;
+    m.function_meta(Date::partial_eq__meta)?;
+    m.implement_trait::<Date>(item!(::std::cmp::PartialEq))?;
+    m.function_meta(Date::eq__meta)?;
+    m.implement_trait::<Date>(item!(::std::cmp::Eq))?;
+    m.function_meta(Date::partial_cmp__meta)?;
+    m.implement_trait::<Date>(item!(::std::cmp::PartialOrd))?;
+    m.function_meta(Date::cmp__meta)?;
+    m.implement_trait::<Date>(item!(::std::cmp::Ord))?;
+    m.function_meta(Date::hash__meta)?;
+    m.function_meta(Date::display_fmt__meta)?;
+    m.function_meta(Date::debug_fmt__meta)?;
+    m.function_meta(Date::clone__meta)?;
+    m.implement_trait::<Date>(item!(::std::clone::Clone))?;
+
+    m.ty::<Time>()?;
+    m.function_meta(Time::new__meta)?;
+    m.function_meta(Time::from_hms_nano__meta)?;
+    m.function_meta(Time::parse__meta)?;
+    m.function_meta(Time::format__meta)?;
+    m.function_meta(Time::hour__meta)?;
+    m.function_meta(Time::minute__meta)?;
+    m.function_meta(Time::second__meta)?;
+    m.function_meta(Time::nanosecond__meta)?;
+    m.function_meta(Time::partial_eq__meta)?;
+    m.implement_trait::<Time>(item!(::std::cmp::PartialEq))?;
+    m.function_meta(Time::eq__meta)?;
+    m.implement_trait::<Time>(item!(::std::cmp::Eq))?;
+    m.function_meta(Time::partial_cmp__meta)?;
+    m.implement_trait::<Time>(item!(::std::cmp::PartialOrd))?;
+    m.function_meta(Time::cmp__meta)?;
+    m.implement_trait::<Time>(item!(::std::cmp::Ord))?;
+    m.function_meta(Time::hash__meta)?;
+    m.function_meta(Time::display_fmt__meta)?;
+    m.function_meta(Time::debug_fmt__meta)?;
+    m.function_meta(Time::clone__meta)?;
+    m.implement_trait::<Time>(item!(::std::clone::Clone))?;
+
+    m.constant("MIDNIGHT", Time::MIDNIGHT)
+        .build_associated::<Time>()?
+        .docs(docstring! {
+            /// Midnight, `00:00:00`.
+            ///
+            /// # Examples
+            ///
+            /// ```rune
+            /// use time::Time;
+            ///
+            /// assert_eq!(Time::MIDNIGHT.hour(), 0);
+            /// ```
+        })?;
+
+    m.ty::<UtcOffset>()?;
+    m.function_meta(UtcOffset::from_hms__meta)?;
+    m.function_meta(UtcOffset::from_whole_seconds__meta)?;
+    m.function_meta(UtcOffset::whole_hours__meta)?;
+    m.function_meta(UtcOffset::whole_minutes__meta)?;
+    m.function_meta(UtcOffset::whole_seconds__meta)?;
+    m.function_meta(UtcOffset::is_utc__meta)?;
+    m.function_meta(UtcOffset::partial_eq__meta)?;
+    m.implement_trait::<UtcOffset>(item!(::std::cmp::PartialEq))?;
+    m.function_meta(UtcOffset::eq__meta)?;
+    m.implement_trait::<UtcOffset>(item!(::std::cmp::Eq))?;
+    m.function_meta(UtcOffset::partial_cmp__meta)?;
+    m.implement_trait::<UtcOffset>(item!(::std::cmp::PartialOrd))?;
+    m.function_meta(UtcOffset::cmp__meta)?;
+    m.implement_trait::<UtcOffset>(item!(::std::cmp::Ord))?;
+    m.function_meta(UtcOffset::hash__meta)?;
+    m.function_meta(UtcOffset::display_fmt__meta)?;
+    m.function_meta(UtcOffset::debug_fmt__meta)?;
+    m.function_meta(UtcOffset::clone__meta)?;
+    m.implement_trait::<UtcOffset>(item!(::std::clone::Clone))?;
+
+    m.constant("UTC", UtcOffset::UTC)
+        .build_associated::<UtcOffset>()?
+        .docs(docstring! {
+            /// The UTC offset, `+00:00`.
+            ///
+            /// # Examples
+            ///
+            /// ```rune
+            /// use time::UtcOffset;
+            ///
+            /// assert!(UtcOffset::UTC.is_utc());
+            /// ```
+        })?;
+
+    m.ty::<Error>()?;
+    m.function_meta(Error::display_fmt__meta)?;
+    m.function_meta(Error::debug_fmt__meta)?;
+
     m.ty::<Interval>()?;
     m.function("tick", Interval::tick)
         .build_associated::<Interval>()?;
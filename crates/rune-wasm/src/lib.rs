@@ -245,8 +245,14 @@ async fn inner_compile(
                         WasmPosition::from(source.find_line_column(span.start.into_usize()));
                     let end = WasmPosition::from(source.find_line_column(span.end.into_usize()));
 
+                    let kind = if warning.is_denied() {
+                        WasmDiagnosticKind::Error
+                    } else {
+                        WasmDiagnosticKind::Warning
+                    };
+
                     diagnostics.push(WasmDiagnostic {
-                        kind: WasmDiagnosticKind::Warning,
+                        kind,
                         start,
                         end,
                         message: warning.to_string(),
@@ -58,6 +58,8 @@ pub use rune_alloc::limit;
 #[doc(inline)]
 pub use rune_alloc::path;
 #[doc(inline)]
+pub use rune_alloc::slice;
+#[doc(inline)]
 pub use rune_alloc::str;
 #[doc(inline)]
 pub use rune_alloc::sync;
@@ -29,6 +29,17 @@ impl Fields {
             Fields::Named(body) => body.iter(),
         }
     }
+
+    /// Iterate mutably over the fields of the body.
+    pub(crate) fn fields_mut(
+        &mut self,
+    ) -> impl Iterator<Item = &'_ mut (ast::Field, Option<T![,]>)> {
+        match self {
+            Fields::Empty => IntoIterator::into_iter(&mut []),
+            Fields::Unnamed(body) => body.iter_mut(),
+            Fields::Named(body) => body.iter_mut(),
+        }
+    }
 }
 
 impl Parse for Fields {
@@ -6,6 +6,8 @@ fn ast_parse() {
     rt::<ast::FnArg>("self");
     rt::<ast::FnArg>("_");
     rt::<ast::FnArg>("abc");
+    rt::<ast::FnArg>("abc: i64");
+    rt::<ast::FnArg>("(a, b): (i64, String)");
 }
 
 /// A single argument in a closure.
@@ -16,13 +18,43 @@ pub enum FnArg {
     SelfValue(T![self]),
     /// Function argument is a pattern binding.
     Pat(ast::Pat),
+    /// Function argument is a pattern binding with a type annotation, like
+    /// `value: i64`.
+    Typed(ast::Pat, T![:], ast::Type),
+}
+
+impl FnArg {
+    /// Get the type annotation of the argument, if any.
+    pub(crate) fn ty(&self) -> Option<&ast::Type> {
+        match self {
+            Self::Typed(_, _, ty) => Some(ty),
+            _ => None,
+        }
+    }
 }
 
 impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self> {
         Ok(match p.nth(0)? {
             K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
+            // NB: `<ident>:` would otherwise be parsed as an object binding.
+            K![ident] if matches!(p.nth(1)?, K![:]) => {
+                let pat = ast::Pat::Path(ast::PatPath {
+                    attributes: Vec::new(),
+                    path: p.parse()?,
+                });
+
+                Self::Typed(pat, p.parse()?, p.parse()?)
+            }
+            _ => {
+                let pat = p.parse()?;
+
+                if p.peek::<T![:]>()? {
+                    Self::Typed(pat, p.parse()?, p.parse()?)
+                } else {
+                    Self::Pat(pat)
+                }
+            }
         })
     }
 }
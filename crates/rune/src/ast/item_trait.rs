@@ -0,0 +1,173 @@
+use crate::ast::prelude::*;
+
+#[test]
+#[cfg(not(miri))]
+fn ast_parse() {
+    rt::<ast::ItemTrait>("trait Foo {}");
+    rt::<ast::ItemTrait>("pub trait Foo { fn test(self); }");
+    rt::<ast::ItemTrait>("trait Foo { fn test(self); fn other(self) { self.test() } }");
+    rt::<ast::ItemTrait>("#[doc = \"Shape\"] trait Foo { #[doc = \"Area\"] fn area(self); }");
+
+    let item = rt::<ast::ItemTrait>("trait Foo { fn a(self); async fn b(self) { } }");
+    assert_eq!(item.functions.len(), 2);
+    assert!(item.functions[0].body.is_none());
+    assert!(item.functions[1].body.is_some());
+    assert!(item.functions[1].async_token.is_some());
+}
+
+/// A trait declaration.
+///
+/// * `trait <name> { (<fn>)* }`.
+///
+/// Trait declarations and `impl <trait> for <type>` blocks are only supported
+/// by the default compiler. The unstable v2 compiler (`-O v2`) reports an
+/// error for them.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ItemTrait {
+    /// The attributes of the trait.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the trait.
+    #[rune(option)]
+    pub visibility: ast::Visibility,
+    /// The `trait` keyword.
+    pub trait_token: T![trait],
+    /// The name of the trait.
+    pub name: ast::Ident,
+    /// The open brace.
+    pub open: T!['{'],
+    /// Functions declared in the trait.
+    #[rune(iter)]
+    pub functions: Vec<ast::ItemTraitFn>,
+    /// The close brace.
+    pub close: T!['}'],
+    /// Opaque identifier of the trait.
+    #[rune(skip)]
+    pub(crate) id: ItemId,
+}
+
+impl ItemTrait {
+    /// Parse a `trait` item with the given meta.
+    pub(crate) fn parse_with_meta(
+        parser: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        visibility: ast::Visibility,
+    ) -> Result<Self> {
+        let trait_token = parser.parse()?;
+        let name = parser.parse()?;
+        let open = parser.parse()?;
+
+        let mut functions = Vec::new();
+
+        while !parser.peek::<ast::CloseBrace>()? {
+            functions.try_push(parser.parse()?)?;
+        }
+
+        let close = parser.parse()?;
+
+        Ok(Self {
+            attributes,
+            visibility,
+            trait_token,
+            name,
+            open,
+            functions,
+            close,
+            id: ItemId::ROOT,
+        })
+    }
+}
+
+item_parse!(Trait, ItemTrait, "trait item");
+
+/// A function declared in a trait.
+///
+/// Functions without a body are required to be provided by every
+/// implementation of the trait, while functions with a body act as defaults.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ItemTraitFn {
+    /// The attributes of the function.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The optional `async` keyword.
+    #[rune(iter)]
+    pub async_token: Option<T![async]>,
+    /// The `fn` token.
+    pub fn_token: T![fn],
+    /// The name of the function.
+    pub name: ast::Ident,
+    /// The arguments of the function.
+    pub args: ast::Parenthesized<ast::FnArg, T![,]>,
+    /// The function type.
+    #[rune(option)]
+    pub output: Option<(T![->], ast::Type)>,
+    /// The default body of the function, if any.
+    #[rune(iter)]
+    pub body: Option<ast::Block>,
+    /// The terminating semi-colon of a function without a body.
+    #[rune(iter)]
+    pub semi_token: Option<T![;]>,
+}
+
+impl ItemTraitFn {
+    /// Get the descriptive span of this function, e.g. `fn foo()` instead of
+    /// the span for the whole function declaration, body included.
+    pub(crate) fn descriptive_span(&self) -> Span {
+        if let Some(async_token) = &self.async_token {
+            async_token.span().join(self.args.span())
+        } else {
+            self.fn_token.span().join(self.args.span())
+        }
+    }
+
+    /// Convert the default implementation of this function into a function
+    /// item, if it has one.
+    pub(crate) fn to_item_fn(&self) -> alloc::Result<Option<ast::ItemFn>> {
+        let Some(body) = &self.body else {
+            return Ok(None);
+        };
+
+        Ok(Some(ast::ItemFn {
+            attributes: self.attributes.try_clone()?,
+            visibility: ast::Visibility::Inherited,
+            const_token: None,
+            async_token: self.async_token,
+            fn_token: self.fn_token,
+            name: self.name,
+            args: self.args.try_clone()?,
+            output: self.output.try_clone()?,
+            body: body.try_clone()?,
+            id: ItemId::ROOT,
+        }))
+    }
+}
+
+impl Parse for ItemTraitFn {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        let attributes = p.parse()?;
+        let async_token = p.parse()?;
+        let fn_token = p.parse()?;
+        let name = p.parse()?;
+        let args = p.parse()?;
+        let output = p.parse()?;
+
+        let (body, semi_token) = if p.peek::<T![;]>()? {
+            (None, Some(p.parse()?))
+        } else {
+            (Some(p.parse()?), None)
+        };
+
+        Ok(Self {
+            attributes,
+            async_token,
+            fn_token,
+            name,
+            args,
+            output,
+            body,
+            semi_token,
+        })
+    }
+}
@@ -98,10 +98,12 @@ impl<'a> Resolve<'a> for LitNumber {
             "u16" => Some(ast::NumberSuffix::Unsigned(text.suffix, NumberSize::S16)),
             "u32" => Some(ast::NumberSuffix::Unsigned(text.suffix, NumberSize::S32)),
             "u64" => Some(ast::NumberSuffix::Unsigned(text.suffix, NumberSize::S64)),
+            "u128" => Some(ast::NumberSuffix::Unsigned(text.suffix, NumberSize::S128)),
             "i8" => Some(ast::NumberSuffix::Signed(text.suffix, NumberSize::S8)),
             "i16" => Some(ast::NumberSuffix::Signed(text.suffix, NumberSize::S16)),
             "i32" => Some(ast::NumberSuffix::Signed(text.suffix, NumberSize::S32)),
             "i64" => Some(ast::NumberSuffix::Signed(text.suffix, NumberSize::S64)),
+            "i128" => Some(ast::NumberSuffix::Signed(text.suffix, NumberSize::S128)),
             "f32" | "f64" => Some(ast::NumberSuffix::Float(text.suffix)),
             "" => None,
             _ => {
@@ -144,6 +144,7 @@ mod item_fn;
 mod item_impl;
 mod item_mod;
 mod item_struct;
+mod item_trait;
 mod item_use;
 mod label;
 mod lit;
@@ -213,6 +214,7 @@ pub use self::item_fn::ItemFn;
 pub use self::item_impl::ItemImpl;
 pub use self::item_mod::{ItemInlineBody, ItemMod, ItemModBody};
 pub use self::item_struct::{Field, ItemStruct};
+pub use self::item_trait::{ItemTrait, ItemTraitFn};
 pub use self::item_use::{ItemUse, ItemUsePath, ItemUseSegment};
 pub use self::label::Label;
 pub use self::lit::Lit;
@@ -216,6 +216,8 @@ pub enum NumberSize {
     S32,
     /// A 64-bit sized integer.
     S64,
+    /// A 128-bit sized integer.
+    S128,
 }
 
 impl NumberSize {
@@ -232,7 +234,7 @@ impl NumberSize {
             Self::S8 => i8::MIN as i64,
             Self::S16 => i16::MIN as i64,
             Self::S32 => i32::MIN as i64,
-            Self::S64 => i64::MIN,
+            Self::S64 | Self::S128 => i64::MIN,
         }
     }
 
@@ -241,7 +243,7 @@ impl NumberSize {
             Self::S8 => i8::MAX as i64,
             Self::S16 => i16::MAX as i64,
             Self::S32 => i32::MAX as i64,
-            Self::S64 => i64::MAX,
+            Self::S64 | Self::S128 => i64::MAX,
         }
     }
 
@@ -250,7 +252,7 @@ impl NumberSize {
             Self::S8 => u8::MIN as u64,
             Self::S16 => u16::MIN as u64,
             Self::S32 => u32::MIN as u64,
-            Self::S64 => u64::MIN,
+            Self::S64 | Self::S128 => u64::MIN,
         }
     }
 
@@ -259,7 +261,7 @@ impl NumberSize {
             Self::S8 => u8::MAX as u64,
             Self::S16 => u16::MAX as u64,
             Self::S32 => u32::MAX as u64,
-            Self::S64 => u64::MAX,
+            Self::S64 | Self::S128 => u64::MAX,
         }
     }
 }
@@ -9,7 +9,8 @@ use crate::compile::FileSourceLoader as DefaultSourceLoader;
 #[cfg(not(feature = "std"))]
 use crate::compile::NoopSourceLoader as DefaultSourceLoader;
 use crate::compile::{
-    self, CompileVisitor, Located, MetaError, Options, ParseOptionError, Pool, SourceLoader,
+    self, CompileVisitor, Located, MetaError, Options, ParseCache, ParseOptionError, Pool,
+    SourceLoader,
 };
 use crate::runtime::unit::{DefaultStorage, UnitEncoder};
 use crate::runtime::Unit;
@@ -143,6 +144,7 @@ where
         options: None,
         visitors: Vec::new(),
         source_loader: None,
+        parse_cache: None,
         _unit_storage: PhantomData,
     }
 }
@@ -159,6 +161,7 @@ pub struct Build<'a, S> {
     options: Option<&'a Options>,
     visitors: Vec<&'a mut dyn compile::CompileVisitor>,
     source_loader: Option<&'a mut dyn SourceLoader>,
+    parse_cache: Option<&'a mut ParseCache>,
     _unit_storage: PhantomData<S>,
 }
 
@@ -287,6 +290,15 @@ impl<'a, S> Build<'a, S> {
         self
     }
 
+    /// Modify the current [Build] to reuse sources parsed by earlier builds
+    /// which used the same cache.
+    #[inline]
+    #[cfg(any(test, feature = "languageserver"))]
+    pub(crate) fn with_parse_cache(mut self, parse_cache: &'a mut ParseCache) -> Self {
+        self.parse_cache = Some(parse_cache);
+        self
+    }
+
     /// Build a [`Unit`] with the current configuration.
     ///
     /// See [`rune::prepare`] for more.
@@ -372,6 +384,7 @@ impl<'a, S> Build<'a, S> {
             }
         };
 
+        let mut parse_cache = self.parse_cache.take();
         let mut pool = Pool::new()?;
         let mut unit_storage = S::default();
 
@@ -386,8 +399,13 @@ impl<'a, S> Build<'a, S> {
             source_loader,
             options,
             &mut unit_storage,
+            parse_cache.as_deref_mut(),
         )?;
 
+        if let Some(parse_cache) = parse_cache {
+            parse_cache.retain_used();
+        }
+
         if diagnostics.has_error() {
             return Err(BuildError::default());
         }
@@ -0,0 +1,149 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::alloc::prelude::*;
+use crate::alloc::Vec;
+use crate::cli::{
+    visitor, AssetKind, CommandBase, Config, Entry, EntryPoint, ExitCode, Io, SharedFlags,
+};
+use crate::compile::FileSourceLoader;
+use crate::{Diagnostics, Options, Source, Sources};
+
+/// The extension used for compiled units.
+pub(super) const EXTENSION: &str = "runec";
+
+mod cli {
+    use std::path::PathBuf;
+    use std::vec::Vec;
+
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    #[command(rename_all = "kebab-case")]
+    pub(crate) struct Flags {
+        /// The path to write the compiled unit to.
+        ///
+        /// Defaults to the path being built with a `.runec` extension. This
+        /// can only be used when building a single path.
+        #[arg(short, long, value_name = "PATH")]
+        pub(super) output: Option<PathBuf>,
+        /// Explicit paths to build.
+        pub(super) build_path: Vec<PathBuf>,
+    }
+}
+
+pub(super) use cli::Flags;
+
+impl CommandBase for Flags {
+    #[inline]
+    fn is_workspace(&self, kind: AssetKind) -> bool {
+        matches!(kind, AssetKind::Bin)
+    }
+
+    #[inline]
+    fn describe(&self) -> &str {
+        "Building"
+    }
+
+    #[inline]
+    fn paths(&self) -> &[PathBuf] {
+        &self.build_path
+    }
+}
+
+pub(super) fn run<'p, I>(
+    io: &mut Io<'_>,
+    entry: &mut Entry<'_>,
+    c: &Config,
+    flags: &Flags,
+    shared: &SharedFlags,
+    options: &Options,
+    entries: I,
+) -> Result<ExitCode>
+where
+    I: IntoIterator<Item = EntryPoint<'p>>,
+{
+    let entries = entries.into_iter().try_collect::<Vec<_>>()?;
+
+    if flags.output.is_some() && entries.len() > 1 {
+        bail!("Cannot use `--output` when building more than one path");
+    }
+
+    for e in entries {
+        let mut options = options.clone();
+
+        if e.is_argument() {
+            options.script = true;
+        }
+
+        match build(io, entry, c, flags, shared, &options, e.path())? {
+            ExitCode::Success => (),
+            other => return Ok(other),
+        }
+    }
+
+    Ok(ExitCode::Success)
+}
+
+fn build(
+    io: &mut Io<'_>,
+    entry: &mut Entry<'_>,
+    c: &Config,
+    flags: &Flags,
+    shared: &SharedFlags,
+    options: &Options,
+    path: &Path,
+) -> Result<ExitCode> {
+    let context = shared.context(entry, c, None)?;
+
+    let source =
+        Source::from_path(path).with_context(|| format!("reading file: {}", path.display()))?;
+
+    let mut sources = Sources::new();
+    sources.insert(source)?;
+
+    let mut diagnostics = if shared.warnings {
+        Diagnostics::new()
+    } else {
+        Diagnostics::without_warnings()
+    };
+
+    let mut functions = visitor::FunctionVisitor::new(visitor::Attribute::None);
+    let mut source_loader = FileSourceLoader::new();
+
+    let result = crate::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(options)
+        .with_visitor(&mut functions)?
+        .with_source_loader(&mut source_loader)
+        .build();
+
+    diagnostics.emit(&mut io.stdout.lock(), &sources)?;
+
+    let Ok(unit) = result else {
+        return Ok(ExitCode::Failure);
+    };
+
+    let output = match &flags.output {
+        Some(output) => output.clone(),
+        None => path.with_extension(EXTENSION),
+    };
+
+    let bytes = unit.save()?;
+
+    fs::write(&output, &bytes[..])
+        .with_context(|| format!("writing file: {}", output.display()))?;
+
+    writeln!(
+        io.stdout,
+        "Built: {} -> {}",
+        path.display(),
+        output.display()
+    )?;
+
+    Ok(ExitCode::Success)
+}
@@ -20,6 +20,10 @@ mod cli {
         /// Exit with a non-zero exit-code even for warnings
         #[arg(long)]
         pub(super) warnings_are_errors: bool,
+        /// Check type annotations, the same as the `type-check` compiler
+        /// option
+        #[arg(long)]
+        pub(super) types: bool,
         /// Explicit paths to check.
         pub(super) check_path: Vec<PathBuf>,
     }
@@ -78,10 +82,16 @@ pub(super) fn run(
     let mut test_finder = visitor::FunctionVisitor::new(visitor::Attribute::None);
     let mut source_loader = FileSourceLoader::new();
 
+    let mut options = options.clone();
+
+    if flags.types {
+        options.type_check(true);
+    }
+
     let _ = crate::prepare(&mut sources)
         .with_context(&context)
         .with_diagnostics(&mut diagnostics)
-        .with_options(options)
+        .with_options(&options)
         .with_visitor(&mut test_finder)?
         .with_source_loader(&mut source_loader)
         .build();
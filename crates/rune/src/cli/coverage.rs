@@ -0,0 +1,177 @@
+//! Collection of code coverage into LCOV reports.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+
+use crate::runtime::{inst, Coverage, Unit};
+use crate::Sources;
+
+/// Coverage collected for a single source file.
+#[derive(Default)]
+struct File {
+    /// Hit counts for each line with code on it, which is the largest number of
+    /// times any instruction on that line was executed.
+    lines: BTreeMap<usize, u64>,
+    /// Functions by the line they start on and their name.
+    functions: BTreeMap<(usize, String), u64>,
+    /// Branches keyed by line, byte offset and the order of the conditional
+    /// jump at that offset. The counters are `None` if the jump was never
+    /// reached.
+    branches: BTreeMap<(usize, usize, usize), Option<(u64, u64)>>,
+}
+
+/// A coverage report aggregated over any number of units.
+///
+/// Sources are identified by their path, so coverage of the same file compiled
+/// into different units is merged. Sources without a path are ignored.
+#[derive(Default)]
+pub(super) struct Report {
+    files: BTreeMap<PathBuf, File>,
+}
+
+impl Report {
+    /// Add the coverage recorded while executing the given unit.
+    pub(super) fn add(&mut self, unit: &Unit, sources: &Sources, coverage: &Coverage) {
+        let Some(debug) = unit.debug_info() else {
+            return;
+        };
+
+        let mut last_branch = None;
+        let mut order = 0;
+        let mut function = None;
+        // Line counts within this unit, which are added to the report once all
+        // instructions have been visited.
+        let mut lines = BTreeMap::<(&Path, usize), u64>::new();
+
+        for (ip, inst) in unit.iter_instructions() {
+            // The first instruction of a function might not have a span, so
+            // functions are placed at the first instruction which does.
+            if let Some((_, signature)) = debug.function_at(ip) {
+                function = Some((signature.path.to_string(), coverage.instruction(ip)));
+            }
+
+            let Some(d) = debug.instruction_at(ip) else {
+                continue;
+            };
+
+            let Some(source) = sources.get(d.source_id) else {
+                continue;
+            };
+
+            let Some(path) = source.path() else {
+                continue;
+            };
+
+            let start = d.span.start.into_usize();
+            let (line, _) = source.find_line_column(start);
+            let file = self.files.entry(path.to_path_buf()).or_default();
+            let hits = coverage.instruction(ip);
+
+            let count = lines.entry((path, line)).or_default();
+            *count = (*count).max(hits);
+
+            if let Some((name, hits)) = function.take() {
+                let count = file.functions.entry((line, name)).or_default();
+                *count = count.saturating_add(hits);
+            }
+
+            if matches!(
+                inst.kind,
+                inst::Kind::JumpIf { .. }
+                    | inst::Kind::JumpIfNot { .. }
+                    | inst::Kind::OpJumpIfNot { .. }
+            ) {
+                // Multiple conditional jumps can share a span, so
+                // distinguish them by the order in which they appear.
+                if last_branch == Some((d.source_id, start)) {
+                    order += 1;
+                } else {
+                    order = 0;
+                }
+
+                last_branch = Some((d.source_id, start));
+
+                let counters = file.branches.entry((line, start, order)).or_default();
+
+                if let Some(branch) = coverage.branch(ip) {
+                    let (taken, not_taken) = counters.get_or_insert_default();
+                    *taken = taken.saturating_add(branch.taken);
+                    *not_taken = not_taken.saturating_add(branch.not_taken);
+                }
+            }
+        }
+
+        for ((path, line), hits) in lines {
+            let file = self.files.entry(path.to_path_buf()).or_default();
+            let count = file.lines.entry(line).or_default();
+            *count = count.saturating_add(hits);
+        }
+    }
+
+    /// Write the report in the LCOV tracefile format to the given path.
+    pub(super) fn write_lcov(&self, path: &Path) -> io::Result<()> {
+        let mut o = io::BufWriter::new(fs::File::create(path)?);
+
+        for (path, file) in &self.files {
+            writeln!(o, "TN:")?;
+            writeln!(o, "SF:{}", path.display())?;
+
+            for (line, name) in file.functions.keys() {
+                writeln!(o, "FN:{},{name}", line + 1)?;
+            }
+
+            for ((_, name), count) in &file.functions {
+                writeln!(o, "FNDA:{count},{name}")?;
+            }
+
+            let hit = file.functions.values().filter(|c| **c > 0).count();
+            writeln!(o, "FNF:{}", file.functions.len())?;
+            writeln!(o, "FNH:{hit}")?;
+
+            let mut block = 0;
+            let mut current = None;
+            let mut found = 0;
+            let mut hit = 0;
+
+            for (&(line, ..), counters) in &file.branches {
+                if current != Some(line) {
+                    current = Some(line);
+                    block = 0;
+                } else {
+                    block += 1;
+                }
+
+                found += 2;
+
+                match counters {
+                    Some((taken, not_taken)) => {
+                        writeln!(o, "BRDA:{},{block},0,{taken}", line + 1)?;
+                        writeln!(o, "BRDA:{},{block},1,{not_taken}", line + 1)?;
+                        hit += usize::from(*taken > 0) + usize::from(*not_taken > 0);
+                    }
+                    None => {
+                        writeln!(o, "BRDA:{},{block},0,-", line + 1)?;
+                        writeln!(o, "BRDA:{},{block},1,-", line + 1)?;
+                    }
+                }
+            }
+
+            writeln!(o, "BRF:{found}")?;
+            writeln!(o, "BRH:{hit}")?;
+
+            for (line, count) in &file.lines {
+                writeln!(o, "DA:{},{count}", line + 1)?;
+            }
+
+            let hit = file.lines.values().filter(|c| **c > 0).count();
+            writeln!(o, "LF:{}", file.lines.len())?;
+            writeln!(o, "LH:{hit}")?;
+            writeln!(o, "end_of_record")?;
+        }
+
+        o.flush()
+    }
+}
@@ -0,0 +1,676 @@
+//! A server for the [Debug Adapter Protocol] communicating over stdio.
+//!
+//! [Debug Adapter Protocol]: https://microsoft.github.io/debug-adapter-protocol/
+
+#[cfg(test)]
+mod tests;
+
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::{json, Value as Json};
+
+use crate::alloc::prelude::*;
+use crate::alloc::{String, Vec};
+use crate::cli::{CommandBase, Config, ExitCode, Io, SharedFlags};
+use crate::compile::FileSourceLoader;
+use crate::modules::capture_io::CaptureIo;
+use crate::runtime::{DebugFrame, DebugPause, DebugStep, DebugStop, Debugger, Formatter, VmError};
+use crate::sync::Arc;
+use crate::termcolor::Buffer;
+use crate::{Context, Diagnostics, Options, Source, SourceId, Sources, Vm};
+
+mod cli {
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    #[command(rename_all = "kebab-case")]
+    pub(crate) struct Flags {}
+}
+
+pub(super) use cli::Flags;
+
+impl CommandBase for Flags {
+    #[inline]
+    fn is_debug(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn describe(&self) -> &str {
+        "Debugging"
+    }
+}
+
+/// The identifier of the only thread being debugged.
+const THREAD_ID: u64 = 1;
+
+/// The state of a debug session.
+struct Session<'a, O> {
+    context: &'a Context,
+    options: &'a Options,
+    capture: &'a CaptureIo,
+    /// Where messages are written.
+    out: O,
+    /// Whether warnings should be reported when compiling.
+    warnings: bool,
+    /// Sequence number of the next message sent.
+    seq: u64,
+    /// Whether lines sent and received are one-based.
+    lines_start_at_1: bool,
+    /// Whether columns sent and received are one-based.
+    columns_start_at_1: bool,
+    /// Whether execution should stop at the first line.
+    stop_on_entry: bool,
+    /// Handle used to pause the program while it's running.
+    pause: DebugPause,
+    /// Whether execution was last stopped through `pause`, in which case the
+    /// stop is reported once the `pause` request is handled.
+    paused: bool,
+    /// The debugger, once a program has been launched.
+    debugger: Option<Debugger>,
+    /// Frames of the last time execution stopped, used to resolve frame and
+    /// variable references.
+    frames: Vec<DebugFrame>,
+}
+
+pub(super) async fn run(
+    io: &mut Io<'_>,
+    c: &Config,
+    shared: &SharedFlags,
+    options: &Options,
+    entry: &mut crate::cli::Entry<'_>,
+) -> Result<ExitCode> {
+    let capture = CaptureIo::new();
+    let context = shared.context(entry, c, Some(&capture))?;
+
+    let mut session = Session::new(
+        &context,
+        options,
+        &capture,
+        &mut *io.stdout,
+        shared.warnings,
+    )?;
+    serve(&mut session, io::BufReader::new(io::stdin())).await?;
+    Ok(ExitCode::Success)
+}
+
+/// Serve requests read from the given input until the client disconnects.
+///
+/// Requests are read on a separate thread, so that requests which interrupt
+/// execution such as `pause` and `disconnect` take effect while the program is
+/// running. Any other request is handled once the program has stopped.
+async fn serve<I, O>(session: &mut Session<'_, O>, input: I) -> Result<()>
+where
+    I: BufRead + Send + 'static,
+    O: Write,
+{
+    let (sender, receiver) = mpsc::channel();
+    let pause = session.pause.clone();
+    thread::spawn(move || read_requests(input, sender, pause));
+
+    while let Ok(message) = receiver.recv() {
+        let message = message?;
+
+        if message["type"] != "request" {
+            continue;
+        }
+
+        let command = message["command"]
+            .as_str()
+            .unwrap_or_default()
+            .try_to_owned()?;
+        let request_seq = message["seq"].as_u64().unwrap_or_default();
+        let arguments = &message["arguments"];
+
+        let result = session.request(&command, arguments);
+
+        let done = matches!(command.as_str(), "disconnect" | "terminate");
+
+        match result {
+            Ok(Some(body)) => {
+                session.respond(request_seq, &command, Ok(body))?;
+            }
+            Ok(None) => {
+                session.respond(request_seq, &command, Ok(Json::Null))?;
+            }
+            Err(error) => {
+                session.respond(request_seq, &command, Err(error.try_to_string()?))?;
+            }
+        }
+
+        if done {
+            break;
+        }
+
+        if command == "pause" {
+            if session.paused {
+                session.paused = false;
+                session.stopped("pause")?;
+            } else {
+                // The program wasn't running, so there is nothing to pause.
+                session.pause.clear();
+            }
+        }
+
+        // Some requests continue execution, which has to be reported after
+        // the request has been responded to.
+        if let Some(step) = resume_step(&command) {
+            if session.debugger.is_some() {
+                session.resume(step).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read requests and send them to the session, requesting that execution is
+/// paused for requests which need to interrupt a running program.
+fn read_requests<I>(mut input: I, sender: mpsc::Sender<Result<Json>>, pause: DebugPause)
+where
+    I: BufRead,
+{
+    loop {
+        let message = match read_message(&mut input) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(error) => {
+                _ = sender.send(Err(error));
+                break;
+            }
+        };
+
+        let interrupt = matches!(
+            message["command"].as_str(),
+            Some("pause" | "disconnect" | "terminate")
+        );
+
+        if sender.send(Ok(message)).is_err() {
+            break;
+        }
+
+        // Pause after the request has been sent, so that it's available once
+        // execution has stopped.
+        if interrupt {
+            pause.pause();
+        }
+    }
+}
+
+/// Get the kind of step a request resumes execution with.
+fn resume_step(command: &str) -> Option<DebugStep> {
+    match command {
+        "configurationDone" | "continue" => Some(DebugStep::Continue),
+        "next" => Some(DebugStep::Over),
+        "stepIn" => Some(DebugStep::Into),
+        "stepOut" => Some(DebugStep::Out),
+        _ => None,
+    }
+}
+
+impl<'a, O> Session<'a, O>
+where
+    O: Write,
+{
+    fn new(
+        context: &'a Context,
+        options: &'a Options,
+        capture: &'a CaptureIo,
+        out: O,
+        warnings: bool,
+    ) -> Result<Self> {
+        Ok(Self {
+            context,
+            options,
+            capture,
+            out,
+            warnings,
+            seq: 1,
+            lines_start_at_1: true,
+            columns_start_at_1: true,
+            stop_on_entry: false,
+            pause: DebugPause::new()?,
+            paused: false,
+            debugger: None,
+            frames: Vec::new(),
+        })
+    }
+
+    /// Handle a single request, returning the body of its response.
+    fn request(&mut self, command: &str, arguments: &Json) -> Result<Option<Json>> {
+        match command {
+            "initialize" => {
+                self.lines_start_at_1 = arguments["linesStartAt1"].as_bool().unwrap_or(true);
+                self.columns_start_at_1 = arguments["columnsStartAt1"].as_bool().unwrap_or(true);
+
+                Ok(Some(json!({
+                    "supportsConfigurationDoneRequest": true,
+                    "supportTerminateDebuggee": true,
+                    "supportsTerminateRequest": true,
+                })))
+            }
+            "launch" => {
+                let Some(program) = arguments["program"].as_str() else {
+                    bail!("Missing `program` to launch");
+                };
+
+                self.stop_on_entry = arguments["stopOnEntry"].as_bool().unwrap_or(false);
+                self.launch(Path::new(program))?;
+                Ok(None)
+            }
+            "setBreakpoints" => Ok(Some(self.set_breakpoints(arguments)?)),
+            "configurationDone" => Ok(None),
+            "threads" => Ok(Some(json!({
+                "threads": [{ "id": THREAD_ID, "name": "main" }],
+            }))),
+            "stackTrace" => Ok(Some(self.stack_trace()?)),
+            "scopes" => {
+                let frame = arguments["frameId"].as_u64().unwrap_or_default();
+
+                Ok(Some(json!({
+                    "scopes": [{
+                        "name": "Locals",
+                        "presentationHint": "locals",
+                        "variablesReference": frame + 1,
+                        "expensive": false,
+                    }],
+                })))
+            }
+            "variables" => {
+                let reference = arguments["variablesReference"].as_u64().unwrap_or_default();
+                Ok(Some(self.variables(reference)?))
+            }
+            "continue" => Ok(Some(json!({ "allThreadsContinued": true }))),
+            "next" | "stepIn" | "stepOut" | "pause" | "disconnect" | "terminate" => Ok(None),
+            command => bail!("Unsupported request `{command}`"),
+        }
+    }
+
+    /// Compile the given program and prepare it for execution.
+    fn launch(&mut self, path: &Path) -> Result<()> {
+        let mut sources = Sources::new();
+        sources.insert(Source::from_path(path)?)?;
+
+        let mut diagnostics = if self.warnings {
+            Diagnostics::new()
+        } else {
+            Diagnostics::without_warnings()
+        };
+
+        // Optimization passes fold away variables and the instructions lines
+        // are associated with, so programs are debugged without them.
+        let mut options = self.options.clone();
+        options.optimize(false);
+
+        let mut source_loader = FileSourceLoader::new();
+
+        let result = crate::prepare(&mut sources)
+            .with_context(self.context)
+            .with_diagnostics(&mut diagnostics)
+            .with_options(&options)
+            .with_source_loader(&mut source_loader)
+            .build();
+
+        if diagnostics.has_error() || diagnostics.has_warning() {
+            let mut out = Buffer::no_color();
+            diagnostics.emit(&mut out, &sources)?;
+            self.output(
+                "stderr",
+                &rust_alloc::string::String::from_utf8_lossy(out.as_slice()),
+            )?;
+        }
+
+        let Ok(unit) = result else {
+            bail!("Failed to compile `{}`", path.display());
+        };
+
+        let runtime = Arc::try_new(self.context.runtime()?)?;
+        let vm = Vm::new(runtime, Arc::try_new(unit)?);
+
+        self.debugger =
+            Some(Debugger::new(vm, ["main"], (), sources)?.with_pause(self.pause.clone()));
+        self.event("initialized", Json::Null)?;
+        Ok(())
+    }
+
+    /// Replace the breakpoints in a single source.
+    fn set_breakpoints(&mut self, arguments: &Json) -> Result<Json> {
+        let mut lines = Vec::new();
+
+        if let Some(breakpoints) = arguments["breakpoints"].as_array() {
+            for line in breakpoints.iter().filter_map(|b| b["line"].as_u64()) {
+                lines.try_push(self.client_line_to_line(line))?;
+            }
+        }
+
+        let path = arguments["source"]["path"].as_str().map(PathBuf::from);
+
+        let source_id = match (&self.debugger, &path) {
+            (Some(debugger), Some(path)) => find_source(debugger.sources(), path),
+            _ => None,
+        };
+
+        let placed = match (&mut self.debugger, source_id) {
+            (Some(debugger), Some(source_id)) => debugger.set_breakpoints(source_id, &lines)?,
+            _ => Vec::new(),
+        };
+
+        let mut breakpoints = Vec::new();
+
+        for (n, _) in lines.iter().enumerate() {
+            match placed.get(n).copied().flatten() {
+                Some(line) => breakpoints.try_push(json!({
+                    "verified": true,
+                    "line": self.line_to_client(line),
+                }))?,
+                None => breakpoints.try_push(json!({
+                    "verified": false,
+                    "message": "No code at or after this line",
+                }))?,
+            }
+        }
+
+        Ok(json!({ "breakpoints": breakpoints.as_slice() }))
+    }
+
+    /// Build the stack trace of the stopped execution.
+    fn stack_trace(&mut self) -> Result<Json> {
+        let Some(debugger) = &self.debugger else {
+            return Ok(json!({ "stackFrames": [], "totalFrames": 0 }));
+        };
+
+        self.frames = debugger.frames()?;
+
+        let mut stack_frames = Vec::new();
+
+        for (id, frame) in self.frames.iter().enumerate() {
+            let name = match debugger.frame_function(frame) {
+                Some((_, signature)) => signature.path.try_to_string()?,
+                None => "<unknown>".try_to_owned()?,
+            };
+
+            let mut stack_frame = json!({
+                "id": id,
+                "name": name.as_str(),
+                "line": 0,
+                "column": 0,
+            });
+
+            if let Some(location) = debugger.frame_location(frame) {
+                stack_frame["line"] = json!(self.line_to_client(location.line));
+                stack_frame["column"] = json!(self.column_to_client(location.column));
+
+                if let Some(source) = debugger.sources().get(location.source_id) {
+                    let mut json_source = json!({ "name": source.name() });
+
+                    if let Some(path) = source.path() {
+                        json_source["path"] = json!(path.display().try_to_string()?.as_str());
+                    }
+
+                    stack_frame["source"] = json_source;
+                }
+            }
+
+            stack_frames.try_push(stack_frame)?;
+        }
+
+        Ok(json!({
+            "totalFrames": stack_frames.len(),
+            "stackFrames": stack_frames.as_slice(),
+        }))
+    }
+
+    /// Get the variables for the given reference, which is the index of a
+    /// frame offset by one.
+    fn variables(&self, reference: u64) -> Result<Json> {
+        let mut variables = Vec::new();
+
+        let frame = usize::try_from(reference)
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|n| self.frames.get(n));
+
+        if let (Some(debugger), Some(frame)) = (&self.debugger, frame) {
+            for (name, value) in debugger.frame_variables(frame)? {
+                let mut s = String::new();
+
+                let formatted = debugger
+                    .vm()
+                    .with(|| Formatter::format_with(&mut s, |f| value.debug_fmt(f)));
+
+                let value = match formatted {
+                    Ok(()) => s,
+                    Err(error) => try_format!("<{error}>"),
+                };
+
+                variables.try_push(json!({
+                    "name": name,
+                    "value": value.as_str(),
+                    "variablesReference": 0,
+                }))?;
+            }
+        }
+
+        Ok(json!({ "variables": variables.as_slice() }))
+    }
+
+    /// Resume execution and report why it stopped.
+    async fn resume(&mut self, step: DebugStep) -> Result<()> {
+        let Some(debugger) = &mut self.debugger else {
+            return Ok(());
+        };
+
+        // Execution starts once configuration is done, at which point we might
+        // want to stop at the first line.
+        let (step, reason) = if self.stop_on_entry {
+            self.stop_on_entry = false;
+            (DebugStep::Into, "entry")
+        } else {
+            (step, "step")
+        };
+
+        let result = loop {
+            match debugger.resume(step).await {
+                Ok(DebugStop::Yielded(..)) => continue,
+                result => break result,
+            }
+        };
+
+        self.frames.clear();
+        self.flush_captured()?;
+
+        match result {
+            Ok(DebugStop::Breakpoint) => {
+                self.stopped("breakpoint")?;
+            }
+            Ok(DebugStop::Step) => {
+                self.stopped(reason)?;
+            }
+            Ok(DebugStop::Paused) => {
+                self.paused = true;
+            }
+            Ok(DebugStop::Yielded(..)) => {}
+            Ok(DebugStop::Complete(..)) => {
+                self.finish(0)?;
+            }
+            Err(error) => {
+                self.report_error(&error)?;
+                self.finish(1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn stopped(&mut self, reason: &str) -> Result<()> {
+        self.event(
+            "stopped",
+            json!({
+                "reason": reason,
+                "threadId": THREAD_ID,
+                "allThreadsStopped": true,
+            }),
+        )
+    }
+
+    fn finish(&mut self, exit_code: i64) -> Result<()> {
+        self.debugger = None;
+        self.event("exited", json!({ "exitCode": exit_code }))?;
+        self.event("terminated", Json::Null)
+    }
+
+    fn report_error(&mut self, error: &VmError) -> Result<()> {
+        let mut out = Buffer::no_color();
+
+        if let Some(debugger) = &self.debugger {
+            error.emit(&mut out, debugger.sources())?;
+        }
+
+        self.output(
+            "stderr",
+            &rust_alloc::string::String::from_utf8_lossy(out.as_slice()),
+        )
+    }
+
+    /// Forward anything written by the program as output events.
+    fn flush_captured(&mut self) -> Result<()> {
+        if self.capture.is_empty() {
+            return Ok(());
+        }
+
+        let output = self.capture.drain();
+        self.output(
+            "stdout",
+            &rust_alloc::string::String::from_utf8_lossy(&output),
+        )
+    }
+
+    fn output(&mut self, category: &str, output: &str) -> Result<()> {
+        self.event("output", json!({ "category": category, "output": output }))
+    }
+
+    fn event(&mut self, event: &str, body: Json) -> Result<()> {
+        let mut message = json!({
+            "seq": self.seq,
+            "type": "event",
+            "event": event,
+        });
+
+        if !body.is_null() {
+            message["body"] = body;
+        }
+
+        self.send(&message)
+    }
+
+    fn respond(
+        &mut self,
+        request_seq: u64,
+        command: &str,
+        result: Result<Json, String>,
+    ) -> Result<()> {
+        let mut message = json!({
+            "seq": self.seq,
+            "type": "response",
+            "request_seq": request_seq,
+            "command": command,
+        });
+
+        match result {
+            Ok(body) => {
+                message["success"] = json!(true);
+
+                if !body.is_null() {
+                    message["body"] = body;
+                }
+            }
+            Err(error) => {
+                message["success"] = json!(false);
+                message["message"] = json!(error.as_str());
+            }
+        }
+
+        self.send(&message)
+    }
+
+    fn send(&mut self, message: &Json) -> Result<()> {
+        self.seq += 1;
+        let body = serde_json::to_vec(message)?;
+        write!(self.out, "Content-Length: {}\r\n\r\n", body.len())?;
+        self.out.write_all(&body)?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn client_line_to_line(&self, line: u64) -> usize {
+        let line = usize::try_from(line).unwrap_or(usize::MAX);
+
+        if self.lines_start_at_1 {
+            line.saturating_sub(1)
+        } else {
+            line
+        }
+    }
+
+    fn line_to_client(&self, line: usize) -> usize {
+        line + usize::from(self.lines_start_at_1)
+    }
+
+    fn column_to_client(&self, column: usize) -> usize {
+        column + usize::from(self.columns_start_at_1)
+    }
+}
+
+/// Find the source corresponding to the given path.
+fn find_source(sources: &Sources, path: &Path) -> Option<SourceId> {
+    let path = path.canonicalize().ok()?;
+
+    sources.source_ids().find(|&id| {
+        sources
+            .get(id)
+            .and_then(|source| source.path())
+            .and_then(|p| p.canonicalize().ok())
+            .is_some_and(|p| p == path)
+    })
+}
+
+/// Read a single message framed with a `Content-Length` header.
+fn read_message<R>(input: &mut R) -> Result<Option<Json>>
+where
+    R: BufRead,
+{
+    let mut content_length = None;
+    let mut line = rust_alloc::string::String::new();
+
+    loop {
+        line.clear();
+
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let header = line.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+
+    let Some(length) = content_length else {
+        return Err(anyhow!("Missing Content-Length header"));
+    };
+
+    let mut body = rust_alloc::vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
@@ -0,0 +1,138 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::string::String;
+use std::vec::Vec;
+
+use futures_executor::block_on;
+use serde_json::{json, Value as Json};
+
+use crate::modules::capture_io::CaptureIo;
+use crate::{Context, Options};
+
+use super::{read_message, serve, Session};
+
+/// Write the given script to a temporary file.
+fn script(name: &str, source: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(std::format!(
+        "rune-debug-adapter-{name}-{}.rn",
+        std::process::id()
+    ));
+
+    std::fs::write(&path, source).unwrap();
+    path
+}
+
+/// Run a session with the given requests, returning every message sent.
+fn session(requests: &[(&str, Json)]) -> Vec<Json> {
+    let mut input = Vec::new();
+
+    for (seq, (command, arguments)) in requests.iter().enumerate() {
+        let body = serde_json::to_vec(&json!({
+            "seq": seq + 1,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        }))
+        .unwrap();
+
+        input.extend(std::format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes());
+        input.extend(body);
+    }
+
+    let context = Context::with_default_modules().unwrap();
+    let mut options = Options::default();
+    options.debug_info(true);
+    let capture = CaptureIo::new();
+
+    let mut out = Vec::new();
+    let mut session = Session::new(&context, &options, &capture, &mut out, false).unwrap();
+    block_on(serve(&mut session, Cursor::new(input))).unwrap();
+
+    let mut out = Cursor::new(out);
+    let mut messages = Vec::new();
+
+    while let Some(message) = read_message(&mut out).unwrap() {
+        messages.push(message);
+    }
+
+    messages
+}
+
+fn event<'a>(messages: &'a [Json], event: &str) -> Option<&'a Json> {
+    messages
+        .iter()
+        .find(|m| m["type"] == "event" && m["event"] == event)
+}
+
+fn response<'a>(messages: &'a [Json], command: &str) -> Option<&'a Json> {
+    messages
+        .iter()
+        .find(|m| m["type"] == "response" && m["command"] == command)
+}
+
+#[test]
+fn breakpoints() {
+    let path = script(
+        "breakpoints",
+        "pub fn main() {\n    let a = 1;\n    let b = a + 1;\n    b\n}\n",
+    );
+
+    let program = String::from(path.to_str().unwrap());
+
+    let messages = session(&[
+        ("initialize", json!({ "linesStartAt1": true })),
+        ("launch", json!({ "program": program })),
+        (
+            "setBreakpoints",
+            json!({ "source": { "path": program }, "breakpoints": [{ "line": 3 }] }),
+        ),
+        ("configurationDone", json!({})),
+        ("stackTrace", json!({ "threadId": 1 })),
+        ("scopes", json!({ "frameId": 0 })),
+        ("variables", json!({ "variablesReference": 1 })),
+        ("continue", json!({ "threadId": 1 })),
+    ]);
+
+    _ = std::fs::remove_file(&path);
+
+    let breakpoints = response(&messages, "setBreakpoints").unwrap();
+    assert_eq!(breakpoints["body"]["breakpoints"][0]["verified"], true);
+    assert_eq!(breakpoints["body"]["breakpoints"][0]["line"], 3);
+
+    let stopped = event(&messages, "stopped").unwrap();
+    assert_eq!(stopped["body"]["reason"], "breakpoint");
+
+    let trace = response(&messages, "stackTrace").unwrap();
+    assert_eq!(trace["body"]["stackFrames"][0]["line"], 3);
+
+    let variables = response(&messages, "variables").unwrap();
+    assert_eq!(variables["body"]["variables"][0]["name"], "a");
+    assert_eq!(variables["body"]["variables"][0]["value"], "1");
+
+    let exited = event(&messages, "exited").unwrap();
+    assert_eq!(exited["body"]["exitCode"], 0);
+}
+
+#[test]
+fn pause_running_program() {
+    let path = script("pause", "pub fn main() {\n    loop {}\n}\n");
+    let program = String::from(path.to_str().unwrap());
+
+    let messages = session(&[
+        ("initialize", json!({})),
+        ("launch", json!({ "program": program })),
+        ("configurationDone", json!({})),
+        ("pause", json!({ "threadId": 1 })),
+        ("disconnect", json!({})),
+    ]);
+
+    _ = std::fs::remove_file(&path);
+
+    assert_eq!(response(&messages, "pause").unwrap()["success"], true);
+
+    let stopped = event(&messages, "stopped").unwrap();
+    assert_eq!(stopped["body"]["reason"], "pause");
+
+    assert_eq!(response(&messages, "disconnect").unwrap()["success"], true);
+    assert!(event(&messages, "exited").is_none());
+}
@@ -1,6 +1,7 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use similar::{ChangeTag, TextDiff};
 
@@ -9,6 +10,7 @@ use crate::alloc::BTreeSet;
 use crate::cli::{AssetKind, CommandBase, Config, Entry, EntryPoint, ExitCode, Io, SharedFlags};
 use crate::support::{Context, Result};
 use crate::termcolor::{Color, ColorSpec, WriteColor};
+use crate::workspace;
 use crate::{Diagnostics, Options, Source, Sources};
 
 mod cli {
@@ -115,7 +117,14 @@ where
         }
     }
 
+    let mut configs = HashMap::new();
+
     for path in paths {
+        let Some(options) = file_options(io, &mut configs, &path, shared, options)? else {
+            failed += 1;
+            continue;
+        };
+
         let mut sources = Sources::new();
 
         sources.insert(match Source::from_path(&path) {
@@ -126,7 +135,7 @@ where
         let mut diagnostics = Diagnostics::new();
 
         let build = crate::fmt::prepare(&sources)
-            .with_options(options)
+            .with_options(&options)
             .with_diagnostics(&mut diagnostics);
 
         let result = build.format();
@@ -223,12 +232,68 @@ where
     Ok(ExitCode::Success)
 }
 
+/// Construct the options used to format the file at `path`.
+///
+/// This applies the closest `rune-fmt.toml` or `Rune.toml` configuration, after
+/// which options passed with `-O` are re-applied so that they take precedence.
+/// Returns `None` if the configuration failed to load.
+fn file_options(
+    io: &mut Io<'_>,
+    configs: &mut HashMap<PathBuf, Option<Options>>,
+    path: &Path,
+    shared: &SharedFlags,
+    options: &Options,
+) -> Result<Option<Options>> {
+    let Some(config) = path.parent().and_then(workspace::find_fmt_config) else {
+        return Ok(Some(options.clone()));
+    };
+
+    if let Some(options) = configs.get(&config) {
+        return Ok(options.clone());
+    }
+
+    let mut sources = Sources::new();
+
+    sources.insert(match Source::from_path(&config) {
+        Ok(source) => source,
+        Err(error) => return Err(error).context(config.display().try_to_string()?),
+    })?;
+
+    let mut diagnostics = workspace::Diagnostics::new();
+
+    let result = workspace::prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    diagnostics.emit(io.stdout, &sources)?;
+
+    let loaded = match result {
+        Ok(manifest) => {
+            let mut options = options.clone();
+
+            for option in &manifest.fmt {
+                options.parse_option(option)?;
+            }
+
+            for option in &shared.compiler_option {
+                options.parse_option(option)?;
+            }
+
+            Some(options)
+        }
+        Err(..) => None,
+    };
+
+    configs.insert(config, loaded.clone());
+    Ok(loaded)
+}
+
 fn diff(io: &mut Io, source: &str, val: &str, col: &Colors) -> Result<(), anyhow::Error> {
     let diff = TextDiff::from_lines(source, val);
 
     for (idx, group) in diff.grouped_ops(3).iter().enumerate() {
         if idx > 0 {
-            println!("{:-^1$}", "-", 80);
+            writeln!(io.stdout, "{:-^1$}", "-", 80)?;
         }
 
         for op in group {
@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use anyhow::{anyhow, Context as _, Result};
 
 use crate::alloc::{Vec, VecDeque};
-use crate::cli::{visitor, Io, SharedFlags};
+use crate::cli::{build, visitor, Io, SharedFlags};
 use crate::compile::FileSourceLoader;
 use crate::sync::Arc;
 use crate::{Context, Diagnostics, Hash, ItemBuf, Options, Source, Sources, Unit};
@@ -26,6 +26,22 @@ pub(super) fn load(
     path: &Path,
     attribute: visitor::Attribute,
 ) -> Result<Load> {
+    if path.extension() == Some(OsStr::new(build::EXTENSION)) {
+        tracing::trace!("loading compiled unit: {}", path.display());
+
+        let bytes =
+            fs::read(path).with_context(|| anyhow!("cannot read file: {}", path.display()))?;
+        let runtime = context.runtime()?;
+        let unit = Unit::load(&bytes, &runtime)
+            .with_context(|| anyhow!("cannot load compiled unit: {}", path.display()))?;
+
+        return Ok(Load {
+            unit: Arc::try_new(unit)?,
+            sources: Sources::new(),
+            functions: Vec::new(),
+        });
+    }
+
     let bytecode_path = path.with_extension("rnc");
 
     let source =
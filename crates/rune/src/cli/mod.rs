@@ -8,13 +8,18 @@
 
 mod ace;
 mod benches;
+mod build;
 mod check;
+mod coverage;
+mod debug_adapter;
 mod doc;
 mod format;
 mod languageserver;
 mod loader;
 mod naming;
 mod out;
+mod profile;
+mod repl;
 mod run;
 mod tests;
 mod visitor;
@@ -36,6 +41,7 @@ use clap::{Parser, Subcommand, ValueEnum};
 use tracing_subscriber::filter::EnvFilter;
 
 use crate::compile::ParseOptionError;
+use crate::diagnostics::{Lint, LintLevel};
 use crate::modules::capture_io::CaptureIo;
 use crate::termcolor::{ColorChoice, StandardStream};
 use crate::{Context, ContextError, Hash, ItemBuf, Options};
@@ -300,7 +306,7 @@ where
     T: CommandBase + clap::Args,
 {
     /// Construct compiler options from arguments.
-    fn options(&self) -> Result<Options, ParseOptionError> {
+    fn options(&self, c: &Config) -> Result<Options, ParseOptionError> {
         let mut options = Options::from_default_env()?;
 
         // Command-specific override defaults.
@@ -310,10 +316,26 @@ where
             options.bytecode(false);
         }
 
+        for &(lint, level) in &c.lints {
+            options.lint_level(lint, level);
+        }
+
         for option in &self.shared.compiler_option {
             options.parse_option(option)?;
         }
 
+        let levels = [
+            (&self.shared.allow, LintLevel::Allow),
+            (&self.shared.warn, LintLevel::Warn),
+            (&self.shared.deny, LintLevel::Deny),
+        ];
+
+        for (names, level) in levels {
+            for name in names {
+                options.parse_option(&format!("lint.{name}={level}"))?;
+            }
+        }
+
         Ok(options)
     }
 }
@@ -416,6 +438,8 @@ trait CommandBase {
 enum Command {
     /// Run checks but do not execute
     Check(CommandShared<check::Flags>),
+    /// Compile a script into a unit which can be run without the compiler
+    Build(CommandShared<build::Flags>),
     /// Build documentation.
     Doc(CommandShared<doc::Flags>),
     /// Build ace autocompletion.
@@ -426,37 +450,47 @@ enum Command {
     Bench(CommandShared<benches::Flags>),
     /// Run the designated script
     Run(CommandShared<run::Flags>),
+    /// Start an interactive read-eval-print loop
+    Repl(CommandShared<repl::Flags>),
     /// Format the provided file
     Fmt(CommandShared<format::Flags>),
     /// Run a language server.
     LanguageServer(SharedFlags),
+    /// Run a debug adapter speaking the Debug Adapter Protocol over stdio.
+    DebugAdapter(CommandShared<debug_adapter::Flags>),
     /// Helper command to generate type hashes.
     Hash(HashFlags),
 }
 
 impl Command {
-    const ALL: [&'static str; 9] = [
+    const ALL: [&'static str; 12] = [
         "check",
+        "build",
         "doc",
         "ace",
         "test",
         "bench",
         "run",
+        "repl",
         "fmt",
         "languageserver",
+        "debug-adapter",
         "hash",
     ];
 
     fn as_command_base_mut(&mut self) -> Option<(&mut SharedFlags, &mut dyn CommandBase)> {
         let (shared, command): (_, &mut dyn CommandBase) = match self {
             Command::Check(shared) => (&mut shared.shared, &mut shared.command),
+            Command::Build(shared) => (&mut shared.shared, &mut shared.command),
             Command::Doc(shared) => (&mut shared.shared, &mut shared.command),
             Command::Ace(shared) => (&mut shared.shared, &mut shared.command),
             Command::Test(shared) => (&mut shared.shared, &mut shared.command),
             Command::Bench(shared) => (&mut shared.shared, &mut shared.command),
             Command::Run(shared) => (&mut shared.shared, &mut shared.command),
             Command::Fmt(shared) => (&mut shared.shared, &mut shared.command),
+            Command::Repl(..) => return None,
             Command::LanguageServer(..) => return None,
+            Command::DebugAdapter(..) => return None,
             Command::Hash(..) => return None,
         };
 
@@ -466,13 +500,16 @@ impl Command {
     fn as_command_shared_ref(&self) -> Option<CommandSharedRef<'_>> {
         let (shared, command): (_, &dyn CommandBase) = match self {
             Command::Check(shared) => (&shared.shared, &shared.command),
+            Command::Build(shared) => (&shared.shared, &shared.command),
             Command::Doc(shared) => (&shared.shared, &shared.command),
             Command::Ace(shared) => (&shared.shared, &shared.command),
             Command::Test(shared) => (&shared.shared, &shared.command),
             Command::Bench(shared) => (&shared.shared, &shared.command),
             Command::Run(shared) => (&shared.shared, &shared.command),
             Command::Fmt(shared) => (&shared.shared, &shared.command),
+            Command::Repl(..) => return None,
             Command::LanguageServer(..) => return None,
+            Command::DebugAdapter(..) => return None,
             Command::Hash(..) => return None,
         };
 
@@ -499,6 +536,8 @@ struct Config {
     all_targets: bool,
     /// Manifest root directory.
     manifest_root: Option<PathBuf>,
+    /// Lint levels configured in the manifest.
+    lints: alloc::Vec<(Lint, LintLevel)>,
 }
 
 #[derive(Default)]
@@ -648,6 +687,23 @@ struct SharedFlags {
     #[arg(long)]
     list_options: bool,
 
+    /// Allow the given lint or group of lints.
+    #[arg(short = 'A', long = "allow", value_name = "LINT", num_args = 1)]
+    allow: Vec<String>,
+
+    /// Warn about the given lint or group of lints.
+    #[arg(short = 'W', long = "warn", value_name = "LINT", num_args = 1)]
+    warn: Vec<String>,
+
+    /// Deny the given lint or group of lints, which causes compilation to fail
+    /// if it's encountered.
+    #[arg(short = 'D', long = "deny", value_name = "LINT", num_args = 1)]
+    deny: Vec<String>,
+
+    /// List available lints.
+    #[arg(long)]
+    list_lints: bool,
+
     /// Run with the following binary from a loaded manifest. This requires a
     /// `Rune.toml` manifest.
     #[arg(long)]
@@ -800,6 +856,11 @@ fn populate_config(
 
     diagnostics.emit(io.stdout, &sources)?;
     inputs.manifest = result?;
+
+    for &(lint, level) in &inputs.manifest.lints {
+        c.lints.try_push((lint, level))?;
+    }
+
     Ok(())
 }
 
@@ -857,6 +918,41 @@ async fn main_with_out(io: &mut Io<'_>, entry: &mut Entry<'_>, mut args: Args) -
             return Ok(ExitCode::Success);
         }
 
+        if cmd.shared.list_lints {
+            writeln!(
+                io.stdout,
+                "Available lints (set with -A, -W or -D <lint>, or in the [lints] table of {}):",
+                workspace::MANIFEST_FILE
+            )?;
+            writeln!(io.stdout)?;
+
+            for lint in Lint::ALL {
+                io.write(
+                    format_args!("{}", lint.name()),
+                    Stream::Stdout,
+                    Color::Highlight,
+                )?;
+
+                writeln!(io.stdout, "={}", lint.default_level())?;
+                writeln!(io.stdout, "    {}", lint.description())?;
+            }
+
+            for &(group, lints) in Lint::GROUPS {
+                writeln!(io.stdout)?;
+                write!(io.stdout, "Group ")?;
+                io.write(group, Stream::Stdout, Color::Highlight)?;
+                write!(io.stdout, " includes:")?;
+
+                for lint in lints {
+                    write!(io.stdout, " {lint}")?;
+                }
+
+                writeln!(io.stdout)?;
+            }
+
+            return Ok(ExitCode::Success);
+        }
+
         populate_config(io, &mut c, &mut inputs, cmd)?;
 
         let build_paths = inputs.build_paths(cmd, &mut c)?;
@@ -915,7 +1011,7 @@ where
 {
     match cmd {
         Command::Check(f) => {
-            let options = f.options()?;
+            let options = f.options(c)?;
 
             for e in entries {
                 let mut options = options.clone();
@@ -930,20 +1026,24 @@ where
                 }
             }
         }
+        Command::Build(f) => {
+            let options = f.options(c)?;
+            return build::run(io, entry, c, &f.command, &f.shared, &options, entries);
+        }
         Command::Doc(f) => {
-            let options = f.options()?;
+            let options = f.options(c)?;
             return doc::run(io, entry, c, &f.command, &f.shared, &options, entries);
         }
         Command::Ace(f) => {
-            let options = f.options()?;
+            let options = f.options(c)?;
             return ace::run(io, entry, c, &f.command, &f.shared, &options, entries);
         }
         Command::Fmt(f) => {
-            let options = f.options()?;
+            let options = f.options(c)?;
             return format::run(io, entry, c, entries, &f.command, &f.shared, &options);
         }
         Command::Test(f) => {
-            let options = f.options()?;
+            let options = f.options(c)?;
 
             match tests::run(io, c, &f.command, &f.shared, &options, entry, entries).await? {
                 ExitCode::Success => (),
@@ -951,7 +1051,7 @@ where
             }
         }
         Command::Bench(f) => {
-            let options = f.options()?;
+            let options = f.options(c)?;
 
             for e in entries {
                 let mut options = options.clone();
@@ -989,7 +1089,7 @@ where
             }
         }
         Command::Run(f) => {
-            let options = f.options()?;
+            let options = f.options(c)?;
             let context = f.shared.context(entry, c, None)?;
 
             for e in entries {
@@ -1008,7 +1108,9 @@ where
                     visitor::Attribute::None,
                 )?;
 
-                let entry = if e.is_argument() {
+                // Compiled units carry a script entrypoint if they were built
+                // from one.
+                let entry = if e.is_argument() && load.unit.function(&Hash::EMPTY).is_some() {
                     Hash::EMPTY
                 } else {
                     Hash::type_hash(["main"])
@@ -1021,10 +1123,19 @@ where
                 }
             }
         }
+        Command::Repl(f) => {
+            let options = f.options(c)?;
+            let context = f.shared.context(entry, c, None)?;
+            return repl::run(io, c, &f.command, &f.shared, &options, &context).await;
+        }
         Command::LanguageServer(shared) => {
             let context = shared.context(entry, c, None)?;
             languageserver::run(context).await?;
         }
+        Command::DebugAdapter(f) => {
+            let options = f.options(c)?;
+            return debug_adapter::run(io, c, &f.shared, &options, entry).await;
+        }
         Command::Hash(args) => {
             use rand::prelude::*;
 
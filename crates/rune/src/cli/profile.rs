@@ -0,0 +1,375 @@
+//! An instrumenting profiler which attributes instructions and time to the
+//! functions being executed.
+
+#[cfg(test)]
+mod tests;
+
+use core::cmp::Reverse;
+use core::time::Duration;
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use anyhow::Result;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, HashMap};
+use crate::compile::meta;
+use crate::runtime::{inst, Function, Inline, Repr, VmError, VmExecution, VmOutcome};
+use crate::TypeHash;
+use crate::{Context, Hash, Value, Vm};
+
+/// The name used for a frame which could not be identified.
+const UNKNOWN: &str = "<unknown>";
+
+/// The cost attributed to a single stack.
+#[derive(Default, Clone, Copy, TryClone)]
+#[try_clone(crate, copy)]
+struct Cost {
+    instructions: u64,
+    duration: Duration,
+}
+
+/// A collected profile.
+pub(super) struct Profile {
+    /// Interned frame names.
+    names: Vec<String>,
+    /// Lookup for interned frame names.
+    lookup: HashMap<String, usize>,
+    /// The cost of each distinct stack of interned frames.
+    stacks: HashMap<Vec<usize>, Cost>,
+    /// When the time spent was last attributed to a stack, or `None` if time
+    /// is not being measured.
+    last: Option<Instant>,
+}
+
+impl Profile {
+    fn new(timed: bool) -> Self {
+        Self {
+            names: Vec::new(),
+            lookup: HashMap::new(),
+            stacks: HashMap::new(),
+            last: timed.then(Instant::now),
+        }
+    }
+
+    fn intern(&mut self, name: &str) -> alloc::Result<usize> {
+        if let Some(&index) = self.lookup.get(name) {
+            return Ok(index);
+        }
+
+        let index = self.names.len();
+        self.names.try_push(name.try_to_owned()?)?;
+        self.lookup.try_insert(name.try_to_owned()?, index)?;
+        Ok(index)
+    }
+
+    fn cost(&mut self, stack: &[usize]) -> alloc::Result<&mut Cost> {
+        if !self.stacks.contains_key(stack) {
+            self.stacks
+                .try_insert(Vec::try_from(stack)?, Cost::default())?;
+        }
+
+        Ok(self.stacks.get_mut(stack).expect("cost was just inserted"))
+    }
+
+    /// Count an instruction executed in the given stack.
+    fn count(&mut self, stack: &[usize]) -> alloc::Result<()> {
+        self.cost(stack)?.instructions += 1;
+        Ok(())
+    }
+
+    /// Attribute the time spent since the last lap to the given stack.
+    ///
+    /// This is only called when the stack changes, so that the clock isn't
+    /// read for every instruction.
+    fn lap(&mut self, stack: &[usize]) -> alloc::Result<()> {
+        let Some(last) = self.last else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        self.last = Some(now);
+
+        if !stack.is_empty() {
+            self.cost(stack)?.duration += now.saturating_duration_since(last);
+        }
+
+        Ok(())
+    }
+
+    /// The weight of the given cost, which is the number of nanoseconds spent
+    /// if time is measured and otherwise the number of instructions executed.
+    fn weight(&self, cost: &Cost) -> u64 {
+        if self.last.is_some() {
+            u64::try_from(cost.duration.as_nanos()).unwrap_or(u64::MAX)
+        } else {
+            cost.instructions
+        }
+    }
+
+    /// Collect the profile as sorted collapsed stacks, where each stack is
+    /// paired with its weight.
+    fn collapsed(&self) -> alloc::Result<Vec<(String, u64)>> {
+        let mut lines = Vec::try_with_capacity(self.stacks.len())?;
+
+        for (stack, cost) in &self.stacks {
+            let mut line = String::new();
+
+            for (n, &index) in stack.iter().enumerate() {
+                if n > 0 {
+                    line.try_push(';')?;
+                }
+
+                line.try_push_str(&self.names[index])?;
+            }
+
+            lines.try_push((line, self.weight(cost)))?;
+        }
+
+        lines.sort();
+        Ok(lines)
+    }
+
+    /// Write the profile as collapsed stacks, which is the format expected by
+    /// flamegraph tooling such as `inferno` or `flamegraph.pl`.
+    ///
+    /// Each stack is weighted by the number of nanoseconds spent in it, or by
+    /// the number of instructions executed if time is not measured.
+    pub(super) fn write_collapsed(&self, path: &Path) -> Result<()> {
+        let lines = self.collapsed()?;
+        let mut out = io::BufWriter::new(fs::File::create(path)?);
+
+        for (line, weight) in lines {
+            writeln!(out, "{line} {weight}")?;
+        }
+
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Write a summary of the frames with the most time spent in them, or the
+    /// most instructions executed if time is not measured, to the given
+    /// output.
+    pub(super) fn write_summary<O>(&self, o: &mut O, limit: usize) -> Result<()>
+    where
+        O: ?Sized + Write,
+    {
+        let mut totals = try_vec![Cost::default(); self.names.len()];
+        let mut total = Cost::default();
+
+        for (stack, cost) in &self.stacks {
+            total.instructions += cost.instructions;
+            total.duration += cost.duration;
+
+            if let Some(&index) = stack.last() {
+                totals[index].instructions += cost.instructions;
+                totals[index].duration += cost.duration;
+            }
+        }
+
+        let mut totals = totals.into_iter().enumerate().try_collect::<Vec<_>>()?;
+
+        totals.sort_by_key(|(_, cost)| Reverse(self.weight(cost)));
+
+        if self.last.is_some() {
+            writeln!(
+                o,
+                "== profile: {} instructions ({:?})",
+                total.instructions, total.duration
+            )?;
+        } else {
+            writeln!(o, "== profile: {} instructions", total.instructions)?;
+        }
+
+        let weight = self.weight(&total);
+
+        for (index, cost) in totals.into_iter().take(limit) {
+            let percent = if weight == 0 {
+                0.0
+            } else {
+                self.weight(&cost) as f64 / weight as f64 * 100.0
+            };
+
+            if self.last.is_some() {
+                writeln!(
+                    o,
+                    "  {percent:>6.2}% {:>12?} {:>10} {}",
+                    cost.duration, cost.instructions, self.names[index]
+                )?;
+            } else {
+                writeln!(
+                    o,
+                    "  {percent:>6.2}% {:>10} {}",
+                    cost.instructions, self.names[index]
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Run the given execution to completion one instruction at a time, recording
+/// where instructions are executed and, if `timed` is set, where time is spent.
+///
+/// Time spent inside of a call to a native function is attributed to a frame
+/// named after the native function.
+pub(super) async fn profile<T>(
+    context: &Context,
+    execution: &mut VmExecution<T>,
+    timed: bool,
+) -> (Profile, Result<Value, VmError>)
+where
+    T: AsRef<Vm> + AsMut<Vm>,
+{
+    let mut profile = Profile::new(timed);
+    let result = run(context, execution, &mut profile).await;
+    (profile, result)
+}
+
+async fn run<T>(
+    context: &Context,
+    execution: &mut VmExecution<T>,
+    profile: &mut Profile,
+) -> Result<Value, VmError>
+where
+    T: AsRef<Vm> + AsMut<Vm>,
+{
+    let mut natives = HashMap::<Hash, String>::new();
+
+    for (meta, _) in context.iter_functions() {
+        let name = match (&meta.item, &meta.kind) {
+            (Some(item), _) => item.try_to_string()?,
+            // Protocol functions don't have an item, so name them after their
+            // container instead.
+            (
+                None,
+                meta::Kind::Function {
+                    associated: Some(associated),
+                    container: Some(container),
+                    ..
+                },
+            ) => {
+                let Some(item) = context
+                    .lookup_meta_by_hash(*container)
+                    .find_map(|meta| meta.item.as_ref())
+                else {
+                    continue;
+                };
+
+                try_format!("{item}::{associated}")
+            }
+            _ => continue,
+        };
+
+        natives.try_insert(meta.hash, name)?;
+    }
+
+    let mut stack = Vec::new();
+
+    loop {
+        let vm = execution.vm();
+        let ip = vm.ip();
+        let depth = vm.call_frames().len();
+
+        // Resynchronize the stack of frames in case it has diverged from the
+        // virtual machine, such as when execution first starts.
+        if stack.len() != depth + 1 {
+            profile.lap(&stack)?;
+            stack.clear();
+
+            for frame in vm.call_frames() {
+                let name = function_name(vm, frame.ip.saturating_sub(1))?;
+                stack.try_push(profile.intern(&name)?)?;
+            }
+
+            let name = function_name(vm, ip)?;
+            stack.try_push(profile.intern(&name)?)?;
+        }
+
+        let native = match call_target(vm, ip).and_then(|hash| natives.get(&hash)) {
+            Some(name) => Some(profile.intern(name)?),
+            None => None,
+        };
+
+        if native.is_some() {
+            profile.lap(&stack)?;
+        }
+
+        let outcome = execution.resume().with_budget(1).await;
+
+        let vm = execution.vm();
+        let current = vm.call_frames().len();
+
+        match native {
+            // Calls into script functions push a frame, so only attribute the
+            // instruction to the native function if that did not happen.
+            Some(native) if current <= depth => {
+                stack.try_push(native)?;
+                profile.count(&stack)?;
+                profile.lap(&stack)?;
+                stack.pop();
+            }
+            _ => {
+                profile.count(&stack)?;
+            }
+        }
+
+        // Anything but running out of budget means that the execution has
+        // halted, in which case it's treated the same way as when running it.
+        if !matches!(outcome, Ok(VmOutcome::Limited)) {
+            profile.lap(&stack)?;
+            return outcome.and_then(VmOutcome::into_complete);
+        }
+
+        if current > depth {
+            profile.lap(&stack)?;
+            let name = function_name(vm, vm.ip())?;
+            stack.try_push(profile.intern(&name)?)?;
+        } else if current < depth {
+            profile.lap(&stack)?;
+            stack.truncate(stack.len().saturating_sub(depth - current));
+        }
+    }
+}
+
+/// Get the hash of the function called by the instruction at `ip`, if it is a
+/// call.
+fn call_target(vm: &Vm, ip: usize) -> Option<Hash> {
+    let (inst, _) = vm.unit().instruction_at(ip).ok()??;
+
+    match inst.kind {
+        inst::Kind::Call { hash, .. } => Some(hash),
+        inst::Kind::CallAssociated { hash, addr, .. } => {
+            let type_hash = vm.stack().at(addr).type_hash();
+            Some(Hash::associated_function(type_hash, hash))
+        }
+        inst::Kind::CallFn { function, .. } => match vm.stack().at(function).as_ref() {
+            Repr::Inline(Inline::Type(ty)) => Some(ty.into_hash()),
+            Repr::Any(value) if value.type_hash() == Function::HASH => {
+                Some(value.borrow_ref::<Function>().ok()?.type_hash())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn function_name(vm: &Vm, ip: usize) -> alloc::Result<String> {
+    let Some((_, signature)) = vm
+        .unit()
+        .debug_info()
+        .and_then(|debug| debug.function_containing(ip))
+    else {
+        return UNKNOWN.try_to_owned();
+    };
+
+    if signature.path.is_empty() {
+        return "{root}".try_to_owned();
+    }
+
+    signature.path.try_to_string()
+}
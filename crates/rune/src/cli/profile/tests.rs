@@ -0,0 +1,90 @@
+use futures_executor::block_on;
+
+use crate::sync::Arc;
+use crate::{Context, Source, Sources, Vm};
+
+use super::profile;
+
+/// Profile the given script by instructions and collect its collapsed stacks.
+fn collapsed(source: &str) -> std::vec::Vec<(std::string::String, u64)> {
+    let context = Context::with_default_modules().unwrap();
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source).unwrap()).unwrap();
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .build()
+        .unwrap();
+
+    let runtime = Arc::try_new(context.runtime().unwrap()).unwrap();
+    let mut vm = Vm::new(runtime, Arc::try_new(unit).unwrap());
+    let mut execution = vm.execute(["main"], ()).unwrap();
+
+    let (profile, result) = block_on(profile(&context, &mut execution, false));
+    result.unwrap();
+
+    profile
+        .collapsed()
+        .unwrap()
+        .into_iter()
+        .map(|(line, weight)| (std::string::String::from(line.as_str()), weight))
+        .collect()
+}
+
+#[test]
+fn collapsed_stacks() {
+    let lines = collapsed(
+        r#"
+        fn inner() { 1 }
+        fn outer() { inner() + inner() }
+        pub fn main() { outer() + [1, 2].len() }
+        "#,
+    );
+
+    let stacks = lines
+        .iter()
+        .map(|(line, _)| line.as_str())
+        .collect::<std::vec::Vec<_>>();
+
+    assert_eq!(
+        stacks,
+        [
+            "main",
+            "main;::std::vec::Vec::len",
+            "main;outer",
+            "main;outer;inner",
+        ]
+    );
+
+    assert!(lines.iter().all(|(_, weight)| *weight > 0));
+
+    let inner = lines.iter().find(|(line, _)| line == "main;outer;inner");
+    let vec_len = lines
+        .iter()
+        .find(|(line, _)| line == "main;::std::vec::Vec::len");
+    assert_eq!(inner.map(|(_, weight)| weight % 2), Some(0));
+    assert_eq!(vec_len.map(|(_, weight)| *weight), Some(1));
+}
+
+#[test]
+fn yield_is_an_error() {
+    let context = Context::with_default_modules().unwrap();
+
+    let mut sources = Sources::new();
+    sources
+        .insert(Source::memory("pub fn main() { yield 1; }").unwrap())
+        .unwrap();
+
+    let unit = crate::prepare(&mut sources)
+        .with_context(&context)
+        .build()
+        .unwrap();
+
+    let runtime = Arc::try_new(context.runtime().unwrap()).unwrap();
+    let mut vm = Vm::new(runtime, Arc::try_new(unit).unwrap());
+    let mut execution = vm.execute(["main"], ()).unwrap();
+
+    let (_, result) = block_on(profile(&context, &mut execution, true));
+    assert!(result.is_err());
+}
@@ -0,0 +1,526 @@
+#[cfg(test)]
+mod tests;
+
+use std::io::{self, BufRead};
+use std::time::Instant;
+
+use anyhow::Result;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, try_format, String, Vec};
+use crate::ast::{self, Spanned};
+use crate::cli::{CommandBase, Config, ExitCode, Io, SharedFlags};
+use crate::runtime::{Formatter, VmOutcome};
+use crate::sync::Arc;
+use crate::termcolor::WriteColor;
+use crate::{Context, Diagnostics, Hash, Options, Source, SourceId, Sources, Unit, Value, Vm};
+
+mod cli {
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    #[command(rename_all = "kebab-case")]
+    pub(crate) struct Flags {
+        /// Time how long each evaluation took.
+        #[arg(long)]
+        pub(super) time: bool,
+    }
+}
+
+pub(super) use cli::Flags;
+
+impl CommandBase for Flags {
+    #[inline]
+    fn describe(&self) -> &str {
+        "Evaluating"
+    }
+}
+
+/// The name of the function which evaluates the current input.
+const EVAL: &str = "__repl_eval";
+
+const HELP: &str = r#"Enter items such as `fn`, `struct` or `use` declarations to define them,
+or statements and expressions to evaluate them. Defining an item with the
+same name as an existing one replaces it. Bindings introduced with `let` are
+kept between inputs.
+
+Commands:
+  :help   Show this help.
+  :reset  Forget all definitions and bindings.
+  :quit   Exit the repl."#;
+
+/// A single input which has been split up into items and an evaluation.
+struct Input<'a> {
+    /// Items defined by the input, with their names if they have one.
+    items: Vec<(Option<String>, &'a str)>,
+    /// Code to evaluate, if the input contains any statements or expressions.
+    eval: Option<Eval>,
+}
+
+/// An item which has been defined.
+struct Defined {
+    /// The name of the item, if it has one. Defining a new item with the same
+    /// name replaces this one.
+    name: Option<String>,
+    /// The source of the item.
+    source: Source,
+}
+
+impl TryClone for Defined {
+    fn try_clone(&self) -> alloc::Result<Self> {
+        Ok(Self {
+            name: self.name.try_clone()?,
+            source: self.source.try_clone()?,
+        })
+    }
+}
+
+/// Code generated to evaluate an input.
+struct Eval {
+    /// The source of the evaluation function.
+    source: String,
+    /// The names of all bindings after evaluation.
+    names: Vec<String>,
+}
+
+/// The state of the repl which persists between inputs.
+struct Repl<'a> {
+    context: &'a Context,
+    options: &'a Options,
+    /// Emit warnings when compiling.
+    warnings: bool,
+    /// Time how long each evaluation took.
+    time: bool,
+    /// Items which have been defined.
+    items: Vec<Defined>,
+    /// Bindings introduced through `let` statements.
+    bindings: Vec<(String, Value)>,
+    /// The number of sources constructed, used to name them.
+    count: usize,
+    vm: Vm,
+}
+
+pub(super) async fn run(
+    io: &mut Io<'_>,
+    _: &Config,
+    flags: &Flags,
+    shared: &SharedFlags,
+    options: &Options,
+    context: &Context,
+) -> Result<ExitCode> {
+    let mut repl = Repl::new(context, options, shared.warnings, flags.time)?;
+    let stdin = io::stdin();
+    repl.run(&mut stdin.lock(), io.stdout).await?;
+    Ok(ExitCode::Success)
+}
+
+impl<'a> Repl<'a> {
+    fn new(context: &'a Context, options: &'a Options, warnings: bool, time: bool) -> Result<Self> {
+        let runtime = Arc::try_new(context.runtime()?)?;
+        let unit = Arc::try_new(Unit::default())?;
+
+        Ok(Self {
+            context,
+            options,
+            warnings,
+            time,
+            items: Vec::new(),
+            bindings: Vec::new(),
+            count: 0,
+            vm: Vm::new(runtime, unit),
+        })
+    }
+
+    /// Read and process lines from the given input until it ends or the user
+    /// quits.
+    async fn run<O>(&mut self, input: &mut impl BufRead, out: &mut O) -> Result<()>
+    where
+        O: WriteColor,
+    {
+        let mut pending = String::new();
+        let mut line = std::string::String::new();
+
+        loop {
+            write!(out, "{}", if pending.is_empty() { "> " } else { ". " })?;
+            out.flush()?;
+
+            line.clear();
+
+            if input.read_line(&mut line)? == 0 {
+                if pending.is_empty() {
+                    writeln!(out)?;
+                    break;
+                }
+
+                // End of input while a multi-line input is pending, evaluate it
+                // so that any errors are reported.
+                self.input(out, &pending, true).await?;
+                break;
+            }
+
+            if pending.is_empty() {
+                match line.trim() {
+                    "" => continue,
+                    ":quit" | ":q" | ":exit" => break,
+                    ":help" | ":h" => {
+                        writeln!(out, "{HELP}")?;
+                        continue;
+                    }
+                    ":reset" => {
+                        self.items.clear();
+                        self.bindings.clear();
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+
+            pending.try_push_str(&line)?;
+
+            if self.input(out, &pending, false).await? {
+                pending.clear();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process the given input, returning `false` if it is incomplete and more
+    /// lines need to be read.
+    async fn input<O>(&mut self, out: &mut O, input: &str, eof: bool) -> Result<bool>
+    where
+        O: WriteColor,
+    {
+        let parsed = match self.parse(input) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                let at_end = error.span().range().start >= input.trim_end().len();
+
+                if at_end && !eof {
+                    return Ok(false);
+                }
+
+                let mut sources = Sources::new();
+                sources.insert(self.source(input)?)?;
+                let mut diagnostics = Diagnostics::new();
+                diagnostics.error(SourceId::new(0), error)?;
+                diagnostics.emit(out, &sources)?;
+                return Ok(true);
+            }
+        };
+
+        let Some(parsed) = parsed else {
+            return Ok(eof);
+        };
+
+        let mut items = Vec::new();
+
+        for (name, text) in parsed.items {
+            let source = self.source(text)?;
+            items.try_push(Defined { name, source })?;
+        }
+
+        let items = self.define(items)?;
+
+        let eval = match parsed.eval {
+            Some(eval) => Some((self.source(&eval.source)?, eval.names)),
+            None => None,
+        };
+
+        let Some((unit, sources)) = self.compile(out, &items, eval.as_ref().map(|(s, _)| s))?
+        else {
+            return Ok(true);
+        };
+
+        self.items = items;
+
+        if let Some((_, names)) = eval {
+            self.eval(out, unit, &sources, names).await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Construct a named source for the given input.
+    fn source(&mut self, input: &str) -> Result<Source> {
+        self.count += 1;
+        Ok(Source::new(try_format!("<repl {}>", self.count), input)?)
+    }
+
+    /// Parse the given input and split it up into items and code to evaluate,
+    /// returning `None` if the input is empty.
+    fn parse<'i>(&self, input: &'i str) -> crate::compile::Result<Option<Input<'i>>> {
+        if input.trim().is_empty() {
+            return Ok(None);
+        }
+
+        let block = crate::parse::parse_all::<ast::EmptyBlock>(input, SourceId::new(0), false)?;
+
+        let mut items = Vec::new();
+        let mut stmts = Vec::new();
+
+        let mut it = block.statements.iter().peekable();
+
+        while let Some(stmt) = it.next() {
+            match stmt {
+                ast::Stmt::Item(item, _) => {
+                    let name = match item_name(item) {
+                        Some(ident) => Some(input[ident.span.range()].try_to_owned()?),
+                        None => None,
+                    };
+
+                    // The span of some items doesn't cover their closing
+                    // delimiter, so take everything up until the next
+                    // statement.
+                    let start = stmt.span().range().start;
+
+                    let end = match it.peek() {
+                        Some(next) => next.span().range().start,
+                        None => input.len(),
+                    };
+
+                    items.try_push((name, input[start..end].trim_end()))?;
+                }
+                stmt => {
+                    stmts.try_push(stmt)?;
+                }
+            }
+        }
+
+        if stmts.is_empty() {
+            return Ok(Some(Input { items, eval: None }));
+        }
+
+        let mut names = Vec::new();
+
+        for (name, _) in &self.bindings {
+            names.try_push(name.try_clone()?)?;
+        }
+
+        let mut source = String::new();
+        source.try_push_str("pub fn ")?;
+        source.try_push_str(EVAL)?;
+        source.try_push('(')?;
+        push_names(&mut source, &names)?;
+        source.try_push_str(") {\n")?;
+
+        let mut value = "()";
+
+        let mut it = stmts.iter().peekable();
+
+        while let Some(stmt) = it.next() {
+            let text = &input[stmt.span().range()];
+
+            match stmt {
+                ast::Stmt::Local(local) => {
+                    pat_names(input, &local.pat, &mut names)?;
+                }
+                ast::Stmt::Expr(..) if it.peek().is_none() => {
+                    value = text;
+                    break;
+                }
+                _ => {}
+            }
+
+            source.try_push_str(text)?;
+            source.try_push('\n')?;
+        }
+
+        source.try_push_str(&try_format!("let {EVAL} = {value};\n({EVAL}, ["))?;
+        push_names(&mut source, &names)?;
+        source.try_push_str("])\n}\n")?;
+
+        Ok(Some(Input {
+            items,
+            eval: Some(Eval { source, names }),
+        }))
+    }
+
+    /// Construct the set of defined items after the given items have been
+    /// added, replacing any existing items with the same name.
+    fn define(&self, items: Vec<Defined>) -> Result<Vec<Defined>> {
+        let mut defined = Vec::new();
+
+        for existing in &self.items {
+            let replaced =
+                existing.name.is_some() && items.iter().any(|item| item.name == existing.name);
+
+            if !replaced {
+                defined.try_push(existing.try_clone()?)?;
+            }
+        }
+
+        for item in items {
+            defined.try_push(item)?;
+        }
+
+        Ok(defined)
+    }
+
+    /// Compile the given items together with the source to evaluate.
+    fn compile<O>(
+        &self,
+        out: &mut O,
+        items: &[Defined],
+        eval: Option<&Source>,
+    ) -> Result<Option<(Unit, Sources)>>
+    where
+        O: WriteColor,
+    {
+        let mut sources = Sources::new();
+
+        for item in items {
+            sources.insert(item.source.try_clone()?)?;
+        }
+
+        if let Some(eval) = eval {
+            sources.insert(eval.try_clone()?)?;
+        }
+
+        let mut diagnostics = if self.warnings {
+            Diagnostics::new()
+        } else {
+            Diagnostics::without_warnings()
+        };
+
+        let result = crate::prepare(&mut sources)
+            .with_context(self.context)
+            .with_diagnostics(&mut diagnostics)
+            .with_options(self.options)
+            .build();
+
+        diagnostics.emit(out, &sources)?;
+
+        match result {
+            Ok(unit) => Ok(Some((unit, sources))),
+            Err(..) => Ok(None),
+        }
+    }
+
+    /// Evaluate the given unit, printing the result and storing any bindings.
+    async fn eval<O>(
+        &mut self,
+        out: &mut O,
+        unit: Unit,
+        sources: &Sources,
+        names: Vec<String>,
+    ) -> Result<()>
+    where
+        O: WriteColor,
+    {
+        *self.vm.unit_mut() = Arc::try_new(unit)?;
+        self.vm.clear();
+
+        let mut args = Vec::new();
+
+        for (_, value) in &self.bindings {
+            args.try_push(value.clone())?;
+        }
+
+        let last = Instant::now();
+
+        let result = match self.vm.execute(Hash::type_hash([EVAL]), args) {
+            Ok(mut execution) => execution.resume().await.and_then(VmOutcome::into_complete),
+            Err(error) => Err(error),
+        };
+
+        let duration = Instant::now().saturating_duration_since(last);
+
+        let value =
+            match result.and_then(|value| Ok(crate::from_value::<(Value, Vec<Value>)>(value)?)) {
+                Ok(value) => value,
+                Err(error) => {
+                    error.emit(out, sources)?;
+                    return Ok(());
+                }
+            };
+
+        let (value, values) = value;
+
+        self.bindings.clear();
+
+        for (name, value) in names.into_iter().zip(values) {
+            self.bindings.try_push((name, value))?;
+        }
+
+        let mut s = String::new();
+
+        let formatted = self
+            .vm
+            .with(|| Formatter::format_with(&mut s, |f| value.debug_fmt(f)));
+
+        if let Err(error) = formatted {
+            error.emit(out, sources)?;
+            return Ok(());
+        }
+
+        if self.time {
+            writeln!(out, "{s} ({duration:?})")?;
+        } else if value.into_unit().is_err() {
+            writeln!(out, "{s}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Push a comma-separated list of names.
+fn push_names(source: &mut String, names: &[String]) -> alloc::Result<()> {
+    for (n, name) in names.iter().enumerate() {
+        if n > 0 {
+            source.try_push_str(", ")?;
+        }
+
+        source.try_push_str(name)?;
+    }
+
+    Ok(())
+}
+
+/// Get the name of an item, if it has one.
+fn item_name(item: &ast::Item) -> Option<&ast::Ident> {
+    match item {
+        ast::Item::Fn(item) => Some(&item.name),
+        ast::Item::Enum(item) => Some(&item.name),
+        ast::Item::Struct(item) => Some(&item.ident),
+        ast::Item::Trait(item) => Some(&item.name),
+        ast::Item::Mod(item) => Some(&item.name),
+        ast::Item::Const(item) => Some(&item.name),
+        _ => None,
+    }
+}
+
+/// Collect the names bound by a pattern, replacing any existing bindings with
+/// the same name.
+fn pat_names(input: &str, pat: &ast::Pat, names: &mut Vec<String>) -> alloc::Result<()> {
+    match pat {
+        ast::Pat::Path(pat) => {
+            if let Some(ident) = pat.path.try_as_ident() {
+                let name = &input[ident.span.range()];
+                names.retain(|existing| existing != name);
+                names.try_push(name.try_to_owned()?)?;
+            }
+        }
+        ast::Pat::Vec(pat) => {
+            for (pat, _) in &pat.items {
+                pat_names(input, pat, names)?;
+            }
+        }
+        ast::Pat::Tuple(pat) => {
+            for (pat, _) in &pat.items {
+                pat_names(input, pat, names)?;
+            }
+        }
+        ast::Pat::Object(pat) => {
+            for (pat, _) in &pat.items {
+                pat_names(input, pat, names)?;
+            }
+        }
+        ast::Pat::Binding(pat) => {
+            pat_names(input, &pat.pat, names)?;
+        }
+        ast::Pat::Ignore(..) | ast::Pat::Lit(..) | ast::Pat::Rest(..) => {}
+    }
+
+    Ok(())
+}
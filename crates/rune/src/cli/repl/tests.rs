@@ -0,0 +1,59 @@
+use futures_executor::block_on;
+
+use crate::termcolor::NoColor;
+use crate::{Context, Options};
+
+use super::Repl;
+
+/// Feed the given input to a repl and collect the values it prints, one per
+/// line.
+fn repl(input: &str) -> std::vec::Vec<std::string::String> {
+    let context = Context::with_default_modules().unwrap();
+    let options = Options::default();
+    let mut repl = Repl::new(&context, &options, false, false).unwrap();
+    let mut out = NoColor::new(std::vec::Vec::new());
+    block_on(repl.run(&mut input.as_bytes(), &mut out)).unwrap();
+    let out = std::string::String::from_utf8(out.into_inner()).unwrap();
+
+    out.split(['\n', '>', '.'])
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(std::string::String::from)
+        .collect()
+}
+
+#[test]
+fn bindings() {
+    assert_eq!(repl("let a = 1;\nlet b = a + 1;\na + b\n"), ["3"]);
+    assert_eq!(repl("let (a, b) = (1, 2);\nlet a = a + b;\na\n"), ["3"]);
+}
+
+#[test]
+fn multi_line() {
+    assert_eq!(repl("fn foo() {\n    40\n}\nfoo() + 2\n"), ["42"]);
+}
+
+#[test]
+fn mixed_items_and_expressions() {
+    assert_eq!(repl("fn foo() { 1 } foo()\nfoo() + 1\n"), ["1", "2"]);
+    assert_eq!(
+        repl("struct Point { x } let p = Point { x: 2 };\np.x\n"),
+        ["2"]
+    );
+}
+
+#[test]
+fn redefine_items() {
+    assert_eq!(repl("fn foo() { 1 }\nfn foo() { 2 }\nfoo()\n"), ["2"]);
+    assert_eq!(
+        repl("fn foo() { 1 } foo()\nfn foo() { 2 } foo()\nfoo()\n"),
+        ["1", "2", "2"]
+    );
+    assert_eq!(repl("const A = 1;\nconst A = 2;\nA\n"), ["2"]);
+}
+
+#[test]
+fn reset() {
+    let out = repl("fn foo() { 1 }\n:reset\nfn foo() { 2 }\nfoo()\n");
+    assert_eq!(out, ["2"]);
+}
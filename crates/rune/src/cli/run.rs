@@ -2,9 +2,9 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::time::Instant;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context as _, Result};
 
-use crate::cli::{AssetKind, CommandBase, Config, ExitCode, Io, SharedFlags};
+use crate::cli::{profile, AssetKind, CommandBase, Config, ExitCode, Io, SharedFlags};
 use crate::runtime::{UnitStorage, VmError, VmExecution, VmOutcome};
 use crate::sync::Arc;
 use crate::{Context, Hash, Sources, Unit, Value, Vm};
@@ -67,6 +67,18 @@ mod cli {
         /// implies `--trace`.
         #[arg(long)]
         pub(super) trace_limit: Option<usize>,
+        /// Profile the script, writing the time spent in each function and
+        /// native call as collapsed stacks to the given path.
+        ///
+        /// The output is compatible with flamegraph tooling such as `inferno`
+        /// or `flamegraph.pl`.
+        #[arg(long, value_name = "PATH", conflicts_with = "trace")]
+        pub(super) profile: Option<PathBuf>,
+        /// When profiling, weight collapsed stacks by the number of
+        /// instructions executed instead of by time, in which case time is not
+        /// measured at all.
+        #[arg(long, requires = "profile")]
+        pub(super) profile_instructions: bool,
         /// Explicit paths to run.
         pub(super) run_path: Vec<PathBuf>,
     }
@@ -176,13 +188,15 @@ pub(super) async fn run(
             "Unit size: {} bytes",
             unit.instructions().bytes()
         )?;
+    }
 
-        if args.emit_instructions {
-            let mut o = io.stdout.lock();
-            writeln!(o, "# instructions")?;
-            unit.emit_instructions(&mut o, sources, args.without_source)?;
-        }
+    if args.emit_instructions {
+        let mut o = io.stdout.lock();
+        writeln!(o, "# instructions")?;
+        unit.emit_instructions(&mut o, sources, args.without_source)?;
+    }
 
+    if args.dump_unit {
         let mut functions = unit.iter_functions().peekable();
         let mut strings = unit.iter_static_strings().peekable();
         let mut bytes = unit.iter_static_bytes().peekable();
@@ -266,6 +280,17 @@ pub(super) async fn run(
             Err(TraceError::VmError(vm)) => Err(vm),
             Err(TraceError::Limited) => return Err(anyhow!("Trace limit reached")),
         }
+    } else if let Some(path) = &args.profile {
+        let (profile, result) =
+            profile::profile(context, &mut execution, !args.profile_instructions).await;
+
+        profile
+            .write_collapsed(path)
+            .with_context(|| anyhow!("{}", path.display()))?;
+
+        profile.write_summary(io.stderr, 10)?;
+        writeln!(io.stderr, "== profile written to {}", path.display())?;
+        result
     } else {
         execution.resume().await.and_then(VmOutcome::into_complete)
     };
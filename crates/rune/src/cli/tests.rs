@@ -8,6 +8,7 @@ use anyhow::{Context, Result};
 
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
+use crate::cli::coverage::Report;
 use crate::cli::naming::Naming;
 use crate::cli::visitor;
 use crate::cli::{
@@ -17,11 +18,12 @@ use crate::cli::{
 use crate::compile::FileSourceLoader;
 use crate::doc::{TestKind, TestParams};
 use crate::modules::capture_io::CaptureIo;
-use crate::runtime::{Repr, Value, Vm, VmError, VmOutcome};
+use crate::runtime::{Coverage, Repr, Value, Vm, VmError, VmOutcome};
 use crate::sync::Arc;
 use crate::{Diagnostics, Hash, Item, ItemBuf, Source, Sources, TypeHash, Unit};
 
 mod cli {
+    use std::path::PathBuf;
     use std::string::String;
     use std::vec::Vec;
 
@@ -43,6 +45,10 @@ mod cli {
         /// tests found in runtime contexts will be run.
         #[arg(long)]
         pub skip_lib_tests: bool,
+        /// Record line and branch coverage of lib tests and write it as an
+        /// LCOV report to the given path, which defaults to `lcov.info`.
+        #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "lcov.info")]
+        pub coverage: Option<PathBuf>,
         /// Filter tests by name.
         pub filters: Vec<String>,
     }
@@ -263,6 +269,7 @@ where
 
     let runtime = Arc::try_new(context.runtime()?)?;
     let mut failed = Vec::new();
+    let mut report = flags.coverage.as_ref().map(|_| Report::default());
 
     for batch in batches {
         if batch.cases.is_empty() {
@@ -298,6 +305,15 @@ where
             section.close()?;
         }
 
+        // All lib tests in a batch share a unit, so their coverage is
+        // recorded together.
+        let mut coverage = match (&report, &batch.kind) {
+            (Some(..), BatchKind::LibTests) => Some(Coverage::new()),
+            _ => None,
+        };
+
+        let mut covered = None;
+
         for mut case in batch.cases {
             if case.filtered {
                 skipped = skipped.wrapping_add(1);
@@ -308,8 +324,12 @@ where
                 continue;
             }
 
+            if coverage.is_some() && covered.is_none() {
+                covered = Some((case.unit.clone(), case.sources.clone()));
+            }
+
             let mut vm = Vm::new(runtime.clone(), case.unit.clone());
-            case.execute(&mut vm, &capture).await?;
+            case.execute(&mut vm, &capture, coverage.as_mut()).await?;
             executed = executed.wrapping_add(1);
 
             if case.outcome.is_ok() {
@@ -332,6 +352,12 @@ where
                 break;
             }
         }
+
+        if let (Some(report), Some(coverage), Some((unit, sources))) =
+            (&mut report, &coverage, &covered)
+        {
+            report.add(unit, sources, coverage);
+        }
     }
 
     if flags.quiet {
@@ -392,6 +418,16 @@ where
 
     writeln!(io.stdout, " in {:.3} seconds", elapsed.as_secs_f64())?;
 
+    if let (Some(report), Some(path)) = (&report, &flags.coverage) {
+        if let Err(error) = report.write_lcov(path) {
+            return Err(error).context(path.display().try_to_string()?);
+        }
+
+        let mut section = io.section("Coverage", Stream::Stdout, Color::Highlight)?;
+        section.append(format_args!(" written to {}", path.display()))?;
+        section.close()?;
+    }
+
     if build_errors == 0 && failures == 0 {
         Ok(ExitCode::Success)
     } else {
@@ -525,9 +561,18 @@ impl TestCase {
         }
     }
 
-    async fn execute(&mut self, vm: &mut Vm, capture_io: &CaptureIo) -> Result<()> {
+    async fn execute(
+        &mut self,
+        vm: &mut Vm,
+        capture_io: &CaptureIo,
+        coverage: Option<&mut Coverage>,
+    ) -> Result<()> {
         let result = match vm.execute(self.hash, ()) {
-            Ok(mut execution) => execution.resume().await.and_then(VmOutcome::into_complete),
+            Ok(mut execution) => match coverage {
+                Some(coverage) => execution.resume().with_diagnostics(coverage).await,
+                None => execution.resume().await,
+            }
+            .and_then(VmOutcome::into_complete),
             Err(err) => Err(err),
         };
 
@@ -5,13 +5,29 @@ use core::fmt;
 use crate as rune;
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
-use crate::alloc::{hash_map, HashMap};
+use crate::alloc::{self, hash_map, Box, HashMap, Vec};
 use crate::ast::{Span, Spanned};
 use crate::compile::{self, Location};
+use crate::hir;
 use crate::runtime::inst;
-use crate::runtime::{Address, Label, Output};
+use crate::runtime::{Address, InstOp, Label, Output};
 use crate::{Hash, SourceId};
 
+/// A variable defined in an assembly.
+#[derive(Debug, TryClone)]
+pub(crate) struct AssemblyVariable {
+    /// The variable.
+    pub(crate) variable: hir::Variable,
+    /// The resolved name of the variable, if known.
+    pub(crate) name: Option<Box<str>>,
+    /// The address the variable is stored at.
+    pub(crate) addr: Address,
+    /// The instruction at which the variable was defined.
+    pub(crate) start: usize,
+    /// The instruction at which the variable went out of scope, if it did.
+    pub(crate) end: Option<usize>,
+}
+
 #[derive(Debug, TryClone)]
 pub(crate) enum AssemblyInst {
     Jump {
@@ -30,6 +46,13 @@ pub(crate) enum AssemblyInst {
         label: Label,
         out: Output,
     },
+    OpJumpIfNot {
+        op: InstOp,
+        a: Address,
+        b: Address,
+        out: Output,
+        label: Label,
+    },
     Raw {
         raw: inst::Kind,
     },
@@ -50,6 +73,8 @@ pub(crate) struct Assembly {
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
     pub(crate) required_functions: HashMap<Hash, Vec<(Span, SourceId)>>,
+    /// Variables defined in the assembly.
+    pub(crate) variables: Vec<AssemblyVariable>,
 }
 
 impl Assembly {
@@ -62,6 +87,7 @@ impl Assembly {
             comments: Default::default(),
             label_count,
             required_functions: Default::default(),
+            variables: Default::default(),
         }
     }
 
@@ -184,6 +210,48 @@ impl Assembly {
         Ok(())
     }
 
+    /// Mark that the given variable is defined at the current instruction.
+    pub(crate) fn define_variable(
+        &mut self,
+        variable: hir::Variable,
+        addr: Address,
+    ) -> alloc::Result<()> {
+        self.variables.try_push(AssemblyVariable {
+            variable,
+            name: None,
+            addr,
+            start: self.instructions.len(),
+            end: None,
+        })
+    }
+
+    /// Mark that the given variable goes out of scope at the current
+    /// instruction.
+    pub(crate) fn end_variable(&mut self, variable: hir::Variable) {
+        let end = self.instructions.len();
+
+        for var in self.variables.iter_mut().rev() {
+            if var.variable == variable && var.end.is_none() {
+                var.end = Some(end);
+                break;
+            }
+        }
+    }
+
+    /// Resolve the names of defined variables.
+    pub(crate) fn name_variables(
+        &mut self,
+        names: &HashMap<hir::Variable, hir::Name<'_>>,
+    ) -> alloc::Result<()> {
+        for var in &mut self.variables {
+            if let Some(name) = names.get(&var.variable) {
+                var.name = Some(name.try_to_string()?.try_into_boxed_str()?);
+            }
+        }
+
+        Ok(())
+    }
+
     fn inner_push(&mut self, inst: AssemblyInst, span: &dyn Spanned) -> compile::Result<()> {
         if let AssemblyInst::Raw {
             raw: inst::Kind::Call { hash, .. },
@@ -229,3 +229,39 @@ impl Attribute for Doc {
     /// Must match the specified name.
     const PATH: &'static str = "doc";
 }
+
+/// The `#[allow(..)]` attribute.
+#[derive(Parse)]
+pub(crate) struct Allow {
+    /// The lints to allow.
+    pub lints: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Attribute for Allow {
+    /// Must match the specified name.
+    const PATH: &'static str = "allow";
+}
+
+/// The `#[warn(..)]` attribute.
+#[derive(Parse)]
+pub(crate) struct Warn {
+    /// The lints to warn about.
+    pub lints: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Attribute for Warn {
+    /// Must match the specified name.
+    const PATH: &'static str = "warn";
+}
+
+/// The `#[deny(..)]` attribute.
+#[derive(Parse)]
+pub(crate) struct Deny {
+    /// The lints to deny.
+    pub lints: ast::Parenthesized<ast::Ident, T![,]>,
+}
+
+impl Attribute for Deny {
+    /// Must match the specified name.
+    const PATH: &'static str = "deny";
+}
@@ -3,8 +3,8 @@ use crate::alloc::prelude::*;
 use crate::ast::{Span, Spanned};
 use crate::compile::v1;
 use crate::compile::{
-    self, Assembly, CompileVisitor, Context, ErrorKind, Location, Options, Pool, Prelude,
-    SourceLoader, UnitBuilder,
+    self, optimize, Assembly, CompileVisitor, Context, ErrorKind, Location, Options, ParseCache,
+    Pool, Prelude, SourceLoader, UnitBuilder,
 };
 use crate::hir;
 use crate::indexing::FunctionAst;
@@ -28,7 +28,10 @@ pub(crate) fn compile(
     source_loader: &mut dyn SourceLoader,
     options: &Options,
     unit_storage: &mut dyn UnitEncoder,
+    parse_cache: Option<&mut ParseCache>,
 ) -> alloc::Result<()> {
+    diagnostics.configure_lints(&options.lints);
+
     // Shared id generator.
     let gen = Gen::new();
     let const_arena = hir::Arena::new();
@@ -54,7 +57,7 @@ pub(crate) fn compile(
     );
 
     // The worker queue.
-    let mut worker = Worker::new(q);
+    let mut worker = Worker::new(q, parse_cache);
 
     // Queue up the initial sources to be loaded.
     for source_id in worker.q.sources.source_ids() {
@@ -227,16 +230,31 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
 
                         node.parse(|p| hir::lowering2::item_fn(&mut cx, p, f.impl_item.is_some()))?
                     }
-                    FunctionAst::Item(ast, _) => hir::lowering::item_fn(&mut cx, ast)?,
+                    FunctionAst::Item(ast, _) => {
+                        let hir = hir::lowering::item_fn(&mut cx, ast)?;
+
+                        if cx.q.options.type_check {
+                            hir::type_check::item_fn(&mut cx, ast, &hir, type_hash)?;
+                        }
+
+                        hir
+                    }
                     FunctionAst::Empty(ast, span) => hir::lowering::empty_fn(&mut cx, ast, &span)?,
                 };
 
+                let names = cx.take_variable_names();
+
                 let count = hir.args.len();
 
                 let mut scopes = self::v1::Scopes::new(location.source_id)?;
                 let mut c = self.compiler1(location, span, &mut asm, &mut scopes)?;
                 assemble::fn_from_item_fn(&mut c, &hir, f.is_instance)?;
                 let size = c.scopes.size();
+                asm.name_variables(&names)?;
+
+                if self.q.options.optimize {
+                    optimize::assembly(&mut asm, size)?;
+                }
 
                 if !self.q.is_used(&item_meta) {
                     self.q
@@ -287,6 +305,11 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                             let mut cx = self.compiler1(location, c.hir, &mut asm, &mut scopes)?;
                             assemble::expr_closure_secondary(&mut cx, c.hir)?;
                             let size = cx.scopes.size();
+                            asm.name_variables(&names)?;
+
+                            if self.q.options.optimize {
+                                optimize::assembly(&mut asm, size)?;
+                            }
 
                             if !self.q.is_used(&item_meta) {
                                 self.q.diagnostics.not_used(
@@ -325,6 +348,11 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                             let mut cx = self.compiler1(location, b.hir, &mut asm, &mut scopes)?;
                             assemble::async_block_secondary(&mut cx, b.hir)?;
                             let size = cx.scopes.size();
+                            asm.name_variables(&names)?;
+
+                            if self.q.options.optimize {
+                                optimize::assembly(&mut asm, size)?;
+                            }
 
                             if !self.q.is_used(&item_meta) {
                                 self.q.diagnostics.not_used(
@@ -378,7 +406,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                 if !self.q.is_used(&item_meta) {
                     self.q
                         .diagnostics
-                        .not_used(location.source_id, &location.span, None)?;
+                        .unused_import(location.source_id, &location.span)?;
                 }
 
                 let missing = match result {
@@ -17,6 +17,8 @@ use crate::module::{
     ModuleItem, ModuleItemCommon, ModuleReexport, ModuleTrait, ModuleTraitImpl, ModuleType,
     TypeSpecification,
 };
+#[cfg(feature = "std")]
+use crate::runtime::sandbox::Sandbox;
 use crate::runtime::{
     Address, AnyTypeInfo, ConstConstructImpl, ConstContext, ConstValue, FunctionHandler, Memory,
     Output, Protocol, Rtti, RttiKind, RuntimeContext, TypeInfo, VmError,
@@ -295,6 +297,8 @@ pub struct Context {
     item_to_hash: HashMap<ItemBuf, BTreeSet<Hash>>,
     /// Registered native function handlers.
     functions: hash::Map<FunctionHandler>,
+    /// The names of registered instance functions.
+    instance_functions: hash::Set,
     /// Registered deprecation mesages for native functions.
     deprecations: hash::Map<String>,
     /// Information on associated types.
@@ -319,6 +323,9 @@ pub struct Context {
     constants: hash::Map<ConstValue>,
     /// Constant constructor.
     construct: hash::Map<ConstConstructImpl>,
+    /// The sandbox policy consulted by native modules.
+    #[cfg(feature = "std")]
+    sandbox: Option<std::sync::Arc<Sandbox>>,
 }
 
 impl Context {
@@ -362,6 +369,9 @@ impl Context {
         this.install(crate::modules::collections::hash_map::module()?)?;
         this.install(crate::modules::collections::hash_set::module()?)?;
         this.install(crate::modules::collections::vec_deque::module()?)?;
+        this.install(crate::modules::collections::btree_map::module()?)?;
+        this.install(crate::modules::collections::btree_set::module()?)?;
+        this.install(crate::modules::collections::binary_heap::module()?)?;
 
         this.install(crate::modules::char::module()?)?;
         this.install(crate::modules::f64::module()?)?;
@@ -370,6 +380,14 @@ impl Context {
         this.install(crate::modules::future::module()?)?;
         this.install(crate::modules::i64::module()?)?;
         this.install(crate::modules::u64::module()?)?;
+        this.install(crate::modules::u8::module()?)?;
+        this.install(crate::modules::u16::module()?)?;
+        this.install(crate::modules::u32::module()?)?;
+        this.install(crate::modules::u128::module()?)?;
+        this.install(crate::modules::i8::module()?)?;
+        this.install(crate::modules::i16::module()?)?;
+        this.install(crate::modules::i32::module()?)?;
+        this.install(crate::modules::i128::module()?)?;
         this.install(crate::modules::io::module(stdio)?)?;
         this.install(crate::modules::macros::module()?)?;
         this.install(crate::modules::macros::builtin::module()?)?;
@@ -379,6 +397,8 @@ impl Context {
         this.install(crate::modules::ops::generator::module()?)?;
         this.install(crate::modules::option::module()?)?;
         this.install(crate::modules::result::module()?)?;
+        #[cfg(feature = "std")]
+        this.install(crate::modules::sandbox::module()?)?;
         this.install(crate::modules::stream::module()?)?;
         this.install(crate::modules::test::module()?)?;
         this.install(crate::modules::vec::module()?)?;
@@ -412,11 +432,39 @@ impl Context {
     pub fn runtime(&self) -> alloc::Result<RuntimeContext> {
         Ok(RuntimeContext::new(
             self.functions.try_clone()?,
+            self.instance_functions.try_clone()?,
             self.constants.try_clone()?,
             self.construct.try_clone()?,
+            #[cfg(feature = "std")]
+            self.sandbox.clone(),
         ))
     }
 
+    /// Install a [Sandbox] policy which restricts what native modules like
+    /// `fs`, `process` and `http` are allowed to access.
+    ///
+    /// The policy is carried over into any [RuntimeContext] constructed
+    /// through [Context::runtime] after this has been called.
+    ///
+    /// ```
+    /// use rune::runtime::sandbox::Sandbox;
+    /// use rune::Context;
+    ///
+    /// let mut sandbox = Sandbox::new();
+    /// sandbox.allow_read("scripts")?;
+    ///
+    /// let mut context = Context::with_default_modules()?;
+    /// context.set_sandbox(sandbox);
+    ///
+    /// let runtime = context.runtime()?;
+    /// assert!(runtime.sandbox().is_some());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn set_sandbox(&mut self, sandbox: Sandbox) {
+        self.sandbox = Some(std::sync::Arc::new(sandbox));
+    }
+
     /// Install the specified module.
     ///
     /// This installs everything that has been declared in the given [Module]
@@ -535,7 +583,6 @@ impl Context {
     }
 
     /// Lookup meta by its hash.
-    #[cfg(any(feature = "cli", feature = "languageserver", feature = "emit"))]
     pub(crate) fn lookup_meta_by_hash(
         &self,
         hash: Hash,
@@ -1163,6 +1210,18 @@ impl Context {
                     assoc.common.deprecated.as_deref(),
                 )?;
 
+                let name = match &assoc.name.kind {
+                    meta::AssociatedKind::Protocol(protocol) => Some(protocol.hash),
+                    meta::AssociatedKind::Instance(name) => Some(Hash::ident(name)),
+                    _ => None,
+                };
+
+                if let Some(name) = name {
+                    self.instance_functions.try_insert(
+                        name.with_function_parameters(assoc.name.function_parameters),
+                    )?;
+                }
+
                 meta::Kind::Function {
                     associated: Some(assoc.name.kind.try_clone()?),
                     trait_hash: f.trait_hash,
@@ -395,6 +395,26 @@ pub(crate) enum ErrorKind {
     FunctionReExportConflict {
         hash: Hash,
     },
+    TraitFunctionConflict {
+        name: Box<str>,
+    },
+    MissingTraitFunction {
+        name: Box<str>,
+        item: ItemBuf,
+    },
+    NotTraitFunction {
+        name: Box<str>,
+        item: ItemBuf,
+    },
+    TraitFunctionArity {
+        name: Box<str>,
+        item: ItemBuf,
+        expected: usize,
+        actual: usize,
+    },
+    UnsupportedTraitImpl {
+        item: ItemBuf,
+    },
     ConstantConflict {
         hash: Hash,
     },
@@ -442,6 +462,13 @@ pub(crate) enum ErrorKind {
         expected: &'static str,
         meta: MetaInfo,
     },
+    TypeMismatch {
+        expected: Box<str>,
+        actual: Box<str>,
+    },
+    UnknownLint {
+        name: Box<str>,
+    },
     NoSuchBuiltInMacro {
         name: Box<str>,
     },
@@ -630,6 +657,8 @@ pub(crate) enum ErrorKind {
     UnsupportedDelimiter {
         expectation: Expectation,
     },
+    #[cfg(feature = "fmt")]
+    LostComment,
 }
 
 impl ErrorKind {
@@ -921,6 +950,32 @@ impl fmt::Display for ErrorKind {
             ErrorKind::FunctionReExportConflict { hash } => {
                 write!(f, "Conflicting function hash already exists `{hash}`")?;
             }
+            ErrorKind::TraitFunctionConflict { name } => {
+                write!(f, "Function `{name}` is declared more than once in trait")?;
+            }
+            ErrorKind::MissingTraitFunction { name, item } => {
+                write!(f, "Missing function `{name}` required by trait `{item}`")?;
+            }
+            ErrorKind::NotTraitFunction { name, item } => {
+                write!(f, "Function `{name}` is not a member of trait `{item}`")?;
+            }
+            ErrorKind::TraitFunctionArity {
+                name,
+                item,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "Function `{name}` takes {actual} arguments but trait `{item}` declares it with {expected}"
+                )?;
+            }
+            ErrorKind::UnsupportedTraitImpl { item } => {
+                write!(
+                    f,
+                    "Implementing trait `{item}` is not supported, only traits declared in scripts can be implemented"
+                )?;
+            }
             ErrorKind::ConstantConflict { hash } => {
                 write!(f, "Conflicting constant for hash `{hash}`")?;
             }
@@ -983,6 +1038,12 @@ impl fmt::Display for ErrorKind {
             ErrorKind::ExpectedMeta { expected, meta } => {
                 write!(f, "Expected {expected} but got `{meta}`")?;
             }
+            ErrorKind::TypeMismatch { expected, actual } => {
+                write!(f, "Expected type `{expected}` but found `{actual}`")?;
+            }
+            ErrorKind::UnknownLint { name } => {
+                write!(f, "Unknown lint `{name}`")?;
+            }
             ErrorKind::NoSuchBuiltInMacro { name } => {
                 write!(f, "No such built-in macro `{name}`")?;
             }
@@ -1250,6 +1311,13 @@ impl fmt::Display for ErrorKind {
             ErrorKind::UnsupportedDelimiter { expectation } => {
                 write!(f, "Unsupported delimiter {expectation}")?;
             }
+            #[cfg(feature = "fmt")]
+            ErrorKind::LostComment => {
+                write!(
+                    f,
+                    "Formatting would lose or move this comment, this is a bug in the formatter"
+                )?;
+            }
         }
 
         Ok(())
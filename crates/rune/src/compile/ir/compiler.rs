@@ -7,7 +7,7 @@ use crate::compile::ir;
 use crate::compile::{self, ErrorKind, WithSpan};
 use crate::hir;
 use crate::query::Query;
-use crate::runtime::{Bytes, Value};
+use crate::runtime::{Bytes, Fixed, Value, Wide};
 use crate::SourceId;
 
 use tracing::instrument_ast;
@@ -216,6 +216,38 @@ fn lit(_c: &mut Ctxt<'_, '_>, span: Span, hir: hir::Lit<'_>) -> compile::Result<
             let value = Value::from(n);
             ir::Ir::new(span, value)
         }
+        hir::Lit::U8(n) => {
+            let value = Value::from(Fixed(n));
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::U16(n) => {
+            let value = Value::from(Fixed(n));
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::U32(n) => {
+            let value = Value::from(Fixed(n));
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::I8(n) => {
+            let value = Value::from(Fixed(n));
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::I16(n) => {
+            let value = Value::from(Fixed(n));
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::I32(n) => {
+            let value = Value::from(Fixed(n));
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::U128(n) => {
+            let value = Wide::Unsigned(n).into_value().with_span(span)?;
+            ir::Ir::new(span, value)
+        }
+        hir::Lit::I128(n) => {
+            let value = Wide::Signed(n).into_value().with_span(span)?;
+            ir::Ir::new(span, value)
+        }
         hir::Lit::Float(n) => {
             let value = Value::from(n);
             ir::Ir::new(span, value)
@@ -245,7 +245,7 @@ pub enum Kind {
 
 impl Kind {
     /// Access the underlying signature of the kind, if available.
-    #[cfg(all(feature = "doc", any(feature = "languageserver", feature = "cli")))]
+    #[cfg(feature = "doc")]
     pub(crate) fn as_signature(&self) -> Option<&Signature> {
         match self {
             Kind::Struct { constructor, .. } => constructor.as_ref(),
@@ -6,6 +6,8 @@
 mod assembly;
 pub(crate) use self::assembly::{Assembly, AssemblyInst};
 
+mod optimize;
+
 pub(crate) mod attrs;
 
 pub(crate) mod error;
@@ -34,6 +36,9 @@ pub(crate) use self::prelude::Prelude;
 
 pub(crate) mod ir;
 
+mod parse_cache;
+pub(crate) use self::parse_cache::ParseCache;
+
 mod source_loader;
 #[cfg(feature = "std")]
 pub use self::source_loader::FileSourceLoader;
@@ -47,7 +52,7 @@ pub(crate) mod v1;
 
 mod options;
 #[cfg(any(feature = "fmt", feature = "languageserver"))]
-pub(crate) use self::options::FmtOptions;
+pub(crate) use self::options::{FmtIndent, FmtOptions, TrailingComma};
 pub use self::options::{Options, ParseOptionError};
 
 mod location;
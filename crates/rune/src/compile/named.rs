@@ -3,6 +3,7 @@ use core::fmt;
 use core::marker::PhantomData;
 
 use crate as rune;
+use crate::runtime::Fixed;
 use crate::{item, Hash, Item};
 
 /// The trait used for something that can be statically named.
@@ -54,6 +55,46 @@ impl Named for u64 {
     const ITEM: &'static Item = item!(::std::u64);
 }
 
+impl Named for Fixed<u8> {
+    const ITEM: &'static Item = item!(::std::u8);
+}
+
+impl Named for Fixed<u16> {
+    const ITEM: &'static Item = item!(::std::u16);
+}
+
+impl Named for Fixed<u32> {
+    const ITEM: &'static Item = item!(::std::u32);
+}
+
+impl Named for Fixed<u64> {
+    const ITEM: &'static Item = item!(::std::u64);
+}
+
+impl Named for Fixed<u128> {
+    const ITEM: &'static Item = item!(::std::u128);
+}
+
+impl Named for Fixed<i8> {
+    const ITEM: &'static Item = item!(::std::i8);
+}
+
+impl Named for Fixed<i16> {
+    const ITEM: &'static Item = item!(::std::i16);
+}
+
+impl Named for Fixed<i32> {
+    const ITEM: &'static Item = item!(::std::i32);
+}
+
+impl Named for Fixed<i64> {
+    const ITEM: &'static Item = item!(::std::i64);
+}
+
+impl Named for Fixed<i128> {
+    const ITEM: &'static Item = item!(::std::i128);
+}
+
 impl Named for f64 {
     const ITEM: &'static Item = item!(::std::f64);
 }
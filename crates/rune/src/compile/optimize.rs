@@ -0,0 +1,1102 @@
+//! Optimization passes over assembled instructions.
+//!
+//! These operate on an [`Assembly`] before it is linked into a unit, so jumps
+//! still refer to labels and instructions can be freely removed or rewritten.
+//! Every pass preserves the observable behavior of the function, including
+//! which errors are raised, so for example arithmetic which would overflow is
+//! never folded.
+
+use core::cmp::Ordering;
+use core::mem;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, hash_map, HashMap, Vec};
+use crate::compile::{Assembly, AssemblyInst};
+use crate::runtime::inst::Kind;
+use crate::runtime::{
+    Address, InstArithmeticOp, InstBitwiseOp, InstOp, InstRange, InstTarget, InstValue, Label,
+    Output,
+};
+
+/// The maximum number of rounds passes are repeated for while they are still
+/// making progress.
+const MAX_ROUNDS: usize = 16;
+
+/// The maximum number of words used by a liveness analysis, beyond which
+/// passes depending on it are skipped.
+const MAX_LIVENESS_WORDS: usize = 1 << 20;
+
+/// Sentinel for a missing successor.
+const NONE: usize = usize::MAX;
+
+/// Optimize the given assembly, where `size` is the number of slots in the
+/// frame of the function it belongs to.
+pub(crate) fn assembly(asm: &mut Assembly, size: usize) -> alloc::Result<()> {
+    if !is_supported(asm) {
+        return Ok(());
+    }
+
+    for _ in 0..MAX_ROUNDS {
+        let mut changed = fold_constants(asm, size)?;
+        changed |= thread_jumps(asm)?;
+        changed |= invert_jumps(asm)?;
+        changed |= remove_redundant_jumps(asm)?;
+        changed |= remove_unreachable(asm)?;
+        changed |= remove_dead_stores(asm, size)?;
+        changed |= forward_outputs(asm, size)?;
+
+        if !changed {
+            break;
+        }
+    }
+
+    fuse_jumps(asm)?;
+    Ok(())
+}
+
+/// Test if the assembly can be optimized.
+///
+/// Every label has to be placed exactly once, and the assembly must not
+/// contain a `select`, since it jumps to instructions relative to itself.
+fn is_supported(asm: &Assembly) -> bool {
+    for (slot, labels) in asm.labels.values() {
+        if labels.iter().any(|label| label.jump() != Some(*slot)) {
+            return false;
+        }
+    }
+
+    let slots = asm.labels.len();
+
+    for (inst, _) in &asm.instructions {
+        match inst {
+            AssemblyInst::Raw {
+                raw: Kind::Select { .. },
+            } => return false,
+            inst => {
+                if let Some(label) = jump_label(inst) {
+                    if label.jump().is_none_or(|slot| slot >= slots) {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+
+    true
+}
+
+/// Fold arithmetic and comparisons over literals, and conditional jumps over
+/// known conditions.
+///
+/// Literals are only tracked within a straight sequence of instructions, so
+/// anything which is jumped to starts out knowing nothing.
+fn fold_constants(asm: &mut Assembly, size: usize) -> alloc::Result<bool> {
+    let mut known = try_vec![None::<InstValue>; size];
+    let mut removed = try_vec![false; asm.instructions.len()];
+    let mut changed = false;
+
+    for (pos, (inst, _)) in asm.instructions.iter_mut().enumerate() {
+        if asm.labels.contains_key(&pos) {
+            known.fill(None);
+        }
+
+        let get = |addr: Address| known.get(addr.offset()).copied().flatten();
+
+        match inst {
+            AssemblyInst::Raw { raw } => {
+                let folded = match *raw {
+                    Kind::Arithmetic { op, a, b, out } => {
+                        fold_arithmetic(op, get(a), get(b)).map(|value| (value, out))
+                    }
+                    Kind::Bitwise { op, a, b, out } => {
+                        fold_bitwise(op, get(a), get(b)).map(|value| (value, out))
+                    }
+                    Kind::Op { op, a, b, out } => {
+                        fold_op(op, get(a), get(b)).map(|value| (value, out))
+                    }
+                    _ => None,
+                };
+
+                if let Some((value, out)) = folded {
+                    *raw = Kind::Store { value, out };
+                    changed = true;
+                }
+            }
+            AssemblyInst::JumpIf { addr, label } => match get(*addr) {
+                Some(InstValue::Bool(true)) => {
+                    let label = label.try_clone()?;
+                    *inst = AssemblyInst::Jump { label };
+                    changed = true;
+                }
+                Some(..) => {
+                    removed[pos] = true;
+                }
+                None => {}
+            },
+            AssemblyInst::JumpIfNot { addr, label } => match get(*addr) {
+                Some(InstValue::Bool(false)) => {
+                    let label = label.try_clone()?;
+                    *inst = AssemblyInst::Jump { label };
+                    changed = true;
+                }
+                Some(..) => {
+                    removed[pos] = true;
+                }
+                None => {}
+            },
+            _ => {}
+        }
+
+        track_known(&mut known, inst);
+    }
+
+    changed |= compact(asm, &removed)?;
+    Ok(changed)
+}
+
+/// Update which slots are known to contain literals after the given
+/// instruction has executed.
+fn track_known(known: &mut [Option<InstValue>], inst: &AssemblyInst) {
+    fn set(known: &mut [Option<InstValue>], out: Output, value: Option<InstValue>) {
+        if let Some(slot) = out.as_addr().and_then(|addr| known.get_mut(addr.offset())) {
+            *slot = value;
+        }
+    }
+
+    match inst {
+        AssemblyInst::Jump { .. }
+        | AssemblyInst::JumpIf { .. }
+        | AssemblyInst::JumpIfNot { .. } => {}
+        AssemblyInst::IterNext { out, .. } | AssemblyInst::OpJumpIfNot { out, .. } => {
+            set(known, *out, None);
+        }
+        AssemblyInst::Raw { raw } => match *raw {
+            Kind::Store { value, out } => {
+                set(known, out, Some(value));
+            }
+            Kind::Copy { addr, out } => {
+                let value = known.get(addr.offset()).copied().flatten();
+                set(known, out, value);
+            }
+            Kind::Not { out, .. }
+            | Kind::Neg { out, .. }
+            | Kind::LoadFn { out, .. }
+            | Kind::String { out, .. }
+            | Kind::Bytes { out, .. }
+            | Kind::Format { out, .. }
+            | Kind::EqChar { out, .. }
+            | Kind::EqSigned { out, .. }
+            | Kind::EqUnsigned { out, .. }
+            | Kind::EqBool { out, .. }
+            | Kind::EqString { out, .. }
+            | Kind::EqBytes { out, .. }
+            | Kind::Op { out, .. }
+            | Kind::Arithmetic { out, .. }
+            | Kind::Bitwise { out, .. }
+            | Kind::Shift { out, .. } => {
+                set(known, out, None);
+            }
+            // Anything else might move values out of or write to other
+            // slots, so we conservatively forget everything.
+            _ => {
+                known.fill(None);
+            }
+        },
+    }
+}
+
+/// Fold an arithmetic operation, unless it would raise an error.
+fn fold_arithmetic(
+    op: InstArithmeticOp,
+    a: Option<InstValue>,
+    b: Option<InstValue>,
+) -> Option<InstValue> {
+    let value = match (a?, b?) {
+        (InstValue::Integer(a), InstValue::Integer(b)) => InstValue::Integer(match op {
+            InstArithmeticOp::Add => a.checked_add(b)?,
+            InstArithmeticOp::Sub => a.checked_sub(b)?,
+            InstArithmeticOp::Mul => a.checked_mul(b)?,
+            InstArithmeticOp::Div => a.checked_div(b)?,
+            InstArithmeticOp::Rem => a.checked_rem(b)?,
+        }),
+        (InstValue::Unsigned(a), InstValue::Unsigned(b)) => InstValue::Unsigned(match op {
+            InstArithmeticOp::Add => a.checked_add(b)?,
+            InstArithmeticOp::Sub => a.checked_sub(b)?,
+            InstArithmeticOp::Mul => a.checked_mul(b)?,
+            InstArithmeticOp::Div => a.checked_div(b)?,
+            InstArithmeticOp::Rem => a.checked_rem(b)?,
+        }),
+        (InstValue::Float(a), InstValue::Float(b)) => InstValue::Float(match op {
+            InstArithmeticOp::Add => a + b,
+            InstArithmeticOp::Sub => a - b,
+            InstArithmeticOp::Mul => a * b,
+            InstArithmeticOp::Div => a / b,
+            InstArithmeticOp::Rem => a % b,
+        }),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Fold a bitwise operation.
+fn fold_bitwise(
+    op: InstBitwiseOp,
+    a: Option<InstValue>,
+    b: Option<InstValue>,
+) -> Option<InstValue> {
+    let value = match (a?, b?) {
+        (InstValue::Integer(a), InstValue::Integer(b)) => InstValue::Integer(match op {
+            InstBitwiseOp::BitAnd => a & b,
+            InstBitwiseOp::BitXor => a ^ b,
+            InstBitwiseOp::BitOr => a | b,
+        }),
+        (InstValue::Unsigned(a), InstValue::Unsigned(b)) => InstValue::Unsigned(match op {
+            InstBitwiseOp::BitAnd => a & b,
+            InstBitwiseOp::BitXor => a ^ b,
+            InstBitwiseOp::BitOr => a | b,
+        }),
+        (InstValue::Bool(a), InstValue::Bool(b)) => InstValue::Bool(match op {
+            InstBitwiseOp::BitAnd => a & b,
+            InstBitwiseOp::BitXor => a ^ b,
+            InstBitwiseOp::BitOr => a | b,
+        }),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Fold a comparison or logical operation.
+fn fold_op(op: InstOp, a: Option<InstValue>, b: Option<InstValue>) -> Option<InstValue> {
+    let (a, b) = (a?, b?);
+
+    let value = match op {
+        InstOp::Lt => matches!(partial_cmp(a, b)?, Some(Ordering::Less)),
+        InstOp::Le => matches!(partial_cmp(a, b)?, Some(Ordering::Less | Ordering::Equal)),
+        InstOp::Gt => matches!(partial_cmp(a, b)?, Some(Ordering::Greater)),
+        InstOp::Ge => matches!(
+            partial_cmp(a, b)?,
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+        InstOp::Eq => partial_eq(a, b)?,
+        InstOp::Neq => !partial_eq(a, b)?,
+        InstOp::And | InstOp::Or => {
+            let (InstValue::Bool(a), InstValue::Bool(b)) = (a, b) else {
+                return None;
+            };
+
+            if matches!(op, InstOp::And) {
+                a && b
+            } else {
+                a || b
+            }
+        }
+        InstOp::As | InstOp::Is | InstOp::IsNot => return None,
+    };
+
+    Some(InstValue::Bool(value))
+}
+
+fn partial_cmp(a: InstValue, b: InstValue) -> Option<Option<Ordering>> {
+    match (a, b) {
+        (InstValue::Bool(a), InstValue::Bool(b)) => Some(a.partial_cmp(&b)),
+        (InstValue::Char(a), InstValue::Char(b)) => Some(a.partial_cmp(&b)),
+        (InstValue::Integer(a), InstValue::Integer(b)) => Some(a.partial_cmp(&b)),
+        (InstValue::Unsigned(a), InstValue::Unsigned(b)) => Some(a.partial_cmp(&b)),
+        (InstValue::Float(a), InstValue::Float(b)) => Some(a.partial_cmp(&b)),
+        _ => None,
+    }
+}
+
+fn partial_eq(a: InstValue, b: InstValue) -> Option<bool> {
+    match (a, b) {
+        (InstValue::Bool(a), InstValue::Bool(b)) => Some(a == b),
+        (InstValue::Char(a), InstValue::Char(b)) => Some(a == b),
+        (InstValue::Integer(a), InstValue::Integer(b)) => Some(a == b),
+        (InstValue::Unsigned(a), InstValue::Unsigned(b)) => Some(a == b),
+        (InstValue::Float(a), InstValue::Float(b)) => Some(a == b),
+        _ => None,
+    }
+}
+
+/// Retarget jumps which lead to an unconditional jump to its final
+/// destination.
+fn thread_jumps(asm: &mut Assembly) -> alloc::Result<bool> {
+    let targets = targets(asm)?;
+    let len = asm.instructions.len();
+    let mut changed = false;
+
+    for pos in 0..len {
+        let Some(label) = jump_label(&asm.instructions[pos].0) else {
+            continue;
+        };
+
+        let mut target = resolve(&targets, label);
+        let mut threaded = None;
+
+        for _ in 0..len {
+            let Some((AssemblyInst::Jump { label }, _)) = asm.instructions.get(target) else {
+                break;
+            };
+
+            let next = resolve(&targets, label);
+
+            if next == target {
+                break;
+            }
+
+            threaded = Some(label);
+            target = next;
+        }
+
+        let Some(threaded) = threaded else {
+            continue;
+        };
+
+        let threaded = threaded.try_clone()?;
+
+        if let Some(label) = jump_label_mut(&mut asm.instructions[pos].0) {
+            *label = threaded;
+            changed = true;
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Rewrite a conditional jump over an unconditional jump into a single
+/// conditional jump with the inverse condition.
+///
+/// This is only done when the condition is known to be a boolean, since
+/// conditional jumps treat every other value as not matching.
+fn invert_jumps(asm: &mut Assembly) -> alloc::Result<bool> {
+    let targets = targets(asm)?;
+    let len = asm.instructions.len();
+    let mut removed = try_vec![false; len];
+    let mut pos = 1;
+
+    while pos + 1 < len {
+        let current = pos;
+        pos += 1;
+
+        if asm.labels.contains_key(&current) || asm.labels.contains_key(&(current + 1)) {
+            continue;
+        }
+
+        let (AssemblyInst::JumpIf { addr, label } | AssemblyInst::JumpIfNot { addr, label }) =
+            &asm.instructions[current].0
+        else {
+            continue;
+        };
+
+        let AssemblyInst::Jump { label: over } = &asm.instructions[current + 1].0 else {
+            continue;
+        };
+
+        if resolve(&targets, label) != current + 2
+            || !produces_bool(&asm.instructions[current - 1].0, *addr)
+        {
+            continue;
+        }
+
+        let addr = *addr;
+        let label = over.try_clone()?;
+
+        let inverted = match &asm.instructions[current].0 {
+            AssemblyInst::JumpIf { .. } => AssemblyInst::JumpIfNot { addr, label },
+            _ => AssemblyInst::JumpIf { addr, label },
+        };
+
+        asm.instructions[current].0 = inverted;
+        removed[current + 1] = true;
+        pos += 1;
+    }
+
+    compact(asm, &removed)
+}
+
+/// Test if the given instruction always stores a boolean in `addr`.
+fn produces_bool(inst: &AssemblyInst, addr: Address) -> bool {
+    let AssemblyInst::Raw { raw } = inst else {
+        return false;
+    };
+
+    let out = match *raw {
+        Kind::Op { op, out, .. } if !matches!(op, InstOp::As) => out,
+        Kind::EqChar { out, .. }
+        | Kind::EqSigned { out, .. }
+        | Kind::EqUnsigned { out, .. }
+        | Kind::EqBool { out, .. }
+        | Kind::EqString { out, .. }
+        | Kind::EqBytes { out, .. } => out,
+        _ => return false,
+    };
+
+    out.as_addr() == Some(addr)
+}
+
+/// Remove jumps to the instruction immediately following them.
+fn remove_redundant_jumps(asm: &mut Assembly) -> alloc::Result<bool> {
+    let targets = targets(asm)?;
+    let mut removed = try_vec![false; asm.instructions.len()];
+
+    for (pos, (inst, _)) in asm.instructions.iter().enumerate() {
+        if let AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label, .. }
+        | AssemblyInst::JumpIfNot { label, .. } = inst
+        {
+            removed[pos] = resolve(&targets, label) == pos + 1;
+        }
+    }
+
+    compact(asm, &removed)
+}
+
+/// Remove instructions which can never be reached.
+fn remove_unreachable(asm: &mut Assembly) -> alloc::Result<bool> {
+    let len = asm.instructions.len();
+
+    if len == 0 {
+        return Ok(false);
+    }
+
+    let successors = successors(asm)?;
+    let mut removed = try_vec![true; len];
+    let mut queue = Vec::new();
+    queue.try_push(0)?;
+    removed[0] = false;
+
+    while let Some(pos) = queue.pop() {
+        for next in successors[pos] {
+            if next < len && removed[next] {
+                removed[next] = false;
+                queue.try_push(next)?;
+            }
+        }
+    }
+
+    compact(asm, &removed)
+}
+
+/// Remove instructions which only store a value that is never read.
+fn remove_dead_stores(asm: &mut Assembly, size: usize) -> alloc::Result<bool> {
+    let Some(live) = Liveness::compute(asm, size)? else {
+        return Ok(false);
+    };
+
+    let mut removed = try_vec![false; asm.instructions.len()];
+
+    for (pos, (inst, _)) in asm.instructions.iter().enumerate() {
+        let AssemblyInst::Raw { raw } = inst else {
+            continue;
+        };
+
+        removed[pos] = match *raw {
+            Kind::Copy { addr, out } if out.as_addr() == Some(addr) => true,
+            Kind::Store { out, .. }
+            | Kind::Copy { out, .. }
+            | Kind::String { out, .. }
+            | Kind::Bytes { out, .. } => match out.as_addr() {
+                Some(addr) => !live.is_live_after(pos, addr),
+                None => true,
+            },
+            _ => false,
+        };
+    }
+
+    compact(asm, &removed)
+}
+
+/// Store the output of an instruction directly in the place it is copied or
+/// moved to by the instruction following it, if the original output is never
+/// read.
+fn forward_outputs(asm: &mut Assembly, size: usize) -> alloc::Result<bool> {
+    let Some(live) = Liveness::compute(asm, size)? else {
+        return Ok(false);
+    };
+
+    let len = asm.instructions.len();
+    let mut removed = try_vec![false; len];
+    let mut pos = 0;
+
+    while pos + 1 < len {
+        let current = pos;
+        let next = pos + 1;
+        pos += 1;
+
+        if asm.labels.contains_key(&next) {
+            continue;
+        }
+
+        let (AssemblyInst::Raw {
+            raw: Kind::Copy { addr, out: to },
+        }
+        | AssemblyInst::Raw {
+            raw: Kind::Move { addr, out: to },
+        }) = asm.instructions[next].0
+        else {
+            continue;
+        };
+
+        if to.as_addr().is_none_or(|to| to == addr) || live.is_live_after(next, addr) {
+            continue;
+        }
+
+        let Some(out) = output_mut(&mut asm.instructions[current].0) else {
+            continue;
+        };
+
+        if out.as_addr() != Some(addr) {
+            continue;
+        }
+
+        *out = to;
+        removed[next] = true;
+        pos += 1;
+    }
+
+    compact(asm, &removed)
+}
+
+/// Fuse an operation followed by a conditional jump on its result into a
+/// single instruction.
+fn fuse_jumps(asm: &mut Assembly) -> alloc::Result<bool> {
+    let len = asm.instructions.len();
+    let mut removed = try_vec![false; len];
+    let mut pos = 0;
+
+    while pos + 1 < len {
+        let current = pos;
+        let next = pos + 1;
+        pos += 1;
+
+        if asm.labels.contains_key(&next) {
+            continue;
+        }
+
+        let AssemblyInst::Raw {
+            raw: Kind::Op { op, a, b, out },
+        } = asm.instructions[current].0
+        else {
+            continue;
+        };
+
+        let AssemblyInst::JumpIfNot { addr, label } = &asm.instructions[next].0 else {
+            continue;
+        };
+
+        if out.as_addr() != Some(*addr) {
+            continue;
+        }
+
+        let label = label.try_clone()?;
+
+        asm.instructions[current].0 = AssemblyInst::OpJumpIfNot {
+            op,
+            a,
+            b,
+            out,
+            label,
+        };
+
+        removed[next] = true;
+        pos += 1;
+    }
+
+    compact(asm, &removed)
+}
+
+/// Remove the given instructions, moving any labels, comments and variable
+/// boundaries which refer to them to the next remaining instruction.
+fn compact(asm: &mut Assembly, removed: &[bool]) -> alloc::Result<bool> {
+    if !removed.contains(&true) {
+        return Ok(false);
+    }
+
+    // The new position of every old position, including one past the end.
+    let mut map = Vec::try_with_capacity(removed.len().saturating_add(1))?;
+    let mut len = 0usize;
+
+    for &removed in removed {
+        map.try_push(len)?;
+        len += usize::from(!removed);
+    }
+
+    map.try_push(len)?;
+
+    let mut index = 0;
+
+    asm.instructions.retain(|_| {
+        let keep = !removed[index];
+        index += 1;
+        keep
+    });
+
+    let mut labels = HashMap::<usize, (usize, Vec<Label>)>::new();
+
+    for (pos, (_, moved)) in mem::take(&mut asm.labels) {
+        match labels.entry(map[pos]) {
+            hash_map::Entry::Occupied(e) => {
+                e.into_mut().1.try_extend(moved)?;
+            }
+            hash_map::Entry::Vacant(e) => {
+                e.try_insert((0, moved))?;
+            }
+        }
+    }
+
+    let mut positions = labels.keys().copied().try_collect::<Vec<_>>()?;
+    positions.sort();
+
+    for (slot, pos) in positions.into_iter().enumerate() {
+        if let Some(entry) = labels.get_mut(&pos) {
+            entry.0 = slot;
+
+            for label in &entry.1 {
+                label.set_jump(slot);
+            }
+        }
+    }
+
+    asm.labels = labels;
+
+    let mut comments = HashMap::new();
+
+    for (pos, comment) in mem::take(&mut asm.comments) {
+        if !removed[pos] {
+            comments.try_insert(map[pos], comment)?;
+        }
+    }
+
+    asm.comments = comments;
+
+    for var in &mut asm.variables {
+        var.start = map[var.start];
+        var.end = var.end.map(|end| map[end]);
+    }
+
+    Ok(true)
+}
+
+/// Construct a mapping from label slots to instruction positions.
+fn targets(asm: &Assembly) -> alloc::Result<Vec<usize>> {
+    let mut targets = try_vec![NONE; asm.labels.len()];
+
+    for (&pos, &(slot, _)) in &asm.labels {
+        if let Some(target) = targets.get_mut(slot) {
+            *target = pos;
+        }
+    }
+
+    Ok(targets)
+}
+
+/// Resolve the position a label refers to.
+fn resolve(targets: &[usize], label: &Label) -> usize {
+    label
+        .jump()
+        .and_then(|slot| targets.get(slot).copied())
+        .unwrap_or(NONE)
+}
+
+/// Get the label an instruction might jump to.
+fn jump_label(inst: &AssemblyInst) -> Option<&Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label, .. }
+        | AssemblyInst::JumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. }
+        | AssemblyInst::OpJumpIfNot { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+/// Get the label an instruction might jump to mutably.
+fn jump_label_mut(inst: &mut AssemblyInst) -> Option<&mut Label> {
+    match inst {
+        AssemblyInst::Jump { label }
+        | AssemblyInst::JumpIf { label, .. }
+        | AssemblyInst::JumpIfNot { label, .. }
+        | AssemblyInst::IterNext { label, .. }
+        | AssemblyInst::OpJumpIfNot { label, .. } => Some(label),
+        AssemblyInst::Raw { .. } => None,
+    }
+}
+
+/// Construct the successors of every instruction.
+///
+/// Successors which do not exist are represented by [`NONE`].
+fn successors(asm: &Assembly) -> alloc::Result<Vec<[usize; 2]>> {
+    let targets = targets(asm)?;
+    let mut successors = Vec::try_with_capacity(asm.instructions.len())?;
+
+    for (pos, (inst, _)) in asm.instructions.iter().enumerate() {
+        let next = pos + 1;
+
+        let entry = match inst {
+            AssemblyInst::Jump { label } => [resolve(&targets, label), NONE],
+            AssemblyInst::JumpIf { label, .. }
+            | AssemblyInst::JumpIfNot { label, .. }
+            | AssemblyInst::IterNext { label, .. }
+            | AssemblyInst::OpJumpIfNot { label, .. } => [next, resolve(&targets, label)],
+            AssemblyInst::Raw {
+                raw: Kind::Return { .. } | Kind::ReturnUnit | Kind::Panic { .. },
+            } => [NONE, NONE],
+            AssemblyInst::Raw { .. } => [next, NONE],
+        };
+
+        successors.try_push(entry)?;
+    }
+
+    Ok(successors)
+}
+
+/// Get the single output of an instruction, if it only writes to one slot.
+fn output_mut(inst: &mut AssemblyInst) -> Option<&mut Output> {
+    match inst {
+        AssemblyInst::IterNext { out, .. } | AssemblyInst::OpJumpIfNot { out, .. } => Some(out),
+        AssemblyInst::Raw { raw } => raw_output_mut(raw),
+        _ => None,
+    }
+}
+
+fn raw_output_mut(raw: &mut Kind) -> Option<&mut Output> {
+    match raw {
+        Kind::Not { out, .. }
+        | Kind::Neg { out, .. }
+        | Kind::Closure { out, .. }
+        | Kind::CallOffset { out, .. }
+        | Kind::Call { out, .. }
+        | Kind::CallAssociated { out, .. }
+        | Kind::LoadInstanceFn { out, .. }
+        | Kind::CallFn { out, .. }
+        | Kind::IndexGet { out, .. }
+        | Kind::TupleIndexGetAt { out, .. }
+        | Kind::ObjectIndexGetAt { out, .. }
+        | Kind::Await { out, .. }
+        | Kind::LoadFn { out, .. }
+        | Kind::Store { out, .. }
+        | Kind::Copy { out, .. }
+        | Kind::Move { out, .. }
+        | Kind::Vec { out, .. }
+        | Kind::Tuple1 { out, .. }
+        | Kind::Tuple2 { out, .. }
+        | Kind::Tuple3 { out, .. }
+        | Kind::Tuple4 { out, .. }
+        | Kind::Tuple { out, .. }
+        | Kind::Object { out, .. }
+        | Kind::Range { out, .. }
+        | Kind::Struct { out, .. }
+        | Kind::ConstConstruct { out, .. }
+        | Kind::String { out, .. }
+        | Kind::Bytes { out, .. }
+        | Kind::StringConcat { out, .. }
+        | Kind::Format { out, .. }
+        | Kind::Try { out, .. }
+        | Kind::EqChar { out, .. }
+        | Kind::EqSigned { out, .. }
+        | Kind::EqUnsigned { out, .. }
+        | Kind::EqBool { out, .. }
+        | Kind::EqString { out, .. }
+        | Kind::EqBytes { out, .. }
+        | Kind::MatchType { out, .. }
+        | Kind::MatchSequence { out, .. }
+        | Kind::MatchObject { out, .. }
+        | Kind::Yield { out, .. }
+        | Kind::YieldUnit { out }
+        | Kind::Op { out, .. }
+        | Kind::OpJumpIfNot { out, .. }
+        | Kind::Arithmetic { out, .. }
+        | Kind::Bitwise { out, .. }
+        | Kind::Shift { out, .. } => Some(out),
+        Kind::Allocate { .. }
+        | Kind::TupleIndexSet { .. }
+        | Kind::ObjectIndexSet { .. }
+        | Kind::IndexSet { .. }
+        | Kind::Select { .. }
+        | Kind::Drop { .. }
+        | Kind::Swap { .. }
+        | Kind::Return { .. }
+        | Kind::ReturnUnit
+        | Kind::Jump { .. }
+        | Kind::JumpIf { .. }
+        | Kind::JumpIfNot { .. }
+        | Kind::Environment { .. }
+        | Kind::AssignArithmetic { .. }
+        | Kind::AssignBitwise { .. }
+        | Kind::AssignShift { .. }
+        | Kind::IterNext { .. }
+        | Kind::Panic { .. } => None,
+    }
+}
+
+/// The slot which is unconditionally overwritten by an instruction.
+fn kill(inst: &AssemblyInst) -> Option<Address> {
+    match inst {
+        AssemblyInst::OpJumpIfNot { out, .. } => out.as_addr(),
+        // The output of an iterator is only written when it doesn't jump.
+        AssemblyInst::IterNext { .. } => None,
+        AssemblyInst::Raw { raw } => {
+            let mut raw = *raw;
+            raw_output_mut(&mut raw)?.as_addr()
+        }
+        _ => None,
+    }
+}
+
+/// A read performed by an instruction.
+enum Read {
+    /// Read a single slot.
+    Addr(Address),
+    /// Read a range of slots.
+    Range(Address, usize),
+    /// Read an unknown set of slots.
+    All,
+}
+
+/// Visit every slot read by an instruction.
+fn reads(inst: &AssemblyInst, mut read: impl FnMut(Read)) {
+    let raw = match inst {
+        AssemblyInst::Jump { .. } => return,
+        AssemblyInst::JumpIf { addr, .. }
+        | AssemblyInst::JumpIfNot { addr, .. }
+        | AssemblyInst::IterNext { addr, .. } => {
+            read(Read::Addr(*addr));
+            return;
+        }
+        AssemblyInst::OpJumpIfNot { a, b, .. } => {
+            read(Read::Addr(*a));
+            read(Read::Addr(*b));
+            return;
+        }
+        AssemblyInst::Raw { raw } => *raw,
+    };
+
+    match raw {
+        Kind::Allocate { .. }
+        | Kind::LoadFn { .. }
+        | Kind::Store { .. }
+        | Kind::Drop { .. }
+        | Kind::ReturnUnit
+        | Kind::Jump { .. }
+        | Kind::String { .. }
+        | Kind::Bytes { .. }
+        | Kind::YieldUnit { .. }
+        | Kind::Panic { .. } => {}
+        Kind::Not { addr, .. }
+        | Kind::Neg { addr, .. }
+        | Kind::LoadInstanceFn { addr, .. }
+        | Kind::TupleIndexGetAt { addr, .. }
+        | Kind::ObjectIndexGetAt { addr, .. }
+        | Kind::Await { addr, .. }
+        | Kind::Copy { addr, .. }
+        | Kind::Move { addr, .. }
+        | Kind::Return { addr }
+        | Kind::Environment { addr, .. }
+        | Kind::Format { addr, .. }
+        | Kind::Try { addr, .. }
+        | Kind::EqChar { addr, .. }
+        | Kind::EqSigned { addr, .. }
+        | Kind::EqUnsigned { addr, .. }
+        | Kind::EqBool { addr, .. }
+        | Kind::EqString { addr, .. }
+        | Kind::EqBytes { addr, .. }
+        | Kind::MatchType { addr, .. }
+        | Kind::MatchSequence { addr, .. }
+        | Kind::MatchObject { addr, .. }
+        | Kind::Yield { addr, .. }
+        | Kind::IterNext { addr, .. } => {
+            read(Read::Addr(addr));
+        }
+        Kind::JumpIf { cond, .. } | Kind::JumpIfNot { cond, .. } => {
+            read(Read::Addr(cond));
+        }
+        Kind::Closure { addr, count, .. }
+        | Kind::Vec { addr, count, .. }
+        | Kind::Tuple { addr, count, .. }
+        | Kind::ConstConstruct { addr, count, .. } => {
+            read(Read::Range(addr, count));
+        }
+        Kind::CallOffset { addr, args, .. }
+        | Kind::Call { addr, args, .. }
+        | Kind::CallAssociated { addr, args, .. } => {
+            read(Read::Range(addr, args));
+        }
+        Kind::CallFn {
+            function,
+            addr,
+            args,
+            ..
+        } => {
+            read(Read::Addr(function));
+            read(Read::Range(addr, args));
+        }
+        Kind::StringConcat { addr, len, .. } => {
+            read(Read::Range(addr, len));
+        }
+        Kind::IndexGet { target, index, .. } => {
+            read(Read::Addr(target));
+            read(Read::Addr(index));
+        }
+        Kind::TupleIndexSet { target, value, .. } | Kind::ObjectIndexSet { target, value, .. } => {
+            read(Read::Addr(target));
+            read(Read::Addr(value));
+        }
+        Kind::IndexSet {
+            target,
+            index,
+            value,
+        } => {
+            read(Read::Addr(target));
+            read(Read::Addr(index));
+            read(Read::Addr(value));
+        }
+        Kind::Swap { a, b }
+        | Kind::Op { a, b, .. }
+        | Kind::OpJumpIfNot { a, b, .. }
+        | Kind::Arithmetic { a, b, .. }
+        | Kind::Bitwise { a, b, .. }
+        | Kind::Shift { a, b, .. } => {
+            read(Read::Addr(a));
+            read(Read::Addr(b));
+        }
+        Kind::Tuple1 { addr, .. } => {
+            addr.into_iter().for_each(|addr| read(Read::Addr(addr)));
+        }
+        Kind::Tuple2 { addr, .. } => {
+            addr.into_iter().for_each(|addr| read(Read::Addr(addr)));
+        }
+        Kind::Tuple3 { addr, .. } => {
+            addr.into_iter().for_each(|addr| read(Read::Addr(addr)));
+        }
+        Kind::Tuple4 { addr, .. } => {
+            addr.into_iter().for_each(|addr| read(Read::Addr(addr)));
+        }
+        Kind::Range { range, .. } => match range {
+            InstRange::RangeFull => {}
+            InstRange::RangeFrom { start } => {
+                read(Read::Addr(start));
+            }
+            InstRange::RangeTo { end } | InstRange::RangeToInclusive { end } => {
+                read(Read::Addr(end));
+            }
+            InstRange::Range { start, end } | InstRange::RangeInclusive { start, end } => {
+                read(Read::Addr(start));
+                read(Read::Addr(end));
+            }
+        },
+        Kind::AssignArithmetic { target, rhs, .. }
+        | Kind::AssignBitwise { target, rhs, .. }
+        | Kind::AssignShift { target, rhs, .. } => {
+            let (InstTarget::Address(target)
+            | InstTarget::Field(target, _)
+            | InstTarget::TupleField(target, _)) = target;
+            read(Read::Addr(target));
+            read(Read::Addr(rhs));
+        }
+        // The number of slots read by these depends on metadata which isn't
+        // available in the assembly.
+        Kind::Select { .. } | Kind::Object { .. } | Kind::Struct { .. } => {
+            read(Read::All);
+        }
+    }
+}
+
+/// Which slots are live after every instruction in an assembly.
+struct Liveness {
+    words: usize,
+    live_out: Vec<u64>,
+}
+
+impl Liveness {
+    /// Compute liveness for the given assembly, or `None` if the function is
+    /// too large to analyze.
+    fn compute(asm: &Assembly, size: usize) -> alloc::Result<Option<Self>> {
+        let len = asm.instructions.len();
+        let words = size.div_ceil(64);
+
+        let Some(total) = len
+            .checked_mul(words)
+            .filter(|total| *total <= MAX_LIVENESS_WORDS)
+        else {
+            return Ok(None);
+        };
+
+        let successors = successors(asm)?;
+        let mut live_in = try_vec![0u64; total];
+        let mut live_out = try_vec![0u64; total];
+        let mut set = try_vec![0u64; words];
+
+        loop {
+            let mut changed = false;
+
+            for pos in (0..len).rev() {
+                set.fill(0);
+
+                for next in successors[pos] {
+                    if next < len {
+                        let live = &live_in[next * words..][..words];
+
+                        for (to, from) in set.iter_mut().zip(live) {
+                            *to |= *from;
+                        }
+                    }
+                }
+
+                live_out[pos * words..][..words].copy_from_slice(&set);
+
+                let inst = &asm.instructions[pos].0;
+
+                if let Some(addr) = kill(inst) {
+                    if addr.offset() < size {
+                        set[addr.offset() / 64] &= !(1u64 << (addr.offset() % 64));
+                    }
+                }
+
+                reads(inst, |read| {
+                    let (start, count) = match read {
+                        Read::Addr(addr) => (addr.offset(), 1),
+                        Read::Range(addr, count) => (addr.offset(), count),
+                        Read::All => (0, size),
+                    };
+
+                    let end = start.saturating_add(count).min(size);
+
+                    for n in start..end {
+                        set[n / 64] |= 1u64 << (n % 64);
+                    }
+                });
+
+                let live = &mut live_in[pos * words..][..words];
+
+                if *live != *set {
+                    live.copy_from_slice(&set);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        Ok(Some(Self { words, live_out }))
+    }
+
+    /// Test if the given slot is live after the instruction at `pos`.
+    fn is_live_after(&self, pos: usize, addr: Address) -> bool {
+        let n = addr.offset();
+
+        // Slots outside of the frame are conservatively treated as live.
+        if n / 64 >= self.words {
+            return true;
+        }
+
+        let Some(word) = self.live_out.get(pos * self.words + n / 64) else {
+            return true;
+        };
+
+        word & (1u64 << (n % 64)) != 0
+    }
+}
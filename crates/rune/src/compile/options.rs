@@ -2,6 +2,7 @@ use core::fmt;
 
 use rust_alloc::boxed::Box;
 
+use crate::diagnostics::{Lint, LintLevel, LintLevels};
 use crate::docstring;
 
 /// Error raised when trying to parse an invalid option.
@@ -25,6 +26,26 @@ impl fmt::Display for ParseOptionError {
 
 impl core::error::Error for ParseOptionError {}
 
+/// How indentation is written when formatting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FmtIndent {
+    /// Indent with the given number of spaces.
+    Spaces(usize),
+    /// Indent with one tab per level.
+    Tab,
+}
+
+/// When to write a trailing comma after the last element of a list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TrailingComma {
+    /// Only when the list is laid out over multiple lines.
+    Vertical,
+    /// Always, including lists on a single line.
+    Always,
+    /// Never, unless it is needed like in a single element tuple.
+    Never,
+}
+
 /// Options specific to formatting.
 #[derive(Debug, Clone)]
 pub(crate) struct FmtOptions {
@@ -32,6 +53,17 @@ pub(crate) struct FmtOptions {
     pub(crate) error_recovery: bool,
     /// Force newline at end of document.
     pub(crate) force_newline: bool,
+    /// The width at which lists, objects and chains are expanded over multiple
+    /// lines.
+    pub(crate) max_width: usize,
+    /// How to indent.
+    pub(crate) indent: FmtIndent,
+    /// When to write trailing commas.
+    pub(crate) trailing_comma: TrailingComma,
+    /// Sort consecutive imports and the names inside of import groups.
+    pub(crate) sort_imports: bool,
+    /// Separate imports into `std`, external and local groups.
+    pub(crate) group_imports: bool,
 }
 
 impl FmtOptions {
@@ -39,6 +71,11 @@ impl FmtOptions {
     pub(crate) const DEFAULT: Self = Self {
         error_recovery: false,
         force_newline: true,
+        max_width: 80,
+        indent: FmtIndent::Spaces(4),
+        trailing_comma: TrailingComma::Vertical,
+        sort_imports: false,
+        group_imports: false,
     };
 
     /// Parse an option with the extra diagnostics metadata.
@@ -53,6 +90,11 @@ impl FmtOptions {
             (option.trim(), None)
         };
 
+        let error = || ParseOptionError {
+            env,
+            option: option.into(),
+        };
+
         match head {
             "error-recovery" => {
                 self.error_recovery = tail.is_none_or(|s| s == "true");
@@ -60,11 +102,39 @@ impl FmtOptions {
             "force-newline" => {
                 self.force_newline = tail.is_none_or(|s| s == "true");
             }
+            "max-width" => {
+                let Some(Ok(width)) = tail.map(str::parse) else {
+                    return Err(error());
+                };
+
+                self.max_width = width;
+            }
+            "indent" => {
+                self.indent = match tail {
+                    Some("tab") => FmtIndent::Tab,
+                    Some(n) => match n.parse() {
+                        Ok(n) if n > 0 => FmtIndent::Spaces(n),
+                        _ => return Err(error()),
+                    },
+                    None => return Err(error()),
+                };
+            }
+            "trailing-comma" => {
+                self.trailing_comma = match tail {
+                    Some("vertical") => TrailingComma::Vertical,
+                    Some("always") => TrailingComma::Always,
+                    Some("never") => TrailingComma::Never,
+                    _ => return Err(error()),
+                };
+            }
+            "sort-imports" => {
+                self.sort_imports = tail.is_none_or(|s| s == "true");
+            }
+            "group-imports" => {
+                self.group_imports = tail.is_none_or(|s| s == "true");
+            }
             _ => {
-                return Err(ParseOptionError {
-                    env,
-                    option: option.into(),
-                });
+                return Err(error());
             }
         }
 
@@ -124,6 +194,14 @@ pub struct Options {
     pub(crate) v2: bool,
     /// Maximum macro depth.
     pub(crate) max_macro_depth: usize,
+    /// Check type annotations.
+    pub(crate) type_check: bool,
+    /// Run optimization passes over assembled instructions.
+    pub(crate) optimize: bool,
+    /// Give suffixed integer and byte literals their sized types.
+    pub(crate) sized_integers: bool,
+    /// The level of lints.
+    pub(crate) lints: LintLevels,
     /// Rune format options.
     pub(crate) fmt: FmtOptions,
 }
@@ -142,6 +220,10 @@ impl Options {
         print_tree: false,
         v2: false,
         max_macro_depth: 64,
+        type_check: false,
+        optimize: false,
+        sized_integers: false,
+        lints: LintLevels::DEFAULT,
         fmt: FmtOptions::DEFAULT,
     };
 
@@ -271,6 +353,9 @@ impl Options {
                 unstable: true,
                 doc: &docstring! {
                     /// Use the v2 compiler.
+                    ///
+                    /// The v2 compiler does not support trait declarations or
+                    /// trait implementations yet.
                 },
                 default: "false",
                 options: BOOL,
@@ -284,6 +369,59 @@ impl Options {
                 default: "64",
                 options: "<number>",
             },
+            OptionMeta {
+                key: "type-check",
+                unstable: false,
+                doc: &docstring! {
+                    /// Check type annotations on function arguments,
+                    /// return values and struct fields against the
+                    /// types they are used with.
+                    ///
+                    /// Without this, type annotations are not permitted.
+                },
+                default: "false",
+                options: BOOL,
+            },
+            OptionMeta {
+                key: "optimize",
+                unstable: false,
+                doc: &docstring! {
+                    /// Run optimization passes over the instructions of
+                    /// every function, like constant folding and the
+                    /// removal of dead stores and redundant jumps.
+                    ///
+                    /// Optimized units are not suitable for debugging, since
+                    /// variables and the instructions which source lines map
+                    /// to might have been removed.
+                },
+                default: "false",
+                options: BOOL,
+            },
+            OptionMeta {
+                key: "sized-integers",
+                unstable: false,
+                doc: &docstring! {
+                    /// Give integer literals with a suffix like `u8` or
+                    /// `i128`, and byte literals, their sized integer types.
+                    ///
+                    /// Without this, they are widened to `i64` or `u64`.
+                },
+                default: "false",
+                options: BOOL,
+            },
+            OptionMeta {
+                key: "lint.<name>",
+                unstable: false,
+                doc: &docstring! {
+                    /// Set the level of the named lint, or group of
+                    /// lints like `unused`.
+                    ///
+                    /// A lint which is denied causes compilation to
+                    /// fail.
+                },
+                default: "<depends on lint>",
+                options: "allow, warn, deny",
+            },
             OptionMeta {
                 key: "fmt.error-recovery",
                 unstable: true,
@@ -305,6 +443,62 @@ impl Options {
                 default: "true",
                 options: BOOL,
             },
+            OptionMeta {
+                key: "fmt.max-width",
+                unstable: false,
+                doc: &docstring! {
+                    /// The width at which lists, objects and chained
+                    /// calls are expanded over multiple lines.
+                },
+                default: "80",
+                options: "<number>",
+            },
+            OptionMeta {
+                key: "fmt.indent",
+                unstable: false,
+                doc: &docstring! {
+                    /// The number of spaces to indent with, or `tab`
+                    /// to indent with tabs.
+                },
+                default: "4",
+                options: "<number>, tab",
+            },
+            OptionMeta {
+                key: "fmt.trailing-comma",
+                unstable: false,
+                doc: &docstring! {
+                    /// When to write a trailing comma after the last
+                    /// element of a list. With `vertical` this is only
+                    /// done for lists which span multiple lines.
+                },
+                default: "vertical",
+                options: "vertical, always, never",
+            },
+            OptionMeta {
+                key: "fmt.sort-imports",
+                unstable: false,
+                doc: &docstring! {
+                    /// Sort consecutive `use` declarations and the
+                    /// names inside of import groups.
+                    ///
+                    /// Declarations separated by comments or
+                    /// attributes are left in place.
+                },
+                default: "false",
+                options: BOOL,
+            },
+            OptionMeta {
+                key: "fmt.group-imports",
+                unstable: false,
+                doc: &docstring! {
+                    /// Separate consecutive `use` declarations into
+                    /// groups of `std`, external and local imports,
+                    /// separated by an empty line. Implies
+                    /// `fmt.sort-imports`.
+                },
+                default: "false",
+                options: BOOL,
+            },
         ];
 
         VALUES
@@ -384,6 +578,15 @@ impl Options {
 
                     self.max_macro_depth = number;
                 }
+                "type-check" => {
+                    self.type_check = tail.is_none_or(|s| s == "true");
+                }
+                "optimize" => {
+                    self.optimize = tail.is_none_or(|s| s == "true");
+                }
+                "sized-integers" => {
+                    self.sized_integers = tail.is_none_or(|s| s == "true");
+                }
                 other => {
                     let Some((head, tail)) = other.split_once('.') else {
                         return Err(ParseOptionError {
@@ -397,7 +600,26 @@ impl Options {
 
                     match head {
                         "fmt" => {
-                            self.fmt.parse_option_with(tail, env)?;
+                            // Forward the value as well, which was split off
+                            // from the head above.
+                            let option = option.split_once('.').map_or(tail, |(_, rest)| rest);
+                            self.fmt.parse_option_with(option, env)?;
+                        }
+                        "lint" => {
+                            let level = option.split_once('=').map(|(_, level)| level.trim());
+
+                            let (Some(lints), Some(level)) =
+                                (Lint::lookup(tail), level.and_then(LintLevel::parse))
+                            else {
+                                return Err(ParseOptionError {
+                                    env,
+                                    option: option.into(),
+                                });
+                            };
+
+                            for &lint in lints {
+                                self.lints.set(lint, level);
+                            }
                         }
                         _ => {
                             return Err(ParseOptionError {
@@ -457,6 +679,45 @@ impl Options {
     pub fn script(&mut self, enabled: bool) {
         self.script = enabled;
     }
+
+    /// Set if type annotations should be checked. Defaults to `false`.
+    ///
+    /// Type annotations are only permitted when this is enabled.
+    #[inline]
+    pub fn type_check(&mut self, enabled: bool) {
+        self.type_check = enabled;
+    }
+
+    /// Set if optimization passes should be run over assembled instructions.
+    /// Defaults to `false`.
+    #[inline]
+    pub fn optimize(&mut self, enabled: bool) {
+        self.optimize = enabled;
+    }
+
+    /// Set if suffixed integer literals and byte literals should have sized
+    /// integer types like `u8`, instead of being widened to `i64` or `u64`.
+    /// Defaults to `false`.
+    #[inline]
+    pub fn sized_integers(&mut self, enabled: bool) {
+        self.sized_integers = enabled;
+    }
+
+    /// Set the level of the given lint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::Options;
+    /// use rune::diagnostics::{Lint, LintLevel};
+    ///
+    /// let mut options = Options::default();
+    /// options.lint_level(Lint::UnusedImports, LintLevel::Deny);
+    /// ```
+    #[inline]
+    pub fn lint_level(&mut self, lint: Lint, level: LintLevel) {
+        self.lints.set(lint, level);
+    }
 }
 
 impl Default for Options {
@@ -0,0 +1,118 @@
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::alloc::{HashMap, String};
+use crate::ast;
+use crate::compile;
+use crate::parse::{self, Parse};
+use crate::SourceId;
+
+/// A parsed source stored in a [`ParseCache`].
+#[derive(TryClone)]
+pub(crate) enum Parsed {
+    File(ast::File),
+    Body(ast::EmptyBlock),
+}
+
+/// An abstract syntax tree which can be stored in a [`ParseCache`].
+pub(crate) trait CachedAst: Parse + TryClone {
+    #[doc(hidden)]
+    fn wrap(self) -> Parsed;
+
+    #[doc(hidden)]
+    fn unwrap(parsed: &Parsed) -> Option<&Self>;
+}
+
+impl CachedAst for ast::File {
+    fn wrap(self) -> Parsed {
+        Parsed::File(self)
+    }
+
+    fn unwrap(parsed: &Parsed) -> Option<&Self> {
+        match parsed {
+            Parsed::File(file) => Some(file),
+            _ => None,
+        }
+    }
+}
+
+impl CachedAst for ast::EmptyBlock {
+    fn wrap(self) -> Parsed {
+        Parsed::Body(self)
+    }
+
+    fn unwrap(parsed: &Parsed) -> Option<&Self> {
+        match parsed {
+            Parsed::Body(body) => Some(body),
+            _ => None,
+        }
+    }
+}
+
+struct Entry {
+    /// The text the source had when it was parsed.
+    text: String,
+    parsed: Parsed,
+    /// If the entry has been used since the last call to
+    /// [`ParseCache::retain_used`].
+    used: bool,
+}
+
+/// A cache of parsed sources which is kept across builds.
+///
+/// Entries are keyed by [`SourceId`] and are only reused if the text of the
+/// source is unchanged since it was parsed. Only the parsing is cached, the
+/// parsed tree is still indexed and compiled from scratch by every build.
+#[derive(Default)]
+pub(crate) struct ParseCache {
+    entries: HashMap<SourceId, Entry>,
+    #[cfg(test)]
+    hits: usize,
+}
+
+impl ParseCache {
+    /// Parse the given source, or clone the tree it was parsed into by an
+    /// earlier build if its text is unchanged.
+    pub(crate) fn parse<T>(&mut self, source_id: SourceId, text: &str) -> compile::Result<T>
+    where
+        T: CachedAst,
+    {
+        if let Some(entry) = self.entries.get_mut(&source_id) {
+            if entry.text == text {
+                if let Some(ast) = T::unwrap(&entry.parsed) {
+                    entry.used = true;
+
+                    #[cfg(test)]
+                    {
+                        self.hits += 1;
+                    }
+
+                    return Ok(ast.try_clone()?);
+                }
+            }
+        }
+
+        let ast = parse::parse_all::<T>(text, source_id, true)?;
+
+        let entry = Entry {
+            text: text.try_to_owned()?,
+            parsed: ast.try_clone()?.wrap(),
+            used: true,
+        };
+
+        self.entries.try_insert(source_id, entry)?;
+        Ok(ast)
+    }
+
+    /// Remove entries which haven't been used since the last call, so that
+    /// sources which are no longer part of the build are forgotten.
+    pub(crate) fn retain_used(&mut self) {
+        self.entries
+            .retain(|_, entry| core::mem::take(&mut entry.used));
+    }
+
+    /// The number of times a parsed tree has been reused.
+    #[cfg(test)]
+    pub(crate) fn hits(&self) -> usize {
+        self.hits
+    }
+}
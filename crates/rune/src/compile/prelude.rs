@@ -24,6 +24,13 @@ impl Prelude {
         this.add_prelude("f64", ["f64"])?;
         this.add_prelude("i64", ["i64"])?;
         this.add_prelude("u64", ["u64"])?;
+        this.add_prelude("u16", ["u16"])?;
+        this.add_prelude("u32", ["u32"])?;
+        this.add_prelude("u128", ["u128"])?;
+        this.add_prelude("i8", ["i8"])?;
+        this.add_prelude("i16", ["i16"])?;
+        this.add_prelude("i32", ["i32"])?;
+        this.add_prelude("i128", ["i128"])?;
         this.add_prelude("char", ["char"])?;
         this.add_prelude("dbg", ["io", "dbg"])?;
         this.add_prelude("drop", ["mem", "drop"])?;
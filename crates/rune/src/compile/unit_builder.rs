@@ -13,7 +13,7 @@ use crate::compile::meta;
 use crate::compile::{self, Assembly, AssemblyInst, ErrorKind, Location, Pool, WithSpan};
 use crate::hash;
 use crate::query::QueryInner;
-use crate::runtime::debug::{DebugArgs, DebugSignature};
+use crate::runtime::debug::{DebugArgs, DebugSignature, DebugVariable};
 use crate::runtime::inst;
 use crate::runtime::unit::UnitEncoder;
 use crate::runtime::{
@@ -832,10 +832,13 @@ impl UnitBuilder {
             }
         }
 
+        let mut offsets = Vec::try_with_capacity(assembly.instructions.len())?;
+
         for (pos, (inst, span)) in assembly.instructions.into_iter().enumerate() {
             let mut comment = String::new();
 
             let at = storage.offset();
+            offsets.try_push(at)?;
 
             let mut labels = Vec::new();
 
@@ -891,6 +894,25 @@ impl UnitBuilder {
                         .encode(Inst::new(inst::Kind::IterNext { addr, jump, out }))
                         .with_span(span)?;
                 }
+                AssemblyInst::OpJumpIfNot {
+                    op,
+                    a,
+                    b,
+                    out,
+                    label,
+                } => {
+                    write!(comment, "label:{}", label)?;
+                    let jump = build_label(label)?;
+                    storage
+                        .encode(Inst::new(inst::Kind::OpJumpIfNot {
+                            op,
+                            a,
+                            b,
+                            out,
+                            jump,
+                        }))
+                        .with_span(span)?;
+                }
                 AssemblyInst::Raw { raw } => {
                     // Optimization to avoid performing lookups for recursive
                     // function calls.
@@ -942,6 +964,28 @@ impl UnitBuilder {
             )?;
         }
 
+        let end = storage.offset();
+
+        for var in assembly.variables {
+            let Some(name) = var.name else {
+                continue;
+            };
+
+            let start = offsets.get(var.start).copied().unwrap_or(end);
+
+            let end = var
+                .end
+                .and_then(|end| offsets.get(end).copied())
+                .unwrap_or(end);
+
+            self.debug_mut()?.variables.try_push(DebugVariable::new(
+                name,
+                var.addr.offset(),
+                start,
+                end,
+            ))?;
+        }
+
         Ok(())
     }
 }
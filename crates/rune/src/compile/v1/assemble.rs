@@ -227,7 +227,7 @@ pub(crate) fn fn_from_item_fn<'hir>(
                     return Err(compile::Error::new(span, ErrorKind::UnsupportedSelf));
                 }
 
-                cx.scopes.define(span, *name, needs)?;
+                cx.scopes.define(cx.asm, span, *name, needs)?;
             }
             hir::FnArg::Pat(pat) => {
                 let asm = pattern_panic(cx, pat, move |cx, false_label| {
@@ -252,7 +252,7 @@ pub(crate) fn fn_from_item_fn<'hir>(
     }
 
     arguments.free()?;
-    cx.scopes.pop_last(hir)?;
+    cx.scopes.pop_last(cx.asm, hir)?;
     Ok(())
 }
 
@@ -265,13 +265,13 @@ pub(crate) fn async_block_secondary<'hir>(
     let linear = cx.scopes.linear(&hir.block, hir.captures.len())?;
 
     for (name, needs) in hir.captures.iter().copied().zip(&linear) {
-        cx.scopes.define(&hir.block, name, needs)?;
+        cx.scopes.define(cx.asm, &hir.block, name, needs)?;
     }
 
     return_(cx, &hir.block, hir.block, block_without_scope)?.ignore();
 
     linear.free()?;
-    cx.scopes.pop_last(&hir.block)?;
+    cx.scopes.pop_last(cx.asm, &hir.block)?;
     Ok(())
 }
 
@@ -295,7 +295,7 @@ pub(crate) fn expr_closure_secondary<'hir>(
         )?;
 
         for (capture, needs) in hir.captures.iter().copied().zip(&environment) {
-            cx.scopes.define(hir, capture, needs)?;
+            cx.scopes.define(cx.asm, hir, capture, needs)?;
         }
     }
 
@@ -318,7 +318,7 @@ pub(crate) fn expr_closure_secondary<'hir>(
 
     environment.free()?;
     arguments.free()?;
-    cx.scopes.pop_last(hir)?;
+    cx.scopes.pop_last(cx.asm, hir)?;
     Ok(())
 }
 
@@ -451,7 +451,7 @@ fn pat_binding_with<'a, 'hir>(
     }
 
     for (name, needs) in names.iter().copied().zip(linear.iter()) {
-        cx.scopes.define(needs.span(), name, needs)?;
+        cx.scopes.define(cx.asm, needs.span(), name, needs)?;
     }
 
     Ok(asm)
@@ -488,7 +488,7 @@ fn pat_binding_with_single<'a, 'hir>(
         ));
     };
 
-    cx.scopes.define(needs.span(), name, addr)?;
+    cx.scopes.define(cx.asm, needs.span(), name, addr)?;
     Ok(asm)
 }
 
@@ -628,6 +628,36 @@ fn pat_lit_inst(
         },
         hir::Lit::Unsigned(value) => inst::Kind::EqUnsigned { addr, value, out },
         hir::Lit::Signed(value) => inst::Kind::EqSigned { addr, value, out },
+        hir::Lit::U8(value) => inst::Kind::EqUnsigned {
+            addr,
+            value: value.into(),
+            out,
+        },
+        hir::Lit::U16(value) => inst::Kind::EqUnsigned {
+            addr,
+            value: value.into(),
+            out,
+        },
+        hir::Lit::U32(value) => inst::Kind::EqUnsigned {
+            addr,
+            value: value.into(),
+            out,
+        },
+        hir::Lit::I8(value) => inst::Kind::EqSigned {
+            addr,
+            value: value.into(),
+            out,
+        },
+        hir::Lit::I16(value) => inst::Kind::EqSigned {
+            addr,
+            value: value.into(),
+            out,
+        },
+        hir::Lit::I32(value) => inst::Kind::EqSigned {
+            addr,
+            value: value.into(),
+            out,
+        },
         hir::Lit::Bool(value) => inst::Kind::EqBool { addr, value, out },
         _ => return Ok(None),
     };
@@ -655,7 +685,7 @@ fn condition<'a, 'hir>(
                 Asm::new(hir, (scope, Pattern::Irrefutable))
             } else {
                 addr.free()?;
-                cx.scopes.pop(hir, scope)?;
+                cx.scopes.pop(cx.asm, hir, scope)?;
                 Asm::diverge(hir)
             };
 
@@ -684,7 +714,7 @@ fn condition<'a, 'hir>(
                 cx.asm.jump(then_label, span)?;
                 Ok(Asm::new(span, (scope, pat)))
             } else {
-                cx.scopes.pop(span, scope)?;
+                cx.scopes.pop(cx.asm, span, scope)?;
                 Ok(Asm::diverge(span))
             }
         }
@@ -910,7 +940,7 @@ fn block<'a, 'hir>(
 
     let scope = cx.scopes.child(hir)?;
     let asm = block_without_scope(cx, hir, needs)?;
-    cx.scopes.pop(hir, scope)?;
+    cx.scopes.pop(cx.asm, hir, scope)?;
 
     cx.drop_dangling(hir)?;
 
@@ -1109,6 +1139,24 @@ fn const_<'a, 'hir>(
             Inline::Unsigned(v) => {
                 cx.asm.push(inst::Kind::unsigned(v, out), span)?;
             }
+            Inline::U8(v) => {
+                cx.asm.push(inst::Kind::u8(v, out), span)?;
+            }
+            Inline::U16(v) => {
+                cx.asm.push(inst::Kind::u16(v, out), span)?;
+            }
+            Inline::U32(v) => {
+                cx.asm.push(inst::Kind::u32(v, out), span)?;
+            }
+            Inline::I8(v) => {
+                cx.asm.push(inst::Kind::i8(v, out), span)?;
+            }
+            Inline::I16(v) => {
+                cx.asm.push(inst::Kind::i16(v, out), span)?;
+            }
+            Inline::I32(v) => {
+                cx.asm.push(inst::Kind::i32(v, out), span)?;
+            }
             Inline::Float(v) => {
                 cx.asm.push(inst::Kind::float(v, out), span)?;
             }
@@ -1125,6 +1173,12 @@ fn const_<'a, 'hir>(
                 cx.asm.push(inst::Kind::hash(v, out), span)?;
             }
         },
+        ConstValueKind::U128(v) => {
+            cx.asm.push(inst::Kind::u128(*v, out), span)?;
+        }
+        ConstValueKind::I128(v) => {
+            cx.asm.push(inst::Kind::i128(*v, out), span)?;
+        }
         ConstValueKind::String(s) => {
             let slot = cx.q.unit.new_static_string(span, s)?;
             cx.asm.push(inst::Kind::String { slot, out }, span)?;
@@ -2353,7 +2407,7 @@ fn expr_for<'a, 'hir>(
 
     let asm = block(cx, &hir.body, &mut Any::ignore(span))?;
     bindings.free()?;
-    cx.scopes.pop(span, inner_loop_scope)?;
+    cx.scopes.pop(cx.asm, span, inner_loop_scope)?;
 
     if asm.converging() {
         cx.asm.jump(&continue_label, span)?;
@@ -2470,7 +2524,7 @@ fn expr_if<'a, 'hir>(
             block(cx, &branch.block, needs)?
         };
 
-        cx.scopes.pop(branch, scope)?;
+        cx.scopes.pop(cx.asm, branch, scope)?;
 
         if asm.converging() && it.peek().is_some() {
             cx.asm.jump(&end_label, branch)?;
@@ -2599,7 +2653,7 @@ fn expr_match<'a, 'hir>(
                 }
 
                 cond.free()?;
-                cx.scopes.pop(span, scope)?;
+                cx.scopes.pop(cx.asm, span, scope)?;
             } else {
                 // If there is no branch condition, and the branch is
                 // irrefutable, there is no point in assembling the additional
@@ -2615,7 +2669,7 @@ fn expr_match<'a, 'hir>(
                 // If the branch condition diverges, there is no reason to
                 // assemble the other branches if this one is irrefutable.
                 is_irrefutable = matches!(pat, Pattern::Irrefutable);
-                cx.scopes.pop(span, pattern_scope)?;
+                cx.scopes.pop(cx.asm, span, pattern_scope)?;
             }
         }
 
@@ -2646,7 +2700,7 @@ fn expr_match<'a, 'hir>(
             cx.asm.jump(&end_label, span)?;
         }
 
-        cx.scopes.pop(span, scope)?;
+        cx.scopes.pop(cx.asm, span, scope)?;
     }
 
     cx.asm.label(&end_label)?;
@@ -2945,7 +2999,7 @@ fn expr_select_inner<'a, 'hir>(
             cx.asm.jump(&end_label, span)?;
         }
 
-        cx.scopes.pop(&branch.body, scope)?;
+        cx.scopes.pop(cx.asm, &branch.body, scope)?;
     }
 
     cx.select_branches = branches;
@@ -3195,7 +3249,7 @@ fn expr_loop<'a, 'hir>(
     block(cx, &hir.body, &mut Any::ignore(span))?.ignore();
 
     if let Some(scope) = condition_scope {
-        cx.scopes.pop(span, scope)?;
+        cx.scopes.pop(cx.asm, span, scope)?;
     }
 
     cx.asm.jump(&continue_label, span)?;
@@ -3272,6 +3326,30 @@ fn lit<'a, 'hir>(
         hir::Lit::Signed(v) => {
             cx.asm.push(inst::Kind::signed(v, out), span)?;
         }
+        hir::Lit::U8(v) => {
+            cx.asm.push(inst::Kind::u8(v, out), span)?;
+        }
+        hir::Lit::U16(v) => {
+            cx.asm.push(inst::Kind::u16(v, out), span)?;
+        }
+        hir::Lit::U32(v) => {
+            cx.asm.push(inst::Kind::u32(v, out), span)?;
+        }
+        hir::Lit::I8(v) => {
+            cx.asm.push(inst::Kind::i8(v, out), span)?;
+        }
+        hir::Lit::I16(v) => {
+            cx.asm.push(inst::Kind::i16(v, out), span)?;
+        }
+        hir::Lit::I32(v) => {
+            cx.asm.push(inst::Kind::i32(v, out), span)?;
+        }
+        hir::Lit::U128(v) => {
+            cx.asm.push(inst::Kind::u128(v, out), span)?;
+        }
+        hir::Lit::I128(v) => {
+            cx.asm.push(inst::Kind::i128(v, out), span)?;
+        }
         hir::Lit::Float(v) => {
             cx.asm.push(inst::Kind::float(v, out), span)?;
         }
@@ -291,9 +291,10 @@ impl<'hir> Scopes<'hir> {
     }
 
     /// Construct a new variable.
-    #[tracing::instrument(skip(self, span))]
+    #[tracing::instrument(skip(self, asm, span))]
     pub(super) fn define(
         &self,
+        asm: &mut Assembly,
         span: &'hir dyn Spanned,
         name: hir::Variable,
         addr: &Address<'_, 'hir>,
@@ -312,6 +313,7 @@ impl<'hir> Scopes<'hir> {
         };
 
         scope.names.try_insert(name, var).with_span(span)?;
+        asm.define_variable(name, addr.addr()).with_span(span)?;
         tracing::trace!(?scope, ?name);
         Ok(())
     }
@@ -473,8 +475,13 @@ impl<'hir> Scopes<'hir> {
         Ok(())
     }
 
-    #[tracing::instrument(skip(self, span, handle), fields(id = ?handle.id))]
-    pub(super) fn pop(&self, span: &dyn Spanned, handle: ScopeHandle) -> compile::Result<()> {
+    #[tracing::instrument(skip(self, asm, span, handle), fields(id = ?handle.id))]
+    pub(super) fn pop(
+        &self,
+        asm: &mut Assembly,
+        span: &dyn Spanned,
+        handle: ScopeHandle,
+    ) -> compile::Result<()> {
         let ScopeHandle { id } = handle;
 
         let Some(mut scope) = self.scopes.borrow_mut().try_remove(id.index) else {
@@ -514,15 +521,19 @@ impl<'hir> Scopes<'hir> {
             dangling.insert(addr).with_span(span)?;
         }
 
+        for &name in scope.names.keys() {
+            asm.end_variable(name);
+        }
+
         scope.locals.clear();
         self.top.set(scope.parent);
         Ok(())
     }
 
     /// Pop the last of the scope.
-    #[tracing::instrument(skip(self, span))]
-    pub(super) fn pop_last(&self, span: &dyn Spanned) -> compile::Result<()> {
-        self.pop(span, ScopeHandle { id: ROOT })?;
+    #[tracing::instrument(skip(self, asm, span))]
+    pub(super) fn pop_last(&self, asm: &mut Assembly, span: &dyn Spanned) -> compile::Result<()> {
+        self.pop(asm, span, ScopeHandle { id: ROOT })?;
         Ok(())
     }
 
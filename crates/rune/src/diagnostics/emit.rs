@@ -470,10 +470,16 @@ where
         );
     }
 
-    let diagnostic = d::Diagnostic::warning()
-        .with_message("Warning")
-        .with_labels(labels)
-        .with_notes(notes);
+    let level = if this.is_denied() { "deny" } else { "warn" };
+    notes.push(format!("Note: `#[{level}({})]` is enabled", this.lint()));
+
+    let diagnostic = if this.is_denied() {
+        d::Diagnostic::error().with_message("Error")
+    } else {
+        d::Diagnostic::warning().with_message("Warning")
+    };
+
+    let diagnostic = diagnostic.with_labels(labels).with_notes(notes);
 
     term::emit(out, config, sources, &diagnostic)?;
     Ok(())
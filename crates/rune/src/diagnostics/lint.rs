@@ -0,0 +1,236 @@
+use core::fmt;
+use core::slice;
+
+/// The level at which a [`Lint`] is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LintLevel {
+    /// The lint is not reported.
+    Allow,
+    /// The lint is reported as a warning.
+    Warn,
+    /// The lint is reported as an error, which causes compilation to fail.
+    Deny,
+}
+
+impl LintLevel {
+    /// Parse a lint level, which is one of `allow`, `warn` or `deny`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::diagnostics::LintLevel;
+    ///
+    /// assert_eq!(LintLevel::parse("deny"), Some(LintLevel::Deny));
+    /// assert_eq!(LintLevel::parse("forbid"), None);
+    /// ```
+    pub fn parse(level: &str) -> Option<Self> {
+        match level {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+
+    /// Get the name of the level, as used in attributes like `#[allow(..)]`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// A named lint which can be allowed, warned about or denied.
+///
+/// Lint levels can be set through the `lint.<name>=<level>` compiler option,
+/// through a `[lints]` table in `Rune.toml`, or for a single item through the
+/// `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Lint {
+    /// Code which is never used, like an expression whose value is discarded
+    /// or a function which is never called.
+    UnusedCode,
+    /// An import which is never used.
+    UnusedImports,
+    /// A function parameter which is never used.
+    UnusedParameters,
+    /// Code which can never be reached.
+    UnreachableCode,
+    /// A `let` pattern which might not match, causing a panic.
+    LetPatternMightPanic,
+    /// A template string without any expansions.
+    TemplateWithoutExpansions,
+    /// Parenthesis which are not needed when constructing a variant.
+    UnnecessaryParens,
+    /// A semicolon which is not needed.
+    UnnecessarySemicolon,
+    /// Use of a deprecated function.
+    Deprecated,
+    /// A binding which shadows an earlier binding with the same name.
+    ShadowedBindings,
+    /// A `return` which is the last expression in a function.
+    NeedlessReturn,
+    /// A comparison to the unit value `()`.
+    UnitComparisons,
+}
+
+impl Lint {
+    /// All available lints.
+    pub const ALL: &'static [Lint] = &[
+        Lint::UnusedCode,
+        Lint::UnusedImports,
+        Lint::UnusedParameters,
+        Lint::UnreachableCode,
+        Lint::LetPatternMightPanic,
+        Lint::TemplateWithoutExpansions,
+        Lint::UnnecessaryParens,
+        Lint::UnnecessarySemicolon,
+        Lint::Deprecated,
+        Lint::ShadowedBindings,
+        Lint::NeedlessReturn,
+        Lint::UnitComparisons,
+    ];
+
+    /// Named groups of lints which can be configured at once.
+    pub const GROUPS: &'static [(&'static str, &'static [Lint])] = &[(
+        "unused",
+        &[
+            Lint::UnusedCode,
+            Lint::UnusedImports,
+            Lint::UnusedParameters,
+        ],
+    )];
+
+    /// Look up the lints identified by the given name.
+    ///
+    /// This is either the name of a single lint, or the name of a group like
+    /// `unused`. Dashes can be used in place of underscores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::diagnostics::Lint;
+    ///
+    /// assert_eq!(Lint::lookup("unused-imports"), Some(&[Lint::UnusedImports][..]));
+    /// assert!(Lint::lookup("unused").is_some_and(|lints| lints.len() > 1));
+    /// assert_eq!(Lint::lookup("missing"), None);
+    /// ```
+    pub fn lookup(name: &str) -> Option<&'static [Lint]> {
+        fn matches(name: &str, expected: &str) -> bool {
+            name.len() == expected.len()
+                && name
+                    .bytes()
+                    .zip(expected.bytes())
+                    .all(|(a, b)| a == b || a == b'-' && b == b'_')
+        }
+
+        for lint in Self::ALL {
+            if matches(name, lint.name()) {
+                return Some(slice::from_ref(lint));
+            }
+        }
+
+        for &(group, lints) in Self::GROUPS {
+            if matches(name, group) {
+                return Some(lints);
+            }
+        }
+
+        None
+    }
+
+    /// The name of the lint.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnusedCode => "unused_code",
+            Lint::UnusedImports => "unused_imports",
+            Lint::UnusedParameters => "unused_parameters",
+            Lint::UnreachableCode => "unreachable_code",
+            Lint::LetPatternMightPanic => "let_pattern_might_panic",
+            Lint::TemplateWithoutExpansions => "template_without_expansions",
+            Lint::UnnecessaryParens => "unnecessary_parens",
+            Lint::UnnecessarySemicolon => "unnecessary_semicolon",
+            Lint::Deprecated => "deprecated",
+            Lint::ShadowedBindings => "shadowed_bindings",
+            Lint::NeedlessReturn => "needless_return",
+            Lint::UnitComparisons => "unit_comparisons",
+        }
+    }
+
+    /// The level of the lint unless it has been configured.
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            Lint::UnusedParameters
+            | Lint::ShadowedBindings
+            | Lint::NeedlessReturn
+            | Lint::UnitComparisons => LintLevel::Allow,
+            _ => LintLevel::Warn,
+        }
+    }
+
+    /// A short description of what the lint reports.
+    pub fn description(self) -> &'static str {
+        match self {
+            Lint::UnusedCode => "Values, functions and other items which are never used",
+            Lint::UnusedImports => "Imports which are never used",
+            Lint::UnusedParameters => "Function parameters which are never used",
+            Lint::UnreachableCode => "Code which can never be reached",
+            Lint::LetPatternMightPanic => "Let patterns which might panic if they don't match",
+            Lint::TemplateWithoutExpansions => "Template strings without any expansions",
+            Lint::UnnecessaryParens => "Parenthesis which aren't needed to construct a variant",
+            Lint::UnnecessarySemicolon => "Semicolons which aren't needed",
+            Lint::Deprecated => "Use of deprecated functions",
+            Lint::ShadowedBindings => "Bindings which shadow an earlier binding with the same name",
+            Lint::NeedlessReturn => "A `return` as the last expression of a function",
+            Lint::UnitComparisons => "Comparisons to the unit value `()`",
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// The configured level of every lint.
+#[derive(Debug, Clone)]
+pub(crate) struct LintLevels {
+    levels: [Option<LintLevel>; Lint::ALL.len()],
+}
+
+impl LintLevels {
+    /// Lint levels where every lint uses its default level.
+    pub(crate) const DEFAULT: Self = Self {
+        levels: [None; Lint::ALL.len()],
+    };
+
+    /// Set the level of the given lint.
+    pub(crate) fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels[lint as usize] = Some(level);
+    }
+
+    /// Get the level of the given lint.
+    pub(crate) fn get(&self, lint: Lint) -> LintLevel {
+        self.levels[lint as usize].unwrap_or(lint.default_level())
+    }
+}
+
+impl Default for LintLevels {
+    #[inline]
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -37,6 +37,10 @@ pub use self::warning::WarningDiagnostic;
 pub(crate) use self::warning::WarningDiagnosticKind;
 mod warning;
 
+pub(crate) use self::lint::LintLevels;
+pub use self::lint::{Lint, LintLevel};
+mod lint;
+
 pub use self::runtime_warning::RuntimeWarningDiagnostic;
 pub(crate) use self::runtime_warning::RuntimeWarningDiagnosticKind;
 mod runtime_warning;
@@ -45,7 +49,7 @@ use rune_alloc::String;
 use rust_alloc::boxed::Box;
 
 use crate::alloc::{self, Vec};
-use crate::ast::Spanned;
+use crate::ast::{Span, Spanned};
 use crate::{Hash, SourceId};
 
 #[cfg(feature = "emit")]
@@ -115,6 +119,20 @@ pub struct Diagnostics {
     has_error: bool,
     /// Indicates if diagnostics contains warnings.
     has_warning: bool,
+    /// The configured level of lints.
+    lints: LintLevels,
+    /// Lint levels which have been set for a part of a source, like through
+    /// the `#[allow(..)]` attribute on an item.
+    scoped_lints: Vec<ScopedLint>,
+}
+
+/// A lint level which applies to a span in a source.
+#[derive(Debug)]
+struct ScopedLint {
+    source_id: SourceId,
+    span: Span,
+    lint: Lint,
+    level: LintLevel,
 }
 
 impl Diagnostics {
@@ -124,6 +142,8 @@ impl Diagnostics {
             mode,
             has_error: false,
             has_warning: false,
+            lints: LintLevels::DEFAULT,
+            scoped_lints: Vec::new(),
         }
     }
 
@@ -322,27 +342,164 @@ impl Diagnostics {
         )
     }
 
+    /// Add a warning about an import which is never used.
+    pub(crate) fn unused_import(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedImport { span: span.span() },
+        )
+    }
+
+    /// Add a warning about a function parameter which is never used.
+    pub(crate) fn unused_parameter(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        name: &str,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedParameter {
+                span: span.span(),
+                name: name.try_into()?,
+            },
+        )
+    }
+
+    /// Add a warning about a binding which shadows an earlier binding.
+    pub(crate) fn shadowed_binding(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        name: &str,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::ShadowedBinding {
+                span: span.span(),
+                name: name.try_into()?,
+            },
+        )
+    }
+
+    /// Add a warning about a `return` at the end of a function.
+    pub(crate) fn needless_return(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::NeedlessReturn { span: span.span() },
+        )
+    }
+
+    /// Add a warning about a comparison to the unit value.
+    pub(crate) fn unit_comparison(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnitComparison { span: span.span() },
+        )
+    }
+
     /// Add a warning about using a deprecated function
     pub(crate) fn runtime_used_deprecated(&mut self, ip: usize, hash: Hash) -> alloc::Result<()> {
         self.runtime_warning(ip, RuntimeWarningDiagnosticKind::UsedDeprecated { hash })
     }
 
+    /// Configure the level of lints which applies to all sources.
+    ///
+    /// This also clears any lint levels set for parts of sources by an earlier
+    /// build.
+    pub(crate) fn configure_lints(&mut self, lints: &LintLevels) {
+        self.lints = lints.clone();
+        self.scoped_lints.clear();
+    }
+
+    /// Set the level of a lint for the given span in a source.
+    pub(crate) fn set_lint_level(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        lint: Lint,
+        level: LintLevel,
+    ) -> alloc::Result<()> {
+        self.scoped_lints.try_push(ScopedLint {
+            source_id,
+            span,
+            lint,
+            level,
+        })
+    }
+
+    /// Get the level of a lint at the given span, where the innermost scoped
+    /// level takes precedence.
+    fn lint_level(&self, source_id: SourceId, span: Span, lint: Lint) -> LintLevel {
+        let mut level = None::<(u32, LintLevel)>;
+
+        for scoped in &self.scoped_lints {
+            if scoped.source_id != source_id
+                || scoped.lint != lint
+                || scoped.span.start > span.start
+                || scoped.span.end < span.end
+            {
+                continue;
+            }
+
+            let len = scoped.span.end.0.saturating_sub(scoped.span.start.0);
+
+            if level.is_none_or(|(existing, _)| len <= existing) {
+                level = Some((len, scoped.level));
+            }
+        }
+
+        match level {
+            Some((_, level)) => level,
+            None => self.lints.get(lint),
+        }
+    }
+
     /// Push a warning to the collection of diagnostics.
+    ///
+    /// Depending on the level of its lint the warning is either ignored,
+    /// reported or reported as an error.
     pub(crate) fn warning<T>(&mut self, source_id: SourceId, kind: T) -> alloc::Result<()>
     where
         WarningDiagnosticKind: From<T>,
     {
-        if !self.mode.warnings() {
-            return Ok(());
+        let kind = WarningDiagnosticKind::from(kind);
+
+        let warning = WarningDiagnostic {
+            source_id,
+            level: LintLevel::Warn,
+            kind,
+        };
+
+        let level = self.lint_level(source_id, warning.span(), warning.lint());
+
+        match level {
+            LintLevel::Allow => return Ok(()),
+            LintLevel::Warn if !self.mode.warnings() => return Ok(()),
+            _ => {}
         }
 
         self.diagnostics
-            .try_push(Diagnostic::Warning(WarningDiagnostic {
-                source_id,
-                kind: kind.into(),
-            }))?;
+            .try_push(Diagnostic::Warning(WarningDiagnostic { level, ..warning }))?;
+
+        if let LintLevel::Deny = level {
+            self.has_error = true;
+        } else {
+            self.has_warning = true;
+        }
 
-        self.has_warning = true;
         Ok(())
     }
 
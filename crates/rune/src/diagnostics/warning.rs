@@ -3,6 +3,7 @@ use core::fmt;
 use crate::alloc::String;
 use crate::ast::Span;
 use crate::ast::Spanned;
+use crate::diagnostics::{Lint, LintLevel};
 use crate::SourceId;
 
 /// Warning diagnostic emitted during compilation. Warning diagnostics indicates
@@ -13,6 +14,8 @@ pub struct WarningDiagnostic {
     pub(crate) source_id: SourceId,
     /// The kind of the warning.
     pub(crate) kind: WarningDiagnosticKind,
+    /// The level the lint was reported at.
+    pub(crate) level: LintLevel,
 }
 
 impl WarningDiagnostic {
@@ -21,6 +24,17 @@ impl WarningDiagnostic {
         self.source_id
     }
 
+    /// The lint which caused the warning.
+    pub fn lint(&self) -> Lint {
+        self.kind.lint()
+    }
+
+    /// Test if the lint which caused the warning is denied, in which case it
+    /// should be treated as an error.
+    pub fn is_denied(&self) -> bool {
+        matches!(self.level, LintLevel::Deny)
+    }
+
     /// The kind of the warning.
     #[cfg(feature = "emit")]
     pub(crate) fn kind(&self) -> &WarningDiagnosticKind {
@@ -57,6 +71,11 @@ impl Spanned for WarningDiagnostic {
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
             WarningDiagnosticKind::UsedDeprecated { span, .. } => *span,
+            WarningDiagnosticKind::UnusedImport { span, .. } => *span,
+            WarningDiagnosticKind::UnusedParameter { span, .. } => *span,
+            WarningDiagnosticKind::ShadowedBinding { span, .. } => *span,
+            WarningDiagnosticKind::NeedlessReturn { span, .. } => *span,
+            WarningDiagnosticKind::UnitComparison { span, .. } => *span,
         }
     }
 }
@@ -138,6 +157,57 @@ pub(crate) enum WarningDiagnosticKind {
         /// Deprecated message.
         message: String,
     },
+    /// An import which is never used.
+    UnusedImport {
+        /// The span of the import.
+        span: Span,
+    },
+    /// A function parameter which is never used.
+    UnusedParameter {
+        /// The span of the parameter.
+        span: Span,
+        /// The name of the parameter.
+        name: String,
+    },
+    /// A binding which shadows an earlier binding.
+    ShadowedBinding {
+        /// The span of the binding.
+        span: Span,
+        /// The name of the binding.
+        name: String,
+    },
+    /// A `return` which is the last expression of a function.
+    NeedlessReturn {
+        /// The span of the return expression.
+        span: Span,
+    },
+    /// A comparison to the unit value.
+    UnitComparison {
+        /// The span of the comparison.
+        span: Span,
+    },
+}
+
+impl WarningDiagnosticKind {
+    /// The lint which the warning belongs to.
+    pub(crate) fn lint(&self) -> Lint {
+        match self {
+            WarningDiagnosticKind::NotUsed { .. } => Lint::UnusedCode,
+            WarningDiagnosticKind::Unreachable { .. } => Lint::UnreachableCode,
+            WarningDiagnosticKind::LetPatternMightPanic { .. } => Lint::LetPatternMightPanic,
+            WarningDiagnosticKind::TemplateWithoutExpansions { .. } => {
+                Lint::TemplateWithoutExpansions
+            }
+            WarningDiagnosticKind::RemoveTupleCallParams { .. } => Lint::UnnecessaryParens,
+            WarningDiagnosticKind::UnnecessarySemiColon { .. } => Lint::UnnecessarySemicolon,
+            WarningDiagnosticKind::UsedDeprecated { .. } => Lint::Deprecated,
+            WarningDiagnosticKind::UnusedImport { .. } => Lint::UnusedImports,
+            WarningDiagnosticKind::UnusedParameter { .. } => Lint::UnusedParameters,
+            WarningDiagnosticKind::ShadowedBinding { .. } => Lint::ShadowedBindings,
+            WarningDiagnosticKind::NeedlessReturn { .. } => Lint::NeedlessReturn,
+            WarningDiagnosticKind::UnitComparison { .. } => Lint::UnitComparisons,
+        }
+    }
 }
 
 impl fmt::Display for WarningDiagnosticKind {
@@ -161,6 +231,19 @@ impl fmt::Display for WarningDiagnosticKind {
             WarningDiagnosticKind::UsedDeprecated { message, .. } => {
                 write!(f, "Used deprecated function: {message}")
             }
+            WarningDiagnosticKind::UnusedImport { .. } => write!(f, "Unused import"),
+            WarningDiagnosticKind::UnusedParameter { name, .. } => {
+                write!(f, "Parameter `{name}` is never used")
+            }
+            WarningDiagnosticKind::ShadowedBinding { name, .. } => {
+                write!(f, "Binding `{name}` shadows an earlier binding")
+            }
+            WarningDiagnosticKind::NeedlessReturn { .. } => {
+                write!(f, "Unnecessary `return` at the end of a function")
+            }
+            WarningDiagnosticKind::UnitComparison { .. } => {
+                write!(f, "Comparison to the unit value `()`")
+            }
         }
     }
 }
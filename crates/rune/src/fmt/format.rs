@@ -1,10 +1,13 @@
-use core::mem::take;
+use core::cmp::Ordering;
+use core::mem::{replace, take};
 
-use crate::ast::{Delimiter, Kind};
-use crate::compile::Result;
+use crate::alloc::prelude::*;
+use crate::alloc::{String, Vec};
+use crate::ast::{Delimiter, Kind, Span};
+use crate::compile::{Result, TrailingComma, WithSpan};
 use crate::grammar::{classify, object_key, MaybeNode, NodeClass};
 
-use super::{Comments, Formatter, Node, Remaining, Stream, Tree};
+use super::{Comments, Formatter, Node, Remaining, Stream, Tree, NL_CHAR};
 
 use Comments::*;
 use Kind::*;
@@ -183,6 +186,14 @@ fn item<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
                     item_impl(fmt, p)?;
                 }
             }
+            ItemTrait => {
+                if attrs.skip {
+                    p.write_remaining(fmt)?;
+                } else {
+                    modifiers(fmt, p)?;
+                    item_trait(fmt, p)?;
+                }
+            }
             ItemMod | ItemFileMod => {
                 if attrs.skip {
                     p.write_remaining(fmt)?;
@@ -338,7 +349,7 @@ fn pat_array<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         fmt.comments(Suffix)?;
     }
 
-    if !comma.ignore(fmt)? {
+    if !compact_comma(fmt, comma)? {
         fmt.comments(Infix)?;
     }
 
@@ -372,7 +383,7 @@ fn pat_tuple<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>, trailing: bool) ->
     if count == 1 && trailing {
         comma.fmt(fmt)?;
     } else {
-        comma.ignore(fmt)?;
+        compact_comma(fmt, comma)?;
 
         if count == 0 {
             fmt.comments(Infix)?;
@@ -405,7 +416,7 @@ fn expr_tuple<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     if count == 1 {
         comma.fmt(fmt)?;
     } else {
-        comma.ignore(fmt)?;
+        compact_comma(fmt, comma)?;
 
         if count == 0 {
             fmt.comments(Infix)?;
@@ -428,7 +439,7 @@ fn expr_object<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     }
 
     let mut count = 0;
-    let mut expanded = fmt.source.is_at_least(p.span(), 80)?;
+    let mut expanded = fmt.source.is_at_least(p.span(), fmt.options.max_width)?;
 
     for node in p.children() {
         if expanded {
@@ -462,7 +473,9 @@ fn expr_object_loose<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<
             p.pump()?.parse(|p| expr(fmt, p))
         })?;
 
-        p.remaining(fmt, K![,])?.fmt(fmt)?;
+        let comma = p.remaining(fmt, K![,])?;
+        let last = !matches!(p.peek(), object_key!());
+        loose_comma(fmt, comma, last)?;
         fmt.nl(1)?;
     }
 
@@ -493,7 +506,7 @@ fn expr_object_compact<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Resul
         comma = p.remaining(fmt, K![,])?;
     }
 
-    if comma.ignore(fmt)? {
+    if compact_comma(fmt, comma)? {
         fmt.ws()?;
     } else {
         fmt.comments(Infix)?;
@@ -540,7 +553,7 @@ fn pat_object<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         comma = p.remaining(fmt, K![,])?;
     }
 
-    if comma.ignore(fmt)? {
+    if compact_comma(fmt, comma)? {
         fmt.ws()?;
     } else {
         fmt.comments(Infix)?;
@@ -785,7 +798,7 @@ fn expr_assign<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
 
 fn exprs<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>, open: Kind, close: Kind) -> Result<()> {
     let mut count = 0;
-    let mut expanded = fmt.source.is_at_least(p.span(), 80)?;
+    let mut expanded = fmt.source.is_at_least(p.span(), fmt.options.max_width)?;
 
     for node in p.children() {
         if expanded {
@@ -815,7 +828,9 @@ fn exprs_loose<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     while let MaybeNode::Some(node) = p.eat(Expr) {
         fmt.comments(Line)?;
         node.parse(|p| expr(fmt, p))?;
-        p.remaining(fmt, K![,])?.fmt(fmt)?;
+        let comma = p.remaining(fmt, K![,])?;
+        let last = !matches!(p.peek(), Expr);
+        loose_comma(fmt, comma, last)?;
         fmt.nl(1)?;
     }
 
@@ -840,13 +855,30 @@ fn exprs_compact<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()>
         fmt.comments(Suffix)?;
     }
 
-    if !comma.ignore(fmt)? {
+    if !compact_comma(fmt, comma)? {
         fmt.comments(Infix)?;
     }
 
     Ok(())
 }
 
+/// Write or drop the trailing comma of a list laid out on a single line.
+///
+/// Returns `true` if the list has any elements.
+fn compact_comma<'a>(fmt: &mut Formatter<'a>, comma: Remaining<'a>) -> Result<bool> {
+    match fmt.options.trailing_comma {
+        TrailingComma::Always => comma.fmt(fmt),
+        TrailingComma::Vertical | TrailingComma::Never => comma.ignore(fmt),
+    }
+}
+
+/// Write the comma following an element in a list laid out over multiple
+/// lines, where `last` indicates that it is the last element.
+fn loose_comma<'a>(fmt: &mut Formatter<'a>, comma: Remaining<'a>, last: bool) -> Result<()> {
+    let needed = !last || !matches!(fmt.options.trailing_comma, TrailingComma::Never);
+    comma.write_only_if(fmt, needed)
+}
+
 fn expr_binary<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     p.pump()?.parse(|p| inner_expr(fmt, p))?;
 
@@ -1182,7 +1214,10 @@ fn expr_closure<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
 }
 
 fn expr_chain<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
-    let expanded = fmt.source.is_at_least(p.span(), 80)?;
+    // Comments between the links of a chain can only be preserved if every
+    // link is on its own line.
+    let expanded =
+        fmt.source.is_at_least(p.span(), fmt.options.max_width)? || has_link_comments(p.node());
 
     // If the first expression *is* small, and there are no other expressions
     // that need indentation in the chain, we can keep it all on one line.
@@ -1203,9 +1238,10 @@ fn expr_chain<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     };
 
     let first_is_small = if let Some((_, tail)) = tail {
-        !fmt.source.is_at_least(head.join(tail.head()), 80)?
+        !fmt.source
+            .is_at_least(head.join(tail.head()), fmt.options.max_width)?
     } else {
-        !fmt.source.is_at_least(head, 80)?
+        !fmt.source.is_at_least(head, fmt.options.max_width)?
     };
 
     let from;
@@ -1237,6 +1273,7 @@ fn expr_chain<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         if n >= from {
             fmt.indent(isize::from(take(&mut unindented)))?;
             fmt.nl(usize::from(matches!(node.kind(), ExprField | ExprAwait)))?;
+            fmt.comments(Line)?;
         }
 
         node.parse(|p| {
@@ -1286,6 +1323,19 @@ fn expr_chain<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     Ok(())
 }
 
+/// Test if there are any comments in between the links of a chain.
+fn has_link_comments(chain: Node<'_>) -> bool {
+    let is_comment = |n: &Node<'_>| matches!(n.kind(), Comment | MultilineComment(..));
+
+    chain.children().skip(1).any(|link| {
+        is_comment(&link)
+            || link
+                .children()
+                .take_while(|n| is_comment(n) || n.is_whitespace())
+                .any(|n| is_comment(&n))
+    })
+}
+
 fn condition_or_expr<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     if p.eat(Condition).parse(|p| condition(fmt, p))?.is_none() {
         p.expect(Expr)?.parse(|p| expr(fmt, p))?;
@@ -1357,7 +1407,9 @@ fn item_enum<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         fmt.comments(Line)?;
         node.parse(|p| variant(fmt, p))?;
         empty = false;
-        p.remaining(fmt, K![,])?.fmt(fmt)?;
+        let comma = p.remaining(fmt, K![,])?;
+        let last = !matches!(p.peek(), Variant);
+        loose_comma(fmt, comma, last)?;
     }
 
     fmt.comments(Line)?;
@@ -1404,7 +1456,9 @@ fn struct_body<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         fmt.nl(1)?;
         fmt.comments(Line)?;
         field.parse(|p| p.pump()?.fmt(fmt))?;
-        p.remaining(fmt, K![,])?.fmt(fmt)?;
+        let comma = p.remaining(fmt, K![,])?;
+        let last = !matches!(p.peek(), Field);
+        loose_comma(fmt, comma, last)?;
         empty = false;
     }
 
@@ -1432,7 +1486,7 @@ fn tuple_body<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         fmt.comments(Suffix)?;
     }
 
-    if !comma.ignore(fmt)? {
+    if !compact_comma(fmt, comma)? {
         fmt.comments(Infix)?;
     }
 
@@ -1453,9 +1507,18 @@ fn item_fn<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         fmt.lit("()")?;
     }
 
-    fmt.ws()?;
+    let in_trait = replace(&mut fmt.in_trait, false);
 
-    if p.eat(Block).parse(|p| block(fmt, p))?.is_none() {
+    let body = p.eat(Block).parse(|p| {
+        fmt.ws()?;
+        block(fmt, p)
+    });
+
+    fmt.in_trait = in_trait;
+
+    // Only functions declared in a trait may omit their body.
+    if body?.is_none() && !in_trait {
+        fmt.ws()?;
         fmt.lit("{")?;
         fmt.nl(1)?;
         fmt.lit("}")?;
@@ -1517,16 +1580,60 @@ fn item_use_group<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()>
 
     let mut comma = Remaining::default();
 
-    while let MaybeNode::Some(inner) = p.eat(ItemUsePath) {
-        fmt.comments(Prefix)?;
+    let sort = nested > 1
+        && (fmt.options.sort_imports || fmt.options.group_imports)
+        && !p
+            .node()
+            .walk()
+            .any(|n| matches!(n.kind(), Comment | MultilineComment(..)));
 
-        if comma.fmt(fmt)? {
-            fmt.ws()?;
+    if sort {
+        let mut inners = Vec::new();
+
+        while let MaybeNode::Some(inner) = p.eat(ItemUsePath) {
+            let key = path_key(fmt.source.get(inner.span())?).with_span(inner.span())?;
+            let comma = p.remaining(fmt, K![,])?;
+            inners.try_push((key, inner, comma)).with_span(p.span())?;
         }
 
-        inner.parse(|p| item_use_path(fmt, p))?;
-        comma = p.remaining(fmt, K![,])?;
-        fmt.comments(Suffix)?;
+        let mut order = (0..inners.len())
+            .try_collect::<Vec<_>>()
+            .with_span(p.span())?;
+
+        order.sort_by(|&a, &b| cmp_use_path(&inners[a].0, &inners[b].0));
+
+        let mut nodes = Vec::new();
+        let mut commas = Vec::new();
+
+        for (_, inner, comma) in inners {
+            nodes.try_push(Some(inner)).with_span(p.span())?;
+            commas.try_push(comma).with_span(p.span())?;
+        }
+
+        // Separators are written positionally, so only the paths are moved.
+        for (index, next) in order.into_iter().zip(commas) {
+            if take(&mut comma).fmt(fmt)? {
+                fmt.ws()?;
+            }
+
+            if let Some(inner) = nodes[index].take() {
+                inner.parse(|p| item_use_path(fmt, p))?;
+            }
+
+            comma = next;
+        }
+    } else {
+        while let MaybeNode::Some(inner) = p.eat(ItemUsePath) {
+            fmt.comments(Prefix)?;
+
+            if comma.fmt(fmt)? {
+                fmt.ws()?;
+            }
+
+            inner.parse(|p| item_use_path(fmt, p))?;
+            comma = p.remaining(fmt, K![,])?;
+            fmt.comments(Suffix)?;
+        }
     }
 
     if !comma.ignore(fmt)? {
@@ -1548,11 +1655,31 @@ fn item_impl<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     p.expect(K![impl])?.fmt(fmt)?;
     fmt.ws()?;
     p.expect(Path)?.parse(|p| path(fmt, p))?;
+
+    if let MaybeNode::Some(for_token) = p.eat(K![for]) {
+        fmt.ws()?;
+        for_token.fmt(fmt)?;
+        fmt.ws()?;
+        p.expect(Path)?.parse(|p| path(fmt, p))?;
+    }
+
     fmt.ws()?;
     p.expect(Block)?.parse(|p| block(fmt, p))?;
     Ok(())
 }
 
+fn item_trait<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
+    p.expect(K![trait])?.fmt(fmt)?;
+    fmt.ws()?;
+    p.pump()?.fmt(fmt)?;
+    fmt.ws()?;
+    let in_trait = replace(&mut fmt.in_trait, true);
+    let result = p.expect(Block)?.parse(|p| block(fmt, p));
+    fmt.in_trait = in_trait;
+    result?;
+    Ok(())
+}
+
 fn item_mod<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     p.expect(K![mod])?.fmt(fmt)?;
     fmt.ws()?;
@@ -1593,7 +1720,7 @@ fn fn_args<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
         fmt.comments(Suffix)?;
     }
 
-    if !comma.ignore(fmt)? {
+    if !compact_comma(fmt, comma)? {
         fmt.comments(Infix)?;
     }
 
@@ -1641,11 +1768,22 @@ fn block_with<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>, compact: bool) ->
 fn block_content<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()> {
     inner_attributes(fmt, p)?;
 
+    let runs = if fmt.options.sort_imports || fmt.options.group_imports {
+        use_runs(fmt, p)?
+    } else {
+        Vec::new()
+    };
+
     let mut last_kind = None;
 
     while !p.is_eof() {
         let node = p.pump()?;
-        let (needs_semi, class) = classify(&node);
+        let (mut needs_semi, class) = classify(&node);
+
+        // Functions outside of traits are always given a body.
+        if !fmt.in_trait && node.children().any(|n| matches!(n.kind(), ItemFn)) {
+            needs_semi = false;
+        }
 
         if let Some(last_kind) = last_kind {
             let n = match last_kind {
@@ -1660,6 +1798,12 @@ fn block_content<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()>
 
         fmt.comments(Line)?;
 
+        if let Some(&(_, len)) = runs.iter().find(|(span, _)| *span == node.span()) {
+            sorted_uses(fmt, p, node, len)?;
+            last_kind = Some(class);
+            continue;
+        }
+
         node.parse(|p| stmt(fmt, p))?;
 
         let trailing_semi = p.remaining(fmt, K![;])?;
@@ -1674,3 +1818,188 @@ fn block_content<'a>(fmt: &mut Formatter<'a>, p: &mut Stream<'a>) -> Result<()>
 
     Ok(())
 }
+
+/// Find runs of consecutive `use` items in a block which can be reordered.
+///
+/// Runs only contain plain `use` items terminated by a single semicolon with no
+/// attributes or comments in between them, since those can't be moved safely.
+/// Unless imports are grouped, a blank line also ends a run.
+fn use_runs<'a>(fmt: &Formatter<'a>, p: &Stream<'a>) -> Result<Vec<(Span, usize)>> {
+    let mut runs = Vec::new();
+    let mut current = None::<(Span, usize)>;
+    let mut needs_semi = false;
+
+    for node in p.node().children() {
+        match node.kind() {
+            Item if !needs_semi && is_plain_use(&node) => {
+                match &mut current {
+                    Some((_, len)) => *len += 1,
+                    None => current = Some((node.span(), 1)),
+                }
+
+                needs_semi = true;
+                continue;
+            }
+            K![;] if needs_semi => {
+                needs_semi = false;
+                continue;
+            }
+            Whitespace if needs_semi => {
+                continue;
+            }
+            Whitespace => {
+                let lines = fmt.source.get(node.span())?.matches(NL_CHAR).count();
+
+                if fmt.options.group_imports || lines < 2 {
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        push_use_run(&mut runs, current.take(), take(&mut needs_semi)).with_span(node.span())?;
+    }
+
+    push_use_run(&mut runs, current, needs_semi).with_span(p.span())?;
+    Ok(runs)
+}
+
+fn push_use_run(
+    runs: &mut Vec<(Span, usize)>,
+    run: Option<(Span, usize)>,
+    missing_semi: bool,
+) -> crate::alloc::Result<()> {
+    if let Some((span, len)) = run {
+        let len = len - usize::from(missing_semi);
+
+        if len > 1 {
+            runs.try_push((span, len))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Test if the item is a `use` item without attributes or comments.
+fn is_plain_use(node: &Node<'_>) -> bool {
+    let mut children = node.children().filter(|n| !n.is_whitespace());
+
+    if !matches!((children.next(), children.next()), (Some(n), None) if n.kind() == ItemUse) {
+        return false;
+    }
+
+    !node
+        .walk()
+        .any(|n| matches!(n.kind(), Comment | MultilineComment(..)))
+}
+
+/// Write a run of `use` items starting at `first` in sorted order.
+fn sorted_uses<'a>(
+    fmt: &mut Formatter<'a>,
+    p: &mut Stream<'a>,
+    first: Node<'a>,
+    len: usize,
+) -> Result<()> {
+    let mut items = Vec::new();
+    let mut node = first;
+
+    loop {
+        let key = use_key(fmt, &node)?;
+        let semi = p.remaining(fmt, K![;])?;
+        items
+            .try_push((key, node, Some(semi)))
+            .with_span(p.span())?;
+
+        if items.len() == len {
+            break;
+        }
+
+        node = p.pump()?;
+    }
+
+    let mut order = (0..len).try_collect::<Vec<_>>().with_span(p.span())?;
+    order.sort_by(|&a, &b| items[a].0.cmp(&items[b].0));
+
+    let mut last = None;
+    let mut deferred = None;
+
+    for index in order {
+        let (key, node, semi) = &mut items[index];
+
+        if let Some(group) = last {
+            fmt.nl_exact(if group != key.0 { 2 } else { 1 })?;
+        }
+
+        node.clone().parse(|p| stmt(fmt, p))?;
+
+        let Some(semi) = semi.take() else {
+            continue;
+        };
+
+        // Comments following the run are attached to the semicolon of the
+        // last item in the source, so it's ignored once everything has been
+        // written to process them in the right position.
+        if index + 1 == len {
+            fmt.lit(";")?;
+            deferred = Some(semi);
+        } else {
+            semi.write_if(fmt, true)?;
+        }
+
+        last = Some(key.0);
+    }
+
+    if let Some(semi) = deferred {
+        fmt.nl_exact(0)?;
+        semi.ignore(fmt)?;
+    }
+
+    Ok(())
+}
+
+/// Construct the sort key of a `use` item, which is its group followed by the
+/// path being imported without whitespace.
+fn use_key(fmt: &Formatter<'_>, node: &Node<'_>) -> Result<(u8, String)> {
+    let Some(item) = node.children().find(|n| n.kind() == ItemUse) else {
+        return Err(node.unsupported(ItemUse));
+    };
+
+    let Some(start) = item
+        .children()
+        .find(|n| n.kind() == K![use])
+        .map(|n| n.span().end)
+    else {
+        return Err(item.unsupported(K![use]));
+    };
+
+    let span = Span::new(start, item.span().end);
+    let path = path_key(fmt.source.get(span)?).with_span(span)?;
+
+    let group = if !fmt.options.group_imports {
+        0
+    } else {
+        match path.split("::").find(|s| !s.is_empty()) {
+            Some("std") => 0,
+            Some("crate" | "self" | "super") => 2,
+            _ => 1,
+        }
+    };
+
+    Ok((group, path))
+}
+
+/// Strip all whitespace from a path so that it can be compared.
+fn path_key(source: &str) -> crate::alloc::Result<String> {
+    let mut key = String::new();
+
+    for c in source.chars().filter(|c| !c.is_whitespace()) {
+        key.try_push(c)?;
+    }
+
+    Ok(key)
+}
+
+/// Compare two paths in a `use` group, where `self` always comes first.
+fn cmp_use_path(a: &str, b: &str) -> Ordering {
+    (a != "self", a).cmp(&(b != "self", b))
+}
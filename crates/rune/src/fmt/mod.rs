@@ -10,8 +10,8 @@ use core::fmt;
 
 use crate::alloc;
 use crate::alloc::prelude::*;
-use crate::ast::Span;
-use crate::compile::{ParseOptionError, Result, WithSpan};
+use crate::ast::{Kind, Span};
+use crate::compile::{Error, ErrorKind, ParseOptionError, Result, WithSpan};
 use crate::grammar::{Node, Remaining, Stream, Tree};
 use crate::{Diagnostics, Options, SourceId, Sources};
 
@@ -21,7 +21,6 @@ pub(crate) use self::output::Formatter;
 const WS: &str = " ";
 const NL: &str = "\n";
 const NL_CHAR: char = '\n';
-const INDENT: &str = "    ";
 
 #[derive(Debug)]
 enum FormatErrorKind {
@@ -195,5 +194,54 @@ pub(crate) fn layout_source_with(
             .with_span(Span::new(source.len(), source.len()))?;
     }
 
+    verify_comments(source, source_id, &o)?;
     Ok(o)
 }
+
+/// Verify that the formatted output contains the same comments in the same
+/// order as the original source.
+fn verify_comments(source: &str, source_id: SourceId, output: &str) -> Result<()> {
+    let before = crate::grammar::text(source_id, source)
+        .without_processing()
+        .include_whitespace()
+        .root()?;
+
+    let after = crate::grammar::text(source_id, output)
+        .without_processing()
+        .include_whitespace()
+        .root()?;
+
+    let mut after = after.walk().filter(is_comment);
+
+    for comment in before.walk().filter(is_comment) {
+        let matches = match after.next() {
+            Some(other) => comment_text(source, &comment) == comment_text(output, &other),
+            None => false,
+        };
+
+        if !matches {
+            return Err(Error::new(comment.span(), ErrorKind::LostComment));
+        }
+    }
+
+    if after.next().is_some() {
+        let end = Span::new(source.len(), source.len());
+        return Err(Error::new(end, ErrorKind::LostComment));
+    }
+
+    Ok(())
+}
+
+fn is_comment(node: &Node<'_>) -> bool {
+    matches!(node.kind(), Kind::Comment | Kind::MultilineComment(..))
+}
+
+fn comment_text<'a>(source: &'a str, node: &Node<'_>) -> Option<&'a str> {
+    let text = source.get(node.span().range())?;
+
+    if matches!(node.kind(), Kind::Comment) {
+        return Some(text.trim_end());
+    }
+
+    Some(text)
+}
@@ -3,11 +3,11 @@ use core::mem::take;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, VecDeque};
 use crate::ast::{Kind, Span};
-use crate::compile::{Error, ErrorKind, FmtOptions, Result, WithSpan};
+use crate::compile::{Error, ErrorKind, FmtIndent, FmtOptions, Result, WithSpan};
 use crate::grammar::{Ignore, Node, Tree};
 use crate::{Diagnostics, SourceId};
 
-use super::{INDENT, NL, NL_CHAR, WS};
+use super::{NL, NL_CHAR, WS};
 
 /// Hint for how comments may be laid out.
 pub(super) enum Comments {
@@ -89,7 +89,7 @@ impl Buffer {
         self.0.try_push_str(s)
     }
 
-    fn lines(&mut self, indent: usize, lines: usize) -> alloc::Result<()> {
+    fn lines(&mut self, indent: usize, lines: usize, style: FmtIndent) -> alloc::Result<()> {
         if lines == 0 {
             return Ok(());
         }
@@ -99,7 +99,16 @@ impl Buffer {
         }
 
         for _ in 0..indent {
-            self.0.try_push_str(INDENT)?;
+            match style {
+                FmtIndent::Spaces(n) => {
+                    for _ in 0..n {
+                        self.0.try_push(' ')?;
+                    }
+                }
+                FmtIndent::Tab => {
+                    self.0.try_push('\t')?;
+                }
+            }
         }
 
         Ok(())
@@ -119,6 +128,9 @@ pub(crate) struct Formatter<'a> {
     use_lines: bool,
     ws: bool,
     indent: usize,
+    /// If we are directly inside of a trait body, where functions are allowed
+    /// to not have a body.
+    pub(super) in_trait: bool,
 }
 
 impl<'a> Formatter<'a> {
@@ -143,6 +155,7 @@ impl<'a> Formatter<'a> {
             use_lines: false,
             ws: false,
             indent: 0,
+            in_trait: false,
         }
     }
 
@@ -237,6 +250,15 @@ impl<'a> Formatter<'a> {
         Ok(())
     }
 
+    /// Emit a line hint like [`Formatter::nl`], but which overrides the
+    /// number of lines present in the source.
+    pub(super) fn nl_exact(&mut self, lines: usize) -> Result<()> {
+        self.comments_line(true)?;
+        self.lines = lines;
+        self.use_lines = lines > 0;
+        Ok(())
+    }
+
     /// Emit a whitespace hint, indicating that the next node write should
     /// happen with preceeding whitespace.
     ///
@@ -269,7 +291,7 @@ impl<'a> Formatter<'a> {
                     self.o.str(WS).with_span(c.span)?;
                 } else {
                     self.o
-                        .lines(self.indent, c.before.min(2))
+                        .lines(self.indent, c.before.min(2), self.options.indent)
                         .with_span(c.span)?;
                 }
             }
@@ -381,7 +403,8 @@ impl<'a> Formatter<'a> {
 
     pub(crate) fn flush_whitespace(&mut self, preserve: bool) -> Result<()> {
         if self.use_lines && self.lines > 0 {
-            self.o.lines(self.indent, self.lines.min(2))?;
+            self.o
+                .lines(self.indent, self.lines.min(2), self.options.indent)?;
             self.ws = false;
             self.use_lines = false;
             self.lines = 0;
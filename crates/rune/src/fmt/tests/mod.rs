@@ -1357,3 +1357,243 @@ fn test_expanded_chain() {
         "#
     );
 }
+
+#[test]
+fn traits() {
+    assert_format!(
+        r#"
+        trait Shape {
+            fn area(self);
+
+            fn name(self) {
+                "shape"
+            }
+        }
+
+        impl Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        impl Shape for Circle {
+        }
+        "#
+    );
+}
+
+#[test]
+fn fn_without_body() {
+    assert_format!(
+        r#"
+        fn missing()
+        "#,
+        r#"
+        fn missing() {
+        }
+        "#
+    );
+
+    assert_format!(
+        r#"
+        trait Shape {
+            fn area(self);
+
+            fn inner(self) {
+                fn nested()
+            }
+        }
+        "#,
+        r#"
+        trait Shape {
+            fn area(self);
+
+            fn inner(self) {
+                fn nested() {
+                }
+            }
+        }
+        "#
+    );
+}
+
+#[test]
+fn indent_options() {
+    assert_format_with!(
+        { "fmt.indent=2" },
+        r#"
+        pub fn main() {
+            if true {
+                1
+            }
+        }
+        "#,
+        r#"
+        pub fn main() {
+          if true {
+            1
+          }
+        }
+        "#
+    );
+
+    assert_format_with!(
+        { "fmt.indent=tab" },
+        "pub fn main() {\n    if true {\n        1\n    }\n}\n",
+        "pub fn main() {\n\tif true {\n\t\t1\n\t}\n}\n"
+    );
+}
+
+#[test]
+fn trailing_comma_options() {
+    assert_format_with!(
+        { "fmt.trailing-comma=always" },
+        r#"
+        let a = (1, 2);
+        let b = [1, 2];
+
+        enum Foo {
+            A,
+            B
+        }
+        "#,
+        r#"
+        let a = (1, 2,);
+        let b = [1, 2,];
+
+        enum Foo {
+            A,
+            B,
+        }
+        "#
+    );
+
+    assert_format_with!(
+        { "fmt.trailing-comma=never" },
+        r#"
+        let a = (1, 2,);
+
+        enum Foo {
+            A,
+            B,
+        }
+
+        struct Bar {
+            a,
+            b,
+        }
+        "#,
+        r#"
+        let a = (1, 2);
+
+        enum Foo {
+            A,
+            B
+        }
+
+        struct Bar {
+            a,
+            b
+        }
+        "#
+    );
+}
+
+#[test]
+fn max_width_option() {
+    assert_format_with!(
+        { "fmt.max-width=16" },
+        r#"
+        let a = [first, second, third];
+        "#,
+        r#"
+        let a = [
+            first,
+            second,
+            third,
+        ];
+        "#
+    );
+}
+
+#[test]
+fn sort_imports() {
+    assert_format_with!(
+        { "fmt.sort-imports" },
+        r#"
+        use std::io;
+        use crate::foo::{c, self, a};
+        use ::http::Client;
+
+        use std::collections::{HashMap, BTreeMap};
+        // Comments end a run.
+        use zed;
+        use alpha;
+
+        pub fn main() {
+            use std::b;
+            use std::a;
+        }
+        "#,
+        r#"
+        use ::http::Client;
+        use crate::foo::{self, a, c};
+        use std::io;
+
+        use std::collections::{BTreeMap, HashMap};
+        // Comments end a run.
+        use alpha;
+        use zed;
+
+        pub fn main() {
+            use std::a;
+            use std::b;
+        }
+        "#
+    );
+}
+
+#[test]
+fn group_imports() {
+    assert_format_with!(
+        { "fmt.group-imports" },
+        r#"
+        use self::bar;
+        use std::io;
+        use http::Client;
+
+        use std::collections::HashMap;
+        use crate::foo;
+
+        pub fn main() {
+            foo()
+        }
+        "#,
+        r#"
+        use std::collections::HashMap;
+        use std::io;
+
+        use http::Client;
+
+        use crate::foo;
+        use self::bar;
+
+        pub fn main() {
+            foo()
+        }
+        "#
+    );
+}
+
+#[test]
+fn chain_comments() {
+    assert_format!(
+        r#"
+        let s = value
+            // Before the call.
+            .to_string()
+            .len() // After the length.
+            .to_string();
+        "#
+    );
+}
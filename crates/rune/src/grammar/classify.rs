@@ -22,7 +22,9 @@ pub(crate) fn classify(node: &Node<'_>) -> (bool, NodeClass) {
                         .children()
                         .rev()
                         .any(|n| matches!(n.kind(), TupleBody | EmptyBody)),
-                    ItemEnum | ItemFn | ItemImpl | ItemMod => false,
+                    // Functions without a body are declared in traits.
+                    ItemFn => !node.children().any(|n| matches!(n.kind(), Block)),
+                    ItemEnum | ItemImpl | ItemTrait | ItemMod => false,
                     ItemFileMod => true,
                     _ => continue,
                 };
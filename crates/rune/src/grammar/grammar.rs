@@ -189,6 +189,11 @@ fn stmt(p: &mut Parser<'_>) -> Result<()> {
             p.close_at(&inner_c, ItemImpl)?;
             Item
         }
+        K![trait] => {
+            item_trait(p)?;
+            p.close_at(&inner_c, ItemTrait)?;
+            Item
+        }
         K![mod] => {
             if item_mod(p)? {
                 p.close_at(&inner_c, ItemMod)?;
@@ -518,6 +523,22 @@ fn item_impl(p: &mut Parser<'_>) -> Result<()> {
         path(p)?;
     }
 
+    if p.bump_if(K![for])? && matches!(p.peek()?, path_component!()) {
+        path(p)?;
+    }
+
+    block(p)?;
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+fn item_trait(p: &mut Parser<'_>) -> Result<()> {
+    p.bump()?;
+
+    if matches!(p.peek()?, K![ident]) {
+        p.bump()?;
+    }
+
     block(p)?;
     Ok(())
 }
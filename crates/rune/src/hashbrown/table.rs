@@ -105,6 +105,26 @@ impl<V> Table<V> {
         }
     }
 
+    /// Retain only the entries for which `f` returns `true`.
+    pub(crate) fn retain<F>(&mut self, mut f: F) -> Result<(), VmError>
+    where
+        F: FnMut(&Value, &V) -> Result<bool, VmError>,
+    {
+        // SAFETY: the table is exclusively borrowed, and buckets are only
+        // erased after they have been yielded by the iterator.
+        unsafe {
+            for bucket in self.table.iter() {
+                let (key, value) = bucket.as_ref();
+
+                if !f(key, value)? {
+                    self.table.erase(bucket);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub(crate) fn clear(&mut self) {
         self.table.clear()
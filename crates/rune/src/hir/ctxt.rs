@@ -1,7 +1,7 @@
 use tracing::instrument_ast;
 
-use crate::alloc;
 use crate::alloc::prelude::*;
+use crate::alloc::{self, HashMap};
 use crate::ast::{self, Spanned};
 use crate::compile::{meta, DynLocation, Error, ItemId, Result};
 use crate::grammar::{Ignore, Node};
@@ -81,6 +81,11 @@ impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
         })
     }
 
+    /// Take the names of all variables defined while lowering.
+    pub(crate) fn take_variable_names(&mut self) -> HashMap<hir::Variable, hir::Name<'hir>> {
+        self.scopes.take_names()
+    }
+
     #[instrument_ast(span = ast)]
     pub(super) fn try_lookup_meta(
         &mut self,
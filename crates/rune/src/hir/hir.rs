@@ -1,6 +1,8 @@
 use core::fmt;
 use core::num::NonZeroUsize;
 
+use num::ToPrimitive;
+
 use crate as rune;
 use crate::alloc::prelude::*;
 use crate::ast::{self, Span, Spanned};
@@ -185,12 +187,91 @@ pub(crate) enum Lit<'hir> {
     Bool(bool),
     Unsigned(u64),
     Signed(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    U128(u128),
+    I128(i128),
     Float(f64),
     Char(char),
     Str(&'hir str),
     ByteStr(&'hir [u8]),
 }
 
+impl Lit<'_> {
+    /// Construct an unsigned literal with the given suffix size.
+    ///
+    /// Unless `sized` is set, the literal is widened into a 64-bit integer the
+    /// same as an unsuffixed one would be.
+    ///
+    /// Returns `None` if the value is out of bounds for `size`.
+    pub(crate) fn unsigned(n: &num::BigInt, size: ast::NumberSize, sized: bool) -> Option<Self> {
+        if sized && size == ast::NumberSize::S128 {
+            return Some(Self::U128(n.to_u128()?));
+        }
+
+        let n = n.to_u64()?;
+
+        if !size.unsigned_in(n) {
+            return None;
+        }
+
+        if !sized {
+            return Some(Self::Unsigned(n));
+        }
+
+        Some(match size {
+            ast::NumberSize::S8 => Self::U8(n as u8),
+            ast::NumberSize::S16 => Self::U16(n as u16),
+            ast::NumberSize::S32 => Self::U32(n as u32),
+            _ => Self::Unsigned(n),
+        })
+    }
+
+    /// Construct a signed literal with the given suffix size.
+    ///
+    /// Unless `sized` is set, the literal is widened into a 64-bit integer the
+    /// same as an unsuffixed one would be.
+    ///
+    /// Returns `None` if the value is out of bounds for `size`.
+    pub(crate) fn signed(n: &num::BigInt, size: ast::NumberSize, sized: bool) -> Option<Self> {
+        if sized && size == ast::NumberSize::S128 {
+            return Some(Self::I128(n.to_i128()?));
+        }
+
+        let n = n.to_i64()?;
+
+        if !size.signed_in(n) {
+            return None;
+        }
+
+        if !sized {
+            return Some(Self::Signed(n));
+        }
+
+        Some(match size {
+            ast::NumberSize::S8 => Self::I8(n as i8),
+            ast::NumberSize::S16 => Self::I16(n as i16),
+            ast::NumberSize::S32 => Self::I32(n as i32),
+            _ => Self::Signed(n),
+        })
+    }
+
+    /// Construct a byte literal.
+    ///
+    /// Unless `sized` is set, the byte is widened into a `u64`.
+    pub(crate) fn byte(b: u8, sized: bool) -> Self {
+        if sized {
+            Self::U8(b)
+        } else {
+            Self::Unsigned(u64::from(b))
+        }
+    }
+}
+
 /// The kind of an [Expr].
 #[derive(Debug, TryClone, Clone, Copy)]
 #[try_clone(copy)]
@@ -44,10 +44,43 @@ pub(crate) fn item_fn<'hir>(
 ) -> compile::Result<hir::ItemFn<'hir>> {
     alloc_with!(cx, ast);
 
+    let args = iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?);
+    let body = block(cx, None, &ast.body)?;
+
+    for ((ast, _), arg) in ast.args.iter().zip(args.iter()) {
+        let hir::FnArg::Pat(binding) = arg else {
+            continue;
+        };
+
+        for &id in binding.names {
+            if cx.scopes.is_used(id) {
+                continue;
+            }
+
+            let Some(hir::Name::Str(name)) = cx.scopes.name(id) else {
+                continue;
+            };
+
+            if !name.starts_with('_') {
+                cx.q.diagnostics.unused_parameter(cx.source_id, ast, name)?;
+            }
+        }
+    }
+
+    let last = match ast.body.statements.last() {
+        Some(ast::Stmt::Expr(ast)) => Some(ast),
+        Some(ast::Stmt::Semi(ast)) => Some(&ast.expr),
+        _ => None,
+    };
+
+    if let Some(ast::Expr::Return(ast)) = last {
+        cx.q.diagnostics.needless_return(cx.source_id, ast)?;
+    }
+
     Ok(hir::ItemFn {
         span: ast.span(),
-        args: iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?),
-        body: block(cx, None, &ast.body)?,
+        args,
+        body,
     })
 }
 
@@ -497,6 +530,12 @@ pub(crate) fn expr<'hir>(
             hir::ExprKind::Group(alloc!(expr(cx, &ast.expr)?))
         }
         ast::Expr::Binary(ast) => {
+            if let ast::BinOp::Eq(..) | ast::BinOp::Neq(..) = ast.op {
+                if is_unit(&ast.lhs) || is_unit(&ast.rhs) {
+                    cx.q.diagnostics.unit_comparison(cx.source_id, ast)?;
+                }
+            }
+
             let rhs_needs = match &ast.op {
                 ast::BinOp::As(..) | ast::BinOp::Is(..) | ast::BinOp::IsNot(..) => Needs::Type,
                 _ => Needs::Value,
@@ -650,6 +689,12 @@ fn pat_const_value<'hir>(
                 Inline::Char(ch) => hir::Lit::Char(ch),
                 Inline::Unsigned(integer) => hir::Lit::Unsigned(integer),
                 Inline::Signed(integer) => hir::Lit::Signed(integer),
+                Inline::U8(value) => hir::Lit::U8(value),
+                Inline::U16(value) => hir::Lit::U16(value),
+                Inline::U32(value) => hir::Lit::U32(value),
+                Inline::I8(value) => hir::Lit::I8(value),
+                Inline::I16(value) => hir::Lit::I16(value),
+                Inline::I32(value) => hir::Lit::I32(value),
                 _ => {
                     return Err(compile::Error::msg(
                         span,
@@ -657,6 +702,8 @@ fn pat_const_value<'hir>(
                     ))
                 }
             },
+            ConstValueKind::U128(value) => hir::Lit::U128(*value),
+            ConstValueKind::I128(value) => hir::Lit::I128(*value),
             ConstValueKind::String(string) => hir::Lit::Str(alloc_str!(string.as_ref())),
             ConstValueKind::Bytes(bytes) => hir::Lit::ByteStr(alloc_bytes!(bytes.as_ref())),
             ConstValueKind::Instance(instance) => match &**instance {
@@ -798,38 +845,26 @@ fn lit<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Lit) -> compile::Result<hir
             match (n.value, n.suffix) {
                 (ast::NumberValue::Float(n), _) => Ok(hir::Lit::Float(n)),
                 (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::Unsigned(_, size))) => {
-                    let Some(n) = int.to_u64() else {
+                    let Some(lit) = hir::Lit::unsigned(&int, size, cx.q.options.sized_integers)
+                    else {
                         return Err(compile::Error::new(
                             ast,
                             ErrorKind::BadUnsignedOutOfBounds { size },
                         ));
                     };
 
-                    if !size.unsigned_in(n) {
-                        return Err(compile::Error::new(
-                            ast,
-                            ErrorKind::BadUnsignedOutOfBounds { size },
-                        ));
-                    }
-
-                    Ok(hir::Lit::Unsigned(n))
+                    Ok(lit)
                 }
                 (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::Signed(_, size))) => {
-                    let Some(n) = int.to_i64() else {
+                    let Some(lit) = hir::Lit::signed(&int, size, cx.q.options.sized_integers)
+                    else {
                         return Err(compile::Error::new(
                             ast,
                             ErrorKind::BadSignedOutOfBounds { size },
                         ));
                     };
 
-                    if !size.signed_in(n) {
-                        return Err(compile::Error::new(
-                            ast,
-                            ErrorKind::BadSignedOutOfBounds { size },
-                        ));
-                    }
-
-                    Ok(hir::Lit::Signed(n))
+                    Ok(lit)
                 }
                 (ast::NumberValue::Integer(int), _) => {
                     let Some(n) = int.to_i64() else {
@@ -847,7 +882,7 @@ fn lit<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Lit) -> compile::Result<hir
         }
         ast::Lit::Byte(lit) => {
             let b = lit.resolve(resolve_context!(cx.q))?;
-            Ok(hir::Lit::Unsigned(b as u64))
+            Ok(hir::Lit::byte(b, cx.q.options.sized_integers))
         }
         ast::Lit::Char(lit) => {
             let ch = lit.resolve(resolve_context!(cx.q))?;
@@ -900,38 +935,24 @@ fn expr_unary<'hir>(
     match (number.value, number.suffix) {
         (ast::NumberValue::Float(n), _) => Ok(hir::ExprKind::Lit(hir::Lit::Float(-n))),
         (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::Unsigned(_, size))) => {
-            let Some(n) = int.neg().to_u64() else {
+            let Some(lit) = hir::Lit::unsigned(&int.neg(), size, cx.q.options.sized_integers) else {
                 return Err(compile::Error::new(
                     ast,
                     ErrorKind::BadUnsignedOutOfBounds { size },
                 ));
             };
 
-            if !size.unsigned_in(n) {
-                return Err(compile::Error::new(
-                    ast,
-                    ErrorKind::BadUnsignedOutOfBounds { size },
-                ));
-            }
-
-            Ok(hir::ExprKind::Lit(hir::Lit::Unsigned(n)))
+            Ok(hir::ExprKind::Lit(lit))
         }
         (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::Signed(_, size))) => {
-            let Some(n) = int.neg().to_i64() else {
+            let Some(lit) = hir::Lit::signed(&int.neg(), size, cx.q.options.sized_integers) else {
                 return Err(compile::Error::new(
                     ast,
                     ErrorKind::BadSignedOutOfBounds { size },
                 ));
             };
 
-            if !size.signed_in(n) {
-                return Err(compile::Error::new(
-                    ast,
-                    ErrorKind::BadSignedOutOfBounds { size },
-                ));
-            }
-
-            Ok(hir::ExprKind::Lit(hir::Lit::Signed(n)))
+            Ok(hir::ExprKind::Lit(lit))
         }
         (ast::NumberValue::Integer(int), _) => {
             let Some(n) = int.neg().to_i64() else {
@@ -1137,7 +1158,9 @@ fn fn_arg<'hir>(
             let id = cx.scopes.define(hir::Name::SelfValue, ast)?;
             hir::FnArg::SelfValue(ast.span(), id)
         }
-        ast::FnArg::Pat(ast) => hir::FnArg::Pat(alloc!(pat_binding(cx, ast)?)),
+        ast::FnArg::Pat(ast) | ast::FnArg::Typed(ast, ..) => {
+            hir::FnArg::Pat(alloc!(pat_binding(cx, ast)?))
+        }
     })
 }
 
@@ -1226,6 +1249,25 @@ fn pat_binding<'hir>(
     Ok(hir::PatBinding { pat, names })
 }
 
+/// Warn if a binding shadows a visible variable with the same name.
+fn shadowed_binding<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    name: &'hir str,
+    span: &dyn Spanned,
+) -> compile::Result<()> {
+    if !name.starts_with('_') && cx.scopes.is_visible(hir::Name::Str(name)) {
+        cx.q.diagnostics
+            .shadowed_binding(cx.source_id, span, name)?;
+    }
+
+    Ok(())
+}
+
+/// Test if the expression is the unit value `()`.
+fn is_unit(ast: &ast::Expr) -> bool {
+    matches!(ast, ast::Expr::Tuple(tuple) if tuple.items.is_empty())
+}
+
 fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir::Pat<'hir>> {
     fn filter((ast, _): &(ast::Pat, Option<ast::Comma>)) -> Option<&ast::Pat> {
         if matches!(ast, ast::Pat::Binding(..) | ast::Pat::Rest(..)) {
@@ -1268,6 +1310,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
 
                     if let Some(ident) = ast.path.try_as_ident() {
                         let name = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
+                        shadowed_binding(cx, name, ast)?;
                         let name = cx.scopes.define(hir::Name::Str(name), ast)?;
                         cx.pattern_bindings.try_push(name)?;
                         break 'path hir::PatPathKind::Ident(name);
@@ -1367,6 +1410,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                             };
 
                             let key = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
+                            shadowed_binding(cx, key, ident)?;
                             let id = cx.scopes.define(hir::Name::Str(key), ident)?;
                             cx.pattern_bindings.try_push(id)?;
                             (key, hir::Binding::Ident(path.span(), key, id))
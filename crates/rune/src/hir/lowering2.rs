@@ -2551,8 +2551,16 @@ fn pat_const_value<'hir>(
                 Inline::Char(ch) => hir::Lit::Char(ch),
                 Inline::Unsigned(value) => hir::Lit::Unsigned(value),
                 Inline::Signed(value) => hir::Lit::Signed(value),
+                Inline::U8(value) => hir::Lit::U8(value),
+                Inline::U16(value) => hir::Lit::U16(value),
+                Inline::U32(value) => hir::Lit::U32(value),
+                Inline::I8(value) => hir::Lit::I8(value),
+                Inline::I16(value) => hir::Lit::I16(value),
+                Inline::I32(value) => hir::Lit::I32(value),
                 _ => return Err(Error::msg(span, "Unsupported constant value in pattern")),
             },
+            ConstValueKind::U128(value) => hir::Lit::U128(*value),
+            ConstValueKind::I128(value) => hir::Lit::I128(*value),
             ConstValueKind::String(string) => hir::Lit::Str(alloc_str!(string.as_ref())),
             ConstValueKind::Bytes(bytes) => hir::Lit::ByteStr(alloc_bytes!(bytes.as_ref())),
             ConstValueKind::Instance(instance) => match &**instance {
@@ -2725,28 +2733,22 @@ fn lit<'hir>(cx: &mut Ctxt<'hir, '_, '_>, p: &mut Stream<'_>) -> Result<hir::Lit
                 (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::Unsigned(_, size))) => {
                     let int = if neg { int.neg() } else { int };
 
-                    let Some(n) = int.to_u64() else {
+                    let Some(value) = hir::Lit::unsigned(&int, size, cx.q.options.sized_integers)
+                    else {
                         return Err(Error::new(lit, ErrorKind::BadUnsignedOutOfBounds { size }));
                     };
 
-                    if !size.unsigned_in(n) {
-                        return Err(Error::new(lit, ErrorKind::BadUnsignedOutOfBounds { size }));
-                    }
-
-                    Ok(hir::Lit::Unsigned(n))
+                    Ok(value)
                 }
                 (ast::NumberValue::Integer(int), Some(ast::NumberSuffix::Signed(_, size))) => {
                     let int = if neg { int.neg() } else { int };
 
-                    let Some(n) = int.to_u64() else {
-                        return Err(Error::new(lit, ErrorKind::BadUnsignedOutOfBounds { size }));
+                    let Some(value) = hir::Lit::signed(&int, size, cx.q.options.sized_integers)
+                    else {
+                        return Err(Error::new(lit, ErrorKind::BadSignedOutOfBounds { size }));
                     };
 
-                    if !size.unsigned_in(n) {
-                        return Err(Error::new(lit, ErrorKind::BadUnsignedOutOfBounds { size }));
-                    }
-
-                    Ok(hir::Lit::Unsigned(n))
+                    Ok(value)
                 }
                 (ast::NumberValue::Integer(int), _) => {
                     let int = if neg { int.neg() } else { int };
@@ -2767,7 +2769,7 @@ fn lit<'hir>(cx: &mut Ctxt<'hir, '_, '_>, p: &mut Stream<'_>) -> Result<hir::Lit
         K![byte] => {
             let lit = p.ast::<ast::LitByte>()?;
             let b = lit.resolve(resolve_context!(cx.q))?;
-            Ok(hir::Lit::Unsigned(b as u64))
+            Ok(hir::Lit::byte(b, cx.q.options.sized_integers))
         }
         K![char] => {
             let lit = p.ast::<ast::LitChar>()?;
@@ -5,7 +5,7 @@ use core::fmt;
 use core::num::NonZeroUsize;
 
 use crate::alloc::prelude::*;
-use crate::alloc::{self, BTreeSet, HashMap, Vec};
+use crate::alloc::{self, BTreeSet, HashMap, HashSet, Vec};
 use crate::ast::Spanned;
 use crate::compile::error::{MissingScope, PopError};
 use crate::compile::{self, HasSpan};
@@ -66,6 +66,10 @@ impl<'hir> Layer<'hir> {
 pub(crate) struct Scopes<'hir, 'a> {
     scope: Scope,
     scopes: Vec<Layer<'hir>>,
+    /// The names of all variables which have been defined.
+    names: HashMap<hir::Variable, hir::Name<'hir>>,
+    /// Variables which have been looked up.
+    used: HashSet<hir::Variable>,
     gen: &'a Gen,
 }
 
@@ -81,6 +85,8 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
         Ok(Self {
             scope: Scopes::ROOT,
             scopes,
+            names: HashMap::new(),
+            used: HashSet::new(),
             gen,
         })
     }
@@ -155,6 +161,7 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
 
         layer.variables.try_insert(name, id)?;
         layer.order.try_push(id)?;
+        self.names.try_insert(id, name)?;
         Ok(id)
     }
 
@@ -174,9 +181,47 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
         let id = hir::Variable(self.gen.next());
         layer.variables.try_insert(name, id)?;
         layer.order.try_push(id)?;
+        self.names.try_insert(id, name)?;
         Ok(id)
     }
 
+    /// Test if a variable with the given name is visible in the current scope.
+    ///
+    /// Unlike [`Scopes::get`], this doesn't capture the variable or mark it as
+    /// used.
+    pub(crate) fn is_visible(&self, name: hir::Name<'hir>) -> bool {
+        let mut scope = self.scopes.get(self.scope.0);
+
+        while let Some(layer) = scope.take() {
+            if layer.variables.contains_key(&name) {
+                return true;
+            }
+
+            let Some(parent) = layer.parent() else {
+                break;
+            };
+
+            scope = self.scopes.get(parent);
+        }
+
+        false
+    }
+
+    /// Test if the given variable has been used.
+    pub(crate) fn is_used(&self, id: hir::Variable) -> bool {
+        self.used.contains(&id)
+    }
+
+    /// Get the name of a defined variable.
+    pub(crate) fn name(&self, id: hir::Variable) -> Option<hir::Name<'hir>> {
+        self.names.get(&id).copied()
+    }
+
+    /// Take the names of all variables which have been defined so far.
+    pub(crate) fn take_names(&mut self) -> HashMap<hir::Variable, hir::Name<'hir>> {
+        core::mem::take(&mut self.names)
+    }
+
     /// Try to lookup the given variable.
     #[tracing::instrument(skip_all, fields(?self.scope, ?name))]
     pub(crate) fn get(
@@ -220,6 +265,8 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
             layer.captures.try_insert((name, id))?;
         }
 
+        self.used.try_insert(id)?;
+
         Ok(Some((id, scope)))
     }
 
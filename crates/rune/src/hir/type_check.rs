@@ -0,0 +1,714 @@
+//! Optional checking of type annotations.
+//!
+//! This is enabled through the `type-check` compiler option. Types are inferred
+//! on a best-effort basis from literals, constructors, annotated arguments and
+//! the signatures of the functions being called. Whenever both the expected and
+//! the actual type of an expression is known and they differ, an error is
+//! reported. Everything which can't be inferred is left to be checked at
+//! runtime.
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, HashMap, HashSet, String, Vec};
+use crate::ast::{self, Span};
+use crate::compile::{self, meta, ErrorKind};
+use crate::hir;
+use crate::indexing::Annotations;
+use crate::query::GenericsParameters;
+use crate::runtime::{Bytes, Call, Fixed, Object, OwnedTuple, TypeHash};
+use crate::Hash;
+
+use super::Ctxt;
+
+/// The signature of a function being called.
+struct Signature {
+    /// The expected types of the arguments.
+    args: Vec<Option<Hash>>,
+    /// The type being returned.
+    output: Option<Hash>,
+}
+
+struct Checker<'a, 'hir, 'q, 'arena> {
+    cx: &'a mut Ctxt<'hir, 'q, 'arena>,
+    /// The known types of variables.
+    variables: HashMap<hir::Variable, Hash>,
+    /// Variables whose type is declared through an annotation.
+    declared: HashSet<hir::Variable>,
+    /// Variables which are assigned to after they've been declared.
+    assigned: HashSet<hir::Variable>,
+    /// The declared return type of the function being checked.
+    output: Option<Hash>,
+    /// If mismatches should be reported.
+    report: bool,
+}
+
+/// Check the type annotations of a function and the calls it performs.
+///
+/// The `self_type` is the type of `self` if this is an instance function.
+pub(crate) fn item_fn(
+    cx: &mut Ctxt<'_, '_, '_>,
+    ast: &ast::ItemFn,
+    hir: &hir::ItemFn<'_>,
+    self_type: Option<Hash>,
+) -> compile::Result<()> {
+    let mut args = Vec::new();
+
+    for (arg, _) in &ast.args {
+        let ty = match arg.ty() {
+            Some(ty) => resolve(cx, ty)?,
+            None => None,
+        };
+
+        args.try_push(ty)?;
+    }
+
+    let output = match &ast.output {
+        Some((_, ty)) => resolve(cx, ty)?,
+        None => None,
+    };
+
+    let mut c = Checker {
+        cx,
+        variables: HashMap::new(),
+        declared: HashSet::new(),
+        assigned: HashSet::new(),
+        output,
+        report: false,
+    };
+
+    // The first pass collects variables which are being assigned to, since
+    // their type can't be inferred from their declaration. The second pass
+    // reports mismatches.
+    for report in [false, true] {
+        c.report = report;
+        c.variables.clear();
+        c.declared.clear();
+
+        for (arg, ty) in hir.args.iter().zip(args.iter()) {
+            match (arg, ty) {
+                (hir::FnArg::SelfValue(_, variable), _) => {
+                    if let Some(self_type) = self_type {
+                        c.declare(*variable, self_type)?;
+                    }
+                }
+                (hir::FnArg::Pat(pat), Some(ty)) => {
+                    if let Some(variable) = binding(pat) {
+                        c.declare(variable, *ty)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match c.output {
+            Some(output) => c.check_block(&hir.body, output)?,
+            None => {
+                c.block(&hir.body)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a type annotation into a type hash.
+fn resolve(cx: &mut Ctxt<'_, '_, '_>, ast: &ast::Type) -> compile::Result<Option<Hash>> {
+    match ast {
+        ast::Type::Path(path) => {
+            let named = cx.q.convert_path(path)?;
+            let meta = cx.lookup_meta(path, named.item, GenericsParameters::default())?;
+
+            match &meta.kind {
+                meta::Kind::Type { .. }
+                | meta::Kind::Enum { .. }
+                | meta::Kind::Struct {
+                    enum_hash: Hash::EMPTY,
+                    ..
+                } => Ok(Some(meta.hash)),
+                _ => Err(compile::Error::expected_meta(
+                    path,
+                    meta.info(cx.q.pool)?,
+                    "type",
+                )),
+            }
+        }
+        ast::Type::Bang(..) => Ok(None),
+        ast::Type::Tuple(..) => Ok(Some(OwnedTuple::HASH)),
+    }
+}
+
+/// Get the variable bound by a pattern, if it binds the whole value.
+fn binding(pat: &hir::PatBinding<'_>) -> Option<hir::Variable> {
+    match pat.pat.kind {
+        hir::PatKind::Path(&hir::PatPathKind::Ident(variable)) => Some(variable),
+        _ => None,
+    }
+}
+
+/// Test if the given types are compatible.
+fn compatible(expected: Hash, actual: Hash) -> bool {
+    // The 64-bit integers are what unsuffixed literals and native integers are
+    // widened into, so they can stand in for an integer of any width. Sized
+    // integers of different widths are distinct types.
+    static WIDE: [Hash; 2] = [i64::HASH, u64::HASH];
+
+    static SIZED: [Hash; 8] = [
+        Fixed::<u8>::HASH,
+        Fixed::<u16>::HASH,
+        Fixed::<u32>::HASH,
+        Fixed::<u128>::HASH,
+        Fixed::<i8>::HASH,
+        Fixed::<i16>::HASH,
+        Fixed::<i32>::HASH,
+        Fixed::<i128>::HASH,
+    ];
+
+    if expected == actual {
+        return true;
+    }
+
+    let is_integer = |ty| WIDE.contains(&ty) || SIZED.contains(&ty);
+    is_integer(expected)
+        && is_integer(actual)
+        && (WIDE.contains(&expected) || WIDE.contains(&actual))
+}
+
+impl Checker<'_, '_, '_, '_> {
+    /// Declare the type of a variable through an annotation.
+    fn declare(&mut self, variable: hir::Variable, ty: Hash) -> alloc::Result<()> {
+        self.variables.try_insert(variable, ty)?;
+        self.declared.try_insert(variable)?;
+        Ok(())
+    }
+
+    /// Bind the inferred type of a variable.
+    fn bind(&mut self, pat: &hir::PatBinding<'_>, ty: Option<Hash>) -> alloc::Result<()> {
+        let (Some(variable), Some(ty)) = (binding(pat), ty) else {
+            return Ok(());
+        };
+
+        if !self.assigned.contains(&variable) {
+            self.variables.try_insert(variable, ty)?;
+        }
+
+        Ok(())
+    }
+
+    /// Report a mismatch if the actual type isn't compatible with the expected
+    /// one.
+    fn expect(&mut self, span: Span, expected: Hash, actual: Option<Hash>) -> compile::Result<()> {
+        let Some(actual) = actual else {
+            return Ok(());
+        };
+
+        if !self.report || expected == Hash::EMPTY || compatible(expected, actual) {
+            return Ok(());
+        }
+
+        let error = compile::Error::new(
+            span,
+            ErrorKind::TypeMismatch {
+                expected: self.type_name(expected)?.try_into()?,
+                actual: self.type_name(actual)?.try_into()?,
+            },
+        );
+
+        self.cx.q.diagnostics.error(self.cx.source_id, error)?;
+        Ok(())
+    }
+
+    /// Get a human readable name of a type.
+    fn type_name(&self, hash: Hash) -> alloc::Result<String> {
+        if let Some(item) = self
+            .cx
+            .q
+            .context
+            .lookup_meta_by_hash(hash)
+            .find_map(|meta| meta.item.as_ref())
+        {
+            return item.try_to_string();
+        }
+
+        for item_meta in self.cx.q.inner.items.values() {
+            if self.cx.q.pool.item_type_hash(item_meta.item) == hash {
+                return self.cx.q.pool.item(item_meta.item).try_to_string();
+            }
+        }
+
+        hash.try_to_string()
+    }
+
+    /// Check that an expression produces the expected type, descending into
+    /// the branches of blocks and conditionals.
+    fn check(&mut self, hir: &hir::Expr<'_>, expected: Hash) -> compile::Result<()> {
+        match hir.kind {
+            hir::ExprKind::Block(block) => {
+                self.check_block(block, expected)?;
+            }
+            hir::ExprKind::Group(hir) => {
+                self.check(hir, expected)?;
+            }
+            hir::ExprKind::If(hir) => {
+                for branch in hir.branches {
+                    self.condition(branch.condition)?;
+                    self.check_block(&branch.block, expected)?;
+                }
+
+                if let Some(fallback) = hir.fallback {
+                    self.check_block(fallback, expected)?;
+                }
+            }
+            hir::ExprKind::Match(hir) => {
+                let ty = self.expr(hir.expr)?;
+
+                for branch in hir.branches {
+                    self.bind(&branch.pat, ty)?;
+
+                    if let Some(condition) = branch.condition {
+                        self.expr(condition)?;
+                    }
+
+                    self.check(&branch.body, expected)?;
+                }
+            }
+            _ => {
+                let actual = self.expr(hir)?;
+                self.expect(hir.span, expected, actual)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that a block produces the expected type.
+    fn check_block(&mut self, hir: &hir::Block<'_>, expected: Hash) -> compile::Result<()> {
+        self.statements(hir.statements)?;
+
+        if let Some(value) = hir.value {
+            self.check(value, expected)?;
+        }
+
+        Ok(())
+    }
+
+    /// Infer the type produced by a block.
+    fn block(&mut self, hir: &hir::Block<'_>) -> compile::Result<Option<Hash>> {
+        self.statements(hir.statements)?;
+
+        match hir.value {
+            Some(value) => self.expr(value),
+            None => Ok(None),
+        }
+    }
+
+    fn statements(&mut self, statements: &[hir::Stmt<'_>]) -> compile::Result<()> {
+        for stmt in statements {
+            match stmt {
+                hir::Stmt::Local(local) => {
+                    let ty = self.expr(&local.expr)?;
+                    self.bind(&local.pat, ty)?;
+                }
+                hir::Stmt::Expr(hir) => {
+                    self.expr(hir)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn condition(&mut self, hir: &hir::Condition<'_>) -> compile::Result<()> {
+        match hir {
+            hir::Condition::Expr(hir) => {
+                self.expr(hir)?;
+            }
+            hir::Condition::ExprLet(hir) => {
+                self.expr(&hir.expr)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Infer the type of an expression, checking any nested expressions.
+    fn expr(&mut self, hir: &hir::Expr<'_>) -> compile::Result<Option<Hash>> {
+        let ty = match hir.kind {
+            hir::ExprKind::Variable(variable) => self.variables.get(&variable).copied(),
+            hir::ExprKind::Assign(hir) => {
+                let ty = self.expr(&hir.rhs)?;
+
+                if let hir::ExprKind::Variable(variable) = hir.lhs.kind {
+                    if let Some(&expected) = self.variables.get(&variable) {
+                        if self.declared.contains(&variable) {
+                            self.expect(hir.rhs.span, expected, ty)?;
+                        }
+                    }
+
+                    self.assigned.try_insert(variable)?;
+                } else {
+                    self.expr(&hir.lhs)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Loop(hir) => {
+                if let Some(condition) = hir.condition {
+                    self.condition(condition)?;
+                }
+
+                self.block(&hir.body)?;
+                None
+            }
+            hir::ExprKind::For(hir) => {
+                self.expr(&hir.iter)?;
+                self.block(&hir.body)?;
+                None
+            }
+            hir::ExprKind::Let(hir) => {
+                self.expr(&hir.expr)?;
+                Some(bool::HASH)
+            }
+            hir::ExprKind::If(hir) => {
+                for branch in hir.branches {
+                    self.condition(branch.condition)?;
+                    self.block(&branch.block)?;
+                }
+
+                if let Some(fallback) = hir.fallback {
+                    self.block(fallback)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Match(hir) => {
+                let ty = self.expr(hir.expr)?;
+
+                for branch in hir.branches {
+                    self.bind(&branch.pat, ty)?;
+
+                    if let Some(condition) = branch.condition {
+                        self.expr(condition)?;
+                    }
+
+                    self.expr(&branch.body)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Call(hir) => self.call(hir)?,
+            hir::ExprKind::FieldAccess(hir) => {
+                let ty = self.expr(&hir.expr)?;
+
+                match (ty, hir.expr_field) {
+                    (Some(ty), hir::ExprField::Ident(name)) => self.field(ty, name)?,
+                    _ => None,
+                }
+            }
+            hir::ExprKind::Binary(hir) => {
+                let lhs = self.expr(&hir.lhs)?;
+                let rhs = self.expr(&hir.rhs)?;
+
+                match hir.op {
+                    ast::BinOp::Eq(..)
+                    | ast::BinOp::Neq(..)
+                    | ast::BinOp::Gt(..)
+                    | ast::BinOp::Lt(..)
+                    | ast::BinOp::Gte(..)
+                    | ast::BinOp::Lte(..)
+                    | ast::BinOp::Is(..)
+                    | ast::BinOp::IsNot(..)
+                    | ast::BinOp::And(..)
+                    | ast::BinOp::Or(..) => Some(bool::HASH),
+                    ast::BinOp::As(..) => match hir.rhs.kind {
+                        hir::ExprKind::Type(ty) => Some(ty.into_hash()),
+                        _ => None,
+                    },
+                    ast::BinOp::Add(..)
+                    | ast::BinOp::Sub(..)
+                    | ast::BinOp::Div(..)
+                    | ast::BinOp::Mul(..)
+                    | ast::BinOp::Rem(..) => lhs.filter(|&lhs| rhs == Some(lhs) && is_number(lhs)),
+                    _ => None,
+                }
+            }
+            hir::ExprKind::Unary(hir) => {
+                let ty = self.expr(&hir.expr)?;
+
+                match hir.op {
+                    ast::UnOp::Not(..) | ast::UnOp::Neg(..) => ty,
+                    _ => None,
+                }
+            }
+            hir::ExprKind::Index(hir) => {
+                self.expr(&hir.target)?;
+                self.expr(&hir.index)?;
+                None
+            }
+            hir::ExprKind::Block(hir) => self.block(hir)?,
+            hir::ExprKind::Break(hir) => {
+                if let Some(hir) = hir.expr {
+                    self.expr(hir)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Yield(hir) => {
+                if let Some(hir) = hir {
+                    self.expr(hir)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Return(hir) => {
+                if let Some(hir) = hir {
+                    match self.output {
+                        Some(output) => self.check(hir, output)?,
+                        None => {
+                            self.expr(hir)?;
+                        }
+                    }
+                }
+
+                None
+            }
+            hir::ExprKind::Await(hir) | hir::ExprKind::Try(hir) => {
+                self.expr(hir)?;
+                None
+            }
+            hir::ExprKind::Select(hir) => {
+                for hir in hir.exprs {
+                    self.expr(hir)?;
+                }
+
+                for branch in hir.branches {
+                    self.expr(&branch.body)?;
+                }
+
+                if let Some(hir) = hir.default {
+                    self.expr(hir)?;
+                }
+
+                None
+            }
+            hir::ExprKind::Lit(lit) => Some(match lit {
+                hir::Lit::Bool(..) => bool::HASH,
+                hir::Lit::Unsigned(..) => u64::HASH,
+                hir::Lit::Signed(..) => i64::HASH,
+                hir::Lit::U8(..) => Fixed::<u8>::HASH,
+                hir::Lit::U16(..) => Fixed::<u16>::HASH,
+                hir::Lit::U32(..) => Fixed::<u32>::HASH,
+                hir::Lit::I8(..) => Fixed::<i8>::HASH,
+                hir::Lit::I16(..) => Fixed::<i16>::HASH,
+                hir::Lit::I32(..) => Fixed::<i32>::HASH,
+                hir::Lit::U128(..) => Fixed::<u128>::HASH,
+                hir::Lit::I128(..) => Fixed::<i128>::HASH,
+                hir::Lit::Float(..) => f64::HASH,
+                hir::Lit::Char(..) => char::HASH,
+                hir::Lit::Str(..) => String::HASH,
+                hir::Lit::ByteStr(..) => Bytes::HASH,
+            }),
+            hir::ExprKind::Object(hir) => {
+                let ty = match hir.kind {
+                    hir::ExprObjectKind::Struct { hash } => hash,
+                    hir::ExprObjectKind::ExternalType { hash, .. } => hash,
+                    hir::ExprObjectKind::Anonymous => Object::HASH,
+                };
+
+                for assign in hir.assignments {
+                    let actual = self.expr(&assign.assign)?;
+
+                    if let Some(expected) = self.field(ty, assign.key.1)? {
+                        self.expect(assign.assign.span, expected, actual)?;
+                    }
+                }
+
+                Some(ty)
+            }
+            hir::ExprKind::Tuple(hir) => {
+                for hir in hir.items {
+                    self.expr(hir)?;
+                }
+
+                Some(OwnedTuple::HASH)
+            }
+            hir::ExprKind::Vec(hir) => {
+                for hir in hir.items {
+                    self.expr(hir)?;
+                }
+
+                Some(crate::runtime::Vec::HASH)
+            }
+            hir::ExprKind::Range(hir) => {
+                match hir {
+                    hir::ExprRange::RangeFrom { start } => {
+                        self.expr(start)?;
+                    }
+                    hir::ExprRange::RangeFull => {}
+                    hir::ExprRange::RangeInclusive { start, end }
+                    | hir::ExprRange::Range { start, end } => {
+                        self.expr(start)?;
+                        self.expr(end)?;
+                    }
+                    hir::ExprRange::RangeToInclusive { end } | hir::ExprRange::RangeTo { end } => {
+                        self.expr(end)?;
+                    }
+                }
+
+                None
+            }
+            hir::ExprKind::Group(hir) => self.expr(hir)?,
+            hir::ExprKind::Template(hir) => {
+                for hir in hir.exprs {
+                    self.expr(hir)?;
+                }
+
+                Some(String::HASH)
+            }
+            hir::ExprKind::Format(hir) => {
+                self.expr(hir.value)?;
+                None
+            }
+            _ => None,
+        };
+
+        Ok(ty)
+    }
+
+    /// Check a call against the signature of the function being called and
+    /// infer its return type.
+    fn call(&mut self, hir: &hir::ExprCall<'_>) -> compile::Result<Option<Hash>> {
+        let mut args = Vec::new();
+
+        let hash = match hir.call {
+            hir::Call::Meta { hash } => Some(hash),
+            hir::Call::Associated { target, hash } => {
+                let ty = self.expr(target)?;
+                args.try_push((target.span, ty))?;
+                ty.map(|ty| Hash::associated_function(ty, hash))
+            }
+            hir::Call::Expr { expr } => {
+                self.expr(expr)?;
+                None
+            }
+            _ => None,
+        };
+
+        for hir in hir.args {
+            let ty = self.expr(hir)?;
+            args.try_push((hir.span, ty))?;
+        }
+
+        let Some(hash) = hash else {
+            return Ok(None);
+        };
+
+        let Some(signature) = self.signature(hash)? else {
+            return Ok(None);
+        };
+
+        for ((span, actual), expected) in args.into_iter().zip(signature.args) {
+            if let Some(expected) = expected {
+                self.expect(span, expected, actual)?;
+            }
+        }
+
+        Ok(signature.output)
+    }
+
+    /// Look up the signature of the function with the given hash.
+    fn signature(&mut self, hash: Hash) -> compile::Result<Option<Signature>> {
+        if let Some(annotations) = self.cx.q.get_annotations(hash) {
+            let signature = match &*annotations {
+                Annotations::Function { call, args, output } => Signature {
+                    args: self.resolve_all(args.iter().map(Option::as_ref))?,
+                    output: match call {
+                        Call::Immediate => self.resolve_foreign(output.as_ref()),
+                        _ => None,
+                    },
+                },
+                Annotations::Struct { fields } => Signature {
+                    args: self.resolve_all(fields.iter().map(|(_, ty)| ty.as_ref()))?,
+                    output: Some(hash),
+                },
+            };
+
+            return Ok(Some(signature));
+        }
+
+        self.native_signature(hash)
+    }
+
+    /// Look up the signature of a native function.
+    #[cfg(feature = "doc")]
+    fn native_signature(&self, hash: Hash) -> compile::Result<Option<Signature>> {
+        let Some(signature) = self
+            .cx
+            .q
+            .context
+            .lookup_meta_by_hash(hash)
+            .find_map(|meta| meta.kind.as_signature())
+        else {
+            return Ok(None);
+        };
+
+        let mut args = Vec::new();
+
+        for arg in signature.arguments.iter().flat_map(|args| args.iter()) {
+            args.try_push(Some(arg.base).filter(|hash| *hash != Hash::EMPTY))?;
+        }
+
+        let output = Some(signature.return_type.base)
+            .filter(|hash| *hash != Hash::EMPTY && !signature.is_async);
+
+        Ok(Some(Signature { args, output }))
+    }
+
+    /// Native signatures are only available with the `doc` feature.
+    #[cfg(not(feature = "doc"))]
+    fn native_signature(&self, _: Hash) -> compile::Result<Option<Signature>> {
+        Ok(None)
+    }
+
+    /// Infer the type of a field on a type with annotated fields.
+    fn field(&mut self, ty: Hash, name: &str) -> compile::Result<Option<Hash>> {
+        let Some(annotations) = self.cx.q.get_annotations(ty) else {
+            return Ok(None);
+        };
+
+        let Annotations::Struct { fields } = &*annotations else {
+            return Ok(None);
+        };
+
+        let ty = fields
+            .iter()
+            .find(|(field, _)| field.as_ref() == name)
+            .and_then(|(_, ty)| ty.as_ref());
+
+        Ok(self.resolve_foreign(ty))
+    }
+
+    fn resolve_all<'t>(
+        &mut self,
+        types: impl IntoIterator<Item = Option<&'t ast::Type>>,
+    ) -> alloc::Result<Vec<Option<Hash>>> {
+        let mut output = Vec::new();
+
+        for ty in types {
+            output.try_push(self.resolve_foreign(ty))?;
+        }
+
+        Ok(output)
+    }
+
+    /// Resolve an annotation which belongs to another item.
+    ///
+    /// Errors are ignored, since they are reported when the item itself is
+    /// being compiled.
+    fn resolve_foreign(&mut self, ty: Option<&ast::Type>) -> Option<Hash> {
+        resolve(self.cx, ty?).ok().flatten()
+    }
+}
+
+/// Test if the type is a number which arithmetic is preserved for.
+fn is_number(ty: Hash) -> bool {
+    [i64::HASH, u64::HASH, f64::HASH].contains(&ty)
+}
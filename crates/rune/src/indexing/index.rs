@@ -1,18 +1,22 @@
 use core::mem::take;
 
+use rust_alloc::rc::Rc;
+
 use tracing::instrument_ast;
 
 use crate::alloc::prelude::*;
-use crate::alloc::VecDeque;
-use crate::ast::{self, OptionSpanned, Spanned};
+use crate::alloc::{HashSet, VecDeque};
+use crate::ast::{self, OptionSpanned, Span, Spanned};
 use crate::compile::{
     self, attrs, meta, Doc, DynLocation, ErrorKind, ItemMeta, Location, Visibility, WithSpan,
 };
+use crate::diagnostics::{Lint, LintLevel};
 use crate::indexing::{self, Indexed};
 use crate::parse::{Resolve, ResolveContext};
 use crate::query::{DeferEntry, ImplItem, ImplItemKind};
 use crate::runtime::Call;
 use crate::worker::{Import, ImportKind, ImportState};
+use crate::Hash;
 
 use super::{ast_to_visibility, validate_call, Indexer};
 
@@ -40,6 +44,10 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
             .with_span(span)?;
     }
 
+    if let Some(span) = ast.option_span() {
+        lint_attributes(idx, &mut p, &ast.attributes, span)?;
+    }
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -238,12 +246,35 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
     let guard = idx.items.push_name(name.as_ref())?;
+    lint_attributes(idx, &mut p, &ast.attributes, ast.span())?;
     let item_meta = idx.insert_new_item(&ast, visibility, &docs)?;
     let idx_item = idx.item.replace(item_meta.item);
 
     for (arg, _) in &mut ast.args {
-        if let ast::FnArg::Pat(p) = arg {
-            pat(idx, p)?;
+        let span = arg.span();
+
+        match arg {
+            ast::FnArg::SelfValue(..) => {}
+            ast::FnArg::Pat(p) => {
+                pat(idx, p)?;
+            }
+            ast::FnArg::Typed(p, _, ty) => {
+                if !idx.q.options.type_check {
+                    return Err(compile::Error::msg(
+                        span,
+                        "Static typing on function arguments is not supported",
+                    ));
+                }
+
+                pat(idx, p)?;
+                type_annotation(idx, ty)?;
+            }
+        }
+    }
+
+    if let Some((_, ty)) = &mut ast.output {
+        if idx.q.options.type_check {
+            type_annotation(idx, ty)?;
         }
     }
 
@@ -317,7 +348,7 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
         ));
     }
 
-    if ast.output.is_some() {
+    if ast.output.is_some() && !idx.q.options.type_check {
         return Err(compile::Error::msg(
             &ast,
             "Adding a return type in functions is not supported",
@@ -349,6 +380,10 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
         };
     }
 
+    if idx.q.options.type_check {
+        fn_annotations(idx, &ast, &item_meta, call, is_instance)?;
+    }
+
     let name = ast.name;
     let args = ast.args.iter().map(|(a, _)| a.span()).try_collect()?;
 
@@ -840,6 +875,7 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_attributes(idx, &mut p, &ast.attributes, ast.span())?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -929,6 +965,7 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_attributes(idx, &mut p, &ast.attributes, ast.span())?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -958,7 +995,7 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
             ));
         }
 
-        if field.ty.is_some() {
+        if field.ty.is_some() && !idx.q.options.type_check {
             return Err(compile::Error::msg(
                 field,
                 "Static typing on fields is not supported",
@@ -988,6 +1025,10 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
         }
     }
 
+    if idx.q.options.type_check {
+        struct_annotations(idx, &mut ast.body, &item_meta)?;
+    }
+
     idx.item = idx_item;
     idx.items.pop(guard).with_span(&ast)?;
 
@@ -998,7 +1039,10 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
 
 #[instrument_ast(span = ast)]
 fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Result<()> {
-    if let Some(first) = ast.attributes.first() {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+    lint_attributes(idx, &mut p, &ast.attributes, ast.span())?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
             "Attributes on impl blocks are not supported",
@@ -1007,6 +1051,14 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
 
     path(idx, &mut ast.path)?;
 
+    let trait_ = match ast.trait_.take() {
+        Some((mut trait_path, _)) => {
+            path(idx, &mut trait_path)?;
+            Some(Box::try_new(trait_path)?)
+        }
+        None => None,
+    };
+
     let location = Location::new(idx.source_id, ast.path.span());
 
     idx.q
@@ -1015,6 +1067,7 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
         .try_push_back(DeferEntry::ImplItem(ImplItem {
             kind: ImplItemKind::Ast {
                 path: Box::try_new(ast.path)?,
+                trait_,
                 functions: take(&mut ast.functions),
             },
             location,
@@ -1026,12 +1079,81 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
     Ok(())
 }
 
+#[instrument_ast(span = ast)]
+fn item_trait(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemTrait) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_attributes(idx, &mut p, &ast.attributes, ast.span())?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "Attributes on traits are not supported",
+        ));
+    }
+
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let guard = idx.items.push_name(name.as_ref())?;
+
+    let visibility = ast_to_visibility(&ast.visibility)?;
+    let item_meta = idx.insert_new_item(&ast, visibility, &docs)?;
+    ast.id = item_meta.item;
+
+    let mut names = HashSet::new();
+
+    for f in &ast.functions {
+        let mut p = attrs::Parser::new(&f.attributes)?;
+        Doc::collect_from(resolve_context!(idx.q), &mut p, &f.attributes)?;
+
+        if let Some(first) = p.remaining(&f.attributes).next() {
+            return Err(compile::Error::msg(
+                first,
+                "Attributes on trait functions are not supported",
+            ));
+        }
+
+        if f.output.is_some() && !idx.q.options.type_check {
+            return Err(compile::Error::msg(
+                f,
+                "Adding a return type in functions is not supported",
+            ));
+        }
+
+        let name = f.name.resolve(resolve_context!(idx.q))?;
+
+        if !names.try_insert(name)? {
+            return Err(compile::Error::new(
+                f.descriptive_span(),
+                ErrorKind::TraitFunctionConflict {
+                    name: name.try_into()?,
+                },
+            ));
+        }
+    }
+
+    drop(names);
+    idx.items.pop(guard).with_span(&ast)?;
+
+    idx.q.index_trait(indexing::Trait {
+        item_meta,
+        root: idx.root.map(TryToOwned::try_to_owned).transpose()?,
+        functions: take(&mut ast.functions),
+    })?;
+
+    Ok(())
+}
+
 #[instrument_ast(span = ast)]
 fn item_mod(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemMod) -> compile::Result<()> {
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
+    if let ast::ItemModBody::InlineBody(..) = &ast.body {
+        lint_attributes(idx, &mut p, &ast.attributes, ast.span())?;
+    }
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -1077,6 +1199,7 @@ fn item_const(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemConst) -> compile::Re
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_attributes(idx, &mut p, &ast.attributes, ast.span())?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -1122,6 +1245,9 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
         ast::Item::Impl(item) => {
             item_impl(idx, item)?;
         }
+        ast::Item::Trait(item) => {
+            item_trait(idx, item)?;
+        }
         ast::Item::Mod(item) => {
             item_mod(idx, item)?;
         }
@@ -1189,6 +1315,150 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
     Ok(())
 }
 
+/// Index a type annotation.
+/// Register the lint levels set through the `#[allow(..)]`, `#[warn(..)]`
+/// and `#[deny(..)]` attributes, which apply to everything within `span`.
+fn lint_attributes(
+    idx: &mut Indexer<'_, '_>,
+    p: &mut attrs::Parser,
+    attributes: &[ast::Attribute],
+    span: Span,
+) -> compile::Result<()> {
+    let mut levels = Vec::new();
+
+    for attr in p.parse_all::<attrs::Allow>(resolve_context!(idx.q), attributes)? {
+        let (_, attr) = attr?;
+
+        for (ident, _) in &attr.lints {
+            levels.try_push((*ident, LintLevel::Allow))?;
+        }
+    }
+
+    for attr in p.parse_all::<attrs::Warn>(resolve_context!(idx.q), attributes)? {
+        let (_, attr) = attr?;
+
+        for (ident, _) in &attr.lints {
+            levels.try_push((*ident, LintLevel::Warn))?;
+        }
+    }
+
+    for attr in p.parse_all::<attrs::Deny>(resolve_context!(idx.q), attributes)? {
+        let (_, attr) = attr?;
+
+        for (ident, _) in &attr.lints {
+            levels.try_push((*ident, LintLevel::Deny))?;
+        }
+    }
+
+    for (ident, level) in levels {
+        let name = ident.resolve(resolve_context!(idx.q))?;
+
+        let Some(lints) = Lint::lookup(name) else {
+            return Err(compile::Error::new(
+                ident,
+                ErrorKind::UnknownLint {
+                    name: name.try_into()?,
+                },
+            ));
+        };
+
+        for &lint in lints {
+            idx.q
+                .diagnostics
+                .set_lint_level(idx.source_id, span, lint, level)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn type_annotation(idx: &mut Indexer<'_, '_>, ast: &mut ast::Type) -> compile::Result<()> {
+    match ast {
+        ast::Type::Path(p) => {
+            path(idx, p)?;
+        }
+        ast::Type::Bang(..) => {}
+        ast::Type::Tuple(tuple) => {
+            for (ty, _) in tuple {
+                type_annotation(idx, ty)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Register the type annotations of a function so that they can be checked
+/// where it is called.
+fn fn_annotations(
+    idx: &mut Indexer<'_, '_>,
+    ast: &ast::ItemFn,
+    item_meta: &ItemMeta,
+    call: Call,
+    is_instance: bool,
+) -> compile::Result<()> {
+    let output = match &ast.output {
+        Some((_, ty)) => Some(ty.try_clone()?),
+        None => None,
+    };
+
+    let mut args = Vec::new();
+
+    for (arg, _) in &ast.args {
+        args.try_push(arg.ty().map(TryClone::try_clone).transpose()?)?;
+    }
+
+    if output.is_none() && args.iter().all(Option::is_none) {
+        return Ok(());
+    }
+
+    let annotations = Rc::new(indexing::Annotations::Function { call, args, output });
+
+    let hash = idx.q.pool.item_type_hash(item_meta.item);
+    idx.q.index_annotations(hash, annotations.clone())?;
+
+    // Instance functions are also called through their associated hash.
+    if let (true, Some(impl_item)) = (is_instance, idx.item.impl_item) {
+        let name = ast.name.resolve(resolve_context!(idx.q))?;
+        let type_hash = idx.q.pool.item_type_hash(impl_item);
+        let hash = Hash::associated_function(type_hash, name);
+        idx.q.index_annotations(hash, annotations)?;
+    }
+
+    Ok(())
+}
+
+/// Index and register the type annotations of the fields in a struct.
+fn struct_annotations(
+    idx: &mut Indexer<'_, '_>,
+    body: &mut ast::Fields,
+    item_meta: &ItemMeta,
+) -> compile::Result<()> {
+    let mut fields = Vec::new();
+
+    for (field, _) in body.fields_mut() {
+        let ty = match &mut field.ty {
+            Some((_, ty)) => {
+                type_annotation(idx, ty)?;
+                Some(ty.try_clone()?)
+            }
+            None => None,
+        };
+
+        let name = field.name.resolve(resolve_context!(idx.q))?;
+        fields.try_push((name.try_into()?, ty))?;
+    }
+
+    if fields.iter().all(|(_, ty)| ty.is_none()) {
+        return Ok(());
+    }
+
+    let hash = idx.q.pool.item_type_hash(item_meta.item);
+    let annotations = Rc::new(indexing::Annotations::Struct { fields });
+    idx.q.index_annotations(hash, annotations)?;
+    Ok(())
+}
+
 #[instrument_ast(span = ast)]
 fn path(idx: &mut Indexer<'_, '_>, ast: &mut ast::Path) -> compile::Result<()> {
     ast.id = idx.item.id;
@@ -1253,6 +1523,12 @@ fn expr_closure(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprClosure) -> compil
             ast::FnArg::Pat(p) => {
                 pat(idx, p)?;
             }
+            ast::FnArg::Typed(..) => {
+                return Err(compile::Error::msg(
+                    &*arg,
+                    "Static typing on closure arguments is not supported",
+                ));
+            }
         }
     }
 
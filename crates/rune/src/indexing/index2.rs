@@ -592,6 +592,10 @@ impl<'a> Processor<'a> {
             ItemImpl => {
                 item_impl(idx, p, mods, attrs)?;
             }
+            ItemTrait => {
+                idx.error(Error::msg(&*p, "trait declarations are not supported"))?;
+                p.ignore();
+            }
             ItemStruct => {
                 item_struct(idx, p, mods, attrs)?;
             }
@@ -834,6 +838,12 @@ fn item_impl(
 
     node.replace(IndexedPath(idx.item.id));
 
+    if let MaybeNode::Some(for_token) = p.eat(K![for]) {
+        idx.error(Error::msg(&for_token, "trait implementations are not supported"))?;
+        p.ignore();
+        return Ok(());
+    }
+
     let mut functions = Vec::new();
 
     p.eat(Block).parse(|p| {
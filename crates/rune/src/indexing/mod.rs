@@ -5,6 +5,7 @@ pub(crate) mod items;
 mod scopes;
 
 use crate as rune;
+use crate::alloc::path::PathBuf;
 use crate::alloc::prelude::*;
 use crate::ast::{self, Span, Spanned};
 use crate::compile::meta;
@@ -58,6 +59,8 @@ pub(crate) enum Indexed {
     Import(Import),
     /// An indexed module.
     Module,
+    /// A trait.
+    Trait,
 }
 
 /// The ast of a function.
@@ -108,6 +111,36 @@ pub(crate) struct Struct {
     pub(crate) fields: meta::Fields,
 }
 
+/// Type annotations collected when type checking is enabled.
+#[derive(Debug)]
+pub(crate) enum Annotations {
+    /// Annotations on the arguments and return value of a function.
+    Function {
+        /// The calling convention of the function.
+        call: Call,
+        /// Annotations of the arguments.
+        args: Vec<Option<ast::Type>>,
+        /// Annotation of the return value.
+        output: Option<ast::Type>,
+    },
+    /// Annotations on the fields of a struct, in declaration order.
+    Struct {
+        /// Names and annotations of the fields.
+        fields: Vec<(Box<str>, Option<ast::Type>)>,
+    },
+}
+
+/// A trait declared in a script.
+#[derive(Debug, TryClone)]
+pub(crate) struct Trait {
+    /// The item of the trait.
+    pub(crate) item_meta: ItemMeta,
+    /// The root URL that the trait was declared in.
+    pub(crate) root: Option<PathBuf>,
+    /// Functions declared in the trait.
+    pub(crate) functions: Vec<ast::ItemTraitFn>,
+}
+
 #[derive(Debug, TryClone)]
 pub(crate) struct Variant {
     /// Id of of the enum type.
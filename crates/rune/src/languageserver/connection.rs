@@ -5,7 +5,8 @@ use rust_alloc::sync::Arc;
 use anyhow::{anyhow, bail, Result};
 use tokio::io;
 use tokio::io::{
-    AsyncBufRead, AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _, BufReader,
+    AsyncBufRead, AsyncBufReadExt as _, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _,
+    BufReader,
 };
 use tokio::sync::Mutex;
 
@@ -48,10 +49,18 @@ impl Input {
 /// Output connection.
 #[derive(Clone)]
 pub(super) struct Output {
-    stdout: Arc<Mutex<io::Stdout>>,
+    writer: Arc<Mutex<rust_alloc::boxed::Box<dyn AsyncWrite + Send + Unpin>>>,
 }
 
 impl Output {
+    /// Construct an output which discards everything written to it.
+    #[cfg(test)]
+    pub(super) fn sink() -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(rust_alloc::boxed::Box::new(io::sink()))),
+        }
+    }
+
     /// Send the given response.
     pub(super) async fn response<R>(&self, id: Option<envelope::RequestId>, result: R) -> Result<()>
     where
@@ -148,9 +157,9 @@ impl Output {
         write!(m, "\r\n")?;
         m.append(bytes);
 
-        let mut stdout = self.stdout.lock().await;
-        stdout.write_all(&m).await?;
-        stdout.flush().await?;
+        let mut writer = self.writer.lock().await;
+        writer.write_all(&m).await?;
+        writer.flush().await?;
         Ok(())
     }
 }
@@ -166,7 +175,7 @@ pub(super) fn stdio() -> Result<(Input, Output)> {
     };
 
     let output = Output {
-        stdout: Arc::new(Mutex::new(stdout)),
+        writer: Arc::new(Mutex::new(rust_alloc::boxed::Box::new(stdout))),
     };
 
     Ok((input, output))
@@ -9,13 +9,17 @@ mod completion;
 mod connection;
 pub mod envelope;
 mod fs;
+mod signature;
 mod state;
 mod url;
 
+use core::time::Duration;
+
 use lsp::notification::Notification;
 use lsp::request::Request;
 use serde::Deserialize;
 use tokio::sync::Notify;
+use tokio::time::Instant;
 
 use crate::alloc::String;
 use crate::languageserver::connection::stdio;
@@ -27,6 +31,9 @@ use crate::{Context, Options};
 
 use self::state::StateEncoding;
 
+/// How long to wait for edits to settle before rebuilding the project.
+const REBUILD_DEBOUNCE: Duration = Duration::from_millis(150);
+
 enum Language {
     Rune,
     Other,
@@ -41,6 +48,12 @@ pub async fn run(context: Context, options: Options) -> Result<()> {
     let rebuild = rebuild_notify.notified();
     tokio::pin!(rebuild);
 
+    // Rebuilds are delayed until no interest has been indicated for
+    // `REBUILD_DEBOUNCE`, so that we don't rebuild on every keystroke.
+    let debounce = tokio::time::sleep(REBUILD_DEBOUNCE);
+    tokio::pin!(debounce);
+    let mut pending = false;
+
     let mut state = State::new(output, &rebuild_notify, context, options);
     tracing::info!("Starting server");
     state.rebuild().await?;
@@ -48,9 +61,14 @@ pub async fn run(context: Context, options: Options) -> Result<()> {
     while !state.is_stopped() {
         tokio::select! {
             _ = rebuild.as_mut() => {
+                debounce.as_mut().reset(Instant::now() + REBUILD_DEBOUNCE);
+                pending = true;
+                rebuild.set(rebuild_notify.notified());
+            },
+            _ = debounce.as_mut(), if pending => {
+                pending = false;
                 tracing::info!("rebuilding project");
                 state.rebuild().await?;
-                rebuild.set(rebuild_notify.notified());
             },
             frame = input.next() => {
                 let frame = match frame? {
@@ -104,6 +122,12 @@ pub async fn run(context: Context, options: Options) -> Result<()> {
                     req(lsp::request::Initialize, initialize),
                     req(lsp::request::Shutdown, shutdown),
                     req(lsp::request::GotoDefinition, goto_definition),
+                    req(lsp::request::HoverRequest, hover),
+                    req(lsp::request::References, references),
+                    req(lsp::request::Rename, rename),
+                    req(lsp::request::DocumentSymbolRequest, document_symbol),
+                    req(lsp::request::SignatureHelpRequest, signature_help),
+                    req(lsp::request::InlayHintRequest, inlay_hint),
                     req(lsp::request::Completion, completion),
                     req(lsp::request::Formatting, formatting),
                     req(lsp::request::RangeFormatting, range_formatting),
@@ -171,6 +195,16 @@ async fn initialize(
             lsp::TextDocumentSyncKind::INCREMENTAL,
         )),
         definition_provider: Some(lsp::OneOf::Left(true)),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+        references_provider: Some(lsp::OneOf::Left(true)),
+        rename_provider: Some(lsp::OneOf::Left(true)),
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
+        signature_help_provider: Some(lsp::SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: None,
+            work_done_progress_options: Default::default(),
+        }),
+        inlay_hint_provider: Some(lsp::OneOf::Left(true)),
         completion_provider: Some(lsp::CompletionOptions {
             all_commit_characters: None,
             resolve_provider: Some(false),
@@ -247,6 +281,82 @@ async fn goto_definition(
     Ok(position.map(lsp::GotoDefinitionResponse::Scalar))
 }
 
+/// Handle hover request.
+async fn hover(s: &mut State<'_>, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
+    s.hover(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle references request.
+async fn references(
+    s: &mut State<'_>,
+    params: lsp::ReferenceParams,
+) -> Result<Option<rust_alloc::vec::Vec<lsp::Location>>> {
+    let locations = s.references(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        params.context.include_declaration,
+    )?;
+
+    Ok(locations.map(|locations| locations.into_std()))
+}
+
+/// Handle rename request.
+async fn rename(
+    s: &mut State<'_>,
+    params: lsp::RenameParams,
+) -> Result<Option<lsp::WorkspaceEdit>> {
+    if !state::is_valid_name(&params.new_name) {
+        s.output
+            .log(
+                lsp::MessageType::WARNING,
+                format!(
+                    "Cannot rename to `{}`, which is not an identifier",
+                    params.new_name
+                ),
+            )
+            .await?;
+
+        return Ok(None);
+    }
+
+    s.rename(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        &params.new_name,
+    )
+}
+
+/// Handle document symbol request.
+async fn document_symbol(
+    s: &mut State<'_>,
+    params: lsp::DocumentSymbolParams,
+) -> Result<Option<lsp::DocumentSymbolResponse>> {
+    let symbols = s.document_symbols(&params.text_document.uri)?;
+    Ok(symbols.map(lsp::DocumentSymbolResponse::Nested))
+}
+
+/// Handle signature help request.
+async fn signature_help(
+    s: &mut State<'_>,
+    params: lsp::SignatureHelpParams,
+) -> Result<Option<lsp::SignatureHelp>> {
+    s.signature_help(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle inlay hint request.
+async fn inlay_hint(
+    s: &mut State<'_>,
+    params: lsp::InlayHintParams,
+) -> Result<Option<std::vec::Vec<lsp::InlayHint>>> {
+    s.inlay_hints(&params.text_document.uri, params.range)
+}
+
 /// Handle initialized notification.
 async fn completion(
     state: &mut State<'_>,
@@ -318,18 +428,22 @@ async fn did_change_text_document(
 ) -> Result<()> {
     let mut interest = false;
 
-    if let Some(source) = s.workspace.get_mut(&params.text_document.uri) {
-        for change in params.content_changes {
-            if let Some(range) = change.range {
-                source.modify_lsp_range(&s.encoding, range, &change.text)?;
-                interest = true;
-            }
+    for change in params.content_changes {
+        let Some(range) = change.range else {
+            continue;
+        };
+
+        let url = &params.text_document.uri;
+
+        if !s
+            .workspace
+            .modify_lsp_range(url, &s.encoding, range, &change.text)?
+        {
+            tracing::warn!("tried to modify `{url}`, but it was not open!");
+            break;
         }
-    } else {
-        tracing::warn!(
-            "tried to modify `{}`, but it was not open!",
-            params.text_document.uri
-        );
+
+        interest = true;
     }
 
     if interest {
@@ -350,10 +464,14 @@ async fn did_close_text_document(
 }
 
 /// Handle saving of text documents.
+///
+/// This performs a full rebuild, since files which are not open might have
+/// been modified on disk.
 async fn did_save_text_document(
     s: &mut State<'_>,
     _: lsp::DidSaveTextDocumentParams,
 ) -> Result<()> {
+    s.workspace.invalidate();
     s.rebuild_interest();
     Ok(())
 }
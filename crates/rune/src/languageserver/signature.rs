@@ -0,0 +1,389 @@
+//! Helpers for signature help and inlay hints.
+
+use anyhow::Result;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{String, Vec};
+use crate::ast::{self, Span};
+use crate::compile::context::ContextMeta;
+use crate::compile::{meta, Prelude};
+use crate::parse::Lexer;
+use crate::runtime::debug::{DebugArgs, DebugSignature};
+use crate::{Context, Hash, ItemBuf, SourceId};
+
+/// The parameters of a function which can be called.
+pub(super) struct Parameters {
+    /// The label of the whole signature.
+    pub(super) label: String,
+    /// The parameters, as offsets into the label and their names.
+    pub(super) parameters: Vec<Parameter>,
+    /// The return type of the function, if known.
+    pub(super) return_type: Option<String>,
+    /// Documentation of the function.
+    pub(super) docs: Option<String>,
+}
+
+/// A single parameter.
+pub(super) struct Parameter {
+    /// The name of the parameter.
+    pub(super) name: String,
+    /// If the parameter has a meaningful name which is worth hinting.
+    pub(super) named: bool,
+    /// The byte range of the parameter in the label.
+    pub(super) range: (usize, usize),
+}
+
+impl Parameters {
+    /// Construct parameters from a function registered in the context.
+    pub(super) fn native(
+        context: &Context,
+        meta: &ContextMeta,
+        signature: &meta::Signature,
+    ) -> Result<Self> {
+        let mut label = String::new();
+
+        match &meta.item {
+            Some(item) => write!(label, "{}(", item.unqualified())?,
+            None => write!(label, "(")?,
+        }
+
+        let mut parameters = Vec::new();
+
+        for (n, argument) in signature
+            .arguments
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            if n > 0 {
+                write!(label, ", ")?;
+            }
+
+            let start = label.len();
+            let name = argument.name.try_to_string()?;
+            write!(label, "{name}")?;
+
+            if let Some(ty) = type_name(context, argument.base, &argument.generics)? {
+                write!(label, ": {ty}")?;
+            }
+
+            let named = match &argument.name {
+                meta::DocName::Name(name) => name.as_ref() != "self",
+                meta::DocName::Index(..) => false,
+            };
+
+            parameters.try_push(Parameter {
+                name,
+                named,
+                range: (start, label.len()),
+            })?;
+        }
+
+        write!(label, ")")?;
+
+        let return_type = type_name(
+            context,
+            signature.return_type.base,
+            &signature.return_type.generics,
+        )?;
+
+        if let Some(ty) = &return_type {
+            write!(label, " -> {ty}")?;
+        }
+
+        Ok(Self {
+            label,
+            parameters,
+            return_type,
+            docs: join_docs(meta.docs.lines())?,
+        })
+    }
+
+    /// Construct parameters from a function declared in a script.
+    pub(super) fn script(signature: &DebugSignature, docs: Option<&[String]>) -> Result<Self> {
+        let mut label = String::new();
+        write!(label, "{}(", signature.path)?;
+
+        let mut parameters = Vec::new();
+
+        if let DebugArgs::Named(names) = &signature.args {
+            for (n, name) in names.iter().enumerate() {
+                if n > 0 {
+                    write!(label, ", ")?;
+                }
+
+                let start = label.len();
+                write!(label, "{name}")?;
+
+                parameters.try_push(Parameter {
+                    name: name.as_ref().try_to_owned()?,
+                    named: name.as_ref() != "self",
+                    range: (start, label.len()),
+                })?;
+            }
+        }
+
+        write!(label, ")")?;
+
+        Ok(Self {
+            label,
+            parameters,
+            return_type: None,
+            docs: join_docs(docs.unwrap_or_default())?,
+        })
+    }
+}
+
+/// Find the function registered in the context with the given path, as it
+/// would be written in a script.
+pub(super) fn lookup_native<'a>(
+    context: &'a Context,
+    path: &[&str],
+) -> Result<Option<(&'a ContextMeta, &'a meta::Signature)>> {
+    let Some((first, rest)) = path.split_first() else {
+        return Ok(None);
+    };
+
+    let prelude = Prelude::with_default_prelude()?;
+
+    let mut item = match prelude.get(first) {
+        Some(item) => item.try_to_owned()?,
+        None => ItemBuf::with_item([*first])?,
+    };
+
+    for component in rest {
+        item.push(*component)?;
+    }
+
+    let Some(mut metas) = context.lookup_meta(&item) else {
+        return Ok(None);
+    };
+
+    Ok(metas.find_map(|meta| Some((meta, meta.kind.as_signature()?))))
+}
+
+/// Lex the given source, ignoring whitespace and comments.
+///
+/// Lexing stops at the first error, since tokens are used to make sense of
+/// code which is in the process of being written.
+pub(super) fn tokens(source: &str) -> crate::alloc::Result<Vec<ast::Token>> {
+    let mut lexer = Lexer::new(source, SourceId::EMPTY, true).without_processing();
+    let mut tokens = Vec::new();
+
+    while let Ok(Some(token)) = lexer.next() {
+        if matches!(
+            token.kind,
+            ast::Kind::Whitespace | ast::Kind::Comment | ast::Kind::MultilineComment(..)
+        ) {
+            continue;
+        }
+
+        tokens.try_push(token)?;
+    }
+
+    Ok(tokens)
+}
+
+/// Get the spans of the arguments of a call whose opening parenthesis is at
+/// the given token.
+pub(super) fn arguments(tokens: &[ast::Token], open: usize) -> crate::alloc::Result<Vec<Span>> {
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut current = None::<Span>;
+
+    for token in tokens.iter().skip(open) {
+        match token.kind {
+            ast::Kind::Open(..) => {
+                depth += 1;
+
+                if depth == 1 {
+                    continue;
+                }
+            }
+            ast::Kind::Close(..) => {
+                depth = depth.saturating_sub(1);
+
+                if depth == 0 {
+                    break;
+                }
+            }
+            ast::Kind::Comma if depth == 1 => {
+                if let Some(span) = current.take() {
+                    arguments.try_push(span)?;
+                }
+
+                continue;
+            }
+            _ => {}
+        }
+
+        current = Some(match current {
+            Some(span) => span.join(token.span),
+            None => token.span,
+        });
+    }
+
+    if let Some(span) = current {
+        arguments.try_push(span)?;
+    }
+
+    Ok(arguments)
+}
+
+/// Find the call whose arguments the last token is part of, returning the
+/// path of the function being called and the index of the argument.
+///
+/// Method calls are not considered, since the type of their receiver is not
+/// known.
+pub(super) fn enclosing_call<'a>(
+    source: &'a str,
+    tokens: &[ast::Token],
+) -> crate::alloc::Result<Option<(Vec<&'a str>, usize)>> {
+    let mut stack = Vec::<(usize, usize)>::new();
+
+    for (n, token) in tokens.iter().enumerate() {
+        match token.kind {
+            ast::Kind::Open(..) => {
+                stack.try_push((n, 0))?;
+            }
+            ast::Kind::Close(..) => {
+                stack.pop();
+            }
+            ast::Kind::Comma => {
+                if let Some((_, commas)) = stack.last_mut() {
+                    *commas += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for &(open, commas) in stack.iter().rev() {
+        if !matches!(
+            tokens[open].kind,
+            ast::Kind::Open(ast::Delimiter::Parenthesis)
+        ) {
+            continue;
+        }
+
+        let path = path_before(source, &tokens[..open])?;
+
+        if !path.is_empty() {
+            return Ok(Some((path, commas)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// If the call whose path ends at the given token is assigned to a variable
+/// with `let`, get the span of the variable.
+pub(super) fn let_binding(tokens: &[ast::Token], end: usize) -> Option<Span> {
+    let mut n = end;
+
+    while n > 0 && matches!(tokens[n - 1].kind, ast::Kind::ColonColon) {
+        n = n.checked_sub(2)?;
+
+        if !matches!(tokens.get(n)?.kind, ast::Kind::Ident(..)) {
+            return None;
+        }
+    }
+
+    let [.., let_, name, eq] = tokens.get(..n)? else {
+        return None;
+    };
+
+    match (let_.kind, name.kind, eq.kind) {
+        (ast::Kind::Let, ast::Kind::Ident(..), ast::Kind::Eq) => Some(name.span),
+        _ => None,
+    }
+}
+
+/// Get the components of the path which ends right before the given tokens.
+fn path_before<'a>(source: &'a str, tokens: &[ast::Token]) -> crate::alloc::Result<Vec<&'a str>> {
+    let mut path = Vec::new();
+    let mut it = tokens.iter().rev().peekable();
+
+    while let Some(token) = it.next() {
+        let ast::Kind::Ident(..) = token.kind else {
+            break;
+        };
+
+        let Some(name) = source.get(token.span.range()) else {
+            break;
+        };
+
+        path.try_push(name)?;
+
+        if !matches!(it.peek().map(|t| t.kind), Some(ast::Kind::ColonColon)) {
+            // A method call, which can't be resolved.
+            if matches!(it.peek().map(|t| t.kind), Some(ast::Kind::Dot)) {
+                path.clear();
+            }
+
+            break;
+        }
+
+        it.next();
+    }
+
+    path.reverse();
+    Ok(path)
+}
+
+/// Format the name of a type, if it is known.
+fn type_name(context: &Context, base: Hash, generics: &[meta::DocType]) -> Result<Option<String>> {
+    if base == Hash::EMPTY {
+        return Ok(None);
+    }
+
+    let Some(item) = context
+        .lookup_meta_by_hash(base)
+        .find_map(|meta| meta.item.as_ref())
+    else {
+        return Ok(None);
+    };
+
+    let mut name = String::new();
+    write!(name, "{}", item.base_name().unwrap_or_default())?;
+
+    if !generics.is_empty() {
+        write!(name, "<")?;
+
+        for (n, ty) in generics.iter().enumerate() {
+            if n > 0 {
+                write!(name, ", ")?;
+            }
+
+            match type_name(context, ty.base, &ty.generics)? {
+                Some(ty) => write!(name, "{ty}")?,
+                None => write!(name, "_")?,
+            }
+        }
+
+        write!(name, ">")?;
+    }
+
+    Ok(Some(name))
+}
+
+/// Join lines of documentation, if there are any.
+fn join_docs(lines: &[String]) -> crate::alloc::Result<Option<String>> {
+    if lines.is_empty() {
+        return Ok(None);
+    }
+
+    let mut docs = String::new();
+
+    for (n, line) in lines.iter().enumerate() {
+        if n > 0 {
+            docs.try_push('\n')?;
+        }
+
+        docs.try_push_str(line)?;
+    }
+
+    Ok(Some(docs))
+}
@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, HashSet};
 use std::fmt;
+use std::mem::take;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -8,21 +9,24 @@ use lsp::Url;
 use ropey::Rope;
 use tokio::sync::Notify;
 
+use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, HashMap, String, Vec};
-use crate::ast::{Span, Spanned};
+use crate::ast::{self, Span, Spanned};
 use crate::compile::meta;
 use crate::compile::{
-    self, CompileVisitor, LinkerError, Located, Location, MetaError, MetaRef, SourceMeta, WithSpan,
+    self, CompileVisitor, LinkerError, Located, Location, MetaError, MetaRef, ParseCache,
+    SourceMeta, WithSpan,
 };
 use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
 use crate::doc::VisitorData;
 use crate::item::ComponentRef;
 use crate::languageserver::connection::Output;
+use crate::languageserver::signature::{self, Parameters};
 use crate::languageserver::Language;
 use crate::workspace::{self, WorkspaceError};
 use crate::{self as rune, Diagnostics};
-use crate::{BuildError, Context, Hash, Item, Options, Source, SourceId, Sources, Unit};
+use crate::{BuildError, Context, Hash, Item, ItemBuf, Options, Source, SourceId, Sources, Unit};
 
 #[derive(Default)]
 struct Reporter {
@@ -44,26 +48,34 @@ impl Reporter {
 }
 
 struct Build {
+    /// The url of the entry source of the build.
+    url: Url,
     id_to_url: HashMap<SourceId, Url>,
     sources: Sources,
     /// If a file is coming from a workspace.
     workspace: bool,
+    /// Sources parsed by the previous build of the same entry.
+    parsed: ParseCache,
 }
 
 impl Build {
-    pub(super) fn from_workspace() -> Self {
+    pub(super) fn from_workspace(url: Url) -> Self {
         Self {
+            url,
             id_to_url: HashMap::new(),
             sources: Sources::default(),
             workspace: true,
+            parsed: ParseCache::default(),
         }
     }
 
-    pub(super) fn from_file() -> Self {
+    pub(super) fn from_file(url: Url) -> Self {
         Self {
+            url,
             id_to_url: HashMap::new(),
             sources: Sources::default(),
             workspace: false,
+            parsed: ParseCache::default(),
         }
     }
 
@@ -107,6 +119,63 @@ impl Build {
     }
 }
 
+/// The result of a previous build, which is reused for as long as none of the
+/// sources it was built from have been modified.
+///
+/// Reuse happens at the granularity of a whole build. Modifying any source
+/// which is part of a build causes the entire build to be compiled again,
+/// while builds which don't include the modified source are left untouched.
+/// When a build is compiled again, only the sources which were modified are
+/// parsed again. Indexing and compiling is always redone for the whole build,
+/// since their results aren't tracked per source.
+pub(super) struct CachedBuild {
+    /// If the build is coming from a workspace.
+    pub(super) workspace: bool,
+    /// Every source which was loaded as part of the build.
+    pub(super) urls: HashSet<Url>,
+    /// Diagnostics emitted by the build, grouped by URL.
+    pub(super) diagnostics: BTreeMap<Url, Vec<lsp::Diagnostic>>,
+    /// Sources parsed by the build.
+    pub(super) parsed: ParseCache,
+}
+
+impl CachedBuild {
+    /// Test if the build is still valid given the set of modified sources.
+    fn is_fresh(&self, workspace: bool, dirty: &HashSet<Url>) -> bool {
+        self.workspace == workspace && self.urls.is_disjoint(dirty)
+    }
+}
+
+/// Builds which have been performed, keyed by the url of their entry source.
+#[derive(Default)]
+pub(super) struct BuildCache {
+    pub(super) builds: HashMap<Url, CachedBuild>,
+}
+
+impl BuildCache {
+    /// Take a cached build for the given entry if it can be reused.
+    ///
+    /// If the cached build is stale, the sources it parsed are moved into
+    /// `parsed` so that the next build can reuse the unmodified ones.
+    pub(super) fn take_fresh(
+        &mut self,
+        url: &Url,
+        workspace: bool,
+        dirty: &HashSet<Url>,
+        parsed: &mut ParseCache,
+    ) -> Option<CachedBuild> {
+        let mut build = self.builds.remove(url)?;
+
+        if !build.is_fresh(workspace, dirty) {
+            tracing::trace!(url = ?url.try_to_string(), "build is stale");
+            *parsed = take(&mut build.parsed);
+            return None;
+        }
+
+        Some(build)
+    }
+}
+
 pub(super) enum StateEncoding {
     Utf8,
     Utf16,
@@ -185,6 +254,10 @@ pub(super) struct State<'a> {
     stopped: bool,
     /// Sources used in the project.
     pub(super) workspace: Workspace,
+    /// Builds from the previous rebuild.
+    builds: BuildCache,
+    /// Diagnostics which have been published to the client, grouped by URL.
+    published: BTreeMap<Url, Vec<lsp::Diagnostic>>,
 }
 
 impl<'a> State<'a> {
@@ -204,6 +277,8 @@ impl<'a> State<'a> {
             initialized: bool::default(),
             stopped: bool::default(),
             workspace: Workspace::default(),
+            builds: BuildCache::default(),
+            published: BTreeMap::new(),
         }
     }
 
@@ -246,7 +321,7 @@ impl<'a> State<'a> {
 
         let offset = self.encoding.rope_position(&source.content, position)?;
 
-        let Some(def) = source.find_definition_at(Span::point(offset)) else {
+        let Some((_, def)) = source.find_definition_at(Span::point(offset)) else {
             return Ok(None);
         };
 
@@ -271,6 +346,560 @@ impl<'a> State<'a> {
         Ok(Some(location))
     }
 
+    /// Get hover information for the definition at the given uri and LSP
+    /// position.
+    pub(super) fn hover(&self, uri: &Url, position: lsp::Position) -> Result<Option<lsp::Hover>> {
+        let Some(resolved) = self.resolve(uri, position)? else {
+            return Ok(None);
+        };
+
+        let Some(build) = resolved.source.build_source() else {
+            return Ok(None);
+        };
+
+        let def = resolved.def;
+
+        let mut value = String::new();
+        writeln!(value, "```rune")?;
+
+        match &def.meta {
+            Some(meta) => write_signature(&mut value, resolved.source, def.kind, meta)?,
+            None => write!(value, "let {}", resolved.name)?,
+        }
+
+        writeln!(value)?;
+        write!(value, "```")?;
+
+        let docs = def
+            .meta
+            .as_ref()
+            .and_then(|meta| resolved.source.get_docs_by_hash(meta.hash))
+            .filter(|data| !data.docs.is_empty());
+
+        if let Some(data) = docs {
+            write!(value, "\n\n{}", data.docs.join("\n"))?;
+        }
+
+        Ok(Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: value.into_std(),
+            }),
+            range: Some(self.encoding.source_range(build, resolved.span)?),
+        }))
+    }
+
+    /// Find all references to the definition at the given uri and LSP
+    /// position.
+    ///
+    /// References can only be found in sources which are open in the
+    /// workspace, since those are the only ones which are indexed.
+    pub(super) fn references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Result<Option<Vec<lsp::Location>>> {
+        let Some(resolved) = self.resolve(uri, position)? else {
+            return Ok(None);
+        };
+
+        let def = resolved.def;
+        let name = resolved.name;
+
+        let Some(key) = definition_key(uri, def)? else {
+            return Ok(None);
+        };
+
+        let mut locations = Vec::new();
+
+        if include_declaration {
+            let declaration = resolved
+                .source
+                .build_sources
+                .as_ref()
+                .and_then(|sources| sources.get(def.source.source_id()));
+
+            let span = def.source.span();
+
+            let span = declaration.and_then(|s| name_span(s, span, name, def.name_at()));
+
+            if let (Some(declaration), Some(span)) = (declaration, span) {
+                locations.try_push(lsp::Location {
+                    uri: key.0.clone(),
+                    range: self.encoding.source_range(declaration, span)?,
+                })?;
+            }
+        }
+
+        for (url, source) in &self.workspace.sources {
+            let Some(build) = source.build_source() else {
+                continue;
+            };
+
+            for (span, other) in &source.index.definitions {
+                let span = if definition_key(url, other)?.as_ref() == Some(&key)
+                    && other.name(build, *span) == Some(name)
+                {
+                    name_span(build, *span, name, NameAt::Path).unwrap_or(*span)
+                } else if is_parent(def, other) {
+                    // Paths to associated items and variants also reference
+                    // the item they belong to, such as `Foo` in `Foo::new`.
+                    match name_span(build, *span, name, NameAt::Path) {
+                        Some(span) => span,
+                        None => continue,
+                    }
+                } else {
+                    continue;
+                };
+
+                let location = lsp::Location {
+                    uri: url.clone(),
+                    range: self.encoding.source_range(build, span)?,
+                };
+
+                if !locations.contains(&location) {
+                    locations.try_push(location)?;
+                }
+            }
+        }
+
+        locations.sort_by(|a, b| {
+            let a = (a.uri.as_str(), a.range.start.line, a.range.start.character);
+            let b = (b.uri.as_str(), b.range.start.line, b.range.start.character);
+            a.cmp(&b)
+        });
+
+        Ok(Some(locations))
+    }
+
+    /// Rename the definition at the given uri and LSP position, and all
+    /// references to it.
+    ///
+    /// Like [`State::references`], this only considers sources which are
+    /// open in the workspace.
+    pub(super) fn rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Result<Option<lsp::WorkspaceEdit>> {
+        let Some(locations) = self.references(uri, position, true)? else {
+            return Ok(None);
+        };
+
+        let mut changes = std::collections::HashMap::<_, rust_alloc::vec::Vec<_>>::new();
+
+        for location in locations {
+            changes
+                .entry(location.uri)
+                .or_default()
+                .push(lsp::TextEdit::new(location.range, new_name.into()));
+        }
+
+        Ok(Some(lsp::WorkspaceEdit::new(changes)))
+    }
+
+    /// Get the items declared in the document at the given uri, nested by
+    /// the items which contain them.
+    pub(super) fn document_symbols(
+        &self,
+        uri: &Url,
+    ) -> Result<Option<rust_alloc::vec::Vec<lsp::DocumentSymbol>>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let Some(build) = source.build_source() else {
+            return Ok(None);
+        };
+
+        let mut symbols = Vec::new();
+
+        for def in &source.index.symbols {
+            let span = def.source.span();
+
+            let Some(name) = def.name(build, span) else {
+                continue;
+            };
+
+            let selection = name_span(build, span, name, NameAt::Item).unwrap_or(span);
+
+            let detail = match (def.kind, &def.meta, &source.unit) {
+                (
+                    DefinitionKind::Function | DefinitionKind::AssociatedFunction,
+                    Some(meta),
+                    Some(unit),
+                ) => unit
+                    .debug_info()
+                    .and_then(|debug| debug.functions.get(&meta.hash))
+                    .map(|signature| signature.try_to_string())
+                    .transpose()?
+                    .map(String::into_std),
+                _ => None,
+            };
+
+            #[allow(deprecated)]
+            let symbol = lsp::DocumentSymbol {
+                name: name.into(),
+                detail,
+                kind: symbol_kind(def.kind),
+                tags: None,
+                deprecated: None,
+                range: self.encoding.source_range(build, span)?,
+                selection_range: self.encoding.source_range(build, selection)?,
+                children: None,
+            };
+
+            symbols.try_push((span, symbol))?;
+        }
+
+        symbols.sort_by_key(|(span, _)| (span.start, core::cmp::Reverse(span.end)));
+
+        // Nest symbols inside of the symbols whose spans contain them, such as
+        // variants inside of their enum or items inside of an inline module.
+        let mut roots = rust_alloc::vec::Vec::new();
+        let mut stack = Vec::<(Span, lsp::DocumentSymbol)>::new();
+
+        for (span, symbol) in symbols {
+            while let Some((parent, _)) = stack.last() {
+                if parent.start <= span.start && span.end <= parent.end {
+                    break;
+                }
+
+                pop_symbol(&mut stack, &mut roots);
+            }
+
+            stack.try_push((span, symbol))?;
+        }
+
+        while !stack.is_empty() {
+            pop_symbol(&mut stack, &mut roots);
+        }
+
+        Ok(Some(roots))
+    }
+
+    /// Get help for the signature of the function being called at the given
+    /// uri and LSP position.
+    pub(super) fn signature_help(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::SignatureHelp>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+        let offset = source.content.try_char_to_byte(offset)?;
+        let text = source.chunks().try_collect::<String>()?;
+
+        let Some(prefix) = text.get(..offset) else {
+            return Ok(None);
+        };
+
+        let tokens = signature::tokens(prefix)?;
+
+        let Some((path, active)) = signature::enclosing_call(prefix, &tokens)? else {
+            return Ok(None);
+        };
+
+        let Some(parameters) = self.lookup_parameters(source, &path)? else {
+            return Ok(None);
+        };
+
+        let label = parameters.label.as_str();
+
+        let offset = |at: usize| u32::try_from(label[..at].encode_utf16().count());
+
+        let mut params = rust_alloc::vec::Vec::new();
+
+        for parameter in &parameters.parameters {
+            let (start, end) = parameter.range;
+
+            params.push(lsp::ParameterInformation {
+                label: lsp::ParameterLabel::LabelOffsets([offset(start)?, offset(end)?]),
+                documentation: None,
+            });
+        }
+
+        let documentation = parameters.docs.map(|docs| {
+            lsp::Documentation::MarkupContent(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: docs.into_std(),
+            })
+        });
+
+        let active = u32::try_from(active)?;
+
+        let signature = lsp::SignatureInformation {
+            label: label.into(),
+            documentation,
+            parameters: Some(params),
+            active_parameter: Some(active),
+        };
+
+        Ok(Some(lsp::SignatureHelp {
+            signatures: rust_alloc::vec![signature],
+            active_signature: Some(0),
+            active_parameter: Some(active),
+        }))
+    }
+
+    /// Get inlay hints for the calls in the given range of the document at the
+    /// given uri.
+    ///
+    /// Hints name the parameters that arguments are passed to, and the types
+    /// of variables assigned the result of a native function. They are only
+    /// provided if the document is unchanged since it was last built.
+    pub(super) fn inlay_hints(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+    ) -> Result<Option<rust_alloc::vec::Vec<lsp::InlayHint>>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let Some(build) = source.build_source() else {
+            return Ok(None);
+        };
+
+        let text = build.as_str();
+
+        if source.chunks().try_collect::<String>()?.as_str() != text {
+            return Ok(None);
+        }
+
+        let tokens = signature::tokens(text)?;
+        let mut hints = Vec::new();
+
+        for (n, window) in tokens.windows(2).enumerate() {
+            let [callee, open] = window else {
+                continue;
+            };
+
+            if !matches!(
+                (callee.kind, open.kind),
+                (
+                    ast::Kind::Ident(..),
+                    ast::Kind::Open(ast::Delimiter::Parenthesis)
+                )
+            ) {
+                continue;
+            }
+
+            let Some(parameters) = self.parameters_at(source, callee.span)? else {
+                continue;
+            };
+
+            let arguments = signature::arguments(&tokens, n + 1)?;
+
+            for (argument, parameter) in arguments.iter().zip(&parameters.parameters) {
+                if !parameter.named || text.get(argument.range()) == Some(&parameter.name) {
+                    continue;
+                }
+
+                let position = self
+                    .encoding
+                    .source_position(build, argument.start.into_usize())?;
+
+                let mut label = String::new();
+                write!(label, "{}:", parameter.name)?;
+
+                hints.try_push(lsp::InlayHint {
+                    position,
+                    label: lsp::InlayHintLabel::String(label.into_std()),
+                    kind: Some(lsp::InlayHintKind::PARAMETER),
+                    text_edits: None,
+                    tooltip: None,
+                    padding_left: None,
+                    padding_right: Some(true),
+                    data: None,
+                })?;
+            }
+
+            let (Some(ty), Some(binding)) =
+                (&parameters.return_type, signature::let_binding(&tokens, n))
+            else {
+                continue;
+            };
+
+            let mut label = String::new();
+            write!(label, ": {ty}")?;
+
+            hints.try_push(lsp::InlayHint {
+                position: self
+                    .encoding
+                    .source_position(build, binding.end.into_usize())?,
+                label: lsp::InlayHintLabel::String(label.into_std()),
+                kind: Some(lsp::InlayHintKind::TYPE),
+                text_edits: None,
+                tooltip: None,
+                padding_left: None,
+                padding_right: None,
+                data: None,
+            })?;
+        }
+
+        hints.retain(|hint| range.start <= hint.position && hint.position <= range.end);
+        hints.sort_by_key(|hint| hint.position);
+        Ok(Some(hints.into_std()))
+    }
+
+    /// Get the parameters of the function called through the path written at
+    /// the current position of a source, which might not have been built yet.
+    fn lookup_parameters(
+        &self,
+        source: &ServerSource,
+        path: &[&str],
+    ) -> Result<Option<Parameters>> {
+        if let Some((meta, signature)) = signature::lookup_native(&self.context, path)? {
+            return Ok(Some(Parameters::native(&self.context, meta, signature)?));
+        }
+
+        let build = source.build_source();
+
+        // Functions imported with `use` are only known through the paths
+        // which were resolved when the source was last built.
+        for (span, hash) in &source.index.natives {
+            let Some(text) = build.and_then(|build| build.get(span.range())) else {
+                continue;
+            };
+
+            let mut components = text.split("::").map(str::trim);
+
+            if components.by_ref().eq(path.iter().copied()) {
+                return self.native_parameters(*hash);
+            }
+        }
+
+        let definitions = source.index.definitions.values();
+
+        for def in source.index.symbols.iter().chain(definitions) {
+            let Some(meta) = &def.meta else {
+                continue;
+            };
+
+            let mut it = meta.item.iter();
+
+            let matches = path
+                .iter()
+                .rev()
+                .all(|name| matches!(it.next_back(), Some(ComponentRef::Str(c)) if c == *name));
+
+            if matches {
+                if let Some(parameters) = self.script_parameters(source, def)? {
+                    return Ok(Some(parameters));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the parameters of the function referenced through the path which
+    /// ends at the given span of the last build of a source.
+    fn parameters_at(&self, source: &ServerSource, span: Span) -> Result<Option<Parameters>> {
+        let end = Span::new(span.start, u32::MAX);
+
+        if let Some((found, hash)) = source.index.natives.range(..=end).next_back() {
+            if found.start <= span.start && span.end <= found.end {
+                return self.native_parameters(*hash);
+            }
+        }
+
+        let Some((_, def)) = source.find_definition_at(span) else {
+            return Ok(None);
+        };
+
+        self.script_parameters(source, def)
+    }
+
+    /// Get the parameters of the native function with the given hash.
+    fn native_parameters(&self, hash: Hash) -> Result<Option<Parameters>> {
+        let found = self
+            .context
+            .lookup_meta_by_hash(hash)
+            .find_map(|meta| Some((meta, meta.kind.as_signature()?)));
+
+        let Some((meta, signature)) = found else {
+            return Ok(None);
+        };
+
+        Ok(Some(Parameters::native(&self.context, meta, signature)?))
+    }
+
+    /// Get the parameters of the function declared in a script by the given
+    /// definition.
+    fn script_parameters(
+        &self,
+        source: &ServerSource,
+        def: &Definition,
+    ) -> Result<Option<Parameters>> {
+        let (DefinitionKind::Function | DefinitionKind::AssociatedFunction, Some(meta)) =
+            (def.kind, &def.meta)
+        else {
+            return Ok(None);
+        };
+
+        let Some(signature) = source
+            .unit
+            .as_ref()
+            .and_then(|unit| unit.debug_info())
+            .and_then(|debug| debug.functions.get(&meta.hash))
+        else {
+            return Ok(None);
+        };
+
+        let docs = source
+            .get_docs_by_hash(meta.hash)
+            .map(|data| data.docs.as_slice());
+
+        Ok(Some(Parameters::script(signature, docs)?))
+    }
+
+    /// Resolve the definition referenced or declared at the given uri and LSP
+    /// position.
+    fn resolve(&self, uri: &Url, position: lsp::Position) -> Result<Option<Resolved<'_>>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let Some(build) = source.build_source() else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+
+        let (span, def) = match source.find_definition_at(Span::point(offset)) {
+            Some((span, def)) => {
+                let Some(name) = def.name(build, span) else {
+                    return Ok(None);
+                };
+
+                let span = name_span(build, span, name, NameAt::Path).unwrap_or(span);
+                (span, def)
+            }
+            None => match source.find_declaration_at(Span::point(offset)) {
+                Some(found) => found,
+                None => return Ok(None),
+            },
+        };
+
+        let Some(name) = def.name(build, span) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Resolved {
+            source,
+            span,
+            def,
+            name,
+        }))
+    }
+
     /// Find definition at the given uri and LSP position.
     #[tracing::instrument(skip_all)]
     pub(super) fn complete(
@@ -335,23 +964,21 @@ impl<'a> State<'a> {
     }
 
     pub(super) fn format(&mut self, uri: &Url) -> Result<Option<lsp::TextEdit>> {
-        let sources = &mut self.workspace.sources;
+        let sources = &self.workspace.sources;
         tracing::trace!(uri = ?uri.try_to_string()?, uri_exists = sources.get(uri).is_some());
 
-        let Some(s) = sources.get_mut(uri) else {
+        let Some(s) = sources.get(uri) else {
             return Ok(None);
         };
 
         let source = s.content.try_to_string()?;
+        let options = self.fmt_options(uri)?;
 
         let mut diagnostics = Diagnostics::new();
 
-        let Ok(formatted) = crate::fmt::layout_source_with(
-            &source,
-            SourceId::EMPTY,
-            &self.options,
-            &mut diagnostics,
-        ) else {
+        let Ok(formatted) =
+            crate::fmt::layout_source_with(&source, SourceId::EMPTY, &options, &mut diagnostics)
+        else {
             return Ok(None);
         };
 
@@ -377,10 +1004,10 @@ impl<'a> State<'a> {
         uri: &Url,
         range: &lsp::Range,
     ) -> Result<Option<lsp::TextEdit>> {
-        let sources = &mut self.workspace.sources;
+        let sources = &self.workspace.sources;
         tracing::trace!(uri = ?uri.try_to_string()?, uri_exists = sources.get(uri).is_some());
 
-        let Some(s) = sources.get_mut(uri) else {
+        let Some(s) = sources.get(uri) else {
             return Ok(None);
         };
 
@@ -393,7 +1020,7 @@ impl<'a> State<'a> {
 
         let source = source.try_to_string()?;
 
-        let mut options = self.options.clone();
+        let mut options = self.fmt_options(uri)?;
         options.fmt.force_newline = false;
 
         let mut diagnostics = Diagnostics::new();
@@ -413,20 +1040,66 @@ impl<'a> State<'a> {
         Ok(Some(edit))
     }
 
+    /// Construct formatting options for the given document, applying the
+    /// closest `rune-fmt.toml` or `Rune.toml` configuration.
+    ///
+    /// A configuration which fails to load is ignored so that formatting
+    /// falls back to the defaults.
+    fn fmt_options(&self, uri: &Url) -> Result<Options> {
+        let mut options = self.options.clone();
+
+        let Some(config) = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| workspace::find_fmt_config(path.parent()?))
+        else {
+            return Ok(options);
+        };
+
+        let mut sources = Sources::new();
+
+        let Ok(source) = Source::from_path(&config) else {
+            return Ok(options);
+        };
+
+        sources.insert(source)?;
+
+        if let Ok(manifest) = workspace::prepare(&mut sources).build() {
+            for option in &manifest.fmt {
+                options.parse_option(option)?;
+            }
+        }
+
+        Ok(options)
+    }
+
     /// Rebuild the project.
+    ///
+    /// Builds whose sources have not been modified since the last rebuild are
+    /// reused as-is, including their indexes and diagnostics. Other builds
+    /// reuse the parsed form of the sources which weren't modified.
     pub(super) async fn rebuild(&mut self) -> Result<()> {
+        // Sources modified since the last rebuild.
+        let dirty = self.workspace.take_dirty();
         // Keep track of URLs visited as part of workspace builds.
         let mut visited = HashSet::new();
         // Workspace results.
         let mut workspace_results = Vec::new();
         // Build results.
         let mut script_results = Vec::new();
-        // Emitted diagnostics, grouped by URL.
-        let mut reporter = Reporter::default();
+        // Builds which are reused from the previous rebuild.
+        let mut builds = BuildCache::default();
+
+        let mut previous = if self.workspace.take_invalidated() || self.is_manifest_dirty(&dirty) {
+            tracing::trace!("invalidating all builds");
+            BuildCache::default()
+        } else {
+            take(&mut self.builds)
+        };
 
         if let Some((workspace_url, workspace_path)) = &self.workspace.manifest_path {
             let mut diagnostics = workspace::Diagnostics::default();
-            let mut build = Build::from_workspace();
+            let mut build = Build::from_workspace(workspace_url.clone());
 
             let result = self.load_workspace(
                 workspace_url,
@@ -445,7 +1118,21 @@ impl<'a> State<'a> {
                     }
                 }
                 Ok(script_builds) => {
-                    for script_build in script_builds {
+                    for mut script_build in script_builds {
+                        let cached = previous.take_fresh(
+                            &script_build.url,
+                            true,
+                            &dirty,
+                            &mut script_build.parsed,
+                        );
+
+                        if let Some(cached) = cached {
+                            tracing::trace!(url = ?script_build.url.try_to_string()?, "reusing workspace build");
+                            visited.extend(cached.urls.iter().cloned());
+                            builds.builds.try_insert(script_build.url, cached)?;
+                            continue;
+                        }
+
                         script_results
                             .try_push(self.build_scripts(script_build, Some(&mut visited))?)?;
                     }
@@ -465,9 +1152,15 @@ impl<'a> State<'a> {
                 continue;
             }
 
-            tracing::trace!(url = ?url.try_to_string()?, "build plain source");
+            let mut build = Build::from_file(url.clone());
 
-            let mut build = Build::from_file();
+            if let Some(cached) = previous.take_fresh(url, false, &dirty, &mut build.parsed) {
+                tracing::trace!(url = ?url.try_to_string()?, "reusing plain source build");
+                builds.builds.try_insert(url.clone(), cached)?;
+                continue;
+            }
+
+            tracing::trace!(url = ?url.try_to_string()?, "build plain source");
 
             let input = match url.to_file_path() {
                 Ok(path) => Source::with_path(url, source.try_to_string()?, path)?,
@@ -478,11 +1171,9 @@ impl<'a> State<'a> {
             script_results.try_push(self.build_scripts(build, None)?)?;
         }
 
-        // We need to pupulate diagnostics for everything we know about, in
-        // order to clear errors which might've previously been set.
-        for url in self.workspace.removed.drain(..) {
-            reporter.ensure(&url);
-        }
+        // Diagnostics for the workspace manifest are always recomputed, since
+        // loading the manifest is cheap.
+        let mut reporter = Reporter::default();
 
         for (diagnostics, mut build) in workspace_results {
             build.populate(&mut reporter)?;
@@ -490,8 +1181,9 @@ impl<'a> State<'a> {
         }
 
         for (diagnostics, mut build, source_visitor, doc_visitor, unit) in script_results {
-            build.populate(&mut reporter)?;
-            self.emit_scripts(diagnostics, &build, &mut reporter)?;
+            let mut build_reporter = Reporter::default();
+            build.populate(&mut build_reporter)?;
+            self.emit_scripts(diagnostics, &build, &mut build_reporter)?;
 
             let sources = Arc::new(build.sources);
             let doc_visitor = Arc::new(doc_visitor);
@@ -507,6 +1199,7 @@ impl<'a> State<'a> {
 
                 source.index = value;
                 source.build_sources = Some(sources.clone());
+                source.source_id = Some(source_id);
 
                 if let Ok(unit) = &unit {
                     source.unit = Some(unit.try_clone()?);
@@ -514,29 +1207,81 @@ impl<'a> State<'a> {
 
                 source.docs = Some(doc_visitor.clone());
             }
+
+            let mut urls = build.id_to_url.into_values().collect::<HashSet<_>>();
+            urls.insert(build.url.clone());
+
+            let cached = CachedBuild {
+                workspace: build.workspace,
+                urls,
+                diagnostics: build_reporter.by_url,
+                parsed: build.parsed,
+            };
+
+            builds.builds.try_insert(build.url, cached)?;
         }
 
-        for (url, diagnostics) in reporter.by_url {
-            tracing::info!(
-                url = ?url.try_to_string()?,
-                diagnostics = diagnostics.len(),
-                "publishing diagnostics"
-            );
+        for build in builds.builds.values() {
+            for (url, diagnostics) in &build.diagnostics {
+                let entry = reporter.entry(url);
 
-            let diagnostics = lsp::PublishDiagnosticsParams {
-                uri: url.clone(),
-                diagnostics: diagnostics.into_std(),
-                version: None,
+                for diagnostic in diagnostics {
+                    entry.try_push(diagnostic.clone())?;
+                }
+            }
+        }
+
+        self.builds = builds;
+
+        // Sources which no longer have any diagnostics reported for them have
+        // to be cleared, in case they were previously set.
+        for url in self.published.keys() {
+            reporter.ensure(url);
+        }
+
+        let mut published = BTreeMap::new();
+
+        for (url, diagnostics) in reporter.by_url {
+            let unchanged = match self.published.get(&url) {
+                Some(previous) => *previous == diagnostics,
+                None => diagnostics.is_empty(),
             };
 
-            self.output
-                .notification::<lsp::notification::PublishDiagnostics>(diagnostics)
-                .await?;
+            if !unchanged {
+                tracing::info!(
+                    url = ?url.try_to_string()?,
+                    diagnostics = diagnostics.len(),
+                    "publishing diagnostics"
+                );
+
+                let params = lsp::PublishDiagnosticsParams {
+                    uri: url.clone(),
+                    diagnostics: diagnostics.iter().cloned().collect(),
+                    version: None,
+                };
+
+                self.output
+                    .notification::<lsp::notification::PublishDiagnostics>(params)
+                    .await?;
+            }
+
+            if !diagnostics.is_empty() {
+                published.insert(url, diagnostics);
+            }
         }
 
+        self.published = published;
         Ok(())
     }
 
+    /// Test if the workspace manifest is among the modified sources.
+    fn is_manifest_dirty(&self, dirty: &HashSet<Url>) -> bool {
+        match &self.workspace.manifest_path {
+            Some((url, _)) => dirty.contains(url),
+            None => false,
+        }
+    }
+
     /// Try to load workspace.
     fn load_workspace(
         &self,
@@ -586,7 +1331,7 @@ impl<'a> State<'a> {
                 },
             };
 
-            let mut build = Build::from_workspace();
+            let mut build = Build::from_workspace(url.clone());
 
             build
                 .sources
@@ -628,6 +1373,7 @@ impl<'a> State<'a> {
             .with_visitor(&mut doc_visitor)?
             .with_visitor(&mut source_visitor)?
             .with_source_loader(&mut source_loader)
+            .with_parse_cache(&mut build.parsed)
             .build();
 
         if let Some(built) = built {
@@ -714,6 +1460,9 @@ impl<'a> State<'a> {
                         report_without_span(build, reporter, f.source_id(), e, to_error)?;
                     }
                 },
+                Diagnostic::Warning(e) if e.is_denied() => {
+                    self.report(build, reporter, e.source_id(), e, to_error)?;
+                }
                 Diagnostic::Warning(e) => {
                     self.report(build, reporter, e.source_id(), e, to_warning)?;
                 }
@@ -761,8 +1510,11 @@ pub(super) struct Workspace {
     pub(super) manifest_path: Option<(Url, PathBuf)>,
     /// Sources that might be modified.
     sources: HashMap<Url, ServerSource>,
-    /// A source that has been removed.
-    removed: Vec<Url>,
+    /// Sources which have been opened, modified or closed since the last
+    /// rebuild.
+    dirty: HashSet<Url>,
+    /// Indicates that nothing from a previous build should be reused.
+    invalidated: bool,
 }
 
 impl Workspace {
@@ -777,11 +1529,13 @@ impl Workspace {
             content: Rope::from_str(text.as_str()),
             index: Default::default(),
             build_sources: None,
+            source_id: None,
             language,
             unit: None,
             docs: None,
         };
 
+        self.dirty.insert(url.clone());
         self.sources.try_insert(url, source)
     }
 
@@ -790,19 +1544,50 @@ impl Workspace {
         self.sources.get(url)
     }
 
-    /// Get the mutable source at the given url.
-    pub(super) fn get_mut(&mut self, url: &Url) -> Option<&mut ServerSource> {
-        self.sources.get_mut(url)
+    /// Modify the given lsp range in the source at the given url, marking it
+    /// as modified.
+    ///
+    /// Returns `false` if there is no source at the given url.
+    pub(super) fn modify_lsp_range(
+        &mut self,
+        url: &Url,
+        encoding: &StateEncoding,
+        range: lsp::Range,
+        content: &str,
+    ) -> Result<bool> {
+        let Some(source) = self.sources.get_mut(url) else {
+            return Ok(false);
+        };
+
+        source.modify_lsp_range(encoding, range, content)?;
+        self.dirty.insert(url.clone());
+        Ok(true)
     }
 
     /// Remove the given url as a source.
     pub(super) fn remove(&mut self, url: &Url) -> Result<()> {
         if self.sources.remove(url).is_some() {
-            self.removed.try_push(url.clone())?;
+            self.dirty.insert(url.clone());
         }
 
         Ok(())
     }
+
+    /// Mark every build as stale, such as when files might have been modified
+    /// on disk.
+    pub(super) fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Take the set of sources modified since this was last called.
+    pub(super) fn take_dirty(&mut self) -> HashSet<Url> {
+        take(&mut self.dirty)
+    }
+
+    /// Take the invalidation flag.
+    fn take_invalidated(&mut self) -> bool {
+        take(&mut self.invalidated)
+    }
 }
 
 /// A single open source.
@@ -814,6 +1599,8 @@ pub(super) struct ServerSource {
     /// Loaded Rune sources for this source file. Will be present after the
     /// source file has been built.
     build_sources: Option<Arc<Sources>>,
+    /// The id of this source in `build_sources`.
+    source_id: Option<SourceId>,
     /// The language of the source.
     language: Language,
     /// The compiled unit
@@ -823,20 +1610,64 @@ pub(super) struct ServerSource {
 }
 
 impl ServerSource {
-    /// Find the definition at the given span.
-    pub(super) fn find_definition_at(&self, span: Span) -> Option<&Definition> {
-        let (found_span, definition) = self.index.definitions.range(..=span).next_back()?;
+    /// Find the definition at the given span, and the span of the reference
+    /// to it.
+    pub(super) fn find_definition_at(&self, span: Span) -> Option<(Span, &Definition)> {
+        // Include references starting at the span, which sort after it.
+        let end = Span::new(span.start, u32::MAX);
+        let (found_span, definition) = self.index.definitions.range(..=end).next_back()?;
 
         if span.start >= found_span.start && span.end <= found_span.end {
             tracing::trace!("found {:?}", definition);
-            return Some(definition);
+            return Some((*found_span, definition));
+        }
+
+        None
+    }
+
+    /// Find the item or variable declared in this source whose name is at the
+    /// given span, and the span of the name.
+    pub(super) fn find_declaration_at(&self, span: Span) -> Option<(Span, &Definition)> {
+        let source = self.build_source()?;
+
+        let symbols = self
+            .index
+            .symbols
+            .iter()
+            .map(|def| (def.source.span(), def));
+
+        let variables = self
+            .index
+            .definitions
+            .iter()
+            .filter(|(_, def)| matches!(def.kind, DefinitionKind::Local))
+            .map(|(span, def)| (*span, def));
+
+        for (at, def) in symbols.chain(variables) {
+            let Some(name) = def.name(source, at) else {
+                continue;
+            };
+
+            let Some(found) = name_span(source, def.source.span(), name, def.name_at()) else {
+                continue;
+            };
+
+            if span.start >= found.start && span.end <= found.end {
+                return Some((found, def));
+            }
         }
 
         None
     }
 
+    /// Get the compiled source corresponding to this source, as it was when
+    /// it was last built.
+    pub(super) fn build_source(&self) -> Option<&Source> {
+        self.build_sources.as_ref()?.get(self.source_id?)
+    }
+
     /// Modify the given lsp range in the file.
-    pub(super) fn modify_lsp_range(
+    fn modify_lsp_range(
         &mut self,
         encoding: &StateEncoding,
         range: lsp::Range,
@@ -954,10 +1785,256 @@ where
     })
 }
 
+/// A definition resolved from a position in a source.
+struct Resolved<'a> {
+    /// The source the position is in.
+    source: &'a ServerSource,
+    /// The span of the name at the position.
+    span: Span,
+    /// The definition being referenced or declared.
+    def: &'a Definition,
+    /// The name of the definition.
+    name: &'a str,
+}
+
+/// Test if `child` is an associated item or variant of `parent`.
+fn is_parent(parent: &Definition, child: &Definition) -> bool {
+    let (Some(parent), Some(child)) = (&parent.meta, &child.meta) else {
+        return false;
+    };
+
+    child.item.parent() == Some(&*parent.item)
+}
+
+/// Get the location which identifies a definition, which is the url of the
+/// source it is declared in and the span of the declaration.
+///
+/// Modules are not identified, since references to them can't be tracked.
+fn definition_key(uri: &Url, def: &Definition) -> Result<Option<(Url, Span)>> {
+    if matches!(def.kind, DefinitionKind::Module) {
+        return Ok(None);
+    }
+
+    let url = match def.source.path() {
+        Some(path) => crate::languageserver::url::from_file_path(path)?,
+        None => uri.clone(),
+    };
+
+    Ok(Some((url, def.source.span())))
+}
+
+/// Write the signature of the given item as it would be declared.
+fn write_signature(
+    o: &mut String,
+    source: &ServerSource,
+    kind: DefinitionKind,
+    meta: &DefinitionMeta,
+) -> alloc::Result<()> {
+    let item = &meta.item;
+
+    match kind {
+        DefinitionKind::Function | DefinitionKind::AssociatedFunction => {
+            let signature = source
+                .unit
+                .as_ref()
+                .and_then(|unit| unit.debug_info())
+                .and_then(|debug| debug.functions.get(&meta.hash));
+
+            match signature {
+                Some(signature) => write!(o, "fn {signature}")?,
+                None => write!(o, "fn {item}")?,
+            }
+        }
+        DefinitionKind::EmptyStruct | DefinitionKind::TupleStruct | DefinitionKind::Struct => {
+            write!(o, "struct {item}")?;
+            write_fields(o, source, meta)?;
+        }
+        DefinitionKind::UnitVariant
+        | DefinitionKind::TupleVariant
+        | DefinitionKind::StructVariant => {
+            write!(o, "{item}")?;
+            write_fields(o, source, meta)?;
+        }
+        DefinitionKind::Enum => write!(o, "enum {item}")?,
+        DefinitionKind::Const => write!(o, "const {item}")?,
+        DefinitionKind::Trait => write!(o, "trait {item}")?,
+        DefinitionKind::Module => write!(o, "mod {item}")?,
+        DefinitionKind::Local => write!(o, "let {item}")?,
+    }
+
+    Ok(())
+}
+
+/// Write the fields of a struct or variant, if they are known.
+fn write_fields(o: &mut String, source: &ServerSource, meta: &DefinitionMeta) -> alloc::Result<()> {
+    let Some(data) = source.get_docs_by_hash(meta.hash) else {
+        return Ok(());
+    };
+
+    let Some(meta::Kind::Struct { fields, .. }) = &data.kind else {
+        return Ok(());
+    };
+
+    match fields {
+        meta::Fields::Named(named) => {
+            write!(o, " {{ ")?;
+
+            for (n, field) in named.fields.iter().enumerate() {
+                if n > 0 {
+                    write!(o, ", ")?;
+                }
+
+                write!(o, "{}", field.name)?;
+            }
+
+            write!(o, " }}")?;
+        }
+        meta::Fields::Unnamed(count) => {
+            write!(o, "(")?;
+
+            for n in 0..*count {
+                if n > 0 {
+                    write!(o, ", ")?;
+                }
+
+                write!(o, "_")?;
+            }
+
+            write!(o, ")")?;
+        }
+        meta::Fields::Empty => {}
+    }
+
+    Ok(())
+}
+
+/// Convert a definition kind into the kind of symbol it corresponds to.
+fn symbol_kind(kind: DefinitionKind) -> lsp::SymbolKind {
+    match kind {
+        DefinitionKind::EmptyStruct | DefinitionKind::TupleStruct | DefinitionKind::Struct => {
+            lsp::SymbolKind::STRUCT
+        }
+        DefinitionKind::UnitVariant
+        | DefinitionKind::TupleVariant
+        | DefinitionKind::StructVariant => lsp::SymbolKind::ENUM_MEMBER,
+        DefinitionKind::Enum => lsp::SymbolKind::ENUM,
+        DefinitionKind::Function => lsp::SymbolKind::FUNCTION,
+        DefinitionKind::AssociatedFunction => lsp::SymbolKind::METHOD,
+        DefinitionKind::Const => lsp::SymbolKind::CONSTANT,
+        DefinitionKind::Trait => lsp::SymbolKind::INTERFACE,
+        DefinitionKind::Local => lsp::SymbolKind::VARIABLE,
+        DefinitionKind::Module => lsp::SymbolKind::MODULE,
+    }
+}
+
+/// Pop the innermost symbol of a stack of nested symbols, adding it to its
+/// parent or to the roots if it has none.
+fn pop_symbol(
+    stack: &mut Vec<(Span, lsp::DocumentSymbol)>,
+    roots: &mut rust_alloc::vec::Vec<lsp::DocumentSymbol>,
+) {
+    let Some((_, symbol)) = stack.pop() else {
+        return;
+    };
+
+    match stack.last_mut() {
+        Some((_, parent)) => parent
+            .children
+            .get_or_insert_with(Default::default)
+            .push(symbol),
+        None => roots.push(symbol),
+    }
+}
+
+/// How a name is expected to appear in the source covered by a span.
+#[derive(Clone, Copy)]
+enum NameAt {
+    /// The span covers the declaration of an item, and the name either starts
+    /// the span or follows the keyword introducing the item.
+    Item,
+    /// The span covers the declaration of a variable, and the name is its
+    /// first occurrence.
+    Variable,
+    /// The span covers a path, and the name is its last occurrence in it.
+    Path,
+}
+
+/// Find the span of the given name in the source covered by `span`.
+fn name_span(source: &Source, span: Span, name: &str, at: NameAt) -> Option<Span> {
+    const KEYWORDS: &[&str] = &["fn", "struct", "enum", "const", "mod", "trait"];
+
+    let text = source.get(span.range())?;
+
+    let is_ident = |c: char| c == '_' || c.is_alphanumeric();
+
+    let mut found = None;
+
+    for (n, _) in text.match_indices(name) {
+        let before = &text[..n];
+        let after = &text[n + name.len()..];
+
+        if before.ends_with(is_ident) || after.starts_with(is_ident) {
+            continue;
+        }
+
+        match at {
+            NameAt::Item => {
+                let before = before.trim_end();
+
+                let is_keyword = |k: &&str| {
+                    before
+                        .strip_suffix(*k)
+                        .is_some_and(|rest| !rest.ends_with(is_ident))
+                };
+
+                if before.is_empty() || KEYWORDS.iter().any(is_keyword) {
+                    found = Some(n);
+                    break;
+                }
+            }
+            NameAt::Variable => {
+                found = Some(n);
+                break;
+            }
+            NameAt::Path => {
+                // Only consider the leading path, and not any arguments or
+                // bodies following it.
+                if before.contains(|c: char| !(is_ident(c) || c == ':' || c.is_whitespace())) {
+                    break;
+                }
+
+                found = Some(n);
+            }
+        }
+    }
+
+    let start = span.start.into_usize() + found?;
+    Some(Span::new(start, start + name.len()))
+}
+
+/// Test if the given name is a valid identifier which can be used when
+/// renaming an item or variable.
+pub(super) fn is_valid_name(name: &str) -> bool {
+    let mut it = name.chars();
+
+    let Some(first) = it.next() else {
+        return false;
+    };
+
+    (first == '_' || unicode_ident::is_xid_start(first))
+        && it.all(unicode_ident::is_xid_continue)
+        && crate::ast::Kind::from_keyword(name).is_none()
+}
+
 #[derive(Default)]
 pub(super) struct Index {
     /// Spans mapping to their corresponding definitions.
     definitions: BTreeMap<Span, Definition>,
+    /// Items declared in the source.
+    symbols: Vec<Definition>,
+    /// Paths which resolve to functions in the context, mapping to their
+    /// hashes.
+    natives: BTreeMap<Span, Hash>,
 }
 
 /// A definition source.
@@ -1002,6 +2079,38 @@ pub(super) struct Definition {
     pub(super) kind: DefinitionKind,
     /// The id of the source id the definition corresponds to.
     pub(super) source: DefinitionSource,
+    /// The item being defined, unless this is a local variable or module.
+    pub(super) meta: Option<DefinitionMeta>,
+}
+
+impl Definition {
+    /// Get the name of the definition.
+    ///
+    /// Local variables don't record their name, so it is read from the given
+    /// span in the source, which is expected to be a reference to it.
+    fn name<'a>(&'a self, source: &'a Source, span: Span) -> Option<&'a str> {
+        match &self.meta {
+            Some(meta) => meta.item.base_name(),
+            None => source.get(span.range()),
+        }
+    }
+
+    /// How the name appears in the span the definition is declared at.
+    fn name_at(&self) -> NameAt {
+        match self.meta {
+            Some(..) => NameAt::Item,
+            None => NameAt::Variable,
+        }
+    }
+}
+
+/// The item a definition corresponds to.
+#[derive(Debug, TryClone)]
+pub(super) struct DefinitionMeta {
+    /// The hash of the item.
+    pub(super) hash: Hash,
+    /// The item.
+    pub(super) item: ItemBuf,
 }
 
 #[derive(Debug, TryClone, Clone, Copy)]
@@ -1025,6 +2134,10 @@ pub(super) enum DefinitionKind {
     Function,
     /// An associated function.
     AssociatedFunction,
+    /// A constant.
+    Const,
+    /// A trait.
+    Trait,
     /// A local variable.
     Local,
     /// A module that can be jumped to.
@@ -1044,53 +2157,69 @@ impl Visitor {
 }
 
 impl CompileVisitor for Visitor {
+    fn register_meta(&mut self, meta: MetaRef<'_>) -> Result<(), MetaError> {
+        let Some(source) = meta.source else {
+            return Ok(());
+        };
+
+        let kind = match meta.kind {
+            meta::Kind::Module => DefinitionKind::Module,
+            kind => match definition_kind(kind) {
+                Some(kind) => kind,
+                None => return Ok(()),
+            },
+        };
+
+        if meta.item.base_name().is_none() {
+            return Ok(());
+        }
+
+        let index = self
+            .indexes
+            .entry(source.location.source_id)
+            .or_try_default()?;
+
+        let exists = index.symbols.iter().any(|symbol| {
+            symbol.meta.as_ref().is_some_and(|m| m.hash == meta.hash)
+                && symbol.source.span() == source.location.span
+        });
+
+        if !exists {
+            index.symbols.try_push(Definition {
+                kind,
+                source: DefinitionSource::SourceMeta(source.try_clone()?),
+                meta: Some(DefinitionMeta {
+                    hash: meta.hash,
+                    item: meta.item.try_to_owned()?,
+                }),
+            })?;
+        }
+
+        Ok(())
+    }
+
     fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) -> Result<(), MetaError> {
         let Some(source) = meta.source else {
+            if meta.context && matches!(meta.kind, meta::Kind::Function { .. }) {
+                let location = location.location();
+                let index = self.indexes.entry(location.source_id).or_try_default()?;
+                index.natives.insert(location.span, meta.hash);
+            }
+
             return Ok(());
         };
 
-        let kind = match &meta.kind {
-            meta::Kind::Struct {
-                fields: meta::Fields::Empty,
-                enum_hash: Hash::EMPTY,
-                ..
-            } => DefinitionKind::EmptyStruct,
-            meta::Kind::Struct {
-                fields: meta::Fields::Unnamed(..),
-                enum_hash: Hash::EMPTY,
-                ..
-            } => DefinitionKind::TupleStruct,
-            meta::Kind::Struct {
-                fields: meta::Fields::Named(..),
-                enum_hash: Hash::EMPTY,
-                ..
-            } => DefinitionKind::Struct,
-            meta::Kind::Struct {
-                fields: meta::Fields::Empty,
-                ..
-            } => DefinitionKind::UnitVariant,
-            meta::Kind::Struct {
-                fields: meta::Fields::Unnamed(..),
-                ..
-            } => DefinitionKind::TupleVariant,
-            meta::Kind::Struct {
-                fields: meta::Fields::Named(..),
-                ..
-            } => DefinitionKind::StructVariant,
-            meta::Kind::Enum { .. } => DefinitionKind::Enum,
-            meta::Kind::Function {
-                associated: None, ..
-            } => DefinitionKind::Function,
-            meta::Kind::Function {
-                associated: Some(..),
-                ..
-            } => DefinitionKind::AssociatedFunction,
-            _ => return Ok(()),
+        let Some(kind) = definition_kind(meta.kind) else {
+            return Ok(());
         };
 
         let definition = Definition {
             kind,
             source: DefinitionSource::SourceMeta(source.try_clone()?),
+            meta: Some(DefinitionMeta {
+                hash: meta.hash,
+                item: meta.item.try_to_owned()?,
+            }),
         };
 
         let location = location.location();
@@ -1113,6 +2242,7 @@ impl CompileVisitor for Visitor {
         let definition = Definition {
             kind: DefinitionKind::Local,
             source: DefinitionSource::Location(Location::new(source_id, var_span.span())),
+            meta: None,
         };
 
         let index = self.indexes.entry(source_id).or_try_default()?;
@@ -1130,6 +2260,7 @@ impl CompileVisitor for Visitor {
         let definition = Definition {
             kind: DefinitionKind::Module,
             source: DefinitionSource::Source(location.source_id),
+            meta: None,
         };
 
         let index = self.indexes.entry(location.source_id).or_try_default()?;
@@ -1142,6 +2273,54 @@ impl CompileVisitor for Visitor {
     }
 }
 
+/// Get the kind of definition corresponding to the given meta, if it is one
+/// which can be navigated to.
+fn definition_kind(kind: &meta::Kind) -> Option<DefinitionKind> {
+    let kind = match kind {
+        meta::Kind::Struct {
+            fields: meta::Fields::Empty,
+            enum_hash: Hash::EMPTY,
+            ..
+        } => DefinitionKind::EmptyStruct,
+        meta::Kind::Struct {
+            fields: meta::Fields::Unnamed(..),
+            enum_hash: Hash::EMPTY,
+            ..
+        } => DefinitionKind::TupleStruct,
+        meta::Kind::Struct {
+            fields: meta::Fields::Named(..),
+            enum_hash: Hash::EMPTY,
+            ..
+        } => DefinitionKind::Struct,
+        meta::Kind::Struct {
+            fields: meta::Fields::Empty,
+            ..
+        } => DefinitionKind::UnitVariant,
+        meta::Kind::Struct {
+            fields: meta::Fields::Unnamed(..),
+            ..
+        } => DefinitionKind::TupleVariant,
+        meta::Kind::Struct {
+            fields: meta::Fields::Named(..),
+            ..
+        } => DefinitionKind::StructVariant,
+        meta::Kind::Enum { .. } => DefinitionKind::Enum,
+        meta::Kind::Function {
+            associated: None, ..
+        } => DefinitionKind::Function,
+        meta::Kind::Function {
+            associated: Some(..),
+            ..
+        } => DefinitionKind::AssociatedFunction,
+        meta::Kind::ConstFn => DefinitionKind::Function,
+        meta::Kind::Const => DefinitionKind::Const,
+        meta::Kind::Trait => DefinitionKind::Trait,
+        _ => return None,
+    };
+
+    Some(kind)
+}
+
 struct ScriptSourceLoader<'a> {
     sources: &'a HashMap<Url, ServerSource>,
     base: compile::FileSourceLoader,
@@ -10,3 +10,320 @@ fn test_code() {
     assert_eq!(code, Code::MethodNotFound);
     assert_eq!(serde_json::to_string(&code).unwrap(), "-32601");
 }
+
+#[test]
+fn test_valid_name() {
+    use super::state::is_valid_name;
+
+    assert!(is_valid_name("foo"));
+    assert!(is_valid_name("_foo2"));
+    assert!(is_valid_name("Point"));
+    assert!(!is_valid_name(""));
+    assert!(!is_valid_name("2foo"));
+    assert!(!is_valid_name("foo bar"));
+    assert!(!is_valid_name("foo::bar"));
+    assert!(!is_valid_name("fn"));
+}
+
+#[test]
+fn test_enclosing_call() {
+    use super::signature::{enclosing_call, tokens};
+
+    let call = |source: &str| {
+        let tokens = tokens(source).unwrap();
+        enclosing_call(source, &tokens)
+            .unwrap()
+            .map(|(path, n)| (path.join("::"), n))
+    };
+
+    assert_eq!(call("foo("), Some(("foo".into(), 0)));
+    assert_eq!(call("std::i64::max(1, "), Some(("std::i64::max".into(), 1)));
+    assert_eq!(call("foo(bar(1), [2, 3], "), Some(("foo".into(), 2)));
+    assert_eq!(call("foo(bar(1, "), Some(("bar".into(), 1)));
+    assert_eq!(call("foo(1)"), None);
+    assert_eq!(call("value.foo(1, "), None);
+}
+
+#[test]
+fn test_dirty_sources() {
+    use super::state::{StateEncoding, Workspace};
+    use super::Language;
+
+    let a = lsp::Url::parse("file:///a.rn").unwrap();
+    let b = lsp::Url::parse("file:///b.rn").unwrap();
+
+    let mut workspace = Workspace::default();
+    workspace
+        .insert_source(
+            a.clone(),
+            "pub fn main() {}".try_into().unwrap(),
+            Language::Rune,
+        )
+        .unwrap();
+    workspace
+        .insert_source(
+            b.clone(),
+            "pub fn b() {}".try_into().unwrap(),
+            Language::Rune,
+        )
+        .unwrap();
+
+    let dirty = workspace.take_dirty();
+    assert!(dirty.contains(&a) && dirty.contains(&b));
+    assert!(workspace.take_dirty().is_empty());
+
+    assert!(workspace.get(&b).is_some());
+    assert!(workspace.take_dirty().is_empty());
+
+    let range = lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 8));
+
+    assert!(workspace
+        .modify_lsp_range(&b, &StateEncoding::Utf8, range, "c")
+        .unwrap());
+    let dirty = workspace.take_dirty();
+    assert!(dirty.len() == 1 && dirty.contains(&b));
+
+    let missing = lsp::Url::parse("file:///missing.rn").unwrap();
+    assert!(!workspace
+        .modify_lsp_range(&missing, &StateEncoding::Utf8, range, "c")
+        .unwrap());
+    workspace.remove(&missing).unwrap();
+    assert!(workspace.take_dirty().is_empty());
+
+    workspace.remove(&a).unwrap();
+    let dirty = workspace.take_dirty();
+    assert!(dirty.len() == 1 && dirty.contains(&a));
+}
+
+#[test]
+fn test_build_cache() {
+    use super::state::{BuildCache, CachedBuild};
+    use crate::compile::ParseCache;
+    use crate::{ast, SourceId};
+
+    const MAIN: &str = "pub fn main() { 42 }";
+
+    let main = lsp::Url::parse("file:///main.rn").unwrap();
+    let lib = lsp::Url::parse("file:///lib.rn").unwrap();
+    let other = lsp::Url::parse("file:///other.rn").unwrap();
+
+    let cache = || {
+        let mut cache = BuildCache::default();
+        let mut parsed = ParseCache::default();
+
+        parsed.parse::<ast::File>(SourceId::new(0), MAIN).unwrap();
+
+        let build = CachedBuild {
+            workspace: false,
+            urls: [main.clone(), lib.clone()].into_iter().collect(),
+            diagnostics: Default::default(),
+            parsed,
+        };
+
+        cache.builds.try_insert(main.clone(), build).unwrap();
+        cache
+    };
+
+    let mut parsed = ParseCache::default();
+
+    let dirty = [other.clone()].into_iter().collect();
+    assert!(cache()
+        .take_fresh(&main, false, &dirty, &mut parsed)
+        .is_some());
+    assert!(cache()
+        .take_fresh(&main, true, &dirty, &mut parsed)
+        .is_none());
+
+    let dirty = [lib.clone()].into_iter().collect();
+    assert!(cache()
+        .take_fresh(&main, false, &dirty, &mut parsed)
+        .is_none());
+    assert!(cache()
+        .take_fresh(&lib, false, &Default::default(), &mut parsed)
+        .is_none());
+
+    // The sources parsed by a stale build are handed over to the next one.
+    let mut parsed = ParseCache::default();
+
+    assert!(cache()
+        .take_fresh(&main, false, &dirty, &mut parsed)
+        .is_none());
+
+    parsed.parse::<ast::File>(SourceId::new(0), MAIN).unwrap();
+    assert_eq!(parsed.hits(), 1);
+}
+
+const SOURCE: &str = r#"/// Add two numbers.
+fn add(a, b) { a + b }
+
+enum Shape { Circle(r), Square(side) }
+
+pub fn main() {
+    let shape = Shape::Circle(2);
+    add(1, add(2, 3))
+}
+"#;
+
+/// Build a workspace containing the given sources and call `f` with the
+/// resulting state.
+fn with_state<T>(
+    sources: &[(&lsp::Url, &str)],
+    f: impl FnOnce(&super::state::State<'_>) -> T,
+) -> T {
+    use super::connection::Output;
+    use super::state::State;
+    use super::Language;
+
+    let notify = tokio::sync::Notify::new();
+    let context = crate::Context::with_default_modules().unwrap();
+    let mut state = State::new(Output::sink(), &notify, context, crate::Options::default());
+
+    for (url, text) in sources {
+        state
+            .workspace
+            .insert_source((*url).clone(), (*text).try_into().unwrap(), Language::Rune)
+            .unwrap();
+    }
+
+    futures_executor::block_on(state.rebuild()).unwrap();
+    f(&state)
+}
+
+#[test]
+fn test_hover() {
+    let url = lsp::Url::parse("file:///main.rn").unwrap();
+
+    with_state(&[(&url, SOURCE)], |state| {
+        let hover = state
+            .hover(&url, lsp::Position::new(7, 5))
+            .unwrap()
+            .unwrap();
+
+        let lsp::HoverContents::Markup(contents) = hover.contents else {
+            panic!("expected markup");
+        };
+
+        assert_eq!(
+            contents.value,
+            "```rune\nfn add(a, b)\n```\n\n Add two numbers."
+        );
+        assert_eq!(
+            hover.range,
+            Some(lsp::Range::new(
+                lsp::Position::new(7, 4),
+                lsp::Position::new(7, 7)
+            ))
+        );
+
+        assert!(state
+            .hover(&url, lsp::Position::new(2, 0))
+            .unwrap()
+            .is_none());
+    });
+}
+
+#[test]
+fn test_references_and_rename() {
+    let url = lsp::Url::parse("file:///main.rn").unwrap();
+
+    let range = |line, start, end| {
+        lsp::Range::new(
+            lsp::Position::new(line, start),
+            lsp::Position::new(line, end),
+        )
+    };
+
+    with_state(&[(&url, SOURCE)], |state| {
+        let references = state
+            .references(&url, lsp::Position::new(7, 5), true)
+            .unwrap()
+            .unwrap();
+
+        let ranges = references
+            .iter()
+            .map(|location| location.range)
+            .collect::<std::vec::Vec<_>>();
+
+        assert_eq!(ranges, [range(1, 3, 6), range(7, 4, 7), range(7, 11, 14)]);
+        assert!(references.iter().all(|location| location.uri == url));
+
+        let references = state
+            .references(&url, lsp::Position::new(7, 5), false)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(references.len(), 2);
+
+        let edit = state
+            .rename(&url, lsp::Position::new(1, 4), "sum")
+            .unwrap()
+            .unwrap();
+
+        let changes = edit.changes.unwrap();
+        let edits = &changes[&url];
+
+        assert_eq!(edits.len(), 3);
+        assert!(edits.iter().all(|edit| edit.new_text == "sum"));
+    });
+}
+
+#[test]
+fn test_document_symbols() {
+    let url = lsp::Url::parse("file:///main.rn").unwrap();
+
+    fn names(symbols: &[lsp::DocumentSymbol]) -> std::vec::Vec<std::string::String> {
+        symbols
+            .iter()
+            .map(|symbol| match &symbol.children {
+                Some(children) => std::format!("{}({})", symbol.name, names(children).join(", ")),
+                None => symbol.name.clone(),
+            })
+            .collect()
+    }
+
+    with_state(&[(&url, SOURCE)], |state| {
+        let symbols = state.document_symbols(&url).unwrap().unwrap();
+        assert_eq!(names(&symbols), ["add", "Shape(Circle, Square)", "main"]);
+        assert_eq!(symbols[0].kind, lsp::SymbolKind::FUNCTION);
+        assert_eq!(symbols[1].kind, lsp::SymbolKind::ENUM);
+    });
+}
+
+#[test]
+fn test_inlay_hints() {
+    let url = lsp::Url::parse("file:///main.rn").unwrap();
+
+    let source = r#"fn area(width, height) { width * height }
+
+pub fn main() {
+    let height = 3;
+    let size = area(2, height);
+    let text = std::string::String::from("x");
+}
+"#;
+
+    with_state(&[(&url, source)], |state| {
+        let range = lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(7, 0));
+        let hints = state.inlay_hints(&url, range).unwrap().unwrap();
+
+        let hints = hints
+            .iter()
+            .map(|hint| {
+                let lsp::InlayHintLabel::String(label) = &hint.label else {
+                    panic!("expected string label");
+                };
+
+                (hint.position.line, hint.position.character, label.as_str())
+            })
+            .collect::<std::vec::Vec<_>>();
+
+        // The argument which is named the same as its parameter is not
+        // hinted.
+        assert_eq!(hints[0], (4, 20, "width:"));
+        assert!(!hints.iter().any(|(_, _, label)| *label == "height:"));
+        assert!(hints.contains(&(5, 12, ": String")));
+
+        let range = lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(1, 0));
+        assert!(state.inlay_hints(&url, range).unwrap().unwrap().is_empty());
+    });
+}
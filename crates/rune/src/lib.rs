@@ -956,6 +956,26 @@ rune_macros::binding! {
     #[type_of]
     impl ::std::u64 for usize;
     #[type_of]
+    impl ::std::u8 for crate::runtime::Fixed<u8>;
+    #[type_of]
+    impl ::std::u16 for crate::runtime::Fixed<u16>;
+    #[type_of]
+    impl ::std::u32 for crate::runtime::Fixed<u32>;
+    #[type_of]
+    impl ::std::u64 for crate::runtime::Fixed<u64>;
+    #[type_of]
+    impl ::std::u128 for crate::runtime::Fixed<u128>;
+    #[type_of]
+    impl ::std::i8 for crate::runtime::Fixed<i8>;
+    #[type_of]
+    impl ::std::i16 for crate::runtime::Fixed<i16>;
+    #[type_of]
+    impl ::std::i32 for crate::runtime::Fixed<i32>;
+    #[type_of]
+    impl ::std::i64 for crate::runtime::Fixed<i64>;
+    #[type_of]
+    impl ::std::i128 for crate::runtime::Fixed<i128>;
+    #[type_of]
     impl ::std::f64 for f32;
     #[type_of]
     impl ::std::f64 for f64;
@@ -1,5 +1,6 @@
 use core::cmp::Ordering;
 
+use crate::runtime::Fixed;
 use crate::{ContextError, Hash};
 
 use super::Module;
@@ -15,6 +16,16 @@ pub trait InstallWith {
 
 impl InstallWith for i64 {}
 impl InstallWith for u64 {}
+impl InstallWith for Fixed<u8> {}
+impl InstallWith for Fixed<u16> {}
+impl InstallWith for Fixed<u32> {}
+impl InstallWith for Fixed<u64> {}
+impl InstallWith for Fixed<u128> {}
+impl InstallWith for Fixed<i8> {}
+impl InstallWith for Fixed<i16> {}
+impl InstallWith for Fixed<i32> {}
+impl InstallWith for Fixed<i64> {}
+impl InstallWith for Fixed<i128> {}
 impl InstallWith for f64 {}
 impl InstallWith for char {}
 impl InstallWith for bool {}
@@ -0,0 +1,536 @@
+use core::cmp::Ordering;
+use core::iter;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::runtime::{
+    EnvProtocolCaller, Formatter, Iterator, ProtocolCaller, RawAnyGuard, Ref, Value, Vec, VmError,
+};
+use crate::{Any, ContextError, Module};
+
+/// A priority queue implemented with a binary heap.
+#[rune::module(::std::collections::binary_heap)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.ty::<BinaryHeap>()?;
+    m.function_meta(BinaryHeap::new__meta)?;
+    m.function_meta(BinaryHeap::with_capacity__meta)?;
+    m.function_meta(BinaryHeap::len__meta)?;
+    m.function_meta(BinaryHeap::is_empty__meta)?;
+    m.function_meta(BinaryHeap::push__meta)?;
+    m.function_meta(BinaryHeap::pop__meta)?;
+    m.function_meta(BinaryHeap::peek__meta)?;
+    m.function_meta(BinaryHeap::clear__meta)?;
+    m.function_meta(BinaryHeap::iter__meta)?;
+    m.function_meta(BinaryHeap::into_iter__meta)?;
+    m.function_meta(BinaryHeap::into_vec__meta)?;
+    m.function_meta(BinaryHeap::into_sorted_vec__meta)?;
+    m.function_meta(BinaryHeap::from_iter__meta)?;
+    m.function_meta(BinaryHeap::extend__meta)?;
+    m.function_meta(BinaryHeap::debug_fmt__meta)?;
+
+    m.function_meta(BinaryHeap::clone__meta)?;
+    m.implement_trait::<BinaryHeap>(rune::item!(::std::clone::Clone))?;
+
+    m.ty::<Iter>()?;
+    m.function_meta(Iter::next__meta)?;
+    m.function_meta(Iter::size_hint__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Iter::next_back__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.function_meta(Iter::len__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    Ok(m)
+}
+
+/// A priority queue implemented with a binary heap.
+///
+/// This will be a max-heap, where elements are ordered using the [`CMP`]
+/// protocol. Any value which can be compared with [`Ord`] can be stored,
+/// including user-defined types which implement it.
+///
+/// It is a logic error for an item to be modified in such a way that its
+/// ordering relative to any other item changes while it is in the heap. The
+/// behavior resulting from such a logic error is not specified, but will be
+/// encapsulated to the `BinaryHeap` that observed it and will not result in
+/// undefined behavior.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::collections::BinaryHeap;
+///
+/// let heap = BinaryHeap::new();
+///
+/// heap.push(1);
+/// heap.push(5);
+/// heap.push(2);
+///
+/// assert_eq!(heap.peek(), Some(5));
+/// assert_eq!(heap.len(), 3);
+///
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Any)]
+#[rune(item = ::std::collections::binary_heap)]
+pub(crate) struct BinaryHeap {
+    data: alloc::Vec<Value>,
+}
+
+impl BinaryHeap {
+    /// Creates an empty `BinaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// heap.push(4);
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub(crate) fn new() -> Self {
+        Self {
+            data: alloc::Vec::new(),
+        }
+    }
+
+    /// Creates an empty `BinaryHeap` with at least the specified capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[rune::function(keep, path = Self::with_capacity)]
+    pub(crate) fn with_capacity(capacity: usize) -> Result<Self, VmError> {
+        Ok(Self {
+            data: alloc::Vec::try_with_capacity(capacity)?,
+        })
+    }
+
+    /// Returns the length of the binary heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 3]);
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    #[rune::function(keep)]
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the binary heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// assert!(heap.is_empty());
+    /// heap.push(3);
+    /// assert!(!heap.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Pushes an item onto the binary heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(5));
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn push(&mut self, item: Value) -> Result<(), VmError> {
+        self.data.try_push(item)?;
+        self.sift_up(self.data.len() - 1, &mut EnvProtocolCaller)
+    }
+
+    /// Removes the greatest item from the binary heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 3]);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop(&mut self) -> Result<Option<Value>, VmError> {
+        let Some(mut item) = self.data.pop() else {
+            return Ok(None);
+        };
+
+        if !self.data.is_empty() {
+            core::mem::swap(&mut item, &mut self.data[0]);
+            self.sift_down(0, self.data.len(), &mut EnvProtocolCaller)?;
+        }
+
+        Ok(Some(item))
+    }
+
+    /// Returns the greatest item in the binary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(5));
+    /// ```
+    #[rune::function(keep)]
+    fn peek(&self) -> Option<Value> {
+        self.data.first().cloned()
+    }
+
+    /// Drops all items from the binary heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 3]);
+    /// heap.clear();
+    /// assert!(heap.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 2, 3, 4]);
+    ///
+    /// let values = heap.iter().collect::<Vec>();
+    /// values.sort();
+    /// assert_eq!(values, [1, 2, 3, 4]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::iter)]
+    fn iter(this: Ref<Self>) -> Iter {
+        // SAFETY: We're holding onto the reference guard.
+        let iter = unsafe { this.data.raw_iter() };
+        let (_, guard) = Ref::into_raw(this);
+        Iter { iter, guard }
+    }
+
+    /// Consumes the `BinaryHeap` and returns the underlying vector in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 2, 3, 4, 5, 6, 7]);
+    /// let vec = heap.into_vec();
+    /// vec.sort();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[rune::function(keep)]
+    fn into_vec(self) -> Vec {
+        Vec::from(self.data)
+    }
+
+    /// Consumes the `BinaryHeap` and returns a vector in sorted (ascending)
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([4, 1, 5, 2, 3]);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3, 4, 5]);
+    /// ```
+    #[rune::function(keep)]
+    fn into_sorted_vec(mut self) -> Result<Vec, VmError> {
+        let caller = &mut EnvProtocolCaller;
+        let mut end = self.data.len();
+
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end, caller)?;
+        }
+
+        Ok(Vec::from(self.data))
+    }
+
+    /// Extend this heap from an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1]);
+    /// heap.extend([4, 2]);
+    /// assert_eq!(heap.peek(), Some(4));
+    /// assert_eq!(heap.len(), 3);
+    /// ```
+    #[rune::function(keep)]
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        let mut it = value.into_iter()?;
+
+        while let Some(item) = it.next()? {
+            self.push(item)?;
+        }
+
+        Ok(())
+    }
+
+    /// Convert a heap from a value convert into an iterator.
+    ///
+    /// The heap can be converted from anything that implements the
+    /// [`INTO_ITER`] protocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([3, 7, 5]);
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(7));
+    /// ```
+    #[rune::function(keep, path = Self::from_iter)]
+    fn from_iter(it: Iterator) -> Result<BinaryHeap, VmError> {
+        Self::from_iter_with(it)
+    }
+
+    pub(crate) fn from_iter_with(mut it: Iterator) -> Result<Self, VmError> {
+        let (cap, _) = it.size_hint()?;
+        let mut data = alloc::Vec::try_with_capacity(cap)?;
+
+        while let Some(item) = it.next()? {
+            data.try_push(item)?;
+        }
+
+        let mut heap = Self { data };
+        heap.rebuild(&mut EnvProtocolCaller)?;
+        Ok(heap)
+    }
+
+    /// Clone the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let a = BinaryHeap::from_iter([1, 2]);
+    /// let b = a.clone();
+    ///
+    /// b.push(3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone, protocol = CLONE)]
+    fn clone(this: &BinaryHeap) -> Result<BinaryHeap, VmError> {
+        Ok(Self {
+            data: this.data.try_clone()?,
+        })
+    }
+
+    /// Debug format the current heap, showing the underlying vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1]);
+    /// assert_eq!(format!("{:?}", heap), "BinaryHeap[1]");
+    /// ```
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> Result<(), VmError> {
+        self.debug_fmt_with(f, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn debug_fmt_with(
+        &self,
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        write!(f, "BinaryHeap")?;
+        Vec::debug_fmt_with(&self.data, f, caller)
+    }
+
+    /// Iterate over the heap in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([3, 1, 2]);
+    /// let total = 0;
+    ///
+    /// for value in heap {
+    ///     total += value;
+    /// }
+    ///
+    /// assert_eq!(total, 6);
+    /// ```
+    #[rune::function(keep, instance, protocol = INTO_ITER, path = Self)]
+    fn into_iter(this: Ref<Self>) -> Iter {
+        Self::iter(this)
+    }
+
+    /// Move the element at `pos` up towards the root until its parent is
+    /// greater or equal to it.
+    fn sift_up(&mut self, mut pos: usize, caller: &mut dyn ProtocolCaller) -> Result<(), VmError> {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+
+            if Value::cmp_with(&self.data[pos], &self.data[parent], caller)? != Ordering::Greater {
+                break;
+            }
+
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+
+        Ok(())
+    }
+
+    /// Move the element at `pos` down towards the leaves until both of its
+    /// children within `end` are less or equal to it.
+    fn sift_down(
+        &mut self,
+        mut pos: usize,
+        end: usize,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        loop {
+            let mut child = 2 * pos + 1;
+
+            if child >= end {
+                break;
+            }
+
+            if child + 1 < end
+                && Value::cmp_with(&self.data[child + 1], &self.data[child], caller)?
+                    == Ordering::Greater
+            {
+                child += 1;
+            }
+
+            if Value::cmp_with(&self.data[pos], &self.data[child], caller)? != Ordering::Less {
+                break;
+            }
+
+            self.data.swap(pos, child);
+            pos = child;
+        }
+
+        Ok(())
+    }
+
+    /// Restore the heap invariant over the whole underlying vector.
+    fn rebuild(&mut self, caller: &mut dyn ProtocolCaller) -> Result<(), VmError> {
+        let end = self.data.len();
+
+        for pos in (0..end / 2).rev() {
+            self.sift_down(pos, end, caller)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An iterator over the elements of a binary heap in arbitrary order.
+#[derive(Any)]
+#[rune(item = ::std::collections::binary_heap)]
+pub(crate) struct Iter {
+    iter: alloc::slice::RawIter<Value>,
+    // Drop must happen after the raw iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Iter {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*self.iter.next()?).clone()) }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*self.iter.next_back()?).clone()) }
+    }
+}
+
+impl iter::Iterator for Iter {
+    type Item = Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Iter::next(self)
+    }
+}
+
+impl iter::DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Iter::next_back(self)
+    }
+}
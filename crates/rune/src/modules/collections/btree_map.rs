@@ -0,0 +1,986 @@
+use core::cmp::Ordering;
+use core::iter;
+use core::ops::Bound;
+
+use crate as rune;
+use crate::alloc::btree_map::{self, Entry};
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::runtime::{
+    self, EnvProtocolCaller, Formatter, FromValue, Iterator, ProtocolCaller, RangeFrom, RangeFull,
+    RangeInclusive, RangeTo, RangeToInclusive, RawAnyGuard, Ref, TypeOf, Value, VmError,
+    VmErrorKind,
+};
+use crate::{Any, ContextError, Module, TypeHash};
+
+/// A dynamic ordered map.
+#[rune::module(::std::collections::btree_map)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.ty::<BTreeMap>()?;
+    m.function_meta(BTreeMap::new__meta)?;
+    m.function_meta(BTreeMap::len__meta)?;
+    m.function_meta(BTreeMap::is_empty__meta)?;
+    m.function_meta(BTreeMap::insert__meta)?;
+    m.function_meta(BTreeMap::get__meta)?;
+    m.function_meta(BTreeMap::contains_key__meta)?;
+    m.function_meta(BTreeMap::remove__meta)?;
+    m.function_meta(BTreeMap::clear__meta)?;
+    m.function_meta(BTreeMap::first_key_value__meta)?;
+    m.function_meta(BTreeMap::last_key_value__meta)?;
+    m.function_meta(BTreeMap::pop_first__meta)?;
+    m.function_meta(BTreeMap::pop_last__meta)?;
+    m.function_meta(BTreeMap::range__meta)?;
+    m.function_meta(BTreeMap::iter__meta)?;
+    m.function_meta(BTreeMap::into_iter__meta)?;
+    m.function_meta(BTreeMap::from_iter__meta)?;
+    m.function_meta(BTreeMap::keys__meta)?;
+    m.function_meta(BTreeMap::values__meta)?;
+    m.function_meta(BTreeMap::extend__meta)?;
+    m.function_meta(BTreeMap::index_set__meta)?;
+    m.function_meta(BTreeMap::index_get__meta)?;
+    m.function_meta(BTreeMap::debug_fmt__meta)?;
+
+    m.function_meta(BTreeMap::clone__meta)?;
+    m.implement_trait::<BTreeMap>(rune::item!(::std::clone::Clone))?;
+
+    m.function_meta(BTreeMap::partial_eq__meta)?;
+    m.implement_trait::<BTreeMap>(rune::item!(::std::cmp::PartialEq))?;
+
+    m.function_meta(BTreeMap::eq__meta)?;
+    m.implement_trait::<BTreeMap>(rune::item!(::std::cmp::Eq))?;
+
+    m.ty::<Iter>()?;
+    m.function_meta(Iter::next__meta)?;
+    m.function_meta(Iter::size_hint__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Iter::next_back__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.function_meta(Iter::len__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<Keys>()?;
+    m.function_meta(Keys::next__meta)?;
+    m.function_meta(Keys::size_hint__meta)?;
+    m.implement_trait::<Keys>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Keys::next_back__meta)?;
+    m.implement_trait::<Keys>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.function_meta(Keys::len__meta)?;
+    m.implement_trait::<Keys>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<Values>()?;
+    m.function_meta(Values::next__meta)?;
+    m.function_meta(Values::size_hint__meta)?;
+    m.implement_trait::<Values>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Values::next_back__meta)?;
+    m.implement_trait::<Values>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.function_meta(Values::len__meta)?;
+    m.implement_trait::<Values>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<Range>()?;
+    m.function_meta(Range::next__meta)?;
+    m.function_meta(Range::size_hint__meta)?;
+    m.implement_trait::<Range>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Range::next_back__meta)?;
+    m.implement_trait::<Range>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
+    Ok(m)
+}
+
+/// An ordered map based on a [B-Tree].
+///
+/// Keys are ordered using the [`CMP`] protocol, so any value which can be
+/// compared with [`Ord`] can be used as a key, including user-defined types
+/// which implement it. Iteration always visits entries in ascending key order.
+///
+/// It is a logic error for a key to be modified in such a way that its
+/// ordering relative to any other key changes while it is in the map. The
+/// behavior resulting from such a logic error is not specified, but will be
+/// encapsulated to the `BTreeMap` that observed it and will not result in
+/// undefined behavior.
+///
+/// [B-Tree]: https://en.wikipedia.org/wiki/B-tree
+///
+/// # Examples
+///
+/// ```rune
+/// use std::collections::BTreeMap;
+///
+/// let m = BTreeMap::new();
+///
+/// m.insert("b", 2);
+/// m.insert("a", 1);
+/// m["c"] = 3;
+///
+/// assert_eq!(m.get("a"), Some(1));
+/// assert_eq!(m.get("d"), None);
+/// assert_eq!(m.keys().collect::<Vec>(), ["a", "b", "c"]);
+/// ```
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_map)]
+pub(crate) struct BTreeMap {
+    inner: btree_map::BTreeMap<Value, Value>,
+}
+
+impl BTreeMap {
+    /// Makes a new, empty `BTreeMap`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: btree_map::BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::new();
+    /// assert_eq!(a.len(), 0);
+    /// a.insert(1, "a");
+    /// assert_eq!(a.len(), 1);
+    /// ```
+    #[rune::function(keep)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::new();
+    /// assert!(a.is_empty());
+    /// a.insert(1, "a");
+    /// assert!(!a.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(37, "b");
+    /// assert_eq!(map.insert(37, "c"), Some("b"));
+    /// assert_eq!(map[37], "c");
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn insert(&mut self, key: Value, value: Value) -> Result<Option<Value>, VmError> {
+        match self.inner.entry_with(&mut EnvProtocolCaller, key, cmp)? {
+            Entry::Vacant(entry) => {
+                entry.try_insert(value)?;
+                Ok(None)
+            }
+            Entry::Occupied(mut entry) => Ok(Some(entry.insert(value))),
+        }
+    }
+
+    /// Returns the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get(1), Some("a"));
+    /// assert_eq!(map.get(2), None);
+    /// ```
+    #[rune::function(keep)]
+    fn get(&self, key: Value) -> Result<Option<Value>, VmError> {
+        Ok(self
+            .inner
+            .get_with(&mut EnvProtocolCaller, &key, cmp)?
+            .cloned())
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.contains_key(1), true);
+    /// assert_eq!(map.contains_key(2), false);
+    /// ```
+    #[rune::function(keep)]
+    fn contains_key(&self, key: Value) -> Result<bool, VmError> {
+        self.inner
+            .contains_key_with(&mut EnvProtocolCaller, &key, cmp)
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(1), Some("a"));
+    /// assert_eq!(map.remove(1), None);
+    /// ```
+    #[rune::function(keep)]
+    fn remove(&mut self, key: Value) -> Result<Option<Value>, VmError> {
+        Ok(self
+            .inner
+            .remove_entry_with(&mut EnvProtocolCaller, &key, cmp)?
+            .map(|(_, value)| value))
+    }
+
+    /// Clears the map, removing all elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.clear();
+    /// assert!(a.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn clear(&mut self) {
+        self.inner.clear()
+    }
+
+    /// Returns the first key-value pair in the map. The key in this pair is the
+    /// minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.first_key_value(), Some((1, "b")));
+    /// ```
+    #[rune::function(keep)]
+    fn first_key_value(&self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.first_key_value()?;
+        Some((key.clone(), value.clone()))
+    }
+
+    /// Returns the last key-value pair in the map. The key in this pair is the
+    /// maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.last_key_value(), Some((2, "a")));
+    /// ```
+    #[rune::function(keep)]
+    fn last_key_value(&self) -> Option<(Value, Value)> {
+        let (key, value) = self.inner.last_key_value()?;
+        Some((key.clone(), value.clone()))
+    }
+
+    /// Removes and returns the first element in the map. The key of this
+    /// element is the minimum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([(2, "b"), (1, "a")]);
+    /// assert_eq!(map.pop_first(), Some((1, "a")));
+    /// assert_eq!(map.pop_first(), Some((2, "b")));
+    /// assert_eq!(map.pop_first(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop_first(&mut self) -> Option<(Value, Value)> {
+        self.inner.pop_first()
+    }
+
+    /// Removes and returns the last element in the map. The key of this
+    /// element is the maximum key that was in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([(2, "b"), (1, "a")]);
+    /// assert_eq!(map.pop_last(), Some((2, "b")));
+    /// assert_eq!(map.pop_last(), Some((1, "a")));
+    /// assert_eq!(map.pop_last(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop_last(&mut self) -> Option<(Value, Value)> {
+        self.inner.pop_last()
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the
+    /// map.
+    ///
+    /// The range can be any of the range types, such as `a..b`, `a..=b`,
+    /// `a..`, `..b`, `..=b` or `..`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end.
+    ///
+    /// ```rune,should_panic
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([(1, "a")]);
+    /// let _ = map.range(5..1);
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([(3, "a"), (5, "b"), (8, "c")]);
+    ///
+    /// assert_eq!(map.range(4..).collect::<Vec>(), [(5, "b"), (8, "c")]);
+    /// assert_eq!(map.range(..=5).rev().collect::<Vec>(), [(5, "b"), (3, "a")]);
+    /// assert_eq!(map.range(4..8).collect::<Vec>(), [(5, "b")]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::range)]
+    fn range(this: Ref<Self>, range: Value) -> Result<Range, VmError> {
+        let (start, end) = range_bounds(range)?;
+        let (map, guard) = Ref::into_raw(this);
+
+        // SAFETY: We're holding onto the reference guard for as long as the
+        // iterator is alive.
+        let map = unsafe { map.as_ref() };
+
+        let iter =
+            map.inner
+                .range_with(&mut EnvProtocolCaller, (start.as_ref(), end.as_ref()), cmp)?;
+
+        Ok(Range { iter, guard })
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([(3, "c"), (2, "b"), (1, "a")]);
+    ///
+    /// assert_eq!(map.iter().collect::<Vec>(), [(1, "a"), (2, "b"), (3, "c")]);
+    /// assert_eq!(map.iter().rev().next(), Some((3, "c")));
+    /// ```
+    #[rune::function(keep, instance, path = Self::iter)]
+    fn iter(this: Ref<Self>) -> Iter {
+        // SAFETY: We're holding onto the reference guard.
+        let iter = unsafe { this.inner.iter_raw() };
+        let (_, guard) = Ref::into_raw(this);
+        Iter { iter, guard }
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([(2, "b"), (1, "a")]);
+    /// assert_eq!(map.keys().collect::<Vec>(), [1, 2]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::keys)]
+    fn keys(this: Ref<Self>) -> Keys {
+        // SAFETY: We're holding onto the reference guard.
+        let iter = unsafe { this.inner.iter_raw() };
+        let (_, guard) = Ref::into_raw(this);
+        Keys { iter, guard }
+    }
+
+    /// Gets an iterator over the values of the map, in order by key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([(2, "b"), (1, "a")]);
+    /// assert_eq!(map.values().collect::<Vec>(), ["a", "b"]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::values)]
+    fn values(this: Ref<Self>) -> Values {
+        // SAFETY: We're holding onto the reference guard.
+        let iter = unsafe { this.inner.iter_raw() };
+        let (_, guard) = Ref::into_raw(this);
+        Values { iter, guard }
+    }
+
+    /// Extend this map from an iterator of key-value pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    ///
+    /// map.extend([
+    ///     ("b", 2),
+    ///     ("a", 1),
+    /// ]);
+    ///
+    /// assert_eq!(map.keys().collect::<Vec>(), ["a", "b"]);
+    /// ```
+    #[rune::function(keep)]
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        let mut it = value.into_iter()?;
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Value, Value)>::from_value(value)?;
+            self.insert(key, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Clone the map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let a = BTreeMap::from_iter([
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let b = a.clone();
+    ///
+    /// b.insert("c", 3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone, protocol = CLONE)]
+    fn clone(this: &BTreeMap) -> Result<BTreeMap, VmError> {
+        Ok(Self {
+            inner: this.inner.try_clone()?,
+        })
+    }
+
+    /// Convert a map from a value convert into an iterator.
+    ///
+    /// The map can be converted from anything that implements the
+    /// [`INTO_ITER`] protocol, and each item produces should be a tuple pair.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([("b", 2), ("a", 1)]);
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get("a"), Some(1));
+    /// assert_eq!(map.get("b"), Some(2));
+    /// ```
+    #[rune::function(keep, path = Self::from_iter)]
+    fn from_iter(it: Iterator) -> Result<BTreeMap, VmError> {
+        Self::from_iter_with(it)
+    }
+
+    pub(crate) fn from_iter_with(mut it: Iterator) -> Result<Self, VmError> {
+        let mut map = Self::new();
+
+        while let Some(value) = it.next()? {
+            let (key, value) = <(Value, Value)>::from_value(value)?;
+            map.insert(key, value)?;
+        }
+
+        Ok(map)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did have this key present, the value is updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map[37] = "a";
+    /// assert!(!map.is_empty());
+    ///
+    /// map[37] = "c";
+    /// assert_eq!(map[37], "c");
+    /// ```
+    #[rune::function(keep, protocol = INDEX_SET)]
+    fn index_set(&mut self, key: Value, value: Value) -> Result<(), VmError> {
+        let _ = self.insert(key, value)?;
+        Ok(())
+    }
+
+    /// Returns a the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given value is not present in the map.
+    ///
+    /// ```rune,should_panic
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// let _ = map[1];
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map[1] = "a";
+    /// assert_eq!(map[1], "a");
+    /// ```
+    #[rune::function(keep, protocol = INDEX_GET)]
+    fn index_get(&self, key: Value) -> Result<Value, VmError> {
+        let Some(value) = self.inner.get_with(&mut EnvProtocolCaller, &key, cmp)? else {
+            return Err(VmError::from(VmErrorKind::MissingIndexKey {
+                target: Self::type_info(),
+            }));
+        };
+
+        Ok(value.clone())
+    }
+
+    /// Debug format the current map.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::new();
+    /// map[2] = "b";
+    /// map[1] = "a";
+    ///
+    /// assert_eq!(format!("{:?}", map), "{1: \"a\", 2: \"b\"}");
+    /// ```
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> Result<(), VmError> {
+        self.debug_fmt_with(f, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn debug_fmt_with(
+        &self,
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        write!(f, "{{")?;
+
+        let mut it = self.inner.iter().peekable();
+
+        while let Some((key, value)) = it.next() {
+            key.debug_fmt_with(f, caller)?;
+            write!(f, ": ")?;
+            value.debug_fmt_with(f, caller)?;
+
+            if it.peek().is_some() {
+                write!(f, ", ")?;
+            }
+        }
+
+        write!(f, "}}")?;
+        Ok(())
+    }
+
+    /// Perform a partial equality check over two maps.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map1 = BTreeMap::from_iter([
+    ///     ("a", 1.0),
+    ///     ("c", 3.0),
+    ///     ("b", 2.0),
+    /// ]);
+    ///
+    /// let map2 = BTreeMap::from_iter([
+    ///     ("c", 3.0),
+    ///     ("a", 1.0),
+    ///     ("b", 2.0),
+    /// ]);
+    ///
+    /// assert!(map1 == map2);
+    ///
+    /// map1["b"] = f64::NAN;
+    /// map2["b"] = f64::NAN;
+    ///
+    /// assert!(map1 != map2);
+    /// ```
+    #[rune::function(keep, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, other: &Self) -> Result<bool, VmError> {
+        self.partial_eq_with(other, &mut EnvProtocolCaller)
+    }
+
+    fn partial_eq_with(
+        &self,
+        other: &Self,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<bool, VmError> {
+        if self.inner.len() != other.inner.len() {
+            return Ok(false);
+        }
+
+        for ((k1, v1), (k2, v2)) in self.inner.iter().zip(other.inner.iter()) {
+            if !Value::partial_eq_with(k1, k2, caller)? {
+                return Ok(false);
+            }
+
+            if !Value::partial_eq_with(v1, v2, caller)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Perform a total equality check over two maps.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    /// use std::ops::eq;
+    ///
+    /// let map1 = BTreeMap::from_iter([
+    ///     ("a", 1),
+    ///     ("c", 3),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let map2 = BTreeMap::from_iter([
+    ///     ("c", 3),
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// assert!(eq(map1, map2));
+    /// ```
+    #[rune::function(keep, protocol = EQ)]
+    fn eq(&self, other: &Self) -> Result<bool, VmError> {
+        self.eq_with(other, &mut EnvProtocolCaller)
+    }
+
+    fn eq_with(&self, other: &Self, caller: &mut dyn ProtocolCaller) -> Result<bool, VmError> {
+        if self.inner.len() != other.inner.len() {
+            return Ok(false);
+        }
+
+        for ((k1, v1), (k2, v2)) in self.inner.iter().zip(other.inner.iter()) {
+            if !Value::eq_with(k1, k2, caller)? {
+                return Ok(false);
+            }
+
+            if !Value::eq_with(v1, v2, caller)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_iter([
+    ///     ("c", 3),
+    ///     ("a", 1),
+    ///     ("b", 2),
+    /// ]);
+    ///
+    /// let pairs = [];
+    ///
+    /// for pair in map {
+    ///     pairs.push(pair);
+    /// }
+    ///
+    /// assert_eq!(pairs, [("a", 1), ("b", 2), ("c", 3)]);
+    /// ```
+    #[rune::function(keep, instance, protocol = INTO_ITER, path = Self)]
+    fn into_iter(this: Ref<Self>) -> Iter {
+        Self::iter(this)
+    }
+}
+
+/// Compare two keys using the [`CMP`] protocol.
+pub(super) fn cmp(
+    caller: &mut EnvProtocolCaller,
+    a: &Value,
+    b: &Value,
+) -> Result<Ordering, VmError> {
+    Value::cmp_with(a, b, caller)
+}
+
+/// Convert a range value into a pair of bounds, making sure that the start of
+/// the range is not greater than its end.
+pub(super) fn range_bounds(range: Value) -> Result<(Bound<Value>, Bound<Value>), VmError> {
+    let bounds = 'out: {
+        if let Some(value) = range.as_any() {
+            match value.type_hash() {
+                RangeFrom::HASH => {
+                    let range = value.borrow_ref::<RangeFrom>()?;
+                    break 'out (Bound::Included(range.start.clone()), Bound::Unbounded);
+                }
+                RangeFull::HASH => {
+                    _ = value.borrow_ref::<RangeFull>()?;
+                    break 'out (Bound::Unbounded, Bound::Unbounded);
+                }
+                RangeInclusive::HASH => {
+                    let range = value.borrow_ref::<RangeInclusive>()?;
+
+                    break 'out (
+                        Bound::Included(range.start.clone()),
+                        Bound::Included(range.end.clone()),
+                    );
+                }
+                RangeToInclusive::HASH => {
+                    let range = value.borrow_ref::<RangeToInclusive>()?;
+                    break 'out (Bound::Unbounded, Bound::Included(range.end.clone()));
+                }
+                RangeTo::HASH => {
+                    let range = value.borrow_ref::<RangeTo>()?;
+                    break 'out (Bound::Unbounded, Bound::Excluded(range.end.clone()));
+                }
+                runtime::Range::HASH => {
+                    let range = value.borrow_ref::<runtime::Range>()?;
+
+                    break 'out (
+                        Bound::Included(range.start.clone()),
+                        Bound::Excluded(range.end.clone()),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        return Err(VmError::expected::<runtime::Range>(range.type_info()));
+    };
+
+    if let (
+        Bound::Included(start) | Bound::Excluded(start),
+        Bound::Included(end) | Bound::Excluded(end),
+    ) = &bounds
+    {
+        if cmp(&mut EnvProtocolCaller, start, end)? == Ordering::Greater {
+            return Err(VmError::panic("range start is greater than range end"));
+        }
+    }
+
+    Ok(bounds)
+}
+
+/// An iterator over the entries of a btree map.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_map)]
+pub(crate) struct Iter {
+    iter: btree_map::IterRaw<Value, Value>,
+    // Drop must happen after the raw iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Iter {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<(Value, Value)> {
+        let (key, value) = self.iter.next()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some(((*key).clone(), (*value).clone())) }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<(Value, Value)> {
+        let (key, value) = self.iter.next_back()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some(((*key).clone(), (*value).clone())) }
+    }
+}
+
+impl iter::Iterator for Iter {
+    type Item = (Value, Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Iter::next(self)
+    }
+}
+
+impl iter::DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Iter::next_back(self)
+    }
+}
+
+/// An iterator over the keys of a btree map.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_map)]
+pub(crate) struct Keys {
+    iter: btree_map::IterRaw<Value, Value>,
+    // Drop must happen after the raw iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Keys {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        let (key, _) = self.iter.next()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*key).clone()) }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        let (key, _) = self.iter.next_back()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*key).clone()) }
+    }
+}
+
+/// An iterator over the values of a btree map.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_map)]
+pub(crate) struct Values {
+    iter: btree_map::IterRaw<Value, Value>,
+    // Drop must happen after the raw iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Values {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        let (_, value) = self.iter.next()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*value).clone()) }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        let (_, value) = self.iter.next_back()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*value).clone()) }
+    }
+}
+
+/// An iterator over a sub-range of entries in a btree map.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_map)]
+pub(crate) struct Range {
+    iter: btree_map::Range<'static, Value, Value>,
+    // Drop must happen after the range iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Range {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<(Value, Value)> {
+        let (key, value) = self.iter.next()?;
+        Some((key.clone(), value.clone()))
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<(Value, Value)> {
+        let (key, value) = self.iter.next_back()?;
+        Some((key.clone(), value.clone()))
+    }
+}
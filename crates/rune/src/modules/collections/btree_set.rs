@@ -0,0 +1,662 @@
+use core::iter;
+
+use crate as rune;
+use crate::alloc::btree_map::{self, Entry};
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::runtime::{
+    EnvProtocolCaller, Formatter, Iterator, ProtocolCaller, RawAnyGuard, Ref, Value, VmError,
+};
+use crate::{Any, ContextError, Module};
+
+use super::btree_map::{cmp, range_bounds};
+
+/// A dynamic ordered set.
+#[rune::module(::std::collections::btree_set)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+
+    m.ty::<BTreeSet>()?;
+    m.function_meta(BTreeSet::new__meta)?;
+    m.function_meta(BTreeSet::len__meta)?;
+    m.function_meta(BTreeSet::is_empty__meta)?;
+    m.function_meta(BTreeSet::insert__meta)?;
+    m.function_meta(BTreeSet::contains__meta)?;
+    m.function_meta(BTreeSet::remove__meta)?;
+    m.function_meta(BTreeSet::clear__meta)?;
+    m.function_meta(BTreeSet::first__meta)?;
+    m.function_meta(BTreeSet::last__meta)?;
+    m.function_meta(BTreeSet::pop_first__meta)?;
+    m.function_meta(BTreeSet::pop_last__meta)?;
+    m.function_meta(BTreeSet::range__meta)?;
+    m.function_meta(BTreeSet::iter__meta)?;
+    m.function_meta(BTreeSet::into_iter__meta)?;
+    m.function_meta(BTreeSet::from_iter__meta)?;
+    m.function_meta(BTreeSet::extend__meta)?;
+    m.function_meta(BTreeSet::debug_fmt__meta)?;
+
+    m.function_meta(BTreeSet::clone__meta)?;
+    m.implement_trait::<BTreeSet>(rune::item!(::std::clone::Clone))?;
+
+    m.function_meta(BTreeSet::partial_eq__meta)?;
+    m.implement_trait::<BTreeSet>(rune::item!(::std::cmp::PartialEq))?;
+
+    m.function_meta(BTreeSet::eq__meta)?;
+    m.implement_trait::<BTreeSet>(rune::item!(::std::cmp::Eq))?;
+
+    m.ty::<Iter>()?;
+    m.function_meta(Iter::next__meta)?;
+    m.function_meta(Iter::size_hint__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Iter::next_back__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.function_meta(Iter::len__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<Range>()?;
+    m.function_meta(Range::next__meta)?;
+    m.function_meta(Range::size_hint__meta)?;
+    m.implement_trait::<Range>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Range::next_back__meta)?;
+    m.implement_trait::<Range>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
+    Ok(m)
+}
+
+/// An ordered set based on a B-Tree.
+///
+/// Values are ordered using the [`CMP`] protocol, so any value which can be
+/// compared with [`Ord`] can be stored, including user-defined types which
+/// implement it. Iteration always visits values in ascending order.
+///
+/// It is a logic error for a value to be modified in such a way that its
+/// ordering relative to any other value changes while it is in the set. The
+/// behavior resulting from such a logic error is not specified, but will be
+/// encapsulated to the `BTreeSet` that observed it and will not result in
+/// undefined behavior.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::collections::BTreeSet;
+///
+/// let books = BTreeSet::new();
+///
+/// books.insert("Pride and Prejudice");
+/// books.insert("A Dance With Dragons");
+/// books.insert("The Odyssey");
+///
+/// assert!(books.contains("The Odyssey"));
+/// assert_eq!(books.first(), Some("A Dance With Dragons"));
+/// assert_eq!(books.len(), 3);
+/// ```
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_set)]
+pub(crate) struct BTreeSet {
+    inner: btree_map::BTreeMap<Value, ()>,
+}
+
+impl BTreeSet {
+    /// Makes a new, empty `BTreeSet`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// set.insert(1);
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: btree_map::BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let v = BTreeSet::new();
+    /// assert_eq!(v.len(), 0);
+    /// v.insert(1);
+    /// assert_eq!(v.len(), 1);
+    /// ```
+    #[rune::function(keep)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let v = BTreeSet::new();
+    /// assert!(v.is_empty());
+    /// v.insert(1);
+    /// assert!(!v.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. That is:
+    ///
+    /// - If the set did not previously contain an equal value, `true` is
+    ///   returned.
+    /// - If the set already contained an equal value, `false` is returned, and
+    ///   the entry is not updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    ///
+    /// assert!(set.insert(2));
+    /// assert!(!set.insert(2));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn insert(&mut self, key: Value) -> Result<bool, VmError> {
+        match self.inner.entry_with(&mut EnvProtocolCaller, key, cmp)? {
+            Entry::Vacant(entry) => {
+                entry.try_insert(())?;
+                Ok(true)
+            }
+            Entry::Occupied(..) => Ok(false),
+        }
+    }
+
+    /// Returns `true` if the set contains a value.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([1, 2, 3]);
+    /// assert!(set.contains(1));
+    /// assert!(!set.contains(4));
+    /// ```
+    #[rune::function(keep)]
+    fn contains(&self, key: Value) -> Result<bool, VmError> {
+        self.inner
+            .contains_key_with(&mut EnvProtocolCaller, &key, cmp)
+    }
+
+    /// If the set contains an element equal to the value, removes it from the
+    /// set and drops it. Returns whether such an element was present.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    ///
+    /// set.insert(2);
+    /// assert!(set.remove(2));
+    /// assert!(!set.remove(2));
+    /// ```
+    #[rune::function(keep)]
+    fn remove(&mut self, key: Value) -> Result<bool, VmError> {
+        Ok(self
+            .inner
+            .remove_entry_with(&mut EnvProtocolCaller, &key, cmp)?
+            .is_some())
+    }
+
+    /// Clears the set, removing all values.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let v = BTreeSet::new();
+    /// v.insert(1);
+    /// v.clear();
+    /// assert!(v.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn clear(&mut self) {
+        self.inner.clear()
+    }
+
+    /// Returns the first element in the set, if any. This element is always
+    /// the minimum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// assert_eq!(set.first(), None);
+    /// set.insert(2);
+    /// set.insert(1);
+    /// assert_eq!(set.first(), Some(1));
+    /// ```
+    #[rune::function(keep)]
+    fn first(&self) -> Option<Value> {
+        let (key, _) = self.inner.first_key_value()?;
+        Some(key.clone())
+    }
+
+    /// Returns the last element in the set, if any. This element is always
+    /// the maximum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// assert_eq!(set.last(), None);
+    /// set.insert(1);
+    /// set.insert(2);
+    /// assert_eq!(set.last(), Some(2));
+    /// ```
+    #[rune::function(keep)]
+    fn last(&self) -> Option<Value> {
+        let (key, _) = self.inner.last_key_value()?;
+        Some(key.clone())
+    }
+
+    /// Removes the first element from the set and returns it, if any. The
+    /// first element is always the minimum element in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([2, 1]);
+    /// assert_eq!(set.pop_first(), Some(1));
+    /// assert_eq!(set.pop_first(), Some(2));
+    /// assert_eq!(set.pop_first(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop_first(&mut self) -> Option<Value> {
+        let (key, ()) = self.inner.pop_first()?;
+        Some(key)
+    }
+
+    /// Removes the last element from the set and returns it, if any. The last
+    /// element is always the maximum element in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([2, 1]);
+    /// assert_eq!(set.pop_last(), Some(2));
+    /// assert_eq!(set.pop_last(), Some(1));
+    /// assert_eq!(set.pop_last(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop_last(&mut self) -> Option<Value> {
+        let (key, ()) = self.inner.pop_last()?;
+        Some(key)
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the
+    /// set.
+    ///
+    /// The range can be any of the range types, such as `a..b`, `a..=b`,
+    /// `a..`, `..b`, `..=b` or `..`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end.
+    ///
+    /// ```rune,should_panic
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([1]);
+    /// let _ = set.range(5..1);
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([3, 5, 8]);
+    ///
+    /// assert_eq!(set.range(4..).collect::<Vec>(), [5, 8]);
+    /// assert_eq!(set.range(..=5).rev().collect::<Vec>(), [5, 3]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::range)]
+    fn range(this: Ref<Self>, range: Value) -> Result<Range, VmError> {
+        let (start, end) = range_bounds(range)?;
+        let (set, guard) = Ref::into_raw(this);
+
+        // SAFETY: We're holding onto the reference guard for as long as the
+        // iterator is alive.
+        let set = unsafe { set.as_ref() };
+
+        let iter =
+            set.inner
+                .range_with(&mut EnvProtocolCaller, (start.as_ref(), end.as_ref()), cmp)?;
+
+        Ok(Range { iter, guard })
+    }
+
+    /// Gets an iterator that visits the elements in the set in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([3, 1, 2]);
+    ///
+    /// assert_eq!(set.iter().collect::<Vec>(), [1, 2, 3]);
+    /// assert_eq!(set.iter().rev().collect::<Vec>(), [3, 2, 1]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::iter)]
+    fn iter(this: Ref<Self>) -> Iter {
+        // SAFETY: We're holding onto the reference guard.
+        let iter = unsafe { this.inner.iter_raw() };
+        let (_, guard) = Ref::into_raw(this);
+        Iter { iter, guard }
+    }
+
+    /// Extend this set from an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// set.extend([3, 1, 2]);
+    /// assert_eq!(set.iter().collect::<Vec>(), [1, 2, 3]);
+    /// ```
+    #[rune::function(keep)]
+    fn extend(&mut self, value: Value) -> Result<(), VmError> {
+        let mut it = value.into_iter()?;
+
+        while let Some(key) = it.next()? {
+            self.insert(key)?;
+        }
+
+        Ok(())
+    }
+
+    /// Clone the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let a = BTreeSet::from_iter([1, 2]);
+    /// let b = a.clone();
+    ///
+    /// b.insert(3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone, protocol = CLONE)]
+    fn clone(this: &BTreeSet) -> Result<BTreeSet, VmError> {
+        Ok(Self {
+            inner: this.inner.try_clone()?,
+        })
+    }
+
+    /// Convert a set from a value convert into an iterator.
+    ///
+    /// The set can be converted from anything that implements the
+    /// [`INTO_ITER`] protocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter(["b", "a", "b"]);
+    /// assert_eq!(set.len(), 2);
+    /// assert_eq!(set.iter().collect::<Vec>(), ["a", "b"]);
+    /// ```
+    #[rune::function(keep, path = Self::from_iter)]
+    fn from_iter(it: Iterator) -> Result<BTreeSet, VmError> {
+        Self::from_iter_with(it)
+    }
+
+    pub(crate) fn from_iter_with(mut it: Iterator) -> Result<Self, VmError> {
+        let mut set = Self::new();
+
+        while let Some(key) = it.next()? {
+            set.insert(key)?;
+        }
+
+        Ok(set)
+    }
+
+    /// Debug format the current set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([2, 1]);
+    /// assert_eq!(format!("{:?}", set), "{1, 2}");
+    /// ```
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> Result<(), VmError> {
+        self.debug_fmt_with(f, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn debug_fmt_with(
+        &self,
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<(), VmError> {
+        write!(f, "{{")?;
+
+        let mut it = self.inner.keys().peekable();
+
+        while let Some(key) = it.next() {
+            key.debug_fmt_with(f, caller)?;
+
+            if it.peek().is_some() {
+                write!(f, ", ")?;
+            }
+        }
+
+        write!(f, "}}")?;
+        Ok(())
+    }
+
+    /// Perform a partial equality check over two sets.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let a = BTreeSet::from_iter([1, 2]);
+    /// let b = BTreeSet::from_iter([2, 1]);
+    ///
+    /// assert!(a == b);
+    /// b.insert(3);
+    /// assert!(a != b);
+    /// ```
+    #[rune::function(keep, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, other: &Self) -> Result<bool, VmError> {
+        self.partial_eq_with(other, &mut EnvProtocolCaller)
+    }
+
+    fn partial_eq_with(
+        &self,
+        other: &Self,
+        caller: &mut dyn ProtocolCaller,
+    ) -> Result<bool, VmError> {
+        if self.inner.len() != other.inner.len() {
+            return Ok(false);
+        }
+
+        for (a, b) in self.inner.keys().zip(other.inner.keys()) {
+            if !Value::partial_eq_with(a, b, caller)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Perform a total equality check over two sets.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    /// use std::ops::eq;
+    ///
+    /// let a = BTreeSet::from_iter([1, 2]);
+    /// let b = BTreeSet::from_iter([2, 1]);
+    ///
+    /// assert!(eq(a, b));
+    /// ```
+    #[rune::function(keep, protocol = EQ)]
+    fn eq(&self, other: &Self) -> Result<bool, VmError> {
+        self.eq_with(other, &mut EnvProtocolCaller)
+    }
+
+    fn eq_with(&self, other: &Self, caller: &mut dyn ProtocolCaller) -> Result<bool, VmError> {
+        if self.inner.len() != other.inner.len() {
+            return Ok(false);
+        }
+
+        for (a, b) in self.inner.keys().zip(other.inner.keys()) {
+            if !Value::eq_with(a, b, caller)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Gets an iterator that visits the elements in the set in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([3, 1, 2]);
+    /// let out = [];
+    ///
+    /// for value in set {
+    ///     out.push(value);
+    /// }
+    ///
+    /// assert_eq!(out, [1, 2, 3]);
+    /// ```
+    #[rune::function(keep, instance, protocol = INTO_ITER, path = Self)]
+    fn into_iter(this: Ref<Self>) -> Iter {
+        Self::iter(this)
+    }
+}
+
+/// An iterator over the values of a btree set.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_set)]
+pub(crate) struct Iter {
+    iter: btree_map::IterRaw<Value, ()>,
+    // Drop must happen after the raw iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Iter {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        let (key, _) = self.iter.next()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*key).clone()) }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        let (key, _) = self.iter.next_back()?;
+        // SAFETY: We're holding onto the reference guard.
+        unsafe { Some((*key).clone()) }
+    }
+}
+
+impl iter::Iterator for Iter {
+    type Item = Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Iter::next(self)
+    }
+}
+
+impl iter::DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Iter::next_back(self)
+    }
+}
+
+/// An iterator over a sub-range of values in a btree set.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_set)]
+pub(crate) struct Range {
+    iter: btree_map::Range<'static, Value, ()>,
+    // Drop must happen after the range iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Range {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        let (key, _) = self.iter.next()?;
+        Some(key.clone())
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        let (key, _) = self.iter.next_back()?;
+        Some(key.clone())
+    }
+}
@@ -3,8 +3,8 @@ use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
 use crate::hashbrown::{IterRef, KeysRef, Table, ValuesRef};
 use crate::runtime::{
-    EnvProtocolCaller, Formatter, FromValue, Iterator, ProtocolCaller, Ref, Value, VmError,
-    VmErrorKind,
+    EnvProtocolCaller, Formatter, FromValue, Function, Iterator, Mut, ProtocolCaller, Ref, Value,
+    VmError, VmErrorKind,
 };
 use crate::{Any, ContextError, Module};
 
@@ -20,9 +20,12 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(HashMap::capacity__meta)?;
     m.function_meta(HashMap::insert__meta)?;
     m.function_meta(HashMap::get__meta)?;
+    m.function_meta(HashMap::get_mut__meta)?;
+    m.function_meta(HashMap::entry__meta)?;
     m.function_meta(HashMap::contains_key__meta)?;
     m.function_meta(HashMap::remove__meta)?;
     m.function_meta(HashMap::clear__meta)?;
+    m.function_meta(HashMap::retain__meta)?;
     m.function_meta(HashMap::is_empty__meta)?;
     m.function_meta(HashMap::iter__meta)?;
     m.function_meta(HashMap::into_iter__meta)?;
@@ -43,6 +46,12 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(HashMap::eq__meta)?;
     m.implement_trait::<HashMap>(rune::item!(::std::cmp::Eq))?;
 
+    m.ty::<Entry>()?;
+    m.function_meta(Entry::key__meta)?;
+    m.function_meta(Entry::or_insert__meta)?;
+    m.function_meta(Entry::or_insert_with__meta)?;
+    m.function_meta(Entry::or_insert_with_key__meta)?;
+
     m.ty::<Iter>()?;
     m.function_meta(Iter::next)?;
     m.function_meta(Iter::size_hint)?;
@@ -277,6 +286,56 @@ impl HashMap {
             .map(|(_, v)| v.clone()))
     }
 
+    /// Returns the value corresponding to the [`Key`].
+    ///
+    /// Values are shared, so the returned value can be modified in place the
+    /// same way as one returned by [`get`].
+    ///
+    /// [`get`]: HashMap::get
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashMap;
+    ///
+    /// let map = HashMap::new();
+    /// map.insert(1, [1, 2]);
+    ///
+    /// if let Some(x) = map.get_mut(1) {
+    ///     x.push(3);
+    /// }
+    ///
+    /// assert_eq!(map.get(1), Some([1, 2, 3]));
+    /// ```
+    #[rune::function(keep)]
+    fn get_mut(&self, key: Value) -> Result<Option<Value>, VmError> {
+        self.get(key)
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place
+    /// manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashMap;
+    ///
+    /// let words = HashMap::new();
+    ///
+    /// for word in "the cat and the hat on the mat".split_whitespace() {
+    ///     let counter = words.entry(word).or_insert(0);
+    ///     words[word] = counter + 1;
+    /// }
+    ///
+    /// assert_eq!(words["the"], 3);
+    /// assert_eq!(words["cat"], 1);
+    /// assert_eq!(words.get("dog"), None);
+    /// ```
+    #[rune::function(keep, instance, path = Self::entry)]
+    fn entry(this: Mut<Self>, key: Value) -> Entry {
+        Entry { map: this, key }
+    }
+
     /// Returns `true` if the map contains a value for the specified [`Key`].
     ///
     /// # Examples
@@ -330,6 +389,28 @@ impl HashMap {
         self.table.clear()
     }
 
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs `(k, v)` for which `f(k, v)` returns
+    /// `false`. The elements are visited in unsorted (and unspecified) order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashMap;
+    ///
+    /// let map = (0..8).iter().map(|x| (x, x * 10)).collect::<HashMap>();
+    /// map.retain(|k, _| k % 2 == 0);
+    /// assert_eq!(map.len(), 4);
+    /// assert_eq!(map.get(2), Some(20));
+    /// assert_eq!(map.get(3), None);
+    /// ```
+    #[rune::function(keep)]
+    fn retain(&mut self, f: Function) -> Result<(), VmError> {
+        self.table
+            .retain(|key, value| f.call::<bool>((key.clone(), value.clone())))
+    }
+
     /// An iterator visiting all key-value pairs in arbitrary order.
     ///
     /// # Examples
@@ -733,6 +814,119 @@ impl HashMap {
     }
 }
 
+/// A view into a single entry in a map, which may either be vacant or
+/// occupied.
+///
+/// This is constructed from the [`entry`] method on [`HashMap`].
+///
+/// [`entry`]: HashMap::entry
+#[derive(Any)]
+#[rune(item = ::std::collections::hash_map)]
+pub(crate) struct Entry {
+    map: Mut<HashMap>,
+    key: Value,
+}
+
+impl Entry {
+    /// Returns a reference to this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashMap;
+    ///
+    /// let map = HashMap::new();
+    /// assert_eq!(map.entry("poneyland").key(), "poneyland");
+    /// ```
+    #[rune::function(keep)]
+    fn key(&self) -> Value {
+        self.key.clone()
+    }
+
+    /// Ensures a value is in the entry by inserting the default if empty, and
+    /// returns the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashMap;
+    ///
+    /// let map = HashMap::new();
+    ///
+    /// assert_eq!(map.entry("poneyland").or_insert(3), 3);
+    /// assert_eq!(map.entry("poneyland").or_insert(10), 3);
+    /// assert_eq!(map["poneyland"], 3);
+    /// ```
+    #[rune::function(keep)]
+    fn or_insert(self, default: Value) -> Result<Value, VmError> {
+        self.or_insert_with_value(|_| Ok(default))
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default
+    /// function if empty, and returns the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashMap;
+    ///
+    /// let map = HashMap::new();
+    /// let value = "hoho";
+    ///
+    /// assert_eq!(map.entry("poneyland").or_insert_with(|| value), "hoho");
+    /// assert_eq!(map.entry("poneyland").or_insert_with(|| "haha"), "hoho");
+    ///
+    /// let groups = HashMap::new();
+    ///
+    /// for n in [1, 2, 3, 4, 5] {
+    ///     groups.entry(n % 2).or_insert_with(Vec::new).push(n);
+    /// }
+    ///
+    /// assert_eq!(groups[0], [2, 4]);
+    /// assert_eq!(groups[1], [1, 3, 5]);
+    /// ```
+    #[rune::function(keep)]
+    fn or_insert_with(self, default: Function) -> Result<Value, VmError> {
+        self.or_insert_with_value(|_| default.call(()))
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of
+    /// the default function called with the entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::HashMap;
+    ///
+    /// let map = HashMap::new();
+    ///
+    /// let value = map.entry("poneyland").or_insert_with_key(|key| key.len());
+    /// assert_eq!(value, 9);
+    /// assert_eq!(map["poneyland"], 9);
+    /// ```
+    #[rune::function(keep)]
+    fn or_insert_with_key(self, default: Function) -> Result<Value, VmError> {
+        self.or_insert_with_value(|key| default.call((key,)))
+    }
+
+    fn or_insert_with_value<F>(mut self, default: F) -> Result<Value, VmError>
+    where
+        F: FnOnce(Value) -> Result<Value, VmError>,
+    {
+        if let Some((_, value)) = self.map.table.get(&self.key, &mut EnvProtocolCaller)? {
+            return Ok(value.clone());
+        }
+
+        let value = default(self.key.clone())?;
+
+        self.map
+            .table
+            .insert_with(self.key, value.clone(), &mut EnvProtocolCaller)?;
+
+        Ok(value)
+    }
+}
+
 /// An iterator over a hash map.
 #[derive(Any)]
 #[rune(item = ::std::collections::hash_map)]
@@ -1,5 +1,14 @@
 //! Dynamic collections.
 
+pub(crate) mod binary_heap;
+pub(crate) use binary_heap::BinaryHeap;
+
+pub(crate) mod btree_map;
+pub(crate) use btree_map::BTreeMap;
+
+pub(crate) mod btree_set;
+pub(crate) use btree_set::BTreeSet;
+
 pub(crate) mod hash_map;
 pub(crate) use hash_map::HashMap;
 
@@ -17,6 +26,21 @@ use crate::{ContextError, Module};
 pub fn module() -> Result<Module, ContextError> {
     let mut m = Module::from_meta(self::module__meta)?;
 
+    m.reexport(
+        ["BinaryHeap"],
+        rune::item!(::std::collections::binary_heap::BinaryHeap),
+    )?;
+
+    m.reexport(
+        ["BTreeMap"],
+        rune::item!(::std::collections::btree_map::BTreeMap),
+    )?;
+
+    m.reexport(
+        ["BTreeSet"],
+        rune::item!(::std::collections::btree_set::BTreeSet),
+    )?;
+
     m.reexport(
         ["HashMap"],
         rune::item!(::std::collections::hash_map::HashMap),
@@ -5,7 +5,7 @@ use crate::alloc::prelude::*;
 use crate::compile;
 use crate::macros::{quote, FormatArgs, MacroContext, TokenStream};
 use crate::parse::Parser;
-use crate::runtime::{Value, VmError};
+use crate::runtime::{Fixed, Value, VmError};
 use crate::{docstring, ContextError, Module};
 
 /// Core types and methods in Rune.
@@ -27,6 +27,30 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<i64>()?.docs(docstring! {
         /// The signed integer type.
     })?;
+    module.ty::<Fixed<u8>>()?.docs(docstring! {
+        /// The 8-bit unsigned integer type.
+    })?;
+    module.ty::<Fixed<u16>>()?.docs(docstring! {
+        /// The 16-bit unsigned integer type.
+    })?;
+    module.ty::<Fixed<u32>>()?.docs(docstring! {
+        /// The 32-bit unsigned integer type.
+    })?;
+    module.ty::<Fixed<i8>>()?.docs(docstring! {
+        /// The 8-bit signed integer type.
+    })?;
+    module.ty::<Fixed<i16>>()?.docs(docstring! {
+        /// The 16-bit signed integer type.
+    })?;
+    module.ty::<Fixed<i32>>()?.docs(docstring! {
+        /// The 32-bit signed integer type.
+    })?;
+    module.ty::<Fixed<u128>>()?.docs(docstring! {
+        /// The 128-bit unsigned integer type.
+    })?;
+    module.ty::<Fixed<i128>>()?.docs(docstring! {
+        /// The 128-bit signed integer type.
+    })?;
     module.ty::<f64>()?.docs(docstring! {
         /// The primitive float type.
     })?;
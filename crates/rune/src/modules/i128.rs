@@ -0,0 +1,67 @@
+//! Integers.
+
+use core::cmp::Ordering;
+use core::hash::Hash as _;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::string::TryToString;
+use crate::runtime::{Fixed, Formatter, Hasher};
+use crate::{ContextError, Module};
+
+/// Signed integers.
+///
+/// This provides methods for computing over and parsing 128-bit signed
+/// integers.
+#[rune::module(::std::i128)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+    signed!(m, i128, "-i128::MAX - 1", "!i128::MIN");
+    m.function_meta(hash__meta)?;
+    m.function_meta(display_fmt__meta)?;
+    m.function_meta(debug_fmt__meta)?;
+    Ok(m)
+}
+
+signed_fns!(cast i128);
+
+/// Hash the integer.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::hash;
+///
+/// assert_eq!(hash(10 as i128), hash(10 as i128));
+/// assert_ne!(hash(10 as i128), hash(11 as i128));
+/// ```
+#[rune::function(keep, instance, protocol = HASH)]
+fn hash(this: Fixed<i128>, hasher: &mut Hasher) {
+    this.0.hash(hasher);
+}
+
+/// Write a display representation of the integer.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(format!("{}", -(10 as i128)), "-10");
+/// ```
+#[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+fn display_fmt(this: Fixed<i128>, f: &mut Formatter) -> alloc::Result<()> {
+    write!(f, "{}", this.0)
+}
+
+/// Write a debug representation of the integer.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(format!("{:?}", -(10 as i128)), "-10");
+/// ```
+#[rune::function(keep, instance, protocol = DEBUG_FMT)]
+fn debug_fmt(this: Fixed<i128>, f: &mut Formatter) -> alloc::Result<()> {
+    write!(f, "{:?}", this.0)
+}
@@ -0,0 +1,21 @@
+//! Integers.
+
+use core::cmp::Ordering;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc::string::TryToString;
+use crate::runtime::Fixed;
+use crate::{ContextError, Module};
+
+/// Signed integers.
+///
+/// This provides methods for computing over and parsing 32-bit signed integers.
+#[rune::module(::std::i32)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+    signed!(m, i32, "-2147483648", "2147483647");
+    Ok(m)
+}
+
+signed_fns!(cast i32);
@@ -5,6 +5,7 @@ use core::num::ParseIntError;
 
 use crate as rune;
 use crate::alloc::string::TryToString;
+use crate::runtime::Fixed;
 use crate::{ContextError, Module};
 
 /// Signed integers.
@@ -13,7 +14,7 @@ use crate::{ContextError, Module};
 #[rune::module(::std::i64)]
 pub fn module() -> Result<Module, ContextError> {
     let mut m = Module::from_meta(self::module__meta)?;
-    signed!(m, i64);
+    signed!(m, i64, "-9223372036854775808", "9223372036854775807");
     Ok(m)
 }
 
@@ -0,0 +1,21 @@
+//! Integers.
+
+use core::cmp::Ordering;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc::string::TryToString;
+use crate::runtime::Fixed;
+use crate::{ContextError, Module};
+
+/// Signed integers.
+///
+/// This provides methods for computing over and parsing 8-bit signed integers.
+#[rune::module(::std::i8)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+    signed!(m, i8, "-128", "127");
+    Ok(m)
+}
+
+signed_fns!(cast i8);
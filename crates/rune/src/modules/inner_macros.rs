@@ -1,9 +1,9 @@
 macro_rules! unsigned {
-    ($m:ident, $ty:ty) => {
-        unsigned!($m, $ty, stringify!($ty));
+    ($m:ident, $ty:ty, $min:literal, $max:literal) => {
+        unsigned!($m, $ty, stringify!($ty), $min, $max);
     };
 
-    ($m:ident, $ty:ty, $n:expr) => {
+    ($m:ident, $ty:ty, $n:expr, $min:literal, $max:literal) => {
         $m.function("parse", parse).build()?;
         $m.function_meta(to_float)?;
 
@@ -28,50 +28,79 @@ macro_rules! unsigned {
         $m.function_meta(saturating_mul)?;
         $m.function_meta(saturating_pow)?;
 
+        $m.function_meta(overflowing_add)?;
+        $m.function_meta(overflowing_sub)?;
+        $m.function_meta(overflowing_mul)?;
+
+        $m.function_meta(checked_pow)?;
+        $m.function_meta(wrapping_pow)?;
+
+        $m.function_meta(leading_zeros)?;
+        $m.function_meta(trailing_zeros)?;
+        $m.function_meta(count_ones)?;
+        $m.function_meta(count_zeros)?;
+
+        $m.function_meta(to_be_bytes)?;
+        $m.function_meta(to_le_bytes)?;
+        $m.function_meta(from_be_bytes)?;
+        $m.function_meta(from_le_bytes)?;
+
         $m.function_meta(to_string)?;
 
         $m.function_meta(clone__meta)?;
-        $m.implement_trait::<$ty>(rune::item!(::std::clone::Clone))?;
+        $m.implement_trait::<Fixed<$ty>>(rune::item!(::std::clone::Clone))?;
 
         $m.function_meta(partial_eq__meta)?;
-        $m.implement_trait::<$ty>(rune::item!(::std::cmp::PartialEq))?;
+        $m.implement_trait::<Fixed<$ty>>(rune::item!(::std::cmp::PartialEq))?;
 
         $m.function_meta(eq__meta)?;
-        $m.implement_trait::<$ty>(rune::item!(::std::cmp::Eq))?;
+        $m.implement_trait::<Fixed<$ty>>(rune::item!(::std::cmp::Eq))?;
 
         $m.function_meta(partial_cmp__meta)?;
-        $m.implement_trait::<$ty>(rune::item!(::std::cmp::PartialOrd))?;
+        $m.implement_trait::<Fixed<$ty>>(rune::item!(::std::cmp::PartialOrd))?;
 
         $m.function_meta(cmp__meta)?;
-        $m.implement_trait::<$ty>(rune::item!(::std::cmp::Ord))?;
+        $m.implement_trait::<Fixed<$ty>>(rune::item!(::std::cmp::Ord))?;
+
+        $m.constant("MIN", Fixed(<$ty>::MIN))
+            .build()?
+            .docs($crate::docstring! {
+                /// The smallest value that can be represented by this integer type.
+                ///
+                /// # Examples
+                ///
+                /// Basic usage:
+                ///
+                /// ```rune
+                #[doc = concat!(" assert_eq!(", $n, "::MIN, ", $min, ");")]
+                /// ```
+            })?;
 
-        $m.constant("MIN", <$ty>::MIN)
+        $m.constant("MAX", Fixed(<$ty>::MAX))
             .build()?
             .docs($crate::docstring! {
-                /// The smallest value that can be represented by this integer type
-                /// (&minus;2<sup>63</sup>).
+                /// The largest value that can be represented by this integer type.
                 ///
                 /// # Examples
                 ///
                 /// Basic usage:
                 ///
                 /// ```rune
-                #[doc = concat!(" assert_eq!(", $n, "::MIN, -9223372036854775808);")]
+                #[doc = concat!(" assert_eq!(", $n, "::MAX, ", $max, ");")]
                 /// ```
             })?;
 
-        $m.constant("MAX", <$ty>::MAX)
+        $m.constant("BITS", <$ty>::BITS)
             .build()?
             .docs($crate::docstring! {
-                /// The largest value that can be represented by this integer type
-                /// (2<sup>63</sup> &minus; 1).
+                /// The size of this integer type in bits.
                 ///
                 /// # Examples
                 ///
                 /// Basic usage:
                 ///
                 /// ```rune
-                #[doc = concat!(" assert_eq!(", $n, "::MAX, 9223372036854775807);")]
+                #[doc = concat!(" assert_eq!(", $n, "::MAX.count_ones() + ", $n, "::MIN.count_ones(), ", $n, "::BITS);")]
                 /// ```
             })?;
     };
@@ -79,44 +108,48 @@ macro_rules! unsigned {
 
 macro_rules! unsigned_fns {
     ($ty:ty) => {
-        unsigned_fns!($ty, stringify!($ty));
+        unsigned_fns!($ty, stringify!($ty), "", stringify!($ty));
+    };
+
+    (cast $ty:ty) => {
+        unsigned_fns!($ty, stringify!($ty), "(", concat!(" as ", stringify!($ty), ")"));
     };
 
-    ($ty:ty, $n:expr) => {
+    ($ty:ty, $n:expr, $l:expr, $r:expr) => {
         unsigned_fns! {
-            inner $ty, $n,
+            inner $ty, $n, $l, $r,
             checked_div {
-                #[doc = concat!(" assert_eq!(128", $n, ".checked_div(2), Some(64));")]
-                #[doc = concat!(" assert_eq!(1", $n, ".checked_div(0), None);")]
+                #[doc = concat!(" assert_eq!(", $l, "128", $r, ".checked_div(2), Some(64));")]
+                #[doc = concat!(" assert_eq!(", $l, "1", $r, ".checked_div(0), None);")]
             },
             saturating_pow {
-                #[doc = concat!(" assert_eq!(4", $n, ".saturating_pow(3), 64);")]
+                #[doc = concat!(" assert_eq!(", $l, "4", $r, ".saturating_pow(3), 64);")]
                 #[doc = concat!(" assert_eq!(", $n, "::MAX.saturating_pow(2), ", $n, "::MAX);")]
             },
             checked_rem {
-                #[doc = concat!(" assert_eq!(5", $n, ".checked_rem(2), Some(1));")]
-                #[doc = concat!(" assert_eq!(5", $n, ".checked_rem(0), None);")]
+                #[doc = concat!(" assert_eq!(", $l, "5", $r, ".checked_rem(2), Some(1));")]
+                #[doc = concat!(" assert_eq!(", $l, "5", $r, ".checked_rem(0), None);")]
             },
             wrapping_sub {
-                #[doc = concat!(" assert_eq!(200", $n, ".wrapping_add(55), 255);")]
-                #[doc = concat!(" assert_eq!(200", $n, ".wrapping_add(", $n, "::MAX), 199);")]
+                #[doc = concat!(" assert_eq!(", $l, "200", $r, ".wrapping_add(55), 255);")]
+                #[doc = concat!(" assert_eq!(", $l, "200", $r, ".wrapping_add(", $n, "::MAX), 199);")]
             },
             saturating_add {
-                #[doc = concat!(" assert_eq!(100", $n, ".saturating_add(1), 101);")]
+                #[doc = concat!(" assert_eq!(", $l, "100", $r, ".saturating_add(1), 101);")]
                 #[doc = concat!(" assert_eq!(", $n, "::MAX.saturating_add(127), ", $n, "::MAX);")]
             },
             saturating_sub {
-                #[doc = concat!(" assert_eq!(100", $n, ".saturating_sub(27), 73);")]
-                #[doc = concat!(" assert_eq!(13", $n, ".saturating_sub(127), 0);")]
+                #[doc = concat!(" assert_eq!(", $l, "100", $r, ".saturating_sub(27), 73);")]
+                #[doc = concat!(" assert_eq!(", $l, "13", $r, ".saturating_sub(127), 0);")]
             },
             to_string {
-                #[doc = concat!(" assert_eq!(10", $n, ".to_string(), \"10\");")]
+                #[doc = concat!(" assert_eq!(", $l, "10", $r, ".to_string(), \"10\");")]
             },
         }
     };
 
     (
-        inner $ty:ty, $n:expr,
+        inner $ty:ty, $n:expr, $l:expr, $r:expr,
         checked_div { $(#[$checked_div:meta])* },
         saturating_pow { $(#[$saturating_pow:meta])* },
         checked_rem { $(#[$checked_rem:meta])* },
@@ -130,10 +163,10 @@ macro_rules! unsigned_fns {
         /// # Examples
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(", $n, "::parse(\"10\")?, 10", $n, ");")]
+        #[doc = concat!(" assert_eq!(", $n, "::parse(\"10\")?, ", $l, "10", $r, ");")]
         /// ```
-        fn parse(s: &str) -> Result<$ty, ParseIntError> {
-            str::parse::<$ty>(s)
+        fn parse(s: &str) -> Result<Fixed<$ty>, ParseIntError> {
+            str::parse::<$ty>(s).map(Fixed)
         }
 
         #[doc = concat!(" Converts an `", $n, "` to a `f64`.")]
@@ -141,12 +174,12 @@ macro_rules! unsigned_fns {
         /// # Examples
         ///
         /// ```rune
-        #[doc = concat!(" assert!(10", $n, ".to::<f64>() is f64);")]
+        #[doc = concat!(" assert!(", $l, "10", $r, ".to::<f64>() is f64);")]
         /// ```
         #[rune::function(instance, path = to::<f64>)]
         #[inline]
-        fn to_float(value: $ty) -> f64 {
-            value as f64
+        fn to_float(value: Fixed<$ty>) -> f64 {
+            value.0 as f64
         }
 
         /// Compares and returns the maximum of two values.
@@ -157,13 +190,13 @@ macro_rules! unsigned_fns {
         /// # Examples
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(1", $n, ".max(2", $n, "), 2", $n, ");")]
-        #[doc = concat!(" assert_eq!(2", $n, ".max(2", $n, "), 2", $n, ");")]
+        #[doc = concat!(" assert_eq!(", $l, "1", $r, ".max(", $l, "2", $r, "), ", $l, "2", $r, ");")]
+        #[doc = concat!(" assert_eq!(", $l, "2", $r, ".max(", $l, "2", $r, "), ", $l, "2", $r, ");")]
         /// ```
         #[rune::function(keep, instance, protocol = MAX)]
         #[inline]
-        fn max(this: $ty, other: $ty) -> $ty {
-            <$ty>::max(this, other)
+        fn max(this: Fixed<$ty>, other: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::max(this.0, other.0))
         }
 
         /// Compares and returns the minimum of two values.
@@ -173,13 +206,13 @@ macro_rules! unsigned_fns {
         /// # Examples
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(1", $n, ".min(2", $n, "), 1", $n, ");")]
-        #[doc = concat!(" assert_eq!(2", $n, ".min(2", $n, "), 2", $n, ");")]
+        #[doc = concat!(" assert_eq!(", $l, "1", $r, ".min(", $l, "2", $r, "), ", $l, "1", $r, ");")]
+        #[doc = concat!(" assert_eq!(", $l, "2", $r, ".min(", $l, "2", $r, "), ", $l, "2", $r, ");")]
         /// ```
         #[rune::function(keep, instance, protocol = MIN)]
         #[inline]
-        fn min(this: $ty, other: $ty) -> $ty {
-            <$ty>::min(this, other)
+        fn min(this: Fixed<$ty>, other: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::min(this.0, other.0))
         }
 
         /// Raises self to the power of `exp`, using exponentiation by squaring.
@@ -199,8 +232,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn pow(this: $ty, pow: u32) -> $ty {
-            <$ty>::wrapping_pow(this, pow)
+        fn pow(this: Fixed<$ty>, pow: u32) -> Fixed<$ty> {
+            Fixed(<$ty>::wrapping_pow(this.0, pow))
         }
 
         /// Checked integer addition. Computes `self + rhs`, returning `None` if
@@ -216,8 +249,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn checked_add(this: $ty, rhs: $ty) -> Option<$ty> {
-            <$ty>::checked_add(this, rhs)
+        fn checked_add(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Option<Fixed<$ty>> {
+            <$ty>::checked_add(this.0, rhs.0).map(Fixed)
         }
 
         /// Checked integer subtraction. Computes `self - rhs`, returning `None` if
@@ -233,8 +266,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn checked_sub(this: $ty, rhs: $ty) -> Option<$ty> {
-            <$ty>::checked_sub(this, rhs)
+        fn checked_sub(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Option<Fixed<$ty>> {
+            <$ty>::checked_sub(this.0, rhs.0).map(Fixed)
         }
 
         /// Checked integer division. Computes `self / rhs`, returning `None` if
@@ -249,8 +282,8 @@ macro_rules! unsigned_fns {
         /// ``````
         #[rune::function(instance)]
         #[inline]
-        fn checked_div(this: $ty, rhs: $ty) -> Option<$ty> {
-            <$ty>::checked_div(this, rhs)
+        fn checked_div(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Option<Fixed<$ty>> {
+            <$ty>::checked_div(this.0, rhs.0).map(Fixed)
         }
 
         /// Checked integer multiplication. Computes `self * rhs`, returning `None` if
@@ -266,8 +299,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn checked_mul(this: $ty, rhs: $ty) -> Option<$ty> {
-            <$ty>::checked_mul(this, rhs)
+        fn checked_mul(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Option<Fixed<$ty>> {
+            <$ty>::checked_mul(this.0, rhs.0).map(Fixed)
         }
 
         /// Checked integer remainder. Computes `self % rhs`, returning `None` if `rhs
@@ -282,8 +315,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn checked_rem(this: $ty, rhs: $ty) -> Option<$ty> {
-            <$ty>::checked_rem(this, rhs)
+        fn checked_rem(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Option<Fixed<$ty>> {
+            <$ty>::checked_rem(this.0, rhs.0).map(Fixed)
         }
 
         /// Wrapping (modular) addition. Computes `self + rhs`, wrapping around at the
@@ -294,13 +327,13 @@ macro_rules! unsigned_fns {
         /// Basic usage:
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(100", $n, ".wrapping_add(27), 127", $n, ");")]
+        #[doc = concat!(" assert_eq!(", $l, "100", $r, ".wrapping_add(27), ", $l, "127", $r, ");")]
         #[doc = concat!(" assert_eq!(", $n, "::MAX.wrapping_add(2), ", $n, "::MIN + 1);")]
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn wrapping_add(this: $ty, rhs: $ty) -> $ty {
-            <$ty>::wrapping_add(this, rhs)
+        fn wrapping_add(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::wrapping_add(this.0, rhs.0))
         }
 
         /// Wrapping (modular) subtraction. Computes `self - rhs`, wrapping around at
@@ -315,8 +348,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn wrapping_sub(this: $ty, rhs: $ty) -> $ty {
-            <$ty>::wrapping_sub(this, rhs)
+        fn wrapping_sub(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::wrapping_sub(this.0, rhs.0))
         }
 
         /// Wrapping (modular) division. Computes `self / rhs`, wrapping around at the
@@ -336,16 +369,16 @@ macro_rules! unsigned_fns {
         /// Basic usage:
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(100", $n, ".wrapping_div(10), 10", $n, ");")]
+        #[doc = concat!(" assert_eq!(", $l, "100", $r, ".wrapping_div(10), ", $l, "10", $r, ");")]
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn wrapping_div(this: $ty, rhs: $ty) -> Result<$ty, $crate::runtime::VmError> {
-            if rhs == 0 {
+        fn wrapping_div(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Result<Fixed<$ty>, $crate::runtime::VmError> {
+            if rhs.0 == 0 {
                 return Err($crate::runtime::VmError::new($crate::runtime::VmErrorKind::DivideByZero));
             }
 
-            Ok(<$ty>::wrapping_div(this, rhs))
+            Ok(Fixed(<$ty>::wrapping_div(this.0, rhs.0)))
         }
 
         /// Wrapping (modular) multiplication. Computes `self * rhs`, wrapping around at
@@ -356,12 +389,12 @@ macro_rules! unsigned_fns {
         /// Basic usage:
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(10", $n, ".wrapping_mul(12), 120", $n, ");")]
+        #[doc = concat!(" assert_eq!(", $l, "10", $r, ".wrapping_mul(12), ", $l, "120", $r, ");")]
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn wrapping_mul(this: $ty, rhs: $ty) -> $ty {
-            <$ty>::wrapping_mul(this, rhs)
+        fn wrapping_mul(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::wrapping_mul(this.0, rhs.0))
         }
 
         /// Wrapping (modular) remainder. Computes `self % rhs`, wrapping around at the
@@ -380,16 +413,16 @@ macro_rules! unsigned_fns {
         /// Basic usage:
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(100", $n, ".wrapping_rem(10), 0);")]
+        #[doc = concat!(" assert_eq!(", $l, "100", $r, ".wrapping_rem(10), 0);")]
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn wrapping_rem(this: $ty, rhs: $ty) -> Result<$ty, $crate::runtime::VmError> {
-            if rhs == 0 {
+        fn wrapping_rem(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Result<Fixed<$ty>, $crate::runtime::VmError> {
+            if rhs.0 == 0 {
                 return Err($crate::runtime::VmError::new($crate::runtime::VmErrorKind::DivideByZero));
             }
 
-            Ok(<$ty>::wrapping_rem(this, rhs))
+            Ok(Fixed(<$ty>::wrapping_rem(this.0, rhs.0)))
         }
 
         /// Saturating integer addition. Computes `self + rhs`, saturating at the
@@ -404,8 +437,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn saturating_add(this: $ty, rhs: $ty) -> $ty {
-            <$ty>::saturating_add(this, rhs)
+        fn saturating_add(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::saturating_add(this.0, rhs.0))
         }
 
         /// Saturating integer subtraction. Computes `self - rhs`, saturating at the
@@ -420,8 +453,8 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn saturating_sub(this: $ty, rhs: $ty) -> $ty {
-            <$ty>::saturating_sub(this, rhs)
+        fn saturating_sub(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::saturating_sub(this.0, rhs.0))
         }
 
         /// Saturating integer multiplication. Computes `self * rhs`, saturating at the
@@ -432,14 +465,14 @@ macro_rules! unsigned_fns {
         /// Basic usage:
         ///
         /// ```rune
-        #[doc = concat!(" assert_eq!(10", $n, ".saturating_mul(12), 120);")]
+        #[doc = concat!(" assert_eq!(", $l, "10", $r, ".saturating_mul(12), 120);")]
         #[doc = concat!(" assert_eq!(", $n, "::MAX.saturating_mul(10), ", $n, "::MAX);")]
         #[doc = concat!(" assert_eq!(", $n, "::MIN.saturating_mul(10), ", $n, "::MIN);")]
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn saturating_mul(this: $ty, rhs: $ty) -> $ty {
-            <$ty>::saturating_mul(this, rhs)
+        fn saturating_mul(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::saturating_mul(this.0, rhs.0))
         }
 
         /// Saturating integer exponentiation. Computes `self.pow(exp)`, saturating at
@@ -454,8 +487,267 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn saturating_pow(this: $ty, rhs: u32) -> $ty {
-            <$ty>::saturating_pow(this, rhs)
+        fn saturating_pow(this: Fixed<$ty>, rhs: u32) -> Fixed<$ty> {
+            Fixed(<$ty>::saturating_pow(this.0, rhs))
+        }
+
+        /// Calculates `self + rhs`.
+        ///
+        /// Returns a tuple of the addition along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would have
+        /// occurred then the wrapped value is returned.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "5", $r, ".overflowing_add(2), (7, false));")]
+        #[doc = concat!(" assert_eq!(", $n, "::MAX.overflowing_add(1), (", $n, "::MIN, true));")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn overflowing_add(this: Fixed<$ty>, rhs: Fixed<$ty>) -> (Fixed<$ty>, bool) {
+            let (value, overflow) = <$ty>::overflowing_add(this.0, rhs.0);
+            (Fixed(value), overflow)
+        }
+
+        /// Calculates `self - rhs`.
+        ///
+        /// Returns a tuple of the subtraction along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would have
+        /// occurred then the wrapped value is returned.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "5", $r, ".overflowing_sub(2), (3, false));")]
+        #[doc = concat!(" assert_eq!(", $n, "::MIN.overflowing_sub(1), (", $n, "::MAX, true));")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn overflowing_sub(this: Fixed<$ty>, rhs: Fixed<$ty>) -> (Fixed<$ty>, bool) {
+            let (value, overflow) = <$ty>::overflowing_sub(this.0, rhs.0);
+            (Fixed(value), overflow)
+        }
+
+        /// Calculates the multiplication of `self` and `rhs`.
+        ///
+        /// Returns a tuple of the multiplication along with a boolean indicating
+        /// whether an arithmetic overflow would occur. If an overflow would have
+        /// occurred then the wrapped value is returned.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "5", $r, ".overflowing_mul(2), (10, false));")]
+        #[doc = concat!(" assert!(", $n, "::MAX.overflowing_mul(2).1);")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn overflowing_mul(this: Fixed<$ty>, rhs: Fixed<$ty>) -> (Fixed<$ty>, bool) {
+            let (value, overflow) = <$ty>::overflowing_mul(this.0, rhs.0);
+            (Fixed(value), overflow)
+        }
+
+        /// Checked exponentiation. Computes `self.pow(exp)`, returning `None` if
+        /// overflow occurred.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "2", $r, ".checked_pow(5), Some(32));")]
+        #[doc = concat!(" assert_eq!(", $n, "::MAX.checked_pow(2), None);")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn checked_pow(this: Fixed<$ty>, exp: u32) -> Option<Fixed<$ty>> {
+            <$ty>::checked_pow(this.0, exp).map(Fixed)
+        }
+
+        /// Wrapping (modular) exponentiation. Computes `self.pow(exp)`, wrapping
+        /// around at the boundary of the type.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "3", $r, ".wrapping_pow(3), 27);")]
+        #[doc = concat!(" assert_eq!(", $l, "2", $r, ".wrapping_pow(", $n, "::BITS), 0);")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn wrapping_pow(this: Fixed<$ty>, exp: u32) -> Fixed<$ty> {
+            Fixed(<$ty>::wrapping_pow(this.0, exp))
+        }
+
+        /// Returns the number of leading zeros in the binary representation of
+        /// `self`.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "0", $r, ".leading_zeros(), ", $n, "::BITS);")]
+        #[doc = concat!(" assert_eq!(", $l, "1", $r, ".leading_zeros(), ", $n, "::BITS - 1);")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn leading_zeros(this: Fixed<$ty>) -> u32 {
+            <$ty>::leading_zeros(this.0)
+        }
+
+        /// Returns the number of trailing zeros in the binary representation of
+        /// `self`.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "8", $r, ".trailing_zeros(), 3);")]
+        #[doc = concat!(" assert_eq!(", $l, "0", $r, ".trailing_zeros(), ", $n, "::BITS);")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn trailing_zeros(this: Fixed<$ty>) -> u32 {
+            <$ty>::trailing_zeros(this.0)
+        }
+
+        /// Returns the number of ones in the binary representation of `self`.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "7", $r, ".count_ones(), 3);")]
+        #[doc = concat!(" assert_eq!(", $l, "0", $r, ".count_ones(), 0);")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn count_ones(this: Fixed<$ty>) -> u32 {
+            <$ty>::count_ones(this.0)
+        }
+
+        /// Returns the number of zeros in the binary representation of `self`.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" assert_eq!(", $l, "7", $r, ".count_zeros(), ", $n, "::BITS - 3);")]
+        #[doc = concat!(" assert_eq!(", $l, "0", $r, ".count_zeros(), ", $n, "::BITS);")]
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn count_zeros(this: Fixed<$ty>) -> u32 {
+            <$ty>::count_zeros(this.0)
+        }
+
+        /// Returns the memory representation of this integer as a byte array in
+        /// big-endian (network) byte order.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" let bytes = ", $l, "1", $r, ".to_be_bytes();")]
+        #[doc = concat!(" assert_eq!(bytes.len(), ", $n, "::BITS / 8);")]
+        /// assert_eq!(bytes.last(), Some(1));
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn to_be_bytes(this: Fixed<$ty>) -> $crate::alloc::Result<$crate::runtime::Bytes> {
+            $crate::runtime::Bytes::from_slice(this.0.to_be_bytes())
+        }
+
+        /// Returns the memory representation of this integer as a byte array in
+        /// little-endian byte order.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" let bytes = ", $l, "1", $r, ".to_le_bytes();")]
+        #[doc = concat!(" assert_eq!(bytes.len(), ", $n, "::BITS / 8);")]
+        /// assert_eq!(bytes.first(), Some(1));
+        /// ```
+        #[rune::function(instance)]
+        #[inline]
+        fn to_le_bytes(this: Fixed<$ty>) -> $crate::alloc::Result<$crate::runtime::Bytes> {
+            $crate::runtime::Bytes::from_slice(this.0.to_le_bytes())
+        }
+
+        /// Creates an integer value from its representation as a byte array in
+        /// big endian.
+        ///
+        /// # Panics
+        ///
+        /// This panics if the number of bytes doesn't match the size of the
+        /// integer type.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" let bytes = ", $l, "42", $r, ".to_be_bytes();")]
+        #[doc = concat!(" assert_eq!(", $n, "::from_be_bytes(bytes), 42);")]
+        /// ```
+        ///
+        /// ```rune,should_panic
+        #[doc = concat!(" ", $n, "::from_be_bytes(b\"\");")]
+        /// ```
+        #[rune::function]
+        #[inline]
+        fn from_be_bytes(bytes: &$crate::runtime::Bytes) -> Result<Fixed<$ty>, $crate::runtime::VmError> {
+            let Ok(bytes) = bytes.as_slice().try_into() else {
+                return Err($crate::runtime::VmError::panic(concat!("wrong number of bytes for `", $n, "`")));
+            };
+
+            Ok(Fixed(<$ty>::from_be_bytes(bytes)))
+        }
+
+        /// Creates an integer value from its representation as a byte array in
+        /// little endian.
+        ///
+        /// # Panics
+        ///
+        /// This panics if the number of bytes doesn't match the size of the
+        /// integer type.
+        ///
+        /// # Examples
+        ///
+        /// Basic usage:
+        ///
+        /// ```rune
+        #[doc = concat!(" let bytes = ", $l, "42", $r, ".to_le_bytes();")]
+        #[doc = concat!(" assert_eq!(", $n, "::from_le_bytes(bytes), 42);")]
+        /// ```
+        ///
+        /// ```rune,should_panic
+        #[doc = concat!(" ", $n, "::from_le_bytes(b\"\");")]
+        /// ```
+        #[rune::function]
+        #[inline]
+        fn from_le_bytes(bytes: &$crate::runtime::Bytes) -> Result<Fixed<$ty>, $crate::runtime::VmError> {
+            let Ok(bytes) = bytes.as_slice().try_into() else {
+                return Err($crate::runtime::VmError::panic(concat!("wrong number of bytes for `", $n, "`")));
+            };
+
+            Ok(Fixed(<$ty>::from_le_bytes(bytes)))
         }
 
         #[doc = concat!(" Clone a `", $n, "`.")]
@@ -466,7 +758,7 @@ macro_rules! unsigned_fns {
         /// # Examples
         ///
         /// ```rune
-        #[doc = concat!(" let a = 5", $n, ";")]
+        #[doc = concat!(" let a = ", $l, "5", $r, ";")]
         /// let b = a;
         /// let c = a.clone();
         ///
@@ -478,7 +770,7 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(keep, instance, protocol = CLONE)]
         #[inline]
-        fn clone(this: $ty) -> $ty {
+        fn clone(this: Fixed<$ty>) -> Fixed<$ty> {
             this
         }
 
@@ -487,13 +779,13 @@ macro_rules! unsigned_fns {
         /// # Examples
         ///
         /// ```rune
-        #[doc = concat!("  assert_eq!(5", $n, " == 5, true);")]
-        #[doc = concat!("  assert_eq!(5", $n, " == 10, false);")]
-        #[doc = concat!("  assert_eq!(10", $n, " == 5, false);")]
+        #[doc = concat!("  assert_eq!(", $l, "5", $r, " == 5, true);")]
+        #[doc = concat!("  assert_eq!(", $l, "5", $r, " == 10, false);")]
+        #[doc = concat!("  assert_eq!(", $l, "10", $r, " == 5, false);")]
         /// ```
         #[rune::function(keep, instance, protocol = PARTIAL_EQ)]
         #[inline]
-        fn partial_eq(this: $ty, rhs: $ty) -> bool {
+        fn partial_eq(this: Fixed<$ty>, rhs: Fixed<$ty>) -> bool {
             this.eq(&rhs)
         }
 
@@ -504,13 +796,13 @@ macro_rules! unsigned_fns {
         /// ```rune
         /// use std::ops::eq;
         ///
-        #[doc = concat!("  assert_eq!(eq(5", $n, ", 5", $n, "), true);")]
-        #[doc = concat!("  assert_eq!(eq(5", $n, ", 10", $n, "), false);")]
-        #[doc = concat!("  assert_eq!(eq(10", $n, ", 5", $n, "), false);")]
+        #[doc = concat!("  assert_eq!(eq(", $l, "5", $r, ", ", $l, "5", $r, "), true);")]
+        #[doc = concat!("  assert_eq!(eq(", $l, "5", $r, ", ", $l, "10", $r, "), false);")]
+        #[doc = concat!("  assert_eq!(eq(", $l, "10", $r, ", ", $l, "5", $r, "), false);")]
         /// ```
         #[rune::function(keep, instance, protocol = EQ)]
         #[inline]
-        fn eq(this: $ty, rhs: $ty) -> bool {
+        fn eq(this: Fixed<$ty>, rhs: Fixed<$ty>) -> bool {
             this.eq(&rhs)
         }
 
@@ -522,13 +814,13 @@ macro_rules! unsigned_fns {
         /// use std::cmp::Ordering;
         /// use std::ops::partial_cmp;
         ///
-        #[doc = concat!(" assert_eq!(partial_cmp(5", $n, ", 10", $n, "), Some(Ordering::Less));")]
-        #[doc = concat!(" assert_eq!(partial_cmp(10", $n, ", 5", $n, "), Some(Ordering::Greater));")]
-        #[doc = concat!(" assert_eq!(partial_cmp(5", $n, ", 5", $n, "), Some(Ordering::Equal));")]
+        #[doc = concat!(" assert_eq!(partial_cmp(", $l, "5", $r, ", ", $l, "10", $r, "), Some(Ordering::Less));")]
+        #[doc = concat!(" assert_eq!(partial_cmp(", $l, "10", $r, ", ", $l, "5", $r, "), Some(Ordering::Greater));")]
+        #[doc = concat!(" assert_eq!(partial_cmp(", $l, "5", $r, ", ", $l, "5", $r, "), Some(Ordering::Equal));")]
         /// ```
         #[rune::function(keep, instance, protocol = PARTIAL_CMP)]
         #[inline]
-        fn partial_cmp(this: $ty, rhs: $ty) -> Option<Ordering> {
+        fn partial_cmp(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Option<Ordering> {
             this.partial_cmp(&rhs)
         }
 
@@ -540,13 +832,13 @@ macro_rules! unsigned_fns {
         /// use std::cmp::Ordering;
         /// use std::ops::cmp;
         ///
-        #[doc = concat!(" assert_eq!(cmp(5", $n, ", 10", $n, "), Ordering::Less);")]
-        #[doc = concat!(" assert_eq!(cmp(10", $n, ", 5", $n, "), Ordering::Greater);")]
-        #[doc = concat!(" assert_eq!(cmp(5", $n, ", 5", $n, "), Ordering::Equal);")]
+        #[doc = concat!(" assert_eq!(cmp(", $l, "5", $r, ", ", $l, "10", $r, "), Ordering::Less);")]
+        #[doc = concat!(" assert_eq!(cmp(", $l, "10", $r, ", ", $l, "5", $r, "), Ordering::Greater);")]
+        #[doc = concat!(" assert_eq!(cmp(", $l, "5", $r, ", ", $l, "5", $r, "), Ordering::Equal);")]
         /// ```
         #[rune::function(keep, instance, protocol = CMP)]
         #[inline]
-        fn cmp(this: $ty, rhs: $ty) -> Ordering {
+        fn cmp(this: Fixed<$ty>, rhs: Fixed<$ty>) -> Ordering {
             this.cmp(&rhs)
         }
 
@@ -561,15 +853,15 @@ macro_rules! unsigned_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn to_string(this: $ty) -> $crate::alloc::Result<$crate::alloc::String> {
-            this.try_to_string()
+        fn to_string(this: Fixed<$ty>) -> $crate::alloc::Result<$crate::alloc::String> {
+            this.0.try_to_string()
         }
     };
 }
 
 macro_rules! signed {
-    ($m:ident, $ty:ty) => {
-        unsigned!($m, $ty, stringify!($ty));
+    ($m:ident, $ty:ty, $min:literal, $max:literal) => {
+        unsigned!($m, $ty, stringify!($ty), $min, $max);
 
         $m.function_meta(abs)?;
         $m.function_meta(saturating_abs)?;
@@ -581,16 +873,20 @@ macro_rules! signed {
 
 macro_rules! signed_fns {
     ($ty:ty) => {
-        signed_fns!($ty, stringify!($ty));
+        signed_fns!($ty, stringify!($ty), "", stringify!($ty));
+    };
+
+    (cast $ty:ty) => {
+        signed_fns!($ty, stringify!($ty), "(", concat!(" as ", stringify!($ty), ")"));
     };
 
-    ($ty:ty, $n:expr) => {
+    ($ty:ty, $n:expr, $l:expr, $r:expr) => {
         unsigned_fns! {
-            inner $ty, $n,
+            inner $ty, $n, $l, $r,
             checked_div {
                 #[doc = concat!(" assert_eq!((", $n, "::MIN + 1).checked_div(-1), Some(", $n, "::MAX));")]
                 #[doc = concat!(" assert_eq!(", $n, "::MIN.checked_div(-1), None);")]
-                #[doc = concat!(" assert_eq!(1", $n, ".checked_div(0), None);")]
+                #[doc = concat!(" assert_eq!(", $l, "1", $r, ".checked_div(0), None);")]
             },
             saturating_pow {
                 /// assert_eq!((-4).saturating_pow(3), -64);
@@ -598,13 +894,13 @@ macro_rules! signed_fns {
                 #[doc = concat!(" assert_eq!(", $n, "::MIN.saturating_pow(3), ", $n, "::MIN);")]
             },
             checked_rem {
-                #[doc = concat!(" assert_eq!(5", $n, ".checked_rem(2), Some(1));")]
-                #[doc = concat!(" assert_eq!(5", $n, ".checked_rem(0), None);")]
+                #[doc = concat!(" assert_eq!(", $l, "5", $r, ".checked_rem(2), Some(1));")]
+                #[doc = concat!(" assert_eq!(", $l, "5", $r, ".checked_rem(0), None);")]
                 #[doc = concat!(" assert_eq!(", $n, "::MIN.checked_rem(-1), None);")]
             },
             wrapping_sub {
                 /// assert_eq!(0.wrapping_sub(127), -127);
-                #[doc = concat!(" assert_eq!((-2", $n, ").wrapping_sub(", $n, "::MAX), ", $n, "::MAX);")]
+                #[doc = concat!(" assert_eq!((-", $l, "2", $r, ").wrapping_sub(", $n, "::MAX), ", $n, "::MAX);")]
             },
             saturating_add {
                 /// assert_eq!(100.saturating_add(1), 101);
@@ -617,8 +913,8 @@ macro_rules! signed_fns {
                 #[doc = concat!(" assert_eq!(", $n, "::MAX.saturating_sub(-1), ", $n, "::MAX);")]
             },
             to_string {
-                #[doc = concat!(" assert_eq!((-10", $n, ").to_string(), \"-10\");")]
-                #[doc = concat!(" assert_eq!(10", $n, ".to_string(), \"10\");")]
+                #[doc = concat!(" assert_eq!((-", $l, "10", $r, ").to_string(), \"-10\");")]
+                #[doc = concat!(" assert_eq!(", $l, "10", $r, ".to_string(), \"10\");")]
             },
         }
 
@@ -640,8 +936,8 @@ macro_rules! signed_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn abs(this: $ty) -> $ty {
-            <$ty>::wrapping_abs(this)
+        fn abs(this: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::wrapping_abs(this.0))
         }
 
         /// Saturating absolute value. Computes `self.abs()`, returning `MAX` if `self
@@ -659,8 +955,8 @@ macro_rules! signed_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn saturating_abs(this: $ty) -> $ty {
-            <$ty>::saturating_abs(this)
+        fn saturating_abs(this: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::saturating_abs(this.0))
         }
 
         /// Returns a number representing sign of `self`.
@@ -680,8 +976,8 @@ macro_rules! signed_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn signum(this: $ty) -> $ty {
-            <$ty>::signum(this)
+        fn signum(this: Fixed<$ty>) -> Fixed<$ty> {
+            Fixed(<$ty>::signum(this.0))
         }
 
         /// Returns `true` if `self` is positive and `false` if the number is zero or
@@ -697,8 +993,8 @@ macro_rules! signed_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn is_positive(this: $ty) -> bool {
-            <$ty>::is_positive(this)
+        fn is_positive(this: Fixed<$ty>) -> bool {
+            <$ty>::is_positive(this.0)
         }
 
         /// Returns `true` if `self` is negative and `false` if the number is zero or
@@ -714,8 +1010,8 @@ macro_rules! signed_fns {
         /// ```
         #[rune::function(instance)]
         #[inline]
-        fn is_negative(this: $ty) -> bool {
-            <$ty>::is_negative(this)
+        fn is_negative(this: Fixed<$ty>) -> bool {
+            <$ty>::is_negative(this.0)
         }
     }
 }
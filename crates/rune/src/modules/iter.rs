@@ -3,7 +3,7 @@
 use crate as rune;
 use crate::alloc;
 use crate::alloc::prelude::*;
-use crate::modules::collections::{HashMap, HashSet, VecDeque};
+use crate::modules::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
 use crate::runtime::range::RangeIter;
 use crate::runtime::{
     Address, FromValue, Function, Inline, Object, Output, OwnedTuple, Protocol, Repr, TypeHash,
@@ -103,6 +103,81 @@ pub fn module() -> Result<Module, ContextError> {
     m.implement_trait::<Take>(rune::item!(::std::iter::DoubleEndedIterator))?;
     m.implement_trait::<Take>(rune::item!(::std::iter::ExactSizeIterator))?;
 
+    m.ty::<Zip>()?;
+    m.function_meta(Zip::next__meta)?;
+    m.function_meta(Zip::next_back__meta)?;
+    m.function_meta(Zip::size_hint__meta)?;
+    m.function_meta(Zip::len__meta)?;
+    m.implement_trait::<Zip>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Zip>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.implement_trait::<Zip>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<StepBy>()?;
+    m.function_meta(StepBy::next__meta)?;
+    m.function_meta(StepBy::next_back__meta)?;
+    m.function_meta(StepBy::size_hint__meta)?;
+    m.function_meta(StepBy::len__meta)?;
+    m.implement_trait::<StepBy>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<StepBy>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.implement_trait::<StepBy>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<TakeWhile>()?;
+    m.function_meta(TakeWhile::next__meta)?;
+    m.function_meta(TakeWhile::size_hint__meta)?;
+    m.implement_trait::<TakeWhile>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<SkipWhile>()?;
+    m.function_meta(SkipWhile::next__meta)?;
+    m.function_meta(SkipWhile::size_hint__meta)?;
+    m.implement_trait::<SkipWhile>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<MapWhile>()?;
+    m.function_meta(MapWhile::next__meta)?;
+    m.function_meta(MapWhile::size_hint__meta)?;
+    m.implement_trait::<MapWhile>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Scan>()?;
+    m.function_meta(Scan::next__meta)?;
+    m.function_meta(Scan::size_hint__meta)?;
+    m.implement_trait::<Scan>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Inspect>()?;
+    m.function_meta(Inspect::next__meta)?;
+    m.function_meta(Inspect::next_back__meta)?;
+    m.function_meta(Inspect::size_hint__meta)?;
+    m.function_meta(Inspect::len__meta)?;
+    m.implement_trait::<Inspect>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Inspect>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.implement_trait::<Inspect>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<Cycle>()?;
+    m.function_meta(Cycle::next__meta)?;
+    m.function_meta(Cycle::size_hint__meta)?;
+    m.implement_trait::<Cycle>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Flatten>()?;
+    m.function_meta(Flatten::next__meta)?;
+    m.function_meta(Flatten::next_back__meta)?;
+    m.function_meta(Flatten::size_hint__meta)?;
+    m.implement_trait::<Flatten>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Flatten>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
+    m.ty::<Chunks>()?;
+    m.function_meta(Chunks::next__meta)?;
+    m.function_meta(Chunks::next_back__meta)?;
+    m.function_meta(Chunks::size_hint__meta)?;
+    m.function_meta(Chunks::len__meta)?;
+    m.implement_trait::<Chunks>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Chunks>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.implement_trait::<Chunks>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<Windows>()?;
+    m.function_meta(Windows::next__meta)?;
+    m.function_meta(Windows::size_hint__meta)?;
+    m.function_meta(Windows::len__meta)?;
+    m.implement_trait::<Windows>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Windows>(rune::item!(::std::iter::ExactSizeIterator))?;
+
     {
         let mut t = m.define_trait(["ExactSizeIterator"])?;
 
@@ -331,6 +406,147 @@ pub fn module() -> Result<Module, ContextError> {
                 )?;
             }
 
+            {
+                let next = next.clone();
+
+                cx.function(
+                    "position",
+                    move |iter: Value, f: Function| -> Result<Option<usize>, VmError> {
+                        let mut n = 0usize;
+
+                        loop {
+                            let Some(value) = next.call((iter.clone(),))? else {
+                                break Ok(None);
+                            };
+
+                            if f.call::<bool>((value,))? {
+                                break Ok(Some(n));
+                            }
+
+                            n += 1;
+                        }
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function("last", move |iter: Value| {
+                    let mut last = None;
+
+                    while let Some(value) = next.call((iter.clone(),))? {
+                        last = Some(value);
+                    }
+
+                    Ok::<_, VmError>(last)
+                })?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function("min", move |iter: Value| {
+                    let Some(mut min) = next.call((iter.clone(),))? else {
+                        return Ok::<_, VmError>(None);
+                    };
+
+                    while let Some(value) = next.call((iter.clone(),))? {
+                        if Value::cmp(&value, &min)?.is_lt() {
+                            min = value;
+                        }
+                    }
+
+                    Ok(Some(min))
+                })?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function("max", move |iter: Value| {
+                    let Some(mut max) = next.call((iter.clone(),))? else {
+                        return Ok::<_, VmError>(None);
+                    };
+
+                    while let Some(value) = next.call((iter.clone(),))? {
+                        if Value::cmp(&value, &max)?.is_ge() {
+                            max = value;
+                        }
+                    }
+
+                    Ok(Some(max))
+                })?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function("min_by_key", move |iter: Value, f: Function| {
+                    let Some(mut min) = next.call((iter.clone(),))? else {
+                        return Ok::<_, VmError>(None);
+                    };
+
+                    let mut min_key = f.call::<Value>((min.clone(),))?;
+
+                    while let Some(value) = next.call((iter.clone(),))? {
+                        let key = f.call::<Value>((value.clone(),))?;
+
+                        if Value::cmp(&key, &min_key)?.is_lt() {
+                            min = value;
+                            min_key = key;
+                        }
+                    }
+
+                    Ok(Some(min))
+                })?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function("max_by_key", move |iter: Value, f: Function| {
+                    let Some(mut max) = next.call((iter.clone(),))? else {
+                        return Ok::<_, VmError>(None);
+                    };
+
+                    let mut max_key = f.call::<Value>((max.clone(),))?;
+
+                    while let Some(value) = next.call((iter.clone(),))? {
+                        let key = f.call::<Value>((value.clone(),))?;
+
+                        if Value::cmp(&key, &max_key)?.is_ge() {
+                            max = value;
+                            max_key = key;
+                        }
+                    }
+
+                    Ok(Some(max))
+                })?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    "partition",
+                    move |iter: Value, f: Function| -> Result<(Vec, Vec), VmError> {
+                        let mut left = Vec::new();
+                        let mut right = Vec::new();
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            if f.call::<bool>((value.clone(),))? {
+                                left.push(value)?;
+                            } else {
+                                right.push(value)?;
+                            }
+                        }
+
+                        Ok((left, right))
+                    },
+                )?;
+            }
+
             {
                 cx.function("chain", |a: Value, b: Value| -> Result<Chain, VmError> {
                     let b = b.protocol_into_iter()?;
@@ -364,6 +580,59 @@ pub fn module() -> Result<Module, ContextError> {
                 })?;
                 cx.function("skip", move |iter: Value, n: usize| Skip { iter, n })?;
                 cx.function("take", move |iter: Value, n: usize| Take { iter, n })?;
+                cx.function("zip", |a: Value, b: Value| -> Result<Zip, VmError> {
+                    let b = b.protocol_into_iter()?;
+                    Ok(Zip { a, b })
+                })?;
+                cx.function(
+                    "step_by",
+                    move |iter: Value, step: usize| -> Result<StepBy, VmError> {
+                        if step == 0 {
+                            return Err(VmError::panic("assertion failed: step != 0"));
+                        }
+
+                        Ok(StepBy {
+                            iter,
+                            step: step - 1,
+                            first_take: true,
+                        })
+                    },
+                )?;
+                cx.function("take_while", move |iter: Value, f: Function| TakeWhile {
+                    iter,
+                    f,
+                    done: false,
+                })?;
+                cx.function("skip_while", move |iter: Value, f: Function| SkipWhile {
+                    iter,
+                    f,
+                    done: false,
+                })?;
+                cx.function("map_while", move |iter: Value, f: Function| MapWhile {
+                    iter,
+                    f,
+                })?;
+                cx.function("scan", move |iter: Value, state: Value, f: Function| Scan {
+                    iter,
+                    state,
+                    f,
+                })?;
+                cx.function("inspect", move |iter: Value, f: Function| Inspect {
+                    iter,
+                    f,
+                })?;
+                cx.function("cycle", move |iter: Value| Cycle {
+                    iter: Some(iter),
+                    seen: alloc::Vec::new(),
+                    index: 0,
+                })?;
+                cx.function("flatten", move |iter: Value| Flatten {
+                    iter: Some(iter),
+                    frontiter: None,
+                    backiter: None,
+                })?;
+                cx.function("chunks", Chunks::new)?;
+                cx.function("windows", Windows::new)?;
             }
 
             {
@@ -442,6 +711,59 @@ pub fn module() -> Result<Module, ContextError> {
                 )?;
             }
 
+            {
+                let next = next.with_return::<Option<(Value, Value)>>();
+
+                cx.function(
+                    Params::new("collect", [BTreeMap::HASH]),
+                    move |iter: Value| -> Result<BTreeMap, VmError> {
+                        let mut map = BTreeMap::new();
+
+                        while let Some((key, value)) = next.call((iter.clone(),))? {
+                            map.insert(key, value)?;
+                        }
+
+                        Ok(map)
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    Params::new("collect", [BTreeSet::HASH]),
+                    move |iter: Value| -> Result<BTreeSet, VmError> {
+                        let mut set = BTreeSet::new();
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            set.insert(value)?;
+                        }
+
+                        Ok(set)
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+                let size_hint = size_hint.clone();
+
+                cx.function(
+                    Params::new("collect", [BinaryHeap::HASH]),
+                    move |iter: Value| -> Result<BinaryHeap, VmError> {
+                        let (cap, _) = size_hint.call((&iter,))?;
+                        let mut heap = BinaryHeap::with_capacity(cap)?;
+
+                        while let Some(value) = next.call((iter.clone(),))? {
+                            heap.push(value)?;
+                        }
+
+                        Ok(heap)
+                    },
+                )?;
+            }
+
             {
                 let next = next.with_return::<Option<(String, Value)>>();
                 let size_hint = size_hint.clone();
@@ -1032,118 +1354,300 @@ pub fn module() -> Result<Module, ContextError> {
                 /// ```
             })?;
 
-        t.function("chain")?
-            .argument_types::<(Value, Value)>()?
-            .argument_names(["self", "other"])?
-            .return_type::<Chain>()?
+        t.function("position")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "predicate"])?
+            .return_type::<Option<usize>>()?
             .docs(docstring! {
-                /// Takes two iterators and creates a new iterator over both in sequence.
+                /// Searches for an element in an iterator, returning its index.
                 ///
-                /// `chain()` will return a new iterator which will first iterate over
-                /// values from the first iterator and then over values from the second
-                /// iterator.
+                /// `position()` takes a closure that returns `true` or `false`.
+                /// It applies this closure to each element of the iterator, and
+                /// if one of them returns `true`, then `position()` returns
+                /// [`Some(index)`]. If all of them return `false`, it returns
+                /// [`None`].
                 ///
-                /// In other words, it links two iterators together, in a chain. 🔗
+                /// `position()` is short-circuiting; in other words, it will
+                /// stop processing as soon as it finds a `true`.
                 ///
-                /// [`once`] is commonly used to adapt a single value into a chain of other
-                /// kinds of iteration.
+                /// [`Some(index)`]: Some
                 ///
                 /// # Examples
                 ///
                 /// Basic usage:
                 ///
                 /// ```rune
-                /// let a1 = [1, 2, 3];
-                /// let a2 = [4, 5, 6];
+                /// let a = [1, 2, 3];
                 ///
-                /// let iter = a1.iter().chain(a2.iter());
+                /// assert_eq!(a.iter().position(|x| x == 2), Some(1));
                 ///
-                /// assert_eq!(iter.next(), Some(1));
-                /// assert_eq!(iter.next(), Some(2));
-                /// assert_eq!(iter.next(), Some(3));
-                /// assert_eq!(iter.next(), Some(4));
-                /// assert_eq!(iter.next(), Some(5));
-                /// assert_eq!(iter.next(), Some(6));
-                /// assert_eq!(iter.next(), None);
+                /// assert_eq!(a.iter().position(|x| x == 5), None);
                 /// ```
                 ///
-                /// Since the argument to `chain()` uses [`INTO_ITER`], we can pass anything
-                /// that can be converted into an [`Iterator`], not just an [`Iterator`] itself.
-                /// For example, slices (`[T]`) implement [`INTO_ITER`], and so can be passed to
-                /// `chain()` directly:
+                /// Stopping at the first `true`:
                 ///
                 /// ```rune
-                /// let s1 = [1, 2, 3];
-                /// let s2 = [4, 5, 6];
+                /// let a = [1, 2, 3, 4];
                 ///
-                /// let iter = s1.iter().chain(s2);
+                /// let iter = a.iter();
                 ///
-                /// assert_eq!(iter.next(), Some(1));
-                /// assert_eq!(iter.next(), Some(2));
+                /// assert_eq!(iter.position(|x| x >= 2), Some(1));
+                ///
+                /// // we can still use `iter`, as there are more elements.
                 /// assert_eq!(iter.next(), Some(3));
-                /// assert_eq!(iter.next(), Some(4));
-                /// assert_eq!(iter.next(), Some(5));
-                /// assert_eq!(iter.next(), Some(6));
-                /// assert_eq!(iter.next(), None);
-                /// ```
                 ///
-                /// [`INTO_ITER`]: protocol@INTO_ITER
+                /// // The returned index depends on iterator state
+                /// assert_eq!(iter.position(|x| x == 4), Some(0));
+                /// ```
             })?;
 
-        t.function("enumerate")?
+        t.function("last")?
             .argument_types::<(Value,)>()?
             .argument_names(["self"])?
-            .return_type::<Enumerate>()?
+            .return_type::<Option<Value>>()?
             .docs(docstring! {
-                /// Creates an iterator which gives the current iteration count as well as
-                /// the next value.
-                ///
-                /// The iterator returned yields pairs `(i, val)`, where `i` is the current
-                /// index of iteration and `val` is the value returned by the iterator.
+                /// Consumes the iterator, returning the last element.
                 ///
-                /// `enumerate()` keeps its count as a usize. If you want to count by a
-                /// different sized integer, the zip function provides similar
-                /// functionality.
+                /// This method will evaluate the iterator until it returns
+                /// [`None`]. While doing so, it keeps track of the current
+                /// element. After [`None`] is returned, `last()` will then
+                /// return the last element it saw.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// let a = ['a', 'b', 'c'];
+                /// let a = [1, 2, 3];
+                /// assert_eq!(a.iter().last(), Some(3));
                 ///
-                /// let iter = a.iter().enumerate();
+                /// let a = [1, 2, 3, 4, 5];
+                /// assert_eq!(a.iter().last(), Some(5));
                 ///
-                /// assert_eq!(iter.next(), Some((0u64, 'a')));
-                /// assert_eq!(iter.next(), Some((1u64, 'b')));
-                /// assert_eq!(iter.next(), Some((2u64, 'c')));
-                /// assert_eq!(iter.next(), None);
+                /// assert_eq!([].iter().last(), None);
                 /// ```
             })?;
 
-        t.function("filter")?
-            .argument_types::<(Value, Function)>()?
-            .argument_names(["self", "filter"])?
-            .return_type::<Filter>()?
+        t.function("min")?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<Option<Value>>()?
             .docs(docstring! {
-                /// Creates an iterator which uses a closure to determine if an element
-                /// should be yielded.
+                /// Returns the minimum element of an iterator.
                 ///
-                /// Given an element the closure must return `true` or `false`. The returned
-                /// iterator will yield only the elements for which the closure returns
-                /// `true`.
+                /// Elements are compared using the same ordering as the `cmp`
+                /// function. If several elements are equally minimum, the first
+                /// element is returned. If the iterator is empty, [`None`] is
+                /// returned.
                 ///
-                /// ```rune
-                /// let a = [0, 1, 2];
+                /// # Examples
                 ///
-                /// let iter = a.iter().filter(|x| x.is_positive());
+                /// ```rune
+                /// let a = [1, 2, 3];
+                /// let b = [];
                 ///
-                /// assert_eq!(iter.next(), Some(1));
-                /// assert_eq!(iter.next(), Some(2));
-                /// assert_eq!(iter.next(), None);
+                /// assert_eq!(a.iter().min(), Some(1));
+                /// assert_eq!(b.iter().min(), None);
                 /// ```
             })?;
 
-        t.function("map")?
-            .argument_types::<(Value, Function)>()?
+        t.function("max")?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<Option<Value>>()?
+            .docs(docstring! {
+                /// Returns the maximum element of an iterator.
+                ///
+                /// Elements are compared using the same ordering as the `cmp`
+                /// function. If several elements are equally maximum, the last
+                /// element is returned. If the iterator is empty, [`None`] is
+                /// returned.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [1, 2, 3];
+                /// let b = [];
+                ///
+                /// assert_eq!(a.iter().max(), Some(3));
+                /// assert_eq!(b.iter().max(), None);
+                /// ```
+            })?;
+
+        t.function("min_by_key")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<Option<Value>>()?
+            .docs(docstring! {
+                /// Returns the element that gives the minimum value from the
+                /// specified function.
+                ///
+                /// If several elements are equally minimum, the first element
+                /// is returned. If the iterator is empty, [`None`] is returned.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [-3, 0, 1, 5, -10];
+                /// assert_eq!(a.iter().min_by_key(|x| x.abs()), Some(0));
+                /// ```
+            })?;
+
+        t.function("max_by_key")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<Option<Value>>()?
+            .docs(docstring! {
+                /// Returns the element that gives the maximum value from the
+                /// specified function.
+                ///
+                /// If several elements are equally maximum, the last element is
+                /// returned. If the iterator is empty, [`None`] is returned.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [-3, 0, 1, 5, -10];
+                /// assert_eq!(a.iter().max_by_key(|x| x.abs()), Some(-10));
+                /// ```
+            })?;
+
+        t.function("partition")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<(Vec, Vec)>()?
+            .docs(docstring! {
+                /// Consumes an iterator, creating two vectors from it.
+                ///
+                /// The predicate passed to `partition()` can return `true`, or
+                /// `false`. `partition()` returns a pair, all of the elements
+                /// for which it returned `true`, and all of the elements for
+                /// which it returned `false`.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [1, 2, 3];
+                ///
+                /// let (even, odd) = a.iter().partition(|n| n % 2 == 0);
+                ///
+                /// assert_eq!(even, [2]);
+                /// assert_eq!(odd, [1, 3]);
+                /// ```
+            })?;
+
+        t.function("chain")?
+            .argument_types::<(Value, Value)>()?
+            .argument_names(["self", "other"])?
+            .return_type::<Chain>()?
+            .docs(docstring! {
+                /// Takes two iterators and creates a new iterator over both in sequence.
+                ///
+                /// `chain()` will return a new iterator which will first iterate over
+                /// values from the first iterator and then over values from the second
+                /// iterator.
+                ///
+                /// In other words, it links two iterators together, in a chain. 🔗
+                ///
+                /// [`once`] is commonly used to adapt a single value into a chain of other
+                /// kinds of iteration.
+                ///
+                /// # Examples
+                ///
+                /// Basic usage:
+                ///
+                /// ```rune
+                /// let a1 = [1, 2, 3];
+                /// let a2 = [4, 5, 6];
+                ///
+                /// let iter = a1.iter().chain(a2.iter());
+                ///
+                /// assert_eq!(iter.next(), Some(1));
+                /// assert_eq!(iter.next(), Some(2));
+                /// assert_eq!(iter.next(), Some(3));
+                /// assert_eq!(iter.next(), Some(4));
+                /// assert_eq!(iter.next(), Some(5));
+                /// assert_eq!(iter.next(), Some(6));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// Since the argument to `chain()` uses [`INTO_ITER`], we can pass anything
+                /// that can be converted into an [`Iterator`], not just an [`Iterator`] itself.
+                /// For example, slices (`[T]`) implement [`INTO_ITER`], and so can be passed to
+                /// `chain()` directly:
+                ///
+                /// ```rune
+                /// let s1 = [1, 2, 3];
+                /// let s2 = [4, 5, 6];
+                ///
+                /// let iter = s1.iter().chain(s2);
+                ///
+                /// assert_eq!(iter.next(), Some(1));
+                /// assert_eq!(iter.next(), Some(2));
+                /// assert_eq!(iter.next(), Some(3));
+                /// assert_eq!(iter.next(), Some(4));
+                /// assert_eq!(iter.next(), Some(5));
+                /// assert_eq!(iter.next(), Some(6));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// [`INTO_ITER`]: protocol@INTO_ITER
+            })?;
+
+        t.function("enumerate")?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<Enumerate>()?
+            .docs(docstring! {
+                /// Creates an iterator which gives the current iteration count as well as
+                /// the next value.
+                ///
+                /// The iterator returned yields pairs `(i, val)`, where `i` is the current
+                /// index of iteration and `val` is the value returned by the iterator.
+                ///
+                /// `enumerate()` keeps its count as a usize. If you want to count by a
+                /// different sized integer, the zip function provides similar
+                /// functionality.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = ['a', 'b', 'c'];
+                ///
+                /// let iter = a.iter().enumerate();
+                ///
+                /// assert_eq!(iter.next(), Some((0u64, 'a')));
+                /// assert_eq!(iter.next(), Some((1u64, 'b')));
+                /// assert_eq!(iter.next(), Some((2u64, 'c')));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+            })?;
+
+        t.function("filter")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "filter"])?
+            .return_type::<Filter>()?
+            .docs(docstring! {
+                /// Creates an iterator which uses a closure to determine if an element
+                /// should be yielded.
+                ///
+                /// Given an element the closure must return `true` or `false`. The returned
+                /// iterator will yield only the elements for which the closure returns
+                /// `true`.
+                ///
+                /// ```rune
+                /// let a = [0, 1, 2];
+                ///
+                /// let iter = a.iter().filter(|x| x.is_positive());
+                ///
+                /// assert_eq!(iter.next(), Some(1));
+                /// assert_eq!(iter.next(), Some(2));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+            })?;
+
+        t.function("map")?
+            .argument_types::<(Value, Function)>()?
             .argument_names(["self", "f"])?
             .return_type::<Map>()?
             .docs(docstring! {
@@ -1397,238 +1901,749 @@ pub fn module() -> Result<Module, ContextError> {
                 /// ```
             })?;
 
-        macro_rules! sum_ops {
-            ($ty:ty) => {
-                t.function(Params::new("sum", [<$ty>::HASH]))?
-                    .argument_types::<(Value,)>()?
-                    .argument_names(["self"])?
-                    .return_type::<$ty>()?
-                    .docs(docstring! {
-                        /// Sums the elements of an iterator.
-                        ///
-                        /// Takes each element, adds them together, and returns
-                        /// the result.
-                        ///
-                        /// An empty iterator returns the zero value of the
-                        /// type.
-                        ///
-                        /// `sum()` can be used to sum numerical built-in types,
-                        /// such as `i64`, `float` and `u64`. The first element
-                        /// returned by the iterator determines the type being
-                        /// summed.
-                        ///
-                        /// # Panics
-                        ///
-                        /// When calling `sum()` and a primitive integer type is
-                        /// being returned, this method will panic if the
-                        /// computation overflows.
-                        ///
-                        /// # Examples
-                        ///
-                        /// Basic usage:
-                        ///
-                        /// ```rune
-                        #[doc = concat!(" let a = [1", stringify!($ty), ", 2", stringify!($ty), ", 3", stringify!($ty), "];")]
-                        #[doc = concat!(" let sum = a.iter().sum::<", stringify!($ty), ">();")]
-                        ///
-                        #[doc = concat!(" assert_eq!(sum, 6", stringify!($ty), ");")]
-                        /// ```
-                    })?;
-            };
-        }
-
-        sum_ops!(u64);
-        sum_ops!(i64);
-        sum_ops!(f64);
-
-        macro_rules! integer_product_ops {
-            ($ty:ty) => {
-                t.function(Params::new("product", [<$ty>::HASH]))?
-                    .argument_types::<(Value,)>()?
-                    .argument_names(["self"])?
-                    .return_type::<$ty>()?
-                    .docs(docstring! {
-                        /// Iterates over the entire iterator, multiplying all
-                        /// the elements
-                        ///
-                        /// An empty iterator returns the one value of the type.
-                        ///
-                        /// `sum()` can be used to sum numerical built-in types,
-                        /// such as `i64`, `f64` and `u64`. The first element
-                        /// returned by the iterator determines the type being
-                        /// multiplied.
-                        ///
-                        /// # Panics
-                        ///
-                        /// When calling `product()` and a primitive integer
-                        /// type is being returned, method will panic if the
-                        /// computation overflows.
-                        ///
-                        /// # Examples
-                        ///
-                        /// ```rune
-                        /// fn factorial(n) {
-                        #[doc = concat!("     (1", stringify!($ty), "..=n).iter().product::<", stringify!($ty), ">()")]
-                        /// }
-                        ///
-                        #[doc = concat!(" assert_eq!(factorial(0", stringify!($ty), "), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(1", stringify!($ty), "), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(5", stringify!($ty), "), 120", stringify!($ty), ");")]
-                        /// ```
-                    })?;
-            };
-        }
-
-        t.function(Params::new("collect", [Vec::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<Vec>()?
+        t.function("zip")?
+            .argument_types::<(Value, Value)>()?
+            .argument_names(["self", "other"])?
+            .return_type::<Zip>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`Vec`].
+                /// 'Zips up' two iterators into a single iterator of pairs.
                 ///
-                /// # Examples
+                /// `zip()` returns a new iterator that will iterate over two
+                /// other iterators, returning a tuple where the first element
+                /// comes from the first iterator, and the second element comes
+                /// from the second iterator.
                 ///
-                /// ```rune
-                /// use std::iter::range;
+                /// In other words, it zips two iterators together, into a
+                /// single one.
                 ///
-                /// assert_eq!((0..3).iter().collect::<Vec>(), [0, 1, 2]);
+                /// If either iterator returns [`None`], [`next`] from the
+                /// zipped iterator will return [`None`]. If the zipped iterator
+                /// has no more elements to return then each further attempt to
+                /// advance it will first try to advance the first iterator at
+                /// most one time and if it still yielded an item try to advance
+                /// the second iterator at most one time.
+                ///
+                /// The argument can be anything which can be converted into an
+                /// iterator.
+                ///
+                /// [`next`]: Iterator::next
+                ///
+                /// # Examples
+                ///
+                /// Basic usage:
+                ///
+                /// ```rune
+                /// let a1 = [1, 2, 3];
+                /// let a2 = [4, 5, 6];
+                ///
+                /// let iter = a1.iter().zip(a2);
+                ///
+                /// assert_eq!(iter.next(), Some((1, 4)));
+                /// assert_eq!(iter.next(), Some((2, 5)));
+                /// assert_eq!(iter.next(), Some((3, 6)));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// If both iterators have an exact length, the zipped iterator
+                /// can be iterated from the back:
+                ///
+                /// ```rune
+                /// let iter = (0..5).iter().zip(['a', 'b', 'c']);
+                ///
+                /// assert_eq!(iter.len(), 3);
+                /// assert_eq!(iter.next_back(), Some((2, 'c')));
+                /// assert_eq!(iter.rev().collect::<Vec>(), [(1, 'b'), (0, 'a')]);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [VecDeque::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<VecDeque>()?
+        t.function("step_by")?
+            .argument_types::<(Value, usize)>()?
+            .argument_names(["self", "step"])?
+            .return_type::<StepBy>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`VecDeque`].
+                /// Creates an iterator starting at the same point, but stepping
+                /// by the given amount at each iteration.
+                ///
+                /// Note 1: The first element of the iterator will always be
+                /// returned, regardless of the step given.
+                ///
+                /// Note 2: The time at which ignored elements are pulled is not
+                /// fixed. `StepBy` behaves like the sequence `self.next()`,
+                /// `self.nth(step-1)`, `self.nth(step-1)`, …
+                ///
+                /// # Panics
+                ///
+                /// The method will panic if the given step is `0`.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// use std::collections::VecDeque;
+                /// let a = [0, 1, 2, 3, 4, 5];
+                /// let iter = a.iter().step_by(2);
                 ///
-                /// assert_eq!((0..3).iter().collect::<VecDeque>(), VecDeque::from::<Vec>([0, 1, 2]));
+                /// assert_eq!(iter.next(), Some(0));
+                /// assert_eq!(iter.next(), Some(2));
+                /// assert_eq!(iter.next(), Some(4));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// Stepping from the back lines up with stepping from the
+                /// front:
+                ///
+                /// ```rune
+                /// let iter = (0..10).iter().step_by(3);
+                ///
+                /// assert_eq!(iter.len(), 4);
+                /// assert_eq!(iter.rev().collect::<Vec>(), [9, 6, 3, 0]);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [HashSet::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<HashSet>()?
+        t.function("take_while")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "predicate"])?
+            .return_type::<TakeWhile>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`HashSet`].
+                /// Creates an iterator that yields elements based on a
+                /// predicate.
+                ///
+                /// `take_while()` takes a closure as an argument. It will call
+                /// this closure on each element of the iterator, and yield
+                /// elements while it returns `true`.
+                ///
+                /// After `false` is returned, `take_while()`'s job is over, and
+                /// the rest of the elements are ignored. Note that the element
+                /// which failed the predicate is consumed from the underlying
+                /// iterator.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// use std::collections::HashSet;
+                /// let a = [-1, 0, 1];
                 ///
-                /// let a = (0..3).iter().collect::<HashSet>();
-                /// let b = HashSet::from_iter([0, 1, 2]);
+                /// let iter = a.iter().take_while(|x| x < 0);
                 ///
-                /// assert_eq!(a, b);
+                /// assert_eq!(iter.next(), Some(-1));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// Stopping after an initial `false`:
+                ///
+                /// ```rune
+                /// let a = [-1, 0, 1, -2];
+                ///
+                /// let iter = a.iter().take_while(|x| x < 0);
+                ///
+                /// assert_eq!(iter.next(), Some(-1));
+                ///
+                /// // We have more elements that are less than zero, but since we already
+                /// // got a false, take_while() isn't used any more
+                /// assert_eq!(iter.next(), None);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [HashMap::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<HashMap>()?
+        t.function("skip_while")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "predicate"])?
+            .return_type::<SkipWhile>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`HashMap`].
+                /// Creates an iterator that skips elements based on a
+                /// predicate.
+                ///
+                /// `skip_while()` takes a closure as an argument. It will call
+                /// this closure on each element of the iterator, and ignore
+                /// elements until it returns `false`.
+                ///
+                /// After `false` is returned, `skip_while()`'s job is over, and
+                /// the rest of the elements are yielded.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// use std::collections::HashMap;
+                /// let a = [-1, 0, 1];
                 ///
-                /// let actual = (0..3).iter().map(|n| (n, n.to_string())).collect::<HashMap>();
+                /// let iter = a.iter().skip_while(|x| x < 0);
                 ///
-                /// let expected = HashMap::from_iter([
-                ///     (0, "0"),
-                ///     (1, "1"),
-                ///     (2, "2"),
-                /// ]);
+                /// assert_eq!(iter.next(), Some(0));
+                /// assert_eq!(iter.next(), Some(1));
+                /// assert_eq!(iter.next(), None);
+                /// ```
                 ///
-                /// assert_eq!(actual, expected);
+                /// After the first `false`:
+                ///
+                /// ```rune
+                /// let a = [-1, 0, 1, -2];
+                ///
+                /// let iter = a.iter().skip_while(|x| x < 0);
+                ///
+                /// assert_eq!(iter.next(), Some(0));
+                /// assert_eq!(iter.next(), Some(1));
+                ///
+                /// // while this would have been false, since we already got a false,
+                /// // skip_while() isn't used any more
+                /// assert_eq!(iter.next(), Some(-2));
+                /// assert_eq!(iter.next(), None);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [Object::HASH]))?
-            .argument_types::<(Value,)>()?
-            .argument_names(["self"])?
-            .return_type::<HashMap>()?
+        t.function("map_while")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "predicate"])?
+            .return_type::<MapWhile>()?
             .docs(docstring! {
-                /// Collect the iterator as an [`Object`].
+                /// Creates an iterator that both yields elements based on a
+                /// predicate and maps.
+                ///
+                /// `map_while()` takes a closure as an argument. It will call
+                /// this closure on each element of the iterator, and yield
+                /// elements while it returns [`Some(_)`][`Some`].
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// assert_eq!([("first", 1), ("second", 2)].iter().collect::<Object>(), #{first: 1, second: 2});
+                /// let a = [-1, 4, 0, 1];
+                ///
+                /// let iter = a.iter().map_while(|x| if x != 0 { Some(16 / x) } else { None });
+                ///
+                /// assert_eq!(iter.next(), Some(-16));
+                /// assert_eq!(iter.next(), Some(4));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// Stopping after an initial [`None`]:
+                ///
+                /// ```rune
+                /// let a = [0, 1, 2, -3, 4, 5, -6];
+                ///
+                /// let iter = a.iter().map_while(|x| if x >= 0 { Some(x * 2) } else { None });
+                /// assert_eq!(iter.collect::<Vec>(), [0, 2, 4]);
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [OwnedTuple::HASH]))?
+        t.function("scan")?
+            .argument_types::<(Value, Value, Function)>()?
+            .argument_names(["self", "initial_state", "f"])?
+            .return_type::<Scan>()?
+            .docs(docstring! {
+                /// An iterator adapter which, like [`fold`], holds internal
+                /// state, but unlike [`fold`], produces a new iterator.
+                ///
+                /// [`fold`]: Iterator::fold
+                ///
+                /// `scan()` takes two arguments: an initial value which seeds
+                /// the internal state, and a closure with two arguments, the
+                /// first being the internal state and the second an iterator
+                /// element. The closure can modify the internal state to share
+                /// it between iterations, which means that the state should be
+                /// a value which can be mutated in place, like an object or a
+                /// vector.
+                ///
+                /// On iteration, the closure will be applied to each element of
+                /// the iterator and the return value from the closure, an
+                /// [`Option`], is returned by the `next` method. Thus the
+                /// closure can return `Some(value)` to yield `value`, or `None`
+                /// to end the iteration.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let a = [1, 2, 3, 4];
+                ///
+                /// let iter = a.iter().scan(#{ acc: 1 }, |state, x| {
+                ///     // each iteration, we'll multiply the state by the element ...
+                ///     state.acc = state.acc * x;
+                ///
+                ///     // ... and terminate if the state exceeds 6
+                ///     if state.acc > 6 {
+                ///         return None;
+                ///     }
+                ///
+                ///     // ... else yield the negation of the state
+                ///     Some(-state.acc)
+                /// });
+                ///
+                /// assert_eq!(iter.next(), Some(-1));
+                /// assert_eq!(iter.next(), Some(-2));
+                /// assert_eq!(iter.next(), Some(-6));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+            })?;
+
+        t.function("inspect")?
+            .argument_types::<(Value, Function)>()?
+            .argument_names(["self", "f"])?
+            .return_type::<Inspect>()?
+            .docs(docstring! {
+                /// Does something with each element of an iterator, passing the
+                /// value on.
+                ///
+                /// When using iterators, you'll often chain several of them
+                /// together. While working on such code, you might want to
+                /// check out what's happening at various parts in the pipeline.
+                /// To do that, insert a call to `inspect()`.
+                ///
+                /// # Examples
+                ///
+                /// Basic usage:
+                ///
+                /// ```rune
+                /// let seen = [];
+                ///
+                /// let sum = [1, 4, 2, 3].iter()
+                ///     .inspect(|x| seen.push(x))
+                ///     .filter(|x| x % 2 == 0)
+                ///     .fold(0, |sum, i| sum + i);
+                ///
+                /// assert_eq!(sum, 6);
+                /// assert_eq!(seen, [1, 4, 2, 3]);
+                /// ```
+            })?;
+
+        t.function("cycle")?
             .argument_types::<(Value,)>()?
             .argument_names(["self"])?
-            .return_type::<OwnedTuple>()?
+            .return_type::<Cycle>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`Tuple`].
+                /// Repeats an iterator endlessly.
+                ///
+                /// Instead of stopping at [`None`], the iterator will instead
+                /// start again, from the beginning. After iterating again, it
+                /// will start at the beginning again. And again. And again.
+                /// Forever. Note that in case the original iterator is empty,
+                /// the resulting iterator will also be empty.
+                ///
+                /// Values are remembered as they are produced by the original
+                /// iterator and replayed once it's exhausted, so the original
+                /// iterator is only ever consumed once.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// assert_eq!((0..3).iter().collect::<Tuple>(), (0, 1, 2));
+                /// let a = [1, 2, 3];
+                ///
+                /// let it = a.iter().cycle();
+                ///
+                /// assert_eq!(it.next(), Some(1));
+                /// assert_eq!(it.next(), Some(2));
+                /// assert_eq!(it.next(), Some(3));
+                /// assert_eq!(it.next(), Some(1));
+                /// assert_eq!(it.next(), Some(2));
+                /// assert_eq!(it.next(), Some(3));
+                /// assert_eq!(it.next(), Some(1));
                 /// ```
             })?;
 
-        t.function(Params::new("collect", [String::HASH]))?
+        t.function("flatten")?
             .argument_types::<(Value,)>()?
             .argument_names(["self"])?
-            .return_type::<String>()?
+            .return_type::<Flatten>()?
             .docs(docstring! {
-                /// Collect the iterator as a [`String`].
+                /// Creates an iterator that flattens nested structure.
+                ///
+                /// This is useful when you have an iterator of iterators or an
+                /// iterator of things that can be turned into iterators and you
+                /// want to remove one level of indirection.
                 ///
                 /// # Examples
                 ///
+                /// Basic usage:
+                ///
                 /// ```rune
-                /// assert_eq!(["first", "second"].iter().collect::<String>(), "firstsecond");
+                /// let data = [[1, 2, 3, 4], [5, 6]];
+                /// let flattened = data.iter().flatten().collect::<Vec>();
+                /// assert_eq!(flattened, [1, 2, 3, 4, 5, 6]);
+                /// ```
+                ///
+                /// Mapping and then flattening:
+                ///
+                /// ```rune
+                /// let words = ["alpha", "beta", "gamma"];
+                ///
+                /// let merged = words.iter()
+                ///     .map(|s| s.chars())
+                ///     .flatten()
+                ///     .collect::<String>();
+                ///
+                /// assert_eq!(merged, "alphabetagamma");
+                /// ```
+                ///
+                /// Flattening works from the back as well:
+                ///
+                /// ```rune
+                /// let data = [[1, 2], [], [3]];
+                /// let flattened = data.iter().flatten().rev().collect::<Vec>();
+                /// assert_eq!(flattened, [3, 2, 1]);
                 /// ```
             })?;
 
-        macro_rules! float_product_ops {
+        t.function("chunks")?
+            .argument_types::<(Value, usize)>()?
+            .argument_names(["self", "chunk_size"])?
+            .return_type::<Chunks>()?
+            .docs(docstring! {
+                /// Returns an iterator over `chunk_size` elements of the
+                /// iterator at a time, collected into vectors.
+                ///
+                /// The chunks do not overlap. If `chunk_size` does not divide
+                /// the length of the iterator, then the last chunk will not
+                /// have length `chunk_size`.
+                ///
+                /// Iterating from the back requires the underlying iterator to
+                /// know its exact length, so that the chunks line up with the
+                /// ones produced from the front.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `chunk_size` is 0.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let iter = ['l', 'o', 'r', 'e', 'm'].iter().chunks(2);
+                ///
+                /// assert_eq!(iter.next(), Some(['l', 'o']));
+                /// assert_eq!(iter.next(), Some(['r', 'e']));
+                /// assert_eq!(iter.next(), Some(['m']));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// ```rune
+                /// let iter = (0..7).iter().chunks(3);
+                ///
+                /// assert_eq!(iter.len(), 3);
+                /// assert_eq!(iter.next_back(), Some([6]));
+                /// assert_eq!(iter.next_back(), Some([3, 4, 5]));
+                /// assert_eq!(iter.next(), Some([0, 1, 2]));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+            })?;
+
+        t.function("windows")?
+            .argument_types::<(Value, usize)>()?
+            .argument_names(["self", "size"])?
+            .return_type::<Windows>()?
+            .docs(docstring! {
+                /// Returns an iterator over all contiguous windows of length
+                /// `size`, collected into vectors. The windows overlap. If the
+                /// iterator is shorter than `size`, the iterator returns no
+                /// values.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `size` is 0.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// let iter = ['l', 'o', 'r', 'e', 'm'].iter().windows(3);
+                ///
+                /// assert_eq!(iter.len(), 3);
+                /// assert_eq!(iter.next(), Some(['l', 'o', 'r']));
+                /// assert_eq!(iter.next(), Some(['o', 'r', 'e']));
+                /// assert_eq!(iter.next(), Some(['r', 'e', 'm']));
+                /// assert_eq!(iter.next(), None);
+                /// ```
+                ///
+                /// If the iterator is shorter than `size`:
+                ///
+                /// ```rune
+                /// let iter = ['f', 'o', 'o'].iter().windows(4);
+                /// assert_eq!(iter.next(), None);
+                /// ```
+            })?;
+
+        macro_rules! sum_ops {
             ($ty:ty) => {
-                t.function(Params::new("product", [<$ty>::HASH]))?
+                t.function(Params::new("sum", [<$ty>::HASH]))?
                     .argument_types::<(Value,)>()?
                     .argument_names(["self"])?
                     .return_type::<$ty>()?
                     .docs(docstring! {
-                        /// Iterates over the entire iterator, multiplying all
-                        /// the elements
+                        /// Sums the elements of an iterator.
                         ///
-                        /// An empty iterator returns the one value of the type.
+                        /// Takes each element, adds them together, and returns
+                        /// the result.
+                        ///
+                        /// An empty iterator returns the zero value of the
+                        /// type.
                         ///
                         /// `sum()` can be used to sum numerical built-in types,
-                        /// such as `i64`, `f64` and `u64`. The first element
+                        /// such as `i64`, `float` and `u64`. The first element
                         /// returned by the iterator determines the type being
-                        /// multiplied.
+                        /// summed.
                         ///
                         /// # Panics
                         ///
-                        /// When calling `product()` and a primitive integer
-                        /// type is being returned, method will panic if the
+                        /// When calling `sum()` and a primitive integer type is
+                        /// being returned, this method will panic if the
                         /// computation overflows.
                         ///
                         /// # Examples
                         ///
+                        /// Basic usage:
+                        ///
                         /// ```rune
-                        /// fn factorial(n) {
-                        #[doc = concat!("     (1..=n).iter().map(|n| n as ", stringify!($ty), ").product::<", stringify!($ty), ">()")]
-                        /// }
+                        #[doc = concat!(" let a = [1", stringify!($ty), ", 2", stringify!($ty), ", 3", stringify!($ty), "];")]
+                        #[doc = concat!(" let sum = a.iter().sum::<", stringify!($ty), ">();")]
                         ///
-                        #[doc = concat!(" assert_eq!(factorial(0), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(1), 1", stringify!($ty), ");")]
-                        #[doc = concat!(" assert_eq!(factorial(5), 120", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(sum, 6", stringify!($ty), ");")]
+                        /// ```
+                    })?;
+            };
+        }
+
+        sum_ops!(u64);
+        sum_ops!(i64);
+        sum_ops!(f64);
+
+        macro_rules! integer_product_ops {
+            ($ty:ty) => {
+                t.function(Params::new("product", [<$ty>::HASH]))?
+                    .argument_types::<(Value,)>()?
+                    .argument_names(["self"])?
+                    .return_type::<$ty>()?
+                    .docs(docstring! {
+                        /// Iterates over the entire iterator, multiplying all
+                        /// the elements
+                        ///
+                        /// An empty iterator returns the one value of the type.
+                        ///
+                        /// `sum()` can be used to sum numerical built-in types,
+                        /// such as `i64`, `f64` and `u64`. The first element
+                        /// returned by the iterator determines the type being
+                        /// multiplied.
+                        ///
+                        /// # Panics
+                        ///
+                        /// When calling `product()` and a primitive integer
+                        /// type is being returned, method will panic if the
+                        /// computation overflows.
+                        ///
+                        /// # Examples
+                        ///
+                        /// ```rune
+                        /// fn factorial(n) {
+                        #[doc = concat!("     (1", stringify!($ty), "..=n).iter().product::<", stringify!($ty), ">()")]
+                        /// }
+                        ///
+                        #[doc = concat!(" assert_eq!(factorial(0", stringify!($ty), "), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(1", stringify!($ty), "), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(5", stringify!($ty), "), 120", stringify!($ty), ");")]
+                        /// ```
+                    })?;
+            };
+        }
+
+        t.function(Params::new("collect", [Vec::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<Vec>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`Vec`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::iter::range;
+                ///
+                /// assert_eq!((0..3).iter().collect::<Vec>(), [0, 1, 2]);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [VecDeque::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<VecDeque>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`VecDeque`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::VecDeque;
+                ///
+                /// assert_eq!((0..3).iter().collect::<VecDeque>(), VecDeque::from::<Vec>([0, 1, 2]));
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [HashSet::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<HashSet>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`HashSet`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::HashSet;
+                ///
+                /// let a = (0..3).iter().collect::<HashSet>();
+                /// let b = HashSet::from_iter([0, 1, 2]);
+                ///
+                /// assert_eq!(a, b);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [HashMap::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<HashMap>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`HashMap`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::HashMap;
+                ///
+                /// let actual = (0..3).iter().map(|n| (n, n.to_string())).collect::<HashMap>();
+                ///
+                /// let expected = HashMap::from_iter([
+                ///     (0, "0"),
+                ///     (1, "1"),
+                ///     (2, "2"),
+                /// ]);
+                ///
+                /// assert_eq!(actual, expected);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [BTreeMap::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<BTreeMap>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`BTreeMap`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::BTreeMap;
+                ///
+                /// let actual = (0..3).iter().rev().map(|n| (n, n.to_string())).collect::<BTreeMap>();
+                /// assert_eq!(actual.keys().collect::<Vec>(), [0, 1, 2]);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [BTreeSet::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<BTreeSet>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`BTreeSet`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::BTreeSet;
+                ///
+                /// let set = [3, 1, 2, 1].iter().collect::<BTreeSet>();
+                /// assert_eq!(set.iter().collect::<Vec>(), [1, 2, 3]);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [BinaryHeap::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<BinaryHeap>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`BinaryHeap`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::BinaryHeap;
+                ///
+                /// let heap = [3, 1, 2].iter().collect::<BinaryHeap>();
+                /// assert_eq!(heap.pop(), Some(3));
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [Object::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<HashMap>()?
+            .docs(docstring! {
+                /// Collect the iterator as an [`Object`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// assert_eq!([("first", 1), ("second", 2)].iter().collect::<Object>(), #{first: 1, second: 2});
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [OwnedTuple::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<OwnedTuple>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`Tuple`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// assert_eq!((0..3).iter().collect::<Tuple>(), (0, 1, 2));
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [String::HASH]))?
+            .argument_types::<(Value,)>()?
+            .argument_names(["self"])?
+            .return_type::<String>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`String`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// assert_eq!(["first", "second"].iter().collect::<String>(), "firstsecond");
+                /// ```
+            })?;
+
+        macro_rules! float_product_ops {
+            ($ty:ty) => {
+                t.function(Params::new("product", [<$ty>::HASH]))?
+                    .argument_types::<(Value,)>()?
+                    .argument_names(["self"])?
+                    .return_type::<$ty>()?
+                    .docs(docstring! {
+                        /// Iterates over the entire iterator, multiplying all
+                        /// the elements
+                        ///
+                        /// An empty iterator returns the one value of the type.
+                        ///
+                        /// `sum()` can be used to sum numerical built-in types,
+                        /// such as `i64`, `f64` and `u64`. The first element
+                        /// returned by the iterator determines the type being
+                        /// multiplied.
+                        ///
+                        /// # Panics
+                        ///
+                        /// When calling `product()` and a primitive integer
+                        /// type is being returned, method will panic if the
+                        /// computation overflows.
+                        ///
+                        /// # Examples
+                        ///
+                        /// ```rune
+                        /// fn factorial(n) {
+                        #[doc = concat!("     (1..=n).iter().map(|n| n as ", stringify!($ty), ").product::<", stringify!($ty), ">()")]
+                        /// }
+                        ///
+                        #[doc = concat!(" assert_eq!(factorial(0), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(1), 1", stringify!($ty), ");")]
+                        #[doc = concat!(" assert_eq!(factorial(5), 120", stringify!($ty), ");")]
                         /// ```
                     })?;
             };
@@ -1906,213 +2921,844 @@ fn once(value: Value) -> Once {
     Once { value: Some(value) }
 }
 
-#[derive(Any)]
+#[derive(Any)]
+#[rune(item = ::std::iter)]
+struct Once {
+    value: Option<Value>,
+}
+
+impl Once {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        self.value.take()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        self.value.take()
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::from(self.value.is_some());
+        (len, Some(len))
+    }
+}
+
+/// Produce an iterator which starts at the range `start` and ends at the value
+/// `end` (exclusive).
+///
+/// # Examples
+///
+/// ```rune
+/// use std::iter::range;
+///
+/// assert!(range(0, 3).next().is_some());
+/// assert_eq!(range(0, 3).collect::<Vec>(), [0, 1, 2]);
+/// ```
+#[rune::function(deprecated = "Use the `<from>..<to>` operator instead")]
+fn range(start: i64, end: i64) -> RangeIter<i64> {
+    RangeIter::new(start..end)
+}
+
+/// Fuse the iterator if the expression is `None`.
+macro_rules! fuse {
+    ($self:ident . $iter:ident . $($call:tt)+) => {
+        match $self.$iter {
+            Some(ref mut iter) => match iter.$($call)+? {
+                None => {
+                    $self.$iter = None;
+                    None
+                }
+                item => item,
+            },
+            None => None,
+        }
+    };
+}
+
+/// Try an iterator method without fusing,
+/// like an inline `.as_mut().and_then(...)`
+macro_rules! maybe {
+    ($self:ident . $iter:ident . $($call:tt)+) => {
+        match $self.$iter {
+            Some(ref mut iter) => iter.$($call)+?,
+            None => None,
+        }
+    };
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Chain {
+    a: Option<Value>,
+    b: Option<Value>,
+}
+
+impl Chain {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(match fuse!(self.a.protocol_next()) {
+            None => maybe!(self.b.protocol_next()),
+            item => item,
+        })
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(match fuse!(self.b.protocol_next_back()) {
+            None => maybe!(self.a.protocol_next_back()),
+            item => item,
+        })
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        match self {
+            Self {
+                a: Some(a),
+                b: Some(b),
+            } => {
+                let (a_lower, a_upper) = a.protocol_size_hint()?;
+                let (b_lower, b_upper) = b.protocol_size_hint()?;
+
+                let lower = a_lower.saturating_add(b_lower);
+
+                let upper = match (a_upper, b_upper) {
+                    (Some(x), Some(y)) => x.checked_add(y),
+                    _ => None,
+                };
+
+                Ok((lower, upper))
+            }
+            Self {
+                a: Some(a),
+                b: None,
+            } => a.protocol_size_hint(),
+            Self {
+                a: None,
+                b: Some(b),
+            } => b.protocol_size_hint(),
+            Self { a: None, b: None } => Ok((0, Some(0))),
+        }
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        match self {
+            Self {
+                a: Some(a),
+                b: Some(b),
+            } => {
+                let a_len = a.protocol_len()?;
+                let b_len = b.protocol_len()?;
+                Ok(a_len.saturating_add(b_len))
+            }
+            Self {
+                a: Some(a),
+                b: None,
+            } => a.protocol_len(),
+            Self {
+                a: None,
+                b: Some(b),
+            } => b.protocol_len(),
+            Self { a: None, b: None } => Ok(0),
+        }
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Enumerate {
+    iter: Value,
+    count: usize,
+}
+
+impl Enumerate {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<(usize, Value)>, VmError> {
+        if let Some(value) = self.iter.protocol_next()? {
+            let i = self.count;
+            self.count += 1;
+            return Ok(Some((i, value)));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<(usize, Value)>, VmError> {
+        if let Some(value) = self.iter.protocol_next_back()? {
+            let len = self.iter.protocol_len()?;
+            return Ok(Some((self.count + len, value)));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        self.iter.protocol_size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        self.iter.protocol_len()
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Filter {
+    iter: Value,
+    f: Function,
+}
+
+impl Filter {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = self.iter.protocol_next()? {
+            if self.f.call::<bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = self.iter.protocol_next_back()? {
+            if self.f.call::<bool>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (_, hi) = self.iter.protocol_size_hint()?;
+        Ok((0, hi))
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Map {
+    iter: Option<Value>,
+    f: Function,
+}
+
+impl Map {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = fuse!(self.iter.protocol_next()) {
+            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(value) = fuse!(self.iter.protocol_next_back()) {
+            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let Some(iter) = &self.iter else {
+            return Ok((0, Some(0)));
+        };
+
+        iter.protocol_size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        let Some(iter) = &self.iter else {
+            return Ok(0);
+        };
+
+        iter.protocol_len()
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct FilterMap {
+    iter: Option<Value>,
+    f: Function,
+}
+
+impl FilterMap {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = fuse!(self.iter.protocol_next()) {
+            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        while let Some(value) = fuse!(self.iter.protocol_next_back()) {
+            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct FlatMap {
+    map: Map,
+    frontiter: Option<Value>,
+    backiter: Option<Value>,
+}
+
+impl FlatMap {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        loop {
+            if let Some(iter) = &mut self.frontiter {
+                match iter.protocol_next()? {
+                    None => self.frontiter = None,
+                    item @ Some(_) => return Ok(item),
+                }
+            }
+
+            let Some(value) = self.map.next()? else {
+                return Ok(match &mut self.backiter {
+                    Some(backiter) => backiter.protocol_next()?,
+                    None => None,
+                });
+            };
+
+            self.frontiter = Some(value.protocol_into_iter()?)
+        }
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        loop {
+            if let Some(ref mut iter) = self.backiter {
+                match iter.protocol_next_back()? {
+                    None => self.backiter = None,
+                    item @ Some(_) => return Ok(item),
+                }
+            }
+
+            let Some(value) = self.map.next_back()? else {
+                return Ok(match &mut self.frontiter {
+                    Some(frontiter) => frontiter.protocol_next_back()?,
+                    None => None,
+                });
+            };
+
+            self.backiter = Some(value.protocol_into_iter()?);
+        }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (flo, fhi) = match &self.frontiter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
+
+        let (blo, bhi) = match &self.backiter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
+
+        let lo = flo.saturating_add(blo);
+
+        Ok(match (self.map.size_hint()?, fhi, bhi) {
+            ((0, Some(0)), Some(a), Some(b)) => (lo, a.checked_add(b)),
+            _ => (lo, None),
+        })
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Peekable {
+    iter: Value,
+    peeked: Option<Option<Value>>,
+}
+
+impl Peekable {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(match self.peeked.take() {
+            Some(v) => v,
+            None => self.iter.protocol_next()?,
+        })
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(match self.peeked.as_mut() {
+            Some(v @ Some(_)) => self.iter.protocol_next_back()?.or_else(|| v.take()),
+            Some(None) => None,
+            None => self.iter.protocol_next_back()?,
+        })
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let peek_len = match &self.peeked {
+            Some(None) => return Ok((0, Some(0))),
+            Some(Some(_)) => 1,
+            None => 0,
+        };
+
+        let (lo, hi) = self.iter.protocol_size_hint()?;
+        let lo = lo.saturating_add(peek_len);
+
+        let hi = match hi {
+            Some(x) => x.checked_add(peek_len),
+            None => None,
+        };
+
+        Ok((lo, hi))
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        let peek_len = match &self.peeked {
+            Some(None) => return Ok(0),
+            Some(Some(_)) => 1,
+            None => 0,
+        };
+
+        let len = self.iter.protocol_len()?;
+        Ok(len.saturating_add(peek_len))
+    }
+
+    /// Returns a reference to the `next()` value without advancing the iterator.
+    ///
+    /// Like [`next`], if there is a value, it is wrapped in a `Some(T)`. But if the
+    /// iteration is over, `None` is returned.
+    ///
+    /// [`next`]: Iterator::next
+    ///
+    /// Because `peek()` returns a reference, and many iterators iterate over
+    /// references, there can be a possibly confusing situation where the return
+    /// value is a double reference. You can see this effect in the examples below.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rune
+    /// let xs = [1, 2, 3];
+    ///
+    /// let iter = xs.iter().peekable();
+    ///
+    /// // peek() lets us see into the future
+    /// assert_eq!(iter.peek(), Some(1));
+    /// assert_eq!(iter.next(), Some(1));
+    ///
+    /// assert_eq!(iter.next(), Some(2));
+    ///
+    /// // The iterator does not advance even if we `peek` multiple times
+    /// assert_eq!(iter.peek(), Some(3));
+    /// assert_eq!(iter.peek(), Some(3));
+    ///
+    /// assert_eq!(iter.next(), Some(3));
+    ///
+    /// // After the iterator is finished, so is `peek()`
+    /// assert_eq!(iter.peek(), None);
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[rune::function(keep)]
+    #[inline]
+    fn peek(&mut self) -> Result<Option<Value>, VmError> {
+        if let Some(v) = &self.peeked {
+            return Ok(v.clone());
+        }
+
+        let value = self.iter.protocol_next()?;
+        self.peeked = Some(value.clone());
+        Ok(value)
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Skip {
+    iter: Value,
+    n: usize,
+}
+
+impl Skip {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.n > 0 {
+            let old_n = self.n;
+            self.n = 0;
+
+            for _ in 0..old_n {
+                match self.iter.protocol_next()? {
+                    Some(..) => (),
+                    None => return Ok(None),
+                }
+            }
+        }
+
+        self.iter.protocol_next()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        Ok(if self.len()? > 0 {
+            self.iter.protocol_next_back()?
+        } else {
+            None
+        })
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (lower, upper) = self.iter.protocol_size_hint()?;
+        let lower = lower.saturating_sub(self.n);
+        let upper = upper.map(|x| x.saturating_sub(self.n));
+        Ok((lower, upper))
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        let len = self.iter.protocol_len()?;
+        Ok(len.saturating_sub(self.n))
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Take {
+    iter: Value,
+    n: usize,
+}
+
+impl Take {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.n == 0 {
+            return Ok(None);
+        }
+
+        self.n -= 1;
+        self.iter.protocol_next()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        if self.n == 0 {
+            Ok(None)
+        } else {
+            let n = self.n;
+            self.n -= 1;
+            let len = self.iter.protocol_len()?;
+            self.iter.protocol_nth_back(len.saturating_sub(n))
+        }
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        if self.n == 0 {
+            return Ok((0, Some(0)));
+        }
+
+        let (lower, upper) = self.iter.protocol_size_hint()?;
+
+        let lower = lower.min(self.n);
+
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+
+        Ok((lower, upper))
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        if self.n == 0 {
+            return Ok(0);
+        }
+
+        let len = self.iter.protocol_len()?;
+        Ok(len.min(self.n))
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Rev {
+    value: Value,
+}
+
+impl Rev {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        self.value.protocol_next_back()
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
+        self.value.protocol_next()
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        self.value.protocol_size_hint()
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        self.value.protocol_len()
+    }
+}
+
+#[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Once {
-    value: Option<Value>,
+struct Zip {
+    a: Value,
+    b: Value,
 }
 
-impl Once {
+impl Zip {
     #[rune::function(keep, protocol = NEXT)]
-    fn next(&mut self) -> Option<Value> {
-        self.value.take()
+    #[inline]
+    fn next(&mut self) -> Result<Option<(Value, Value)>, VmError> {
+        let Some(a) = self.a.protocol_next()? else {
+            return Ok(None);
+        };
+
+        let Some(b) = self.b.protocol_next()? else {
+            return Ok(None);
+        };
+
+        Ok(Some((a, b)))
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
-    fn next_back(&mut self) -> Option<Value> {
-        self.value.take()
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<(Value, Value)>, VmError> {
+        let a_len = self.a.protocol_len()?;
+        let b_len = self.b.protocol_len()?;
+
+        // Trim the longer iterator so that both ends line up.
+        for _ in b_len..a_len {
+            self.a.protocol_next_back()?;
+        }
+
+        for _ in a_len..b_len {
+            self.b.protocol_next_back()?;
+        }
+
+        match (self.a.protocol_next_back()?, self.b.protocol_next_back()?) {
+            (Some(a), Some(b)) => Ok(Some((a, b))),
+            _ => Ok(None),
+        }
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = usize::from(self.value.is_some());
-        (len, Some(len))
-    }
-}
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (a_lower, a_upper) = self.a.protocol_size_hint()?;
+        let (b_lower, b_upper) = self.b.protocol_size_hint()?;
 
-/// Produce an iterator which starts at the range `start` and ends at the value
-/// `end` (exclusive).
-///
-/// # Examples
-///
-/// ```rune
-/// use std::iter::range;
-///
-/// assert!(range(0, 3).next().is_some());
-/// assert_eq!(range(0, 3).collect::<Vec>(), [0, 1, 2]);
-/// ```
-#[rune::function(deprecated = "Use the `<from>..<to>` operator instead")]
-fn range(start: i64, end: i64) -> RangeIter<i64> {
-    RangeIter::new(start..end)
-}
+        let lower = a_lower.min(b_lower);
 
-/// Fuse the iterator if the expression is `None`.
-macro_rules! fuse {
-    ($self:ident . $iter:ident . $($call:tt)+) => {
-        match $self.$iter {
-            Some(ref mut iter) => match iter.$($call)+? {
-                None => {
-                    $self.$iter = None;
-                    None
-                }
-                item => item,
-            },
-            None => None,
-        }
-    };
-}
+        let upper = match (a_upper, b_upper) {
+            (Some(x), Some(y)) => Some(x.min(y)),
+            (Some(x), None) => Some(x),
+            (None, Some(y)) => Some(y),
+            (None, None) => None,
+        };
 
-/// Try an iterator method without fusing,
-/// like an inline `.as_mut().and_then(...)`
-macro_rules! maybe {
-    ($self:ident . $iter:ident . $($call:tt)+) => {
-        match $self.$iter {
-            Some(ref mut iter) => iter.$($call)+?,
-            None => None,
-        }
-    };
+        Ok((lower, upper))
+    }
+
+    #[rune::function(keep, protocol = LEN)]
+    #[inline]
+    fn len(&self) -> Result<usize, VmError> {
+        let a_len = self.a.protocol_len()?;
+        let b_len = self.b.protocol_len()?;
+        Ok(a_len.min(b_len))
+    }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Chain {
-    a: Option<Value>,
-    b: Option<Value>,
+struct StepBy {
+    iter: Value,
+    /// The number of elements to skip between each yielded element.
+    step: usize,
+    first_take: bool,
 }
 
-impl Chain {
+impl StepBy {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(match fuse!(self.a.protocol_next()) {
-            None => maybe!(self.b.protocol_next()),
-            item => item,
-        })
+        let step = if self.first_take { 0 } else { self.step };
+        self.first_take = false;
+        self.iter.protocol_nth(step)
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
     fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(match fuse!(self.b.protocol_next_back()) {
-            None => maybe!(self.a.protocol_next_back()),
-            item => item,
-        })
+        let rem = self.iter.protocol_len()? % (self.step + 1);
+
+        let n = if self.first_take {
+            if rem == 0 {
+                self.step
+            } else {
+                rem - 1
+            }
+        } else {
+            rem
+        };
+
+        self.iter.protocol_nth_back(n)
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        match self {
-            Self {
-                a: Some(a),
-                b: Some(b),
-            } => {
-                let (a_lower, a_upper) = a.protocol_size_hint()?;
-                let (b_lower, b_upper) = b.protocol_size_hint()?;
-
-                let lower = a_lower.saturating_add(b_lower);
-
-                let upper = match (a_upper, b_upper) {
-                    (Some(x), Some(y)) => x.checked_add(y),
-                    _ => None,
-                };
-
-                Ok((lower, upper))
-            }
-            Self {
-                a: Some(a),
-                b: None,
-            } => a.protocol_size_hint(),
-            Self {
-                a: None,
-                b: Some(b),
-            } => b.protocol_size_hint(),
-            Self { a: None, b: None } => Ok((0, Some(0))),
-        }
+        let (lower, upper) = self.iter.protocol_size_hint()?;
+        Ok((self.steps(lower), upper.map(|n| self.steps(n))))
     }
 
     #[rune::function(keep, protocol = LEN)]
     #[inline]
     fn len(&self) -> Result<usize, VmError> {
-        match self {
-            Self {
-                a: Some(a),
-                b: Some(b),
-            } => {
-                let a_len = a.protocol_len()?;
-                let b_len = b.protocol_len()?;
-                Ok(a_len.saturating_add(b_len))
+        let len = self.iter.protocol_len()?;
+        Ok(self.steps(len))
+    }
+
+    /// The number of elements produced out of `n` remaining elements.
+    #[inline]
+    fn steps(&self, n: usize) -> usize {
+        if self.first_take {
+            if n == 0 {
+                0
+            } else {
+                1 + (n - 1) / (self.step + 1)
             }
-            Self {
-                a: Some(a),
-                b: None,
-            } => a.protocol_len(),
-            Self {
-                a: None,
-                b: Some(b),
-            } => b.protocol_len(),
-            Self { a: None, b: None } => Ok(0),
+        } else {
+            n / (self.step + 1)
         }
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Enumerate {
+struct TakeWhile {
     iter: Value,
-    count: usize,
+    f: Function,
+    done: bool,
 }
 
-impl Enumerate {
+impl TakeWhile {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
-    fn next(&mut self) -> Result<Option<(usize, Value)>, VmError> {
-        if let Some(value) = self.iter.protocol_next()? {
-            let i = self.count;
-            self.count += 1;
-            return Ok(Some((i, value)));
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        if self.done {
+            return Ok(None);
         }
 
-        Ok(None)
-    }
+        let Some(value) = self.iter.protocol_next()? else {
+            return Ok(None);
+        };
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<(usize, Value)>, VmError> {
-        if let Some(value) = self.iter.protocol_next_back()? {
-            let len = self.iter.protocol_len()?;
-            return Ok(Some((self.count + len, value)));
+        if self.f.call::<bool>((value.clone(),))? {
+            return Ok(Some(value));
         }
 
+        self.done = true;
         Ok(None)
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        self.iter.protocol_size_hint()
-    }
+        if self.done {
+            return Ok((0, Some(0)));
+        }
 
-    #[rune::function(keep, protocol = LEN)]
-    #[inline]
-    fn len(&self) -> Result<usize, VmError> {
-        self.iter.protocol_len()
+        let (_, upper) = self.iter.protocol_size_hint()?;
+        Ok((0, upper))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Filter {
+struct SkipWhile {
     iter: Value,
     f: Function,
+    done: bool,
 }
 
-impl Filter {
+impl SkipWhile {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
         while let Some(value) = self.iter.protocol_next()? {
-            if self.f.call::<bool>((value.clone(),))? {
+            if self.done || !self.f.call::<bool>((value.clone(),))? {
+                self.done = true;
                 return Ok(Some(value));
             }
         }
@@ -2120,117 +3766,172 @@ impl Filter {
         Ok(None)
     }
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        while let Some(value) = self.iter.protocol_next_back()? {
-            if self.f.call::<bool>((value.clone(),))? {
-                return Ok(Some(value));
-            }
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (lower, upper) = self.iter.protocol_size_hint()?;
+
+        if self.done {
+            return Ok((lower, upper));
         }
 
-        Ok(None)
+        Ok((0, upper))
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct MapWhile {
+    iter: Value,
+    f: Function,
+}
+
+impl MapWhile {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let Some(value) = self.iter.protocol_next()? else {
+            return Ok(None);
+        };
+
+        self.f.call::<Option<Value>>((value,))
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let (_, hi) = self.iter.protocol_size_hint()?;
-        Ok((0, hi))
+        let (_, upper) = self.iter.protocol_size_hint()?;
+        Ok((0, upper))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Map {
-    iter: Option<Value>,
+struct Scan {
+    iter: Value,
+    state: Value,
     f: Function,
 }
 
-impl Map {
+impl Scan {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        if let Some(value) = fuse!(self.iter.protocol_next()) {
-            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
-        }
+        let Some(value) = self.iter.protocol_next()? else {
+            return Ok(None);
+        };
 
-        Ok(None)
+        self.f.call::<Option<Value>>((self.state.clone(), value))
+    }
+
+    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[inline]
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        let (_, upper) = self.iter.protocol_size_hint()?;
+        Ok((0, upper))
+    }
+}
+
+#[derive(Any, Debug)]
+#[rune(item = ::std::iter)]
+struct Inspect {
+    iter: Value,
+    f: Function,
+}
+
+impl Inspect {
+    #[rune::function(keep, protocol = NEXT)]
+    #[inline]
+    fn next(&mut self) -> Result<Option<Value>, VmError> {
+        let Some(value) = self.iter.protocol_next()? else {
+            return Ok(None);
+        };
+
+        self.f.call::<Value>((value.clone(),))?;
+        Ok(Some(value))
     }
 
     #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
     fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        if let Some(value) = fuse!(self.iter.protocol_next_back()) {
-            return Ok(Some(self.f.call::<Value>((value.clone(),))?));
-        }
+        let Some(value) = self.iter.protocol_next_back()? else {
+            return Ok(None);
+        };
 
-        Ok(None)
+        self.f.call::<Value>((value.clone(),))?;
+        Ok(Some(value))
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let Some(iter) = &self.iter else {
-            return Ok((0, Some(0)));
-        };
-
-        iter.protocol_size_hint()
+        self.iter.protocol_size_hint()
     }
 
     #[rune::function(keep, protocol = LEN)]
     #[inline]
     fn len(&self) -> Result<usize, VmError> {
-        let Some(iter) = &self.iter else {
-            return Ok(0);
-        };
-
-        iter.protocol_len()
+        self.iter.protocol_len()
     }
 }
 
+/// Iterators can't be cloned in general, so instead of restarting a clone of
+/// the original iterator the values it produces are remembered during the
+/// first pass and replayed after that.
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct FilterMap {
+struct Cycle {
     iter: Option<Value>,
-    f: Function,
+    seen: alloc::Vec<Value>,
+    index: usize,
 }
 
-impl FilterMap {
+impl Cycle {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
-        while let Some(value) = fuse!(self.iter.protocol_next()) {
-            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
-                return Ok(Some(value));
-            }
+        if let Some(value) = fuse!(self.iter.protocol_next()) {
+            self.seen.try_push(value.clone())?;
+            return Ok(Some(value));
         }
 
-        Ok(None)
+        let Some(value) = self.seen.get(self.index) else {
+            return Ok(None);
+        };
+
+        let value = value.clone();
+        self.index = (self.index + 1) % self.seen.len();
+        Ok(Some(value))
     }
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        while let Some(value) = fuse!(self.iter.protocol_next_back()) {
-            if let Some(value) = self.f.call::<Option<Value>>((value.clone(),))? {
-                return Ok(Some(value));
-            }
+    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
+        if !self.seen.is_empty() {
+            return Ok((usize::MAX, None));
         }
 
-        Ok(None)
+        let Some(iter) = &self.iter else {
+            return Ok((0, Some(0)));
+        };
+
+        Ok(match iter.protocol_size_hint()? {
+            (0, Some(0)) => (0, Some(0)),
+            (0, _) => (0, None),
+            _ => (usize::MAX, None),
+        })
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct FlatMap {
-    map: Map,
+struct Flatten {
+    iter: Option<Value>,
     frontiter: Option<Value>,
     backiter: Option<Value>,
 }
 
-impl FlatMap {
+impl Flatten {
     #[rune::function(keep, protocol = NEXT)]
     #[inline]
     fn next(&mut self) -> Result<Option<Value>, VmError> {
@@ -2242,14 +3943,14 @@ impl FlatMap {
                 }
             }
 
-            let Some(value) = self.map.next()? else {
+            let Some(value) = fuse!(self.iter.protocol_next()) else {
                 return Ok(match &mut self.backiter {
                     Some(backiter) => backiter.protocol_next()?,
                     None => None,
                 });
             };
 
-            self.frontiter = Some(value.protocol_into_iter()?)
+            self.frontiter = Some(value.protocol_into_iter()?);
         }
     }
 
@@ -2257,14 +3958,14 @@ impl FlatMap {
     #[inline]
     fn next_back(&mut self) -> Result<Option<Value>, VmError> {
         loop {
-            if let Some(ref mut iter) = self.backiter {
+            if let Some(iter) = &mut self.backiter {
                 match iter.protocol_next_back()? {
                     None => self.backiter = None,
                     item @ Some(_) => return Ok(item),
                 }
             }
 
-            let Some(value) = self.map.next_back()? else {
+            let Some(value) = fuse!(self.iter.protocol_next_back()) else {
                 return Ok(match &mut self.frontiter {
                     Some(frontiter) => frontiter.protocol_next_back()?,
                     None => None,
@@ -2290,7 +3991,12 @@ impl FlatMap {
 
         let lo = flo.saturating_add(blo);
 
-        Ok(match (self.map.size_hint()?, fhi, bhi) {
+        let inner = match &self.iter {
+            Some(iter) => iter.protocol_size_hint()?,
+            None => (0, Some(0)),
+        };
+
+        Ok(match (inner, fhi, bhi) {
             ((0, Some(0)), Some(a), Some(b)) => (lo, a.checked_add(b)),
             _ => (lo, None),
         })
@@ -2299,155 +4005,75 @@ impl FlatMap {
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Peekable {
+pub(crate) struct Chunks {
     iter: Value,
-    peeked: Option<Option<Value>>,
+    size: usize,
 }
 
-impl Peekable {
-    #[rune::function(keep, protocol = NEXT)]
-    #[inline]
-    fn next(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(match self.peeked.take() {
-            Some(v) => v,
-            None => self.iter.protocol_next()?,
-        })
-    }
+impl Chunks {
+    pub(crate) fn new(iter: Value, size: usize) -> Result<Self, VmError> {
+        if size == 0 {
+            return Err(VmError::panic("chunk size must be non-zero"));
+        }
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(match self.peeked.as_mut() {
-            Some(v @ Some(_)) => self.iter.protocol_next_back()?.or_else(|| v.take()),
-            Some(None) => None,
-            None => self.iter.protocol_next_back()?,
-        })
+        Ok(Self { iter, size })
     }
 
-    #[rune::function(keep, protocol = SIZE_HINT)]
+    #[rune::function(keep, protocol = NEXT)]
     #[inline]
-    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        let peek_len = match &self.peeked {
-            Some(None) => return Ok((0, Some(0))),
-            Some(Some(_)) => 1,
-            None => 0,
-        };
+    fn next(&mut self) -> Result<Option<Vec>, VmError> {
+        let mut chunk = Vec::new();
 
-        let (lo, hi) = self.iter.protocol_size_hint()?;
-        let lo = lo.saturating_add(peek_len);
+        while chunk.len() < self.size {
+            let Some(value) = self.iter.protocol_next()? else {
+                break;
+            };
 
-        let hi = match hi {
-            Some(x) => x.checked_add(peek_len),
-            None => None,
-        };
+            chunk.push(value)?;
+        }
 
-        Ok((lo, hi))
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(chunk))
     }
 
-    #[rune::function(keep, protocol = LEN)]
+    #[rune::function(keep, protocol = NEXT_BACK)]
     #[inline]
-    fn len(&self) -> Result<usize, VmError> {
-        let peek_len = match &self.peeked {
-            Some(None) => return Ok(0),
-            Some(Some(_)) => 1,
-            None => 0,
-        };
-
+    fn next_back(&mut self) -> Result<Option<Vec>, VmError> {
         let len = self.iter.protocol_len()?;
-        Ok(len.saturating_add(peek_len))
-    }
 
-    /// Returns a reference to the `next()` value without advancing the iterator.
-    ///
-    /// Like [`next`], if there is a value, it is wrapped in a `Some(T)`. But if the
-    /// iteration is over, `None` is returned.
-    ///
-    /// [`next`]: Iterator::next
-    ///
-    /// Because `peek()` returns a reference, and many iterators iterate over
-    /// references, there can be a possibly confusing situation where the return
-    /// value is a double reference. You can see this effect in the examples below.
-    ///
-    /// # Examples
-    ///
-    /// Basic usage:
-    ///
-    /// ```rune
-    /// let xs = [1, 2, 3];
-    ///
-    /// let iter = xs.iter().peekable();
-    ///
-    /// // peek() lets us see into the future
-    /// assert_eq!(iter.peek(), Some(1));
-    /// assert_eq!(iter.next(), Some(1));
-    ///
-    /// assert_eq!(iter.next(), Some(2));
-    ///
-    /// // The iterator does not advance even if we `peek` multiple times
-    /// assert_eq!(iter.peek(), Some(3));
-    /// assert_eq!(iter.peek(), Some(3));
-    ///
-    /// assert_eq!(iter.next(), Some(3));
-    ///
-    /// // After the iterator is finished, so is `peek()`
-    /// assert_eq!(iter.peek(), None);
-    /// assert_eq!(iter.next(), None);
-    /// ```
-    #[rune::function(keep)]
-    #[inline]
-    fn peek(&mut self) -> Result<Option<Value>, VmError> {
-        if let Some(v) = &self.peeked {
-            return Ok(v.clone());
+        if len == 0 {
+            return Ok(None);
         }
 
-        let value = self.iter.protocol_next()?;
-        self.peeked = Some(value.clone());
-        Ok(value)
-    }
-}
+        // The last chunk is the one which holds the remainder.
+        let n = match len % self.size {
+            0 => self.size,
+            rem => rem,
+        };
 
-#[derive(Any, Debug)]
-#[rune(item = ::std::iter)]
-struct Skip {
-    iter: Value,
-    n: usize,
-}
+        let mut chunk = alloc::Vec::try_with_capacity(n)?;
 
-impl Skip {
-    #[rune::function(keep, protocol = NEXT)]
-    #[inline]
-    fn next(&mut self) -> Result<Option<Value>, VmError> {
-        if self.n > 0 {
-            let old_n = self.n;
-            self.n = 0;
+        for _ in 0..n {
+            let Some(value) = self.iter.protocol_next_back()? else {
+                break;
+            };
 
-            for _ in 0..old_n {
-                match self.iter.protocol_next()? {
-                    Some(..) => (),
-                    None => return Ok(None),
-                }
-            }
+            chunk.try_push(value)?;
         }
 
-        self.iter.protocol_next()
-    }
-
-    #[rune::function(keep, protocol = NEXT_BACK)]
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        Ok(if self.len()? > 0 {
-            self.iter.protocol_next_back()?
-        } else {
-            None
-        })
+        chunk.reverse();
+        Ok(Some(Vec::from(chunk)))
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
         let (lower, upper) = self.iter.protocol_size_hint()?;
-        let lower = lower.saturating_sub(self.n);
-        let upper = upper.map(|x| x.saturating_sub(self.n));
+        let lower = lower.div_ceil(self.size);
+        let upper = upper.map(|n| n.div_ceil(self.size));
         Ok((lower, upper))
     }
 
@@ -2455,102 +4081,85 @@ impl Skip {
     #[inline]
     fn len(&self) -> Result<usize, VmError> {
         let len = self.iter.protocol_len()?;
-        Ok(len.saturating_sub(self.n))
+        Ok(len.div_ceil(self.size))
     }
 }
 
 #[derive(Any, Debug)]
 #[rune(item = ::std::iter)]
-struct Take {
+pub(crate) struct Windows {
     iter: Value,
-    n: usize,
+    size: usize,
+    window: alloc::VecDeque<Value>,
 }
 
-impl Take {
-    #[rune::function(keep, protocol = NEXT)]
-    #[inline]
-    fn next(&mut self) -> Result<Option<Value>, VmError> {
-        if self.n == 0 {
-            return Ok(None);
+impl Windows {
+    pub(crate) fn new(iter: Value, size: usize) -> Result<Self, VmError> {
+        if size == 0 {
+            return Err(VmError::panic("window size must be non-zero"));
         }
 
-        self.n -= 1;
-        self.iter.protocol_next()
+        Ok(Self {
+            iter,
+            size,
+            window: alloc::VecDeque::new(),
+        })
     }
 
-    #[rune::function(keep, protocol = NEXT_BACK)]
+    #[rune::function(keep, protocol = NEXT)]
     #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        if self.n == 0 {
-            Ok(None)
+    fn next(&mut self) -> Result<Option<Vec>, VmError> {
+        if self.window.is_empty() {
+            while self.window.len() < self.size {
+                let Some(value) = self.iter.protocol_next()? else {
+                    self.window.clear();
+                    return Ok(None);
+                };
+
+                self.window.try_push_back(value)?;
+            }
         } else {
-            let n = self.n;
-            self.n -= 1;
-            let len = self.iter.protocol_len()?;
-            self.iter.protocol_nth_back(len.saturating_sub(n))
+            let Some(value) = self.iter.protocol_next()? else {
+                return Ok(None);
+            };
+
+            self.window.pop_front();
+            self.window.try_push_back(value)?;
         }
+
+        let mut window = Vec::with_capacity(self.size)?;
+
+        for value in self.window.iter() {
+            window.push(value.clone())?;
+        }
+
+        Ok(Some(window))
     }
 
     #[rune::function(keep, protocol = SIZE_HINT)]
     #[inline]
     fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        if self.n == 0 {
-            return Ok((0, Some(0)));
-        }
-
         let (lower, upper) = self.iter.protocol_size_hint()?;
-
-        let lower = lower.min(self.n);
-
-        let upper = match upper {
-            Some(x) if x < self.n => Some(x),
-            _ => Some(self.n),
-        };
-
+        let lower = self.windows(lower);
+        let upper = upper.map(|n| self.windows(n));
         Ok((lower, upper))
     }
 
     #[rune::function(keep, protocol = LEN)]
     #[inline]
     fn len(&self) -> Result<usize, VmError> {
-        if self.n == 0 {
-            return Ok(0);
-        }
-
         let len = self.iter.protocol_len()?;
-        Ok(len.min(self.n))
-    }
-}
-
-#[derive(Any, Debug)]
-#[rune(item = ::std::iter)]
-struct Rev {
-    value: Value,
-}
-
-impl Rev {
-    #[rune::function(keep, protocol = NEXT)]
-    #[inline]
-    fn next(&mut self) -> Result<Option<Value>, VmError> {
-        self.value.protocol_next_back()
-    }
-
-    #[rune::function(keep, protocol = NEXT_BACK)]
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<Value>, VmError> {
-        self.value.protocol_next()
-    }
-
-    #[rune::function(keep, protocol = SIZE_HINT)]
-    #[inline]
-    fn size_hint(&self) -> Result<(usize, Option<usize>), VmError> {
-        self.value.protocol_size_hint()
+        Ok(self.windows(len))
     }
 
-    #[rune::function(keep, protocol = LEN)]
+    /// The number of windows produced out of `n` remaining elements.
     #[inline]
-    fn len(&self) -> Result<usize, VmError> {
-        self.value.protocol_len()
+    fn windows(&self, n: usize) -> usize {
+        if self.window.is_empty() {
+            n.saturating_sub(self.size - 1)
+        } else {
+            n
+        }
     }
 }
 
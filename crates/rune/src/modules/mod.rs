@@ -29,7 +29,11 @@ pub mod f64;
 pub mod fmt;
 pub mod future;
 pub mod hash;
+pub mod i128;
+pub mod i16;
+pub mod i32;
 pub mod i64;
+pub mod i8;
 pub mod io;
 pub mod iter;
 pub mod macros;
@@ -39,10 +43,16 @@ pub mod object;
 pub mod ops;
 pub mod option;
 pub mod result;
+#[cfg(feature = "std")]
+pub mod sandbox;
 pub mod slice;
 pub mod stream;
 pub mod string;
 pub mod test;
 pub mod tuple;
+pub mod u128;
+pub mod u16;
+pub mod u32;
 pub mod u64;
+pub mod u8;
 pub mod vec;
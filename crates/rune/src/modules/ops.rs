@@ -13,7 +13,7 @@ use crate::runtime::range::RangeIter;
 use crate::runtime::range_from::RangeFromIter;
 use crate::runtime::range_inclusive::RangeInclusiveIter;
 use crate::runtime::{
-    ControlFlow, EnvProtocolCaller, Function, Hasher, Range, RangeFrom, RangeFull, RangeInclusive,
+    ControlFlow, EnvProtocolCaller, Fixed, Function, Hasher, Range, RangeFrom, RangeFull, RangeInclusive,
     RangeTo, RangeToInclusive, Value, VmError,
 };
 use crate::{ContextError, Module};
@@ -27,42 +27,37 @@ pub fn module() -> Result<Module, ContextError> {
 
     macro_rules! iter {
         ($ty:ident) => {
-            m.ty::<$ty<i64>>()?;
-            m.function_meta($ty::<i64>::next__meta)?;
-            m.function_meta($ty::<i64>::size_hint__meta)?;
-            m.implement_trait::<$ty<i64>>(rune::item!(::std::iter::Iterator))?;
-
-            m.ty::<$ty<u64>>()?;
-            m.function_meta($ty::<u64>::next__meta)?;
-            m.function_meta($ty::<u64>::size_hint__meta)?;
-            m.implement_trait::<$ty<u64>>(rune::item!(::std::iter::Iterator))?;
-
-            m.ty::<$ty<char>>()?;
-            m.function_meta($ty::<char>::next__meta)?;
-            m.function_meta($ty::<char>::size_hint__meta)?;
-            m.implement_trait::<$ty<char>>(rune::item!(::std::iter::Iterator))?;
+            iter!($ty, i64, u64, Fixed<u8>, Fixed<u16>, Fixed<u32>, Fixed<i8>, Fixed<i16>, Fixed<i32>, char);
+        };
+
+        ($ty:ident, $($el:ty),*) => {
+            $(
+                m.ty::<$ty<$el>>()?;
+                m.function_meta($ty::<$el>::next__meta)?;
+                m.function_meta($ty::<$el>::size_hint__meta)?;
+                m.implement_trait::<$ty<$el>>(rune::item!(::std::iter::Iterator))?;
+            )*
         };
     }
 
     macro_rules! double_ended {
         ($ty:ident) => {
             iter!($ty);
-
-            m.function_meta($ty::<i64>::next_back__meta)?;
-            m.implement_trait::<$ty<i64>>(rune::item!(::std::iter::DoubleEndedIterator))?;
-
-            m.function_meta($ty::<i64>::len__meta)?;
-            m.implement_trait::<$ty<i64>>(rune::item!(::std::iter::ExactSizeIterator))?;
-
-            m.function_meta($ty::<u64>::next_back__meta)?;
-            m.implement_trait::<$ty<u64>>(rune::item!(::std::iter::DoubleEndedIterator))?;
-
-            m.function_meta($ty::<u64>::len__meta)?;
-            m.implement_trait::<$ty<u64>>(rune::item!(::std::iter::ExactSizeIterator))?;
+            double_ended!($ty, i64, u64, Fixed<u8>, Fixed<u16>, Fixed<u32>, Fixed<i8>, Fixed<i16>, Fixed<i32>);
 
             m.function_meta($ty::<char>::next_back__meta)?;
             m.implement_trait::<$ty<char>>(rune::item!(::std::iter::DoubleEndedIterator))?;
         };
+
+        ($ty:ident, $($el:ty),*) => {
+            $(
+                m.function_meta($ty::<$el>::next_back__meta)?;
+                m.implement_trait::<$ty<$el>>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
+                m.function_meta($ty::<$el>::len__meta)?;
+                m.implement_trait::<$ty<$el>>(rune::item!(::std::iter::ExactSizeIterator))?;
+            )*
+        };
     }
 
     {
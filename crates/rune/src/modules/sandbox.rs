@@ -0,0 +1,21 @@
+//! Restrictions imposed on native modules.
+
+use crate as rune;
+use crate::runtime::sandbox::SandboxError;
+use crate::{ContextError, Module};
+
+/// Restrictions imposed on native modules.
+///
+/// When a sandbox is installed in the context, native modules like `fs`,
+/// `process` and `http` report operations which are not permitted by it as a
+/// [`SandboxError`].
+#[rune::module(::std::sandbox)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+    m.ty::<SandboxError>()?;
+    m.function_meta(SandboxError::capability_name__meta)?;
+    m.function_meta(SandboxError::target_string__meta)?;
+    m.function_meta(SandboxError::display_fmt__meta)?;
+    m.function_meta(SandboxError::debug_fmt__meta)?;
+    Ok(m)
+}
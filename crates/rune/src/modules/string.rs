@@ -10,8 +10,9 @@ use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
 use crate::alloc::string::FromUtf8Error;
 use crate::compile::Named;
+use crate::runtime::slice::Iter;
 use crate::runtime::{
-    Bytes, Formatter, FromValue, Function, Hasher, Inline, MaybeTypeOf, Range, RangeFrom,
+    self, Bytes, Formatter, FromValue, Function, Hasher, Inline, MaybeTypeOf, Range, RangeFrom,
     RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Ref, Repr, ToValue, TypeOf, Value,
     VmError, VmErrorKind,
 };
@@ -57,6 +58,16 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(split)?;
     m.function_meta(split_once)?;
     m.associated_function("split_str", __rune_fn__split)?;
+    m.function_meta(rsplit)?;
+    m.function_meta(find)?;
+    m.function_meta(rfind)?;
+    m.function_meta(strip_prefix)?;
+    m.function_meta(strip_suffix)?;
+    m.function_meta(lines)?;
+    m.function_meta(split_whitespace)?;
+    m.function_meta(repeat)?;
+    m.function_meta(pad_start)?;
+    m.function_meta(pad_end)?;
     m.function_meta(trim)?;
     m.function_meta(trim_end)?;
     m.function_meta(replace)?;
@@ -99,6 +110,18 @@ pub fn module() -> Result<Module, ContextError> {
     m.implement_trait::<Chars>(rune::item!(::std::iter::Iterator))?;
     m.implement_trait::<Chars>(rune::item!(::std::iter::DoubleEndedIterator))?;
 
+    m.ty::<Lines>()?;
+    m.function_meta(Lines::next__meta)?;
+    m.function_meta(Lines::next_back__meta)?;
+    m.implement_trait::<Lines>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<Lines>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
+    m.ty::<SplitWhitespace>()?;
+    m.function_meta(SplitWhitespace::next__meta)?;
+    m.function_meta(SplitWhitespace::next_back__meta)?;
+    m.implement_trait::<SplitWhitespace>(rune::item!(::std::iter::Iterator))?;
+    m.implement_trait::<SplitWhitespace>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
     macro_rules! split {
         ($ty:ty) => {
             m.ty::<Split<$ty>>()?;
@@ -987,6 +1010,319 @@ fn split_once(this: &str, value: Value) -> Result<Option<(String, String)>, VmEr
     Ok(Some((a.try_to_owned()?, b.try_to_owned()?)))
 }
 
+/// Returns the byte index of the first character of this string that matches
+/// the pattern.
+///
+/// Returns [`None`] if the pattern doesn't match.
+///
+/// The pattern can be a `char`, a string, or a function which determines if a
+/// character matches.
+///
+/// # Examples
+///
+/// ```rune
+/// let s = "Löwe 老虎 Léopard Gepardi";
+///
+/// assert_eq!(s.find('L'), Some(0));
+/// assert_eq!(s.find('é'), Some(14));
+/// assert_eq!(s.find("pard"), Some(17));
+/// assert_eq!(s.find(char::is_whitespace), Some(5));
+/// assert_eq!(s.find(|c| c == 'x'), None);
+/// ```
+#[rune::function(instance)]
+fn find(this: &str, pattern: Value) -> Result<Option<usize>, VmError> {
+    match Needle::from_value(pattern)? {
+        Needle::Char(c) => Ok(this.find(c)),
+        Needle::String(s) => Ok(this.find(s.as_str())),
+        Needle::Function(f) => with_predicate(&f, |p| this.find(p)),
+    }
+}
+
+/// Returns the byte index for the first character of the last match of the
+/// pattern in this string.
+///
+/// Returns [`None`] if the pattern doesn't match.
+///
+/// The pattern can be a `char`, a string, or a function which determines if a
+/// character matches.
+///
+/// # Examples
+///
+/// ```rune
+/// let s = "Löwe 老虎 Léopard Gepardi";
+///
+/// assert_eq!(s.rfind('L'), Some(13));
+/// assert_eq!(s.rfind('é'), Some(14));
+/// assert_eq!(s.rfind("pard"), Some(24));
+/// assert_eq!(s.rfind(char::is_whitespace), Some(21));
+/// assert_eq!(s.rfind(|c| c == 'x'), None);
+/// ```
+#[rune::function(instance)]
+fn rfind(this: &str, pattern: Value) -> Result<Option<usize>, VmError> {
+    match Needle::from_value(pattern)? {
+        Needle::Char(c) => Ok(this.rfind(c)),
+        Needle::String(s) => Ok(this.rfind(s.as_str())),
+        Needle::Function(f) => with_predicate(&f, |p| this.rfind(p)),
+    }
+}
+
+/// Returns a string with the prefix removed.
+///
+/// If the string starts with the pattern `prefix`, returns the substring after
+/// the prefix, wrapped in `Some`. If the string doesn't start with `prefix`,
+/// returns `None`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!("foo:bar".strip_prefix("foo:"), Some("bar"));
+/// assert_eq!("foo:bar".strip_prefix("bar"), None);
+/// assert_eq!("foofoo".strip_prefix("foo"), Some("foo"));
+/// assert_eq!("foo:bar".strip_prefix('f'), Some("oo:bar"));
+/// ```
+#[rune::function(instance)]
+fn strip_prefix(this: &str, prefix: Value) -> Result<Option<String>, VmError> {
+    let outcome = match Needle::from_value(prefix)? {
+        Needle::Char(c) => this.strip_prefix(c),
+        Needle::String(s) => this.strip_prefix(s.as_str()),
+        Needle::Function(f) => with_predicate(&f, |p| this.strip_prefix(p))?,
+    };
+
+    Ok(outcome.map(str::try_to_owned).transpose()?)
+}
+
+/// Returns a string with the suffix removed.
+///
+/// If the string ends with the pattern `suffix`, returns the substring before
+/// the suffix, wrapped in `Some`. If the string doesn't end with `suffix`,
+/// returns `None`.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!("bar:foo".strip_suffix(":foo"), Some("bar"));
+/// assert_eq!("bar:foo".strip_suffix("bar"), None);
+/// assert_eq!("foofoo".strip_suffix("foo"), Some("foo"));
+/// assert_eq!("bar:foo".strip_suffix('o'), Some("bar:fo"));
+/// ```
+#[rune::function(instance)]
+fn strip_suffix(this: &str, suffix: Value) -> Result<Option<String>, VmError> {
+    let outcome = match Needle::from_value(suffix)? {
+        Needle::Char(c) => this.strip_suffix(c),
+        Needle::String(s) => this.strip_suffix(s.as_str()),
+        Needle::Function(f) => with_predicate(&f, |p| this.strip_suffix(p))?,
+    };
+
+    Ok(outcome.map(str::try_to_owned).transpose()?)
+}
+
+/// An iterator over substrings of the given string, separated by characters
+/// matched by a pattern and yielded in reverse order.
+///
+/// The pattern can be a `char`, a string, or a function which determines if a
+/// character matches.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = "Mary had a little lamb".rsplit(' ').collect::<Vec>();
+/// assert_eq!(v, ["lamb", "little", "a", "had", "Mary"]);
+///
+/// let v = "".rsplit('X').collect::<Vec>();
+/// assert_eq!(v, [""]);
+///
+/// let v = "lionXXtigerXleopard".rsplit('X').collect::<Vec>();
+/// assert_eq!(v, ["leopard", "tiger", "", "lion"]);
+///
+/// let v = "lion::tiger::leopard".rsplit("::").collect::<Vec>();
+/// assert_eq!(v, ["leopard", "tiger", "lion"]);
+///
+/// let v = "abc1defXghi".rsplit(|c| c == '1' || c == 'X').collect::<Vec>();
+/// assert_eq!(v, ["ghi", "def", "abc"]);
+/// ```
+#[rune::function(instance)]
+fn rsplit(this: &str, pattern: Value) -> Result<Iter, VmError> {
+    fn collect<'a>(parts: impl Iterator<Item = &'a str>) -> Result<Vec<Value>, VmError> {
+        let mut out = Vec::new();
+
+        for part in parts {
+            out.try_push(rune::to_value(part.try_to_owned()?)?)?;
+        }
+
+        Ok(out)
+    }
+
+    let parts = match Needle::from_value(pattern)? {
+        Needle::Char(c) => collect(this.rsplit(c))?,
+        Needle::String(s) => collect(this.rsplit(s.as_str()))?,
+        Needle::Function(f) => with_predicate(&f, |p| collect(this.rsplit(p)))??,
+    };
+
+    let parts = Value::new(runtime::Vec::from(parts))?.into_ref::<runtime::Vec>()?;
+    Ok(runtime::Vec::rune_iter(parts))
+}
+
+/// An iterator over the lines of a string.
+///
+/// Lines are split at line endings that are either newlines (`\n`) or
+/// sequences of a carriage return followed by a line feed (`\r\n`).
+///
+/// Line terminators are not included in the lines returned by the iterator.
+///
+/// The final line ending is optional. A string that ends with a final line
+/// ending will return the same lines as an otherwise identical string without
+/// a final line ending.
+///
+/// # Examples
+///
+/// ```rune
+/// let text = "foo\r\nbar\n\nbaz\r";
+/// let lines = text.lines();
+///
+/// assert_eq!(Some("foo"), lines.next());
+/// assert_eq!(Some("bar"), lines.next());
+/// assert_eq!(Some(""), lines.next());
+/// // Trailing carriage return is included in the last line
+/// assert_eq!(Some("baz\r"), lines.next());
+///
+/// assert_eq!(None, lines.next());
+/// ```
+///
+/// The final line does not require any ending:
+///
+/// ```rune
+/// let text = "foo\nbar\n\r\nbaz";
+/// let lines = text.lines().rev();
+///
+/// assert_eq!(Some("baz"), lines.next());
+/// assert_eq!(Some(""), lines.next());
+/// assert_eq!(Some("bar"), lines.next());
+/// assert_eq!(Some("foo"), lines.next());
+///
+/// assert_eq!(None, lines.next());
+/// ```
+#[rune::function(instance)]
+fn lines(this: Ref<str>) -> Lines {
+    Lines::new(this)
+}
+
+/// Splits a string by whitespace.
+///
+/// The iterator returned will return strings that are sub-slices of the
+/// original string, separated by any amount of whitespace.
+///
+/// 'Whitespace' is defined according to the terms of the Unicode Derived Core
+/// Property `White_Space`.
+///
+/// # Examples
+///
+/// ```rune
+/// let iter = "A few words".split_whitespace();
+///
+/// assert_eq!(Some("A"), iter.next());
+/// assert_eq!(Some("few"), iter.next());
+/// assert_eq!(Some("words"), iter.next());
+///
+/// assert_eq!(None, iter.next());
+/// ```
+///
+/// All kinds of whitespace are considered:
+///
+/// ```rune
+/// let iter = " Mary   had\ta\u{2009}little  \n\t lamb".split_whitespace();
+/// assert_eq!(iter.collect::<Vec>(), ["Mary", "had", "a", "little", "lamb"]);
+/// ```
+///
+/// If the string is empty or all whitespace, the iterator yields no strings:
+///
+/// ```rune
+/// assert_eq!("".split_whitespace().next(), None);
+/// assert_eq!("   ".split_whitespace().next(), None);
+/// ```
+#[rune::function(instance)]
+fn split_whitespace(this: Ref<str>) -> SplitWhitespace {
+    SplitWhitespace::new(this)
+}
+
+/// Creates a new string by repeating a string `n` times.
+///
+/// # Panics
+///
+/// This function will panic if the capacity would overflow.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!("abc".repeat(4), "abcabcabcabc");
+/// assert_eq!("abc".repeat(0), "");
+/// ```
+#[rune::function(instance)]
+fn repeat(this: &str, n: usize) -> Result<String, VmError> {
+    let Some(capacity) = this.len().checked_mul(n) else {
+        return Err(VmError::panic("capacity overflow"));
+    };
+
+    let mut string = String::try_with_capacity(capacity)?;
+
+    for _ in 0..n {
+        string.try_push_str(this)?;
+    }
+
+    Ok(string)
+}
+
+/// Pads the start of the string with the `fill` character until it is `width`
+/// characters long.
+///
+/// If the string is already `width` characters or longer, an unmodified copy of
+/// it is returned.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!("42".pad_start(5, '0'), "00042");
+/// assert_eq!("hello".pad_start(3, ' '), "hello");
+/// assert_eq!("åäö".pad_start(4, '*'), "*åäö");
+/// ```
+#[rune::function(instance)]
+fn pad_start(this: &str, width: usize, fill: char) -> Result<String, VmError> {
+    let padding = width.saturating_sub(this.chars().count());
+    let mut string = String::try_with_capacity(this.len() + padding * fill.len_utf8())?;
+
+    for _ in 0..padding {
+        string.try_push(fill)?;
+    }
+
+    string.try_push_str(this)?;
+    Ok(string)
+}
+
+/// Pads the end of the string with the `fill` character until it is `width`
+/// characters long.
+///
+/// If the string is already `width` characters or longer, an unmodified copy of
+/// it is returned.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!("ab".pad_end(5, '.'), "ab...");
+/// assert_eq!("hello".pad_end(3, ' '), "hello");
+/// assert_eq!("åäö".pad_end(4, '*'), "åäö*");
+/// ```
+#[rune::function(instance)]
+fn pad_end(this: &str, width: usize, fill: char) -> Result<String, VmError> {
+    let padding = width.saturating_sub(this.chars().count());
+    let mut string = String::try_with_capacity(this.len() + padding * fill.len_utf8())?;
+    string.try_push_str(this)?;
+
+    for _ in 0..padding {
+        string.try_push(fill)?;
+    }
+
+    Ok(string)
+}
+
 /// Returns a string slice with leading and trailing whitespace removed.
 ///
 /// 'Whitespace' is defined according to the terms of the Unicode Derived Core
@@ -1454,6 +1790,190 @@ impl Chars {
     }
 }
 
+#[derive(Any)]
+#[rune(item = ::std::string)]
+struct Lines {
+    string: Ref<str>,
+    start: usize,
+    end: usize,
+}
+
+impl Lines {
+    fn new(string: Ref<str>) -> Self {
+        let end = string.len();
+        Self {
+            string,
+            start: 0,
+            end,
+        }
+    }
+
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Result<Option<String>, VmError> {
+        let Some(string) = self.string.get(self.start..self.end) else {
+            return Ok(None);
+        };
+
+        if string.is_empty() {
+            return Ok(None);
+        }
+
+        let line = match string.find('\n') {
+            Some(n) => &string[..n + 1],
+            None => string,
+        };
+
+        self.start += line.len();
+        Ok(Some(strip_line_ending(line).try_to_owned()?))
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Result<Option<String>, VmError> {
+        let Some(string) = self.string.get(self.start..self.end) else {
+            return Ok(None);
+        };
+
+        if string.is_empty() {
+            return Ok(None);
+        }
+
+        let head = string.strip_suffix('\n').unwrap_or(string);
+
+        let line = match head.rfind('\n') {
+            Some(n) => &string[n + 1..],
+            None => string,
+        };
+
+        self.end -= line.len();
+        Ok(Some(strip_line_ending(line).try_to_owned()?))
+    }
+}
+
+/// Strip a single trailing `\n` or `\r\n` from a line.
+fn strip_line_ending(line: &str) -> &str {
+    let Some(line) = line.strip_suffix('\n') else {
+        return line;
+    };
+
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+#[derive(Any)]
+#[rune(item = ::std::string)]
+struct SplitWhitespace {
+    string: Ref<str>,
+    start: usize,
+    end: usize,
+}
+
+impl SplitWhitespace {
+    fn new(string: Ref<str>) -> Self {
+        let end = string.len();
+        Self {
+            string,
+            start: 0,
+            end,
+        }
+    }
+
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> Result<Option<String>, VmError> {
+        let Some(string) = self.string.get(self.start..self.end) else {
+            return Ok(None);
+        };
+
+        let trimmed = string.trim_start();
+        self.start += string.len() - trimmed.len();
+
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+
+        let n = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+        self.start += n;
+        Ok(Some(trimmed[..n].try_to_owned()?))
+    }
+
+    #[rune::function(keep, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Result<Option<String>, VmError> {
+        let Some(string) = self.string.get(self.start..self.end) else {
+            return Ok(None);
+        };
+
+        let trimmed = string.trim_end();
+        self.end -= string.len() - trimmed.len();
+
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+
+        let n = trimmed.rfind(char::is_whitespace).map_or(0, |n| {
+            n + trimmed[n..].chars().next().map_or(0, char::len_utf8)
+        });
+
+        self.end -= trimmed.len() - n;
+        Ok(Some(trimmed[n..].try_to_owned()?))
+    }
+}
+
+/// A pattern which can be searched for in a string.
+enum Needle {
+    Char(char),
+    String(String),
+    Function(Function),
+}
+
+impl Needle {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        match value.as_ref() {
+            Repr::Inline(Inline::Char(c)) => return Ok(Self::Char(*c)),
+            Repr::Any(value) => match value.type_hash() {
+                String::HASH => {
+                    let s = value.borrow_ref::<String>()?;
+                    return Ok(Self::String(s.try_clone()?));
+                }
+                Function::HASH => {
+                    let f = value.borrow_ref::<Function>()?;
+                    return Ok(Self::Function(f.try_clone()?));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+
+        Err(VmError::from([
+            VmErrorKind::expected::<String>(value.type_info()),
+            VmErrorKind::bad_argument(0),
+        ]))
+    }
+}
+
+/// Run `op` with a character predicate which calls the function `f`, returning
+/// the first error raised by the function if any.
+fn with_predicate<T>(
+    f: &Function,
+    op: impl FnOnce(&mut dyn FnMut(char) -> bool) -> T,
+) -> Result<T, VmError> {
+    let mut error = None;
+
+    let outcome = op(&mut |c: char| match f.call::<bool>((c,)) {
+        Ok(b) => b,
+        Err(e) => {
+            if error.is_none() {
+                error = Some(e);
+            }
+
+            false
+        }
+    });
+
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    Ok(outcome)
+}
+
 trait Pattern: 'static + TryClone + Named + FromValue + ToValue + MaybeTypeOf + TypeOf {
     fn test(&self, tail: &str) -> Result<(bool, usize), VmError>;
 
@@ -0,0 +1,67 @@
+//! Integers.
+
+use core::cmp::Ordering;
+use core::hash::Hash as _;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::string::TryToString;
+use crate::runtime::{Fixed, Formatter, Hasher};
+use crate::{ContextError, Module};
+
+/// Unsigned integers.
+///
+/// This provides methods for computing over and parsing 128-bit unsigned
+/// integers.
+#[rune::module(::std::u128)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+    unsigned!(m, u128, "0", "!(0 as u128)");
+    m.function_meta(hash__meta)?;
+    m.function_meta(display_fmt__meta)?;
+    m.function_meta(debug_fmt__meta)?;
+    Ok(m)
+}
+
+unsigned_fns!(cast u128);
+
+/// Hash the integer.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::ops::hash;
+///
+/// assert_eq!(hash(10 as u128), hash(10 as u128));
+/// assert_ne!(hash(10 as u128), hash(11 as u128));
+/// ```
+#[rune::function(keep, instance, protocol = HASH)]
+fn hash(this: Fixed<u128>, hasher: &mut Hasher) {
+    this.0.hash(hasher);
+}
+
+/// Write a display representation of the integer.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(format!("{}", 10 as u128), "10");
+/// ```
+#[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+fn display_fmt(this: Fixed<u128>, f: &mut Formatter) -> alloc::Result<()> {
+    write!(f, "{}", this.0)
+}
+
+/// Write a debug representation of the integer.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(format!("{:?}", 10 as u128), "10");
+/// ```
+#[rune::function(keep, instance, protocol = DEBUG_FMT)]
+fn debug_fmt(this: Fixed<u128>, f: &mut Formatter) -> alloc::Result<()> {
+    write!(f, "{:?}", this.0)
+}
@@ -0,0 +1,21 @@
+//! Integers.
+
+use core::cmp::Ordering;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc::string::TryToString;
+use crate::runtime::Fixed;
+use crate::{ContextError, Module};
+
+/// Unsigned integers.
+///
+/// This provides methods for computing over and parsing 16-bit unsigned integers.
+#[rune::module(::std::u16)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+    unsigned!(m, u16, "0", "65535");
+    Ok(m)
+}
+
+unsigned_fns!(cast u16);
@@ -0,0 +1,21 @@
+//! Integers.
+
+use core::cmp::Ordering;
+use core::num::ParseIntError;
+
+use crate as rune;
+use crate::alloc::string::TryToString;
+use crate::runtime::Fixed;
+use crate::{ContextError, Module};
+
+/// Unsigned integers.
+///
+/// This provides methods for computing over and parsing 32-bit unsigned integers.
+#[rune::module(::std::u32)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module__meta)?;
+    unsigned!(m, u32, "0", "4294967295");
+    Ok(m)
+}
+
+unsigned_fns!(cast u32);
@@ -5,6 +5,7 @@ use core::num::ParseIntError;
 
 use crate as rune;
 use crate::alloc::string::TryToString;
+use crate::runtime::Fixed;
 use crate::{ContextError, Module};
 
 /// Unsigned integers.
@@ -13,7 +14,7 @@ use crate::{ContextError, Module};
 #[rune::module(::std::u64)]
 pub fn module() -> Result<Module, ContextError> {
     let mut m = Module::from_meta(self::module__meta)?;
-    unsigned!(m, u64);
+    unsigned!(m, u64, "0", "18446744073709551615");
     Ok(m)
 }
 
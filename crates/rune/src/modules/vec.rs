@@ -1,15 +1,18 @@
 //! The [`Vec`] dynamic vector.
 
 use core::cmp::Ordering;
+use core::ops;
 
 use crate as rune;
 use crate::alloc;
 use crate::alloc::prelude::*;
+use crate::modules::iter::{Chunks, Windows};
 use crate::runtime::slice::Iter;
 use crate::runtime::{
-    EnvProtocolCaller, Formatter, Function, Hasher, Ref, TypeOf, Value, Vec, VmError, VmErrorKind,
+    EnvProtocolCaller, Formatter, Function, Hasher, Range, RangeFrom, RangeFull, RangeInclusive,
+    RangeTo, RangeToInclusive, Ref, TypeOf, Value, Vec, VmError, VmErrorKind,
 };
-use crate::{docstring, ContextError, Module};
+use crate::{docstring, ContextError, Module, TypeHash};
 
 /// The [`Vec`] dynamic vector.
 ///
@@ -81,6 +84,18 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(index_get)?;
     m.function_meta(index_set)?;
     m.function_meta(resize)?;
+    m.function_meta(truncate)?;
+    m.function_meta(retain)?;
+    m.function_meta(dedup)?;
+    m.function_meta(drain)?;
+    m.function_meta(swap)?;
+    m.function_meta(reverse)?;
+    m.function_meta(binary_search)?;
+    m.function_meta(sort_by_key)?;
+    m.function_meta(split_at)?;
+    m.function_meta(windows)?;
+    m.function_meta(chunks)?;
+    m.function_meta(join)?;
     m.function_meta(debug_fmt__meta)?;
 
     m.function_meta(clone__meta)?;
@@ -602,6 +617,418 @@ fn resize(this: &mut Vec, new_len: usize, value: Value) -> Result<(), VmError> {
     Vec::resize(this, new_len, value)
 }
 
+/// Shortens the vector, keeping the first `len` elements and dropping the
+/// rest.
+///
+/// If `len` is greater or equal to the vector's current length, this has no
+/// effect.
+///
+/// # Examples
+///
+/// ```rune
+/// let vec = [1, 2, 3, 4, 5];
+/// vec.truncate(2);
+/// assert_eq!(vec, [1, 2]);
+///
+/// let vec = [1, 2, 3];
+/// vec.truncate(8);
+/// assert_eq!(vec, [1, 2, 3]);
+/// ```
+#[rune::function(instance)]
+fn truncate(this: &mut Vec, len: usize) {
+    this.truncate(len);
+}
+
+/// Retains only the elements specified by the predicate.
+///
+/// In other words, remove all elements `e` for which `f(e)` returns `false`.
+/// This method operates in place, visiting each element exactly once in the
+/// original order, and preserves the order of the retained elements.
+///
+/// # Examples
+///
+/// ```rune
+/// let vec = [1, 2, 3, 4];
+/// vec.retain(|x| x % 2 == 0);
+/// assert_eq!(vec, [2, 4]);
+/// ```
+#[rune::function(instance)]
+fn retain(this: &mut Vec, f: &Function) -> Result<(), VmError> {
+    let mut error = None;
+
+    this.retain(|value| match f.call::<bool>((value,)) {
+        Ok(retain) => retain,
+        Err(e) => {
+            if error.is_none() {
+                error = Some(e);
+            }
+
+            true
+        }
+    });
+
+    if let Some(e) = error {
+        Err(e)
+    } else {
+        Ok(())
+    }
+}
+
+/// Removes consecutive repeated elements in the vector according to the
+/// [`PARTIAL_EQ`] protocol.
+///
+/// If the vector is sorted, this removes all duplicates.
+///
+/// # Examples
+///
+/// ```rune
+/// let vec = [1, 2, 2, 3, 2];
+/// vec.dedup();
+/// assert_eq!(vec, [1, 2, 3, 2]);
+/// ```
+#[rune::function(instance)]
+fn dedup(this: &mut Vec) -> Result<(), VmError> {
+    let mut error = None;
+
+    this.dedup_by(|a, b| match Value::partial_eq(a, b) {
+        Ok(same) => same,
+        Err(e) => {
+            if error.is_none() {
+                error = Some(e);
+            }
+
+            false
+        }
+    });
+
+    if let Some(e) = error {
+        Err(e)
+    } else {
+        Ok(())
+    }
+}
+
+/// Removes the specified range from the vector in bulk, returning all removed
+/// elements as an iterator.
+///
+/// # Panics
+///
+/// Panics if the starting point is greater than the end point or if the end
+/// point is greater than the length of the vector.
+///
+/// ```rune,should_panic
+/// let v = [1, 2, 3];
+/// v.drain(1..4);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let v = [1, 2, 3];
+/// let u = v.drain(1..).collect::<Vec>();
+/// assert_eq!(v, [1]);
+/// assert_eq!(u, [2, 3]);
+///
+/// // A full range clears the vector, like `clear()` does
+/// v.drain(..);
+/// assert_eq!(v, []);
+/// ```
+#[rune::function(instance)]
+fn drain(this: &mut Vec, range: Value) -> Result<Iter, VmError> {
+    let range = slice_range(this.len(), range)?;
+
+    let mut removed = alloc::Vec::try_with_capacity(range.len())?;
+
+    for value in this.drain(range) {
+        removed.try_push(value)?;
+    }
+
+    let removed = Value::new(Vec::from(removed))?.into_ref::<Vec>()?;
+    Ok(Vec::rune_iter(removed))
+}
+
+/// Swaps two elements in the vector.
+///
+/// If `a` equals to `b`, it's guaranteed that elements won't change value.
+///
+/// # Panics
+///
+/// Panics if `a` or `b` are out of bounds.
+///
+/// ```rune,should_panic
+/// let v = [1, 2, 3];
+/// v.swap(1, 3);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let v = ["a", "b", "c", "d", "e"];
+/// v.swap(2, 4);
+/// assert_eq!(v, ["a", "b", "e", "d", "c"]);
+/// ```
+#[rune::function(instance)]
+fn swap(this: &mut Vec, a: usize, b: usize) -> Result<(), VmError> {
+    for index in [a, b] {
+        if index >= this.len() {
+            return Err(VmError::new(VmErrorKind::OutOfRange {
+                index: index.into(),
+                length: this.len().into(),
+            }));
+        }
+    }
+
+    this.swap(a, b);
+    Ok(())
+}
+
+/// Reverses the order of elements in the vector, in place.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = [1, 2, 3];
+/// v.reverse();
+/// assert_eq!(v, [3, 2, 1]);
+/// ```
+#[rune::function(instance)]
+fn reverse(this: &mut Vec) {
+    this.reverse();
+}
+
+/// Binary searches this vector for a given element. If the vector is not
+/// sorted, the returned result is unspecified and meaningless.
+///
+/// If the value is found then `Ok` is returned, containing the index of the
+/// matching element. If there are multiple matches, then any one of the
+/// matches could be returned. If the value is not found then `Err` is
+/// returned, containing the index where a matching element could be inserted
+/// while maintaining sorted order.
+///
+/// Elements are compared using the [`CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// let s = [0, 1, 1, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55];
+///
+/// assert_eq!(s.binary_search(13), Ok(9));
+/// assert_eq!(s.binary_search(4), Err(7));
+/// assert_eq!(s.binary_search(100), Err(13));
+/// let r = s.binary_search(1);
+/// assert!(match r { Ok(i) => i >= 1 && i <= 4, _ => false });
+/// ```
+///
+/// If you want to insert an item to a sorted vector, while maintaining sort
+/// order:
+///
+/// ```rune
+/// let s = [0, 1, 1, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55];
+/// let num = 42;
+///
+/// let idx = match s.binary_search(num) {
+///     Ok(idx) => idx,
+///     Err(idx) => idx,
+/// };
+///
+/// s.insert(idx, num);
+/// assert_eq!(s, [0, 1, 1, 1, 1, 2, 3, 5, 8, 13, 21, 34, 42, 55]);
+/// ```
+#[rune::function(instance)]
+fn binary_search(this: &Vec, x: Value) -> Result<Result<usize, usize>, VmError> {
+    let mut error = None;
+
+    let result = this.binary_search_by(|probe| match Value::cmp(probe, &x) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            if error.is_none() {
+                error = Some(e);
+            }
+
+            Ordering::Equal
+        }
+    });
+
+    if let Some(e) = error {
+        Err(e)
+    } else {
+        Ok(result)
+    }
+}
+
+/// Sorts the vector with a key extraction function.
+///
+/// The keys produced by the function are compared using the [`CMP`] protocol.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = [-5, 4, 1, -3, 2];
+///
+/// v.sort_by_key(|k| k.abs());
+/// assert_eq!(v, [1, 2, -3, 4, -5]);
+/// ```
+#[rune::function(instance)]
+fn sort_by_key(this: &mut Vec, f: &Function) -> Result<(), VmError> {
+    let mut error = None;
+
+    this.sort_by(|a, b| match cmp_by_key(f, a, b) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            if error.is_none() {
+                error = Some(e);
+            }
+
+            Ordering::Equal
+        }
+    });
+
+    if let Some(e) = error {
+        Err(e)
+    } else {
+        Ok(())
+    }
+}
+
+/// Divides the vector into two at an index.
+///
+/// The first will contain all indices from `[0, mid)` (excluding the index
+/// `mid` itself) and the second will contain all indices from `[mid, len)`
+/// (excluding the index `len` itself).
+///
+/// # Panics
+///
+/// Panics if `mid > len`.
+///
+/// ```rune,should_panic
+/// let v = [1, 2, 3];
+/// v.split_at(4);
+/// ```
+///
+/// # Examples
+///
+/// ```rune
+/// let v = [1, 2, 3, 4, 5, 6];
+///
+/// let (left, right) = v.split_at(0);
+/// assert_eq!(left, []);
+/// assert_eq!(right, [1, 2, 3, 4, 5, 6]);
+///
+/// let (left, right) = v.split_at(2);
+/// assert_eq!(left, [1, 2]);
+/// assert_eq!(right, [3, 4, 5, 6]);
+/// ```
+#[rune::function(instance)]
+fn split_at(this: &Vec, mid: usize) -> Result<(Vec, Vec), VmError> {
+    if mid > this.len() {
+        return Err(VmError::new(VmErrorKind::OutOfRange {
+            index: mid.into(),
+            length: this.len().into(),
+        }));
+    }
+
+    let (left, right) = this.split_at(mid);
+    let left = Vec::from(alloc::Vec::try_from(left)?);
+    let right = Vec::from(alloc::Vec::try_from(right)?);
+    Ok((left, right))
+}
+
+/// Returns an iterator over all contiguous windows of length `size`. The
+/// windows overlap. If the vector is shorter than `size`, the iterator returns
+/// no values.
+///
+/// # Panics
+///
+/// Panics if `size` is 0.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = ['l', 'o', 'r', 'e', 'm'];
+/// let iter = v.windows(3);
+///
+/// assert_eq!(iter.next(), Some(['l', 'o', 'r']));
+/// assert_eq!(iter.next(), Some(['o', 'r', 'e']));
+/// assert_eq!(iter.next(), Some(['r', 'e', 'm']));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[rune::function(instance)]
+fn windows(this: Ref<Vec>, size: usize) -> Result<Windows, VmError> {
+    Windows::new(rune::to_value(Vec::rune_iter(this))?, size)
+}
+
+/// Returns an iterator over `chunk_size` elements of the vector at a time,
+/// starting at the beginning of the vector.
+///
+/// The chunks do not overlap. If `chunk_size` does not divide the length of
+/// the vector, then the last chunk will not have length `chunk_size`.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is 0.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = ['l', 'o', 'r', 'e', 'm'];
+/// let iter = v.chunks(2);
+///
+/// assert_eq!(iter.next(), Some(['l', 'o']));
+/// assert_eq!(iter.next(), Some(['r', 'e']));
+/// assert_eq!(iter.next(), Some(['m']));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[rune::function(instance)]
+fn chunks(this: Ref<Vec>, chunk_size: usize) -> Result<Chunks, VmError> {
+    Chunks::new(rune::to_value(Vec::rune_iter(this))?, chunk_size)
+}
+
+/// Flattens the vector into a single value, placing a given separator between
+/// each element.
+///
+/// If the separator is a string, every element must be a string and the
+/// result is a string. Otherwise every element is iterated over and the result
+/// is a vector, with the separator inserted as a value between them.
+///
+/// # Examples
+///
+/// ```rune
+/// assert_eq!(["hello", "world"].join(" "), "hello world");
+/// assert_eq!([[1, 2], [3, 4]].join(0), [1, 2, 0, 3, 4]);
+/// assert_eq!([].join(", "), "");
+/// ```
+#[rune::function(instance)]
+fn join(this: &Vec, separator: Value) -> Result<Value, VmError> {
+    if let Some(value) = separator.as_any() {
+        if value.type_hash() == String::HASH {
+            let separator = value.borrow_ref::<String>()?;
+            let mut string = String::new();
+
+            for (index, value) in this.iter().enumerate() {
+                if index > 0 {
+                    string.try_push_str(&separator)?;
+                }
+
+                string.try_push_str(&value.borrow_string_ref()?)?;
+            }
+
+            return Ok(rune::to_value(string)?);
+        }
+    }
+
+    let mut vec = Vec::new();
+
+    for (index, value) in this.iter().enumerate() {
+        if index > 0 {
+            vec.push(separator.clone())?;
+        }
+
+        vec.extend(value.clone())?;
+    }
+
+    Ok(rune::to_value(vec)?)
+}
+
 /// Write a debug representation to a string.
 ///
 /// This calls the [`DEBUG_FMT`] protocol over all elements of the
@@ -700,3 +1127,62 @@ fn cmp(this: &Vec, other: &Vec) -> Result<Ordering, VmError> {
 fn hash(this: &Vec, hasher: &mut Hasher) -> Result<(), VmError> {
     Vec::hash_with(this, hasher, &mut EnvProtocolCaller)
 }
+
+/// Compare two values by the keys extracted from them by `f`.
+fn cmp_by_key(f: &Function, a: &Value, b: &Value) -> Result<Ordering, VmError> {
+    let a = f.call::<Value>((a,))?;
+    let b = f.call::<Value>((b,))?;
+    Value::cmp(&a, &b)
+}
+
+/// Convert a range into the slice of indexes it covers in a vector of the
+/// given length.
+fn slice_range(len: usize, range: Value) -> Result<ops::Range<usize>, VmError> {
+    let (start, end) = 'out: {
+        if let Some(value) = range.as_any() {
+            match value.type_hash() {
+                Range::HASH => {
+                    let range = value.borrow_ref::<Range>()?;
+                    break 'out (range.start.as_usize()?, range.end.as_usize()?);
+                }
+                RangeFrom::HASH => {
+                    let range = value.borrow_ref::<RangeFrom>()?;
+                    break 'out (range.start.as_usize()?, len);
+                }
+                RangeFull::HASH => {
+                    _ = value.borrow_ref::<RangeFull>()?;
+                    break 'out (0, len);
+                }
+                RangeInclusive::HASH => {
+                    let range = value.borrow_ref::<RangeInclusive>()?;
+                    let end = range.end.as_usize()?;
+                    break 'out (range.start.as_usize()?, end.saturating_add(1));
+                }
+                RangeTo::HASH => {
+                    let range = value.borrow_ref::<RangeTo>()?;
+                    break 'out (0, range.end.as_usize()?);
+                }
+                RangeToInclusive::HASH => {
+                    let range = value.borrow_ref::<RangeToInclusive>()?;
+                    break 'out (0, range.end.as_usize()?.saturating_add(1));
+                }
+                _ => {}
+            }
+        }
+
+        return Err(VmError::expected::<Range>(range.type_info()));
+    };
+
+    if end > len {
+        return Err(VmError::new(VmErrorKind::OutOfRange {
+            index: end.into(),
+            length: len.into(),
+        }));
+    }
+
+    if start > end {
+        return Err(VmError::panic("range start is greater than range end"));
+    }
+
+    Ok(start..end)
+}
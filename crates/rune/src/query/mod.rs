@@ -215,6 +215,8 @@ pub(crate) enum ImplItemKind {
     Ast {
         /// Non-expanded ast of the path.
         path: Box<ast::Path>,
+        /// Non-expanded ast of the trait being implemented, if any.
+        trait_: Option<Box<ast::Path>>,
         /// Functions in the impl block.
         functions: Vec<ast::ItemFn>,
     },
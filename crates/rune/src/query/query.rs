@@ -73,6 +73,11 @@ pub(crate) struct QueryInner<'arena> {
     names: Names,
     /// Queue of impl items to process.
     pub(crate) defer_queue: VecDeque<DeferEntry>,
+    /// Traits declared in scripts, used to expand trait impls.
+    traits: HashMap<ItemId, Rc<indexing::Trait>>,
+    /// Type annotations of functions and structs by their hash, collected when
+    /// type checking is enabled.
+    annotations: HashMap<Hash, Rc<indexing::Annotations>>,
 }
 
 impl QueryInner<'_> {
@@ -748,6 +753,43 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(())
     }
 
+    /// Add a new trait item.
+    #[tracing::instrument(skip_all)]
+    pub(crate) fn index_trait(&mut self, tr: indexing::Trait) -> compile::Result<()> {
+        let item_meta = tr.item_meta;
+        tracing::trace!(item = ?self.pool.item(item_meta.item));
+
+        self.inner.traits.try_insert(item_meta.item, Rc::new(tr))?;
+
+        self.index(indexing::Entry {
+            item_meta,
+            indexed: Indexed::Trait,
+        })?;
+
+        Ok(())
+    }
+
+    /// Get a trait which has been declared in a script.
+    pub(crate) fn get_trait(&self, item: ItemId) -> Option<Rc<indexing::Trait>> {
+        self.inner.traits.get(&item).cloned()
+    }
+
+    /// Register type annotations for the function or struct with the given
+    /// hash.
+    pub(crate) fn index_annotations(
+        &mut self,
+        hash: Hash,
+        annotations: Rc<indexing::Annotations>,
+    ) -> compile::Result<()> {
+        self.inner.annotations.try_insert(hash, annotations)?;
+        Ok(())
+    }
+
+    /// Get type annotations registered for the given hash.
+    pub(crate) fn get_annotations(&self, hash: Hash) -> Option<Rc<indexing::Annotations>> {
+        self.inner.annotations.get(&hash).cloned()
+    }
+
     /// Add a new enum item.
     #[tracing::instrument(skip_all)]
     pub(crate) fn index_enum(&mut self, item_meta: ItemMeta) -> compile::Result<()> {
@@ -1829,6 +1871,7 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 meta::Kind::Import(import.entry)
             }
             Indexed::Module => meta::Kind::Module,
+            Indexed::Trait => meta::Kind::Trait,
         };
 
         let source = SourceMeta {
@@ -1,9 +1,11 @@
 use core::future::Future as _;
 use core::pin::Pin;
+use core::ptr::NonNull;
 use core::task::{ready, Context, Poll};
 
 use crate::async_vm_try;
-use crate::runtime::{Future, Output, Select, Vm, VmError};
+use crate::runtime::vm_diagnostics::VmDiagnosticsObj;
+use crate::runtime::{env, Future, Output, Select, Vm, VmDiagnostics, VmError};
 
 /// A stored await task.
 #[derive(Debug)]
@@ -19,19 +21,37 @@ impl Awaited {
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         vm: &mut Vm,
+        diagnostics: Option<&mut dyn VmDiagnostics>,
     ) -> Poll<Result<(), VmError>> {
         let this = unsafe { Pin::get_unchecked_mut(self) };
 
+        let mut vm_diagnostics_obj;
+
+        let diagnostics = match diagnostics {
+            Some(diagnostics) => {
+                vm_diagnostics_obj = VmDiagnosticsObj::new(diagnostics);
+                Some(NonNull::from(&mut vm_diagnostics_obj))
+            }
+            None => env::diagnostics(vm.unit()),
+        };
+
+        // Native futures might need to access the environment when polled,
+        // like to consult the sandbox or to call back into the virtual machine,
+        // so it's set up the same way as when running instructions.
+        let guard = env::Guard::new(vm.context().clone(), vm.unit().clone(), diagnostics);
+
         match *this {
             Self::Future(ref mut future, out) => {
                 let future = unsafe { Pin::new_unchecked(future) };
                 let result = ready!(future.poll(cx));
+                drop(guard);
                 let value = async_vm_try!(VmError::with_vm(result, vm));
                 async_vm_try!(vm.stack_mut().store(out, value));
             }
             Self::Select(ref mut select, out) => {
                 let select = unsafe { Pin::new_unchecked(select) };
                 let result = ready!(select.poll(cx));
+                drop(guard);
                 let (ip, value) = async_vm_try!(VmError::with_vm(result, vm));
                 vm.set_ip(ip);
                 async_vm_try!(vm.stack_mut().store(out, || value));
@@ -1,7 +1,6 @@
 #[macro_use]
 mod macros;
 
-use core::any;
 use core::cmp::Ordering;
 use core::fmt;
 
@@ -17,8 +16,8 @@ use crate::{self as rune};
 use crate::{declare_dyn_trait, hash_in, Hash, TypeHash};
 
 use super::{
-    AnyTypeInfo, Bytes, ExpectedType, FromValue, Inline, Object, OwnedTuple, Repr, RuntimeError,
-    ToValue, Tuple, Type, TypeInfo, Value, VmErrorKind, VmIntegerRepr,
+    AnyTypeInfo, Bytes, ExpectedType, Fixed, FromValue, Inline, Object, OwnedTuple, Repr,
+    RuntimeError, ToValue, Tuple, Type, TypeInfo, Value, VmErrorKind, Wide, I128, U128,
 };
 
 /// Derive for the [`ToConstValue`] trait.
@@ -229,6 +228,10 @@ pub(crate) enum ConstValueKind {
     String(Box<str>),
     /// A byte string.
     Bytes(Box<[u8]>),
+    /// A 128-bit signed integer.
+    I128(i128),
+    /// A 128-bit unsigned integer.
+    U128(u128),
     /// An instance of some type of value.
     Instance(Box<ConstInstance>),
 }
@@ -240,6 +243,8 @@ impl ConstValueKind {
             ConstValueKind::Inline(value) => value.type_info(),
             ConstValueKind::String(..) => TypeInfo::any::<String>(),
             ConstValueKind::Bytes(..) => TypeInfo::any::<Bytes>(),
+            ConstValueKind::I128(..) => TypeInfo::any::<I128>(),
+            ConstValueKind::U128(..) => TypeInfo::any::<U128>(),
             ConstValueKind::Instance(instance) => instance.type_info(),
         }
     }
@@ -311,24 +316,9 @@ impl ConstValue {
         T: TryFrom<i64> + TryFrom<u64>,
     {
         match self.kind {
-            ConstValueKind::Inline(Inline::Signed(value)) => match value.try_into() {
-                Ok(number) => Ok(number),
-                Err(..) => Err(RuntimeError::new(
-                    VmErrorKind::ValueToIntegerCoercionError {
-                        from: VmIntegerRepr::from(value),
-                        to: any::type_name::<T>(),
-                    },
-                )),
-            },
-            ConstValueKind::Inline(Inline::Unsigned(value)) => match value.try_into() {
-                Ok(number) => Ok(number),
-                Err(..) => Err(RuntimeError::new(
-                    VmErrorKind::ValueToIntegerCoercionError {
-                        from: VmIntegerRepr::from(value),
-                        to: any::type_name::<T>(),
-                    },
-                )),
-            },
+            ConstValueKind::Inline(value) => value.as_integer(),
+            ConstValueKind::I128(value) => Wide::Signed(value).into_narrow(),
+            ConstValueKind::U128(value) => Wide::Unsigned(value).into_narrow(),
             ref kind => Err(RuntimeError::new(VmErrorKind::ExpectedNumber {
                 actual: kind.type_info(),
             })),
@@ -374,6 +364,8 @@ impl ConstValue {
                 Bytes::HASH => {
                     return ConstValue::bytes(value.borrow_ref::<Bytes>()?.as_slice());
                 }
+                I128::HASH => ConstValueKind::I128(value.borrow_ref::<I128>()?.0),
+                U128::HASH => ConstValueKind::U128(value.borrow_ref::<U128>()?.0),
                 runtime::OwnedTuple::HASH => {
                     let tuple = value.borrow_ref::<runtime::OwnedTuple>()?;
                     let mut const_tuple = Vec::try_with_capacity(tuple.len())?;
@@ -491,6 +483,8 @@ impl ConstValue {
             ConstValueKind::Inline(value) => Ok(Value::from(*value)),
             ConstValueKind::String(string) => Ok(Value::try_from(string.as_ref())?),
             ConstValueKind::Bytes(b) => Ok(Value::try_from(b.as_ref())?),
+            ConstValueKind::I128(value) => Fixed(*value).to_value(),
+            ConstValueKind::U128(value) => Fixed(*value).to_value(),
             ConstValueKind::Instance(instance) => match &**instance {
                 ConstInstance {
                     hash,
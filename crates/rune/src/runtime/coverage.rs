@@ -0,0 +1,129 @@
+//! Recording of which instructions a virtual machine has executed.
+
+use crate::alloc::HashMap;
+use crate::hash::Hash;
+use crate::runtime::vm_diagnostics::{vtable_of, VmDiagnosticsObjVtable};
+use crate::runtime::{VmDiagnostics, VmError};
+
+/// Counters for a conditional jump.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CoverageBranch {
+    /// The number of times the jump was taken.
+    pub taken: u64,
+    /// The number of times the jump was not taken.
+    pub not_taken: u64,
+}
+
+/// Diagnostics recording which instructions are executed by a virtual machine,
+/// and which way conditional jumps went.
+///
+/// Instruction pointers are only meaningful in relation to the unit they were
+/// recorded for, so the same recorder should not be used to execute
+/// different units.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::Coverage;
+/// use rune::sync::Arc;
+/// use rune::{Context, Vm};
+///
+/// let context = Context::with_default_modules()?;
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main(n) {
+///             if n > 1 { "big" } else { "small" }
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+/// let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+///
+/// let mut coverage = Coverage::new();
+/// vm.call_with_diagnostics(["main"], (2,), &mut coverage)?;
+///
+/// let (_, branch) = coverage.branches().next().expect("a branch");
+/// assert_eq!(branch.taken + branch.not_taken, 1);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Default, Debug)]
+pub struct Coverage {
+    instructions: HashMap<usize, u64>,
+    branches: HashMap<usize, CoverageBranch>,
+}
+
+impl Coverage {
+    /// Construct a new empty coverage recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of times the instruction at `ip` has been executed.
+    pub fn instruction(&self, ip: usize) -> u64 {
+        self.instructions.get(&ip).copied().unwrap_or_default()
+    }
+
+    /// Get the counters for the conditional jump at `ip`, if it has been
+    /// executed.
+    pub fn branch(&self, ip: usize) -> Option<CoverageBranch> {
+        self.branches.get(&ip).copied()
+    }
+
+    /// Iterate over all executed instructions and the number of times they
+    /// have been executed.
+    pub fn instructions(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.instructions.iter().map(|(ip, count)| (*ip, *count))
+    }
+
+    /// Iterate over all executed conditional jumps.
+    pub fn branches(&self) -> impl Iterator<Item = (usize, CoverageBranch)> + '_ {
+        self.branches.iter().map(|(ip, branch)| (*ip, *branch))
+    }
+}
+
+impl VmDiagnostics for Coverage {
+    #[inline]
+    fn function_used(&mut self, _: Hash, _: usize) -> Result<(), VmError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn instructions(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn instruction(&mut self, ip: usize) -> Result<(), VmError> {
+        match self.instructions.get_mut(&ip) {
+            Some(count) => {
+                *count = count.saturating_add(1);
+            }
+            None => {
+                self.instructions.try_insert(ip, 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn branch(&mut self, ip: usize, taken: bool) -> Result<(), VmError> {
+        let branch = self.branches.entry(ip).or_try_default()?;
+
+        if taken {
+            branch.taken = branch.taken.saturating_add(1);
+        } else {
+            branch.not_taken = branch.not_taken.saturating_add(1);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
+        vtable_of::<Self>()
+    }
+}
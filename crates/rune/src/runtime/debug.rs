@@ -28,6 +28,8 @@ pub struct DebugInfo {
     pub functions_rev: HashMap<usize, Hash>,
     /// Hash to identifier.
     pub hash_to_ident: HashMap<Hash, Box<str>>,
+    /// Named variables and the instructions they are live for.
+    pub variables: Vec<DebugVariable>,
 }
 
 impl DebugInfo {
@@ -43,12 +45,64 @@ impl DebugInfo {
         Some((hash, signature))
     }
 
+    /// Get the function which contains the given instruction pointer.
+    ///
+    /// In contrast to [`DebugInfo::function_at`], this does not require `ip`
+    /// to point to the first instruction of the function.
+    pub fn function_containing(&self, ip: usize) -> Option<(Hash, &DebugSignature)> {
+        let (_, hash) = self
+            .functions_rev
+            .iter()
+            .filter(|(offset, _)| **offset <= ip)
+            .max_by_key(|(offset, _)| **offset)?;
+
+        let signature = self.functions.get(hash)?;
+        Some((*hash, signature))
+    }
+
+    /// Iterate over the variables which are live at the given instruction
+    /// pointer.
+    pub fn variables_at(&self, ip: usize) -> impl Iterator<Item = &DebugVariable> + '_ {
+        self.variables
+            .iter()
+            .filter(move |var| var.start <= ip && ip < var.end)
+    }
+
     /// Access an identifier for the given hash - if it exists.
     pub fn ident_for_hash(&self, hash: Hash) -> Option<&str> {
         Some(self.hash_to_ident.get(&hash)?)
     }
 }
 
+/// Debug information about a named variable.
+#[derive(Debug, TryClone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "musli", derive(Decode, Encode))]
+#[non_exhaustive]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: Box<str>,
+    /// The offset of the variable relative to the top of the stack frame it
+    /// belongs to.
+    pub offset: usize,
+    /// The first instruction for which the variable is live.
+    pub start: usize,
+    /// The instruction after the last one for which the variable is live.
+    pub end: usize,
+}
+
+impl DebugVariable {
+    /// Construct debug information for a new variable.
+    pub fn new(name: Box<str>, offset: usize, start: usize, end: usize) -> Self {
+        Self {
+            name,
+            offset,
+            start,
+            end,
+        }
+    }
+}
+
 /// Debug information for every instruction.
 #[derive(Debug, TryClone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
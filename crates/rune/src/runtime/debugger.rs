@@ -0,0 +1,429 @@
+//! A step debugger for the virtual machine.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::alloc::{self, hash_map, BTreeMap, HashMap, Vec};
+use crate::runtime::debug::DebugSignature;
+use crate::runtime::{Args, DebugInfo, Value, Vm, VmError, VmExecution, VmOutcome};
+use crate::sync::Arc;
+use crate::{Hash, SourceId, Sources, ToTypeHash};
+
+/// A location in a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DebugLocation {
+    /// The source the location belongs to.
+    pub source_id: SourceId,
+    /// The zero-based line of the location.
+    pub line: usize,
+    /// The zero-based column of the location.
+    pub column: usize,
+}
+
+/// How execution should be resumed by [`Debugger::resume`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DebugStep {
+    /// Run until a breakpoint is hit or execution completes.
+    Continue,
+    /// Run until a new line is reached, entering any function calls.
+    Into,
+    /// Run until a new line is reached in the current function or any of its
+    /// callers.
+    Over,
+    /// Run until the current function returns.
+    Out,
+}
+
+/// The reason why [`Debugger::resume`] stopped.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DebugStop {
+    /// A breakpoint was hit.
+    Breakpoint,
+    /// A step requested through [`DebugStep`] was completed.
+    Step,
+    /// Execution was paused through a [`DebugPause`] handle.
+    Paused,
+    /// Execution yielded the given value.
+    Yielded(Value),
+    /// Execution completed with the given value.
+    Complete(Value),
+}
+
+/// A frame in the call stack of a paused execution.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct DebugFrame {
+    /// The instruction pointer of the frame.
+    ///
+    /// For the innermost frame this is the next instruction to execute, for
+    /// any other frame it is the instruction which will be executed once the
+    /// frame is returned to.
+    pub ip: usize,
+    /// The offset on the stack where the values of the frame start.
+    pub top: usize,
+    /// If this is the innermost frame.
+    pub innermost: bool,
+}
+
+/// A handle which can be used to pause a running [`Debugger`], including from
+/// another thread.
+///
+/// Pausing takes effect before the next instruction is executed, at which
+/// point [`Debugger::resume`] returns [`DebugStop::Paused`].
+#[derive(Debug, Clone)]
+pub struct DebugPause {
+    requested: Arc<AtomicBool>,
+}
+
+impl DebugPause {
+    /// Construct a new pause handle.
+    pub fn new() -> alloc::Result<Self> {
+        Ok(Self {
+            requested: Arc::try_new(AtomicBool::new(false))?,
+        })
+    }
+
+    /// Request that execution is paused.
+    pub fn pause(&self) {
+        self.requested.store(true, Ordering::Release);
+    }
+
+    /// Clear a pause request which hasn't taken effect yet.
+    pub fn clear(&self) {
+        self.requested.store(false, Ordering::Release);
+    }
+
+    /// Test if a pause has been requested and clear the request.
+    fn take(&self) -> bool {
+        self.requested.swap(false, Ordering::AcqRel)
+    }
+}
+
+/// A step debugger driving a [`VmExecution`] one instruction at a time.
+///
+/// Breakpoints and steps operate on source lines, which are resolved through
+/// the debug information of the unit being executed. The unit therefore needs
+/// to be compiled with debug information enabled for the debugger to be
+/// useful.
+///
+/// The unit should also be compiled without [`Options::optimize`], which is the
+/// default. Units don't record whether they were optimized, so this can't be
+/// checked, but optimization folds away variables and the instructions lines
+/// are associated with. Debugging an optimized unit therefore still works, but
+/// breakpoints and steps might skip lines and variables might be missing.
+///
+/// [`Options::optimize`]: crate::Options::optimize
+pub struct Debugger {
+    execution: VmExecution<Vm>,
+    sources: Sources,
+    /// Lines which have instructions associated with them, and the
+    /// instructions where execution enters them.
+    lines: HashMap<SourceId, BTreeMap<usize, Vec<usize>>>,
+    /// Instructions with breakpoints, by the source they were set in.
+    breakpoints: HashMap<SourceId, Vec<usize>>,
+    /// The location of the last instruction which was executed.
+    last: Option<DebugLocation>,
+    /// The instruction execution last stopped at, so that resuming from a
+    /// breakpoint doesn't immediately stop at it again.
+    stopped: Option<usize>,
+    /// Handle used to pause execution.
+    pause: Option<DebugPause>,
+}
+
+impl Debugger {
+    /// Construct a new debugger which will call the function `name` in the
+    /// given virtual machine with the specified arguments.
+    ///
+    /// The `sources` must be the ones the unit of the virtual machine was
+    /// compiled from, and the unit should not be optimized as described in the
+    /// [`Debugger`] documentation.
+    pub fn new(
+        mut vm: Vm,
+        name: impl ToTypeHash,
+        args: impl Args,
+        sources: Sources,
+    ) -> Result<Self, VmError> {
+        vm.set_entrypoint(name, args.count())?;
+        args.into_stack(vm.stack_mut())?;
+
+        let mut lines = HashMap::<SourceId, BTreeMap<usize, Vec<usize>>>::new();
+
+        if let Some(debug) = vm.unit().debug_info() {
+            let mut functions = Vec::try_with_capacity(debug.functions_rev.len())?;
+
+            for &offset in debug.functions_rev.keys() {
+                functions.try_push(offset)?;
+            }
+
+            functions.sort();
+
+            // The first instruction of each line in each function, which is
+            // where breakpoints on that line are placed.
+            let mut first = HashMap::<(SourceId, usize, usize), usize>::new();
+
+            for (&ip, inst) in &debug.instructions {
+                let Some(source) = sources.get(inst.source_id) else {
+                    continue;
+                };
+
+                let (line, _) = source.find_line_column(inst.span.start.into_usize());
+                let function = functions.partition_point(|&offset| offset <= ip);
+
+                match first.entry((inst.source_id, line, function)) {
+                    hash_map::Entry::Occupied(mut e) => {
+                        let existing = e.get_mut();
+                        *existing = ip.min(*existing);
+                    }
+                    hash_map::Entry::Vacant(e) => {
+                        e.try_insert(ip)?;
+                    }
+                }
+            }
+
+            for ((source_id, line, _), ip) in first {
+                lines
+                    .entry(source_id)
+                    .or_try_default()?
+                    .entry(line)
+                    .or_try_default()?
+                    .try_push(ip)?;
+            }
+        }
+
+        Ok(Self {
+            execution: VmExecution::new(vm),
+            sources,
+            lines,
+            breakpoints: HashMap::new(),
+            last: None,
+            stopped: None,
+            pause: None,
+        })
+    }
+
+    /// Allow execution to be paused through the given handle.
+    pub fn with_pause(mut self, pause: DebugPause) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
+    /// Access the sources being debugged.
+    pub fn sources(&self) -> &Sources {
+        &self.sources
+    }
+
+    /// Access the virtual machine being debugged.
+    pub fn vm(&self) -> &Vm {
+        self.execution.vm()
+    }
+
+    /// Replace the breakpoints in the given source.
+    ///
+    /// Each requested zero-based line is moved to the first line at or after it
+    /// which has code associated with it. The returned vector contains the
+    /// line each breakpoint was placed on, or `None` if it could not be placed.
+    pub fn set_breakpoints(
+        &mut self,
+        source_id: SourceId,
+        lines: &[usize],
+    ) -> alloc::Result<Vec<Option<usize>>> {
+        let mut placed = Vec::try_with_capacity(lines.len())?;
+        let mut breakpoints = Vec::new();
+
+        for &line in lines {
+            let actual = self
+                .lines
+                .get(&source_id)
+                .and_then(|lines| lines.range(line..).next());
+
+            if let Some((_, ips)) = actual {
+                breakpoints.try_extend_from_slice(ips)?;
+            }
+
+            placed.try_push(actual.map(|(line, _)| *line))?;
+        }
+
+        self.breakpoints.try_insert(source_id, breakpoints)?;
+        Ok(placed)
+    }
+
+    /// Resume execution in the given manner until it should stop.
+    pub async fn resume(&mut self, step: DebugStep) -> Result<DebugStop, VmError> {
+        let depth = self.depth();
+        let start = self.location(self.vm().ip());
+
+        loop {
+            let ip = self.vm().ip();
+
+            // Breakpoints are checked before the instruction they are placed
+            // on is executed.
+            if self.stopped != Some(ip) && self.is_breakpoint(ip) {
+                self.stopped = Some(ip);
+                return Ok(DebugStop::Breakpoint);
+            }
+
+            if self.pause.as_ref().is_some_and(DebugPause::take) {
+                self.stopped = Some(ip);
+                return Ok(DebugStop::Paused);
+            }
+
+            self.stopped = None;
+
+            match self.execution.resume().with_budget(1).await? {
+                VmOutcome::Complete(value) => return Ok(DebugStop::Complete(value)),
+                VmOutcome::Yielded(value) => return Ok(DebugStop::Yielded(value)),
+                VmOutcome::Limited => {}
+            }
+
+            let current = self.depth();
+
+            let Some(location) = self.location(self.vm().ip()) else {
+                continue;
+            };
+
+            let entered = self.last.is_none_or(|last| {
+                last.line != location.line || last.source_id != location.source_id
+            });
+            self.last = Some(location);
+
+            let moved = start.is_none_or(|start| {
+                start.line != location.line || start.source_id != location.source_id
+            }) || current != depth;
+
+            let done = match step {
+                DebugStep::Continue => false,
+                DebugStep::Into => moved && entered,
+                DebugStep::Over => moved && entered && current <= depth,
+                DebugStep::Out => current < depth,
+            };
+
+            if done {
+                self.stopped = Some(self.vm().ip());
+                return Ok(DebugStop::Step);
+            }
+        }
+    }
+
+    /// Get the frames of the call stack, starting with the innermost one.
+    pub fn frames(&self) -> alloc::Result<Vec<DebugFrame>> {
+        let vm = self.vm();
+        let mut frames = Vec::new();
+
+        frames.try_push(DebugFrame {
+            ip: vm.ip(),
+            top: vm.stack().top(),
+            innermost: true,
+        })?;
+
+        for frame in vm.call_frames().iter().rev() {
+            frames.try_push(DebugFrame {
+                ip: frame.ip,
+                top: frame.top,
+                innermost: false,
+            })?;
+        }
+
+        Ok(frames)
+    }
+
+    /// Get the source location of the given frame.
+    pub fn frame_location(&self, frame: &DebugFrame) -> Option<DebugLocation> {
+        if frame.innermost {
+            return self.location(frame.ip);
+        }
+
+        // The instruction pointer of a calling frame points to the instruction
+        // after the call, so find the closest instruction preceding it.
+        let debug = self.debug_info()?;
+
+        let (&ip, _) = debug
+            .instructions
+            .iter()
+            .filter(|(ip, _)| **ip < frame.ip)
+            .max_by_key(|(ip, _)| **ip)?;
+
+        self.location(ip)
+    }
+
+    /// Get the function the given frame is executing.
+    pub fn frame_function(&self, frame: &DebugFrame) -> Option<(Hash, &DebugSignature)> {
+        let ip = if frame.innermost {
+            frame.ip
+        } else {
+            frame.ip.saturating_sub(1)
+        };
+
+        self.debug_info()?.function_containing(ip)
+    }
+
+    /// Get the named variables which are live in the given frame.
+    ///
+    /// If a name is shadowed, only the innermost variable is returned.
+    pub fn frame_variables(&self, frame: &DebugFrame) -> alloc::Result<Vec<(&str, Value)>> {
+        let mut variables = Vec::<(&str, usize, Value)>::new();
+
+        let Some(debug) = self.debug_info() else {
+            return Ok(Vec::new());
+        };
+
+        let ip = if frame.innermost {
+            frame.ip
+        } else {
+            frame.ip.saturating_sub(1)
+        };
+
+        let stack = self.vm().stack();
+
+        for var in debug.variables_at(ip) {
+            let Some(value) = stack.get(frame.top.wrapping_add(var.offset)) else {
+                continue;
+            };
+
+            match variables.iter_mut().find(|(name, ..)| *name == &*var.name) {
+                Some(existing) if existing.1 <= var.start => {
+                    *existing = (&*var.name, var.start, value.clone());
+                }
+                Some(..) => {}
+                None => {
+                    variables.try_push((&*var.name, var.start, value.clone()))?;
+                }
+            }
+        }
+
+        let mut output = Vec::try_with_capacity(variables.len())?;
+
+        for (name, _, value) in variables {
+            output.try_push((name, value))?;
+        }
+
+        Ok(output)
+    }
+
+    /// Get the source location of the instruction at the given instruction
+    /// pointer.
+    pub fn location(&self, ip: usize) -> Option<DebugLocation> {
+        let inst = self.debug_info()?.instruction_at(ip)?;
+        let source = self.sources.get(inst.source_id)?;
+        let (line, column) = source.find_line_column(inst.span.start.into_usize());
+
+        Some(DebugLocation {
+            source_id: inst.source_id,
+            line,
+            column,
+        })
+    }
+
+    fn is_breakpoint(&self, ip: usize) -> bool {
+        self.breakpoints.values().any(|ips| ips.contains(&ip))
+    }
+
+    fn depth(&self) -> usize {
+        self.vm().call_frames().len()
+    }
+
+    fn debug_info(&self) -> Option<&DebugInfo> {
+        self.vm().unit().debug_info()
+    }
+}
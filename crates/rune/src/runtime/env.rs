@@ -86,6 +86,21 @@ where
     c(&context, &unit, diagnostics)
 }
 
+/// Get the diagnostics associated with the current environment if it is
+/// executing the given unit.
+///
+/// This allows nested executions, like closures called from native functions,
+/// to report to the same diagnostics as the execution which called them.
+pub(crate) fn diagnostics(unit: &Arc<Unit>) -> Option<NonNull<VmDiagnosticsObj>> {
+    let env = self::no_std::rune_env_get();
+
+    if env.unit?.as_ptr().cast_const() != Arc::as_ptr(unit) {
+        return None;
+    }
+
+    env.diagnostics
+}
+
 pub(crate) struct Guard {
     env: Env,
 }
@@ -0,0 +1,347 @@
+use core::any;
+use core::cmp::Ordering;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::clone::TryClone;
+use crate::{Any, TypeHash};
+
+use super::{
+    AnyObj, ConstValue, ConstValueKind, FromValue, Inline, IntoOutput, Repr, RuntimeError,
+    ToConstValue, ToValue, Value, VmErrorKind, VmIntegerRepr,
+};
+
+/// An integer which keeps its width when it is passed into Rune.
+///
+/// Rust integers which are narrower than 64 bits are widened to `i64` and
+/// `u64` when they are converted into values. Wrapping them in `Fixed` instead
+/// converts them into the corresponding sized Rune type, like `u8` or `i32`.
+///
+/// 128-bit integers are too wide to be stored inline in a value, so
+/// `Fixed<i128>` and `Fixed<u128>` are stored on the heap.
+///
+/// Converting a value into a `Fixed` accepts an integer of any width, as long
+/// as it fits in the target type.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::Fixed;
+///
+/// let value = rune::to_value(42u8)?;
+/// assert_eq!(value.type_info().to_string(), "::std::u64");
+///
+/// let value = rune::to_value(Fixed(42u8))?;
+/// assert_eq!(value.type_info().to_string(), "::std::u8");
+///
+/// let Fixed(number) = rune::from_value::<Fixed<i128>>(value)?;
+/// assert_eq!(number, 42);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed<T>(pub T);
+
+impl<T> TryClone for Fixed<T>
+where
+    T: Copy,
+{
+    #[inline]
+    fn try_clone(&self) -> alloc::Result<Self> {
+        Ok(*self)
+    }
+}
+
+/// The storage of a 128-bit signed integer.
+#[derive(Any, Clone, Copy)]
+#[rune(item = ::std, name = i128)]
+pub(crate) struct I128(pub(crate) i128);
+
+/// The storage of a 128-bit unsigned integer.
+#[derive(Any, Clone, Copy)]
+#[rune(item = ::std, name = u128)]
+pub(crate) struct U128(pub(crate) u128);
+
+/// An integer of any width.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Wide {
+    Signed(i128),
+    Unsigned(u128),
+}
+
+impl Wide {
+    /// Read an integer of any width out of a value.
+    pub(crate) fn from_value(value: &Value) -> Result<Self, RuntimeError> {
+        match Self::try_from_repr(value)? {
+            Some(wide) => Ok(wide),
+            None => Err(RuntimeError::new(VmErrorKind::ExpectedNumber {
+                actual: value.type_info(),
+            })),
+        }
+    }
+
+    /// Read an integer out of an inline value.
+    pub(crate) fn from_inline(inline: Inline) -> Option<Self> {
+        match inline {
+            Inline::Signed(value) => Some(Self::Signed(value.into())),
+            Inline::I8(value) => Some(Self::Signed(value.into())),
+            Inline::I16(value) => Some(Self::Signed(value.into())),
+            Inline::I32(value) => Some(Self::Signed(value.into())),
+            Inline::Unsigned(value) => Some(Self::Unsigned(value.into())),
+            Inline::U8(value) => Some(Self::Unsigned(value.into())),
+            Inline::U16(value) => Some(Self::Unsigned(value.into())),
+            Inline::U32(value) => Some(Self::Unsigned(value.into())),
+            _ => None,
+        }
+    }
+
+    /// Read a 128-bit integer out of an any value.
+    ///
+    /// Returns `None` if the value is of some other type.
+    pub(crate) fn try_from_any(value: &AnyObj) -> Result<Option<Self>, RuntimeError> {
+        match value.type_hash() {
+            I128::HASH => Ok(Some(Self::Signed(value.borrow_ref::<I128>()?.0))),
+            U128::HASH => Ok(Some(Self::Unsigned(value.borrow_ref::<U128>()?.0))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Read the operands of a binary operation which involves a 128-bit
+    /// integer.
+    ///
+    /// Returns `None` unless both operands are integers and at least one of
+    /// them is a 128-bit integer.
+    pub(crate) fn from_operands(
+        a: &Value,
+        b: &Value,
+    ) -> Result<Option<(Self, Self)>, RuntimeError> {
+        if !matches!(a.as_ref(), Repr::Any(..)) && !matches!(b.as_ref(), Repr::Any(..)) {
+            return Ok(None);
+        }
+
+        let (Some(a), Some(b)) = (Self::try_from_repr(a)?, Self::try_from_repr(b)?) else {
+            return Ok(None);
+        };
+
+        Ok(Some((a, b)))
+    }
+
+    fn try_from_repr(value: &Value) -> Result<Option<Self>, RuntimeError> {
+        match value.as_ref() {
+            Repr::Inline(inline) => Ok(Self::from_inline(*inline)),
+            Repr::Dynamic(..) => Ok(None),
+            Repr::Any(value) => Self::try_from_any(value),
+        }
+    }
+
+    /// Store the integer in a new value.
+    pub(crate) fn into_value(self) -> alloc::Result<Value> {
+        match self {
+            Self::Signed(value) => Value::new(I128(value)),
+            Self::Unsigned(value) => Value::new(U128(value)),
+        }
+    }
+
+    /// Convert into the given integer type.
+    pub(crate) fn into_integer<T>(self) -> Result<T, RuntimeError>
+    where
+        T: TryFrom<i128> + TryFrom<u128>,
+    {
+        let (result, from) = match self {
+            Self::Signed(value) => (T::try_from(value).ok(), VmIntegerRepr::from(value)),
+            Self::Unsigned(value) => (T::try_from(value).ok(), VmIntegerRepr::from(value)),
+        };
+
+        match result {
+            Some(value) => Ok(value),
+            None => Err(RuntimeError::new(
+                VmErrorKind::ValueToIntegerCoercionError {
+                    from,
+                    to: any::type_name::<T>(),
+                },
+            )),
+        }
+    }
+
+    /// Convert into an integer type which only supports 64-bit conversions.
+    pub(crate) fn into_narrow<T>(self) -> Result<T, RuntimeError>
+    where
+        T: TryFrom<i64> + TryFrom<u64>,
+    {
+        let (result, from) = match self {
+            Self::Signed(value) => {
+                let result = match i64::try_from(value) {
+                    Ok(value) => T::try_from(value).ok(),
+                    Err(..) => u64::try_from(value).ok().and_then(|v| T::try_from(v).ok()),
+                };
+
+                (result, VmIntegerRepr::from(value))
+            }
+            Self::Unsigned(value) => {
+                let result = u64::try_from(value).ok().and_then(|v| T::try_from(v).ok());
+                (result, VmIntegerRepr::from(value))
+            }
+        };
+
+        match result {
+            Some(value) => Ok(value),
+            None => Err(RuntimeError::new(
+                VmErrorKind::ValueToIntegerCoercionError {
+                    from,
+                    to: any::type_name::<T>(),
+                },
+            )),
+        }
+    }
+}
+
+impl PartialEq for Wide {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Wide {}
+
+impl PartialOrd for Wide {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Wide {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (*self, *other) {
+            (Self::Signed(a), Self::Signed(b)) => a.cmp(&b),
+            (Self::Unsigned(a), Self::Unsigned(b)) => a.cmp(&b),
+            (Self::Signed(a), Self::Unsigned(b)) => match u128::try_from(a) {
+                Ok(a) => a.cmp(&b),
+                Err(..) => Ordering::Less,
+            },
+            (Self::Unsigned(a), Self::Signed(b)) => match u128::try_from(b) {
+                Ok(b) => a.cmp(&b),
+                Err(..) => Ordering::Greater,
+            },
+        }
+    }
+}
+
+/// Coerce a 128-bit integer stored in an any value into a narrower integer.
+pub(crate) fn any_as_integer<T>(value: &AnyObj) -> Result<T, RuntimeError>
+where
+    T: TryFrom<i64> + TryFrom<u64>,
+{
+    match Wide::try_from_any(value)? {
+        Some(wide) => wide.into_narrow(),
+        None => Err(RuntimeError::new(VmErrorKind::ExpectedNumber {
+            actual: value.type_info(),
+        })),
+    }
+}
+
+macro_rules! fixed_inline {
+    ($($variant:ident => $ty:ty),* $(,)?) => {
+        $(
+            impl From<Fixed<$ty>> for Value {
+                #[inline]
+                fn from(Fixed(value): Fixed<$ty>) -> Self {
+                    Value::from(Inline::$variant(value))
+                }
+            }
+
+            impl From<Fixed<$ty>> for ConstValue {
+                #[inline]
+                fn from(Fixed(value): Fixed<$ty>) -> Self {
+                    ConstValue::from(Inline::$variant(value))
+                }
+            }
+
+            impl super::into_output::sealed::Sealed for Fixed<$ty> {}
+
+            impl IntoOutput for Fixed<$ty> {
+                type Error = core::convert::Infallible;
+
+                #[inline]
+                fn into_output(self) -> Result<Value, Self::Error> {
+                    Ok(Value::from(self))
+                }
+            }
+
+            impl ToValue for Fixed<$ty> {
+                #[inline]
+                fn to_value(self) -> Result<Value, RuntimeError> {
+                    Ok(Value::from(self))
+                }
+            }
+
+            impl ToConstValue for Fixed<$ty> {
+                #[inline]
+                fn to_const_value(self) -> Result<ConstValue, RuntimeError> {
+                    Ok(ConstValue::from(self))
+                }
+            }
+
+            impl FromValue for Fixed<$ty> {
+                #[inline]
+                fn from_value(value: Value) -> Result<Self, RuntimeError> {
+                    Ok(Fixed(Wide::from_value(&value)?.into_integer()?))
+                }
+            }
+        )*
+    };
+}
+
+fixed_inline! {
+    U8 => u8,
+    U16 => u16,
+    U32 => u32,
+    Unsigned => u64,
+    I8 => i8,
+    I16 => i16,
+    I32 => i32,
+    Signed => i64,
+}
+
+macro_rules! fixed_any {
+    ($($storage:ident => $ty:ty),* $(,)?) => {
+        $(
+            impl super::into_output::sealed::Sealed for Fixed<$ty> {}
+
+            impl IntoOutput for Fixed<$ty> {
+                type Error = alloc::Error;
+
+                #[inline]
+                fn into_output(self) -> Result<Value, Self::Error> {
+                    Value::new($storage(self.0))
+                }
+            }
+
+            impl ToValue for Fixed<$ty> {
+                #[inline]
+                fn to_value(self) -> Result<Value, RuntimeError> {
+                    Ok(Value::new($storage(self.0))?)
+                }
+            }
+
+            impl ToConstValue for Fixed<$ty> {
+                #[inline]
+                fn to_const_value(self) -> Result<ConstValue, RuntimeError> {
+                    Ok(ConstValue::from(ConstValueKind::$storage(self.0)))
+                }
+            }
+
+            impl FromValue for Fixed<$ty> {
+                #[inline]
+                fn from_value(value: Value) -> Result<Self, RuntimeError> {
+                    Ok(Fixed(Wide::from_value(&value)?.into_integer()?))
+                }
+            }
+        )*
+    };
+}
+
+fixed_any! {
+    I128 => i128,
+    U128 => u128,
+}
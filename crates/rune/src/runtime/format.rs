@@ -123,6 +123,18 @@ impl FormatSpec {
         }
     }
 
+    /// Traits used when formatting the bit pattern of an integer, which is
+    /// never signed.
+    fn bits_traits(&self) -> (Alignment, char, Option<char>) {
+        if self.flags.test(Flag::SignAwareZeroPad) {
+            (Alignment::Right, '0', None)
+        } else if self.flags.test(Flag::SignPlus) {
+            (self.align, self.fill, Some('+'))
+        } else {
+            (self.align, self.fill, None)
+        }
+    }
+
     /// Format the given number.
     fn format_number(&self, buf: &mut String, n: i64) -> alloc::Result<()> {
         let mut buffer = itoa::Buffer::new();
@@ -223,6 +235,16 @@ impl FormatSpec {
                         self.format_number(f.buf_mut(), n)?;
                         self.format_fill(f, align, fill, sign)?;
                     }
+                    Inline::U8(..)
+                    | Inline::U16(..)
+                    | Inline::U32(..)
+                    | Inline::I8(..)
+                    | Inline::I16(..)
+                    | Inline::I32(..) => {
+                        let (n, align, fill, sign) = self.int_traits(value.as_integer()?);
+                        self.format_number(f.buf_mut(), n)?;
+                        self.format_fill(f, align, fill, sign)?;
+                    }
                     Inline::Float(n) => {
                         let (n, align, fill, sign) = self.float_traits(*n);
                         self.format_float(f.buf_mut(), n)?;
@@ -267,6 +289,16 @@ impl FormatSpec {
                         self.format_number(f.buf_mut(), n)?;
                         self.format_fill(f, align, fill, sign)?;
                     }
+                    Inline::U8(..)
+                    | Inline::U16(..)
+                    | Inline::U32(..)
+                    | Inline::I8(..)
+                    | Inline::I16(..)
+                    | Inline::I32(..) => {
+                        let (n, align, fill, sign) = self.int_traits(value.as_integer()?);
+                        self.format_number(f.buf_mut(), n)?;
+                        self.format_fill(f, align, fill, sign)?;
+                    }
                     Inline::Float(n) => {
                         let (n, align, fill, sign) = self.float_traits(*n);
                         self.format_float(f.buf_mut(), n)?;
@@ -303,7 +335,16 @@ impl FormatSpec {
                 write!(f.buf_mut(), "{:X}", n)?;
                 self.format_fill(f, align, fill, sign)?;
             }
-            _ => {
+            Some(value) => {
+                let Some(n) = bits(value) else {
+                    return Err(VmError::new(VmErrorKind::IllegalFormat));
+                };
+
+                let (align, fill, sign) = self.bits_traits();
+                write!(f.buf_mut(), "{:X}", n)?;
+                self.format_fill(f, align, fill, sign)?;
+            }
+            None => {
                 return Err(VmError::new(VmErrorKind::IllegalFormat));
             }
         }
@@ -318,7 +359,16 @@ impl FormatSpec {
                 write!(f.buf_mut(), "{:x}", n)?;
                 self.format_fill(f, align, fill, sign)?;
             }
-            _ => {
+            Some(value) => {
+                let Some(n) = bits(value) else {
+                    return Err(VmError::new(VmErrorKind::IllegalFormat));
+                };
+
+                let (align, fill, sign) = self.bits_traits();
+                write!(f.buf_mut(), "{:x}", n)?;
+                self.format_fill(f, align, fill, sign)?;
+            }
+            None => {
                 return Err(VmError::new(VmErrorKind::IllegalFormat));
             }
         }
@@ -333,7 +383,16 @@ impl FormatSpec {
                 write!(f.buf_mut(), "{:b}", n)?;
                 self.format_fill(f, align, fill, sign)?;
             }
-            _ => {
+            Some(value) => {
+                let Some(n) = bits(value) else {
+                    return Err(VmError::new(VmErrorKind::IllegalFormat));
+                };
+
+                let (align, fill, sign) = self.bits_traits();
+                write!(f.buf_mut(), "{:b}", n)?;
+                self.format_fill(f, align, fill, sign)?;
+            }
+            None => {
                 return Err(VmError::new(VmErrorKind::IllegalFormat));
             }
         }
@@ -610,3 +669,18 @@ impl fmt::Debug for Flags {
         Ok(())
     }
 }
+
+/// Get the bit pattern of an unsigned or sized integer, as it would be
+/// formatted in hexadecimal or binary.
+fn bits(value: &Inline) -> Option<u64> {
+    match *value {
+        Inline::Unsigned(n) => Some(n),
+        Inline::U8(n) => Some(u64::from(n)),
+        Inline::U16(n) => Some(u64::from(n)),
+        Inline::U32(n) => Some(u64::from(n)),
+        Inline::I8(n) => Some(u64::from(n as u8)),
+        Inline::I16(n) => Some(u64::from(n as u16)),
+        Inline::I32(n) => Some(u64::from(n as u32)),
+        _ => None,
+    }
+}
@@ -6,6 +6,8 @@ use core::task::{Context, Poll};
 
 use crate::alloc::alloc::Global;
 use crate::alloc::{self, Box};
+#[cfg(feature = "std")]
+use crate::runtime::sandbox;
 use crate::runtime::{ToValue, Value, VmError, VmErrorKind};
 use crate::Any;
 
@@ -25,6 +27,9 @@ struct Vtable {
 pub struct Future {
     future: Option<NonNull<()>>,
     vtable: &'static Vtable,
+    /// The sandbox of the context the future was created in.
+    #[cfg(feature = "std")]
+    sandbox: Option<sandbox::Captured>,
 }
 
 impl Future {
@@ -40,6 +45,8 @@ impl Future {
 
         Ok(Self {
             future: Some(future),
+            #[cfg(feature = "std")]
+            sandbox: sandbox::Captured::current(),
             vtable: &Vtable {
                 poll: |future, cx| unsafe {
                     match Pin::new_unchecked(&mut *future.cast::<T>()).poll(cx) {
@@ -79,6 +86,9 @@ impl future::Future for Future {
                 return Poll::Ready(Err(VmError::new(VmErrorKind::FutureCompleted)));
             };
 
+            #[cfg(feature = "std")]
+            let _guard = this.sandbox.as_ref().map(sandbox::Captured::enter);
+
             match (this.vtable.poll)(future.as_ptr(), cx) {
                 Poll::Ready(result) => {
                     this.future = None;
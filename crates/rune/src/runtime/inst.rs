@@ -12,7 +12,7 @@ use crate::alloc;
 use crate::alloc::prelude::*;
 use crate::Hash;
 
-use super::{Call, FormatSpec, Type, Value};
+use super::{Call, Fixed, FormatSpec, Type, Value, Wide};
 
 /// An instruction in the virtual machine.
 #[derive(Clone, Copy)]
@@ -702,7 +702,6 @@ pub(crate) enum Kind {
     },
     /// Push a combined format specification and value onto the stack. The value
     /// used is the last value on the stack.
-    #[cfg_attr(feature = "musli", musli(packed))]
     Format {
         /// Address of the value being formatted.
         addr: Address,
@@ -921,6 +920,24 @@ pub(crate) enum Kind {
         /// Whether the produced value from the operation should be kept or not.
         out: Output,
     },
+    /// An operation followed by a jump to the given offset if its result is
+    /// false.
+    ///
+    /// This is produced by the optimizer as a fusion of [`Kind::Op`] and
+    /// [`Kind::JumpIfNot`].
+    #[cfg_attr(feature = "musli", musli(packed))]
+    OpJumpIfNot {
+        /// The kind of operation.
+        op: InstOp,
+        /// The address of the first argument.
+        a: Address,
+        /// The address of the second argument.
+        b: Address,
+        /// Where the result of the operation is stored.
+        out: Output,
+        /// The offset to jump to if the result is false.
+        jump: usize,
+    },
     /// An arithmetic operation.
     #[cfg_attr(feature = "musli", musli(packed))]
     Arithmetic {
@@ -1050,6 +1067,70 @@ impl Kind {
         }
     }
 
+    /// Construct an instruction to push an 8-bit unsigned integer.
+    pub(crate) fn u8(v: u8, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::U8(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push a 16-bit unsigned integer.
+    pub(crate) fn u16(v: u16, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::U16(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push a 32-bit unsigned integer.
+    pub(crate) fn u32(v: u32, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::U32(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push an 8-bit signed integer.
+    pub(crate) fn i8(v: i8, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::I8(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push a 16-bit signed integer.
+    pub(crate) fn i16(v: i16, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::I16(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push a 32-bit signed integer.
+    pub(crate) fn i32(v: i32, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::I32(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push a 128-bit unsigned integer.
+    pub(crate) fn u128(v: u128, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::U128(v),
+            out,
+        }
+    }
+
+    /// Construct an instruction to push a 128-bit signed integer.
+    pub(crate) fn i128(v: i128, out: Output) -> Self {
+        Self::Store {
+            value: InstValue::I128(v),
+            out,
+        }
+    }
+
     /// Construct an instruction to push a float.
     pub(crate) fn float(v: f64, out: Output) -> Self {
         Self::Store {
@@ -1529,6 +1610,30 @@ pub(crate) enum InstValue {
     /// An integer.
     #[cfg_attr(feature = "musli", musli(packed))]
     Integer(i64),
+    /// An 8-bit unsigned integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    U8(u8),
+    /// A 16-bit unsigned integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    U16(u16),
+    /// A 32-bit unsigned integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    U32(u32),
+    /// An 8-bit signed integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    I8(i8),
+    /// A 16-bit signed integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    I16(i16),
+    /// A 32-bit signed integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    I32(i32),
+    /// A 128-bit unsigned integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    U128(u128),
+    /// A 128-bit signed integer.
+    #[cfg_attr(feature = "musli", musli(packed))]
+    I128(i128),
     /// A float.
     #[cfg_attr(feature = "musli", musli(packed))]
     Float(f64),
@@ -1548,18 +1653,26 @@ pub(crate) enum InstValue {
 
 impl InstValue {
     /// Convert into a value that can be pushed onto the stack.
-    pub(crate) fn into_value(self) -> Value {
-        match self {
+    pub(crate) fn into_value(self) -> alloc::Result<Value> {
+        Ok(match self {
             Self::Unit => Value::unit(),
             Self::Bool(v) => Value::from(v),
             Self::Char(v) => Value::from(v),
             Self::Unsigned(v) => Value::from(v),
             Self::Integer(v) => Value::from(v),
+            Self::U8(v) => Value::from(Fixed(v)),
+            Self::U16(v) => Value::from(Fixed(v)),
+            Self::U32(v) => Value::from(Fixed(v)),
+            Self::I8(v) => Value::from(Fixed(v)),
+            Self::I16(v) => Value::from(Fixed(v)),
+            Self::I32(v) => Value::from(Fixed(v)),
+            Self::U128(v) => Wide::Unsigned(v).into_value()?,
+            Self::I128(v) => Wide::Signed(v).into_value()?,
             Self::Float(v) => Value::from(v),
             Self::Type(v) => Value::from(v),
             Self::Ordering(v) => Value::from(v),
             Self::Hash(v) => Value::from(v),
-        }
+        })
     }
 }
 
@@ -1571,6 +1684,14 @@ impl fmt::Display for InstValue {
             Self::Char(v) => write!(f, "{v:?}")?,
             Self::Unsigned(v) => write!(f, "{v}u64")?,
             Self::Integer(v) => write!(f, "{v}i64")?,
+            Self::U8(v) => write!(f, "{v}u8")?,
+            Self::U16(v) => write!(f, "{v}u16")?,
+            Self::U32(v) => write!(f, "{v}u32")?,
+            Self::I8(v) => write!(f, "{v}i8")?,
+            Self::I16(v) => write!(f, "{v}i16")?,
+            Self::I32(v) => write!(f, "{v}i32")?,
+            Self::U128(v) => write!(f, "{v}u128")?,
+            Self::I128(v) => write!(f, "{v}i128")?,
             Self::Float(v) => write!(f, "{v}")?,
             Self::Type(v) => write!(f, "{}", v.into_hash())?,
             Self::Ordering(v) => write!(f, "{v:?}")?,
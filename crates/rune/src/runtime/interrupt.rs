@@ -0,0 +1,411 @@
+//! Interrupting executions which take too long.
+//!
+//! A [`VmExecution`] can be given a deadline through
+//! [`VmExecution::with_deadline`] or be associated with a
+//! [`CancellationToken`] through [`VmExecution::with_cancellation`]. These are
+//! checked periodically between instructions, including in nested executions
+//! started by native functions, and whenever the execution is polled. An
+//! interrupted execution fails with an error for which
+//! [`VmError::is_deadline_exceeded`] or [`VmError::is_cancelled`] returns
+//! `true`.
+//!
+//! Native functions which hand work off to other tasks or threads can carry
+//! the interrupt of the execution which called them along through [`current`]
+//! and [`Interrupt::scope`].
+//!
+//! Unlike [instruction budgeting], interruption is based on wall-clock time or
+//! on a signal from another thread, regardless of how many instructions have
+//! been executed.
+//!
+//! [`VmExecution`]: crate::runtime::VmExecution
+//! [`VmExecution::with_deadline`]: crate::runtime::VmExecution::with_deadline
+//! [`VmExecution::with_cancellation`]: crate::runtime::VmExecution::with_cancellation
+//! [`VmError::is_deadline_exceeded`]: crate::runtime::VmError::is_deadline_exceeded
+//! [`VmError::is_cancelled`]: crate::runtime::VmError::is_cancelled
+//! [instruction budgeting]: crate::runtime::budget
+
+use core::cell::RefCell;
+use core::future::Future;
+use core::iter;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use pin_project::pin_project;
+use rust_alloc::vec::Vec;
+
+use crate::runtime::{VmError, VmErrorKind};
+
+std::thread_local!(static CURRENT: RefCell<Option<Interrupt>> = const { RefCell::new(None) });
+
+/// The number of instructions executed between each check for whether the
+/// current execution has been interrupted.
+pub(crate) const INTERVAL: usize = 1024;
+
+/// A token which can be used to cancel executions, including from another
+/// thread.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::interrupt::CancellationToken;
+/// use rune::sync::Arc;
+/// use rune::Vm;
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         pub fn main() {
+///             loop {}
+///         }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+/// let mut vm = Vm::without_runtime(Arc::try_new(unit)?)?;
+///
+/// let token = CancellationToken::new();
+/// let mut execution = vm.execute(["main"], ())?.with_cancellation(token.clone());
+///
+/// let handle = std::thread::spawn(move || {
+///     std::thread::sleep(std::time::Duration::from_millis(10));
+///     token.cancel();
+/// });
+///
+/// let error = execution.complete().unwrap_err();
+/// assert!(error.is_cancelled());
+/// handle.join().unwrap();
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CancellationToken {
+    inner: Arc<Shared>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    cancelled: AtomicBool,
+    wakers: Mutex<Wakers>,
+}
+
+/// Wakers registered with a token, each identified by the id of its
+/// [`Registration`].
+#[derive(Debug, Default)]
+struct Wakers {
+    next: usize,
+    entries: Vec<(usize, Waker)>,
+}
+
+impl CancellationToken {
+    /// Construct a new token which has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel any execution associated with this token.
+    ///
+    /// Executions which are suspended waiting for a future are woken up so
+    /// that they can observe the cancellation.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+
+        let entries = match self.inner.wakers.lock() {
+            Ok(mut wakers) => core::mem::take(&mut wakers.entries),
+            Err(..) => return,
+        };
+
+        for (_, waker) in entries {
+            waker.wake();
+        }
+    }
+
+    /// Test if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// The number of wakers registered with the token.
+    #[cfg(test)]
+    pub(crate) fn wakers(&self) -> usize {
+        self.inner
+            .wakers
+            .lock()
+            .map_or(0, |wakers| wakers.entries.len())
+    }
+
+    /// Register a waker to be woken up when the token is cancelled, replacing
+    /// the waker previously registered under `id` if there is one.
+    ///
+    /// Returns the id the waker is registered under.
+    fn register(&self, id: Option<usize>, waker: &Waker) -> Option<usize> {
+        let Ok(mut wakers) = self.inner.wakers.lock() else {
+            return None;
+        };
+
+        let wakers = &mut *wakers;
+
+        if let Some(id) = id {
+            if let Some((_, existing)) = wakers.entries.iter_mut().find(|(n, _)| *n == id) {
+                if !existing.will_wake(waker) {
+                    existing.clone_from(waker);
+                }
+
+                return Some(id);
+            }
+        }
+
+        let id = wakers.next;
+        wakers.next = wakers.next.wrapping_add(1);
+        wakers.entries.push((id, waker.clone()));
+        Some(id)
+    }
+
+    /// Remove the waker registered under `id`.
+    fn deregister(&self, id: usize) {
+        let Ok(mut wakers) = self.inner.wakers.lock() else {
+            return;
+        };
+
+        if let Some(index) = wakers.entries.iter().position(|(n, _)| *n == id) {
+            wakers.entries.swap_remove(index);
+        }
+    }
+}
+
+/// Wakers registered with the cancellation tokens of an interrupt through
+/// [`poll`].
+///
+/// The wakers are deregistered when this is cleared or dropped, so that
+/// tokens which outlive the executions they are associated with don't
+/// accumulate wakers.
+#[derive(Default)]
+pub(crate) struct Registration {
+    slots: Vec<(CancellationToken, usize)>,
+}
+
+impl Registration {
+    /// Deregister the wakers, if any are registered.
+    pub(crate) fn clear(&mut self) {
+        for (token, id) in self.slots.drain(..) {
+            token.deregister(id);
+        }
+    }
+}
+
+impl Drop for Registration {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// The conditions under which an execution is interrupted.
+///
+/// See [`current`].
+#[derive(Debug, Default, Clone)]
+pub struct Interrupt {
+    cancellation: Option<CancellationToken>,
+    deadline: Option<Instant>,
+    /// The interrupt of the execution this one is nested in, which interrupts
+    /// this one as well.
+    parent: Option<Arc<Interrupt>>,
+}
+
+impl Interrupt {
+    /// Set the cancellation token.
+    pub(crate) fn set_cancellation(&mut self, token: CancellationToken) {
+        self.cancellation = Some(token);
+    }
+
+    /// Set the deadline.
+    pub(crate) fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cancellation.is_none() && self.deadline.is_none() && self.parent.is_none()
+    }
+
+    /// Iterate over this interrupt and the ones it's nested in.
+    fn chain(&self) -> impl Iterator<Item = &Interrupt> + Clone {
+        iter::successors(Some(self), |interrupt| interrupt.parent.as_deref())
+    }
+
+    /// Construct an interrupt which is interrupted by both this one and
+    /// `outer`.
+    fn nest(&self, outer: Option<Interrupt>) -> Interrupt {
+        let parent = match &self.parent {
+            Some(parent) => Some(parent.nest(outer)),
+            None => outer,
+        };
+
+        Interrupt {
+            cancellation: self.cancellation.clone(),
+            deadline: self.deadline,
+            parent: parent.map(Arc::new),
+        }
+    }
+
+    /// Wrap a future so that any execution it drives is interrupted under the
+    /// same conditions as this one.
+    ///
+    /// The wrapped future fails as soon as it is polled after being
+    /// interrupted, and is woken up if it's waiting when cancelled.
+    pub fn scope<F>(self, future: F) -> Scope<F> {
+        Scope {
+            interrupt: self,
+            registration: Registration::default(),
+            future,
+        }
+    }
+
+    /// Check if the execution should be interrupted, registering the given
+    /// waker to be woken up if any of the tokens in the chain is cancelled
+    /// later.
+    fn poll(&self, registration: &mut Registration, waker: &Waker) -> Result<(), VmError> {
+        let tokens = self
+            .chain()
+            .filter_map(|interrupt| interrupt.cancellation.as_ref());
+
+        let unchanged = registration.slots.len() == tokens.clone().count()
+            && registration
+                .slots
+                .iter()
+                .zip(tokens.clone())
+                .all(|((current, _), token)| Arc::ptr_eq(&current.inner, &token.inner));
+
+        if unchanged {
+            for (token, id) in &registration.slots {
+                token.register(Some(*id), waker);
+            }
+        } else {
+            registration.clear();
+
+            for token in tokens {
+                if let Some(id) = token.register(None, waker) {
+                    registration.slots.push((token.clone(), id));
+                }
+            }
+        }
+
+        self.check()
+    }
+
+    /// Check if the execution should be interrupted.
+    fn check(&self) -> Result<(), VmError> {
+        for interrupt in self.chain() {
+            if let Some(token) = &interrupt.cancellation {
+                if token.is_cancelled() {
+                    return Err(VmError::new(VmErrorKind::Cancelled));
+                }
+            }
+
+            if let Some(deadline) = interrupt.deadline {
+                if Instant::now() >= deadline {
+                    return Err(VmError::new(VmErrorKind::DeadlineExceeded));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make this the interrupt for the current thread until the returned guard
+    /// is dropped.
+    ///
+    /// The interrupt which is already installed is kept as the parent of this
+    /// one, so that nested executions are interrupted along with the
+    /// execution that started them even if they have a deadline or
+    /// cancellation token of their own.
+    pub(crate) fn enter(&self) -> Guard {
+        if self.is_empty() {
+            return Guard { old: None };
+        }
+
+        let old = CURRENT.with(|current| {
+            let outer = current.borrow().clone();
+            current.replace(Some(self.nest(outer)))
+        });
+
+        Guard { old: Some(old) }
+    }
+}
+
+/// Get the conditions under which the execution running on the current thread
+/// is interrupted.
+///
+/// This is intended for native functions which spawn work that outlives the
+/// call, so that it can be interrupted along with the execution which started
+/// it through [`Interrupt::scope`].
+pub fn current() -> Interrupt {
+    CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+}
+
+/// Check if the execution running on the current thread should be
+/// interrupted, registering the given waker to be woken up if it's cancelled
+/// later.
+pub(crate) fn poll(registration: &mut Registration, waker: &Waker) -> Result<(), VmError> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(interrupt) => interrupt.poll(registration, waker),
+        None => Ok(()),
+    })
+}
+
+/// Check if the execution running on the current thread should be
+/// interrupted.
+pub(crate) fn check() -> Result<(), VmError> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(interrupt) => interrupt.check(),
+        None => Ok(()),
+    })
+}
+
+/// Guard returned by [`Interrupt::enter`].
+pub(crate) struct Guard {
+    old: Option<Option<Interrupt>>,
+}
+
+impl Drop for Guard {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(old) = self.old.take() {
+            CURRENT.with(|current| current.replace(old));
+        }
+    }
+}
+
+/// Future returned by [`Interrupt::scope`].
+#[pin_project]
+pub struct Scope<F> {
+    interrupt: Interrupt,
+    registration: Registration,
+    #[pin]
+    future: F,
+}
+
+impl<F, T> Future for Scope<F>
+where
+    F: Future<Output = Result<T, VmError>>,
+{
+    type Output = Result<T, VmError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let _guard = this.interrupt.enter();
+
+        if let Err(error) = poll(this.registration, cx.waker()) {
+            this.registration.clear();
+            return Poll::Ready(Err(error));
+        }
+
+        let result = this.future.poll(cx);
+
+        if result.is_ready() {
+            this.registration.clear();
+        }
+
+        result
+    }
+}
@@ -61,8 +61,9 @@ macro_rules! range_iter {
             $ty: $crate::compile::Named,
             $ty: $crate::runtime::FromValue + $crate::runtime::ToValue,
             $ty: $crate::runtime::MaybeTypeOf + $crate::runtime::TypeOf,
+            $ty: $crate::runtime::Step,
         {
-            iter: core::ops::$range<$ty>,
+            iter: core::ops::$range<$ty::Inner>,
         }
 
         impl<$ty> $name<$ty>
@@ -71,17 +72,18 @@ macro_rules! range_iter {
             $ty: $crate::compile::Named,
             $ty: $crate::runtime::FromValue + $crate::runtime::ToValue,
             $ty: $crate::runtime::MaybeTypeOf + $crate::runtime::TypeOf,
-            core::ops::$range<$ty>: Iterator<Item = $ty>,
+            $ty: $crate::runtime::Step,
+            core::ops::$range<$ty::Inner>: Iterator<Item = $ty::Inner>,
         {
             #[inline]
-            pub(crate) fn new(iter: core::ops::$range<$ty>) -> Self {
+            pub(crate) fn new(iter: core::ops::$range<$ty::Inner>) -> Self {
                 Self { iter }
             }
 
             #[rune::function(instance, keep, protocol = NEXT)]
             #[inline]
             pub(crate) fn next(&mut self) -> Option<$ty> {
-                self.iter.next()
+                self.iter.next().map($ty::from_inner)
             }
 
             $($($item)*)*
@@ -93,13 +95,14 @@ macro_rules! range_iter {
             $ty: $crate::compile::Named,
             $ty: $crate::runtime::FromValue + $crate::runtime::ToValue,
             $ty: $crate::runtime::MaybeTypeOf + $crate::runtime::TypeOf,
-            core::ops::$range<$ty>: Iterator<Item = $ty>,
+            $ty: $crate::runtime::Step,
+            core::ops::$range<$ty::Inner>: Iterator<Item = $ty::Inner>,
         {
             type Item = $ty;
 
             #[inline]
             fn next(&mut self) -> Option<Self::Item> {
-                self.iter.next()
+                self.iter.next().map($ty::from_inner)
             }
         }
     };
@@ -115,12 +118,13 @@ macro_rules! double_ended_range_iter {
             T: $crate::compile::Named,
             T: $crate::runtime::FromValue + $crate::runtime::ToValue,
             T: $crate::runtime::MaybeTypeOf + $crate::runtime::TypeOf,
-            core::ops::$range<T>: DoubleEndedIterator<Item = T>,
+            T: $crate::runtime::Step,
+            core::ops::$range<T::Inner>: DoubleEndedIterator<Item = T::Inner>,
         {
             #[rune::function(instance, keep, protocol = NEXT_BACK)]
             #[inline]
             pub(crate) fn next_back(&mut self) -> Option<T> {
-                self.iter.next_back()
+                self.iter.next_back().map(T::from_inner)
             }
         }
 
@@ -130,11 +134,12 @@ macro_rules! double_ended_range_iter {
             T: $crate::compile::Named,
             T: $crate::runtime::FromValue + $crate::runtime::ToValue,
             T: $crate::runtime::MaybeTypeOf + $crate::runtime::TypeOf,
-            core::ops::$range<T>: DoubleEndedIterator<Item = T>,
+            T: $crate::runtime::Step,
+            core::ops::$range<T::Inner>: DoubleEndedIterator<Item = T::Inner>,
         {
             #[inline]
             fn next_back(&mut self) -> Option<Self::Item> {
-                self.iter.next_back()
+                self.iter.next_back().map(T::from_inner)
             }
         }
     };
@@ -3,7 +3,6 @@ use core::convert::Infallible;
 use core::fmt;
 use core::mem::replace;
 use core::slice;
-#[cfg(feature = "cli")]
 use core::slice::SliceIndex;
 
 use crate::alloc::alloc::Global;
@@ -477,7 +476,6 @@ impl Stack {
     /// index is within range.
     ///
     /// [top]: Self::top()
-    #[cfg(feature = "cli")]
     #[inline]
     pub(crate) fn get<I>(&self, index: I) -> Option<&<I as SliceIndex<[Value]>>::Output>
     where
@@ -7,7 +7,7 @@ mod tests;
 mod macros;
 
 mod steps_between;
-use self::steps_between::StepsBetween;
+use self::steps_between::{Step, StepsBetween};
 
 mod dynamic;
 pub use self::dynamic::{DynamicEmpty, DynamicStruct, DynamicTuple};
@@ -48,6 +48,9 @@ pub use self::bytes::{bytes_slice_index_get, Bytes};
 mod call;
 pub use self::call::Call;
 
+mod coverage;
+pub use self::coverage::{Coverage, CoverageBranch};
+
 mod const_value;
 #[doc(hidden)]
 pub use self::const_value::ToConstValue;
@@ -60,13 +63,20 @@ pub(crate) use self::const_value::{
 };
 
 pub mod debug;
-pub use self::debug::{DebugInfo, DebugInst};
+pub use self::debug::{DebugInfo, DebugInst, DebugVariable};
+
+mod debugger;
+pub use self::debugger::{DebugFrame, DebugLocation, DebugPause, DebugStep, DebugStop, Debugger};
 
 mod env;
 
 pub mod format;
 pub use self::format::{Format, FormatSpec};
 
+mod fixed;
+pub use self::fixed::Fixed;
+pub(crate) use self::fixed::{any_as_integer, Wide, I128, U128};
+
 mod from_value;
 pub use self::from_value::{from_value, FromValue, UnsafeToMut, UnsafeToRef};
 
@@ -96,6 +106,9 @@ pub(crate) use self::inst::{
     PanicReason,
 };
 
+#[cfg(feature = "std")]
+pub mod interrupt;
+
 mod iterator;
 pub use self::iterator::Iterator;
 
@@ -139,6 +152,9 @@ pub use self::range::Range;
 mod runtime_context;
 pub use self::runtime_context::RuntimeContext;
 
+#[cfg(feature = "std")]
+pub mod sandbox;
+
 mod select;
 pub(crate) use self::select::Select;
 
@@ -7,7 +7,7 @@ use crate::alloc::clone::TryClone;
 use crate::Any;
 
 use super::{
-    EnvProtocolCaller, FromValue, Inline, ProtocolCaller, Repr, RuntimeError, StepsBetween,
+    EnvProtocolCaller, Fixed, FromValue, Inline, ProtocolCaller, Repr, RuntimeError, StepsBetween,
     ToValue, Value, VmError, VmErrorKind,
 };
 
@@ -96,14 +96,38 @@ impl Range {
         let value = match (self.start.as_ref(), self.end.as_ref()) {
             (Repr::Inline(Inline::Unsigned(start)), Repr::Inline(end)) => {
                 let end = end.as_integer::<u64>()?;
-                rune::to_value(RangeIter::new(*start..end))?
+                rune::to_value(RangeIter::<u64>::new(*start..end))?
             }
             (Repr::Inline(Inline::Signed(start)), Repr::Inline(end)) => {
                 let end = end.as_integer::<i64>()?;
-                rune::to_value(RangeIter::new(*start..end))?
+                rune::to_value(RangeIter::<i64>::new(*start..end))?
+            }
+            (Repr::Inline(Inline::U8(start)), Repr::Inline(end)) => {
+                let end = end.as_integer::<u8>()?;
+                rune::to_value(RangeIter::<Fixed<u8>>::new(*start..end))?
+            }
+            (Repr::Inline(Inline::U16(start)), Repr::Inline(end)) => {
+                let end = end.as_integer::<u16>()?;
+                rune::to_value(RangeIter::<Fixed<u16>>::new(*start..end))?
+            }
+            (Repr::Inline(Inline::U32(start)), Repr::Inline(end)) => {
+                let end = end.as_integer::<u32>()?;
+                rune::to_value(RangeIter::<Fixed<u32>>::new(*start..end))?
+            }
+            (Repr::Inline(Inline::I8(start)), Repr::Inline(end)) => {
+                let end = end.as_integer::<i8>()?;
+                rune::to_value(RangeIter::<Fixed<i8>>::new(*start..end))?
+            }
+            (Repr::Inline(Inline::I16(start)), Repr::Inline(end)) => {
+                let end = end.as_integer::<i16>()?;
+                rune::to_value(RangeIter::<Fixed<i16>>::new(*start..end))?
+            }
+            (Repr::Inline(Inline::I32(start)), Repr::Inline(end)) => {
+                let end = end.as_integer::<i32>()?;
+                rune::to_value(RangeIter::<Fixed<i32>>::new(*start..end))?
             }
             (Repr::Inline(Inline::Char(start)), Repr::Inline(Inline::Char(end))) => {
-                rune::to_value(RangeIter::new(*start..*end))?
+                rune::to_value(RangeIter::<char>::new(*start..*end))?
             }
             (start, end) => {
                 return Err(VmError::from(VmErrorKind::UnsupportedIterRange {
@@ -341,9 +365,9 @@ double_ended_range_iter!(Range, RangeIter<T>, {
     #[inline]
     pub(crate) fn len(&self) -> Result<usize, VmError>
     where
-        T: Copy + StepsBetween + fmt::Debug,
+        T::Inner: Copy + StepsBetween + fmt::Debug,
     {
-        let Some(result) = T::steps_between(self.iter.start, self.iter.end) else {
+        let Some(result) = T::Inner::steps_between(self.iter.start, self.iter.end) else {
             return Err(VmError::panic(format!(
                 "could not calculate length of range {:?}..={:?}",
                 self.iter.start, self.iter.end
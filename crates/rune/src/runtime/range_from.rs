@@ -7,7 +7,7 @@ use crate::alloc::clone::TryClone;
 use crate::Any;
 
 use super::{
-    EnvProtocolCaller, FromValue, Inline, ProtocolCaller, Repr, RuntimeError, ToValue, Value,
+    EnvProtocolCaller, Fixed, FromValue, Inline, ProtocolCaller, Repr, RuntimeError, ToValue, Value,
     VmError, VmErrorKind,
 };
 
@@ -90,9 +90,15 @@ impl RangeFrom {
     #[rune::function(keep)]
     pub fn iter(&self) -> Result<Value, VmError> {
         let value = match self.start.as_ref() {
-            Repr::Inline(Inline::Unsigned(start)) => crate::to_value(RangeFromIter::new(*start..))?,
-            Repr::Inline(Inline::Signed(start)) => crate::to_value(RangeFromIter::new(*start..))?,
-            Repr::Inline(Inline::Char(start)) => crate::to_value(RangeFromIter::new(*start..))?,
+            Repr::Inline(Inline::Unsigned(start)) => crate::to_value(RangeFromIter::<u64>::new(*start..))?,
+            Repr::Inline(Inline::Signed(start)) => crate::to_value(RangeFromIter::<i64>::new(*start..))?,
+            Repr::Inline(Inline::U8(start)) => crate::to_value(RangeFromIter::<Fixed<u8>>::new(*start..))?,
+            Repr::Inline(Inline::U16(start)) => crate::to_value(RangeFromIter::<Fixed<u16>>::new(*start..))?,
+            Repr::Inline(Inline::U32(start)) => crate::to_value(RangeFromIter::<Fixed<u32>>::new(*start..))?,
+            Repr::Inline(Inline::I8(start)) => crate::to_value(RangeFromIter::<Fixed<i8>>::new(*start..))?,
+            Repr::Inline(Inline::I16(start)) => crate::to_value(RangeFromIter::<Fixed<i16>>::new(*start..))?,
+            Repr::Inline(Inline::I32(start)) => crate::to_value(RangeFromIter::<Fixed<i32>>::new(*start..))?,
+            Repr::Inline(Inline::Char(start)) => crate::to_value(RangeFromIter::<char>::new(*start..))?,
             start => {
                 return Err(VmError::new(VmErrorKind::UnsupportedIterRangeFrom {
                     start: start.type_info(),
@@ -6,6 +6,8 @@ use crate::hash;
 use crate::runtime::{ConstConstructImpl, ConstValue};
 use crate::Hash;
 
+#[cfg(feature = "std")]
+use super::sandbox::Sandbox;
 use super::FunctionHandler;
 
 /// Static run context visible to the virtual machine.
@@ -18,10 +20,16 @@ use super::FunctionHandler;
 pub struct RuntimeContext {
     /// Registered native function handlers.
     functions: hash::Map<FunctionHandler>,
+    /// The names of all registered instance functions.
+    instance_functions: hash::Set,
     /// Named constant values
     constants: hash::Map<ConstValue>,
     /// Constant constructors.
     construct: hash::Map<ConstConstructImpl>,
+    /// The sandbox policy consulted by native modules.
+    #[cfg(feature = "std")]
+    #[try_clone(with = Clone::clone)]
+    sandbox: Option<std::sync::Arc<Sandbox>>,
 }
 
 assert_impl!(RuntimeContext: Send + Sync);
@@ -29,13 +37,18 @@ assert_impl!(RuntimeContext: Send + Sync);
 impl RuntimeContext {
     pub(crate) fn new(
         functions: hash::Map<FunctionHandler>,
+        instance_functions: hash::Set,
         constants: hash::Map<ConstValue>,
         construct: hash::Map<ConstConstructImpl>,
+        #[cfg(feature = "std")] sandbox: Option<std::sync::Arc<Sandbox>>,
     ) -> Self {
         Self {
             functions,
+            instance_functions,
             constants,
             construct,
+            #[cfg(feature = "std")]
+            sandbox,
         }
     }
 
@@ -45,6 +58,13 @@ impl RuntimeContext {
         self.functions.get(hash)
     }
 
+    /// Test if any type in the context has an instance function with the given
+    /// name.
+    #[inline]
+    pub(crate) fn has_instance_function(&self, name: Hash) -> bool {
+        self.instance_functions.contains(&name)
+    }
+
     /// Read a constant value.
     #[inline]
     pub fn constant(&self, hash: &Hash) -> Option<&ConstValue> {
@@ -56,6 +76,21 @@ impl RuntimeContext {
     pub(crate) fn construct(&self, hash: &Hash) -> Option<&ConstConstructImpl> {
         self.construct.get(hash)
     }
+
+    /// Get the sandbox policy which native modules should consult, if one is
+    /// installed.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn sandbox(&self) -> Option<&Sandbox> {
+        self.sandbox.as_deref()
+    }
+
+    /// Get the shared sandbox policy, if one is installed.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn sandbox_arc(&self) -> Option<&std::sync::Arc<Sandbox>> {
+        self.sandbox.as_ref()
+    }
 }
 
 impl fmt::Debug for RuntimeContext {
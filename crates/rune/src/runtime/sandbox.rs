@@ -0,0 +1,542 @@
+//! Capability-based restrictions for native modules.
+//!
+//! A [`Sandbox`] describes which paths may be read or written, which programs
+//! may be executed, which hosts may be connected to and which hosts may be
+//! listened on. It is installed on a
+//! [`Context`] through [`Context::set_sandbox`] and is carried over into the
+//! [`RuntimeContext`] used by the virtual machine.
+//!
+//! Native functions which interact with the outside world are expected to
+//! consult the sandbox before acting through the [`read`], [`write()`],
+//! [`execute`], [`connect`] and [`listen`] functions in this module. If no
+//! sandbox is installed everything is permitted.
+//!
+//! Native futures are often polled after the function which produced them has
+//! returned, possibly outside of the virtual machine. They therefore capture
+//! the sandbox of the context they were created in, which is consulted while
+//! they are being polled.
+//!
+//! Denials are reported as a [`SandboxError`], which is visible to scripts as
+//! `::std::sandbox::SandboxError`.
+//!
+//! [`Context`]: crate::Context
+//! [`Context::set_sandbox`]: crate::Context::set_sandbox
+//! [`RuntimeContext`]: crate::runtime::RuntimeContext
+
+use core::cell::RefCell;
+use core::fmt;
+
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use rust_alloc::string::ToString;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::clone::TryClone;
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::{String, Vec};
+use crate::runtime::{env, Formatter, MaybeTypeOf, RuntimeError, ToValue, Value};
+use crate::{compile::meta, Any};
+
+std::thread_local!(static CURRENT: RefCell<Option<Captured>> = const { RefCell::new(None) });
+
+/// A policy describing what native modules are allowed to access.
+///
+/// A newly constructed sandbox denies everything, and capabilities are
+/// granted one at a time.
+///
+/// # Examples
+///
+/// ```
+/// use rune::runtime::sandbox::Sandbox;
+/// use rune::Context;
+///
+/// let mut sandbox = Sandbox::new();
+/// sandbox.allow_read("/srv/data")?;
+/// sandbox.allow_write("/srv/data/out")?;
+/// sandbox.allow_execute("git")?;
+/// sandbox.allow_host("*.example.com")?;
+/// sandbox.allow_listen("127.0.0.1")?;
+///
+/// assert!(sandbox.check_read("/srv/data/input.txt").is_ok());
+/// assert!(sandbox.check_write("/srv/data/input.txt").is_err());
+/// assert!(sandbox.check_execute("git").is_ok());
+/// assert!(sandbox.check_connect("api.example.com").is_ok());
+/// assert!(sandbox.check_connect("example.org").is_err());
+/// assert!(sandbox.check_listen("127.0.0.1").is_ok());
+/// assert!(sandbox.check_listen("0.0.0.0").is_err());
+///
+/// let mut context = Context::with_default_modules()?;
+/// context.set_sandbox(sandbox);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+#[derive(Debug, Default, TryClone)]
+pub struct Sandbox {
+    /// Path prefixes which may be read from.
+    read: Vec<PathBuf>,
+    /// Path prefixes which may be written to.
+    write: Vec<PathBuf>,
+    /// Programs which may be executed.
+    execute: Vec<String>,
+    /// Hosts which may be connected to.
+    hosts: Vec<String>,
+    /// Hosts which may be listened on.
+    listen: Vec<String>,
+}
+
+impl Sandbox {
+    /// Construct a new sandbox which denies everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow reading files and directories under the given path prefix.
+    ///
+    /// Relative prefixes are resolved against the current directory at the
+    /// time they are added.
+    pub fn allow_read(&mut self, prefix: impl AsRef<Path>) -> alloc::Result<()> {
+        self.read.try_push(resolve(prefix.as_ref()))
+    }
+
+    /// Allow creating, modifying and removing files and directories under the
+    /// given path prefix.
+    ///
+    /// This does not imply being allowed to read from it, which has to be
+    /// granted separately through [`Sandbox::allow_read`].
+    pub fn allow_write(&mut self, prefix: impl AsRef<Path>) -> alloc::Result<()> {
+        self.write.try_push(resolve(prefix.as_ref()))
+    }
+
+    /// Allow executing the given program.
+    ///
+    /// The program must match exactly what the script passes when it
+    /// constructs the command, so allowing `git` does not allow
+    /// `/usr/bin/git`.
+    pub fn allow_execute(&mut self, program: impl AsRef<str>) -> alloc::Result<()> {
+        self.execute.try_push(String::try_from(program.as_ref())?)
+    }
+
+    /// Allow connecting to the given host.
+    ///
+    /// Hosts are compared without regard to case. A leading `*.` allows any
+    /// subdomain of the given domain, but not the domain itself.
+    pub fn allow_host(&mut self, host: impl AsRef<str>) -> alloc::Result<()> {
+        self.hosts.try_push(lowercase(host.as_ref())?)
+    }
+
+    /// Allow listening for connections on the given host, like `127.0.0.1`.
+    ///
+    /// Hosts are compared without regard to case, and an address which
+    /// listens on all interfaces like `0.0.0.0` has to be allowed explicitly.
+    pub fn allow_listen(&mut self, host: impl AsRef<str>) -> alloc::Result<()> {
+        self.listen.try_push(lowercase(host.as_ref())?)
+    }
+
+    /// Check if the given path may be read.
+    pub fn check_read(&self, path: impl AsRef<Path>) -> Result<(), SandboxError> {
+        check_path(&self.read, Capability::Read, path.as_ref())
+    }
+
+    /// Check if the given path may be written.
+    pub fn check_write(&self, path: impl AsRef<Path>) -> Result<(), SandboxError> {
+        check_path(&self.write, Capability::Write, path.as_ref())
+    }
+
+    /// Check if the given program may be executed.
+    pub fn check_execute(&self, program: impl AsRef<str>) -> Result<(), SandboxError> {
+        let program = program.as_ref();
+
+        if self
+            .execute
+            .iter()
+            .any(|allowed| allowed.as_str() == program)
+        {
+            return Ok(());
+        }
+
+        Err(SandboxError::new(Capability::Execute, program))
+    }
+
+    /// Check if the given host may be connected to.
+    pub fn check_connect(&self, host: impl AsRef<str>) -> Result<(), SandboxError> {
+        let host = host.as_ref();
+
+        let allowed = self
+            .hosts
+            .iter()
+            .any(|allowed| match allowed.strip_prefix("*.") {
+                Some(domain) => {
+                    let split = host.len().saturating_sub(domain.len());
+
+                    match (host.get(..split), host.get(split..)) {
+                        (Some(sub), Some(suffix)) => {
+                            sub.len() > 1
+                                && sub.ends_with('.')
+                                && suffix.eq_ignore_ascii_case(domain)
+                        }
+                        _ => false,
+                    }
+                }
+                None => allowed.eq_ignore_ascii_case(host),
+            });
+
+        if allowed {
+            return Ok(());
+        }
+
+        Err(SandboxError::new(Capability::Connect, host))
+    }
+
+    /// Check if the given host may be listened on.
+    pub fn check_listen(&self, host: impl AsRef<str>) -> Result<(), SandboxError> {
+        let host = host.as_ref();
+
+        if self
+            .listen
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(host))
+        {
+            return Ok(());
+        }
+
+        Err(SandboxError::new(Capability::Listen, host))
+    }
+}
+
+/// Check if the sandbox of the executing context permits reading the given
+/// path.
+///
+/// Everything is permitted if no sandbox is installed.
+pub fn read(path: impl AsRef<Path>) -> Result<(), SandboxError> {
+    let path = path.as_ref();
+    with(|sandbox| sandbox.check_read(path))
+}
+
+/// Check if the sandbox of the executing context permits writing the given
+/// path.
+///
+/// Everything is permitted if no sandbox is installed.
+pub fn write(path: impl AsRef<Path>) -> Result<(), SandboxError> {
+    let path = path.as_ref();
+    with(|sandbox| sandbox.check_write(path))
+}
+
+/// Check if the sandbox of the executing context permits executing the given
+/// program.
+///
+/// Everything is permitted if no sandbox is installed.
+pub fn execute(program: impl AsRef<str>) -> Result<(), SandboxError> {
+    let program = program.as_ref();
+    with(|sandbox| sandbox.check_execute(program))
+}
+
+/// Check if the sandbox of the executing context permits connecting to the
+/// given host.
+///
+/// Everything is permitted if no sandbox is installed.
+pub fn connect(host: impl AsRef<str>) -> Result<(), SandboxError> {
+    let host = host.as_ref();
+    with(|sandbox| sandbox.check_connect(host))
+}
+
+/// Check if the sandbox of the executing context permits listening on the
+/// given host.
+///
+/// Everything is permitted if no sandbox is installed.
+pub fn listen(host: impl AsRef<str>) -> Result<(), SandboxError> {
+    let host = host.as_ref();
+    with(|sandbox| sandbox.check_listen(host))
+}
+
+fn with(check: impl FnOnce(&Sandbox) -> Result<(), SandboxError>) -> Result<(), SandboxError> {
+    let sandbox = match env::shared(|context, _| Ok(context.sandbox_arc().cloned())) {
+        Ok(sandbox) => sandbox,
+        Err(..) => CURRENT.with(|current| current.borrow().as_ref()?.sandbox.clone()),
+    };
+
+    match sandbox {
+        Some(sandbox) => check(&sandbox),
+        None => Ok(()),
+    }
+}
+
+/// The sandbox of the context a native future was created in.
+#[derive(Clone)]
+pub(crate) struct Captured {
+    sandbox: Option<Arc<Sandbox>>,
+}
+
+impl Captured {
+    /// Capture the sandbox of the executing context.
+    ///
+    /// Returns `None` if this is called outside of the virtual machine.
+    pub(crate) fn current() -> Option<Self> {
+        env::shared(|context, _| {
+            Ok(Self {
+                sandbox: context.sandbox_arc().cloned(),
+            })
+        })
+        .ok()
+    }
+
+    /// Make the captured sandbox apply to the current thread until the
+    /// returned guard is dropped.
+    ///
+    /// The sandbox of the virtual machine takes precedence if one is running.
+    pub(crate) fn enter(&self) -> Guard {
+        let old = CURRENT.with(|current| current.replace(Some(self.clone())));
+        Guard { old }
+    }
+}
+
+/// Guard returned by [`Captured::enter`].
+pub(crate) struct Guard {
+    old: Option<Captured>,
+}
+
+impl Drop for Guard {
+    #[inline]
+    fn drop(&mut self) {
+        let old = self.old.take();
+        CURRENT.with(|current| current.replace(old));
+    }
+}
+
+/// A capability which can be granted by a [`Sandbox`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Capability {
+    /// Reading from the filesystem.
+    Read,
+    /// Writing to the filesystem.
+    Write,
+    /// Executing a program.
+    Execute,
+    /// Connecting to a host over the network.
+    Connect,
+    /// Listening for connections on a host.
+    Listen,
+}
+
+impl Capability {
+    /// The name of the capability.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::Read => "read",
+            Capability::Write => "write",
+            Capability::Execute => "execute",
+            Capability::Connect => "connect",
+            Capability::Listen => "listen",
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// An operation was denied by the [`Sandbox`].
+#[derive(Debug, Clone, Any)]
+#[rune(item = ::std::sandbox)]
+pub struct SandboxError {
+    capability: Capability,
+    target: rust_alloc::string::String,
+}
+
+impl SandboxError {
+    /// Construct a new sandbox error.
+    pub fn new(capability: Capability, target: impl fmt::Display) -> Self {
+        Self {
+            capability,
+            target: target.to_string(),
+        }
+    }
+
+    /// The capability which was missing.
+    pub fn capability(&self) -> Capability {
+        self.capability
+    }
+
+    /// The path, program or host which access was denied to.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// The name of the capability which was missing, one of `"read"`,
+    /// `"write"`, `"execute"`, `"connect"` or `"listen"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let error = fs::read_to_string("/etc/passwd").await.unwrap_err();
+    /// assert_eq!(error.capability(), "read");
+    /// ```
+    #[rune::function(keep, instance, path = Self::capability)]
+    pub(crate) fn capability_name(&self) -> &'static str {
+        self.capability.as_str()
+    }
+
+    /// The path, program or host which access was denied to.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let error = fs::read_to_string("/etc/passwd").await.unwrap_err();
+    /// assert_eq!(error.target(), "/etc/passwd");
+    /// ```
+    #[rune::function(keep, instance, path = Self::target)]
+    pub(crate) fn target_string(&self) -> alloc::Result<alloc::String> {
+        alloc::String::try_from(self.target.as_str())
+    }
+
+    #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
+    pub(crate) fn display_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{self}")
+    }
+
+    #[rune::function(keep, instance, protocol = DEBUG_FMT)]
+    pub(crate) fn debug_fmt(&self, f: &mut Formatter) -> alloc::Result<()> {
+        write!(f, "{self:?}")
+    }
+}
+
+impl fmt::Display for SandboxError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Sandbox denied {} access to `{}`",
+            self.capability, self.target
+        )
+    }
+}
+
+impl core::error::Error for SandboxError {}
+
+/// The error of an operation which is subject to the [`Sandbox`].
+///
+/// When returned to a script this becomes either the [`SandboxError`] or the
+/// wrapped error, so that scripts can tell the two apart by type.
+///
+/// Functions which used to return the wrapped error directly keep doing so
+/// when an operation fails, and without a sandbox nothing changes. But once a
+/// sandbox is installed, scripts which assume that every error is of the
+/// wrapped type, like by calling a method which only it has, have to handle a
+/// [`SandboxError`] as well.
+#[derive(Debug)]
+pub enum Restricted<E> {
+    /// The operation was denied by the sandbox.
+    Denied(SandboxError),
+    /// The operation was permitted, but failed.
+    Error(E),
+}
+
+impl<E> From<SandboxError> for Restricted<E> {
+    #[inline]
+    fn from(error: SandboxError) -> Self {
+        Self::Denied(error)
+    }
+}
+
+impl From<std::io::Error> for Restricted<std::io::Error> {
+    #[inline]
+    fn from(error: std::io::Error) -> Self {
+        Self::Error(error)
+    }
+}
+
+impl<E> fmt::Display for Restricted<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Restricted::Denied(error) => error.fmt(f),
+            Restricted::Error(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E> ToValue for Restricted<E>
+where
+    E: ToValue,
+{
+    fn to_value(self) -> Result<Value, RuntimeError> {
+        match self {
+            Restricted::Denied(error) => error.to_value(),
+            Restricted::Error(error) => error.to_value(),
+        }
+    }
+}
+
+impl<E> MaybeTypeOf for Restricted<E>
+where
+    E: MaybeTypeOf,
+{
+    #[inline]
+    fn maybe_type_of() -> alloc::Result<meta::DocType> {
+        E::maybe_type_of()
+    }
+}
+
+/// Copy a host with ASCII characters converted into lowercase.
+fn lowercase(host: &str) -> alloc::Result<String> {
+    let mut host = String::try_from(host)?;
+    host.make_ascii_lowercase();
+    Ok(host)
+}
+
+fn check_path(
+    prefixes: &[PathBuf],
+    capability: Capability,
+    path: &Path,
+) -> Result<(), SandboxError> {
+    let resolved = resolve(path);
+
+    if prefixes.iter().any(|prefix| resolved.starts_with(prefix)) {
+        return Ok(());
+    }
+
+    Err(SandboxError::new(capability, path.display()))
+}
+
+/// Resolve a path into an absolute path with symbolic links in its existing
+/// ancestors followed and any `.` or `..` components removed.
+fn resolve(path: &Path) -> PathBuf {
+    let path = match std::env::current_dir() {
+        Ok(current) if path.is_relative() => current.join(path),
+        _ => path.to_path_buf(),
+    };
+
+    let mut existing = path.as_path();
+
+    let mut resolved = loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            break canonical;
+        }
+
+        match existing.parent() {
+            Some(parent) => existing = parent,
+            None => {
+                existing = Path::new("");
+                break PathBuf::new();
+            }
+        }
+    };
+
+    for component in path.strip_prefix(existing).unwrap_or(&path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+
+    resolved
+}
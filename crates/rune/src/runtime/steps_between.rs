@@ -1,17 +1,63 @@
+use super::Fixed;
+
 pub(crate) trait StepsBetween {
     fn steps_between(start: Self, end: Self) -> Option<usize>;
 }
 
-impl StepsBetween for i64 {
-    #[inline]
-    fn steps_between(start: Self, end: Self) -> Option<usize> {
-        usize::try_from(end.checked_sub(start)?).ok()
-    }
+macro_rules! impl_steps_between {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl StepsBetween for $ty {
+                #[inline]
+                fn steps_between(start: Self, end: Self) -> Option<usize> {
+                    usize::try_from(end.checked_sub(start)?).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_steps_between!(i64, u64, u8, u16, u32, i8, i16, i32);
+
+/// A type which can be produced by iterating over a range of its inner
+/// primitive.
+pub(crate) trait Step {
+    /// The primitive which is stepped over.
+    type Inner;
+
+    /// Wrap a stepped primitive.
+    fn from_inner(inner: Self::Inner) -> Self;
+}
+
+macro_rules! impl_step {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Step for $ty {
+                type Inner = $ty;
+
+                #[inline]
+                fn from_inner(inner: Self::Inner) -> Self {
+                    inner
+                }
+            }
+        )*
+    };
 }
 
-impl StepsBetween for u64 {
-    #[inline]
-    fn steps_between(start: Self, end: Self) -> Option<usize> {
-        usize::try_from(end.checked_sub(start)?).ok()
-    }
+macro_rules! impl_fixed_step {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Step for Fixed<$ty> {
+                type Inner = $ty;
+
+                #[inline]
+                fn from_inner(inner: Self::Inner) -> Self {
+                    Fixed(inner)
+                }
+            }
+        )*
+    };
 }
+
+impl_step!(i64, u64, char);
+impl_fixed_step!(u8, u16, u32, i8, i16, i32);
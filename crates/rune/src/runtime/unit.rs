@@ -3,6 +3,8 @@
 //! A unit consists of a sequence of instructions, and lookaside tables for
 //! metadata like function locations.
 
+#[cfg(feature = "byte-code")]
+mod artifact;
 #[cfg(feature = "byte-code")]
 mod byte_code;
 mod storage;
@@ -29,6 +31,8 @@ use crate::Hash;
 pub use self::storage::{ArrayUnit, EncodeError, UnitEncoder, UnitStorage};
 pub(crate) use self::storage::{BadInstruction, BadJump};
 
+#[cfg(feature = "byte-code")]
+pub use self::artifact::{ArtifactError, FORMAT_VERSION, SCHEMA_VERSION};
 #[cfg(feature = "byte-code")]
 pub use self::byte_code::ByteCodeUnit;
 
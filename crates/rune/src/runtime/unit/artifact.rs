@@ -0,0 +1,259 @@
+//! Versioned on-disk representation of a compiled [`Unit`].
+//!
+//! An artifact starts with a small header which identifies the file, the
+//! version of the artifact format, the version of the unit schema and the
+//! version of Rune which produced it, followed by the unit and its debug
+//! information encoded using [`musli::storage`].
+
+use core::fmt;
+
+use musli::storage;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{self, String, Vec};
+use crate::runtime::inst::Kind;
+use crate::runtime::{Inst, RuntimeContext, Unit, UnitStorage};
+use crate::Hash;
+
+/// Magic bytes which every artifact starts with.
+const MAGIC: [u8; 8] = *b"\0RUNEC\r\n";
+
+/// The current version of the artifact format.
+///
+/// This is bumped whenever the layout of the header changes.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// The current version of the unit schema.
+///
+/// This covers everything encoded after the header, like instructions, inline
+/// values and debug information. It must be bumped whenever any of them change
+/// in a way which affects their encoding, since such changes are not reflected
+/// in the Rune version recorded in the header between releases.
+///
+/// Version 2 added the variables of functions to the debug information.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// The version of Rune which produced an artifact.
+///
+/// Instructions are not guaranteed to be encoded the same way across
+/// releases, so artifacts can only be loaded by the same version of Rune.
+const RUNE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+impl Unit {
+    /// Save the unit as a versioned artifact, which can later be loaded with
+    /// [`Unit::load`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::{Context, Unit, Vm};
+    /// use rune::sync::Arc;
+    ///
+    /// let context = Context::with_default_modules()?;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main(n) { n + 1 }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+    /// let bytes = unit.save()?;
+    ///
+    /// let runtime = Arc::try_new(context.runtime()?)?;
+    /// let unit = Unit::load(&bytes, &runtime)?;
+    ///
+    /// let mut vm = Vm::new(runtime, Arc::try_new(unit)?);
+    /// let output: i64 = rune::from_value(vm.call(["main"], (41,))?)?;
+    /// assert_eq!(output, 42);
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    pub fn save(&self) -> Result<Vec<u8>, ArtifactError> {
+        let mut out = Vec::new();
+        out.try_extend_from_slice(&MAGIC)?;
+        out.try_extend_from_slice(&FORMAT_VERSION.to_le_bytes())?;
+        out.try_extend_from_slice(&SCHEMA_VERSION.to_le_bytes())?;
+
+        out.try_extend_from_slice(&(RUNE_VERSION.len() as u16).to_le_bytes())?;
+        out.try_extend_from_slice(RUNE_VERSION.as_bytes())?;
+        storage::to_writer(&mut out, self)?;
+        Ok(out)
+    }
+
+    /// Load a unit from an artifact produced by [`Unit::save`].
+    ///
+    /// This checks that the artifact was produced by a compatible version of
+    /// Rune, and that every native function referenced by the unit is present
+    /// in the given `context`.
+    pub fn load(bytes: &[u8], context: &RuntimeContext) -> Result<Self, ArtifactError> {
+        let Some(bytes) = bytes.strip_prefix(&MAGIC[..]) else {
+            return Err(ArtifactError::new(ArtifactErrorKind::BadMagic));
+        };
+
+        let (version, bytes) = split::<4>(bytes)?;
+        let version = u32::from_le_bytes(version);
+
+        if version != FORMAT_VERSION {
+            return Err(ArtifactError::new(ArtifactErrorKind::UnsupportedFormat {
+                version,
+            }));
+        }
+
+        let (version, bytes) = split::<4>(bytes)?;
+        let version = u32::from_le_bytes(version);
+
+        if version != SCHEMA_VERSION {
+            return Err(ArtifactError::new(ArtifactErrorKind::UnsupportedSchema {
+                version,
+            }));
+        }
+
+        let (len, bytes) = split::<2>(bytes)?;
+        let len = usize::from(u16::from_le_bytes(len));
+
+        let Some((rune_version, bytes)) = bytes.split_at_checked(len) else {
+            return Err(ArtifactError::new(ArtifactErrorKind::Truncated));
+        };
+
+        if rune_version != RUNE_VERSION.as_bytes() {
+            let version = core::str::from_utf8(rune_version)
+                .unwrap_or("<invalid>")
+                .try_to_owned()?;
+
+            return Err(ArtifactError::new(ArtifactErrorKind::VersionMismatch {
+                version,
+            }));
+        }
+
+        let unit = storage::from_slice::<Unit>(bytes)?;
+        unit.validate(context)?;
+        Ok(unit)
+    }
+
+    /// Check that every function referenced by the unit can be resolved.
+    ///
+    /// Instance functions are looked up by the type of their receiver, which
+    /// is only known at runtime. So for those we check that at least one type
+    /// in the unit or the context provides a function with the given name.
+    fn validate(&self, context: &RuntimeContext) -> Result<(), ArtifactError> {
+        let missing =
+            |hash, ip| ArtifactError::new(ArtifactErrorKind::MissingFunction { hash, ip });
+
+        for (ip, inst) in self.instructions().iter() {
+            let Inst { kind } = inst;
+
+            match kind {
+                Kind::Call { hash, .. } | Kind::LoadFn { hash, .. }
+                    if self.function(&hash).is_none() && context.function(&hash).is_none() =>
+                {
+                    return Err(missing(hash, ip));
+                }
+                Kind::Closure { hash, .. } if self.function(&hash).is_none() => {
+                    return Err(missing(hash, ip));
+                }
+                Kind::CallAssociated { hash, .. } | Kind::LoadInstanceFn { hash, .. }
+                    if !self.has_instance_function(hash)
+                        && !context.has_instance_function(hash) =>
+                {
+                    return Err(missing(hash, ip));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Test if any type declared in the unit has an instance function with
+    /// the given name.
+    fn has_instance_function(&self, name: Hash) -> bool {
+        self.logic.rtti.values().any(|rtti| {
+            self.function(&Hash::associated_function(rtti.hash, name))
+                .is_some()
+        })
+    }
+}
+
+fn split<const N: usize>(bytes: &[u8]) -> Result<([u8; N], &[u8]), ArtifactError> {
+    let Some((head, tail)) = bytes.split_first_chunk::<N>() else {
+        return Err(ArtifactError::new(ArtifactErrorKind::Truncated));
+    };
+
+    Ok((*head, tail))
+}
+
+/// Error raised when saving or loading a unit artifact.
+#[derive(Debug)]
+pub struct ArtifactError {
+    kind: ArtifactErrorKind,
+}
+
+impl ArtifactError {
+    #[inline]
+    fn new(kind: ArtifactErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl From<storage::Error> for ArtifactError {
+    #[inline]
+    fn from(error: storage::Error) -> Self {
+        Self::new(ArtifactErrorKind::StorageError { error })
+    }
+}
+
+impl From<alloc::Error> for ArtifactError {
+    #[inline]
+    fn from(error: alloc::Error) -> Self {
+        Self::new(ArtifactErrorKind::AllocError { error })
+    }
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ArtifactErrorKind::BadMagic => write!(f, "Not a compiled Rune unit"),
+            ArtifactErrorKind::Truncated => write!(f, "Compiled unit is truncated"),
+            ArtifactErrorKind::UnsupportedFormat { version } => write!(
+                f,
+                "Unsupported format version {version}, expected {FORMAT_VERSION}"
+            ),
+            ArtifactErrorKind::UnsupportedSchema { version } => write!(
+                f,
+                "Unsupported schema version {version}, expected {SCHEMA_VERSION}"
+            ),
+            ArtifactErrorKind::VersionMismatch { version } => write!(
+                f,
+                "Unit was compiled by Rune {version}, but this is Rune {RUNE_VERSION}"
+            ),
+            ArtifactErrorKind::MissingFunction { hash, ip } => write!(
+                f,
+                "Missing function with hash {hash} referenced at instruction {ip}"
+            ),
+            ArtifactErrorKind::StorageError { .. } => write!(f, "Failed to decode unit"),
+            ArtifactErrorKind::AllocError { error } => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ArtifactError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.kind {
+            ArtifactErrorKind::StorageError { error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ArtifactErrorKind {
+    BadMagic,
+    Truncated,
+    UnsupportedFormat { version: u32 },
+    UnsupportedSchema { version: u32 },
+    VersionMismatch { version: String },
+    MissingFunction { hash: Hash, ip: usize },
+    StorageError { error: storage::Error },
+    AllocError { error: alloc::Error },
+}
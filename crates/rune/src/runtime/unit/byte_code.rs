@@ -1,5 +1,7 @@
 use core::mem::size_of;
 
+#[cfg(feature = "byte-code")]
+use musli::reader::SliceReader;
 #[cfg(feature = "byte-code")]
 use musli::storage;
 #[cfg(feature = "musli")]
@@ -43,7 +45,9 @@ impl Iterator for ByteCodeUnitIter<'_> {
         }
 
         let ip = self.len.checked_sub(self.address.len())?;
-        let inst = storage::decode(self.address).ok()?;
+        let mut reader = SliceReader::new(self.address);
+        let inst = storage::decode(&mut reader).ok()?;
+        self.address = reader.as_slice();
         Some((ip, inst))
     }
 }
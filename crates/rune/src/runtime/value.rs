@@ -39,7 +39,7 @@ use super::{
     ConstValueKind, DynGuardedArgs, EnvProtocolCaller, Formatter, FromValue, Future, Hasher,
     Iterator, MaybeTypeOf, Mut, Object, OwnedTuple, Protocol, ProtocolCaller, RawAnyObjGuard, Ref,
     RuntimeError, Shared, Snapshot, Tuple, Type, TypeInfo, Vec, VmError, VmErrorKind,
-    VmIntegerRepr,
+    VmIntegerRepr, Wide,
 };
 
 /// Defined guard for a reference value.
@@ -388,6 +388,30 @@ impl Value {
                         let mut buffer = itoa::Buffer::new();
                         f.try_write_str(buffer.format(*integer))?;
                     }
+                    Inline::U8(n) => {
+                        let mut buffer = itoa::Buffer::new();
+                        f.try_write_str(buffer.format(*n))?;
+                    }
+                    Inline::U16(n) => {
+                        let mut buffer = itoa::Buffer::new();
+                        f.try_write_str(buffer.format(*n))?;
+                    }
+                    Inline::U32(n) => {
+                        let mut buffer = itoa::Buffer::new();
+                        f.try_write_str(buffer.format(*n))?;
+                    }
+                    Inline::I8(n) => {
+                        let mut buffer = itoa::Buffer::new();
+                        f.try_write_str(buffer.format(*n))?;
+                    }
+                    Inline::I16(n) => {
+                        let mut buffer = itoa::Buffer::new();
+                        f.try_write_str(buffer.format(*n))?;
+                    }
+                    Inline::I32(n) => {
+                        let mut buffer = itoa::Buffer::new();
+                        f.try_write_str(buffer.format(*n))?;
+                    }
                     Inline::Float(float) => {
                         let mut buffer = ryu::Buffer::new();
                         f.try_write_str(buffer.format(*float))?;
@@ -1146,6 +1170,10 @@ impl Value {
         b: &Value,
         caller: &mut dyn ProtocolCaller,
     ) -> Result<bool, VmError> {
+        if let Some((lhs, rhs)) = Wide::from_operands(self, b)? {
+            return Ok(lhs == rhs);
+        }
+
         self.bin_op_with(
             b,
             caller,
@@ -1216,6 +1244,10 @@ impl Value {
         b: &Value,
         caller: &mut dyn ProtocolCaller,
     ) -> Result<Option<Ordering>, VmError> {
+        if let Some((lhs, rhs)) = Wide::from_operands(self, b)? {
+            return Ok(lhs.partial_cmp(&rhs));
+        }
+
         self.bin_op_with(
             b,
             caller,
@@ -1397,9 +1429,7 @@ impl Value {
             Repr::Dynamic(ref value) => Err(RuntimeError::new(VmErrorKind::ExpectedNumber {
                 actual: value.type_info(),
             })),
-            Repr::Any(ref value) => Err(RuntimeError::new(VmErrorKind::ExpectedNumber {
-                actual: value.type_info(),
-            })),
+            Repr::Any(ref value) => super::any_as_integer(value),
         }
     }
 
@@ -1504,6 +1534,13 @@ impl Value {
         Ok(FromValue::from_value(value)?)
     }
 
+    pub(crate) fn protocol_nth(&self, n: usize) -> Result<Option<Value>, VmError> {
+        let value =
+            EnvProtocolCaller.call_protocol_fn(&Protocol::NTH, self.clone(), &mut Some((n,)))?;
+
+        Ok(FromValue::from_value(value)?)
+    }
+
     pub(crate) fn protocol_nth_back(&self, n: usize) -> Result<Option<Value>, VmError> {
         let value = EnvProtocolCaller.call_protocol_fn(
             &Protocol::NTH_BACK,
@@ -10,7 +10,8 @@ use serde::{Deserialize, Serialize};
 
 use crate as rune;
 use crate::runtime::{
-    Hasher, OwnedTuple, Protocol, RuntimeError, Type, TypeInfo, VmErrorKind, VmIntegerRepr,
+    Fixed, Hasher, OwnedTuple, Protocol, RuntimeError, Type, TypeInfo, VmErrorKind, VmIntegerRepr,
+    Wide,
 };
 use crate::{Hash, TypeHash};
 
@@ -37,6 +38,18 @@ pub enum Inline {
     Signed(i64),
     /// An unsigned number.
     Unsigned(u64),
+    /// An 8-bit unsigned number.
+    U8(u8),
+    /// A 16-bit unsigned number.
+    U16(u16),
+    /// A 32-bit unsigned number.
+    U32(u32),
+    /// An 8-bit signed number.
+    I8(i8),
+    /// A 16-bit signed number.
+    I16(i16),
+    /// A 32-bit signed number.
+    I32(i32),
     /// A float.
     Float(f64),
     /// A type hash. Describes a type in the virtual machine.
@@ -75,6 +88,12 @@ impl Inline {
                     },
                 )),
             },
+            Inline::U8(value) => Inline::Unsigned(u64::from(value)).as_integer(),
+            Inline::U16(value) => Inline::Unsigned(u64::from(value)).as_integer(),
+            Inline::U32(value) => Inline::Unsigned(u64::from(value)).as_integer(),
+            Inline::I8(value) => Inline::Signed(i64::from(value)).as_integer(),
+            Inline::I16(value) => Inline::Signed(i64::from(value)).as_integer(),
+            Inline::I32(value) => Inline::Signed(i64::from(value)).as_integer(),
             ref value => Err(RuntimeError::new(VmErrorKind::ExpectedNumber {
                 actual: value.type_info(),
             })),
@@ -88,20 +107,24 @@ impl Inline {
             (Inline::Bool(a), Inline::Bool(b)) => Ok(*a == *b),
             (Inline::Char(a), Inline::Char(b)) => Ok(*a == *b),
             (Inline::Signed(a), Inline::Signed(b)) => Ok(*a == *b),
-            (Inline::Signed(a), rhs) => Ok(*a == rhs.as_integer::<i64>()?),
             (Inline::Unsigned(a), Inline::Unsigned(b)) => Ok(*a == *b),
-            (Inline::Unsigned(a), rhs) => Ok(*a == rhs.as_integer::<u64>()?),
             (Inline::Float(a), Inline::Float(b)) => Ok(*a == *b),
             (Inline::Type(a), Inline::Type(b)) => Ok(*a == *b),
             (Inline::Ordering(a), Inline::Ordering(b)) => Ok(*a == *b),
             (Inline::Hash(a), Inline::Hash(b)) => Ok(*a == *b),
-            (lhs, rhs) => Err(RuntimeError::from(
-                VmErrorKind::UnsupportedBinaryOperation {
-                    op: Protocol::PARTIAL_EQ.name,
-                    lhs: lhs.type_info(),
-                    rhs: rhs.type_info(),
-                },
-            )),
+            (lhs, rhs) => {
+                if let Some((a, b)) = widen(lhs, rhs)? {
+                    return Ok(a == b);
+                }
+
+                Err(RuntimeError::from(
+                    VmErrorKind::UnsupportedBinaryOperation {
+                        op: Protocol::PARTIAL_EQ.name,
+                        lhs: lhs.type_info(),
+                        rhs: rhs.type_info(),
+                    },
+                ))
+            }
         }
     }
 
@@ -113,6 +136,12 @@ impl Inline {
             (Inline::Char(a), Inline::Char(b)) => Ok(*a == *b),
             (Inline::Unsigned(a), Inline::Unsigned(b)) => Ok(*a == *b),
             (Inline::Signed(a), Inline::Signed(b)) => Ok(*a == *b),
+            (Inline::U8(a), Inline::U8(b)) => Ok(*a == *b),
+            (Inline::U16(a), Inline::U16(b)) => Ok(*a == *b),
+            (Inline::U32(a), Inline::U32(b)) => Ok(*a == *b),
+            (Inline::I8(a), Inline::I8(b)) => Ok(*a == *b),
+            (Inline::I16(a), Inline::I16(b)) => Ok(*a == *b),
+            (Inline::I32(a), Inline::I32(b)) => Ok(*a == *b),
             (Inline::Float(a), Inline::Float(b)) => {
                 let Some(ordering) = a.partial_cmp(b) else {
                     return Err(RuntimeError::new(VmErrorKind::IllegalFloatComparison {
@@ -141,26 +170,24 @@ impl Inline {
             (Inline::Bool(lhs), Inline::Bool(rhs)) => Ok(lhs.partial_cmp(rhs)),
             (Inline::Char(lhs), Inline::Char(rhs)) => Ok(lhs.partial_cmp(rhs)),
             (Inline::Unsigned(lhs), Inline::Unsigned(rhs)) => Ok(lhs.partial_cmp(rhs)),
-            (Inline::Unsigned(lhs), rhs) => {
-                let rhs = rhs.as_integer::<u64>()?;
-                Ok(lhs.partial_cmp(&rhs))
-            }
             (Inline::Signed(lhs), Inline::Signed(rhs)) => Ok(lhs.partial_cmp(rhs)),
-            (Inline::Signed(lhs), rhs) => {
-                let rhs = rhs.as_integer::<i64>()?;
-                Ok(lhs.partial_cmp(&rhs))
-            }
             (Inline::Float(lhs), Inline::Float(rhs)) => Ok(lhs.partial_cmp(rhs)),
             (Inline::Type(lhs), Inline::Type(rhs)) => Ok(lhs.partial_cmp(rhs)),
             (Inline::Ordering(lhs), Inline::Ordering(rhs)) => Ok(lhs.partial_cmp(rhs)),
             (Inline::Hash(lhs), Inline::Hash(rhs)) => Ok(lhs.partial_cmp(rhs)),
-            (lhs, rhs) => Err(RuntimeError::from(
-                VmErrorKind::UnsupportedBinaryOperation {
-                    op: Protocol::PARTIAL_CMP.name,
-                    lhs: lhs.type_info(),
-                    rhs: rhs.type_info(),
-                },
-            )),
+            (lhs, rhs) => {
+                if let Some((a, b)) = widen(lhs, rhs)? {
+                    return Ok(a.partial_cmp(&b));
+                }
+
+                Err(RuntimeError::from(
+                    VmErrorKind::UnsupportedBinaryOperation {
+                        op: Protocol::PARTIAL_CMP.name,
+                        lhs: lhs.type_info(),
+                        rhs: rhs.type_info(),
+                    },
+                ))
+            }
         }
     }
 
@@ -172,6 +199,12 @@ impl Inline {
             (Inline::Char(a), Inline::Char(b)) => Ok(a.cmp(b)),
             (Inline::Unsigned(a), Inline::Unsigned(b)) => Ok(a.cmp(b)),
             (Inline::Signed(a), Inline::Signed(b)) => Ok(a.cmp(b)),
+            (Inline::U8(a), Inline::U8(b)) => Ok(a.cmp(b)),
+            (Inline::U16(a), Inline::U16(b)) => Ok(a.cmp(b)),
+            (Inline::U32(a), Inline::U32(b)) => Ok(a.cmp(b)),
+            (Inline::I8(a), Inline::I8(b)) => Ok(a.cmp(b)),
+            (Inline::I16(a), Inline::I16(b)) => Ok(a.cmp(b)),
+            (Inline::I32(a), Inline::I32(b)) => Ok(a.cmp(b)),
             (Inline::Float(a), Inline::Float(b)) => {
                 let Some(ordering) = a.partial_cmp(b) else {
                     return Err(RuntimeError::new(VmErrorKind::IllegalFloatComparison {
@@ -202,6 +235,24 @@ impl Inline {
             Inline::Signed(value) => {
                 value.hash(hasher);
             }
+            Inline::U8(value) => {
+                value.hash(hasher);
+            }
+            Inline::U16(value) => {
+                value.hash(hasher);
+            }
+            Inline::U32(value) => {
+                value.hash(hasher);
+            }
+            Inline::I8(value) => {
+                value.hash(hasher);
+            }
+            Inline::I16(value) => {
+                value.hash(hasher);
+            }
+            Inline::I32(value) => {
+                value.hash(hasher);
+            }
             // Care must be taken whan hashing floats, to ensure that `hash(v1)
             // === hash(v2)` if `eq(v1) === eq(v2)`. Hopefully we accomplish
             // this by rejecting NaNs and rectifying subnormal values of zero.
@@ -237,6 +288,12 @@ impl fmt::Debug for Inline {
             Inline::Char(value) => value.fmt(f),
             Inline::Unsigned(value) => value.fmt(f),
             Inline::Signed(value) => value.fmt(f),
+            Inline::U8(value) => value.fmt(f),
+            Inline::U16(value) => value.fmt(f),
+            Inline::U32(value) => value.fmt(f),
+            Inline::I8(value) => value.fmt(f),
+            Inline::I16(value) => value.fmt(f),
+            Inline::I32(value) => value.fmt(f),
             Inline::Float(value) => value.fmt(f),
             Inline::Type(value) => value.fmt(f),
             Inline::Ordering(value) => value.fmt(f),
@@ -254,6 +311,12 @@ impl Inline {
             Inline::Char(..) => TypeInfo::named::<char>(),
             Inline::Unsigned(..) => TypeInfo::named::<u64>(),
             Inline::Signed(..) => TypeInfo::named::<i64>(),
+            Inline::U8(..) => TypeInfo::named::<Fixed<u8>>(),
+            Inline::U16(..) => TypeInfo::named::<Fixed<u16>>(),
+            Inline::U32(..) => TypeInfo::named::<Fixed<u32>>(),
+            Inline::I8(..) => TypeInfo::named::<Fixed<i8>>(),
+            Inline::I16(..) => TypeInfo::named::<Fixed<i16>>(),
+            Inline::I32(..) => TypeInfo::named::<Fixed<i32>>(),
             Inline::Float(..) => TypeInfo::named::<f64>(),
             Inline::Type(..) => TypeInfo::named::<Type>(),
             Inline::Ordering(..) => TypeInfo::named::<Ordering>(),
@@ -273,6 +336,12 @@ impl Inline {
             Inline::Char(..) => char::HASH,
             Inline::Signed(..) => i64::HASH,
             Inline::Unsigned(..) => u64::HASH,
+            Inline::U8(..) => Fixed::<u8>::HASH,
+            Inline::U16(..) => Fixed::<u16>::HASH,
+            Inline::U32(..) => Fixed::<u32>::HASH,
+            Inline::I8(..) => Fixed::<i8>::HASH,
+            Inline::I16(..) => Fixed::<i16>::HASH,
+            Inline::I32(..) => Fixed::<i32>::HASH,
             Inline::Float(..) => f64::HASH,
             Inline::Type(..) => Type::HASH,
             Inline::Ordering(..) => Ordering::HASH,
@@ -280,3 +349,20 @@ impl Inline {
         }
     }
 }
+
+/// Widen two integers of any width so that they can be compared.
+///
+/// Returns `None` if `lhs` is not an integer.
+fn widen(lhs: &Inline, rhs: &Inline) -> Result<Option<(Wide, Wide)>, RuntimeError> {
+    let Some(lhs) = Wide::from_inline(*lhs) else {
+        return Ok(None);
+    };
+
+    let Some(rhs) = Wide::from_inline(*rhs) else {
+        return Err(RuntimeError::new(VmErrorKind::ExpectedNumber {
+            actual: rhs.type_info(),
+        }));
+    };
+
+    Ok(Some((lhs, rhs)))
+}
@@ -6,7 +6,7 @@ use crate::runtime::{self, Bytes, Inline, Object, OwnedTuple, Repr, RttiKind, Ve
 use crate::TypeHash;
 
 use serde::de::{self, Deserialize as _, Error as _};
-use serde::ser::{self, Error as _, SerializeMap as _, SerializeSeq as _};
+use serde::ser::{self, Error as _, SerializeMap as _, SerializeSeq as _, SerializeTuple as _};
 
 use super::Value;
 
@@ -34,6 +34,12 @@ impl ser::Serialize for Value {
                 Inline::Char(value) => serializer.serialize_char(value),
                 Inline::Unsigned(value) => serializer.serialize_u64(value),
                 Inline::Signed(value) => serializer.serialize_i64(value),
+                Inline::U8(value) => serializer.serialize_u8(value),
+                Inline::U16(value) => serializer.serialize_u16(value),
+                Inline::U32(value) => serializer.serialize_u32(value),
+                Inline::I8(value) => serializer.serialize_i8(value),
+                Inline::I16(value) => serializer.serialize_i16(value),
+                Inline::I32(value) => serializer.serialize_i32(value),
                 Inline::Float(value) => serializer.serialize_f64(value),
                 Inline::Type(..) => Err(ser::Error::custom("cannot serialize types")),
                 Inline::Ordering(..) => Err(ser::Error::custom("cannot serialize orderings")),
@@ -80,7 +86,7 @@ impl ser::Serialize for Value {
                 }
                 OwnedTuple::HASH => {
                     let tuple = value.borrow_ref::<OwnedTuple>().map_err(S::Error::custom)?;
-                    let mut serializer = serializer.serialize_seq(Some(tuple.len()))?;
+                    let mut serializer = serializer.serialize_tuple(tuple.len())?;
 
                     for value in tuple.iter() {
                         serializer.serialize_element(value)?;
@@ -214,6 +214,46 @@ impl Vec {
         self.inner.try_insert(index, value)
     }
 
+    /// Shortens the vector, keeping the first `len` elements and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater or equal to the vector's current length, this has
+    /// no effect.
+    pub fn truncate(&mut self, len: usize) {
+        self.inner.truncate(len);
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Value) -> bool,
+    {
+        self.inner.retain(f);
+    }
+
+    /// Removes all but the first of consecutive elements in the vector
+    /// satisfying a given equality relation.
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
+    where
+        F: FnMut(&mut Value, &mut Value) -> bool,
+    {
+        self.inner.dedup_by(same_bucket);
+    }
+
+    /// Removes the specified range from the vector in bulk, returning all
+    /// removed elements as an iterator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the starting point is greater than the end point or if the
+    /// end point is greater than the length of the vector.
+    pub fn drain<R>(&mut self, range: R) -> alloc::vec::Drain<'_, Value>
+    where
+        R: ops::RangeBounds<usize>,
+    {
+        self.inner.drain(range)
+    }
+
     /// Extend this vector with something that implements the into_iter
     /// protocol.
     pub fn extend(&mut self, value: Value) -> Result<(), VmError> {
@@ -15,13 +15,14 @@ use self::ops::*;
 
 use super::{
     budget, inst, Address, AnySequence, Args, Awaited, BorrowMut, Bytes, Call, ControlFlow,
-    DynArgs, DynGuardedArgs, Format, FormatSpec, Formatter, FromValue, Function, Future, Generator,
-    GeneratorState, GuardedArgs, Inline, InstArithmeticOp, InstBitwiseOp, InstOp, InstRange,
-    InstShiftOp, InstTarget, InstValue, Object, Output, OwnedTuple, Pair, Panic, Protocol,
-    ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive, Repr,
-    RttiKind, RuntimeContext, Select, SelectFuture, Stack, Stream, Type, TypeHash, TypeInfo,
-    TypeOf, Unit, UnitFn, UnitStorage, Value, Vec, VmDiagnostics, VmDiagnosticsObj, VmError,
-    VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmOutcome, VmSendExecution,
+    DynArgs, DynGuardedArgs, Fixed, Format, FormatSpec, Formatter, FromValue, Function, Future,
+    Generator, GeneratorState, GuardedArgs, Inline, InstArithmeticOp, InstBitwiseOp, InstOp,
+    InstRange, InstShiftOp, InstTarget, InstValue, Object, Output, OwnedTuple, Pair, Panic,
+    Protocol, ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+    RangeToInclusive, Repr, RttiKind, RuntimeContext, Select, SelectFuture, Stack, Stream, Type,
+    TypeHash, TypeInfo, TypeOf, Unit, UnitFn, UnitStorage, Value, Vec, VmDiagnostics,
+    VmDiagnosticsObj, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmOutcome,
+    VmSendExecution, Wide,
 };
 
 /// Helper to take a value, replacing the old one with empty.
@@ -502,7 +503,7 @@ impl Vm {
 
     /// Update the instruction pointer to match the function matching the given
     /// name and check that the number of argument matches.
-    fn set_entrypoint<N>(&mut self, name: N, count: usize) -> Result<(), VmErrorKind>
+    pub(crate) fn set_entrypoint<N>(&mut self, name: N, count: usize) -> Result<(), VmErrorKind>
     where
         N: ToTypeHash,
     {
@@ -950,6 +951,14 @@ impl Vm {
                     f64::HASH => Value::from($value as f64),
                     u64::HASH => Value::from($value as u64),
                     i64::HASH => Value::from($value as i64),
+                    <Fixed<u8>>::HASH => Value::from(Fixed($value as u8)),
+                    <Fixed<u16>>::HASH => Value::from(Fixed($value as u16)),
+                    <Fixed<u32>>::HASH => Value::from(Fixed($value as u32)),
+                    <Fixed<u128>>::HASH => Wide::Unsigned($value as u128).into_value()?,
+                    <Fixed<i8>>::HASH => Value::from(Fixed($value as i8)),
+                    <Fixed<i16>>::HASH => Value::from(Fixed($value as i16)),
+                    <Fixed<i32>>::HASH => Value::from(Fixed($value as i32)),
+                    <Fixed<i128>>::HASH => Wide::Signed($value as i128).into_value()?,
                     ty => {
                         return Err(VmError::new(VmErrorKind::UnsupportedAs {
                             value: TypeInfo::from(<$from as TypeOf>::STATIC_TYPE_INFO),
@@ -963,7 +972,24 @@ impl Vm {
         let value = match a.as_ref() {
             Repr::Inline(Inline::Unsigned(a)) => convert!(u64, *a),
             Repr::Inline(Inline::Signed(a)) => convert!(i64, *a),
+            Repr::Inline(Inline::U8(a)) if ty.into_hash() == char::HASH => Value::from(*a as char),
+            Repr::Inline(Inline::U8(a)) => convert!(Fixed<u8>, *a),
+            Repr::Inline(Inline::U16(a)) => convert!(Fixed<u16>, *a),
+            Repr::Inline(Inline::U32(a)) => convert!(Fixed<u32>, *a),
+            Repr::Inline(Inline::I8(a)) => convert!(Fixed<i8>, *a),
+            Repr::Inline(Inline::I16(a)) => convert!(Fixed<i16>, *a),
+            Repr::Inline(Inline::I32(a)) => convert!(Fixed<i32>, *a),
             Repr::Inline(Inline::Float(a)) => convert!(f64, *a),
+            Repr::Any(value) => match Wide::try_from_any(value)? {
+                Some(Wide::Signed(a)) => convert!(Fixed<i128>, a),
+                Some(Wide::Unsigned(a)) => convert!(Fixed<u128>, a),
+                None => {
+                    return Err(VmError::new(VmErrorKind::UnsupportedAs {
+                        value: value.type_info(),
+                        type_hash: ty.into_hash(),
+                    }));
+                }
+            },
             value => {
                 return Err(VmError::new(VmErrorKind::UnsupportedAs {
                     value: value.type_info(),
@@ -1215,7 +1241,7 @@ impl Vm {
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_store(&mut self, value: InstValue, out: Output) -> Result<(), VmError> {
-        self.stack.store(out, value.into_value())?;
+        self.stack.store(out, value.into_value()?)?;
         Ok(())
     }
 
@@ -1267,28 +1293,30 @@ impl Vm {
     /// Perform a conditional jump operation.
     #[cfg_attr(feature = "bench", inline(never))]
     #[cfg_attr(not(feature = "bench"), inline)]
-    fn op_jump_if(&mut self, cond: Address, jump: usize) -> Result<(), VmErrorKind> {
+    fn op_jump_if(&mut self, cond: Address, jump: usize) -> Result<bool, VmErrorKind> {
         if matches!(
             self.stack.at(cond).as_ref(),
             Repr::Inline(Inline::Bool(true))
         ) {
             self.ip = self.unit.translate(jump)?;
+            return Ok(true);
         }
 
-        Ok(())
+        Ok(false)
     }
 
     /// pop-and-jump-if-not instruction.
     #[cfg_attr(feature = "bench", inline(never))]
-    fn op_jump_if_not(&mut self, cond: Address, jump: usize) -> Result<(), VmErrorKind> {
+    fn op_jump_if_not(&mut self, cond: Address, jump: usize) -> Result<bool, VmErrorKind> {
         if matches!(
             self.stack.at(cond).as_ref(),
             Repr::Inline(Inline::Bool(false))
         ) {
             self.ip = self.unit.translate(jump)?;
+            return Ok(true);
         }
 
-        Ok(())
+        Ok(false)
     }
 
     /// Construct a new vec.
@@ -1363,21 +1391,48 @@ impl Vm {
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_not(&mut self, addr: Address, out: Output) -> Result<(), VmError> {
-        self.unary(addr, out, &Protocol::NOT, |inline| match *inline {
-            Inline::Bool(value) => Some(Inline::Bool(!value)),
-            Inline::Unsigned(value) => Some(Inline::Unsigned(!value)),
-            Inline::Signed(value) => Some(Inline::Signed(!value)),
-            _ => None,
-        })
+        self.unary(
+            addr,
+            out,
+            &Protocol::NOT,
+            |inline| match *inline {
+                Inline::Bool(value) => Some(Inline::Bool(!value)),
+                Inline::Unsigned(value) => Some(Inline::Unsigned(!value)),
+                Inline::Signed(value) => Some(Inline::Signed(!value)),
+                Inline::U8(value) => Some(Inline::U8(!value)),
+                Inline::U16(value) => Some(Inline::U16(!value)),
+                Inline::U32(value) => Some(Inline::U32(!value)),
+                Inline::I8(value) => Some(Inline::I8(!value)),
+                Inline::I16(value) => Some(Inline::I16(!value)),
+                Inline::I32(value) => Some(Inline::I32(!value)),
+                _ => None,
+            },
+            |wide| match wide {
+                Wide::Signed(value) => Some(Wide::Signed(!value)),
+                Wide::Unsigned(value) => Some(Wide::Unsigned(!value)),
+            },
+        )
     }
 
     #[cfg_attr(feature = "bench", inline(never))]
     fn op_neg(&mut self, addr: Address, out: Output) -> Result<(), VmError> {
-        self.unary(addr, out, &Protocol::NEG, |inline| match *inline {
-            Inline::Signed(value) => Some(Inline::Signed(-value)),
-            Inline::Float(value) => Some(Inline::Float(-value)),
-            _ => None,
-        })
+        self.unary(
+            addr,
+            out,
+            &Protocol::NEG,
+            |inline| match *inline {
+                Inline::Signed(value) => Some(Inline::Signed(-value)),
+                Inline::I8(value) => Some(Inline::I8(value.wrapping_neg())),
+                Inline::I16(value) => Some(Inline::I16(value.wrapping_neg())),
+                Inline::I32(value) => Some(Inline::I32(value.wrapping_neg())),
+                Inline::Float(value) => Some(Inline::Float(-value)),
+                _ => None,
+            },
+            |wide| match wide {
+                Wide::Signed(value) => Some(Wide::Signed(value.wrapping_neg())),
+                Wide::Unsigned(..) => None,
+            },
+        )
     }
 
     fn unary(
@@ -1386,6 +1441,7 @@ impl Vm {
         out: Output,
         protocol: &'static Protocol,
         op: impl FnOnce(&Inline) -> Option<Inline>,
+        wide: impl FnOnce(Wide) -> Option<Wide>,
     ) -> Result<(), VmError> {
         let operand = self.stack.at(operand);
 
@@ -1409,6 +1465,13 @@ impl Vm {
 
         let operand = operand.clone();
 
+        if let Repr::Any(value) = operand.as_ref() {
+            if let Some(value) = Wide::try_from_any(value)?.and_then(wide) {
+                self.stack.store(out, value.into_value()?)?;
+                return Ok(());
+            }
+        }
+
         if let CallResult::Unsupported(operand) =
             self.call_instance_fn(Isolated::None, operand, protocol, &mut (), out)?
         {
@@ -1534,11 +1597,16 @@ impl Vm {
                         Inline::Float(value)
                     }
                     (lhs, rhs) => {
-                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
-                            op: ops.protocol.name,
-                            lhs: lhs.type_info(),
-                            rhs: rhs.type_info(),
-                        }));
+                        let Some(value) = sized_integer(*lhs, *rhs, ops.error, ops.u64, ops.i64)?
+                        else {
+                            return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                                op: ops.protocol.name,
+                                lhs: lhs.type_info(),
+                                rhs: rhs.type_info(),
+                            }));
+                        };
+
+                        value
                     }
                 },
                 (Repr::Any(..), ..) => {
@@ -1560,6 +1628,11 @@ impl Vm {
         let lhs = lhs.clone();
         let rhs = rhs.clone();
 
+        if let Some(value) = wide_integer(&lhs, &rhs, ops.error, ops.u128, ops.i128)? {
+            self.stack.store(out, value)?;
+            return Ok(());
+        }
+
         let mut args = DynGuardedArgs::new((rhs.clone(),));
 
         if let CallResult::Unsupported(lhs) =
@@ -1604,6 +1677,24 @@ impl Vm {
                     let value = (ops.bool)(*lhs, *rhs);
                     Inline::Bool(value)
                 }
+                (Repr::Inline(lhs), Repr::Inline(rhs)) => {
+                    let Some(value) = sized_integer(
+                        *lhs,
+                        *rhs,
+                        || VmErrorKind::Overflow,
+                        |a, b| Some((ops.u64)(a, b)),
+                        |a, b| Some((ops.i64)(a, b)),
+                    )?
+                    else {
+                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                            op: ops.protocol.name,
+                            lhs: lhs.type_info(),
+                            rhs: rhs.type_info(),
+                        }));
+                    };
+
+                    value
+                }
                 (Repr::Any(_), _) => {
                     break 'fallback;
                 }
@@ -1623,6 +1714,17 @@ impl Vm {
         let lhs = lhs.clone();
         let rhs = rhs.clone();
 
+        if let Some(value) = wide_integer(
+            &lhs,
+            &rhs,
+            || VmErrorKind::Overflow,
+            |a, b| Some((ops.u128)(a, b)),
+            |a, b| Some((ops.i128)(a, b)),
+        )? {
+            self.stack.store(out, value)?;
+            return Ok(());
+        }
+
         let mut args = DynGuardedArgs::new((&rhs,));
 
         if let CallResult::Unsupported(lhs) =
@@ -1662,6 +1764,21 @@ impl Vm {
                             let value = (ops.i64)(*value, shift).ok_or_else(ops.error)?;
                             Inline::Signed(value)
                         }
+                        Repr::Inline(value) => {
+                            let Some(value) =
+                                sized_shift(*value, *value, ops.error, ops.u64, ops.i64)?
+                            else {
+                                return Err(VmError::new(
+                                    VmErrorKind::UnsupportedBinaryOperation {
+                                        op: ops.protocol.name,
+                                        lhs: value.type_info(),
+                                        rhs: value.type_info(),
+                                    },
+                                ));
+                            };
+
+                            value
+                        }
                         Repr::Any(..) => break 'fallback (value.clone(), value.clone()),
                         value => {
                             return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
@@ -1682,6 +1799,20 @@ impl Vm {
                             let value = (ops.i64)(*lhs, rhs).ok_or_else(ops.error)?;
                             Inline::Signed(value)
                         }
+                        (Repr::Inline(lhs), Repr::Inline(rhs)) => {
+                            let Some(value) = sized_shift(*lhs, *rhs, ops.error, ops.u64, ops.i64)?
+                            else {
+                                return Err(VmError::new(
+                                    VmErrorKind::UnsupportedBinaryOperation {
+                                        op: ops.protocol.name,
+                                        lhs: lhs.type_info(),
+                                        rhs: rhs.type_info(),
+                                    },
+                                ));
+                            };
+
+                            value
+                        }
                         (Repr::Any(..), _) => {
                             break 'fallback (lhs.clone(), rhs.clone());
                         }
@@ -1700,6 +1831,11 @@ impl Vm {
             return Ok(());
         };
 
+        if let Some(value) = wide_shift(&lhs, &rhs, ops.error, ops.u128, ops.i128)? {
+            self.stack.store(out, value)?;
+            return Ok(());
+        }
+
         let mut args = DynGuardedArgs::new((rhs.clone(),));
 
         if let CallResult::Unsupported(lhs) =
@@ -1741,7 +1877,27 @@ impl Vm {
                     *value = out;
                     return Ok(());
                 }
-                Repr::Any(..) => TargetFallback::Value(value.clone(), value.clone()),
+                Repr::Inline(value) => {
+                    let Some(out) = sized_integer(*value, *value, ops.error, ops.u64, ops.i64)?
+                    else {
+                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                            op: ops.protocol.name,
+                            lhs: value.type_info(),
+                            rhs: value.type_info(),
+                        }));
+                    };
+
+                    *value = out;
+                    return Ok(());
+                }
+                Repr::Any(..) => {
+                    if let Some(out) = wide_integer(value, value, ops.error, ops.u128, ops.i128)? {
+                        *value = out;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(value.clone(), value.clone())
+                }
                 value => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1768,7 +1924,26 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (Repr::Any(..), _) => TargetFallback::Value(lhs.clone(), rhs.clone()),
+                (Repr::Inline(lhs), Repr::Inline(rhs)) => {
+                    let Some(out) = sized_integer(*lhs, *rhs, ops.error, ops.u64, ops.i64)? else {
+                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                            op: ops.protocol.name,
+                            lhs: lhs.type_info(),
+                            rhs: rhs.type_info(),
+                        }));
+                    };
+
+                    *lhs = out;
+                    return Ok(());
+                }
+                (Repr::Any(..), _) => {
+                    if let Some(out) = wide_integer(&lhs, rhs, ops.error, ops.u128, ops.i128)? {
+                        *lhs = out;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(lhs.clone(), rhs.clone())
+                }
                 (lhs, rhs) => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1809,7 +1984,51 @@ impl Vm {
                     (ops.bool)(value, rhs);
                     return Ok(());
                 }
-                Repr::Any(..) => TargetFallback::Value(value.clone(), value.clone()),
+                Repr::Inline(value) => {
+                    let Some(out) = sized_integer(
+                        *value,
+                        *value,
+                        || VmErrorKind::Overflow,
+                        |mut a, b| {
+                            (ops.u64)(&mut a, b);
+                            Some(a)
+                        },
+                        |mut a, b| {
+                            (ops.i64)(&mut a, b);
+                            Some(a)
+                        },
+                    )?
+                    else {
+                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                            op: ops.protocol.name,
+                            lhs: value.type_info(),
+                            rhs: value.type_info(),
+                        }));
+                    };
+
+                    *value = out;
+                    return Ok(());
+                }
+                Repr::Any(..) => {
+                    if let Some(out) = wide_integer(
+                        value,
+                        value,
+                        || VmErrorKind::Overflow,
+                        |mut a, b| {
+                            (ops.u128)(&mut a, b);
+                            Some(a)
+                        },
+                        |mut a, b| {
+                            (ops.i128)(&mut a, b);
+                            Some(a)
+                        },
+                    )? {
+                        *value = out;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(value.clone(), value.clone())
+                }
                 value => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1833,7 +2052,51 @@ impl Vm {
                     (ops.bool)(lhs, *rhs);
                     return Ok(());
                 }
-                (Repr::Any(..), ..) => TargetFallback::Value(lhs.clone(), rhs.clone()),
+                (Repr::Inline(lhs), Repr::Inline(rhs)) => {
+                    let Some(out) = sized_integer(
+                        *lhs,
+                        *rhs,
+                        || VmErrorKind::Overflow,
+                        |mut a, b| {
+                            (ops.u64)(&mut a, b);
+                            Some(a)
+                        },
+                        |mut a, b| {
+                            (ops.i64)(&mut a, b);
+                            Some(a)
+                        },
+                    )?
+                    else {
+                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                            op: ops.protocol.name,
+                            lhs: lhs.type_info(),
+                            rhs: rhs.type_info(),
+                        }));
+                    };
+
+                    *lhs = out;
+                    return Ok(());
+                }
+                (Repr::Any(..), _) => {
+                    if let Some(out) = wide_integer(
+                        &lhs,
+                        rhs,
+                        || VmErrorKind::Overflow,
+                        |mut a, b| {
+                            (ops.u128)(&mut a, b);
+                            Some(a)
+                        },
+                        |mut a, b| {
+                            (ops.i128)(&mut a, b);
+                            Some(a)
+                        },
+                    )? {
+                        *lhs = out;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(lhs.clone(), rhs.clone())
+                }
                 (lhs, rhs) => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1871,7 +2134,27 @@ impl Vm {
                     *value = out;
                     return Ok(());
                 }
-                Repr::Any(..) => TargetFallback::Value(value.clone(), value.clone()),
+                Repr::Inline(value) => {
+                    let Some(out) = sized_shift(*value, *value, ops.error, ops.u64, ops.i64)?
+                    else {
+                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                            op: ops.protocol.name,
+                            lhs: value.type_info(),
+                            rhs: value.type_info(),
+                        }));
+                    };
+
+                    *value = out;
+                    return Ok(());
+                }
+                Repr::Any(..) => {
+                    if let Some(out) = wide_shift(value, value, ops.error, ops.u128, ops.i128)? {
+                        *value = out;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(value.clone(), value.clone())
+                }
                 value => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -1893,7 +2176,26 @@ impl Vm {
                     *lhs = out;
                     return Ok(());
                 }
-                (Repr::Any(..), _) => TargetFallback::Value(lhs.clone(), rhs.clone()),
+                (Repr::Inline(lhs), Repr::Inline(rhs)) => {
+                    let Some(out) = sized_shift(*lhs, *rhs, ops.error, ops.u64, ops.i64)? else {
+                        return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
+                            op: ops.protocol.name,
+                            lhs: lhs.type_info(),
+                            rhs: rhs.type_info(),
+                        }));
+                    };
+
+                    *lhs = out;
+                    return Ok(());
+                }
+                (Repr::Any(..), _) => {
+                    if let Some(out) = wide_shift(&lhs, rhs, ops.error, ops.u128, ops.i128)? {
+                        *lhs = out;
+                        return Ok(());
+                    }
+
+                    TargetFallback::Value(lhs.clone(), rhs.clone())
+                }
                 (lhs, rhs) => {
                     return Err(VmError::new(VmErrorKind::UnsupportedBinaryOperation {
                         op: ops.protocol.name,
@@ -2452,6 +2754,9 @@ impl Vm {
 
         let is_match = match v.as_inline() {
             Some(Inline::Unsigned(actual)) => *actual == value,
+            Some(Inline::U8(actual)) => u64::from(*actual) == value,
+            Some(Inline::U16(actual)) => u64::from(*actual) == value,
+            Some(Inline::U32(actual)) => u64::from(*actual) == value,
             _ => false,
         };
 
@@ -2463,6 +2768,9 @@ impl Vm {
     fn op_eq_signed(&mut self, addr: Address, value: i64, out: Output) -> Result<(), VmError> {
         let is_match = match self.stack.at(addr).as_inline() {
             Some(Inline::Signed(actual)) => *actual == value,
+            Some(Inline::I8(actual)) => i64::from(*actual) == value,
+            Some(Inline::I16(actual)) => i64::from(*actual) == value,
+            Some(Inline::I32(actual)) => i64::from(*actual) == value,
             _ => false,
         };
 
@@ -2913,28 +3221,49 @@ impl Vm {
                 vm_diagnostics_obj = VmDiagnosticsObj::new(diagnostics);
                 Some(NonNull::from(&mut vm_diagnostics_obj))
             }
-            None => None,
+            None => runtime::env::diagnostics(&self.unit),
         };
 
         // NB: set up environment so that native function can access context and
         // unit.
         let _guard = runtime::env::Guard::new(self.context.clone(), self.unit.clone(), diagnostics);
 
+        // Safety: the diagnostics object outlives the execution.
+        let mut instructions =
+            diagnostics.filter(|diagnostics| unsafe { diagnostics.as_ref().instructions() });
+
         let mut budget = budget::acquire();
+        #[cfg(feature = "std")]
+        let mut ticks = 0usize;
 
         loop {
             if !budget.take() {
                 return Ok(VmHalt::Limited);
             }
 
-            let Some((inst, inst_len)) = self.unit.instruction_at(self.ip)? else {
+            #[cfg(feature = "std")]
+            {
+                ticks = ticks.wrapping_add(1);
+
+                if ticks.is_multiple_of(runtime::interrupt::INTERVAL) {
+                    runtime::interrupt::check()?;
+                }
+            }
+
+            let ip = self.ip;
+
+            let Some((inst, inst_len)) = self.unit.instruction_at(ip)? else {
                 return Err(VmError::new(VmErrorKind::IpOutOfBounds {
-                    ip: self.ip,
+                    ip,
                     length: self.unit.instructions().end(),
                 }));
             };
 
-            tracing::trace!(ip = ?self.ip, ?inst);
+            tracing::trace!(ip = ?ip, ?inst);
+
+            if let Some(diagnostics) = &mut instructions {
+                unsafe { diagnostics.as_mut().instruction(ip)? };
+            }
 
             self.ip = self.ip.wrapping_add(inst_len);
             self.last_ip_len = inst_len as u8;
@@ -3066,10 +3395,18 @@ impl Vm {
                     self.op_jump(jump)?;
                 }
                 inst::Kind::JumpIf { cond, jump } => {
-                    self.op_jump_if(cond, jump)?;
+                    let taken = self.op_jump_if(cond, jump)?;
+
+                    if let Some(diagnostics) = &mut instructions {
+                        unsafe { diagnostics.as_mut().branch(ip, taken)? };
+                    }
                 }
                 inst::Kind::JumpIfNot { cond, jump } => {
-                    self.op_jump_if_not(cond, jump)?;
+                    let taken = self.op_jump_if_not(cond, jump)?;
+
+                    if let Some(diagnostics) = &mut instructions {
+                        unsafe { diagnostics.as_mut().branch(ip, taken)? };
+                    }
                 }
                 inst::Kind::Vec { addr, count, out } => {
                     self.op_vec(addr, count, out)?;
@@ -3187,6 +3524,24 @@ impl Vm {
                 inst::Kind::Op { op, a, b, out } => {
                     self.op_op(op, a, b, out)?;
                 }
+                inst::Kind::OpJumpIfNot {
+                    op,
+                    a,
+                    b,
+                    out,
+                    jump,
+                } => {
+                    self.op_op(op, a, b, out)?;
+
+                    let taken = match out.as_addr() {
+                        Some(cond) => self.op_jump_if_not(cond, jump)?,
+                        None => false,
+                    };
+
+                    if let Some(diagnostics) = &mut instructions {
+                        unsafe { diagnostics.as_mut().branch(ip, taken)? };
+                    }
+                }
                 inst::Kind::Arithmetic { op, a, b, out } => {
                     self.op_arithmetic(op, a, b, out)?;
                 }
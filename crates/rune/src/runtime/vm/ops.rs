@@ -2,13 +2,18 @@ use core::ops::{
     Add, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, Mul, Rem, Sub,
 };
 
-use crate::runtime::{InstArithmeticOp, InstBitwiseOp, InstShiftOp, Protocol, VmErrorKind};
+use crate::runtime::{
+    Inline, InstArithmeticOp, InstBitwiseOp, InstShiftOp, Protocol, Repr, Value, VmError,
+    VmErrorKind, Wide,
+};
 
 pub(super) struct ArithmeticOps {
     pub(super) protocol: Protocol,
     pub(super) error: fn() -> VmErrorKind,
     pub(super) i64: fn(i64, i64) -> Option<i64>,
     pub(super) u64: fn(u64, u64) -> Option<u64>,
+    pub(super) i128: fn(i128, i128) -> Option<i128>,
+    pub(super) u128: fn(u128, u128) -> Option<u128>,
     pub(super) f64: fn(f64, f64) -> f64,
 }
 
@@ -20,6 +25,8 @@ impl ArithmeticOps {
                 error: || VmErrorKind::Overflow,
                 i64: i64::checked_add,
                 u64: u64::checked_add,
+                i128: i128::checked_add,
+                u128: u128::checked_add,
                 f64: f64::add,
             },
             InstArithmeticOp::Sub => &Self {
@@ -27,6 +34,8 @@ impl ArithmeticOps {
                 error: || VmErrorKind::Underflow,
                 i64: i64::checked_sub,
                 u64: u64::checked_sub,
+                i128: i128::checked_sub,
+                u128: u128::checked_sub,
                 f64: f64::sub,
             },
             InstArithmeticOp::Mul => &Self {
@@ -34,6 +43,8 @@ impl ArithmeticOps {
                 error: || VmErrorKind::Overflow,
                 i64: i64::checked_mul,
                 u64: u64::checked_mul,
+                i128: i128::checked_mul,
+                u128: u128::checked_mul,
                 f64: f64::mul,
             },
             InstArithmeticOp::Div => &Self {
@@ -41,6 +52,8 @@ impl ArithmeticOps {
                 error: || VmErrorKind::DivideByZero,
                 i64: i64::checked_div,
                 u64: u64::checked_div,
+                i128: i128::checked_div,
+                u128: u128::checked_div,
                 f64: f64::div,
             },
             InstArithmeticOp::Rem => &Self {
@@ -48,6 +61,8 @@ impl ArithmeticOps {
                 error: || VmErrorKind::DivideByZero,
                 i64: i64::checked_rem,
                 u64: u64::checked_rem,
+                i128: i128::checked_rem,
+                u128: u128::checked_rem,
                 f64: f64::rem,
             },
         }
@@ -59,6 +74,8 @@ pub(super) struct AssignArithmeticOps {
     pub(super) error: fn() -> VmErrorKind,
     pub(super) i64: fn(i64, i64) -> Option<i64>,
     pub(super) u64: fn(u64, u64) -> Option<u64>,
+    pub(super) i128: fn(i128, i128) -> Option<i128>,
+    pub(super) u128: fn(u128, u128) -> Option<u128>,
     pub(super) f64: fn(f64, f64) -> f64,
 }
 
@@ -70,6 +87,8 @@ impl AssignArithmeticOps {
                 error: || VmErrorKind::Overflow,
                 i64: i64::checked_add,
                 u64: u64::checked_add,
+                i128: i128::checked_add,
+                u128: u128::checked_add,
                 f64: f64::add,
             },
             InstArithmeticOp::Sub => &Self {
@@ -77,6 +96,8 @@ impl AssignArithmeticOps {
                 error: || VmErrorKind::Underflow,
                 i64: i64::checked_sub,
                 u64: u64::checked_sub,
+                i128: i128::checked_sub,
+                u128: u128::checked_sub,
                 f64: f64::sub,
             },
             InstArithmeticOp::Mul => &Self {
@@ -84,6 +105,8 @@ impl AssignArithmeticOps {
                 error: || VmErrorKind::Overflow,
                 i64: i64::checked_mul,
                 u64: u64::checked_mul,
+                i128: i128::checked_mul,
+                u128: u128::checked_mul,
                 f64: f64::mul,
             },
             InstArithmeticOp::Div => &Self {
@@ -91,6 +114,8 @@ impl AssignArithmeticOps {
                 error: || VmErrorKind::DivideByZero,
                 i64: i64::checked_div,
                 u64: u64::checked_div,
+                i128: i128::checked_div,
+                u128: u128::checked_div,
                 f64: f64::div,
             },
             InstArithmeticOp::Rem => &Self {
@@ -98,6 +123,8 @@ impl AssignArithmeticOps {
                 error: || VmErrorKind::DivideByZero,
                 i64: i64::checked_rem,
                 u64: u64::checked_rem,
+                i128: i128::checked_rem,
+                u128: u128::checked_rem,
                 f64: f64::rem,
             },
         }
@@ -108,6 +135,8 @@ pub(super) struct AssignBitwiseOps {
     pub(super) protocol: Protocol,
     pub(super) i64: fn(&mut i64, i64),
     pub(super) u64: fn(&mut u64, u64),
+    pub(super) i128: fn(&mut i128, i128),
+    pub(super) u128: fn(&mut u128, u128),
     pub(super) bool: fn(&mut bool, bool),
 }
 
@@ -118,18 +147,24 @@ impl AssignBitwiseOps {
                 protocol: Protocol::BIT_AND_ASSIGN,
                 i64: i64::bitand_assign,
                 u64: u64::bitand_assign,
+                i128: i128::bitand_assign,
+                u128: u128::bitand_assign,
                 bool: bool::bitand_assign,
             },
             InstBitwiseOp::BitXor => &Self {
                 protocol: Protocol::BIT_XOR_ASSIGN,
                 i64: i64::bitxor_assign,
                 u64: u64::bitxor_assign,
+                i128: i128::bitxor_assign,
+                u128: u128::bitxor_assign,
                 bool: bool::bitxor_assign,
             },
             InstBitwiseOp::BitOr => &Self {
                 protocol: Protocol::BIT_OR_ASSIGN,
                 i64: i64::bitor_assign,
                 u64: u64::bitor_assign,
+                i128: i128::bitor_assign,
+                u128: u128::bitor_assign,
                 bool: bool::bitor_assign,
             },
         }
@@ -140,6 +175,8 @@ pub(super) struct BitwiseOps {
     pub(super) protocol: Protocol,
     pub(super) i64: fn(i64, i64) -> i64,
     pub(super) u64: fn(u64, u64) -> u64,
+    pub(super) i128: fn(i128, i128) -> i128,
+    pub(super) u128: fn(u128, u128) -> u128,
     pub(super) bool: fn(bool, bool) -> bool,
 }
 
@@ -150,18 +187,24 @@ impl BitwiseOps {
                 protocol: Protocol::BIT_AND,
                 i64: i64::bitand,
                 u64: u64::bitand,
+                i128: i128::bitand,
+                u128: u128::bitand,
                 bool: bool::bitand,
             },
             InstBitwiseOp::BitXor => &BitwiseOps {
                 protocol: Protocol::BIT_XOR,
                 i64: i64::bitxor,
                 u64: u64::bitxor,
+                i128: i128::bitxor,
+                u128: u128::bitxor,
                 bool: bool::bitxor,
             },
             InstBitwiseOp::BitOr => &BitwiseOps {
                 protocol: Protocol::BIT_OR,
                 i64: i64::bitor,
                 u64: u64::bitor,
+                i128: i128::bitor,
+                u128: u128::bitor,
                 bool: bool::bitor,
             },
         }
@@ -173,6 +216,8 @@ pub(super) struct AssignShiftOps {
     pub(super) error: fn() -> VmErrorKind,
     pub(super) i64: fn(i64, u32) -> Option<i64>,
     pub(super) u64: fn(u64, u32) -> Option<u64>,
+    pub(super) i128: fn(i128, u32) -> Option<i128>,
+    pub(super) u128: fn(u128, u32) -> Option<u128>,
 }
 
 impl AssignShiftOps {
@@ -183,12 +228,16 @@ impl AssignShiftOps {
                 error: || VmErrorKind::Overflow,
                 i64: i64::checked_shl,
                 u64: u64::checked_shl,
+                i128: i128::checked_shl,
+                u128: u128::checked_shl,
             },
             InstShiftOp::Shr => &Self {
                 protocol: Protocol::SHR_ASSIGN,
                 error: || VmErrorKind::Underflow,
                 i64: i64::checked_shr,
                 u64: u64::checked_shr,
+                i128: i128::checked_shr,
+                u128: u128::checked_shr,
             },
         }
     }
@@ -199,6 +248,8 @@ pub(super) struct ShiftOps {
     pub(super) error: fn() -> VmErrorKind,
     pub(super) i64: fn(i64, u32) -> Option<i64>,
     pub(super) u64: fn(u64, u32) -> Option<u64>,
+    pub(super) i128: fn(i128, u32) -> Option<i128>,
+    pub(super) u128: fn(u128, u32) -> Option<u128>,
 }
 
 impl ShiftOps {
@@ -209,13 +260,154 @@ impl ShiftOps {
                 error: || VmErrorKind::Overflow,
                 i64: i64::checked_shl,
                 u64: u64::checked_shl,
+                i128: i128::checked_shl,
+                u128: u128::checked_shl,
             },
             InstShiftOp::Shr => &Self {
                 protocol: Protocol::SHR,
                 error: || VmErrorKind::Underflow,
                 i64: i64::checked_shr,
                 u64: u64::checked_shr,
+                i128: i128::checked_shr,
+                u128: u128::checked_shr,
             },
         }
     }
 }
+
+/// Perform an operation over sized integers.
+///
+/// The operands are widened to 64 bits before `unsigned` or `signed` is
+/// applied, and the result is narrowed back into the width of `lhs`. A result
+/// which does not fit is reported through `error`, the same as if the operation
+/// itself had failed.
+///
+/// Returns `None` if `lhs` is not a sized integer.
+pub(super) fn sized_integer(
+    lhs: Inline,
+    rhs: Inline,
+    error: fn() -> VmErrorKind,
+    unsigned: impl FnOnce(u64, u64) -> Option<u64>,
+    signed: impl FnOnce(i64, i64) -> Option<i64>,
+) -> Result<Option<Inline>, VmError> {
+    macro_rules! op {
+        ($variant:ident, $ty:ty, $lhs:expr, $op:ident) => {{
+            let rhs = rhs.as_integer::<$ty>()?;
+            let value = $op($lhs.into(), rhs.into()).ok_or_else(error)?;
+            let value = <$ty>::try_from(value).map_err(|_| error())?;
+            Inline::$variant(value)
+        }};
+    }
+
+    let value = match lhs {
+        Inline::U8(lhs) => op!(U8, u8, lhs, unsigned),
+        Inline::U16(lhs) => op!(U16, u16, lhs, unsigned),
+        Inline::U32(lhs) => op!(U32, u32, lhs, unsigned),
+        Inline::I8(lhs) => op!(I8, i8, lhs, signed),
+        Inline::I16(lhs) => op!(I16, i16, lhs, signed),
+        Inline::I32(lhs) => op!(I32, i32, lhs, signed),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(value))
+}
+
+/// Perform a shift over sized integers.
+///
+/// Shifting by the number of bits in `lhs` or more is an error, while bits
+/// which are shifted out are discarded.
+///
+/// Returns `None` if `lhs` is not a sized integer.
+pub(super) fn sized_shift(
+    lhs: Inline,
+    rhs: Inline,
+    error: fn() -> VmErrorKind,
+    unsigned: fn(u64, u32) -> Option<u64>,
+    signed: fn(i64, u32) -> Option<i64>,
+) -> Result<Option<Inline>, VmError> {
+    macro_rules! op {
+        ($variant:ident, $ty:ty, $lhs:expr, $op:ident) => {{
+            let shift = rhs.as_integer::<u32>()?;
+
+            if shift >= <$ty>::BITS {
+                return Err(VmError::new(error()));
+            }
+
+            let value = $op($lhs.into(), shift).ok_or_else(error)?;
+            Inline::$variant(value as $ty)
+        }};
+    }
+
+    let value = match lhs {
+        Inline::U8(lhs) => op!(U8, u8, lhs, unsigned),
+        Inline::U16(lhs) => op!(U16, u16, lhs, unsigned),
+        Inline::U32(lhs) => op!(U32, u32, lhs, unsigned),
+        Inline::I8(lhs) => op!(I8, i8, lhs, signed),
+        Inline::I16(lhs) => op!(I16, i16, lhs, signed),
+        Inline::I32(lhs) => op!(I32, i32, lhs, signed),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(value))
+}
+
+/// Perform an operation over 128-bit integers.
+///
+/// The `rhs` operand can be an integer of any width, as long as it fits in the
+/// type of `lhs`.
+///
+/// Returns `None` if `lhs` is not a 128-bit integer.
+pub(super) fn wide_integer(
+    lhs: &Value,
+    rhs: &Value,
+    error: fn() -> VmErrorKind,
+    unsigned: impl FnOnce(u128, u128) -> Option<u128>,
+    signed: impl FnOnce(i128, i128) -> Option<i128>,
+) -> Result<Option<Value>, VmError> {
+    let Some(lhs) = wide_operand(lhs)? else {
+        return Ok(None);
+    };
+
+    let rhs = Wide::from_value(rhs)?;
+
+    let value = match lhs {
+        Wide::Unsigned(lhs) => {
+            Wide::Unsigned(unsigned(lhs, rhs.into_integer()?).ok_or_else(error)?)
+        }
+        Wide::Signed(lhs) => Wide::Signed(signed(lhs, rhs.into_integer()?).ok_or_else(error)?),
+    };
+
+    Ok(Some(value.into_value()?))
+}
+
+/// Perform a shift over 128-bit integers.
+///
+/// Returns `None` if `lhs` is not a 128-bit integer.
+pub(super) fn wide_shift(
+    lhs: &Value,
+    rhs: &Value,
+    error: fn() -> VmErrorKind,
+    unsigned: fn(u128, u32) -> Option<u128>,
+    signed: fn(i128, u32) -> Option<i128>,
+) -> Result<Option<Value>, VmError> {
+    let Some(lhs) = wide_operand(lhs)? else {
+        return Ok(None);
+    };
+
+    let shift = rhs.as_integer::<u32>()?;
+
+    let value = match lhs {
+        Wide::Unsigned(lhs) => Wide::Unsigned(unsigned(lhs, shift).ok_or_else(error)?),
+        Wide::Signed(lhs) => Wide::Signed(signed(lhs, shift).ok_or_else(error)?),
+    };
+
+    Ok(Some(value.into_value()?))
+}
+
+fn wide_operand(value: &Value) -> Result<Option<Wide>, VmError> {
+    let Repr::Any(value) = value.as_ref() else {
+        return Ok(None);
+    };
+
+    Ok(Wide::try_from_any(value)?)
+}
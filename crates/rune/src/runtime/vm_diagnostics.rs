@@ -1,3 +1,4 @@
+use core::marker::PhantomData;
 use core::ptr::NonNull;
 
 use crate::hash::Hash;
@@ -9,6 +10,31 @@ pub trait VmDiagnostics {
     /// Mark that a function has been used.
     fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError>;
 
+    /// Test if the diagnostics should be notified of every instruction
+    /// executed through [`VmDiagnostics::instruction`] and
+    /// [`VmDiagnostics::branch`].
+    ///
+    /// This is checked once when execution is resumed.
+    #[inline]
+    fn instructions(&self) -> bool {
+        false
+    }
+
+    /// Mark that the instruction at `ip` is about to be executed.
+    #[inline]
+    fn instruction(&mut self, ip: usize) -> Result<(), VmError> {
+        _ = ip;
+        Ok(())
+    }
+
+    /// Mark that the conditional jump at `ip` was executed, and whether the
+    /// jump was `taken` or not.
+    #[inline]
+    fn branch(&mut self, ip: usize, taken: bool) -> Result<(), VmError> {
+        _ = (ip, taken);
+        Ok(())
+    }
+
     /// Returns the vtable for this diagnostics object.
     #[doc(hidden)]
     fn vtable(&self) -> &'static VmDiagnosticsObjVtable;
@@ -23,22 +49,58 @@ impl VmDiagnostics for Diagnostics {
 
     #[inline]
     fn vtable(&self) -> &'static VmDiagnosticsObjVtable {
-        fn function_used_impl<T>(ptr: NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>
-        where
-            T: VmDiagnostics,
-        {
-            unsafe { VmDiagnostics::function_used(ptr.cast::<T>().as_mut(), hash, at) }
-        }
+        vtable_of::<Self>()
+    }
+}
 
-        &VmDiagnosticsObjVtable {
-            function_used: function_used_impl::<Self>,
-        }
+/// Construct the vtable for the given diagnostics implementation.
+#[inline]
+pub(crate) fn vtable_of<T>() -> &'static VmDiagnosticsObjVtable
+where
+    T: VmDiagnostics,
+{
+    struct Vtable<T>(PhantomData<T>);
+
+    impl<T> Vtable<T>
+    where
+        T: VmDiagnostics,
+    {
+        const VTABLE: VmDiagnosticsObjVtable = VmDiagnosticsObjVtable {
+            function_used: function_used_impl::<T>,
+            instruction: instruction_impl::<T>,
+            branch: branch_impl::<T>,
+        };
     }
+
+    fn function_used_impl<T>(ptr: NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>
+    where
+        T: VmDiagnostics,
+    {
+        unsafe { VmDiagnostics::function_used(ptr.cast::<T>().as_mut(), hash, at) }
+    }
+
+    fn instruction_impl<T>(ptr: NonNull<()>, ip: usize) -> Result<(), VmError>
+    where
+        T: VmDiagnostics,
+    {
+        unsafe { VmDiagnostics::instruction(ptr.cast::<T>().as_mut(), ip) }
+    }
+
+    fn branch_impl<T>(ptr: NonNull<()>, ip: usize, taken: bool) -> Result<(), VmError>
+    where
+        T: VmDiagnostics,
+    {
+        unsafe { VmDiagnostics::branch(ptr.cast::<T>().as_mut(), ip, taken) }
+    }
+
+    &Vtable::<T>::VTABLE
 }
 
 #[derive(Debug)]
 pub struct VmDiagnosticsObjVtable {
     function_used: unsafe fn(NonNull<()>, hash: Hash, at: usize) -> Result<(), VmError>,
+    instruction: unsafe fn(NonNull<()>, ip: usize) -> Result<(), VmError>,
+    branch: unsafe fn(NonNull<()>, ip: usize, taken: bool) -> Result<(), VmError>,
 }
 
 #[repr(C)]
@@ -46,21 +108,40 @@ pub struct VmDiagnosticsObjVtable {
 pub(crate) struct VmDiagnosticsObj {
     ptr: NonNull<()>,
     vtable: &'static VmDiagnosticsObjVtable,
+    instructions: bool,
 }
 
 impl VmDiagnosticsObj {
     #[inline]
     pub(crate) fn new(trait_obj: &mut dyn VmDiagnostics) -> Self {
         let vtable = trait_obj.vtable();
+        let instructions = trait_obj.instructions();
 
         Self {
             ptr: unsafe { NonNull::new_unchecked(trait_obj as *mut _ as *mut ()) },
             vtable,
+            instructions,
         }
     }
 
+    /// Test if the diagnostics should be notified of executed instructions.
+    #[inline]
+    pub(crate) fn instructions(&self) -> bool {
+        self.instructions
+    }
+
     #[inline]
     pub(crate) fn function_used(&mut self, hash: Hash, at: usize) -> Result<(), VmError> {
         unsafe { (self.vtable.function_used)(self.ptr, hash, at) }
     }
+
+    #[inline]
+    pub(crate) fn instruction(&mut self, ip: usize) -> Result<(), VmError> {
+        unsafe { (self.vtable.instruction)(self.ptr, ip) }
+    }
+
+    #[inline]
+    pub(crate) fn branch(&mut self, ip: usize, taken: bool) -> Result<(), VmError> {
+        unsafe { (self.vtable.branch)(self.ptr, ip, taken) }
+    }
 }
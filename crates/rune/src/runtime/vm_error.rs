@@ -105,6 +105,25 @@ impl VmError {
         Self::from(VmErrorKind::Overflow)
     }
 
+    /// Test if the error was caused by the execution being cancelled through a
+    /// [`CancellationToken`].
+    ///
+    /// [`CancellationToken`]: crate::runtime::interrupt::CancellationToken
+    #[cfg(feature = "std")]
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.inner.error.kind, VmErrorKind::Cancelled)
+    }
+
+    /// Test if the error was caused by the execution exceeding its deadline.
+    ///
+    /// See [`VmExecution::with_deadline`].
+    ///
+    /// [`VmExecution::with_deadline`]: crate::runtime::VmExecution::with_deadline
+    #[cfg(feature = "std")]
+    pub fn is_deadline_exceeded(&self) -> bool {
+        matches!(self.inner.error.kind, VmErrorKind::DeadlineExceeded)
+    }
+
     /// Get the first error location.
     #[inline]
     pub fn first_location(&self) -> Option<&VmErrorLocation> {
@@ -467,6 +486,8 @@ pub(crate) enum VmErrorKind {
     Halted {
         halt: VmHaltInfo,
     },
+    Cancelled,
+    DeadlineExceeded,
     Overflow,
     Underflow,
     DivideByZero,
@@ -696,6 +717,8 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::Panic { reason } => write!(f, "Panicked: {reason}"),
             VmErrorKind::NoRunningVm => write!(f, "No running virtual machines"),
             VmErrorKind::Halted { halt } => write!(f, "Halted for unexpected reason `{halt}`"),
+            VmErrorKind::Cancelled => write!(f, "Execution was cancelled"),
+            VmErrorKind::DeadlineExceeded => write!(f, "Execution exceeded its deadline"),
             VmErrorKind::Overflow => write!(f, "Numerical overflow"),
             VmErrorKind::Underflow => write!(f, "Numerical underflow"),
             VmErrorKind::DivideByZero => write!(f, "Division by zero"),
@@ -4,9 +4,14 @@ use core::mem::{replace, take};
 use core::pin::{pin, Pin};
 use core::task::{ready, Context, Poll, RawWaker, RawWakerVTable, Waker};
 
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
 use crate::alloc::prelude::*;
 use crate::async_vm_try;
 use crate::runtime::budget::Budget;
+#[cfg(feature = "std")]
+use crate::runtime::interrupt::{self, CancellationToken, Interrupt, Registration};
 use crate::runtime::{budget, Awaited};
 use crate::shared::AssertSend;
 use crate::sync::Arc;
@@ -73,6 +78,9 @@ pub struct VmExecution<T> {
     state: ExecutionState,
     /// Indicates the current stack of suspended contexts.
     states: Vec<VmExecutionState>,
+    /// The conditions under which the execution is interrupted.
+    #[cfg(feature = "std")]
+    interrupt: Interrupt,
 }
 
 impl<T> VmExecution<T> {
@@ -83,9 +91,86 @@ impl<T> VmExecution<T> {
             vm,
             state: ExecutionState::Initial,
             states: Vec::new(),
+            #[cfg(feature = "std")]
+            interrupt: Interrupt::default(),
         }
     }
 
+    /// Interrupt the execution once the given deadline has passed.
+    ///
+    /// The deadline is checked periodically between instructions and whenever
+    /// the execution is polled, and once it has passed the execution fails
+    /// with an error for which [`VmError::is_deadline_exceeded`] returns
+    /// `true`.
+    ///
+    /// Note that a deadline can't wake up an execution which is waiting for a
+    /// future. To bound the time spent waiting, use a timer provided by your
+    /// async runtime to cancel a [`CancellationToken`] associated with the
+    /// execution through [`VmExecution::with_cancellation`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use rune::sync::Arc;
+    /// use rune::Vm;
+    ///
+    /// let mut sources = rune::sources! {
+    ///     entry => {
+    ///         pub fn main() {
+    ///             loop {}
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let unit = rune::prepare(&mut sources).build()?;
+    /// let mut vm = Vm::without_runtime(Arc::try_new(unit)?)?;
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    ///
+    /// let error = vm
+    ///     .execute(["main"], ())?
+    ///     .with_deadline(deadline)
+    ///     .complete()
+    ///     .unwrap_err();
+    ///
+    /// assert!(error.is_deadline_exceeded());
+    /// # Ok::<_, rune::support::Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.interrupt.set_deadline(deadline);
+        self
+    }
+
+    /// Interrupt the execution once the given amount of time has passed from
+    /// now.
+    ///
+    /// See [`VmExecution::with_deadline`].
+    #[cfg(feature = "std")]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.with_deadline(deadline),
+            None => self,
+        }
+    }
+
+    /// Interrupt the execution when the given token is cancelled.
+    ///
+    /// Cancellation is checked periodically between instructions and whenever
+    /// the execution is polled, and once the token has been cancelled the
+    /// execution fails with an error for which [`VmError::is_cancelled`]
+    /// returns `true`. An execution which is waiting for a future is woken up
+    /// when the token is cancelled.
+    ///
+    /// See [`CancellationToken`] for an example.
+    #[cfg(feature = "std")]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.interrupt.set_cancellation(token);
+        self
+    }
+
     /// Get a reference to the current virtual machine.
     #[inline]
     pub fn vm(&self) -> &Vm
@@ -235,6 +320,8 @@ where
             diagnostics: None,
             awaited: None,
             init: Some(Value::empty()),
+            #[cfg(feature = "std")]
+            registration: Registration::default(),
         }
     }
 
@@ -297,6 +384,8 @@ impl VmExecution<&mut Vm> {
             vm: head,
             states: self.states,
             state: self.state,
+            #[cfg(feature = "std")]
+            interrupt: self.interrupt,
         }
     }
 }
@@ -316,6 +405,31 @@ pub struct VmSendExecution(pub(crate) VmExecution<Vm>);
 unsafe impl Send for VmSendExecution {}
 
 impl VmSendExecution {
+    /// Interrupt the execution once the given deadline has passed.
+    ///
+    /// See [`VmExecution::with_deadline`].
+    #[cfg(feature = "std")]
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        Self(self.0.with_deadline(deadline))
+    }
+
+    /// Interrupt the execution once the given amount of time has passed from
+    /// now.
+    ///
+    /// See [`VmExecution::with_timeout`].
+    #[cfg(feature = "std")]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self(self.0.with_timeout(timeout))
+    }
+
+    /// Interrupt the execution when the given token is cancelled.
+    ///
+    /// See [`VmExecution::with_cancellation`].
+    #[cfg(feature = "std")]
+    pub fn with_cancellation(self, token: CancellationToken) -> Self {
+        Self(self.0.with_cancellation(token))
+    }
+
     /// Complete the current execution with support for async instructions.
     ///
     /// This requires that the result of the Vm is converted into a
@@ -377,6 +491,8 @@ where
             vm: self.vm.try_clone()?,
             state: self.state,
             states: self.states.try_clone()?,
+            #[cfg(feature = "std")]
+            interrupt: self.interrupt.clone(),
         })
     }
 }
@@ -439,6 +555,9 @@ pub struct VmResume<'this, 'diag, T> {
     diagnostics: Option<&'diag mut dyn VmDiagnostics>,
     init: Option<Value>,
     awaited: Option<Awaited>,
+    /// The waker registered with the cancellation token of the execution.
+    #[cfg(feature = "std")]
+    registration: Registration,
 }
 
 impl<'this, 'diag, T> VmResume<'this, 'diag, T> {
@@ -467,6 +586,8 @@ impl<'this, 'diag, T> VmResume<'this, 'diag, T> {
             diagnostics: Some(diagnostics),
             init: self.init,
             awaited: self.awaited,
+            #[cfg(feature = "std")]
+            registration: self.registration,
         }
     }
 }
@@ -503,6 +624,34 @@ where
         // projected fields.
         let this = unsafe { Pin::get_unchecked_mut(self) };
 
+        let result = this.poll_resume(cx);
+
+        // The waker is only needed while the execution is suspended.
+        #[cfg(feature = "std")]
+        if result.is_ready() {
+            this.registration.clear();
+        }
+
+        result
+    }
+}
+
+impl<T> VmResume<'_, '_, T>
+where
+    T: AsMut<Vm>,
+{
+    /// Poll the resumed execution.
+    ///
+    /// This must only be called while `self` is pinned, since the future
+    /// being awaited is pinned in place.
+    fn poll_resume(&mut self, cx: &mut Context<'_>) -> Poll<Result<VmOutcome, VmError>> {
+        let this = self;
+
+        #[cfg(feature = "std")]
+        let _interrupt = this.execution.interrupt.enter();
+        #[cfg(feature = "std")]
+        async_vm_try!(interrupt::poll(&mut this.registration, cx.waker()));
+
         if let Some(value) = this.init.take() {
             let state = replace(&mut this.execution.state, ExecutionState::Suspended);
 
@@ -517,7 +666,12 @@ where
 
             if let Some(awaited) = &mut this.awaited {
                 let awaited = unsafe { Pin::new_unchecked(awaited) };
-                async_vm_try!(ready!(awaited.poll(cx, vm)));
+                let diagnostics = this
+                    .diagnostics
+                    .as_mut()
+                    .map(|value| &mut **value as &mut dyn VmDiagnostics);
+
+                async_vm_try!(ready!(awaited.poll(cx, vm, diagnostics)));
                 this.awaited = None;
             }
 
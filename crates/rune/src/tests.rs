@@ -468,10 +468,14 @@ mod continue_;
 #[cfg(not(miri))]
 mod core_macros;
 #[cfg(not(miri))]
+mod coverage;
+#[cfg(not(miri))]
 mod custom_macros;
 #[cfg(not(miri))]
 mod debug_fmt;
 #[cfg(not(miri))]
+mod debugger;
+#[cfg(not(miri))]
 mod deprecation;
 #[cfg(not(miri))]
 mod derive_constructor;
@@ -493,12 +497,20 @@ mod getter_setter;
 #[cfg(not(miri))]
 mod iterator;
 #[cfg(not(miri))]
+mod lints;
+#[cfg(not(miri))]
 mod macros;
 #[cfg(not(miri))]
 mod moved;
 #[cfg(not(miri))]
+mod optimize;
+#[cfg(not(miri))]
 mod option;
 #[cfg(not(miri))]
+mod ordered_collections;
+#[cfg(not(miri))]
+mod parse_cache;
+#[cfg(not(miri))]
 mod patterns;
 #[cfg(not(miri))]
 mod quote;
@@ -510,14 +522,22 @@ mod reference_error;
 mod rename_type;
 #[cfg(not(miri))]
 mod result;
+#[cfg(all(not(miri), feature = "std"))]
+mod sandbox;
+#[cfg(not(miri))]
+mod sized_integers;
 #[cfg(not(miri))]
 mod static_typing;
 #[cfg(not(miri))]
+mod traits;
+#[cfg(not(miri))]
 mod tuple;
 #[cfg(not(miri))]
 mod type_name_native;
 #[cfg(not(miri))]
 mod unit_constants;
+#[cfg(all(not(miri), feature = "byte-code"))]
+mod unit_artifact;
 #[cfg(not(miri))]
 mod unreachable;
 #[cfg(not(miri))]
@@ -540,6 +560,8 @@ mod vm_function;
 mod vm_function_pointers;
 #[cfg(not(miri))]
 mod vm_general;
+#[cfg(all(not(miri), feature = "std"))]
+mod vm_interrupt;
 #[cfg(not(miri))]
 mod vm_literals;
 #[cfg(not(miri))]
@@ -0,0 +1,71 @@
+prelude!();
+
+use crate::runtime::Coverage;
+
+fn coverage(source: &str, n: i64) -> Result<Coverage> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+    let mut coverage = Coverage::new();
+    vm.call_with_diagnostics(["main"], (n,), &mut coverage)?;
+    Ok(coverage)
+}
+
+#[test]
+fn coverage_branches() -> Result<()> {
+    let coverage = coverage(
+        r#"
+        pub fn main(n) {
+            let count = 0;
+
+            for i in 0..n {
+                if i % 2 == 0 {
+                    count += 1;
+                }
+            }
+
+            count
+        }
+        "#,
+        5,
+    )?;
+
+    let mut taken = 0;
+    let mut not_taken = 0;
+
+    for (_, branch) in coverage.branches() {
+        taken += branch.taken;
+        not_taken += branch.not_taken;
+    }
+
+    // The condition is evaluated once for each iteration of the loop.
+    assert_eq!(taken + not_taken, 5);
+    Ok(())
+}
+
+#[test]
+fn coverage_nested_execution() -> Result<()> {
+    let coverage = coverage(
+        r#"
+        pub fn main(n) {
+            (0..n).iter().map(|i| if i > 1 { i } else { 0 }).collect::<Vec>()
+        }
+        "#,
+        3,
+    )?;
+
+    // The closure is called by native code, which executes it in a nested
+    // virtual machine that should still be recorded.
+    let branch = coverage
+        .branches()
+        .map(|(_, branch)| branch)
+        .find(|branch| branch.taken + branch.not_taken == 3);
+
+    assert!(branch.is_some());
+    Ok(())
+}
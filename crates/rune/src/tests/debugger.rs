@@ -0,0 +1,140 @@
+prelude!();
+
+use crate::runtime::{DebugStep, DebugStop, Debugger};
+
+fn debugger(source: &str) -> Result<Debugger> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let mut options = Options::default();
+    options.debug_info(true);
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?;
+
+    let vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+    Ok(Debugger::new(vm, ["main"], (), sources)?)
+}
+
+fn line(debugger: &Debugger) -> Option<usize> {
+    let frames = debugger.frames().ok()?;
+    Some(debugger.frame_location(frames.first()?)?.line)
+}
+
+fn variables(debugger: &Debugger, frame: usize) -> Result<Vec<(String, i64)>> {
+    let frames = debugger.frames()?;
+    let mut output = Vec::new();
+
+    for (name, value) in debugger.frame_variables(&frames[frame])? {
+        output.push((name.to_string(), value.as_signed()?));
+    }
+
+    Ok(output)
+}
+
+const SOURCE: &str = r#"
+fn add(a, b) {
+    let c = a + b;
+    c
+}
+
+pub fn main() {
+    let x = 1;
+    let y = add(x, 2);
+    x + y
+}
+"#;
+
+#[test]
+fn debugger_steps() -> Result<()> {
+    let mut debugger = debugger(SOURCE)?;
+
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Into))?,
+        DebugStop::Step
+    ));
+    assert_eq!(line(&debugger), Some(7));
+
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Over))?,
+        DebugStop::Step
+    ));
+    assert_eq!(line(&debugger), Some(8));
+    assert_eq!(variables(&debugger, 0)?, [("x".to_string(), 1)]);
+
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Into))?,
+        DebugStop::Step
+    ));
+    assert_eq!(line(&debugger), Some(2));
+    assert_eq!(debugger.frames()?.len(), 2);
+    assert_eq!(
+        variables(&debugger, 0)?,
+        [("a".to_string(), 1), ("b".to_string(), 2)]
+    );
+
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Out))?,
+        DebugStop::Step
+    ));
+    assert_eq!(debugger.frames()?.len(), 1);
+
+    let DebugStop::Complete(value) = block_on(debugger.resume(DebugStep::Continue))? else {
+        panic!("expected execution to complete");
+    };
+
+    assert_eq!(value.as_signed()?, 4);
+    Ok(())
+}
+
+#[test]
+fn debugger_breakpoints() -> Result<()> {
+    let mut debugger = debugger(SOURCE)?;
+
+    let source_id = crate::SourceId::new(0);
+    let placed = debugger.set_breakpoints(source_id, &[0, 2, 5, 7])?;
+    assert_eq!(&placed[..], [Some(1), Some(2), Some(6), Some(7)]);
+
+    // A breakpoint on the first line of a function is hit before it executes.
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Continue))?,
+        DebugStop::Breakpoint
+    ));
+    assert_eq!(line(&debugger), Some(7));
+    assert_eq!(variables(&debugger, 0)?, []);
+
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Continue))?,
+        DebugStop::Breakpoint
+    ));
+    assert_eq!(line(&debugger), Some(2));
+
+    let frames = debugger.frames()?;
+    let caller = debugger.frame_location(&frames[1]).map(|l| l.line);
+    assert_eq!(caller, Some(8));
+    assert_eq!(variables(&debugger, 1)?, [("x".to_string(), 1)]);
+
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Continue))?,
+        DebugStop::Breakpoint
+    ));
+    assert_eq!(line(&debugger), Some(1));
+
+    // The return from `main` is associated with the line it's declared on.
+    assert!(matches!(
+        block_on(debugger.resume(DebugStep::Continue))?,
+        DebugStop::Breakpoint
+    ));
+    assert_eq!(line(&debugger), Some(6));
+
+    let DebugStop::Complete(value) = block_on(debugger.resume(DebugStep::Continue))? else {
+        panic!("expected execution to complete");
+    };
+
+    assert_eq!(value.as_signed()?, 4);
+    Ok(())
+}
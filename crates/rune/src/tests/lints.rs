@@ -0,0 +1,204 @@
+prelude!();
+
+use crate::ast::Spanned;
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind, Lint};
+
+/// A reported lint, the source it was reported for and whether it was denied.
+type Reported<'a> = (Lint, &'a str, bool);
+
+/// Build the given source with the given compiler options, returning whether
+/// the build succeeded and the lints which were reported.
+fn lints<'a>(options: &[&str], source: &'a str) -> Result<(bool, Vec<Reported<'a>>)> {
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let mut o = Options::default();
+
+    for option in options {
+        o.parse_option(option)?;
+    }
+
+    let context = Context::with_default_modules()?;
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&o)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    let mut reported = Vec::new();
+
+    for diagnostic in diagnostics.diagnostics() {
+        if let Diagnostic::Warning(warning) = diagnostic {
+            let text = &source[warning.span().range()];
+            reported.push((warning.lint(), text, warning.is_denied()));
+        }
+    }
+
+    Ok((result.is_ok(), reported))
+}
+
+#[test]
+fn allow_attribute() -> Result<()> {
+    let (ok, reported) = lints(&[], "fn unused() {} pub fn main() {}")?;
+    assert!(ok);
+    assert_eq!(reported, [(Lint::UnusedCode, "fn unused() {}", false)]);
+
+    let (ok, reported) = lints(
+        &[],
+        "#[allow(unused_code)] fn unused() {} fn other() {} pub fn main() {}",
+    )?;
+    assert!(ok);
+    assert_eq!(reported, [(Lint::UnusedCode, "fn other() {}", false)]);
+
+    let (ok, reported) = lints(&[], "#![allow(unused)] fn unused() {} pub fn main() {}")?;
+    assert!(ok);
+    assert_eq!(reported, []);
+    Ok(())
+}
+
+#[test]
+fn deny_attribute() -> Result<()> {
+    let (ok, reported) = lints(
+        &[],
+        "#[deny(unnecessary_semicolon)] pub fn main() { if true { 1 }; 2 }",
+    )?;
+
+    assert!(!ok);
+    assert_eq!(
+        reported,
+        [(Lint::UnnecessarySemicolon, "if true { 1 };", true)]
+    );
+    Ok(())
+}
+
+#[test]
+fn innermost_level() -> Result<()> {
+    let (ok, reported) = lints(
+        &[],
+        r#"
+        #![deny(shadowed_bindings)]
+
+        #[allow(shadowed_bindings)]
+        pub fn main() {
+            let a = 1;
+            let a = a + 1;
+            a
+        }
+        "#,
+    )?;
+
+    assert!(ok);
+    assert_eq!(reported, []);
+    Ok(())
+}
+
+#[test]
+fn options() -> Result<()> {
+    let source = "fn unused() {} pub fn main() {}";
+
+    let (ok, reported) = lints(&["lint.unused-code=allow"], source)?;
+    assert!(ok);
+    assert_eq!(reported, []);
+
+    let (ok, reported) = lints(&["lint.unused=deny"], source)?;
+    assert!(!ok);
+    assert_eq!(reported, [(Lint::UnusedCode, "fn unused() {}", true)]);
+
+    let mut options = Options::default();
+    assert!(options.parse_option("lint.missing=deny").is_err());
+    assert!(options.parse_option("lint.unused=forbid").is_err());
+    Ok(())
+}
+
+#[test]
+fn unknown_lint() -> Result<()> {
+    let mut sources = sources! {
+        entry => {
+            #[allow(missing)]
+            pub fn main() {}
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+    let _ = prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    let error = diagnostics
+        .diagnostics()
+        .iter()
+        .find_map(|diagnostic| match diagnostic {
+            Diagnostic::Fatal(fatal) => match fatal.kind() {
+                FatalDiagnosticKind::CompileError(error) => Some(error.to_string()),
+                _ => None,
+            },
+            _ => None,
+        });
+
+    assert_eq!(error.as_deref(), Some("Unknown lint `missing`"));
+    Ok(())
+}
+
+#[test]
+fn unused_imports() -> Result<()> {
+    let (ok, reported) = lints(&[], "use std::iter; pub fn main() {}")?;
+    assert!(ok);
+    assert_eq!(reported, [(Lint::UnusedImports, "std::iter", false)]);
+    Ok(())
+}
+
+#[test]
+fn unused_parameters() -> Result<()> {
+    let (ok, reported) = lints(
+        &["lint.unused-parameters=warn"],
+        "fn add(a, b, _c) { a } pub fn main() { add(1, 2, 3) }",
+    )?;
+
+    assert!(ok);
+    assert_eq!(reported, [(Lint::UnusedParameters, "b", false)]);
+    Ok(())
+}
+
+#[test]
+fn shadowed_bindings() -> Result<()> {
+    let (ok, reported) = lints(
+        &["lint.shadowed-bindings=warn"],
+        "pub fn main() { let a = 1; let _a = 2; let b = a; let a = b + 1; a }",
+    )?;
+
+    assert!(ok);
+    assert_eq!(reported, [(Lint::ShadowedBindings, "a", false)]);
+    Ok(())
+}
+
+#[test]
+fn needless_return() -> Result<()> {
+    let (ok, reported) = lints(
+        &["lint.needless-return=warn"],
+        "fn early(a) { if a { return 1; } return 2; } pub fn main() { early(true) }",
+    )?;
+
+    assert!(ok);
+    assert_eq!(reported, [(Lint::NeedlessReturn, "return 2", false)]);
+    Ok(())
+}
+
+#[test]
+fn unit_comparisons() -> Result<()> {
+    let (ok, reported) = lints(
+        &["lint.unit-comparisons=warn"],
+        "pub fn main() { let a = (); a == () && () != a }",
+    )?;
+
+    assert!(ok);
+    assert_eq!(
+        reported,
+        [
+            (Lint::UnitComparisons, "a == ()", false),
+            (Lint::UnitComparisons, "() != a", false),
+        ]
+    );
+    Ok(())
+}
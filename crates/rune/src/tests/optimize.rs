@@ -0,0 +1,268 @@
+prelude!();
+
+use crate::runtime::inst::Kind;
+use crate::runtime::{InstValue, UnitStorage};
+use crate::Unit;
+
+fn build(context: &Context, source: &str, optimize: bool) -> Result<Unit> {
+    let mut options = Options::default();
+    options.optimize(optimize);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    Ok(prepare(&mut sources)
+        .with_context(context)
+        .with_options(&options)
+        .build()?)
+}
+
+fn instructions(unit: &Unit) -> Vec<Kind> {
+    unit.instructions()
+        .iter()
+        .map(|(_, inst)| inst.kind)
+        .collect()
+}
+
+/// Build the source with and without optimizations, check that calling
+/// `main` produces the same debug output for every argument and return the
+/// instructions of both units.
+fn compare(source: &str, args: &[i64]) -> Result<(Vec<Kind>, Vec<Kind>)> {
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let unoptimized = build(&context, source, false)?;
+    let optimized = build(&context, source, true)?;
+
+    let before = instructions(&unoptimized);
+    let after = instructions(&optimized);
+
+    let mut unoptimized = Vm::new(runtime.clone(), Arc::try_new(unoptimized)?);
+    let mut optimized = Vm::new(runtime, Arc::try_new(optimized)?);
+
+    for &n in args {
+        let expected = unoptimized
+            .call(["main"], (n,))
+            .map(|value| format!("{value:?}"))
+            .map_err(|error| error.to_string());
+
+        let actual = optimized
+            .call(["main"], (n,))
+            .map(|value| format!("{value:?}"))
+            .map_err(|error| error.to_string());
+
+        assert_eq!(expected, actual, "result of main({n})");
+    }
+
+    Ok((before, after))
+}
+
+#[test]
+fn optimize_constant_folding() -> Result<()> {
+    let (before, after) = compare(
+        r#"
+        pub fn main(n) {
+            let a = 1 + 2 * 3;
+            let b = 10 / 4 - 1;
+            let c = 0xf0 & 0x3c | 1;
+            let d = 1.5 * 2.0;
+            let e = 2 < 3 && 'a' != 'b';
+            (a + n, b, c, d, e)
+        }
+        "#,
+        &[0, 5],
+    )?;
+
+    assert!(before.len() > after.len());
+
+    let arithmetic = after
+        .iter()
+        .filter(|kind| matches!(kind, Kind::Arithmetic { .. } | Kind::Bitwise { .. }))
+        .count();
+
+    // Only `a + n` remains.
+    assert_eq!(arithmetic, 1);
+
+    assert!(after.iter().any(|kind| matches!(
+        kind,
+        Kind::Store {
+            value: InstValue::Integer(7),
+            ..
+        }
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn optimize_preserves_errors() -> Result<()> {
+    let (_, after) = compare(
+        r#"
+        pub fn main(n) {
+            let a = 9223372036854775807 + 1;
+            let b = 1 / 0;
+            a + b + n
+        }
+        "#,
+        &[0],
+    )?;
+
+    let arithmetic = after
+        .iter()
+        .filter(|kind| matches!(kind, Kind::Arithmetic { .. }))
+        .count();
+
+    assert!(arithmetic >= 2, "overflowing operations must not be folded");
+    Ok(())
+}
+
+#[test]
+fn optimize_dead_branches() -> Result<()> {
+    let (before, after) = compare(
+        r#"
+        pub fn main(n) {
+            if 1 > 2 {
+                n * 100
+            } else {
+                n + 1
+            }
+        }
+        "#,
+        &[0, 3],
+    )?;
+
+    assert!(before
+        .iter()
+        .any(|kind| matches!(kind, Kind::JumpIf { .. })));
+
+    assert!(!after.iter().any(|kind| matches!(
+        kind,
+        Kind::JumpIf { .. } | Kind::JumpIfNot { .. } | Kind::Jump { .. }
+    )));
+
+    Ok(())
+}
+
+#[test]
+fn optimize_fuses_conditional_jumps() -> Result<()> {
+    let (before, after) = compare(
+        r#"
+        pub fn main(n) {
+            let count = 0;
+
+            while count < n {
+                count += 1;
+            }
+
+            let total = 0;
+
+            for i in 0..n {
+                if i % 3 == 0 {
+                    continue;
+                }
+
+                total += i;
+            }
+
+            (count, total)
+        }
+        "#,
+        &[0, 1, 10],
+    )?;
+
+    assert!(before.len() > after.len());
+    assert!(after
+        .iter()
+        .any(|kind| matches!(kind, Kind::OpJumpIfNot { .. })));
+    Ok(())
+}
+
+#[test]
+fn optimize_copy_coalescing() -> Result<()> {
+    let (before, after) = compare(
+        r#"
+        pub fn main(n) {
+            let total = 0;
+
+            for i in 0..n {
+                total += i;
+            }
+
+            total
+        }
+        "#,
+        &[0, 1, 10],
+    )?;
+
+    let copies = |kinds: &[Kind]| {
+        kinds
+            .iter()
+            .filter(|kind| matches!(kind, Kind::Copy { .. } | Kind::Move { .. }))
+            .count()
+    };
+
+    assert!(copies(&before) > copies(&after));
+    Ok(())
+}
+
+#[test]
+fn optimize_semantics() -> Result<()> {
+    compare(
+        r#"
+        struct Point { x, y }
+
+        enum Shape { Circle(r), Rect { w, h } }
+
+        fn area(shape) {
+            match shape {
+                Shape::Circle(r) => r * r * 3,
+                Shape::Rect { w, h } => w * h,
+            }
+        }
+
+        fn classify(n) {
+            if n < 0 { "negative" } else if n == 0 { "zero" } else { "positive" }
+        }
+
+        pub fn main(n) {
+            let p = Point { x: n, y: 2 };
+            let f = |v| v + p.x;
+            let total = 0;
+            let flag = n > 2 || n == 0;
+
+            for i in 0..n {
+                total += f(i);
+
+                if i > 10 {
+                    break;
+                }
+            }
+
+            let loops = 0;
+
+            loop {
+                loops += 1;
+
+                if loops >= n {
+                    break;
+                }
+            }
+
+            let shapes = [Shape::Circle(n), Shape::Rect { w: 3, h: p.y }];
+            let areas = shapes.iter().map(area).collect::<Vec>();
+            let opt = if n > 1 { Some(n) } else { None };
+
+            let value = match opt {
+                Some(v) if v > 3 => v * 2,
+                Some(v) => v,
+                None => -1,
+            };
+
+            (total, flag, loops, areas, classify(n - 2), value, `${n}!`)
+        }
+        "#,
+        &[-1, 0, 1, 2, 5, 20],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+prelude!();
+
+use crate::alloc::limit;
+use crate::runtime::VmError;
+
+/// Call `main` in the given source with the specified memory limit.
+fn call_limited(source: &str, memory: usize) -> Result<Result<Value, VmError>> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+    Ok(limit::with(memory, || vm.call(["main"], ())).call())
+}
+
+#[test]
+fn ordered_collections_memory_limit() -> Result<()> {
+    for collection in ["BTreeMap", "BTreeSet", "BinaryHeap"] {
+        let add = match collection {
+            "BTreeMap" => "c.insert(n, n)",
+            "BTreeSet" => "c.insert(n)",
+            _ => "c.push(n)",
+        };
+
+        let source = format!(
+            r#"
+            use std::collections::{collection};
+
+            pub fn main() {{
+                let c = {collection}::new();
+
+                for n in 0..100000 {{
+                    {add};
+                }}
+
+                c.len()
+            }}
+            "#
+        );
+
+        let error = call_limited(&source, 64 * 1024)?.unwrap_err();
+
+        assert!(
+            error.to_string().contains("allocat"),
+            "{collection}: unexpected error: {error}"
+        );
+
+        let value = call_limited(&source.replace("100000", "100"), 64 * 1024)?
+            .map_err(|error| anyhow::anyhow!("{collection}: {error}"))?;
+
+        assert_eq!(usize::from_value(value)?, 100);
+    }
+
+    Ok(())
+}
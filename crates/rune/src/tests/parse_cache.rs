@@ -0,0 +1,37 @@
+prelude!();
+
+use crate::compile::ParseCache;
+
+fn build(parse_cache: &mut ParseCache, lib: &str) -> Result<()> {
+    let mut sources = Sources::new();
+    sources.insert(Source::memory("pub fn main() { lib() }")?)?;
+    sources.insert(Source::memory(lib)?)?;
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = prepare(&mut sources)
+        .with_diagnostics(&mut diagnostics)
+        .with_parse_cache(parse_cache)
+        .build();
+
+    assert!(result.is_ok(), "{diagnostics:?}");
+    Ok(())
+}
+
+#[test]
+fn parse_cache_reuses_unmodified_sources() -> Result<()> {
+    let mut parse_cache = ParseCache::default();
+
+    build(&mut parse_cache, "pub fn lib() { 1 }")?;
+    assert_eq!(parse_cache.hits(), 0);
+
+    build(&mut parse_cache, "pub fn lib() { 1 }")?;
+    assert_eq!(parse_cache.hits(), 2);
+
+    build(&mut parse_cache, "pub fn lib() { 2 }")?;
+    assert_eq!(parse_cache.hits(), 3);
+
+    build(&mut parse_cache, "pub fn lib() { 2 }")?;
+    assert_eq!(parse_cache.hits(), 5);
+    Ok(())
+}
@@ -0,0 +1,168 @@
+prelude!();
+
+use std::path::Path;
+
+use crate::runtime::sandbox::{self, Capability, Sandbox, SandboxError};
+
+#[rune::function]
+fn check_read(path: &str) -> Result<(), SandboxError> {
+    sandbox::read(path)
+}
+
+#[rune::function]
+async fn check_write(path: Ref<str>) -> Result<(), SandboxError> {
+    sandbox::write(path.as_ref())
+}
+
+fn run_sandboxed(sandbox: Sandbox, source: &str) -> Result<Value> {
+    let mut module = Module::new();
+    module.function_meta(check_read)?;
+    module.function_meta(check_write)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+    context.set_sandbox(sandbox);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+    Ok(block_on(vm.async_call(["main"], ()))?)
+}
+
+#[test]
+fn sandbox_paths() -> Result<()> {
+    let root = std::env::temp_dir().join("rune-sandbox-paths");
+    let allowed = root.join("allowed");
+    std::fs::create_dir_all(&allowed).unwrap();
+
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_read(&allowed)?;
+
+    assert!(sandbox.check_read(allowed.join("file.txt")).is_ok());
+    assert!(sandbox.check_read(allowed.join("missing/file.txt")).is_ok());
+    assert!(sandbox.check_read(allowed.join("../file.txt")).is_err());
+    assert!(sandbox
+        .check_read(allowed.join("a/../../allowed2"))
+        .is_err());
+    assert!(sandbox.check_read(root.join("allowed2")).is_err());
+    assert!(sandbox.check_write(allowed.join("file.txt")).is_err());
+
+    #[cfg(unix)]
+    {
+        let link = allowed.join("escape");
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&root, &link).unwrap();
+        assert!(sandbox.check_read(link.join("file.txt")).is_err());
+    }
+
+    let error = sandbox.check_read(Path::new("/")).unwrap_err();
+    assert_eq!(error.capability(), Capability::Read);
+    assert_eq!(error.target(), "/");
+    Ok(())
+}
+
+#[test]
+fn sandbox_hosts() -> Result<()> {
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_host("example.com")?;
+    sandbox.allow_host("*.rune-rs.github.io")?;
+
+    assert!(sandbox.check_connect("example.com").is_ok());
+    assert!(sandbox.check_connect("EXAMPLE.com").is_ok());
+    assert!(sandbox.check_connect("www.example.com").is_err());
+    assert!(sandbox.check_connect("docs.rune-rs.github.io").is_ok());
+    assert!(sandbox.check_connect("rune-rs.github.io").is_err());
+    assert!(sandbox.check_connect("evilrune-rs.github.io").is_err());
+    Ok(())
+}
+
+#[test]
+fn sandbox_listen() -> Result<()> {
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_listen("127.0.0.1")?;
+    sandbox.allow_listen("LocalHost")?;
+
+    assert!(sandbox.check_listen("127.0.0.1").is_ok());
+    assert!(sandbox.check_listen("localhost").is_ok());
+    assert!(sandbox.check_connect("127.0.0.1").is_err());
+
+    let error = sandbox.check_listen("0.0.0.0").unwrap_err();
+    assert_eq!(error.capability(), Capability::Listen);
+    assert_eq!(error.target(), "0.0.0.0");
+    Ok(())
+}
+
+#[test]
+fn sandbox_in_vm() -> Result<()> {
+    let allowed = std::env::temp_dir().join("rune-sandbox-vm");
+
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_read(&allowed)?;
+
+    let source = format!(
+        r#"
+        pub async fn main() {{
+            let allowed = {allowed:?};
+            let read = check_read(allowed).is_ok();
+            let denied = check_read("/").is_ok();
+            let error = match check_write(allowed).await {{
+                Err(error) => `${{error.capability()}} ${{error.target()}}`,
+                Ok(()) => "",
+            }};
+            (read, denied, error)
+        }}
+        "#,
+        allowed = allowed.display().to_string()
+    );
+
+    let output = run_sandboxed(sandbox, &source)?;
+    let (read, denied, error) = from_value::<(bool, bool, String)>(output)?;
+
+    assert!(read);
+    assert!(!denied);
+    assert_eq!(error, format!("write {}", allowed.display()));
+    Ok(())
+}
+
+#[test]
+fn sandbox_outside_vm() -> Result<()> {
+    // Outside of the virtual machine there is no sandbox which applies.
+    assert!(sandbox::read("/").is_ok());
+
+    /// Call `main` and poll the native future it returns outside of the
+    /// virtual machine.
+    fn poll_outside(sandbox: Option<Sandbox>) -> Result<Result<(), SandboxError>> {
+        let mut module = Module::new();
+        module.function_meta(check_write)?;
+
+        let mut context = Context::with_default_modules()?;
+        context.install(module)?;
+
+        if let Some(sandbox) = sandbox {
+            context.set_sandbox(sandbox);
+        }
+
+        let mut sources = sources! {
+            entry => {
+                pub fn main() { check_write("/") }
+            }
+        };
+
+        let unit = prepare(&mut sources).with_context(&context).build()?;
+        let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+
+        let future = from_value::<runtime::Future>(vm.call(["main"], ())?)?;
+        Ok(from_value::<Result<(), SandboxError>>(block_on(future)?)?)
+    }
+
+    assert!(poll_outside(None)?.is_ok());
+
+    // The future captures the sandbox of the context it was created in.
+    let error = poll_outside(Some(Sandbox::new()))?.unwrap_err();
+    assert_eq!(error.capability(), Capability::Write);
+    assert_eq!(error.target(), "/");
+    Ok(())
+}
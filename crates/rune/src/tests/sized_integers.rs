@@ -0,0 +1,254 @@
+prelude!();
+
+use crate::runtime::{Fixed, VmError};
+
+use VmErrorKind::*;
+
+/// Build the source with or without sized integers and call its `main`
+/// function.
+fn call<T>(source: &str, sized: bool) -> Result<Result<T, VmError>>
+where
+    T: FromValue,
+{
+    let context = Context::with_default_modules()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut options = Options::default();
+    options.sized_integers(sized);
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let unit = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .build()?;
+
+    let mut vm = Vm::new(runtime, Arc::try_new(unit)?);
+    Ok(vm
+        .call(["main"], ())
+        .and_then(|value| Ok(from_value(value)?)))
+}
+
+fn sized<T>(source: &str) -> T
+where
+    T: FromValue,
+{
+    call(source, true).unwrap().unwrap()
+}
+
+fn sized_error(source: &str) -> VmErrorKind {
+    call::<Value>(source, true)
+        .unwrap()
+        .unwrap_err()
+        .into_kind()
+}
+
+#[test]
+fn literal_suffixes() {
+    let out: bool = sized(
+        r#"
+        pub fn main() {
+            255u8 is u8 && 65535u16 is u16 && 4294967295u32 is u32
+                && -128i8 is i8 && -32768i16 is i16 && -2147483648i32 is i32
+                && b'a' is u8
+        }
+        "#,
+    );
+    assert!(out);
+
+    let out: bool = call(
+        r#"pub fn main() { 255u8 is u64 && b'a' is u64 && -1i8 is i64 }"#,
+        false,
+    )
+    .unwrap()
+    .unwrap();
+    assert!(out);
+}
+
+#[test]
+fn arithmetic_keeps_width() {
+    let out: (bool, u8, i8, u16) = sized(
+        r#"
+        pub fn main() {
+            let a = 200u8 + 55;
+            let b = -100i8 * 1;
+            let c = 1u16;
+            c <<= 15;
+            (a is u8 && b is i8 && c is u16, a, b, c)
+        }
+        "#,
+    );
+    assert_eq!(out, (true, 255, -100, 32768));
+
+    let out: Vec<u8> = sized(
+        r#"pub fn main() { [200u8 + 55, 200u8 - 55, 20u8 * 12, 200u8 / 3, 200u8 % 3, !0b10100u8] }"#,
+    );
+    assert_eq!(out, [255, 145, 240, 66, 2, !0b10100u8]);
+
+    let out: Vec<i8> =
+        sized(r#"pub fn main() { [100i8 + 27, -100i8 - 28, -8i8 * 16, -100i8 / 3, -100i8 % 3] }"#);
+    assert_eq!(out, [127, -128, -128, -33, -1]);
+}
+
+#[test]
+fn arithmetic_errors() {
+    assert!(matches!(
+        sized_error("pub fn main() { 255u8 + 1 }"),
+        Overflow
+    ));
+    assert!(matches!(
+        sized_error("pub fn main() { 0u8 - 1 }"),
+        Underflow
+    ));
+    assert!(matches!(
+        sized_error("pub fn main() { 16u8 * 16 }"),
+        Overflow
+    ));
+    assert!(matches!(
+        sized_error("pub fn main() { 10u8 / 0 }"),
+        DivideByZero
+    ));
+    assert!(matches!(
+        sized_error("pub fn main() { 0b1u8 << 8 }"),
+        Overflow
+    ));
+    assert!(matches!(
+        sized_error("pub fn main() { 127i8 + 1 }"),
+        Overflow
+    ));
+    assert!(matches!(
+        sized_error("pub fn main() { 4294967295u32 + 1 }"),
+        Overflow
+    ));
+    assert!(matches!(
+        sized_error("pub fn main() { let a = 32767i16; a += 1; }"),
+        Overflow
+    ));
+}
+
+#[test]
+fn cross_width_comparisons() {
+    let out: Vec<bool> = sized(
+        r#"
+        pub fn main() {
+            [5u8 == 1000, b'a' < 1000, -1i8 < 200u8, 300 > 255u8, 42u8 == 42]
+        }
+        "#,
+    );
+    assert_eq!(out, [false, true, true, true, true]);
+}
+
+#[test]
+fn as_conversions() {
+    let out: bool = sized(
+        r#"
+        pub fn main() {
+            300 as u8 == 44u8 && -1 as u8 == 255u8 && 255u8 as i8 == -1i8
+                && -1i32 as u32 == u32::MAX && 70000u32 as u16 == 4464u16
+                && 97u8 as char == 'a' && (1u8 as i16) is i16 && 1.9 as i32 == 1i32
+        }
+        "#,
+    );
+    assert!(out);
+}
+
+#[test]
+fn methods() {
+    let out: bool = sized(
+        r#"
+        pub fn main() {
+            250u8.checked_add(5) == Some(255u8) && 250u8.checked_add(6) == None
+                && 200u8.wrapping_mul(2) == 144u8 && 10u8.saturating_sub(20) == 0u8
+                && i16::MAX.overflowing_add(1) == (i16::MIN, true)
+                && 2i32.checked_pow(31) == None && (-1i8).count_ones() == 8
+                && 0x1234u16.to_be_bytes() == b"\x12\x34"
+                && i32::from_le_bytes(b"\xff\xff\xff\xff") == -1i32
+        }
+        "#,
+    );
+    assert!(out);
+}
+
+#[test]
+fn formatting() {
+    let out: String = sized(
+        r#"pub fn main() { format!("{} {} {:04} {:x} {:x} {:b}", 255u8, -5i16, 7u8, 255u8, -1i8, 5u32) }"#,
+    );
+    assert_eq!(out, "255 -5 0007 ff ff 101");
+}
+
+#[test]
+fn ranges_and_patterns() {
+    let out: (bool, Vec<u8>, String) = sized(
+        r#"
+        pub fn main() {
+            let values = (0u8..4).iter().collect::<Vec>();
+
+            let out = match 2u8 {
+                1u8 => "one",
+                2u8 => "two",
+                _ => "other",
+            };
+
+            (values[0] is u8, values, out)
+        }
+        "#,
+    );
+    assert_eq!(out, (true, vec![0, 1, 2, 3], String::from("two")));
+}
+
+#[test]
+fn wide_integers() {
+    let out: (Fixed<i128>, Fixed<i128>, Fixed<u128>, bool) = sized(
+        r#"
+        pub fn main() {
+            let a = 170141183460469231731687303715884105727i128;
+            let b = a;
+            b -= 1;
+            (a, b, 340282366920938463463374607431768211455u128, -(1i128 << 100) < b)
+        }
+        "#,
+    );
+    assert_eq!(
+        out,
+        (
+            Fixed(i128::MAX),
+            Fixed(i128::MAX - 1),
+            Fixed(u128::MAX),
+            true
+        )
+    );
+
+    assert!(matches!(
+        sized_error("pub fn main() { i128::MAX + 1 }"),
+        Overflow
+    ));
+
+    let out: (String, bool, u8) = call(
+        r#"pub fn main() { let a = 10 as u128; (format!("{a:?}"), a * 2 == 20, a as u8) }"#,
+        false,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(out, (String::from("10"), true, 10));
+}
+
+#[test]
+fn host_conversions() -> Result<()> {
+    let value = rune::to_value(42u8)?;
+    assert_eq!(value.type_info().to_string(), "::std::u64");
+
+    let value = rune::to_value(Fixed(42u8))?;
+    assert_eq!(value.type_info().to_string(), "::std::u8");
+    assert_eq!(from_value::<Fixed<u8>>(value.clone())?, Fixed(42));
+    assert_eq!(from_value::<u64>(value)?, 42);
+
+    let value = rune::to_value(Fixed(i128::MIN))?;
+    assert_eq!(value.type_info().to_string(), "::std::i128");
+    assert_eq!(from_value::<Fixed<i128>>(value.clone())?, Fixed(i128::MIN));
+    assert!(from_value::<i64>(value).is_err());
+
+    assert!(from_value::<Fixed<u8>>(rune::to_value(1000u64)?).is_err());
+    Ok(())
+}
@@ -2,6 +2,9 @@ prelude!();
 
 use ErrorKind::*;
 
+use crate::ast::{Span, Spanned};
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
+
 #[test]
 fn deny_static_typing_function() {
     assert_errors! {
@@ -21,3 +24,192 @@ fn deny_static_typing_field() {
         }
     }
 }
+
+#[test]
+fn deny_static_typing_argument() {
+    assert_errors! {
+        "fn foo(a: Bar) {}",
+        span!(7, 13), Custom { error } => {
+            assert_eq!(error.to_string(), "Static typing on function arguments is not supported");
+        }
+    }
+}
+
+/// Build the given source with type checking enabled, returning the errors
+/// which were reported.
+fn type_check(source: &str) -> Result<Vec<(Span, String)>> {
+    type_check_with(source, false)
+}
+
+/// Build the given source with type checking and optionally sized integers
+/// enabled, returning the errors which were reported.
+fn type_check_with(source: &str, sized: bool) -> Result<Vec<(Span, String)>> {
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let mut options = Options::default();
+    options.type_check(true);
+    options.sized_integers(sized);
+
+    let context = Context::with_default_modules()?;
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_context(&context)
+        .with_options(&options)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    let mut errors = Vec::new();
+
+    for diagnostic in diagnostics.diagnostics() {
+        if let Diagnostic::Fatal(fatal) = diagnostic {
+            if let FatalDiagnosticKind::CompileError(error) = fatal.kind() {
+                errors.push((error.span(), error.to_string()));
+            }
+        }
+    }
+
+    Ok(errors)
+}
+
+#[test]
+fn type_check_accepts_annotations() -> Result<()> {
+    let errors = type_check(
+        r#"
+        struct Point { x: i64, y: i64 }
+
+        impl Point {
+            fn sum(self, extra: u64) -> i64 { self.x + self.y + extra }
+        }
+
+        fn describe(name: String, (a, b): (i64, i64)) -> String {
+            if a > b {
+                return name;
+            }
+
+            `${name} ${a}`
+        }
+
+        pub fn main() {
+            let point = Point { x: 1, y: 2 };
+            let sum = point.sum(3);
+            describe("point", (sum, point.x));
+            describe(String::from("point"), (sum, "abc".len()))
+        }
+        "#,
+    )?;
+
+    assert_eq!(errors, []);
+    Ok(())
+}
+
+#[test]
+fn type_check_mismatches() -> Result<()> {
+    let errors = type_check(
+        r#"
+        struct Point { x: i64, y: i64 }
+
+        fn add(a: i64, b: i64) -> i64 { a + b }
+
+        fn name() -> String { 42 }
+
+        pub fn main() {
+            let point = Point { x: 1, y: "two" };
+            let value = "hello";
+            add(value, point.x);
+        }
+        "#,
+    )?;
+
+    let expected_i64 = "Expected type `::std::i64` but found `::std::string::String`";
+    let expected_string = "Expected type `::std::string::String` but found `::std::i64`";
+
+    assert_eq!(
+        errors,
+        [
+            (span!(192, 197), expected_i64.to_string()),
+            (span!(250, 255), expected_i64.to_string()),
+            (span!(121, 123), expected_string.to_string()),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "doc")]
+fn type_check_native_signatures() -> Result<()> {
+    let errors = type_check(
+        r#"
+        fn add(a: i64, b: i64) -> i64 { a + b }
+
+        pub fn main() {
+            add("hello".len(), 1);
+            String::from(add(1, 2));
+            add(String::from("hello"), 1);
+        }
+        "#,
+    )?;
+
+    let expected_i64 = "Expected type `::std::i64` but found `::std::string::String`";
+    let expected_string = "Expected type `::std::string::String` but found `::std::i64`";
+
+    assert_eq!(
+        errors,
+        [
+            (span!(134, 143), expected_string.to_string()),
+            (span!(162, 183), expected_i64.to_string()),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn type_check_reassigned_variables() -> Result<()> {
+    let errors = type_check(
+        r#"
+        fn add(a: i64, b: i64) -> i64 { a + b }
+
+        pub fn main() {
+            let value = "hello";
+            value = 1;
+            add(value, 2)
+        }
+        "#,
+    )?;
+
+    assert_eq!(errors, []);
+    Ok(())
+}
+
+#[test]
+fn type_check_integer_widths() -> Result<()> {
+    let errors = type_check_with(
+        r#"
+        fn small(a: u8) -> u8 { a }
+
+        fn wide(a: u128) -> i64 { 1 }
+
+        pub fn main() {
+            small(1);
+            small(1u8);
+            wide(1u128);
+            small(1u16);
+            wide(1i128);
+        }
+        "#,
+        true,
+    )?;
+
+    let expected_u8 = "Expected type `::std::u8` but found `::std::u16`";
+    let expected_u128 = "Expected type `::std::u128` but found `::std::i128`";
+
+    assert_eq!(
+        errors,
+        [
+            (span!(190, 194), expected_u8.to_string()),
+            (span!(214, 219), expected_u128.to_string()),
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,79 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn trait_missing_function() {
+    assert_errors! {
+        r#"
+        trait Shape { fn area(self); }
+        struct Square;
+        impl Shape for Square {}
+        "#,
+        span!(86, 92), MissingTraitFunction { name, .. } => {
+            assert_eq!(name.as_ref(), "area");
+        }
+    };
+}
+
+#[test]
+fn trait_not_member() {
+    assert_errors! {
+        r#"
+        trait Shape { fn area(self); }
+        struct Square;
+        impl Shape for Square { fn area(self) { 1 } fn side(self) { 1 } }
+        "#,
+        span!(118, 122), NotTraitFunction { name, .. } => {
+            assert_eq!(name.as_ref(), "side");
+        }
+    };
+}
+
+#[test]
+fn trait_function_arity() {
+    assert_errors! {
+        r#"
+        trait Shape { fn scale(self, factor); }
+        struct Square;
+        impl Shape for Square { fn scale(self) { 1 } }
+        "#,
+        span!(112, 118), TraitFunctionArity { name, expected, actual, .. } => {
+            assert_eq!(name.as_ref(), "scale");
+            assert_eq!((expected, actual), (2, 1));
+        }
+    };
+}
+
+#[test]
+fn trait_function_conflict() {
+    assert_errors! {
+        r#"trait Shape { fn area(self); fn area(self) { 1 } }"#,
+        span!(29, 42), TraitFunctionConflict { name } => {
+            assert_eq!(name.as_ref(), "area");
+        }
+    };
+}
+
+#[test]
+fn trait_not_a_trait() {
+    assert_errors! {
+        r#"
+        struct Shape;
+        struct Square;
+        impl Shape for Square {}
+        "#,
+        span!(59, 64), ExpectedMeta { expected: "a trait", .. }
+    };
+}
+
+#[test]
+fn trait_native_impl() {
+    assert_errors! {
+        r#"
+        struct Square;
+        impl ::std::iter::Iterator for Square {}
+        "#,
+        span!(37, 58), UnsupportedTraitImpl { .. }
+    };
+}
@@ -0,0 +1,198 @@
+prelude!();
+
+use crate::runtime::unit::{FORMAT_VERSION, SCHEMA_VERSION};
+use crate::Unit;
+
+fn build(context: &Context, source: &str) -> Result<Unit> {
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+    Ok(prepare(&mut sources).with_context(context).build()?)
+}
+
+#[test]
+fn unit_artifact_roundtrip() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let unit = build(
+        &context,
+        r#"
+        struct Point { x, y }
+        enum Shape { Circle(r), Rect { w, h } }
+
+        fn area(shape) {
+            match shape {
+                Shape::Circle(r) => r * r * 3,
+                Shape::Rect { w, h } => w * h,
+            }
+        }
+
+        pub fn main(n) {
+            let p = Point { x: n, y: 2 };
+            let f = |v| v + p.x;
+            let total = 0;
+
+            for i in 0..n {
+                total += f(i);
+                total <<= 1;
+                total ^= 3;
+            }
+
+            let shapes = [Shape::Circle(2), Shape::Rect { w: 3, h: p.y }];
+            let areas = shapes.iter().map(area).collect::<Vec>();
+            let s = format!("{:>8} {:?} {:.2} {}", total, areas, 1.5, b"bytes".len());
+            format!("{:?}", (s, Some(total)?, 'c', #{ key: p.y }))
+        }
+        "#,
+    )?;
+
+    let bytes = unit.save()?;
+    let runtime = Arc::try_new(context.runtime()?)?;
+    let loaded = Unit::load(&bytes, &runtime)?;
+
+    assert_eq!(
+        format!("{:?}", unit.iter_instructions().collect::<Vec<_>>()),
+        format!("{:?}", loaded.iter_instructions().collect::<Vec<_>>())
+    );
+
+    let mut vm = Vm::new(runtime.clone(), Arc::try_new(unit)?);
+    let expected = from_value::<String>(vm.call(["main"], (5i64,))?)?;
+
+    let mut vm = Vm::new(runtime, Arc::try_new(loaded)?);
+    let actual = from_value::<String>(vm.call(["main"], (5i64,))?)?;
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn unit_artifact_missing_function() -> Result<()> {
+    #[rune::function]
+    fn answer() -> i64 {
+        42
+    }
+
+    let mut module = Module::new();
+    module.function_meta(answer)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let unit = build(&context, "pub fn main() { answer() }")?;
+    let bytes = unit.save()?;
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+    let mut vm = Vm::new(
+        runtime,
+        Arc::try_new(Unit::load(&bytes, &context.runtime()?)?)?,
+    );
+    assert_eq!(from_value::<i64>(vm.call(["main"], ())?)?, 42);
+
+    let context = Context::with_default_modules()?;
+    let error = Unit::load(&bytes, &context.runtime()?).unwrap_err();
+    let hash = Hash::type_hash(["answer"]);
+
+    assert!(
+        error.to_string().contains(&hash.to_string()),
+        "unexpected error: {error}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn unit_artifact_missing_instance_function() -> Result<()> {
+    #[derive(Any)]
+    struct Counter;
+
+    impl Counter {
+        #[rune::function(instance)]
+        fn tally(&self) -> i64 {
+            42
+        }
+    }
+
+    let mut module = Module::new();
+    module.ty::<Counter>()?;
+    module.function_meta(Counter::tally)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let unit = build(&context, "pub fn main(counter) { counter.tally() }")?;
+    let bytes = unit.save()?;
+
+    let runtime = Arc::try_new(context.runtime()?)?;
+    let mut vm = Vm::new(
+        runtime.clone(),
+        Arc::try_new(Unit::load(&bytes, &runtime)?)?,
+    );
+    assert_eq!(from_value::<i64>(vm.call(["main"], (Counter,))?)?, 42);
+
+    let unit = build(
+        &context,
+        r#"
+        struct Local;
+
+        impl Local {
+            fn local(self) { 1 }
+        }
+
+        pub fn main(value) { value.local() }
+        "#,
+    )?;
+    Unit::load(&unit.save()?, &runtime)?;
+
+    let context = Context::with_default_modules()?;
+    let error = Unit::load(&bytes, &context.runtime()?).unwrap_err();
+    let hash = Hash::ident("tally");
+
+    assert!(
+        error.to_string().contains(&hash.to_string()),
+        "unexpected error: {error}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn unit_artifact_bad_header() -> Result<()> {
+    let context = Context::with_default_modules()?;
+    let runtime = context.runtime()?;
+    let unit = build(&context, "pub fn main() { 42 }")?;
+    let bytes = unit.save()?;
+
+    let error = Unit::load(b"#!/bin/rune", &runtime).unwrap_err();
+    assert_eq!(error.to_string(), "Not a compiled Rune unit");
+
+    let error = Unit::load(&bytes[..10], &runtime).unwrap_err();
+    assert_eq!(error.to_string(), "Compiled unit is truncated");
+
+    let mut bumped = bytes.try_clone()?;
+    bumped[8..12].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    let error = Unit::load(&bumped, &runtime).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Unsupported format version {}, expected {FORMAT_VERSION}",
+            FORMAT_VERSION + 1
+        )
+    );
+
+    let mut bumped = bytes.try_clone()?;
+    bumped[12..16].copy_from_slice(&(SCHEMA_VERSION + 1).to_le_bytes());
+    let error = Unit::load(&bumped, &runtime).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Unsupported schema version {}, expected {SCHEMA_VERSION}",
+            SCHEMA_VERSION + 1
+        )
+    );
+
+    let mut other = bytes.try_clone()?;
+    other[18] = b'!';
+    let error = Unit::load(&other, &runtime).unwrap_err();
+    assert!(error.to_string().starts_with("Unit was compiled by Rune !"));
+
+    Ok(())
+}
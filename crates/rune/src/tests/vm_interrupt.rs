@@ -0,0 +1,263 @@
+prelude!();
+
+use core::future::Future;
+use core::pin::pin;
+
+use std::time::{Duration, Instant};
+
+use crate::runtime::interrupt::{self, CancellationToken};
+use crate::runtime::{Function, RuntimeError, VmExecution};
+
+#[rune::function]
+async fn pending() {
+    core::future::pending::<()>().await
+}
+
+fn vm(source: &str) -> Result<Vm> {
+    let mut module = Module::new();
+    module.function_meta(pending)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(
+        Arc::try_new(context.runtime()?)?,
+        Arc::try_new(unit)?,
+    ))
+}
+
+#[test]
+fn deadline_exceeded() -> Result<()> {
+    let mut vm = vm("pub fn main() { loop {} }")?;
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_deadline(Instant::now() + Duration::from_millis(10))
+        .complete()
+        .unwrap_err();
+
+    assert!(error.is_deadline_exceeded());
+    assert!(!error.is_cancelled());
+    Ok(())
+}
+
+#[test]
+fn deadline_in_nested_call() -> Result<()> {
+    let mut vm = vm("pub fn main() { [1, 2, 3].iter().map(|n| loop {}).collect::<Vec>() }")?;
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_timeout(Duration::from_millis(10))
+        .complete()
+        .unwrap_err();
+
+    assert!(error.is_deadline_exceeded());
+    Ok(())
+}
+
+/// Construct a virtual machine whose `main` function calls a native function,
+/// which runs a nested execution configured by `configure`.
+fn nested_vm(configure: fn(VmExecution<&mut Vm>) -> VmExecution<&mut Vm>) -> Result<Vm> {
+    let inner = vm("pub fn main() { loop {} }")?;
+    let runtime = inner.context().clone();
+    let unit = inner.unit().clone();
+
+    let mut module = Module::new();
+
+    module
+        .function("nested", move || {
+            let mut vm = Vm::new(runtime.clone(), unit.clone());
+            configure(vm.execute(["main"], ())?).complete()
+        })
+        .build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = sources! {
+        entry => {
+            pub fn main() { nested() }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(
+        Arc::try_new(context.runtime()?)?,
+        Arc::try_new(unit)?,
+    ))
+}
+
+#[test]
+fn deadline_in_nested_execution() -> Result<()> {
+    let mut vm = nested_vm(|execution| execution.with_timeout(Duration::from_secs(60)))?;
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_timeout(Duration::from_millis(10))
+        .complete()
+        .unwrap_err();
+
+    assert!(error.is_deadline_exceeded());
+    Ok(())
+}
+
+#[test]
+fn cancelled_in_nested_execution() -> Result<()> {
+    let mut vm = nested_vm(|execution| execution.with_cancellation(CancellationToken::new()))?;
+
+    let token = CancellationToken::new();
+
+    let handle = std::thread::spawn({
+        let token = token.clone();
+
+        move || {
+            std::thread::sleep(Duration::from_millis(10));
+            token.cancel();
+        }
+    });
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_cancellation(token)
+        .complete()
+        .unwrap_err();
+
+    assert!(error.is_cancelled());
+    handle.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn deadline_not_exceeded() -> Result<()> {
+    let mut vm = vm("pub fn main() { let n = 0; for i in 0..10000 { n += i; } n }")?;
+
+    let output = vm
+        .execute(["main"], ())?
+        .with_timeout(Duration::from_secs(60))
+        .complete()?;
+
+    assert_eq!(from_value::<i64>(output)?, 49995000);
+    Ok(())
+}
+
+#[test]
+fn cancelled_while_awaiting() -> Result<()> {
+    let mut vm = vm("pub async fn main() { pending().await }")?;
+
+    let token = CancellationToken::new();
+
+    let handle = std::thread::spawn({
+        let token = token.clone();
+
+        move || {
+            std::thread::sleep(Duration::from_millis(10));
+            token.cancel();
+        }
+    });
+
+    let error = block_on(
+        vm.execute(["main"], ())?
+            .with_cancellation(token)
+            .async_complete(),
+    )
+    .unwrap_err();
+
+    assert!(error.is_cancelled());
+    assert!(!error.is_deadline_exceeded());
+    handle.join().unwrap();
+    Ok(())
+}
+
+#[test]
+fn wakers_deregistered() -> Result<()> {
+    let token = CancellationToken::new();
+
+    let mut vm = vm("pub async fn main() { pending().await } pub fn answer() { 42 }")?;
+
+    {
+        let mut execution = vm.execute(["main"], ())?.with_cancellation(token.clone());
+        let future = pin!(execution.resume());
+        let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+        assert!(future.poll(&mut cx).is_pending());
+        assert_eq!(token.wakers(), 1);
+    }
+
+    assert_eq!(token.wakers(), 0);
+
+    let output = vm
+        .execute(["answer"], ())?
+        .with_cancellation(token.clone())
+        .complete()?;
+
+    assert_eq!(from_value::<i64>(output)?, 42);
+    assert_eq!(token.wakers(), 0);
+    Ok(())
+}
+
+#[test]
+fn cancelled_before_start() -> Result<()> {
+    let mut vm = vm("pub fn main() { 42 }")?;
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let error = vm
+        .execute(["main"], ())?
+        .with_cancellation(token)
+        .complete()
+        .unwrap_err();
+
+    assert!(error.is_cancelled());
+    Ok(())
+}
+
+#[test]
+fn cancelled_in_detached_work() -> Result<()> {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let mut module = Module::new();
+
+    module
+        .function("detach", move |function: Function| {
+            let function = function.into_sync()?;
+            let interrupt = interrupt::current();
+            let tx = tx.clone();
+
+            std::thread::spawn(move || {
+                let result = block_on(interrupt.scope(function.async_send_call::<()>(())));
+                tx.send(result).unwrap();
+            });
+
+            Ok::<_, RuntimeError>(())
+        })
+        .build()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(module)?;
+
+    let mut sources = sources! {
+        entry => {
+            fn spin() { loop {} }
+            pub fn main() { detach(spin) }
+        }
+    };
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    let mut vm = Vm::new(Arc::try_new(context.runtime()?)?, Arc::try_new(unit)?);
+
+    let token = CancellationToken::new();
+
+    vm.execute(["main"], ())?
+        .with_cancellation(token.clone())
+        .complete()?;
+
+    token.cancel();
+
+    let error = rx.recv_timeout(Duration::from_secs(60))?.unwrap_err();
+    assert!(error.is_cancelled());
+    Ok(())
+}
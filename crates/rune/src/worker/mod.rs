@@ -7,11 +7,12 @@ mod wildcard_import;
 use rust_alloc::rc::Rc;
 
 use crate::alloc::prelude::*;
-use crate::alloc::{self, HashMap, Vec, VecDeque};
+use crate::alloc::{self, HashMap, HashSet, Vec, VecDeque};
 use crate::ast::{self, Kind, Span, Spanned};
-use crate::compile::{self, ItemId, ModId, WithSpan};
+use crate::compile::{self, meta, ItemId, Location, ModId, ParseCache, WithSpan};
 use crate::grammar::{Node, Stream};
-use crate::indexing::{index, index2};
+use crate::item::ComponentRef;
+use crate::indexing::{self, index, index2};
 use crate::macros::{MacroContext, TokenStream};
 use crate::parse::Resolve;
 use crate::query::{
@@ -31,15 +32,18 @@ pub(crate) struct Worker<'a, 'arena> {
     pub(crate) loaded: HashMap<ModId, (SourceId, Span)>,
     /// Worker queue.
     pub(crate) queue: VecDeque<Task>,
+    /// Parsed sources to reuse from earlier builds.
+    parse_cache: Option<&'a mut ParseCache>,
 }
 
 impl<'a, 'arena> Worker<'a, 'arena> {
     /// Construct a new worker.
-    pub(crate) fn new(q: Query<'a, 'arena>) -> Self {
+    pub(crate) fn new(q: Query<'a, 'arena>, parse_cache: Option<&'a mut ParseCache>) -> Self {
         Self {
             q,
             loaded: HashMap::new(),
             queue: VecDeque::new(),
+            parse_cache,
         }
     }
 
@@ -213,8 +217,10 @@ impl<'a, 'arena> Worker<'a, 'arena> {
             }
         } else {
             if as_function_body {
-                let ast =
-                    crate::parse::parse_all::<ast::EmptyBlock>(source.as_str(), source_id, true)?;
+                let ast = match &mut self.parse_cache {
+                    Some(cache) => cache.parse::<ast::EmptyBlock>(source_id, source.as_str())?,
+                    None => crate::parse::parse_all(source.as_str(), source_id, true)?,
+                };
 
                 let span = Span::new(0, source.len());
 
@@ -223,8 +229,10 @@ impl<'a, 'arena> Worker<'a, 'arena> {
 
                 index::empty_block_fn(&mut idx, ast, &span)?;
             } else {
-                let mut ast =
-                    crate::parse::parse_all::<ast::File>(source.as_str(), source_id, true)?;
+                let mut ast = match &mut self.parse_cache {
+                    Some(cache) => cache.parse::<ast::File>(source_id, source.as_str())?,
+                    None => crate::parse::parse_all(source.as_str(), source_id, true)?,
+                };
 
                 let empty = Rc::default();
                 let mut idx = indexer!(&empty);
@@ -238,21 +246,30 @@ impl<'a, 'arena> Worker<'a, 'arena> {
     #[tracing::instrument(skip_all)]
     fn impl_item(&mut self, this: ImplItem) -> compile::Result<()> {
         macro_rules! indexer {
-            ($tree:expr, $named:expr, $meta:expr) => {{
-                let items =
-                    $crate::indexing::Items::new({ self.q.pool.item($meta.item_meta.item) })?;
+            ($tree:expr, $named:expr, $meta:expr) => {
+                indexer!(
+                    $tree,
+                    self.q.pool.item($meta.item_meta.item),
+                    $crate::indexing::IndexItem::with_impl_item(
+                        $named.module,
+                        $named.item,
+                        $meta.item_meta.item,
+                    ),
+                    this.root.as_deref(),
+                    this.location.source_id
+                )
+            };
+
+            ($tree:expr, $base:expr, $item:expr, $root:expr, $source_id:expr) => {{
+                let items = $crate::indexing::Items::new({ $base })?;
 
                 $crate::indexing::Indexer {
                     q: self.q.borrow(),
-                    root: this.root.as_deref(),
-                    source_id: this.location.source_id,
+                    root: $root,
+                    source_id: $source_id,
                     items,
                     scopes: $crate::indexing::Scopes::new()?,
-                    item: $crate::indexing::IndexItem::with_impl_item(
-                        $named.module,
-                        $named.item,
-                        $meta.item_meta.item,
-                    ),
+                    item: $item,
                     nested_item: this.nested_item,
                     macro_depth: this.macro_depth,
                     loaded: Some(&mut self.loaded),
@@ -267,7 +284,11 @@ impl<'a, 'arena> Worker<'a, 'arena> {
         // we might introduce bounds which would not be communicated
         // through `Self`.
         match this.kind {
-            ImplItemKind::Ast { path, functions } => {
+            ImplItemKind::Ast {
+                path,
+                trait_,
+                functions,
+            } => {
                 let named = self
                     .q
                     .convert_path_with(&path, true, Used::Used, Used::Unused)?;
@@ -287,12 +308,50 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                     GenericsParameters::default(),
                 )?;
 
+                let defaults = match &trait_ {
+                    Some(trait_path) => {
+                        let tr = self.lookup_trait(&this.location, trait_path)?;
+                        let defaults = self.trait_defaults(&this.location, &tr, &functions)?;
+                        Some((tr, defaults))
+                    }
+                    None => None,
+                };
+
                 let empty = Rc::default();
                 let mut idx = indexer!(&empty, named, meta);
 
                 for f in functions {
                     index::item_fn(&mut idx, f)?;
                 }
+
+                // Default functions are indexed under an anonymous item in
+                // the trait for every implementation, so that their bodies
+                // resolve names in the module the trait is declared in while
+                // `Self` and instance calls refer to the implementing type.
+                if let Some((tr, defaults)) = defaults {
+                    let id = self.q.pool.next_id(tr.item_meta.item);
+                    let base = self
+                        .q
+                        .pool
+                        .item(tr.item_meta.item)
+                        .extended(ComponentRef::Id(id))?;
+
+                    let mut idx = indexer!(
+                        &empty,
+                        &base,
+                        indexing::IndexItem::with_impl_item(
+                            tr.item_meta.module,
+                            tr.item_meta.item,
+                            meta.item_meta.item,
+                        ),
+                        tr.root.as_deref(),
+                        tr.item_meta.location.source_id
+                    );
+
+                    for f in defaults {
+                        index::item_fn(&mut idx, f)?;
+                    }
+                }
             }
             ImplItemKind::Node { path, functions } => {
                 let named =
@@ -324,6 +383,119 @@ impl<'a, 'arena> Worker<'a, 'arena> {
         Ok(())
     }
 
+    /// Look up the script trait being implemented by an impl item.
+    fn lookup_trait(
+        &mut self,
+        location: &Location,
+        path: &ast::Path,
+    ) -> compile::Result<Rc<indexing::Trait>> {
+        let named = self
+            .q
+            .convert_path_with(path, true, Used::Used, Used::Unused)?;
+
+        if let Some((spanned, _)) = named.parameters.into_iter().flatten().next() {
+            return Err(compile::Error::new(
+                spanned.span(),
+                compile::ErrorKind::UnsupportedGenerics,
+            ));
+        }
+
+        let meta = self
+            .q
+            .lookup_meta(location, named.item, GenericsParameters::default())?;
+
+        let meta::Kind::Trait = meta.kind else {
+            return Err(compile::Error::expected_meta(
+                path,
+                meta.info(self.q.pool)?,
+                "a trait",
+            ));
+        };
+
+        let Some(tr) = self.q.get_trait(meta.item_meta.item) else {
+            return Err(compile::Error::new(
+                path,
+                compile::ErrorKind::UnsupportedTraitImpl {
+                    item: self.q.pool.item(meta.item_meta.item).try_to_owned()?,
+                },
+            ));
+        };
+
+        Ok(tr)
+    }
+
+    /// Check the functions of an impl item against the trait it implements,
+    /// returning the default functions which should be added to it.
+    fn trait_defaults(
+        &mut self,
+        location: &Location,
+        tr: &indexing::Trait,
+        functions: &[ast::ItemFn],
+    ) -> compile::Result<Vec<ast::ItemFn>> {
+        let cx = resolve_context!(self.q);
+
+        let mut implemented = HashSet::new();
+
+        for f in functions {
+            let name = f.name.resolve(cx)?;
+
+            let declared = tr.functions.iter().find(|declared| {
+                declared
+                    .name
+                    .resolve(cx)
+                    .is_ok_and(|declared| declared == name)
+            });
+
+            let Some(declared) = declared else {
+                return Err(compile::Error::new(
+                    f.name,
+                    compile::ErrorKind::NotTraitFunction {
+                        name: name.try_into()?,
+                        item: self.q.pool.item(tr.item_meta.item).try_to_owned()?,
+                    },
+                ));
+            };
+
+            if declared.args.len() != f.args.len() {
+                return Err(compile::Error::new(
+                    &f.args,
+                    compile::ErrorKind::TraitFunctionArity {
+                        name: name.try_into()?,
+                        item: self.q.pool.item(tr.item_meta.item).try_to_owned()?,
+                        expected: declared.args.len(),
+                        actual: f.args.len(),
+                    },
+                ));
+            }
+
+            implemented.try_insert(name)?;
+        }
+
+        let mut defaults = Vec::new();
+
+        for f in &tr.functions {
+            let name = f.name.resolve(cx)?;
+
+            if implemented.contains(name) {
+                continue;
+            }
+
+            let Some(default) = f.to_item_fn()? else {
+                return Err(compile::Error::new(
+                    location.span,
+                    compile::ErrorKind::MissingTraitFunction {
+                        name: name.try_into()?,
+                        item: self.q.pool.item(tr.item_meta.item).try_to_owned()?,
+                    },
+                ));
+            };
+
+            defaults.try_push(default)?;
+        }
+
+        Ok(defaults)
+    }
+
     #[tracing::instrument(skip_all)]
     fn expand_macro_builtin(&mut self, mut this: ExpandMacroBuiltin) -> compile::Result<()> {
         let (name, stream) = this.node.parse(|p| {
@@ -124,6 +124,15 @@ pub(crate) enum WorkspaceErrorKind {
     UnsupportedKey {
         key: String,
     },
+    UnknownLint {
+        name: String,
+    },
+    UnsupportedLintLevel {
+        level: String,
+    },
+    UnsupportedFmtOption {
+        option: String,
+    },
     AllocError {
         error: alloc::Error,
     },
@@ -176,6 +185,14 @@ impl fmt::Display for WorkspaceErrorKind {
             ),
             WorkspaceErrorKind::ExpectedTable => write!(f, "Expected table"),
             WorkspaceErrorKind::UnsupportedKey { key } => write!(f, "Key `{key}` not supported",),
+            WorkspaceErrorKind::UnknownLint { name } => write!(f, "Unknown lint `{name}`"),
+            WorkspaceErrorKind::UnsupportedLintLevel { level } => write!(
+                f,
+                "Unsupported lint level `{level}`, expected one of `allow`, `warn` or `deny`"
+            ),
+            WorkspaceErrorKind::UnsupportedFmtOption { option } => {
+                write!(f, "Unsupported formatting option `{option}`")
+            }
             WorkspaceErrorKind::AllocError { error } => error.fmt(f),
         }
     }
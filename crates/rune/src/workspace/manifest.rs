@@ -16,11 +16,12 @@ use crate as rune;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, String, Vec};
 use crate::ast::{Span, Spanned};
+use crate::diagnostics::{Lint, LintLevel};
 use crate::workspace::spanned_value::{Array, SpannedValue, Table, Value};
 use crate::workspace::{
-    glob, Diagnostics, SourceLoader, WorkspaceError, WorkspaceErrorKind, MANIFEST_FILE,
+    glob, Diagnostics, SourceLoader, WorkspaceError, WorkspaceErrorKind, FMT_FILE, MANIFEST_FILE,
 };
-use crate::{SourceId, Sources};
+use crate::{Options, SourceId, Sources};
 
 const BIN: &str = "bin";
 const TESTS: &str = "tests";
@@ -111,6 +112,11 @@ impl<T> Spanned for toml::Spanned<T> {
 pub struct Manifest {
     /// List of packages found.
     pub packages: Vec<Package>,
+    /// Lint levels configured in the `[lints]` table.
+    pub lints: Vec<(Lint, LintLevel)>,
+    /// Formatting options configured in the `[fmt]` table or in a
+    /// `rune-fmt.toml` file, like `fmt.indent=2`.
+    pub fmt: Vec<String>,
 }
 
 impl Manifest {
@@ -298,10 +304,18 @@ impl<'a> Loader<'a> {
             .transpose()?;
         let root = root.as_deref();
 
+        let is_fmt_file = source.path().and_then(Path::file_name) == Some(OsStr::new(FMT_FILE));
+
         let Some((mut table, _)) = self.ensure_table(value)? else {
             return Ok(());
         };
 
+        // A standalone formatter configuration is a single `[fmt]` table.
+        if is_fmt_file {
+            self.load_fmt(table)?;
+            return Ok(());
+        }
+
         // If manifest is a package, add it here.
         if let Some((package, span)) = table
             .remove("package")
@@ -314,6 +328,26 @@ impl<'a> Loader<'a> {
             }
         }
 
+        // Load the [lints] section.
+        if let Some((table, _)) = table
+            .remove("lints")
+            .map(|value| self.ensure_table(value))
+            .transpose()?
+            .flatten()
+        {
+            self.load_lints(table)?;
+        }
+
+        // Load the [fmt] section.
+        if let Some((table, _)) = table
+            .remove("fmt")
+            .map(|value| self.ensure_table(value))
+            .transpose()?
+            .flatten()
+        {
+            self.load_fmt(table)?;
+        }
+
         // Load the [workspace] section.
         if let Some((mut table, span)) = table
             .remove("workspace")
@@ -472,6 +506,81 @@ impl<'a> Loader<'a> {
         }))
     }
 
+    /// Load lint levels from a table.
+    fn load_lints(&mut self, table: Table) -> alloc::Result<()> {
+        for (key, value) in table {
+            let Some(lints) = Lint::lookup(key.get_ref()) else {
+                self.fatal(WorkspaceError::new(
+                    Spanned::span(&key),
+                    WorkspaceErrorKind::UnknownLint {
+                        name: key.get_ref().as_str().try_into()?,
+                    },
+                ))?;
+                continue;
+            };
+
+            let span = Spanned::span(&value);
+
+            let level = match deserialize::<String>(value) {
+                Ok(level) => level,
+                Err(error) => {
+                    self.fatal(error)?;
+                    continue;
+                }
+            };
+
+            let Some(level) = LintLevel::parse(&level) else {
+                self.fatal(WorkspaceError::new(
+                    span,
+                    WorkspaceErrorKind::UnsupportedLintLevel { level },
+                ))?;
+                continue;
+            };
+
+            for &lint in lints {
+                self.manifest.lints.try_push((lint, level))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load formatting options from a table.
+    fn load_fmt(&mut self, table: Table) -> alloc::Result<()> {
+        for (key, value) in table {
+            let span = Spanned::span(&value);
+
+            let value = match value.into_inner() {
+                Value::String(value) => value.as_str().try_to_owned()?,
+                Value::Integer(value) => value.try_to_string()?,
+                Value::Boolean(value) => value.try_to_string()?,
+                _ => {
+                    self.fatal(WorkspaceError::new(
+                        span,
+                        WorkspaceErrorKind::UnsupportedFmtOption {
+                            option: key.get_ref().as_str().try_into()?,
+                        },
+                    ))?;
+                    continue;
+                }
+            };
+
+            let option = try_format!("fmt.{}={value}", key.get_ref());
+
+            if Options::default().parse_option(&option).is_err() {
+                self.fatal(WorkspaceError::new(
+                    Spanned::span(&key),
+                    WorkspaceErrorKind::UnsupportedFmtOption { option },
+                ))?;
+                continue;
+            }
+
+            self.manifest.fmt.try_push(option)?;
+        }
+
+        Ok(())
+    }
+
     /// Ensure that a table is empty and mark any additional elements as erroneous.
     fn ensure_empty(&mut self, table: Table) -> alloc::Result<()> {
         for (key, _) in table {
@@ -559,6 +668,24 @@ where
     Ok(value)
 }
 
+/// Find the formatter configuration which applies to files in `dir`.
+///
+/// This walks up from `dir` and in each directory prefers a `rune-fmt.toml`
+/// over a `Rune.toml` manifest.
+pub fn find_fmt_config(dir: &Path) -> Option<PathBuf> {
+    for dir in dir.ancestors() {
+        for name in [FMT_FILE, MANIFEST_FILE] {
+            let path = dir.join(name);
+
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+
+    None
+}
+
 /// Find all rune files in the given path.
 fn find_rune_files(path: &Path) -> Result<impl Iterator<Item = Result<(PathBuf, String)>>> {
     let mut dir = match fs::read_dir(path) {
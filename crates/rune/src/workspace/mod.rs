@@ -3,6 +3,9 @@
 /// The name of the toplevel manifest `Rune.toml`.
 pub const MANIFEST_FILE: &str = "Rune.toml";
 
+/// The name of the standalone formatter configuration `rune-fmt.toml`.
+pub const FMT_FILE: &str = "rune-fmt.toml";
+
 mod glob;
 
 mod spanned_value;
@@ -23,7 +26,9 @@ pub use self::error::WorkspaceError;
 pub(crate) use self::error::WorkspaceErrorKind;
 
 mod manifest;
-pub use self::manifest::{Found, FoundKind, FoundPackage, Manifest, Package, WorkspaceFilter};
+pub use self::manifest::{
+    find_fmt_config, Found, FoundKind, FoundPackage, Manifest, Package, WorkspaceFilter,
+};
 
 mod diagnostics;
 pub use self::diagnostics::{Diagnostic, Diagnostics, FatalDiagnostic};
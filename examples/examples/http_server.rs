@@ -0,0 +1,83 @@
+use rune::runtime::sandbox::Sandbox;
+use rune::sync::Arc;
+use rune::termcolor::{ColorChoice, StandardStream};
+use rune::{Diagnostics, Vm};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> rune::support::Result<()> {
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_listen("127.0.0.1")?;
+    sandbox.allow_host("127.0.0.1")?;
+
+    let mut context = rune_modules::default_context()?;
+    context.set_sandbox(sandbox);
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            use http::{ResponseBuilder, Server, StatusCode};
+
+            async fn greet(request) {
+                let name = request.param("name")?;
+                format!("Hello, {name}!")
+            }
+
+            async fn webhook(request) {
+                let event = request.json()?;
+
+                Ok(ResponseBuilder::new()
+                    .status(StatusCode::ACCEPTED)
+                    .json(#{ received: event["kind"] }))
+            }
+
+            pub async fn main() {
+                let server = Server::bind("127.0.0.1:0").await?;
+                server.get("/greet/:name", greet)?;
+                server.post("/webhook", webhook)?;
+
+                let base = format!("http://{}", server.local_addr()?);
+                let server = server.serve();
+
+                let client = http::Client::new();
+
+                let requests = async {
+                    let response = client.get(format!("{base}/greet/Rune")).send().await?;
+                    println!("{}: {}", response.status(), response.text().await?);
+
+                    let response = client.post(format!("{base}/webhook"))
+                        .body_bytes(b"{\"kind\": \"push\"}")
+                        .send()
+                        .await?;
+
+                    println!("{}: {}", response.status(), response.text().await?);
+                    Ok(())
+                };
+
+                select {
+                    result = server => result,
+                    result = requests => result,
+                }
+            }
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    if !diagnostics.is_empty() {
+        let mut writer = StandardStream::stderr(ColorChoice::Always);
+        diagnostics.emit(&mut writer, &sources)?;
+    }
+
+    let unit = result?;
+    let mut vm = Vm::new(runtime, Arc::try_new(unit)?);
+
+    let output = vm.async_call(["main"], ()).await?;
+    let output: Result<(), rune::Value> = rune::from_value(output)?;
+    println!("{output:?}");
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use rune::runtime::sandbox::Sandbox;
+use rune::sync::Arc;
+use rune::termcolor::{ColorChoice, StandardStream};
+use rune::{Diagnostics, Vm};
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> rune::support::Result<()> {
+    let directory = std::env::temp_dir().join("rune-sandbox-example");
+    std::fs::create_dir_all(&directory)?;
+
+    let mut sandbox = Sandbox::new();
+    sandbox.allow_read(&directory)?;
+    sandbox.allow_write(&directory)?;
+    sandbox.allow_execute("echo")?;
+
+    let mut context = rune_modules::default_context()?;
+    context.set_sandbox(sandbox);
+    let runtime = Arc::try_new(context.runtime()?)?;
+
+    let mut sources = rune::sources! {
+        entry => {
+            use std::sandbox::SandboxError;
+
+            pub async fn main(directory) {
+                let path = fs::PathBuf::new(directory).join("hello.txt");
+                fs::write(path, "Hello World").await?;
+                println!("read: {}", fs::read_to_string(path).await?);
+
+                if let Err(error) = fs::read_to_string("/etc/hostname").await {
+                    assert!(error is SandboxError);
+                    println!("denied {}: {}", error.capability(), error.target());
+                }
+
+                if let Err(error) = process::Command::new("ls").spawn() {
+                    println!("{error}");
+                }
+
+                if let Err(error) = http::get("https://example.com").await {
+                    println!("{error}");
+                }
+
+                Ok(())
+            }
+        }
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    let result = rune::prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .build();
+
+    if !diagnostics.is_empty() {
+        let mut writer = StandardStream::stderr(ColorChoice::Always);
+        diagnostics.emit(&mut writer, &sources)?;
+    }
+
+    let unit = result?;
+    let mut vm = Vm::new(runtime, Arc::try_new(unit)?);
+
+    let directory = directory.display().to_string();
+    let output = vm.async_call(["main"], (directory,)).await?;
+    let output: Result<(), rune::Value> = rune::from_value(output)?;
+    println!("{output:?}");
+    Ok(())
+}